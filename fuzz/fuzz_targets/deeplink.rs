@@ -0,0 +1,27 @@
+//! Deep link parsing must never panic, and every entry point must agree:
+//! the canonical form is a fixed point and routes the same as the raw input.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nearx::{deeplink, router};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(raw) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let route = router::parse(raw);
+    let Some(event) = deeplink::parse_event(raw) else {
+        return;
+    };
+
+    let canonical = event.url.clone();
+    assert_eq!(deeplink::canonicalize(&canonical), Some(canonical.clone()));
+    assert_eq!(deeplink::parse_event(&canonical), Some(event));
+    if deeplink::has_scheme(raw) {
+        assert_eq!(router::parse(&canonical), route);
+    }
+    if let Some(router::Route::V1(v1)) = route {
+        assert_eq!(router::parse(&v1.url()), Some(router::Route::V1(v1)));
+    }
+});
@@ -1,9 +1,16 @@
 use anyhow::{Context, Result};
+use nearx::tail::{MethodTail, TailedCall};
+use nearx::{AppEvent, Config};
 use serde::{Deserialize, Serialize};
 use std::io::{self, Read, Write};
+use std::future::Future;
 use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use tokio::sync::{mpsc, oneshot};
 
-const PROTOCOL_VERSION: u16 = 1;
+// v2: TailMethod / StopTail streaming
+const PROTOCOL_VERSION: u16 = 2;
 
 #[derive(Debug, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -22,6 +29,13 @@ enum InMsg {
         id: String,
         read_only: bool,
     },
+    /// Stream every call to `contract.method` (method "*" = any) as `tail_call` messages.
+    /// Replaces the previous tail, if any.
+    TailMethod {
+        contract: String,
+        method: String,
+    },
+    StopTail,
 }
 
 #[derive(Debug, Serialize)]
@@ -31,6 +45,7 @@ enum OutMsg<'a> {
     Pong { id: &'a str },
     Ok { op: &'a str },
     Err { op: &'a str, message: String },
+    TailCall { call: &'a TailedCall },
 }
 
 fn read_msg(stdin: &mut impl Read) -> Result<Option<serde_json::Value>> {
@@ -52,6 +67,120 @@ fn write_msg(stdout: &mut impl Write, v: &serde_json::Value) -> Result<()> {
     Ok(())
 }
 
+fn send(v: &OutMsg) -> Result<()> {
+    // Lock per message so the tail thread never interleaves frames with replies
+    write_msg(&mut io::stdout().lock(), &serde_json::to_value(v)?)
+}
+
+/// Active tail subscription, shared with the RPC poller thread
+type SharedTail = Arc<Mutex<Option<MethodTail>>>;
+
+/// RPC config for the tail poller (NEAR_NODE_URL / FASTNEAR_AUTH_TOKEN from env)
+fn tail_config() -> Config {
+    Config {
+        near_node_url: std::env::var("NEAR_NODE_URL")
+            .unwrap_or_else(|_| "https://rpc.mainnet.fastnear.com/".to_string()),
+        fastnear_auth_token: {
+            let token = nearx::config::fastnear_token();
+            if token.is_empty() { None } else { Some(token) }
+        },
+        ws_fetch_blocks: false,
+        warm_start: false,
+        ..Config::default()
+    }
+}
+
+/// Running block poller: its thread and the signal that stops it
+struct TailPoller {
+    stop: oneshot::Sender<()>,
+    thread: JoinHandle<()>,
+}
+
+impl TailPoller {
+    /// False once the source failed or stdout closed (the thread has exited)
+    fn is_running(&self) -> bool {
+        !self.thread.is_finished()
+    }
+
+    /// Abort the source and wait for the thread to exit
+    fn stop(self) {
+        let _ = self.stop.send(());
+        let _ = self.thread.join();
+    }
+}
+
+/// Poll RPC for new blocks (errors are reported as `tail_method` errors)
+fn spawn_tail_poller(tail: SharedTail) -> Result<TailPoller> {
+    spawn_poller(tail, |tx| async move {
+        if let Err(e) = nearx::source_rpc::run_rpc(&tail_config(), tx).await {
+            let _ = send(&OutMsg::Err {
+                op: "tail_method",
+                message: e.to_string(),
+            });
+        }
+    })
+}
+
+/// Run `source` on its own runtime; every new block is matched against the
+/// current tail and matching calls are pushed to stdout. The thread exits when
+/// the source ends, stdout closes or the poller is stopped.
+fn spawn_poller<F, Fut>(tail: SharedTail, source: F) -> Result<TailPoller>
+where
+    F: FnOnce(mpsc::UnboundedSender<AppEvent>) -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let rt = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_all()
+        .build()
+        .context("tail runtime")?;
+    let (stop, stopped) = oneshot::channel();
+
+    let thread = std::thread::spawn(move || {
+        rt.block_on(async move {
+            let (tx, mut rx) = mpsc::unbounded_channel::<AppEvent>();
+            let source = tokio::spawn(source(tx));
+            let forward = async {
+                while let Some(ev) = rx.recv().await {
+                    let AppEvent::NewBlock(block) = ev else {
+                        continue;
+                    };
+                    let calls = match tail.lock().ok().and_then(|t| t.clone()) {
+                        Some(t) => t.calls_in_block(&block),
+                        None => continue,
+                    };
+                    for call in &calls {
+                        if send(&OutMsg::TailCall { call }).is_err() {
+                            return; // stdout closed: extension disconnected
+                        }
+                    }
+                }
+            };
+            tokio::select! {
+                _ = forward => {}
+                _ = stopped => {}
+            }
+            source.abort();
+        });
+    });
+    Ok(TailPoller { stop, thread })
+}
+
+/// Keep one live poller: start it if there is none or the last one exited
+fn ensure_poller(
+    poller: &mut Option<TailPoller>,
+    spawn: impl FnOnce() -> Result<TailPoller>,
+) -> Result<()> {
+    if poller.as_ref().is_some_and(TailPoller::is_running) {
+        return Ok(());
+    }
+    if let Some(dead) = poller.take() {
+        dead.stop();
+    }
+    *poller = Some(spawn()?);
+    Ok(())
+}
+
 fn open_url(url: &str) -> Result<()> {
     if cfg!(target_os = "macos") {
         Command::new("open").arg(url).spawn()?;
@@ -65,17 +194,23 @@ fn open_url(url: &str) -> Result<()> {
     Ok(())
 }
 
+/// Canonicalize (`near:` → `nearx://`, case, slashes, encoding) and open, so the
+/// app's router sees the same link whichever entry point it came through
+fn open_deep_link(raw: &str) -> Result<()> {
+    let url = nearx::deeplink::canonicalize(raw)
+        .with_context(|| format!("not a nearx:// deep link: {raw}"))?;
+    open_url(&url)
+}
+
 fn main() -> Result<()> {
     let mut stdin = io::stdin().lock();
-    let mut stdout = io::stdout().lock();
+    let tail: SharedTail = Arc::new(Mutex::new(None));
+    let mut poller: Option<TailPoller> = None;
 
     // Optional: send Hello immediately so the extension learns our version.
-    write_msg(
-        &mut stdout,
-        &serde_json::to_value(OutMsg::Hello {
-            version: PROTOCOL_VERSION,
-        })?,
-    )?;
+    send(&OutMsg::Hello {
+        version: PROTOCOL_VERSION,
+    })?;
 
     loop {
         let Some(v) = read_msg(&mut stdin)? else {
@@ -87,30 +222,21 @@ fn main() -> Result<()> {
             Ok(InMsg::Hello {
                 requested_version: _,
             }) => {
-                write_msg(
-                    &mut stdout,
-                    &serde_json::to_value(OutMsg::Hello {
-                        version: PROTOCOL_VERSION,
-                    })?,
-                )?;
+                send(&OutMsg::Hello {
+                    version: PROTOCOL_VERSION,
+                })?;
             }
             Ok(InMsg::Ping { id }) => {
-                write_msg(
-                    &mut stdout,
-                    &serde_json::to_value(OutMsg::Pong { id: &id })?,
-                )?;
+                send(&OutMsg::Pong { id: &id })?;
             }
             Ok(InMsg::OpenDeepLink { url }) => {
                 let op = "open_deep_link";
-                match open_url(&url) {
-                    Ok(_) => write_msg(&mut stdout, &serde_json::to_value(OutMsg::Ok { op })?)?,
-                    Err(e) => write_msg(
-                        &mut stdout,
-                        &serde_json::to_value(OutMsg::Err {
-                            op,
-                            message: e.to_string(),
-                        })?,
-                    )?,
+                match open_deep_link(&url) {
+                    Ok(_) => send(&OutMsg::Ok { op })?,
+                    Err(e) => send(&OutMsg::Err {
+                        op,
+                        message: e.to_string(),
+                    })?,
                 }
             }
             Ok(InMsg::OpenSession { id, read_only }) => {
@@ -120,27 +246,121 @@ fn main() -> Result<()> {
                     id,
                     if read_only { 1 } else { 0 }
                 );
-                match open_url(&url) {
-                    Ok(_) => write_msg(&mut stdout, &serde_json::to_value(OutMsg::Ok { op })?)?,
-                    Err(e) => write_msg(
-                        &mut stdout,
-                        &serde_json::to_value(OutMsg::Err {
-                            op,
-                            message: e.to_string(),
-                        })?,
-                    )?,
+                match open_deep_link(&url) {
+                    Ok(_) => send(&OutMsg::Ok { op })?,
+                    Err(e) => send(&OutMsg::Err {
+                        op,
+                        message: e.to_string(),
+                    })?,
                 }
             }
-            Err(e) => {
-                write_msg(
-                    &mut stdout,
-                    &serde_json::to_value(OutMsg::Err {
-                        op: "decode",
+            Ok(InMsg::TailMethod { contract, method }) => {
+                let op = "tail_method";
+                if contract.is_empty() || method.is_empty() {
+                    send(&OutMsg::Err {
+                        op,
+                        message: "contract and method are required".to_string(),
+                    })?;
+                    continue;
+                }
+                if let Ok(mut t) = tail.lock() {
+                    *t = Some(MethodTail::new(contract, method));
+                }
+                if let Err(e) = ensure_poller(&mut poller, || spawn_tail_poller(tail.clone())) {
+                    send(&OutMsg::Err {
+                        op,
                         message: e.to_string(),
-                    })?,
-                )?;
+                    })?;
+                    continue;
+                }
+                send(&OutMsg::Ok { op })?;
+            }
+            Ok(InMsg::StopTail) => {
+                if let Ok(mut t) = tail.lock() {
+                    *t = None;
+                }
+                // Nobody is listening: stop fetching blocks until the next TailMethod
+                if let Some(p) = poller.take() {
+                    p.stop();
+                }
+                send(&OutMsg::Ok { op: "stop_tail" })?;
+            }
+            Err(e) => {
+                send(&OutMsg::Err {
+                    op: "decode",
+                    message: e.to_string(),
+                })?;
             }
         }
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::{Duration, Instant};
+
+    fn wait_until(mut done: impl FnMut() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !done() {
+            assert!(Instant::now() < deadline, "timed out");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    /// Source that never produces blocks; counts how often it was started
+    fn idle(starts: &Arc<AtomicUsize>, tail: &SharedTail) -> Result<TailPoller> {
+        let starts = starts.clone();
+        spawn_poller(tail.clone(), move |tx| async move {
+            let _keep = tx;
+            starts.fetch_add(1, Ordering::SeqCst);
+            std::future::pending::<()>().await;
+        })
+    }
+
+    #[test]
+    fn failed_poller_is_restarted_on_the_next_tail() {
+        let tail: SharedTail = Arc::new(Mutex::new(None));
+        let starts = Arc::new(AtomicUsize::new(0));
+        let mut poller = None;
+
+        // Source fails at once: the poller's thread exits
+        ensure_poller(&mut poller, || spawn_poller(tail.clone(), |_tx| async {})).unwrap();
+        wait_until(|| !poller.as_ref().unwrap().is_running());
+
+        ensure_poller(&mut poller, || idle(&starts, &tail)).unwrap();
+        wait_until(|| starts.load(Ordering::SeqCst) == 1);
+        assert!(poller.as_ref().unwrap().is_running());
+
+        // A live poller is kept
+        ensure_poller(&mut poller, || idle(&starts, &tail)).unwrap();
+        std::thread::sleep(Duration::from_millis(50));
+        assert_eq!(starts.load(Ordering::SeqCst), 1);
+        poller.take().unwrap().stop();
+    }
+
+    #[test]
+    fn stop_aborts_the_source() {
+        struct DropFlag(Arc<AtomicUsize>);
+        impl Drop for DropFlag {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let tail: SharedTail = Arc::new(Mutex::new(None));
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let flag = DropFlag(dropped.clone());
+        let poller = spawn_poller(tail, move |tx| async move {
+            let _keep = (flag, tx);
+            std::future::pending::<()>().await;
+        })
+        .unwrap();
+        assert!(poller.is_running());
+
+        poller.stop();
+        wait_until(|| dropped.load(Ordering::SeqCst) == 1);
+    }
+}
@@ -3,7 +3,7 @@ use anyhow::{anyhow, Result};
 use bincode;
 use std::path::Path;
 use std::sync::Arc;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use tokio::net::{TcpListener, TcpStream, UnixListener, UnixStream};
 use tokio::sync::{mpsc, Mutex};
 use tokio::time::{timeout, Duration};
@@ -15,65 +15,74 @@ pub enum Transport {
     Tcp(TcpStream),
 }
 
-impl Transport {
-    /// Send a message over the transport
-    async fn send(&mut self, msg: &PluginMessage) -> Result<()> {
-        let data = bincode::serialize(msg)?;
-        let len = data.len() as u32;
-        let len_bytes = len.to_be_bytes();
+type FrameReader = Box<dyn AsyncRead + Unpin + Send>;
+type FrameWriter = Box<dyn AsyncWrite + Unpin + Send>;
 
+impl Transport {
+    /// Separate halves, so the reader task never blocks a send
+    fn split(self) -> (FrameReader, FrameWriter) {
         match self {
             Transport::Unix(stream) => {
-                stream.write_all(&len_bytes).await?;
-                stream.write_all(&data).await?;
-                stream.flush().await?;
+                let (r, w) = stream.into_split();
+                (Box::new(r), Box::new(w))
             }
             Transport::Tcp(stream) => {
-                stream.write_all(&len_bytes).await?;
-                stream.write_all(&data).await?;
-                stream.flush().await?;
+                let (r, w) = stream.into_split();
+                (Box::new(r), Box::new(w))
             }
         }
-        Ok(())
     }
+}
 
-    /// Receive a message from the transport
-    async fn recv(&mut self) -> Result<PluginMessage> {
-        let mut len_bytes = [0u8; 4];
+/// Send a message: 4-byte big-endian length, then the bincode body
+async fn write_frame(writer: &mut FrameWriter, msg: &PluginMessage) -> Result<()> {
+    let data = bincode::serialize(msg)?;
+    let len = data.len() as u32;
+    writer.write_all(&len.to_be_bytes()).await?;
+    writer.write_all(&data).await?;
+    writer.flush().await?;
+    Ok(())
+}
 
-        match self {
-            Transport::Unix(stream) => {
-                stream.read_exact(&mut len_bytes).await?;
-            }
-            Transport::Tcp(stream) => {
-                stream.read_exact(&mut len_bytes).await?;
-            }
-        }
+/// Receive one message written by `write_frame`
+async fn read_frame(reader: &mut FrameReader) -> Result<PluginMessage> {
+    let mut len_bytes = [0u8; 4];
+    reader.read_exact(&mut len_bytes).await?;
 
-        let len = u32::from_be_bytes(len_bytes) as usize;
-        if len > 1024 * 1024 * 10 {
-            // 10MB max message size
-            return Err(anyhow!("Message too large: {} bytes", len));
-        }
+    let len = u32::from_be_bytes(len_bytes) as usize;
+    if len > 1024 * 1024 * 10 {
+        // 10MB max message size
+        return Err(anyhow!("Message too large: {} bytes", len));
+    }
 
-        let mut data = vec![0u8; len];
-        match self {
-            Transport::Unix(stream) => {
-                stream.read_exact(&mut data).await?;
-            }
-            Transport::Tcp(stream) => {
-                stream.read_exact(&mut data).await?;
+    let mut data = vec![0u8; len];
+    reader.read_exact(&mut data).await?;
+
+    let msg = bincode::deserialize(&data)?;
+    Ok(msg)
+}
+
+/// Spawn the reader task: every frame goes to the returned receiver until the peer closes
+fn spawn_reader(
+    mut reader: FrameReader,
+) -> (
+    mpsc::UnboundedReceiver<PluginMessage>,
+    tokio::task::JoinHandle<()>,
+) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let handle = tokio::spawn(async move {
+        while let Ok(msg) = read_frame(&mut reader).await {
+            if tx.send(msg).is_err() {
+                break;
             }
         }
-
-        let msg = bincode::deserialize(&data)?;
-        Ok(msg)
-    }
+    });
+    (rx, handle)
 }
 
 /// IPC client for plugins to connect to host
 pub struct IPCClient {
-    transport: Arc<Mutex<Transport>>,
+    writer: Arc<Mutex<FrameWriter>>,
     rx: mpsc::UnboundedReceiver<PluginMessage>,
     _handle: tokio::task::JoinHandle<()>,
 }
@@ -82,13 +91,13 @@ impl IPCClient {
     /// Connect to a Unix socket
     pub async fn connect_unix<P: AsRef<Path>>(path: P) -> Result<Self> {
         let stream = UnixStream::connect(path).await?;
-        Self::new(Transport::Unix(stream)).await
+        Ok(Self::new(Transport::Unix(stream)))
     }
 
     /// Connect to a TCP socket
     pub async fn connect_tcp(addr: &str) -> Result<Self> {
         let stream = TcpStream::connect(addr).await?;
-        Self::new(Transport::Tcp(stream)).await
+        Ok(Self::new(Transport::Tcp(stream)))
     }
 
     /// Create client from config
@@ -102,41 +111,20 @@ impl IPCClient {
         }
     }
 
-    async fn new(mut transport: Transport) -> Result<Self> {
-        let (tx, rx) = mpsc::unbounded_channel();
-        let transport = Arc::new(Mutex::new(transport));
-        let transport_clone = transport.clone();
-
-        // Spawn reader task
-        let handle = tokio::spawn(async move {
-            loop {
-                let msg = {
-                    let mut t = transport_clone.lock().await;
-                    t.recv().await
-                };
-
-                match msg {
-                    Ok(msg) => {
-                        if tx.send(msg).is_err() {
-                            break;
-                        }
-                    }
-                    Err(_) => break,
-                }
-            }
-        });
-
-        Ok(Self {
-            transport,
+    fn new(transport: Transport) -> Self {
+        let (reader, writer) = transport.split();
+        let (rx, handle) = spawn_reader(reader);
+        Self {
+            writer: Arc::new(Mutex::new(writer)),
             rx,
             _handle: handle,
-        })
+        }
     }
 
     /// Send a message
     pub async fn send(&self, msg: PluginMessage) -> Result<()> {
-        let mut transport = self.transport.lock().await;
-        transport.send(&msg).await
+        let mut writer = self.writer.lock().await;
+        write_frame(&mut writer, &msg).await
     }
 
     /// Try to receive a message
@@ -149,13 +137,14 @@ impl IPCClient {
         self.rx.recv().await
     }
 
-    /// Send and wait for response
-    pub async fn request(&self, msg: PluginMessage, timeout_ms: u64) -> Result<PluginMessage> {
+    /// Send and wait for the next incoming message
+    pub async fn request(&mut self, msg: PluginMessage, timeout_ms: u64) -> Result<PluginMessage> {
         self.send(msg).await?;
 
         let duration = Duration::from_millis(timeout_ms);
-        match timeout(duration, self.transport.lock()).await {
-            Ok(mut transport) => transport.recv().await,
+        match timeout(duration, self.rx.recv()).await {
+            Ok(Some(response)) => Ok(response),
+            Ok(None) => Err(anyhow!("Connection closed")),
             Err(_) => Err(anyhow!("Request timed out")),
         }
     }
@@ -198,11 +187,11 @@ impl IPCServer {
         match &self.listener {
             IPCListener::Unix(listener) => {
                 let (stream, _) = listener.accept().await?;
-                Ok(IPCConnection::new(Transport::Unix(stream)).await)
+                Ok(IPCConnection::new(Transport::Unix(stream)))
             }
             IPCListener::Tcp(listener) => {
                 let (stream, _) = listener.accept().await?;
-                Ok(IPCConnection::new(Transport::Tcp(stream)).await)
+                Ok(IPCConnection::new(Transport::Tcp(stream)))
             }
         }
     }
@@ -211,40 +200,18 @@ impl IPCServer {
 /// A single IPC connection
 pub struct IPCConnection {
     pub id: uuid::Uuid,
-    transport: Arc<Mutex<Transport>>,
+    writer: Arc<Mutex<FrameWriter>>,
     pub rx: mpsc::UnboundedReceiver<PluginMessage>,
     _handle: tokio::task::JoinHandle<()>,
 }
 
 impl IPCConnection {
-    async fn new(transport: Transport) -> Self {
-        let id = uuid::Uuid::new_v4();
-        let (tx, rx) = mpsc::unbounded_channel();
-        let transport = Arc::new(Mutex::new(transport));
-        let transport_clone = transport.clone();
-
-        // Spawn reader task
-        let handle = tokio::spawn(async move {
-            loop {
-                let msg = {
-                    let mut t = transport_clone.lock().await;
-                    t.recv().await
-                };
-
-                match msg {
-                    Ok(msg) => {
-                        if tx.send(msg).is_err() {
-                            break;
-                        }
-                    }
-                    Err(_) => break,
-                }
-            }
-        });
-
+    fn new(transport: Transport) -> Self {
+        let (reader, writer) = transport.split();
+        let (rx, handle) = spawn_reader(reader);
         Self {
-            id,
-            transport,
+            id: uuid::Uuid::new_v4(),
+            writer: Arc::new(Mutex::new(writer)),
             rx,
             _handle: handle,
         }
@@ -252,8 +219,8 @@ impl IPCConnection {
 
     /// Send a message
     pub async fn send(&self, msg: PluginMessage) -> Result<()> {
-        let mut transport = self.transport.lock().await;
-        transport.send(&msg).await
+        let mut writer = self.writer.lock().await;
+        write_frame(&mut writer, &msg).await
     }
 }
 
@@ -274,13 +241,8 @@ mod tests {
         let server_handle = tokio::spawn(async move {
             let mut conn = server.accept().await.unwrap();
 
-            if let Some(msg) = conn.rx.recv().await {
-                match msg {
-                    PluginMessage::Ping { timestamp } => {
-                        conn.send(PluginMessage::Pong { timestamp }).await.unwrap();
-                    }
-                    _ => {}
-                }
+            if let Some(PluginMessage::Ping { timestamp }) = conn.rx.recv().await {
+                conn.send(PluginMessage::Pong { timestamp }).await.unwrap();
             }
         });
 
@@ -1,16 +1,38 @@
 use crate::ipc::{IPCConnection, IPCServer};
 use crate::traits::{LogLevel, Plugin, PluginFactory, PluginHost};
-use crate::types::{Capability, PluginInfo, PluginMessage, SubscriptionTopic};
+use crate::types::{Capability, PluginInfo, PluginMessage, QueryType, SubscriptionTopic};
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio::sync::{Mutex, RwLock};
+use uuid::Uuid;
+
+/// Default budget for a plugin-to-plugin query (including nested queries)
+const DEFAULT_QUERY_TIMEOUT: Duration = Duration::from_secs(2);
+
+tokio::task_local! {
+    /// Plugins currently handling a call on this task, outermost first.
+    /// A query addressed to any of them would wait on its own lock.
+    static CALL_PATH: Vec<String>;
+}
+
+/// Run `fut` with `plugin_id` appended to the current call path
+async fn in_call_path<F: Future>(plugin_id: &str, fut: F) -> F::Output {
+    let mut path = CALL_PATH.try_with(|p| p.clone()).unwrap_or_default();
+    path.push(plugin_id.to_string());
+    CALL_PATH.scope(path, fut).await
+}
+
+// Locked per plugin so one plugin can query another while being handled
+type SharedPlugin = Arc<Mutex<Box<dyn Plugin>>>;
 
 /// Plugin instance with metadata
 struct PluginInstance {
-    plugin: Box<dyn Plugin>,
+    plugin: SharedPlugin,
     info: PluginInfo,
     enabled: bool,
     subscriptions: Vec<SubscriptionTopic>,
@@ -22,7 +44,7 @@ pub struct PluginRegistry {
     plugins: Arc<RwLock<HashMap<String, PluginInstance>>>,
     host_impl: Arc<dyn PluginHost>,
     message_bus: Arc<MessageBus>,
-    ipc_server: Option<IPCServer>,
+    query_timeout: Duration,
 }
 
 impl PluginRegistry {
@@ -32,37 +54,37 @@ impl PluginRegistry {
             plugins: Arc::new(RwLock::new(HashMap::new())),
             host_impl: host,
             message_bus: Arc::new(MessageBus::new()),
-            ipc_server: None,
+            query_timeout: DEFAULT_QUERY_TIMEOUT,
         }
     }
 
+    /// Set the timeout for plugin-to-plugin queries
+    pub fn set_query_timeout(&mut self, timeout: Duration) {
+        self.query_timeout = timeout;
+    }
+
     /// Start IPC server for external plugins
     pub async fn start_ipc_server(&mut self, socket_path: &str) -> Result<()> {
-        self.ipc_server = Some(IPCServer::bind_unix(socket_path).await?);
-
-        let plugins = self.plugins.clone();
+        let server = IPCServer::bind_unix(socket_path).await?;
         let message_bus = self.message_bus.clone();
 
-        // Spawn IPC accept loop
+        // Spawn IPC accept loop (owns the listener for the rest of the process)
         tokio::spawn(async move {
-            if let Some(server) = &self.ipc_server {
-                loop {
-                    match server.accept().await {
-                        Ok(mut conn) => {
-                            let plugins = plugins.clone();
-                            let bus = message_bus.clone();
-
-                            // Handle connection in separate task
-                            tokio::spawn(async move {
-                                while let Some(msg) = conn.rx.recv().await {
-                                    // Route message through message bus
-                                    let _ = bus.publish(msg).await;
-                                }
-                            });
-                        }
-                        Err(e) => {
-                            log::error!("Failed to accept IPC connection: {}", e);
-                        }
+            loop {
+                match server.accept().await {
+                    Ok(mut conn) => {
+                        let bus = message_bus.clone();
+
+                        // Handle connection in separate task
+                        tokio::spawn(async move {
+                            while let Some(msg) = conn.rx.recv().await {
+                                // Route message through message bus
+                                let _ = bus.publish(msg).await;
+                            }
+                        });
+                    }
+                    Err(e) => {
+                        log::error!("Failed to accept IPC connection: {}", e);
                     }
                 }
             }
@@ -94,7 +116,7 @@ impl PluginRegistry {
         }
 
         let instance = PluginInstance {
-            plugin,
+            plugin: Arc::new(Mutex::new(plugin)),
             info: info.clone(),
             enabled: false,
             subscriptions,
@@ -139,9 +161,9 @@ impl PluginRegistry {
     /// Unregister a plugin
     pub async fn unregister_plugin(&self, id: &str) -> Result<()> {
         let mut plugins = self.plugins.write().await;
-        if let Some(mut instance) = plugins.remove(id) {
+        if let Some(instance) = plugins.remove(id) {
             // Cleanup plugin
-            instance.plugin.cleanup().await?;
+            instance.plugin.lock().await.cleanup().await?;
 
             // Unsubscribe from topics
             for topic in &instance.subscriptions {
@@ -172,11 +194,12 @@ impl PluginRegistry {
                 conn.send(message).await?;
             } else {
                 // Direct call for in-process plugin
+                let plugin = instance.plugin.clone();
                 drop(plugins); // Release read lock
-                let mut plugins = self.plugins.write().await;
-                if let Some(instance) = plugins.get_mut(plugin_id) {
-                    instance.plugin.handle_message(message).await?;
-                }
+                in_call_path(plugin_id, async {
+                    plugin.lock().await.handle_message(message).await
+                })
+                .await?;
             }
             Ok(())
         } else {
@@ -192,14 +215,118 @@ impl PluginRegistry {
 
     /// Run periodic tick on all enabled plugins
     pub async fn tick_all(&self) -> Result<()> {
-        let mut plugins = self.plugins.write().await;
-        for (_, instance) in plugins.iter_mut() {
-            if instance.enabled {
-                instance.plugin.tick().await?;
-            }
+        let enabled: Vec<(String, SharedPlugin)> = {
+            let plugins = self.plugins.read().await;
+            plugins
+                .iter()
+                .filter(|(_, i)| i.enabled)
+                .map(|(id, i)| (id.clone(), i.plugin.clone()))
+                .collect()
+        };
+        for (id, plugin) in enabled {
+            in_call_path(&id, async { plugin.lock().await.tick().await }).await?;
         }
         Ok(())
     }
+
+    /// Query another plugin by id (federation).
+    ///
+    /// The answer is the target's `Response`. Fails if the target is unknown,
+    /// disabled or external, if it is already on the current call path
+    /// (A → B → A would wait on itself), or if it does not answer within the
+    /// query timeout.
+    pub async fn query_plugin(&self, target: &str, query: QueryType) -> Result<PluginMessage> {
+        self.router().dispatch(target, Uuid::new_v4(), query).await
+    }
+
+    /// Pick the enabled plugin that answers `query` (by capability)
+    pub async fn route_query(&self, query: &QueryType) -> Option<String> {
+        self.router().route(query).await
+    }
+
+    fn router(&self) -> QueryRouter {
+        QueryRouter {
+            plugins: self.plugins.clone(),
+            timeout: self.query_timeout,
+        }
+    }
+}
+
+/// What a plugin query needs from the registry. Cloned out so nothing holds a
+/// registry lock while a plugin runs (a nested query would queue behind any
+/// waiting writer and deadlock).
+struct QueryRouter {
+    plugins: Arc<RwLock<HashMap<String, PluginInstance>>>,
+    timeout: Duration,
+}
+
+impl QueryRouter {
+    async fn dispatch(&self, target: &str, id: Uuid, query: QueryType) -> Result<PluginMessage> {
+        let path = CALL_PATH.try_with(|p| p.clone()).unwrap_or_default();
+        if path.iter().any(|p| p == target) {
+            return Err(anyhow!(
+                "Circular plugin query: {} -> {}",
+                path.join(" -> "),
+                target
+            ));
+        }
+
+        let plugin = {
+            let plugins = self.plugins.read().await;
+            let instance = plugins
+                .get(target)
+                .ok_or_else(|| anyhow!("Plugin {} not found", target))?;
+            if !instance.enabled {
+                return Err(anyhow!("Plugin {} is disabled", target));
+            }
+            if instance.connection.is_some() {
+                return Err(anyhow!(
+                    "Plugin {} is external; queries are in-process only",
+                    target
+                ));
+            }
+            instance.plugin.clone()
+        };
+
+        let call = in_call_path(target, async move {
+            plugin
+                .lock()
+                .await
+                .handle_message(PluginMessage::Query { id, query })
+                .await
+        });
+        match tokio::time::timeout(self.timeout, call).await {
+            Err(_) => Err(anyhow!(
+                "Query to plugin {} timed out after {}ms",
+                target,
+                self.timeout.as_millis()
+            )),
+            Ok(Err(e)) => Err(e),
+            Ok(Ok(Some(response @ PluginMessage::Response { .. }))) => Ok(response),
+            Ok(Ok(_)) => Err(anyhow!("Plugin {} did not answer the query", target)),
+        }
+    }
+
+    async fn route(&self, query: &QueryType) -> Option<String> {
+        let wanted = match query {
+            QueryType::GetTodoById(_)
+            | QueryType::GetTodosByProject(_)
+            | QueryType::GetTodosByTag(_) => Capability::TodoManagement,
+            QueryType::GetBlockByHeight(_) => Capability::BlockchainMonitoring,
+            QueryType::GetTransactionByHash(_) | QueryType::GetRecentTransactions { .. } => {
+                Capability::TransactionAnalysis
+            }
+            QueryType::GetValidatorStats(_) => Capability::ValidatorTracking,
+        };
+        let plugins = self.plugins.read().await;
+        let mut candidates: Vec<&String> = plugins
+            .iter()
+            .filter(|(_, i)| i.enabled && i.info.capabilities.contains(&wanted))
+            .map(|(id, _)| id)
+            .collect();
+        candidates.sort(); // Deterministic pick when several plugins qualify
+        candidates.first().map(|id| id.to_string())
+    }
 }
 
 /// Message bus for routing messages between plugins
@@ -232,9 +359,6 @@ impl MessageBus {
 
     async fn publish(&self, message: PluginMessage) -> Result<()> {
         let topic = match &message {
-            PluginMessage::TodoCreated { .. } => Some(SubscriptionTopic::AllTodos),
-            PluginMessage::TodoCompleted { .. } => Some(SubscriptionTopic::AllTodos),
-            PluginMessage::TodoDeleted { .. } => Some(SubscriptionTopic::AllTodos),
             PluginMessage::BlockProduced { .. } => Some(SubscriptionTopic::AllBlocks),
             PluginMessage::TransactionFailed { .. } => Some(SubscriptionTopic::TransactionErrors),
             _ => None,
@@ -255,6 +379,7 @@ impl MessageBus {
         Ok(())
     }
 
+    #[allow(dead_code)] // In-process delivery isn't wired up yet; IPC plugins publish only
     async fn register_handler(&self, plugin_id: String, tx: mpsc::UnboundedSender<PluginMessage>) {
         let mut handlers = self.handlers.write().await;
         handlers.insert(plugin_id, tx);
@@ -266,6 +391,12 @@ pub struct RegistryHost {
     registry: Arc<RwLock<PluginRegistry>>,
 }
 
+impl RegistryHost {
+    pub fn new(registry: Arc<RwLock<PluginRegistry>>) -> Self {
+        Self { registry }
+    }
+}
+
 #[async_trait]
 impl PluginHost for RegistryHost {
     async fn send_message(&self, message: PluginMessage) -> Result<()> {
@@ -274,25 +405,37 @@ impl PluginHost for RegistryHost {
     }
 
     async fn query(&self, message: PluginMessage) -> Result<PluginMessage> {
-        // Route query to appropriate plugin based on message type
-        // This is a simplified implementation
+        // Route query to the plugin whose capabilities cover its type
         match message {
-            PluginMessage::Query { id, query: _ } => Ok(PluginMessage::Response {
-                id,
-                data: serde_json::Value::Null,
-                success: false,
-                error: Some("Query routing not implemented".to_string()),
-            }),
+            PluginMessage::Query { id, query } => {
+                let router = self.registry.read().await.router();
+                let result = match router.route(&query).await {
+                    Some(target) => router.dispatch(&target, id, query).await,
+                    None => Err(anyhow!("No enabled plugin answers {:?}", query)),
+                };
+                Ok(result.unwrap_or_else(|e| PluginMessage::Response {
+                    id,
+                    data: serde_json::Value::Null,
+                    success: false,
+                    error: Some(e.to_string()),
+                }))
+            }
             _ => Err(anyhow!("Invalid query message")),
         }
     }
 
-    async fn subscribe(&self, topic: SubscriptionTopic) -> Result<()> {
+    async fn query_plugin(&self, plugin_id: &str, query: QueryType) -> Result<PluginMessage> {
+        // Registry lock released before the target runs (it may query back through us)
+        let router = self.registry.read().await.router();
+        router.dispatch(plugin_id, Uuid::new_v4(), query).await
+    }
+
+    async fn subscribe(&self, _topic: SubscriptionTopic) -> Result<()> {
         // Subscription handled at registry level
         Ok(())
     }
 
-    async fn unsubscribe(&self, topic: SubscriptionTopic) -> Result<()> {
+    async fn unsubscribe(&self, _topic: SubscriptionTopic) -> Result<()> {
         // Unsubscription handled at registry level
         Ok(())
     }
@@ -312,13 +455,208 @@ impl PluginHost for RegistryHost {
         std::env::var(key).ok()
     }
 
-    async fn store_data(&self, key: &str, value: &[u8]) -> Result<()> {
+    async fn store_data(&self, _key: &str, _value: &[u8]) -> Result<()> {
         // Simplified - in real implementation would use persistent storage
         Ok(())
     }
 
-    async fn get_data(&self, key: &str) -> Result<Option<Vec<u8>>> {
+    async fn get_data(&self, _key: &str) -> Result<Option<Vec<u8>>> {
         // Simplified - in real implementation would use persistent storage
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Host for the registry itself (plugins get a `RegistryHost`)
+    struct NoHost;
+
+    #[async_trait]
+    impl PluginHost for NoHost {
+        async fn send_message(&self, _message: PluginMessage) -> Result<()> {
+            Ok(())
+        }
+        async fn query(&self, _message: PluginMessage) -> Result<PluginMessage> {
+            Err(anyhow!("no host"))
+        }
+        async fn subscribe(&self, _topic: SubscriptionTopic) -> Result<()> {
+            Ok(())
+        }
+        async fn unsubscribe(&self, _topic: SubscriptionTopic) -> Result<()> {
+            Ok(())
+        }
+        fn log(&self, _level: LogLevel, _message: &str) {}
+        fn get_config(&self, _key: &str) -> Option<String> {
+            None
+        }
+        async fn store_data(&self, _key: &str, _value: &[u8]) -> Result<()> {
+            Ok(())
+        }
+        async fn get_data(&self, _key: &str) -> Result<Option<Vec<u8>>> {
+            Ok(None)
+        }
+    }
+
+    /// Answers queries with its id, after `delay`, or forwards them to `forward_to`
+    struct Probe {
+        id: &'static str,
+        host: Arc<dyn PluginHost>,
+        forward_to: Option<&'static str>,
+        delay: Duration,
+    }
+
+    #[async_trait]
+    impl Plugin for Probe {
+        fn info(&self) -> PluginInfo {
+            PluginInfo {
+                id: self.id.to_string(),
+                name: self.id.to_string(),
+                version: "0.0.0".to_string(),
+                author: String::new(),
+                description: String::new(),
+                capabilities: vec![],
+            }
+        }
+        async fn init(&mut self) -> Result<()> {
+            Ok(())
+        }
+        async fn handle_message(
+            &mut self,
+            message: PluginMessage,
+        ) -> Result<Option<PluginMessage>> {
+            let PluginMessage::Query { id, query } = message else {
+                return Ok(None);
+            };
+            tokio::time::sleep(self.delay).await;
+            if let Some(target) = self.forward_to {
+                return self.host.query_plugin(target, query).await.map(Some);
+            }
+            Ok(Some(PluginMessage::Response {
+                id,
+                data: json!(self.id),
+                success: true,
+                error: None,
+            }))
+        }
+        async fn cleanup(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    struct Setup {
+        registry: Arc<RwLock<PluginRegistry>>,
+        host: Arc<dyn PluginHost>,
+    }
+
+    impl Setup {
+        fn new() -> Self {
+            let registry = Arc::new(RwLock::new(PluginRegistry::new(Arc::new(NoHost))));
+            let host: Arc<dyn PluginHost> = Arc::new(RegistryHost::new(registry.clone()));
+            Self { registry, host }
+        }
+
+        async fn add(
+            &self,
+            id: &'static str,
+            forward_to: Option<&'static str>,
+            delay: Duration,
+            enable: bool,
+        ) {
+            let probe = Probe {
+                id,
+                host: self.host.clone(),
+                forward_to,
+                delay,
+            };
+            let registry = self.registry.read().await;
+            registry
+                .register_plugin(id.to_string(), Box::new(probe))
+                .await
+                .unwrap();
+            if enable {
+                registry.enable_plugin(id).await.unwrap();
+            }
+        }
+
+        async fn query(&self, target: &str) -> Result<PluginMessage> {
+            self.host
+                .query_plugin(target, QueryType::GetBlockByHeight(1))
+                .await
+        }
+    }
+
+    fn answered_by(msg: PluginMessage) -> serde_json::Value {
+        match msg {
+            PluginMessage::Response {
+                data,
+                success: true,
+                ..
+            } => data,
+            other => panic!("expected a successful response, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn nested_query_reaches_the_final_plugin() {
+        let s = Setup::new();
+        s.add("a", Some("b"), Duration::ZERO, true).await;
+        s.add("b", None, Duration::ZERO, true).await;
+        assert_eq!(answered_by(s.query("a").await.unwrap()), json!("b"));
+    }
+
+    #[tokio::test]
+    async fn circular_query_is_rejected() {
+        let s = Setup::new();
+        s.add("a", Some("b"), Duration::ZERO, true).await;
+        s.add("b", Some("a"), Duration::ZERO, true).await;
+        let err = s.query("a").await.unwrap_err().to_string();
+        assert!(err.contains("Circular plugin query: a -> b -> a"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn slow_plugin_times_out() {
+        let s = Setup::new();
+        s.registry
+            .write()
+            .await
+            .set_query_timeout(Duration::from_millis(50));
+        s.add("slow", None, Duration::from_secs(5), true).await;
+        let err = s.query("slow").await.unwrap_err().to_string();
+        assert!(err.contains("timed out after 50ms"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn unknown_and_disabled_targets_are_rejected() {
+        let s = Setup::new();
+        s.add("off", None, Duration::ZERO, false).await;
+        let err = s.query("missing").await.unwrap_err().to_string();
+        assert!(err.contains("Plugin missing not found"), "{err}");
+        let err = s.query("off").await.unwrap_err().to_string();
+        assert!(err.contains("Plugin off is disabled"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn nested_query_survives_a_waiting_writer() {
+        // A writer queued on the registry while "a" runs must not block a's
+        // query to "b" (tokio's RwLock makes new readers wait for writers)
+        let s = Setup::new();
+        s.add("a", Some("b"), Duration::from_millis(50), true).await;
+        s.add("b", None, Duration::ZERO, true).await;
+
+        let query = s.query("a");
+        let writer = async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            let mut registry = s.registry.write().await;
+            registry.set_query_timeout(DEFAULT_QUERY_TIMEOUT);
+        };
+        let (answer, ()) = tokio::time::timeout(Duration::from_secs(1), async {
+            tokio::join!(query, writer)
+        })
+        .await
+        .expect("registry lock held across a plugin call");
+        assert_eq!(answered_by(answer.unwrap()), json!("b"));
+    }
+}
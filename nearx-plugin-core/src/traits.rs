@@ -1,8 +1,7 @@
-use crate::types::{Capability, PluginInfo, PluginMessage, SubscriptionTopic};
+use crate::types::{PluginInfo, PluginMessage, QueryType, SubscriptionTopic};
 use anyhow::Result;
 use async_trait::async_trait;
 use std::sync::Arc;
-use tokio::sync::Mutex;
 
 /// Core plugin trait that all plugins must implement
 #[async_trait]
@@ -39,6 +38,15 @@ pub trait PluginHost: Send + Sync {
     /// Query the host for data
     async fn query(&self, message: PluginMessage) -> Result<PluginMessage>;
 
+    /// Query another plugin by id (e.g. a dashboard aggregating tx-analyzer and
+    /// validator-monitor). Hosts that route queries enforce a timeout and reject
+    /// circular call chains.
+    async fn query_plugin(&self, plugin_id: &str, query: QueryType) -> Result<PluginMessage> {
+        Err(anyhow::anyhow!(
+            "Plugin queries not supported by this host ({plugin_id}: {query:?})"
+        ))
+    }
+
     /// Subscribe to a topic
     async fn subscribe(&self, topic: SubscriptionTopic) -> Result<()>;
 
@@ -108,7 +116,7 @@ pub trait PluginUI {
     }
 
     /// Handle UI events
-    fn handle_ui_event(&mut self, event: UIEvent) -> Result<()> {
+    fn handle_ui_event(&mut self, _event: UIEvent) -> Result<()> {
         Ok(())
     }
 }
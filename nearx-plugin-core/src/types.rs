@@ -1,7 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
 use uuid::Uuid;
 
 /// Core message types for plugin communication
@@ -32,6 +31,15 @@ pub enum PluginMessage {
         alert_type: AlertType,
         message: String,
     },
+    /// Generic alert tied to a block and/or tx. The host pins a mark for
+    /// high-severity alerts so the location stays navigable.
+    PluginAlert {
+        plugin_id: String,
+        severity: AlertSeverity,
+        block_height: Option<u64>,
+        tx_hash: Option<String>,
+        message: String,
+    },
 
     // Query Messages
     Query {
@@ -94,7 +102,7 @@ pub enum SubscriptionTopic {
     HighValueTransactions,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Capability {
     TodoManagement,
     BlockchainMonitoring,
@@ -112,6 +120,13 @@ pub enum AlertType {
     ConfigChange,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum AlertSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ErrorCode {
     Unknown = 0,
@@ -35,6 +35,8 @@ struct DecodedAction {
     deposit_attached: Option<String>,
 }
 
+// NEARx stores this JSON for its Risk browser (`nearx::types::TxAnalysis`):
+// keep `hash`, `signer`, `receiver`, `patterns_detected`, `risk_score` and `insights` stable
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct TransactionAnalysis {
     hash: String,
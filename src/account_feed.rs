@@ -0,0 +1,449 @@
+//! Account creation / deletion feed (Ctrl+A in the TUI)
+//!
+//! Collects `CreateAccount` and `DeleteAccount` actions (including ones inside
+//! meta-transactions) from live blocks and from History into one newest-first
+//! list. The filter narrows it by account suffix (`.near`, `.tg`), implicit
+//! accounts or kind, and the summary counts creations per signer so a factory
+//! minting accounts in bulk stands out.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::Deserialize;
+
+use crate::history::AccountTx;
+use crate::types::{ActionSummary, BlockRow};
+
+/// Events kept in the feed (oldest dropped first)
+pub const MAX_EVENTS: usize = 5_000;
+/// Txs read from History when the feed opens (newest first)
+pub const HISTORY_LIMIT: usize = 2_000;
+/// Signers listed in the summary
+const TOP_CREATORS: usize = 3;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AccountEventKind {
+    Create,
+    Delete,
+}
+
+/// One created or deleted account
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountEvent {
+    pub kind: AccountEventKind,
+    pub account: String,
+    /// Who paid for it (tx signer, or the meta-transaction sender)
+    pub signer: Option<String>,
+    /// Where a deleted account's balance went
+    pub beneficiary: Option<String>,
+    pub hash: String,
+    pub height: u64,
+    pub ts_ms: i64,
+}
+
+/// `Vec<ActionSummary>` entry as stored in History (only the fields we need)
+#[derive(Deserialize)]
+struct StoredAction {
+    #[serde(rename = "type")]
+    kind: String,
+    beneficiary_id: Option<String>,
+    sender_id: Option<String>,
+    receiver_id: Option<String>,
+    #[serde(default)]
+    actions: Vec<StoredAction>,
+}
+
+/// Account events in a live block
+pub fn events_from_block(block: &BlockRow) -> Vec<AccountEvent> {
+    let mut out = Vec::new();
+    for tx in &block.transactions {
+        let (Some(actions), Some(receiver)) = (&tx.actions, &tx.receiver_id) else {
+            continue;
+        };
+        let mut push =
+            |kind, account: &str, signer: Option<&String>, beneficiary: Option<&String>| {
+                out.push(AccountEvent {
+                    kind,
+                    account: account.to_string(),
+                    signer: signer.cloned(),
+                    beneficiary: beneficiary.cloned(),
+                    hash: tx.hash.clone(),
+                    height: block.height,
+                    ts_ms: block.timestamp as i64,
+                })
+            };
+        for action in actions {
+            match action {
+                ActionSummary::CreateAccount => push(
+                    AccountEventKind::Create,
+                    receiver,
+                    tx.signer_id.as_ref(),
+                    None,
+                ),
+                ActionSummary::DeleteAccount { beneficiary_id } => push(
+                    AccountEventKind::Delete,
+                    receiver,
+                    tx.signer_id.as_ref(),
+                    Some(beneficiary_id),
+                ),
+                ActionSummary::Delegate {
+                    sender_id,
+                    receiver_id,
+                    actions,
+                } => {
+                    for inner in actions {
+                        match inner {
+                            ActionSummary::CreateAccount => {
+                                push(AccountEventKind::Create, receiver_id, Some(sender_id), None)
+                            }
+                            ActionSummary::DeleteAccount { beneficiary_id } => push(
+                                AccountEventKind::Delete,
+                                receiver_id,
+                                Some(sender_id),
+                                Some(beneficiary_id),
+                            ),
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    out
+}
+
+/// Account events in a tx read back from History
+pub fn events_from_stored(tx: &AccountTx) -> Vec<AccountEvent> {
+    let actions: Vec<StoredAction> = tx
+        .actions_json
+        .as_deref()
+        .and_then(|j| serde_json::from_str(j).ok())
+        .unwrap_or_default();
+    let mut out = Vec::new();
+    let mut push =
+        |kind, account: Option<&String>, signer: Option<&String>, beneficiary: Option<String>| {
+            if let Some(account) = account {
+                out.push(AccountEvent {
+                    kind,
+                    account: account.clone(),
+                    signer: signer.cloned(),
+                    beneficiary,
+                    hash: tx.hash.clone(),
+                    height: tx.height,
+                    ts_ms: tx.ts_ms,
+                });
+            }
+        };
+    for action in actions {
+        match action.kind.as_str() {
+            "CreateAccount" => push(
+                AccountEventKind::Create,
+                tx.receiver.as_ref(),
+                tx.signer.as_ref(),
+                None,
+            ),
+            "DeleteAccount" => push(
+                AccountEventKind::Delete,
+                tx.receiver.as_ref(),
+                tx.signer.as_ref(),
+                action.beneficiary_id,
+            ),
+            "Delegate" => {
+                for inner in action.actions {
+                    let kind = match inner.kind.as_str() {
+                        "CreateAccount" => AccountEventKind::Create,
+                        "DeleteAccount" => AccountEventKind::Delete,
+                        _ => continue,
+                    };
+                    push(
+                        kind,
+                        action.receiver_id.as_ref(),
+                        action.sender_id.as_ref(),
+                        inner.beneficiary_id,
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Account class used by the suffix filter and the summary:
+/// `implicit` (64 hex), `eth` (0x + 40 hex), `.near` / `.tg` / ... (top-level suffix)
+pub fn account_class(account: &str) -> String {
+    let hex = |s: &str| s.chars().all(|c| c.is_ascii_hexdigit());
+    if account.len() == 64 && hex(account) {
+        return "implicit".into();
+    }
+    if let Some(addr) = account.strip_prefix("0x") {
+        if addr.len() == 40 && hex(addr) {
+            return "eth".into();
+        }
+    }
+    match account.rfind('.') {
+        Some(dot) => account[dot..].to_string(),
+        None => "top-level".into(),
+    }
+}
+
+/// Feed filter: space-separated terms, all of which must match. `.near`
+/// matches the suffix, `implicit` / `eth` the implicit kinds, `create` /
+/// `delete` the event kind, anything else a substring of account or signer.
+/// A leading `!` negates a term (`!.near` = everything else).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FeedQuery {
+    terms: Vec<(bool, String)>,
+}
+
+impl FeedQuery {
+    pub fn parse(q: &str) -> Self {
+        let terms = q
+            .split_whitespace()
+            .map(|t| {
+                let t = t.to_lowercase();
+                match t.strip_prefix('!') {
+                    Some(rest) => (true, rest.to_string()),
+                    None => (false, t),
+                }
+            })
+            .filter(|(_, t)| !t.is_empty())
+            .collect();
+        Self { terms }
+    }
+
+    pub fn matches(&self, e: &AccountEvent) -> bool {
+        self.terms.iter().all(|(negate, term)| {
+            let hit = match term.as_str() {
+                "create" => e.kind == AccountEventKind::Create,
+                "delete" => e.kind == AccountEventKind::Delete,
+                "implicit" | "eth" => account_class(&e.account) == *term,
+                t if t.starts_with('.') => e.account.ends_with(t),
+                t => e.account.contains(t) || e.signer.as_deref().is_some_and(|s| s.contains(t)),
+            };
+            hit != *negate
+        })
+    }
+}
+
+/// Counts over the filtered feed
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FeedSummary {
+    pub creates: usize,
+    pub deletes: usize,
+    /// Signers with the most creations, most first
+    pub top_creators: Vec<(String, usize)>,
+}
+
+impl FeedSummary {
+    pub fn of(events: &[&AccountEvent]) -> Self {
+        let mut creators: HashMap<&str, usize> = HashMap::new();
+        let mut summary = FeedSummary::default();
+        for e in events {
+            match e.kind {
+                AccountEventKind::Create => {
+                    summary.creates += 1;
+                    *creators
+                        .entry(e.signer.as_deref().unwrap_or("?"))
+                        .or_default() += 1;
+                }
+                AccountEventKind::Delete => summary.deletes += 1,
+            }
+        }
+        let mut top: Vec<(String, usize)> = creators
+            .into_iter()
+            .map(|(s, n)| (s.to_string(), n))
+            .collect();
+        top.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        top.truncate(TOP_CREATORS);
+        summary.top_creators = top;
+        summary
+    }
+}
+
+/// Live + History events, newest first, without duplicates
+#[derive(Clone, Debug, Default)]
+pub struct AccountFeed {
+    events: Vec<AccountEvent>,
+    seen: HashSet<(String, String, AccountEventKind)>,
+}
+
+impl AccountFeed {
+    pub fn record_block(&mut self, block: &BlockRow) {
+        self.merge(events_from_block(block));
+    }
+
+    /// Add events from any source; already-known ones are skipped
+    pub fn merge(&mut self, events: Vec<AccountEvent>) {
+        let before = self.events.len();
+        for e in events {
+            if self
+                .seen
+                .insert((e.hash.clone(), e.account.clone(), e.kind))
+            {
+                self.events.push(e);
+            }
+        }
+        if self.events.len() == before {
+            return;
+        }
+        self.events.sort_by(|a, b| b.height.cmp(&a.height));
+        for e in self.events.drain(MAX_EVENTS.min(self.events.len())..) {
+            self.seen.remove(&(e.hash, e.account, e.kind));
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Events matching `query`, newest first
+    pub fn filtered(&self, query: &FeedQuery) -> Vec<&AccountEvent> {
+        self.events.iter().filter(|e| query.matches(e)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::fixtures::{block, tx};
+    use crate::types::TxLite;
+
+    fn with_actions(
+        hash: &str,
+        signer: &str,
+        receiver: &str,
+        actions: Vec<ActionSummary>,
+    ) -> TxLite {
+        TxLite {
+            actions: Some(actions),
+            ..tx(hash, signer, receiver)
+        }
+    }
+
+    fn implicit() -> String {
+        "a".repeat(64)
+    }
+
+    /// Two factory creates and a delegated delete at height 10
+    fn live_block() -> BlockRow {
+        let txs = vec![
+            with_actions(
+                "t1",
+                "bot.near",
+                "x1.bot.near",
+                vec![ActionSummary::CreateAccount],
+            ),
+            with_actions(
+                "t2",
+                "bot.near",
+                &implicit(),
+                vec![ActionSummary::CreateAccount],
+            ),
+            with_actions(
+                "t3",
+                "relayer.near",
+                "relayer.near",
+                vec![ActionSummary::Delegate {
+                    sender_id: "old.tg".into(),
+                    receiver_id: "old.tg".into(),
+                    actions: vec![ActionSummary::DeleteAccount {
+                        beneficiary_id: "alice.near".into(),
+                    }],
+                }],
+            ),
+        ];
+        BlockRow {
+            timestamp: 1_000,
+            ..block(10, txs)
+        }
+    }
+
+    fn stored(hash: &str, height: u64, receiver: &str) -> AccountTx {
+        AccountTx {
+            hash: hash.into(),
+            height,
+            ts_ms: 0,
+            signer: Some("bot.near".into()),
+            receiver: Some(receiver.into()),
+            actions_json: Some(
+                r#"[{"type":"CreateAccount"},{"type":"Transfer","deposit":1}]"#.into(),
+            ),
+        }
+    }
+
+    fn feed() -> AccountFeed {
+        let mut feed = AccountFeed::default();
+        feed.record_block(&live_block());
+        feed
+    }
+
+    #[test]
+    fn live_blocks_yield_direct_and_delegated_events() {
+        let events = events_from_block(&live_block());
+        assert_eq!(events.len(), 3);
+        assert_eq!(
+            (events[0].account.as_str(), events[0].ts_ms),
+            ("x1.bot.near", 1_000)
+        );
+
+        // Inside a meta-transaction the sender pays, not the relayer
+        let delete = &events[2];
+        assert_eq!(delete.kind, AccountEventKind::Delete);
+        assert_eq!(delete.account, "old.tg");
+        assert_eq!(delete.signer.as_deref(), Some("old.tg"));
+        assert_eq!(delete.beneficiary.as_deref(), Some("alice.near"));
+    }
+
+    #[test]
+    fn recording_a_block_twice_keeps_one_copy() {
+        let mut feed = feed();
+        feed.record_block(&live_block());
+        assert_eq!(feed.len(), 3);
+    }
+
+    #[test]
+    fn history_copies_are_skipped_and_older_events_sort_last() {
+        let mut feed = feed();
+        let mut from_history = events_from_stored(&stored("t1", 10, "x1.bot.near"));
+        from_history.extend(events_from_stored(&stored("t0", 5, "x0.bot.near")));
+        feed.merge(from_history);
+        assert_eq!(feed.len(), 4);
+
+        let all = feed.filtered(&FeedQuery::default());
+        assert_eq!(all.last().map(|e| e.hash.as_str()), Some("t0"));
+    }
+
+    #[test]
+    fn queries_filter_by_suffix_class_kind_and_negation() {
+        let feed = feed();
+        assert_eq!(feed.filtered(&FeedQuery::parse(".near")).len(), 1);
+        assert_eq!(feed.filtered(&FeedQuery::parse(".tg")).len(), 1);
+        assert_eq!(feed.filtered(&FeedQuery::parse("implicit")).len(), 1);
+        assert_eq!(feed.filtered(&FeedQuery::parse("delete")).len(), 1);
+        assert_eq!(feed.filtered(&FeedQuery::parse("create !.near")).len(), 1);
+    }
+
+    #[test]
+    fn account_classes() {
+        assert_eq!(account_class(&implicit()), "implicit");
+        assert_eq!(account_class("x1.bot.near"), ".near");
+        assert_eq!(account_class("near"), "top-level");
+        assert_eq!(
+            account_class("0x52908400098527886e0f7030069857d2e4169ee7"),
+            "eth"
+        );
+    }
+
+    #[test]
+    fn summary_counts_kinds_and_top_creators() {
+        let feed = feed();
+        let summary = FeedSummary::of(&feed.filtered(&FeedQuery::default()));
+        assert_eq!((summary.creates, summary.deletes), (2, 1));
+        assert_eq!(summary.top_creators, vec![("bot.near".to_string(), 2)]);
+    }
+}
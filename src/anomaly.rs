@@ -0,0 +1,370 @@
+//! Rolling anomaly detection on live blocks
+//!
+//! Per-block tx count and gas used each keep an exponentially weighted mean
+//! and variance (EWMA). A block whose value sits `sigma` standard deviations
+//! above that baseline starts an anomaly: the app raises a warning toast and
+//! keeps a status bar banner up while it lasts. When values fall back under
+//! the threshold the anomaly ends as an incident covering its block range
+//! (added to the pin board when `ANOMALY_INCIDENTS` is on).
+//!
+//! Values join the baseline capped at the threshold, so a burst doesn't hide
+//! itself by inflating the variance. Only heights newer than any seen are
+//! recorded, so backfill and archival fetches never move the baseline. No
+//! plugins involved.
+
+use crate::types::BlockRow;
+
+/// Default threshold (standard deviations above the baseline)
+pub const DEFAULT_ANOMALY_SIGMA: f64 = 5.0;
+/// EWMA weight of the newest block (~ a 40-block memory)
+const ALPHA: f64 = 0.05;
+/// Blocks needed before the baseline is trusted
+const MIN_SAMPLES: u64 = 30;
+/// Standard deviation floor, so a perfectly flat baseline doesn't fire on +1
+const MIN_STD_DEV: f64 = 1.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Metric {
+    TxVolume,
+    GasUsed,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 2] = [Metric::TxVolume, Metric::GasUsed];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Metric::TxVolume => "tx volume",
+            Metric::GasUsed => "gas used",
+        }
+    }
+
+    fn value(self, block: &BlockRow) -> Option<f64> {
+        match self {
+            Metric::TxVolume => Some(block.tx_count as f64),
+            Metric::GasUsed => block.gas_used.map(|g| g as f64),
+        }
+    }
+
+    /// `312 txs`, `1.2 Pgas`
+    pub fn format(self, value: f64) -> String {
+        match self {
+            Metric::TxVolume => format!("{value:.0} txs"),
+            Metric::GasUsed => format_gas(value),
+        }
+    }
+}
+
+fn format_gas(gas: f64) -> String {
+    match gas {
+        g if g >= 1e15 => format!("{:.1} Pgas", g / 1e15),
+        g if g >= 1e12 => format!("{:.1} Tgas", g / 1e12),
+        g if g >= 1e9 => format!("{:.1} Ggas", g / 1e9),
+        g => format!("{g:.0} gas"),
+    }
+}
+
+/// Exponentially weighted mean / variance
+#[derive(Clone, Debug, Default)]
+struct Ewma {
+    mean: f64,
+    var: f64,
+    samples: u64,
+}
+
+impl Ewma {
+    fn std_dev(&self) -> f64 {
+        self.var.sqrt().max(MIN_STD_DEV)
+    }
+
+    fn z_score(&self, value: f64) -> Option<f64> {
+        (self.samples >= MIN_SAMPLES).then(|| (value - self.mean) / self.std_dev())
+    }
+
+    fn update(&mut self, value: f64) {
+        if self.samples == 0 {
+            self.mean = value;
+        } else {
+            let diff = value - self.mean;
+            let incr = ALPHA * diff;
+            self.mean += incr;
+            self.var = (1.0 - ALPHA) * (self.var + diff * incr);
+        }
+        self.samples += 1;
+    }
+}
+
+/// A block that crossed the threshold (start of an anomaly)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Anomaly {
+    pub metric: Metric,
+    pub height: u64,
+    pub value: f64,
+    pub baseline: f64,
+    pub sigma: f64,
+}
+
+impl Anomaly {
+    /// `tx volume 5.2σ above baseline (312 txs vs ~60 txs)`
+    pub fn message(&self) -> String {
+        format!(
+            "{} {:.1}σ above baseline ({} vs ~{})",
+            self.metric.label(),
+            self.sigma,
+            self.metric.format(self.value),
+            self.metric.format(self.baseline)
+        )
+    }
+}
+
+/// A finished anomaly: the blocks it covered and its peak
+#[derive(Clone, Debug, PartialEq)]
+pub struct Incident {
+    pub metric: Metric,
+    pub from_height: u64,
+    pub to_height: u64,
+    pub peak_sigma: f64,
+}
+
+impl Incident {
+    /// `tx volume incident #100..#104 (peak 6.1σ)`
+    pub fn title(&self) -> String {
+        format!(
+            "{} incident #{}..#{} (peak {:.1}σ)",
+            self.metric.label(),
+            self.from_height,
+            self.to_height,
+            self.peak_sigma
+        )
+    }
+
+    /// History browser range (Ctrl+B)
+    pub fn range(&self) -> String {
+        format!("{}..{}", self.from_height, self.to_height)
+    }
+}
+
+/// What recording one block changed
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AnomalyUpdate {
+    pub started: Vec<Anomaly>,
+    pub ended: Vec<Incident>,
+}
+
+#[derive(Clone, Debug)]
+struct Track {
+    metric: Metric,
+    baseline: Ewma,
+    /// Anomaly in progress: its start, last block above the threshold, peak
+    active: Option<(Anomaly, u64, f64)>,
+}
+
+#[derive(Clone, Debug)]
+pub struct AnomalyDetector {
+    /// Threshold in standard deviations (0 = off)
+    sigma: f64,
+    tracks: Vec<Track>,
+    newest: Option<u64>,
+}
+
+impl Default for AnomalyDetector {
+    fn default() -> Self {
+        Self::new(DEFAULT_ANOMALY_SIGMA)
+    }
+}
+
+impl AnomalyDetector {
+    pub fn new(sigma: f64) -> Self {
+        Self {
+            sigma,
+            tracks: Metric::ALL
+                .iter()
+                .map(|&metric| Track {
+                    metric,
+                    baseline: Ewma::default(),
+                    active: None,
+                })
+                .collect(),
+            newest: None,
+        }
+    }
+
+    pub fn set_sigma(&mut self, sigma: f64) {
+        self.sigma = sigma;
+    }
+
+    /// Anomalies in progress (start block), for the banner
+    pub fn active(&self) -> impl Iterator<Item = &Anomaly> {
+        self.tracks
+            .iter()
+            .filter_map(|t| t.active.as_ref().map(|(a, _, _)| a))
+    }
+
+    /// Record a live block; heights at or below the newest seen are ignored
+    pub fn record(&mut self, block: &BlockRow) -> AnomalyUpdate {
+        let mut update = AnomalyUpdate::default();
+        if self.sigma <= 0.0 || self.newest.is_some_and(|h| block.height <= h) {
+            return update;
+        }
+        self.newest = Some(block.height);
+
+        for track in &mut self.tracks {
+            let Some(value) = track.metric.value(block) else {
+                continue;
+            };
+            // Compare against the baseline before this block joins it
+            let z = track.baseline.z_score(value);
+            let above = z.is_some_and(|z| z >= self.sigma);
+            match (&mut track.active, above) {
+                (Some((_, last, peak)), true) => {
+                    *last = block.height;
+                    *peak = peak.max(z.unwrap_or_default());
+                }
+                (None, true) => {
+                    let sigma = z.unwrap_or_default();
+                    let anomaly = Anomaly {
+                        metric: track.metric,
+                        height: block.height,
+                        value,
+                        baseline: track.baseline.mean,
+                        sigma,
+                    };
+                    update.started.push(anomaly.clone());
+                    track.active = Some((anomaly, block.height, sigma));
+                }
+                (Some(_), false) => {
+                    if let Some((start, last, peak)) = track.active.take() {
+                        update.ended.push(Incident {
+                            metric: track.metric,
+                            from_height: start.height,
+                            to_height: last,
+                            peak_sigma: peak,
+                        });
+                    }
+                }
+                (None, false) => {}
+            }
+            // Outliers join the baseline capped at the threshold: a burst can't
+            // inflate the variance enough to hide itself, but a lasting level
+            // shift is still absorbed over time
+            let capped = match z {
+                Some(_) => value.min(track.baseline.mean + self.sigma * track.baseline.std_dev()),
+                None => value,
+            };
+            track.baseline.update(capped);
+        }
+        update
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::fixtures::block;
+
+    const TGAS: u64 = 1_000_000_000_000;
+
+    fn stats(height: u64, txs: usize, gas: Option<u64>) -> BlockRow {
+        BlockRow {
+            tx_count: txs,
+            gas_used: gas,
+            ..block(height, Vec::new())
+        }
+    }
+
+    /// Noisy but steady baseline around 60 txs / 100 Tgas over heights 1..=60
+    fn warmed_up() -> AnomalyDetector {
+        let mut detector = AnomalyDetector::new(5.0);
+        for h in 1..=60u64 {
+            let txs = 55 + (h % 10) as usize;
+            let gas = 100 * TGAS + (h % 7) * 5 * TGAS;
+            assert_eq!(
+                detector.record(&stats(h, txs, Some(gas))),
+                AnomalyUpdate::default()
+            );
+        }
+        detector
+    }
+
+    #[test]
+    fn flags_a_tx_volume_burst_once() {
+        let mut detector = warmed_up();
+        let update = detector.record(&stats(61, 300, Some(100 * TGAS)));
+        assert_eq!(update.started.len(), 1);
+        let anomaly = &update.started[0];
+        assert_eq!((anomaly.metric, anomaly.height), (Metric::TxVolume, 61));
+        assert!(anomaly.message().starts_with("tx volume "));
+        assert!(anomaly.message().contains("σ above baseline (300 txs vs ~"));
+
+        // Still elevated: no second alert
+        assert!(detector.record(&stats(62, 320, None)).started.is_empty());
+        assert_eq!(detector.active().count(), 1);
+    }
+
+    #[test]
+    fn gas_spike_is_tracked_on_its_own() {
+        let mut detector = warmed_up();
+        let update = detector.record(&stats(61, 60, Some(2_000 * TGAS)));
+        assert_eq!(update.started.len(), 1);
+        assert_eq!(update.started[0].metric, Metric::GasUsed);
+
+        // Blocks without gas data leave the gas anomaly open
+        assert_eq!(
+            detector.record(&stats(62, 60, None)),
+            AnomalyUpdate::default()
+        );
+        assert_eq!(detector.active().count(), 1);
+    }
+
+    #[test]
+    fn older_and_repeated_heights_are_ignored() {
+        let mut detector = warmed_up();
+        assert_eq!(
+            detector.record(&stats(40, 900, None)),
+            AnomalyUpdate::default()
+        );
+        assert_eq!(
+            detector.record(&stats(60, 900, None)),
+            AnomalyUpdate::default()
+        );
+        assert_eq!(detector.active().count(), 0);
+    }
+
+    #[test]
+    fn burst_closes_as_an_incident_when_it_subsides() {
+        let mut detector = warmed_up();
+        detector.record(&stats(61, 300, None));
+        detector.record(&stats(62, 320, None));
+
+        let update = detector.record(&stats(63, 60, None));
+        assert_eq!(update.ended.len(), 1);
+        assert_eq!(update.ended[0].range(), "61..62");
+        assert!(update.ended[0]
+            .title()
+            .starts_with("tx volume incident #61..#62"));
+        assert_eq!(detector.active().count(), 0);
+    }
+
+    #[test]
+    fn no_alerts_before_the_baseline_has_enough_samples() {
+        let mut detector = AnomalyDetector::new(5.0);
+        for h in 1..MIN_SAMPLES {
+            let txs = if h == MIN_SAMPLES - 1 { 10_000 } else { 5 };
+            assert_eq!(
+                detector.record(&stats(h, txs, None)),
+                AnomalyUpdate::default()
+            );
+        }
+    }
+
+    #[test]
+    fn zero_threshold_switches_detection_off() {
+        let mut off = AnomalyDetector::new(0.0);
+        for h in 1..=100 {
+            assert_eq!(
+                off.record(&stats(h, if h == 100 { 10_000 } else { 5 }, None)),
+                AnomalyUpdate::default()
+            );
+        }
+    }
+}
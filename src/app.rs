@@ -1,5 +1,4 @@
-use serde_json::json;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[cfg(not(target_arch = "wasm32"))]
 use std::time::{Duration, Instant};
@@ -7,11 +6,15 @@ use std::time::{Duration, Instant};
 #[cfg(target_arch = "wasm32")]
 use web_time::{Duration, Instant};
 
-use crate::filter::{self, compile_filter, tx_matches_filter, CompiledFilter};
+use crate::filter::{
+    self, compile_filter, CompiledFilter, FilterCache, FilterField, FILTER_EVAL_BUDGET_PER_TICK,
+};
 use crate::flags::UiFlags;
-use crate::json_pretty::pretty;
-use crate::theme::Theme;
-use crate::types::{AppEvent, BlockRow, TxLite, WsPayload};
+use crate::status_bar::{self, SegmentAction, StatusSegment, Tone};
+use crate::json_pretty::{pretty, JsonFormat};
+use crate::theme::{Appearance, SystemThemes, Theme, ThemeVariant};
+use crate::toast::{Toast, ToastLevel, ToastQueue};
+use crate::types::{ActionSummary, AlertSeverity, AppEvent, BlockRow, TxLite, WsPayload};
 
 #[cfg(feature = "native")]
 use crate::theme::ratatui_helpers;
@@ -22,6 +25,19 @@ pub enum InputMode {
     Filter,
     Search,
     Marks,
+    Endpoints,
+    Risk,
+    Sources,
+    Board,
+    Watches,
+    Exports,
+    Digest,
+    State,
+    Telemetry,
+    Accounts,
+    HistoryBrowser,
+    Import,
+    Collection,
 }
 
 /// Content type for fullscreen Details pane
@@ -39,6 +55,51 @@ pub enum FullscreenMode {
     Navigate,  // Arrow keys navigate underlying pane (Blocks/Txs rows)
 }
 
+/// Pane maximized to the whole body (`z` zooms the focused pane; Spacebar zooms Details)
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ZoomedPane {
+    Blocks,
+    Txs,
+    Details, // Details fullscreen (content per `FullscreenContentType`)
+}
+
+impl ZoomedPane {
+    pub fn name(self) -> &'static str {
+        match self {
+            ZoomedPane::Blocks => "Blocks",
+            ZoomedPane::Txs => "Txs",
+            ZoomedPane::Details => "Details",
+        }
+    }
+}
+
+/// RPC endpoints the block source talks to (hot-swappable at runtime)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Endpoints {
+    pub near_node_url: String,
+    pub archival_rpc_url: Option<String>,
+}
+
+/// In-progress edit of the endpoint settings overlay (Ctrl+E)
+#[derive(Clone, Debug, Default)]
+pub struct EndpointDraft {
+    pub near_node_url: String,
+    pub archival_rpc_url: String, // Empty = archival fetch disabled
+    pub field: usize,             // 0 = RPC, 1 = archival
+}
+
+/// Example txs for the filter being composed (side panel while the filter bar is focused)
+#[derive(Clone, Debug, Default)]
+pub struct FilterPreview {
+    pub matching: Vec<(u64, TxLite)>, // (block height, tx)
+    pub other: Vec<(u64, TxLite)>,
+    pub scanned: usize,
+    pub truncated: bool, // Gave up scanning before both lists were full
+}
+
+/// Examples per side of the filter preview
+const FILTER_PREVIEW_SAMPLES: usize = 3;
+
 /// Reason for block selection change - determines tx selection behavior
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 enum BlockChangeReason {
@@ -47,8 +108,21 @@ enum BlockChangeReason {
     FilterChange, // Filter was applied/cleared (try to preserve tx)
 }
 
+/// Tx JSON for the details pane, annotated with recognized bridge provenance
+fn tx_details_value(tx: &TxLite) -> serde_json::Value {
+    let mut val = serde_json::to_value(tx).unwrap_or(serde_json::Value::Null);
+    if let (Some(bridge), Some(obj)) = (crate::bridge::detect(tx), val.as_object_mut()) {
+        let mut annotation = serde_json::to_value(&bridge).unwrap_or_default();
+        annotation["summary"] = bridge.summary().into();
+        obj.insert("bridge".to_string(), annotation);
+    }
+    val
+}
+
 const BACK_WINDOW: usize = 50;
 const FRONT_WINDOW: u64 = 50;
+/// How long rows involving a newly detected account key stay highlighted
+const OWNED_HIGHLIGHT: Duration = Duration::from_secs(60);
 
 /// Backwards-fill slot for the block list (ancestors of the anchor block).
 #[derive(Debug, Clone)]
@@ -68,7 +142,8 @@ pub enum BackSlotState {
 }
 
 /// Virtual text buffer for Details pane with windowed rendering.
-/// Stores full JSON and line offsets for efficient scrolling.
+/// Stores full JSON and line offsets for efficient scrolling; colored lines
+/// are produced lazily around the viewport (see `colored_lines`).
 pub struct DetailsBuffer {
     /// Full pretty-printed JSON (or other text)
     text: String,
@@ -78,6 +153,8 @@ pub struct DetailsBuffer {
     scroll_line: usize,
     /// Whether the content was truncated at MAX_LINES
     truncated: bool,
+    /// Colorized chunks near the viewport (LRU)
+    colored: crate::colored_lines::ColoredLines,
 }
 
 impl Default for DetailsBuffer {
@@ -87,8 +164,9 @@ impl Default for DetailsBuffer {
 }
 
 impl DetailsBuffer {
-    /// Maximum lines to index (prevents UI freeze on massive blocks)
-    const MAX_LINES: usize = 5_000;
+    /// Maximum lines to index (8 bytes each; colorizing is lazy, so only the
+    /// index and the text grow with the document)
+    pub const MAX_LINES: usize = 1_000_000;
 
     pub fn new() -> Self {
         Self {
@@ -96,12 +174,17 @@ impl DetailsBuffer {
             line_offsets: vec![0],
             scroll_line: 0,
             truncated: false,
+            colored: Default::default(),
         }
     }
 
     /// Replace buffer contents and rebuild line index
     pub fn set_text(&mut self, text: String) {
-        self.text = text;
+        // Stray control characters would drive the terminal instead of showing
+        self.text = match crate::safe_text::escape_controls(&text) {
+            std::borrow::Cow::Borrowed(_) => text,
+            std::borrow::Cow::Owned(escaped) => escaped,
+        };
         self.line_offsets.clear();
         self.line_offsets.push(0);
 
@@ -122,6 +205,9 @@ impl DetailsBuffer {
             self.truncated = false;
         }
 
+        let trimmed = self.text.trim_start();
+        self.colored
+            .reset(trimmed.starts_with('{') || trimmed.starts_with('['));
         self.scroll_line = 0;
     }
 
@@ -165,6 +251,18 @@ impl DetailsBuffer {
         self.text[start_idx..end_idx].to_string()
     }
 
+    /// Colored lines of the window (same range as `window`), colorized on demand
+    pub fn colored_window(&mut self, max_lines: usize, theme: &Theme) -> Vec<ratatui::text::Line<'static>> {
+        if self.text.is_empty() || max_lines == 0 {
+            return Vec::new();
+        }
+        let total_lines = self.line_offsets.len();
+        let start_line = self.scroll_line.min(total_lines.saturating_sub(1));
+        let end_line = (start_line + max_lines).min(total_lines);
+        self.colored
+            .lines(&self.text, &self.line_offsets, start_line, end_line, theme)
+    }
+
     /// Scroll by delta lines (positive = down, negative = up)
     pub fn scroll_lines(&mut self, delta: isize, viewport_lines: usize) {
         if self.text.is_empty() {
@@ -197,6 +295,53 @@ impl DetailsBuffer {
             self.scroll_line = 0;
         }
     }
+
+    /// Put `line` (0-based) at the top of the viewport (clamped like scrolling)
+    pub fn scroll_to_line(&mut self, line: usize, viewport_lines: usize) {
+        let max_scroll = self.line_offsets.len().saturating_sub(viewport_lines);
+        self.scroll_line = line.min(max_scroll);
+    }
+
+    fn line(&self, i: usize) -> &str {
+        let start = self.line_offsets[i];
+        let end = self
+            .line_offsets
+            .get(i + 1)
+            .map_or(self.text.len(), |&next| next - 1);
+        &self.text[start..end]
+    }
+
+    /// Next (`forward`) or previous section start relative to the top line:
+    /// top-level keys of pretty-printed JSON (whatever indent it uses), or
+    /// lines after a blank line for plain text
+    pub fn section_start(&self, forward: bool) -> Option<usize> {
+        let total = self.line_offsets.len();
+        let key_indent = if self.colored.is_json() {
+            (1..total).find_map(|i| {
+                let line = self.line(i);
+                let body = line.trim_start();
+                body.starts_with('"').then(|| line.len() - body.len())
+            })
+        } else {
+            None
+        };
+        let is_start = |i: usize| match key_indent {
+            Some(indent) => {
+                let line = self.line(i);
+                line.len() > indent
+                    && line.as_bytes()[indent] == b'"'
+                    && line[..indent].trim().is_empty()
+            }
+            None => {
+                i > 0 && !self.line(i).trim().is_empty() && self.line(i - 1).trim().is_empty()
+            }
+        };
+        if forward {
+            (self.scroll_line + 1..total).find(|&i| is_start(i))
+        } else {
+            (0..self.scroll_line).rev().find(|&i| is_start(i))
+        }
+    }
 }
 
 pub struct App {
@@ -209,6 +354,7 @@ pub struct App {
     // Details pane windowed rendering (virtual buffer)
     details_buf: DetailsBuffer,
     details_viewport_lines: usize, // Set by renderer based on pane height
+    details_goto: Option<String>,  // Jump prompt in Details (`:120`, `50%`) while typing
 
     fps: u32,
     fps_choices: Vec<u32>,
@@ -219,16 +365,66 @@ pub struct App {
     // Filter state
     filter_query: String,
     filter_compiled: CompiledFilter,
+    filter_cache: FilterCache, // Precomputed haystacks + per-block counts (stale-while-revalidate)
+    filter_preview: FilterPreview, // Draft query examples (refreshed per keystroke in Filter mode)
     input_mode: InputMode,
 
     // Search state
     search_query: String,
     search_results: Vec<crate::history::HistoryHit>,
     search_selection: usize,
+    // Preview of the highlighted search result (loaded lazily from History)
+    search_preview: DetailsBuffer,
+    search_preview_hash: Option<String>, // Hash currently loaded into search_preview
+    search_preview_viewport_lines: usize, // Set by renderer based on preview height
 
     // Marks state
     marks_list: Vec<crate::types::Mark>,
+    marks_trash: Vec<crate::types::Mark>, // Soft-deleted marks, listed after the active ones
     marks_selection: usize,
+    // Risk browser (Ctrl+R): stored tx-analyzer results, newest first
+    risk_query: String,
+    risk_results: Vec<crate::history::RiskHit>,
+    risk_selection: usize,
+    account_feed: crate::account_feed::AccountFeed, // CreateAccount / DeleteAccount events (Ctrl+A)
+    account_feed_query: String,
+    account_feed_selection: usize,
+    // Pin board (Shift+B): session notes/copies, plus the pinned marks it was opened with
+    pin_board: crate::pin_board::PinBoard,
+    board_marks: Vec<crate::types::Mark>,
+    board_selection: usize,
+    board_note: Option<String>, // Note being typed
+    // Standing watches (W adds the current filter, Ctrl+W manages); rules live in History
+    watches: crate::watch::WatchList,
+    watch_selection: usize,
+    watch_webhook: Option<String>, // Webhook URL being typed
+    watch_hooks: Vec<crate::watch::WatchHit>, // Hits awaiting their webhook POST (runtime side)
+    watches_dirty: bool,
+    // Exports overlay (Shift+E): records loaded from History when opened
+    exports: Vec<crate::exports::ExportRecord>,
+    exports_selection: usize,
+    pending_export_rerun: Option<crate::exports::ExportRecord>, // Digest re-run for the runtime
+    // Staged startup (native): process start + stages done; None once complete
+    startup: Option<(Instant, crate::startup::StartupProgress)>,
+    // Contract digest (Ctrl+G): (contract, window secs) shown / awaiting the runtime
+    digest_target: Option<(String, u64)>,
+    pending_digest: Option<(String, u64)>,
+    digest: Option<crate::digest::ContractDigest>,
+    digest_window: u64,
+    digest_scroll: u16,
+    // Contract state inspector (Ctrl+K): tree view, and (account, prefix) awaiting the runtime
+    state_view: Option<crate::state_view::StateInspector>,
+    pending_state: Option<(String, Vec<u8>)>,
+    // History browser (Ctrl+B): stored blocks paged by range, kept across open/close
+    history_browser: crate::history_browser::HistoryBrowser,
+    // Holders tab of the inspector (Tab): FastNEAR lookups, cached and spaced out
+    holders_tab: Option<crate::holders::HoldersTab>,
+    holders_tab_open: bool,
+    holders_cache: crate::holders::HoldersCache,
+    pending_holders: Option<(String, Option<String>)>,
+    // Opt-in usage counters (Ctrl+T previews the report)
+    telemetry: crate::telemetry::Telemetry,
+    telemetry_scroll: u16,
 
     // Manually-selected blocks cache (preserves blocks after they age out of rolling buffer)
     cached_blocks: HashMap<u64, BlockRow>, // height -> block
@@ -238,34 +434,105 @@ pub struct App {
     loading_block: Option<u64>, // Block height currently being fetched from archival
     archival_fetch_tx: Option<tokio::sync::mpsc::UnboundedSender<u64>>, // Channel to request archival fetches
 
+    // Endpoint hotplug: current endpoints, overlay draft, and a switch awaiting the runtime
+    endpoints: Endpoints,
+    endpoint_draft: EndpointDraft,
+    pending_endpoints: Option<Endpoints>,
+
+    // Source health badges; toggles from the sources overlay wait for the runtime
+    sources: Vec<crate::source_health::SourceHealth>,
+    sources_selection: usize,
+    sources_changed: bool,
+
+    // Footer segments contributed by features and plugins (core ones are built per frame)
+    status_bar: crate::status_bar::StatusBar,
+
+    // Config file (live reload): default filter it last set, account labels
+    default_filter: String,
+    account_labels: std::collections::BTreeMap<String, String>,
+
+    // Related txs listed in Details: the tx shown (with its heuristic view),
+    // its ranked suggestions, and the History lookup awaiting the runtime
+    related_for: Option<(TxLite, crate::related::Candidate)>,
+    related: Vec<crate::related::RelatedTx>,
+    pending_related: Option<crate::related::RelatedQuery>,
+
+    // Tx timeline: (hash, signer) awaiting a receipt trace by the runtime
+    pending_timeline: Option<(String, String)>,
+    pending_report: Option<crate::html_report::ReportJob>, // HTML report for the runtime (Shift+R)
+    // Tx collection (Shift+I): pasted import draft, hashes awaiting resolution, the collection
+    import_draft: String,
+    pending_import: Option<Vec<String>>,
+    collection: Option<crate::collection::Collection>,
+    // Timeline shown in Details (kept so `e` can expand its raw error JSON)
+    timeline: Option<(crate::timeline::TxTimeline, bool)>,
+
+    // Gas profiler: (hash, signer) awaiting a fetch, and the profile shown in Details
+    pending_gas_profile: Option<(String, String)>,
+    gas_profile: Option<crate::gas_profile::GasProfile>,
+
+    // Warm start: heights loaded from the last session, not yet re-fetched
+    stale_heights: HashSet<u64>,
+
+    // Owned accounts (credentials watcher) and the owned-only filter (Ctrl+U)
+    owned_accounts: Option<HashSet<String>>, // None until the first scan arrives
+    owned_only: bool,
+    owned_new: HashSet<String>, // Added mid-session; rows involving them are highlighted
+    owned_new_until: Option<Instant>,
+
     /// When true, new live blocks from RPC are ignored.
     /// Set when user is pinned far behind the live tip (>50 blocks past focal).
     live_updates_paused: bool,
 
+    // Window fill deferred while ↑/↓ repeats: (selected height, last move)
+    block_window_pending: Option<(u64, Instant)>,
+
     // Backwards fill window (second list, anchored at selected block).
     back_slots: Vec<BackSlot>,
     back_anchor_height: Option<u64>,
     back_next_request_at: Option<Instant>,
     back_slots_target: usize,
 
+    // Snapshot mutation tracking: bumped whenever the Blocks / Txs rows could
+    // change (buffer, cache, backfill, filter results); selection moves don't
+    blocks_rev: u64,
+    profiler_visible: bool, // Snapshot profiler overlay (P, Web/Tauri)
+    time_display: crate::time_display::TimeDisplay, // Zone (cycled with T) + timestamp format
+
     // Debug log (for development)
     debug_log: Vec<String>, // Rolling buffer of debug messages
     debug_visible: bool,    // Toggle debug panel visibility (Ctrl+D)
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    debug_log_file: Option<std::path::PathBuf>, // Append debug lines here (native binary only)
 
     // Keyboard shortcuts overlay (Web/Tauri only for now, TUI infrastructure ready for future)
     shortcuts_visible: bool, // Toggle keyboard shortcuts help overlay (? key)
 
     // Toast notification state
-    toast_message: Option<(String, Instant)>, // (message, timestamp)
+    toasts: ToastQueue,
+    toast_history_visible: bool, // Toast history overlay (n key)
+    gas_oracle: crate::gas_oracle::GasOracle,
+    // Tx volume / gas used anomalies (banner while active; incidents to the pin board when enabled)
+    anomaly: crate::anomaly::AnomalyDetector,
+    anomaly_incidents: bool,
+    stale_after_secs: u64, // Stale watermark over Blocks once the newest block is this old (0 = off)
+    gas_panel_visible: bool, // Gas price panel (g key)
+    stake: Option<crate::stake::StakeDistribution>, // Latest epoch's validator stake summary
+    stake_previous: Option<crate::stake::StakeDistribution>,
+    stake_alert_pp: f64,
+    stake_panel_visible: bool, // Stake concentration panel (v key)
+    compact: bool,           // Compact mode (- key): short hashes, no when column, no padding
 
     // UI layout state
-    details_fullscreen: bool,                   // Spacebar toggle for 100% details view
+    zoomed_pane: Option<ZoomedPane>,            // `z`/Spacebar zoom (None = normal three-pane layout)
     fullscreen_content_type: FullscreenContentType, // What to show in fullscreen
     fullscreen_mode: FullscreenMode,            // Scroll (arrow keys scroll JSON) or Navigate (arrow keys move rows)
     details_viewport_height: u16,               // Actual visible height of details pane (set by UI layer)
 
     // Theme (single source of truth for all UI targets)
     theme: Theme,
+    json_format: JsonFormat, // Plain-text JSON indent / key order (details + copy)
+    system_themes: Option<SystemThemes>, // Some = follow OS dark/light appearance
 
     // Cached ratatui styles (invalidated when theme changes)
     #[cfg(feature = "native")]
@@ -301,35 +568,118 @@ impl App {
                 buf
             },
             details_viewport_lines: 32, // Sensible default, updated by renderer
+            details_goto: None,
             fps,
             fps_choices,
             keep_blocks,
             follow_blocks_latest: true, // Start in auto-follow mode
-            filter_query: default_filter,
+            filter_query: default_filter.clone(),
             filter_compiled,
+            filter_cache: FilterCache::default(),
+            filter_preview: FilterPreview::default(),
             input_mode: InputMode::Normal,
             search_query: String::new(),
             search_results: Vec::new(),
             search_selection: 0,
+            search_preview: DetailsBuffer::new(),
+            search_preview_hash: None,
+            search_preview_viewport_lines: 20, // Default estimate, updated by renderer
             marks_list: Vec::new(),
+            marks_trash: Vec::new(),
             marks_selection: 0,
+            risk_query: String::new(),
+            risk_results: Vec::new(),
+            risk_selection: 0,
+            account_feed: crate::account_feed::AccountFeed::default(),
+            account_feed_query: String::new(),
+            account_feed_selection: 0,
+            pin_board: crate::pin_board::PinBoard::default(),
+            board_marks: Vec::new(),
+            board_selection: 0,
+            board_note: None,
+            watches: crate::watch::WatchList::default(),
+            watch_selection: 0,
+            watch_webhook: None,
+            watch_hooks: Vec::new(),
+            watches_dirty: false,
+            exports: Vec::new(),
+            exports_selection: 0,
+            pending_export_rerun: None,
+            startup: None,
+            digest_target: None,
+            pending_digest: None,
+            digest: None,
+            digest_window: crate::digest::WINDOW_CHOICES[0],
+            digest_scroll: 0,
+            state_view: None,
+            pending_state: None,
+            history_browser: crate::history_browser::HistoryBrowser::new(),
+            holders_tab: None,
+            holders_tab_open: false,
+            holders_cache: crate::holders::HoldersCache::default(),
+            pending_holders: None,
+            telemetry: crate::telemetry::Telemetry::default(),
+            telemetry_scroll: 0,
             cached_blocks: HashMap::new(),
             cached_block_order: Vec::new(),
             loading_block: None,
             archival_fetch_tx,
+            endpoints: Endpoints::default(),
+            endpoint_draft: EndpointDraft::default(),
+            pending_endpoints: None,
+            sources: Vec::new(),
+            status_bar: crate::status_bar::StatusBar::default(),
+            sources_selection: 0,
+            sources_changed: false,
+            default_filter,
+            account_labels: std::collections::BTreeMap::new(),
+            related_for: None,
+            related: Vec::new(),
+            pending_related: None,
+            pending_timeline: None,
+            pending_report: None,
+            import_draft: String::new(),
+            pending_import: None,
+            collection: None,
+            timeline: None,
+            pending_gas_profile: None,
+            gas_profile: None,
+            stale_heights: HashSet::new(),
+            owned_accounts: None,
+            owned_only: false,
+            owned_new: HashSet::new(),
+            owned_new_until: None,
             live_updates_paused: false, // Start with live updates enabled
             back_slots: Vec::new(),
             back_anchor_height: None,
             back_next_request_at: None,
+            block_window_pending: None,
             back_slots_target: BACK_WINDOW,
+            blocks_rev: 0,
+            profiler_visible: false,
+            time_display: crate::time_display::TimeDisplay::default(),
             debug_log: Vec::new(),
             debug_visible: false, // Hidden by default
+            debug_log_file: None, // Embedders and tests never write files
             shortcuts_visible: false, // Hidden by default (Web/Tauri only for now)
-            toast_message: None,
-            details_fullscreen: false,                          // Normal view by default
+            toasts: ToastQueue::default(),
+            toast_history_visible: false,
+            gas_oracle: crate::gas_oracle::GasOracle::default(),
+            anomaly: crate::anomaly::AnomalyDetector::default(),
+            anomaly_incidents: false,
+            stale_after_secs: crate::source_health::DEFAULT_STALE_AFTER_SECS,
+            gas_panel_visible: false,
+            stake: None,
+            stake_previous: None,
+            stake_alert_pp: crate::stake::DEFAULT_STAKE_ALERT_PP,
+            stake_panel_visible: false,
+            compact: false,
+            zoomed_pane: None,                                  // Normal view by default
             fullscreen_content_type: FullscreenContentType::ParsedDetails, // Default to parsed view
             fullscreen_mode: FullscreenMode::Scroll,            // Scroll mode by default
             details_viewport_height: 20,                        // Default estimate, will be updated by UI
+            json_format: JsonFormat::default(),
+            system_themes: None,
             theme: Theme::default(),                            // Single source of truth for UI colors
             #[cfg(feature = "native")]
             rat_styles_cache: None, // Computed on first use
@@ -409,24 +759,65 @@ impl App {
     }
 
     /// Count how many transactions in a block match the current filter
+    ///
+    /// Served from the filter cache (possibly stale while a new filter is being
+    /// revalidated in `on_tick`); blocks not indexed yet are evaluated directly.
     fn count_matching_txs(&self, block: &BlockRow) -> usize {
         if filter::is_empty(&self.filter_compiled) {
             return block.transactions.len(); // No filter = all match
         }
+        if let Some(count) = self.filter_cache.count(block.height) {
+            return count;
+        }
+
+        self.block_filter_mask(block).into_iter().filter(|m| *m).count()
+    }
+
+    /// Filter result for every tx of a block, in order (parallel for large blocks on native)
+    fn block_filter_mask(&self, block: &BlockRow) -> Vec<bool> {
+        if filter::is_empty(&self.filter_compiled) {
+            return vec![true; block.transactions.len()];
+        }
+        self.filter_cache.block_matches(block, &self.filter_compiled)
+    }
+
+    /// Blocks whose filter counts are still being revalidated (0 = up to date)
+    pub fn filter_pending(&self) -> usize {
+        if filter::is_empty(&self.filter_compiled) {
+            return 0;
+        }
+        self.filter_cache
+            .pending(self.blocks.iter().chain(self.cached_blocks.values()))
+    }
 
-        block
-            .transactions
+    /// Incrementally refresh per-block filter counts, spending at most `budget` evaluations
+    fn revalidate_filter(&mut self, budget: usize) {
+        if filter::is_empty(&self.filter_compiled) {
+            return;
+        }
+        let held: std::collections::HashSet<u64> = self
+            .blocks
             .iter()
-            .filter(|tx| {
-                // Apply text filter
-                let v = json!({
-                    "hash": &tx.hash,
-                    "signer_id": tx.signer_id.as_deref().unwrap_or(""),
-                    "receiver_id": tx.receiver_id.as_deref().unwrap_or("")
-                });
-                tx_matches_filter(&v, &self.filter_compiled)
-            })
-            .count()
+            .map(|b| b.height)
+            .chain(self.cached_blocks.keys().copied())
+            .collect();
+        self.filter_cache.retain_heights(&held);
+        // Newest first so the visible top of the list settles first
+        let spent = self.filter_cache.revalidate(
+            self.blocks.iter().chain(self.cached_blocks.values()),
+            &self.filter_compiled,
+            budget,
+        );
+        if spent > 0 {
+            self.touch_blocks();
+        }
+    }
+
+    /// Filter changed: mark cached counts stale and do the first slice of work now
+    fn on_filter_changed(&mut self) {
+        self.filter_cache.invalidate();
+        self.touch_blocks();
+        self.revalidate_filter(FILTER_EVAL_BUDGET_PER_TICK);
     }
 
     /// Returns blocks that have at least one matching transaction
@@ -558,18 +949,9 @@ impl App {
             let filtered: Vec<TxLite> = b
                 .transactions
                 .iter()
-                .filter(|tx| {
-                    // Apply text filter - pass complete tx data for filtering
-                    // Note: actions omitted here since ActionSummary doesn't derive Serialize
-                    // TODO: Add Serialize to ActionSummary for full filtering support
-                    let v = json!({
-                        "hash": &tx.hash,
-                        "signer_id": tx.signer_id.as_deref().unwrap_or(""),
-                        "receiver_id": tx.receiver_id.as_deref().unwrap_or("")
-                    });
-                    tx_matches_filter(&v, &self.filter_compiled)
-                })
-                .cloned()
+                .zip(self.block_filter_mask(b))
+                .filter(|(_, pass)| *pass)
+                .map(|(tx, _)| tx.clone())
                 .collect();
             (filtered, self.sel_tx, total)
         } else {
@@ -596,7 +978,10 @@ impl App {
         self.debug_visible
     }
     pub fn details_fullscreen(&self) -> bool {
-        self.details_fullscreen
+        self.zoomed_pane == Some(ZoomedPane::Details)
+    }
+    pub fn zoomed_pane(&self) -> Option<ZoomedPane> {
+        self.zoomed_pane
     }
     pub fn fullscreen_content_type(&self) -> FullscreenContentType {
         self.fullscreen_content_type
@@ -617,6 +1002,8 @@ impl App {
             } else if is_loading {
                 // This specific block is being fetched from archival
                 format!("► Selected: Block #{} (loading...)", block.height)
+            } else if self.compact {
+                format!("► #{} ({} txs)", block.height, block.tx_count)
             } else {
                 // Manual selection mode: show block details with timestamp
                 format!(
@@ -655,7 +1042,7 @@ impl App {
                         if val.is_null() {
                             "Error: Block serialized to null".to_string()
                         } else {
-                            crate::json_pretty::pretty_safe(&val, 2, 100 * 1024)
+                            crate::json_pretty::pretty_safe(&val, self.json_format, 100 * 1024)
                         }
                     }
                     Err(e) => {
@@ -680,7 +1067,7 @@ impl App {
             if let Some(tx) = block.transactions.get(self.sel_tx) {
                 // Serialize with 100KB truncation to prevent UI freezing on massive transactions
                 let val = serde_json::to_value(tx).unwrap_or(serde_json::Value::Null);
-                return crate::json_pretty::pretty_safe(&val, 2, 100 * 1024);
+                return crate::json_pretty::pretty_safe(&val, self.json_format, 100 * 1024);
             }
         }
         "No transaction selected".to_string()
@@ -697,6 +1084,49 @@ impl App {
         }
     }
 
+    /// Follow the OS appearance with this dark/light pair (`None` = fixed theme)
+    pub fn set_follow_system_theme(&mut self, themes: Option<SystemThemes>) {
+        self.system_themes = themes;
+    }
+
+    pub fn follows_system_theme(&self) -> bool {
+        self.system_themes.is_some()
+    }
+
+    /// Switch to the next palette in `ThemeVariant::ALL` order (C); stops following the OS
+    pub fn cycle_theme(&mut self) {
+        let next = ThemeVariant::of(&self.theme)
+            .map(ThemeVariant::next)
+            .unwrap_or_default();
+        self.system_themes = None;
+        self.set_theme(Theme::for_variant(next));
+        self.push_toast(ToastLevel::Info, format!("Theme: {next}"));
+    }
+
+    /// OS switched dark/light; applies the matching variant when following the system
+    pub fn set_system_appearance(&mut self, appearance: Appearance) {
+        if let Some(themes) = self.system_themes {
+            let variant = themes.variant_for(appearance);
+            self.log_debug(format!("[theme] system appearance {appearance:?} -> {variant}"));
+            self.set_theme(Theme::for_variant(variant));
+        }
+    }
+
+    pub fn json_format(&self) -> JsonFormat {
+        self.json_format
+    }
+
+    /// Set plain-text JSON formatting; reformats the current details if they are JSON
+    pub fn set_json_format(&mut self, fmt: JsonFormat) {
+        if self.json_format == fmt {
+            return;
+        }
+        self.json_format = fmt;
+        if let Ok(v) = serde_json::from_str::<serde_json::Value>(self.details_buf.full_text()) {
+            self.set_details_json(pretty(&v, fmt));
+        }
+    }
+
     /// Get cached ratatui styles for current theme (computed on first use, invalidated on theme change)
     #[cfg(feature = "native")]
     pub fn rat_styles(&mut self) -> ratatui_helpers::Styles {
@@ -723,21 +1153,150 @@ impl App {
         self.details_viewport_height = height;
     }
 
-    /// Show a toast notification for 2 seconds
+    /// Show an info toast (level default duration)
     pub fn show_toast(&mut self, msg: String) {
-        self.toast_message = Some((msg, Instant::now()));
+        self.push_toast(ToastLevel::Info, msg);
+    }
+
+    /// Queue a toast with the level's default duration
+    pub fn push_toast(&mut self, level: ToastLevel, msg: String) {
+        self.push_toast_for(level, msg, level.duration());
+    }
+
+    /// Queue a toast with an explicit duration
+    pub fn push_toast_for(&mut self, level: ToastLevel, msg: String, duration: Duration) {
+        self.toasts.push(level, msg, duration, Instant::now());
     }
 
-    /// Get current toast message if still active (visible for 2 seconds)
+    /// Newest visible toast message (single-line surfaces: footer, snapshot `toast`)
     pub fn toast_message(&self) -> Option<&str> {
-        const TOAST_DURATION: Duration = Duration::from_secs(2);
-        self.toast_message.as_ref().and_then(|(msg, time)| {
-            if time.elapsed() < TOAST_DURATION {
-                Some(msg.as_str())
-            } else {
-                None
+        self.toasts
+            .visible(Instant::now())
+            .last()
+            .map(|t| t.message.as_str())
+    }
+
+    /// Visible toasts, oldest first (stacked rendering)
+    pub fn visible_toasts(&self) -> Vec<&Toast> {
+        self.toasts.visible(Instant::now()).collect()
+    }
+
+    /// Toasts waiting for a visible slot
+    pub fn waiting_toasts(&self) -> usize {
+        self.toasts.waiting()
+    }
+
+    /// Past toasts, newest first
+    pub fn toast_history(&self) -> Vec<&Toast> {
+        self.toasts.history().collect()
+    }
+
+    pub fn toast_history_visible(&self) -> bool {
+        self.toast_history_visible
+    }
+
+    /// Toggle the toast history overlay (n key)
+    pub fn toggle_toast_history(&mut self) {
+        self.toast_history_visible = !self.toast_history_visible;
+        if self.toast_history_visible {
+            self.count_feature("view.toast_history");
+            // Recalling history implies the live stack has been seen
+            self.toasts.dismiss_all();
+        }
+    }
+
+    pub fn hide_toast_history(&mut self) {
+        self.toast_history_visible = false;
+    }
+
+    // ----- Gas price panel -----
+    /// Rolling window size and spike threshold (GAS_WINDOW_BLOCKS / GAS_SPIKE_MULTIPLE)
+    pub fn set_gas_policy(&mut self, window: usize, spike_multiple: f64) {
+        self.gas_oracle = crate::gas_oracle::GasOracle::new(window, spike_multiple);
+    }
+
+    /// Anomaly threshold in σ (0 = off) and whether finished anomalies become
+    /// pin board incidents (ANOMALY_SIGMA / ANOMALY_INCIDENTS)
+    pub fn set_anomaly_policy(&mut self, sigma: f64, incidents: bool) {
+        self.anomaly.set_sigma(sigma);
+        self.anomaly_incidents = incidents;
+    }
+
+    /// Record a live block with the anomaly detector: warn when one starts,
+    /// file an incident (pin board note with its range) when one ends
+    fn check_anomalies(&mut self, block: &BlockRow) {
+        let update = self.anomaly.record(block);
+        for anomaly in update.started {
+            self.log_debug(format!("[anomaly] #{}: {}", anomaly.height, anomaly.message()));
+            self.push_toast(ToastLevel::Warn, format!("#{}: {}", anomaly.height, anomaly.message()));
+        }
+        for incident in update.ended {
+            self.log_debug(format!("[anomaly] {}", incident.title()));
+            if self.anomaly_incidents {
+                self.pin_board.add_note(
+                    format!("⚠ {} · history range {}", incident.title(), incident.range()),
+                    Some(incident.from_height),
+                    None,
+                    chrono::Utc::now().timestamp_millis(),
+                );
+                self.show_toast(format!("Incident pinned: {}", incident.title()));
             }
-        })
+        }
+    }
+
+    pub fn gas_guidance(&self) -> Option<crate::gas_oracle::GasGuidance> {
+        self.gas_oracle.guidance()
+    }
+
+    pub fn gas_panel_visible(&self) -> bool {
+        self.gas_panel_visible
+    }
+
+    /// Toggle the gas price panel (g key)
+    pub fn toggle_gas_panel(&mut self) {
+        self.gas_panel_visible = !self.gas_panel_visible;
+        if self.gas_panel_visible {
+            self.count_feature("view.gas_panel");
+        }
+    }
+
+    // ----- Stake concentration panel -----
+    /// Top-10 share change (percentage points) that raises an alert (STAKE_ALERT_PP)
+    pub fn set_stake_alert_pp(&mut self, alert_pp: f64) {
+        self.stake_alert_pp = alert_pp;
+    }
+
+    pub fn stake_distribution(&self) -> Option<&crate::stake::StakeDistribution> {
+        self.stake.as_ref()
+    }
+
+    pub fn stake_previous(&self) -> Option<&crate::stake::StakeDistribution> {
+        self.stake_previous.as_ref()
+    }
+
+    pub fn stake_panel_visible(&self) -> bool {
+        self.stake_panel_visible
+    }
+
+    /// Toggle the stake concentration panel (v key)
+    pub fn toggle_stake_panel(&mut self) {
+        self.stake_panel_visible = !self.stake_panel_visible;
+        if self.stake_panel_visible {
+            self.count_feature("view.stake_panel");
+        }
+    }
+
+    pub fn compact(&self) -> bool {
+        self.compact
+    }
+
+    /// Toggle compact mode (- key) for small panes
+    pub fn toggle_compact(&mut self) {
+        self.compact = !self.compact;
+        if self.compact {
+            self.count_feature("view.compact");
+        }
+        self.show_toast(format!("Compact mode {}", if self.compact { "on" } else { "off" }));
     }
 
     // ----- knobs -----
@@ -754,20 +1313,91 @@ impl App {
         self.fps = self.fps_choices[idx];
     }
 
+    // ----- config file (live reload) -----
+    /// Apply the changed live keys of a config file reload (already resolved
+    /// against CLI / env and start-up values); the default filter only
+    /// replaces the current one when the user hasn't edited it
+    pub fn apply_config_reload(&mut self, reload: crate::config_file::ConfigReload) {
+        let s = reload.settings;
+        for key in &reload.applied {
+            match key.as_str() {
+                "theme" => self.set_theme(Theme::for_variant(s.theme.unwrap_or_default())),
+                "render_fps_choices" => {
+                    if let Some(choices) = s.render_fps_choices.clone() {
+                        self.fps_choices = choices;
+                    }
+                }
+                "render_fps" => {
+                    if let Some(fps) = s.render_fps {
+                        self.fps = fps;
+                    }
+                }
+                "keep_blocks" => {
+                    if let Some(keep) = s.keep_blocks {
+                        self.keep_blocks = keep;
+                        self.blocks.truncate(keep);
+                        self.touch_blocks();
+                    }
+                }
+                "default_filter" => {
+                    let next = s.default_filter.clone().unwrap_or_default();
+                    if self.filter_query == self.default_filter {
+                        self.filter_query = next.clone();
+                        self.recompile_filter();
+                        self.validate_and_refresh_tx(BlockChangeReason::FilterChange);
+                    }
+                    self.default_filter = next;
+                }
+                "labels" => self.account_labels = s.labels.clone(),
+                _ => {}
+            }
+        }
+        if !reload.applied.is_empty() {
+            self.log_debug(format!("[config] applied: {}", reload.applied.join(", ")));
+            self.push_toast(
+                ToastLevel::Success,
+                format!("Config reloaded: {}", reload.applied.join(", ")),
+            );
+        }
+        if !reload.ignored.is_empty() {
+            self.log_debug(format!("[config] set via CLI/env, file change ignored: {}", reload.ignored.join(", ")));
+            self.push_toast(
+                ToastLevel::Info,
+                format!("Config: kept CLI/env value for {}", reload.ignored.join(", ")),
+            );
+        }
+        if !reload.restart.is_empty() {
+            log::warn!("config keys changed that need a restart: {}", reload.restart.join(", "));
+            self.log_debug(format!("[config] restart required: {}", reload.restart.join(", ")));
+            self.push_toast(
+                ToastLevel::Warn,
+                format!("Restart to apply: {}", reload.restart.join(", ")),
+            );
+        }
+    }
+
+    /// Label from the config file's `[labels]` table, else the account itself
+    pub fn account_label<'a>(&'a self, account: &'a str) -> &'a str {
+        self.account_labels
+            .get(&account.to_lowercase())
+            .map_or(account, String::as_str)
+    }
+
+    /// Also append debug lines to this file (off by default)
+    pub fn set_debug_log_file(&mut self, path: Option<std::path::PathBuf>) {
+        self.debug_log_file = path;
+    }
+
     pub fn log_debug(&mut self, msg: String) {
         const MAX_LOG_ENTRIES: usize = 50;
 
-        // Write to file for debugging (native only - WASM doesn't have filesystem)
+        // Write to file for debugging (only when the native binary asked for it)
         #[cfg(not(target_arch = "wasm32"))]
-        {
+        if let Some(path) = &self.debug_log_file {
             use std::fs::OpenOptions;
             use std::io::Write;
             let timestamp = chrono::Utc::now().format("%H:%M:%S%.3f");
-            if let Ok(mut file) = OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open("nearx_debug.log")
-            {
+            if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(path) {
                 let _ = writeln!(file, "[{timestamp}] {msg}");
             }
         }
@@ -810,6 +1440,7 @@ impl App {
                 {
                     e.insert(block.clone());
                     cached_count += 1;
+                    self.touch_blocks();
                 }
             }
         }
@@ -819,6 +1450,7 @@ impl App {
             if let Some(old_height) = self.cached_block_order.first().copied() {
                 self.cached_block_order.remove(0);
                 self.cached_blocks.remove(&old_height);
+                self.touch_blocks();
             }
         }
 
@@ -915,6 +1547,57 @@ impl App {
         self.cache_block_with_context(center_height);
     }
 
+    /// Mark the Blocks / Txs snapshot rows stale (see `snapshot_cache`)
+    fn touch_blocks(&mut self) {
+        self.blocks_rev = self.blocks_rev.wrapping_add(1);
+    }
+
+    /// Revision of everything the Blocks / Txs rows are built from, minus the selection
+    pub fn blocks_rev(&self) -> u64 {
+        self.blocks_rev
+    }
+
+    /// Toggle the snapshot profiler overlay (P)
+    pub fn toggle_profiler(&mut self) {
+        self.profiler_visible = !self.profiler_visible;
+    }
+
+    pub fn profiler_visible(&self) -> bool {
+        self.profiler_visible
+    }
+
+    pub fn set_time_display(&mut self, times: crate::time_display::TimeDisplay) {
+        self.time_display = times;
+        self.touch_blocks();
+    }
+
+    pub fn time_display(&self) -> &crate::time_display::TimeDisplay {
+        &self.time_display
+    }
+
+    /// Switch the timezone every view uses (T): UTC → local → configured offset
+    pub fn cycle_timezone(&mut self) {
+        self.time_display.cycle();
+        self.touch_blocks();
+        if let Some((tx, target)) = self.related_for.take() {
+            let top = self.details_buf.current_scroll_line();
+            self.render_tx_details(&tx, target.height);
+            self.details_buf.scroll_to_line(top, self.details_viewport_lines);
+            self.related_for = Some((tx, target));
+        }
+        let msg = format!("Times in {}", self.time_display.zone.label());
+        self.push_toast(ToastLevel::Info, msg);
+    }
+
+    /// Block row time in the current zone / format (the source's text when it has no timestamp)
+    pub fn block_when(&self, block: &BlockRow) -> String {
+        if block.timestamp > 0 {
+            self.time_display.at(block.timestamp as i64)
+        } else {
+            block.when.clone()
+        }
+    }
+
     /// Toggle debug panel visibility (Ctrl+D)
     pub fn toggle_debug_panel(&mut self) {
         self.debug_visible = !self.debug_visible;
@@ -954,9 +1637,9 @@ impl App {
 
     /// Toggle details fullscreen mode (Spacebar - pane-aware)
     pub fn toggle_details_fullscreen(&mut self) {
-        if self.details_fullscreen {
+        if self.details_fullscreen() {
             // Exit fullscreen - always return to parsed details view and reset to Scroll mode
-            self.details_fullscreen = false;
+            self.zoomed_pane = None;
             self.fullscreen_content_type = FullscreenContentType::ParsedDetails;
             self.fullscreen_mode = FullscreenMode::Scroll;
             self.log_debug("Exited fullscreen, back to parsed details".to_string());
@@ -968,7 +1651,7 @@ impl App {
             }
         } else {
             // Enter fullscreen - content depends on which pane is focused, start in Scroll mode
-            self.details_fullscreen = true;
+            self.zoomed_pane = Some(ZoomedPane::Details);
             self.fullscreen_mode = FullscreenMode::Scroll;
             self.fullscreen_content_type = match self.pane {
                 0 => FullscreenContentType::BlockRawJson,       // Blocks pane
@@ -1005,6 +1688,26 @@ impl App {
         }
     }
 
+    /// Toggle zoom of the focused pane (`z`). Details zoom is the Spacebar fullscreen.
+    pub fn toggle_zoom(&mut self) {
+        self.count_feature("view.zoom");
+        match self.zoomed_pane {
+            Some(ZoomedPane::Details) => self.toggle_details_fullscreen(),
+            Some(zoomed) => {
+                self.zoomed_pane = None;
+                self.log_debug(format!("Restored layout from {} zoom", zoomed.name()));
+            }
+            None => match self.pane {
+                0 | 1 => {
+                    let zoomed = if self.pane == 0 { ZoomedPane::Blocks } else { ZoomedPane::Txs };
+                    self.zoomed_pane = Some(zoomed);
+                    self.log_debug(format!("Zoomed {} pane", zoomed.name()));
+                }
+                _ => self.toggle_details_fullscreen(),
+            },
+        }
+    }
+
     /// Toggle between Scroll and Navigate modes in fullscreen (Tab key)
     pub fn toggle_fullscreen_mode(&mut self) {
         self.fullscreen_mode = match self.fullscreen_mode {
@@ -1032,27 +1735,161 @@ impl App {
     // ----- filter methods -----
     pub fn start_filter(&mut self) {
         self.input_mode = InputMode::Filter;
+        self.refresh_filter_preview();
     }
 
     pub fn clear_filter(&mut self) {
         self.filter_query.clear();
-        self.filter_compiled = CompiledFilter::default();
+        self.recompile_filter();
         self.input_mode = InputMode::Normal;
         self.validate_and_refresh_tx(BlockChangeReason::FilterChange); // Try to preserve tx
     }
 
     pub fn apply_filter(&mut self) {
-        self.filter_compiled = compile_filter(&self.filter_query);
+        self.recompile_filter();
+        for field in self.filter_compiled.fields_used() {
+            self.count_feature(crate::telemetry::filter_counter(field));
+        }
         self.input_mode = InputMode::Normal;
         self.validate_and_refresh_tx(BlockChangeReason::FilterChange); // Try to preserve tx
     }
 
+    /// Query text + owned-only restriction -> compiled filter (cached counts go stale)
+    fn recompile_filter(&mut self) {
+        self.filter_compiled = self.compile_query(&self.filter_query);
+        self.on_filter_changed();
+    }
+
+    /// Compile query text, adding the owned-only restriction when it's on
+    fn compile_query(&self, query: &str) -> CompiledFilter {
+        let mut compiled = compile_filter(query);
+        if self.owned_only {
+            let mut owned: Vec<String> = self.owned_accounts.iter().flatten().cloned().collect();
+            owned.sort();
+            compiled.owned = owned;
+        }
+        compiled
+    }
+
+    /// Examples for the query being typed (None outside Filter mode)
+    pub fn filter_preview(&self) -> Option<&FilterPreview> {
+        (self.input_mode == InputMode::Filter).then_some(&self.filter_preview)
+    }
+
+    /// Re-sample the buffered blocks against the draft query (budgeted, newest first)
+    fn refresh_filter_preview(&mut self) {
+        let draft = self.compile_query(&self.filter_query);
+        let samples = self.filter_cache.sample(
+            &self.blocks,
+            &draft,
+            FILTER_PREVIEW_SAMPLES,
+            FILTER_EVAL_BUDGET_PER_TICK,
+        );
+        let lookup = |(height, idx): (u64, usize)| {
+            let block = self.blocks.iter().find(|b| b.height == height)?;
+            Some((height, block.transactions.get(idx)?.clone()))
+        };
+        self.filter_preview = FilterPreview {
+            matching: samples.matching.into_iter().filter_map(lookup).collect(),
+            other: samples.other.into_iter().filter_map(lookup).collect(),
+            scanned: samples.scanned,
+            truncated: samples.truncated,
+        };
+    }
+
+    // ----- Owned accounts -----
+    /// New account set from the credentials watcher. After the initial scan,
+    /// additions are announced and highlighted, and the owned-only filter is
+    /// recomputed so the visible lists follow the change.
+    pub fn set_owned_accounts(&mut self, accounts: HashSet<String>) {
+        let now_empty = accounts.is_empty();
+        let (mut added, mut removed): (Vec<String>, Vec<String>) = match &self.owned_accounts {
+            Some(previous) => (
+                accounts.difference(previous).cloned().collect(),
+                previous.difference(&accounts).cloned().collect(),
+            ),
+            None => (Vec::new(), Vec::new()), // Initial scan: nothing to diff against
+        };
+        self.owned_accounts = Some(accounts);
+        if added.is_empty() && removed.is_empty() {
+            return;
+        }
+        added.sort();
+        removed.sort();
+
+        if !added.is_empty() {
+            self.push_toast(
+                ToastLevel::Success,
+                format!("detected new account key: {}", added.join(", ")),
+            );
+            self.owned_new.extend(added);
+            self.owned_new_until = Some(Instant::now() + OWNED_HIGHLIGHT);
+        }
+        if !removed.is_empty() {
+            for account in &removed {
+                self.owned_new.remove(account);
+            }
+            self.push_toast(
+                ToastLevel::Info,
+                format!("account key removed: {}", removed.join(", ")),
+            );
+        }
+
+        if self.owned_only {
+            if now_empty {
+                self.owned_only = false;
+                self.push_toast(ToastLevel::Warn, "Owned filter off: no account keys left".to_string());
+            }
+            self.recompile_filter();
+            self.validate_and_refresh_tx(BlockChangeReason::FilterChange);
+        }
+    }
+
+    pub fn owned_only(&self) -> bool {
+        self.owned_only
+    }
+
+    /// Toggle "only txs involving my accounts" (Ctrl+U)
+    pub fn toggle_owned_only(&mut self) {
+        let count = self.owned_accounts.as_ref().map_or(0, |a| a.len());
+        if !self.owned_only && count == 0 {
+            self.push_toast(ToastLevel::Warn, "No account keys found in credentials dir".to_string());
+            return;
+        }
+        self.owned_only = !self.owned_only;
+        if self.owned_only {
+            self.count_feature("filter.owned_only");
+        }
+        self.recompile_filter();
+        self.validate_and_refresh_tx(BlockChangeReason::FilterChange);
+        self.show_toast(if self.owned_only {
+            format!("Owned filter on ({count} accounts)")
+        } else {
+            "Owned filter off".to_string()
+        });
+    }
+
+    /// Whether a tx involves an account whose key appeared mid-session (highlighted)
+    pub fn tx_involves_new_owned(&self, tx: &TxLite) -> bool {
+        !self.owned_new.is_empty()
+            && [&tx.signer_id, &tx.receiver_id]
+                .into_iter()
+                .flatten()
+                .any(|a| self.owned_new.contains(&a.to_lowercase()))
+    }
+
+    pub fn block_involves_new_owned(&self, block: &BlockRow) -> bool {
+        !self.owned_new.is_empty() && block.transactions.iter().any(|t| self.tx_involves_new_owned(t))
+    }
+
     pub fn filter_add_char(&mut self, ch: char) {
         self.filter_query.push(ch);
+        self.refresh_filter_preview();
     }
 
     pub fn filter_backspace(&mut self) {
         self.filter_query.pop();
+        self.refresh_filter_preview();
     }
 
     // ----- copy functionality -----
@@ -1067,6 +1904,7 @@ impl App {
     /// Move focus to next pane (circular: 0→1→2→0)
     pub fn next_pane(&mut self) {
         self.pane = (self.pane + 1) % 3;
+        self.count_pane();
         self.log_debug(format!("Tab -> pane={}", self.pane));
     }
 
@@ -1075,13 +1913,19 @@ impl App {
         // Backward navigation: subtract 1 with wrap-around
         // (pane - 1 + 3) % 3 ensures we don't underflow (e.g., 0-1 = -1)
         self.pane = (self.pane + 3 - 1) % 3;
+        self.count_pane();
         self.log_debug(format!("BackTab -> pane={}", self.pane));
     }
 
     /// Set pane directly (used by deep link router)
     pub fn set_pane_direct(&mut self, pane: usize) {
         if pane < 3 {
+            // A list zoom only makes sense for the pane it was opened on
+            if pane != self.pane && matches!(self.zoomed_pane, Some(ZoomedPane::Blocks | ZoomedPane::Txs)) {
+                self.zoomed_pane = None;
+            }
             self.pane = pane;
+            self.count_pane();
             self.log_debug(format!("DeepLink -> pane={}", self.pane));
         }
     }
@@ -1130,6 +1974,20 @@ impl App {
         }
     }
 
+    /// Apply a route, then narrow its filter with the link's `?filter=` terms
+    pub fn apply_route_link(&mut self, link: &crate::router::RouteLink) {
+        self.apply_route(&link.route);
+        if let Some(extra) = &link.filter {
+            self.filter_query = if self.filter_query.trim().is_empty() {
+                extra.clone()
+            } else {
+                format!("{} {extra}", self.filter_query.trim())
+            };
+            self.apply_filter();
+            self.log_debug(format!("Route filter: {extra}"));
+        }
+    }
+
     /// Refresh tx details if current selection is still valid, otherwise reset
     fn validate_and_refresh_tx(&mut self, reason: BlockChangeReason) {
         let (txs, _, _) = self.txs();
@@ -1163,7 +2021,7 @@ impl App {
         }
 
         // If in fullscreen mode showing block JSON, update it when block changes
-        if self.details_fullscreen && self.fullscreen_content_type == FullscreenContentType::BlockRawJson {
+        if self.details_fullscreen() && self.fullscreen_content_type == FullscreenContentType::BlockRawJson {
             let raw = self.get_raw_block_json();
             self.set_details_json(raw);
         }
@@ -1172,7 +2030,7 @@ impl App {
     /// Handle Up arrow key - behavior depends on which pane is focused
     pub fn up(&mut self) {
         // Fullscreen Navigate mode: route to appropriate pane based on content type
-        if self.details_fullscreen && self.fullscreen_mode == FullscreenMode::Navigate {
+        if self.details_fullscreen() && self.fullscreen_mode == FullscreenMode::Navigate {
             match self.fullscreen_content_type {
                 FullscreenContentType::BlockRawJson => {
                     // Navigate blocks (temporarily switch pane logic)
@@ -1236,8 +2094,7 @@ impl App {
                         if self.is_block_available(new_height) {
                             self.sel_block_height = Some(new_height);
                             self.follow_blocks_latest = false; // User navigation disables auto-follow
-                            self.cache_block_with_context(new_height);
-                            self.ensure_block_window_by_chain(new_height); // Chain-walk backfill
+                            self.defer_block_window(new_height); // Backfill + cache once navigation settles
                             self.validate_and_refresh_tx(BlockChangeReason::ManualNav);
                             self.log_debug(format!("Blocks UP -> #{new_height}"));
                         } else {
@@ -1276,7 +2133,7 @@ impl App {
     /// Handle Down arrow key - behavior depends on which pane is focused
     pub fn down(&mut self) {
         // Fullscreen Navigate mode: route to appropriate pane based on content type
-        if self.details_fullscreen && self.fullscreen_mode == FullscreenMode::Navigate {
+        if self.details_fullscreen() && self.fullscreen_mode == FullscreenMode::Navigate {
             match self.fullscreen_content_type {
                 FullscreenContentType::BlockRawJson => {
                     // Navigate blocks (temporarily switch pane logic)
@@ -1353,8 +2210,7 @@ impl App {
                         if self.is_block_available(new_height) {
                             self.sel_block_height = Some(new_height);
                             self.follow_blocks_latest = false; // User navigation disables auto-follow
-                            self.cache_block_with_context(new_height);
-                            self.ensure_block_window_by_chain(new_height); // Chain-walk backfill
+                            self.defer_block_window(new_height); // Backfill + cache once navigation settles
                             self.validate_and_refresh_tx(BlockChangeReason::ManualNav);
                             self.log_debug(format!("Blocks DOWN -> #{new_height}"));
                         } else {
@@ -1429,6 +2285,7 @@ impl App {
 
                     // Reset backwards window so it re-anchors to the new selection.
                     self.back_slots.clear();
+                    self.touch_blocks();
                     self.back_anchor_height = None;
                     self.back_next_request_at = None;
 
@@ -1477,7 +2334,7 @@ impl App {
                         let new_height = nav_list[new_idx];
                         self.sel_block_height = Some(new_height); // Lock to specific height
                         self.follow_blocks_latest = false; // User navigation disables auto-follow
-                        self.ensure_block_window_by_chain(new_height); // Trigger archival backfill
+                        self.defer_block_window(new_height); // Archival backfill once navigation settles
                         self.validate_and_refresh_tx(BlockChangeReason::ManualNav);
 
                         self.log_debug(format!(
@@ -1560,10 +2417,12 @@ impl App {
             // Only request if not already loading this block
             if self.loading_block != Some(height) {
                 self.loading_block = Some(height);
+                self.touch_blocks();
                 self.log_debug(format!("Requesting archival fetch for block #{height}"));
                 if let Err(e) = tx.send(height) {
                     self.log_debug(format!("Failed to send archival fetch request: {e}"));
                     self.loading_block = None;
+                    self.touch_blocks();
                 }
             }
         }
@@ -1571,30 +2430,49 @@ impl App {
 
 
     pub fn select_tx(&mut self) {
-        if let Some(b) = self.current_block() {
+        if let Some(height) = self.current_block().map(|b| b.height) {
             let (filtered_txs, _, _) = self.txs();
             if let Some(tx) = filtered_txs.get(self.sel_tx) {
-                // Show raw transaction JSON (full data)
-                let val = serde_json::to_value(tx).unwrap_or(serde_json::Value::Null);
-                let raw_json = crate::json_pretty::pretty_safe(&val, 2, 100 * 1024);
-                self.set_details_json(raw_json);
+                self.show_tx(tx.clone(), height);
             }
         }
     }
 
+    /// Show a tx's raw JSON (full data) with related txs from the buffer;
+    /// History's candidates are merged in when the runtime answers
+    fn show_tx(&mut self, tx: TxLite, height: u64) {
+        let target = crate::related::Candidate::from_tx(&tx, height);
+        self.related = crate::related::rank(&target, self.nearby_candidates(height));
+        self.pending_related = Some(crate::related::RelatedQuery::for_target(&target));
+        self.render_tx_details(&tx, height);
+        self.related_for = Some((tx, target));
+    }
+
+    fn render_tx_details(&mut self, tx: &TxLite, height: u64) {
+        let mut val = tx_details_value(tx);
+        let block_time = self
+            .block_by_height(height)
+            .filter(|b| b.timestamp > 0)
+            .map(|b| self.time_display.at(b.timestamp as i64));
+        if let (Some(time), Some(obj)) = (block_time, val.as_object_mut()) {
+            obj.insert("block_time".to_string(), time.into());
+        }
+        if let (false, Some(obj)) = (self.related.is_empty(), val.as_object_mut()) {
+            obj.insert("related".to_string(), crate::related::to_json(&self.related));
+        }
+        let raw_json = crate::json_pretty::pretty_safe(&val, self.json_format, 100 * 1024);
+        self.set_details_json(raw_json);
+    }
+
     /// Select first transaction, bypassing filter (for first block UX)
     pub fn select_tx_bypass_filter(&mut self) {
         // Clone the data we need before mutating self
-        let block_data = self.current_block().map(|b| (b.height, b.transactions.clone()));
+        let block_data = self.current_block().map(|b| (b.height, b.transactions.first().cloned()));
 
-        if let Some((block_height, all_txs)) = block_data {
-            if let Some(tx) = all_txs.first() {
+        if let Some((block_height, first_tx)) = block_data {
+            if let Some(tx) = first_tx {
                 self.sel_tx = 0;
-
-                // Show raw transaction JSON (full data)
-                let val = serde_json::to_value(tx).unwrap_or(serde_json::Value::Null);
-                let raw_json = crate::json_pretty::pretty_safe(&val, 2, 100 * 1024);
-                self.set_details_json(raw_json);
+                self.show_tx(tx, block_height);
             } else {
                 self.set_details_json("No transactions".to_string());
             }
@@ -1605,7 +2483,36 @@ impl App {
 
     /// Called periodically from event loop to throttle backward chain-walk
     pub fn on_tick(&mut self, now: Instant) {
+        self.toasts.tick(now);
+        if self.owned_new_until.is_some_and(|until| now >= until) {
+            self.owned_new.clear();
+            self.owned_new_until = None;
+        }
+        self.maybe_fill_block_window(now);
         self.maybe_step_backchain(now);
+        self.revalidate_filter(FILTER_EVAL_BUDGET_PER_TICK);
+    }
+
+    /// Fill the window around a block once navigation settles (key repeat moves
+    /// the selection instantly but would otherwise request a window per step)
+    fn defer_block_window(&mut self, height: u64) {
+        self.block_window_pending = Some((height, Instant::now()));
+    }
+
+    fn maybe_fill_block_window(&mut self, now: Instant) {
+        use crate::constants::app::NAV_SETTLE_MS;
+
+        let Some((height, moved_at)) = self.block_window_pending else {
+            return;
+        };
+        if now.saturating_duration_since(moved_at) < Duration::from_millis(NAV_SETTLE_MS) {
+            return;
+        }
+        self.block_window_pending = None;
+        // Selection moved on (or went back to live) without settling here
+        if self.sel_block_height == Some(height) {
+            self.ensure_block_window_by_chain(height); // Chain-walk backfill + context cache
+        }
     }
 
     fn maybe_step_backchain(&mut self, now: Instant) {
@@ -1614,7 +2521,10 @@ impl App {
             if let Some(anchor) = self.current_block() {
                 (anchor.height, anchor.prev_height, anchor.prev_hash.clone())
             } else {
-                self.back_slots.clear();
+                if !self.back_slots.is_empty() {
+                    self.back_slots.clear();
+                    self.touch_blocks();
+                }
                 self.back_anchor_height = None;
                 self.back_next_request_at = None;
                 return;
@@ -1624,6 +2534,7 @@ impl App {
         if self.back_anchor_height != Some(anchor_height) {
             self.back_anchor_height = Some(anchor_height);
             self.back_slots.clear();
+            self.touch_blocks();
             self.back_next_request_at = None;
 
             if let (Some(prev_height), Some(ref prev_hash)) =
@@ -1682,6 +2593,7 @@ impl App {
             if !self.back_slots.iter().any(|s| s.height == height)
                 && self.back_slots.len() < self.back_slots_target
             {
+                self.touch_blocks();
                 self.back_slots.push(BackSlot {
                     height,
                     hash,
@@ -1706,6 +2618,8 @@ impl App {
                     tx_count: 0,
                     when: "".into(),
                     transactions: vec![],
+                    gas_price: None,
+                    gas_used: None,
                 });
             }
             AppEvent::FromWs(WsPayload::Tx {
@@ -1715,14 +2629,125 @@ impl App {
                 if let Some(t) = data {
                     // For WS summary, show pretty-formatted JSON
                     let raw = serde_json::to_value(&t).unwrap_or(serde_json::json!({}));
-                    self.set_details_json(pretty(&raw, 2));
+                    self.set_details_json(pretty(&raw, self.json_format));
+                }
+            }
+            AppEvent::SystemAppearance(appearance) => self.set_system_appearance(appearance),
+            AppEvent::Control(action) => crate::ui_snapshot::apply_ui_action(self, action),
+            AppEvent::RelatedTxs { hash, txs } => self.show_related(hash, txs),
+            AppEvent::TxTimeline { hash, result } => self.show_timeline(hash, result),
+            AppEvent::GasProfile { hash, result } => self.show_gas_profile(hash, result),
+            AppEvent::ContractDigest(digest) => self.show_digest(digest),
+            AppEvent::StartupLoaded(load) => self.apply_startup_load(*load),
+            AppEvent::ExportWritten(result) => match result {
+                Ok(record) => self.export_recorded(record),
+                Err(e) => self.push_toast(ToastLevel::Error, format!("Export failed: {e}")),
+            },
+            AppEvent::CollectionResolved(resolution) => self.collection_resolved(resolution),
+            AppEvent::ContractState { account, prefix, result } => self.show_state(account, prefix, result),
+            AppEvent::TokenHolders { token, account, result } => self.show_holders(token, account, result),
+            AppEvent::OwnedAccounts(accounts) => self.set_owned_accounts(accounts),
+            AppEvent::SourceStatus { kind, report } => self.record_source_status(kind, report),
+            AppEvent::ConfigReload(Ok(reload)) => self.apply_config_reload(reload),
+            AppEvent::ConfigReload(Err(e)) => {
+                self.log_debug(format!("[config] reload failed: {e}"));
+                self.push_toast(ToastLevel::Error, format!("Config not reloaded: {e}"));
+            }
+            AppEvent::TxAnalysis(analysis) => {
+                self.log_debug(format!(
+                    "[tx-analyzer] {} risk {} [{}]",
+                    analysis.hash,
+                    analysis.risk_score,
+                    analysis.patterns_detected.join(",")
+                ));
+                if analysis.risk_score > crate::types::HIGH_RISK_SCORE {
+                    self.push_toast(
+                        ToastLevel::Warn,
+                        format!(
+                            "High-risk tx {} (score {}) · Ctrl+R to browse",
+                            &analysis.hash[..analysis.hash.len().min(8)],
+                            analysis.risk_score
+                        ),
+                    );
+                }
+            }
+            AppEvent::PluginAlert(alert) => {
+                self.log_debug(format!(
+                    "[plugin:{}] {:?}: {}",
+                    alert.plugin_id, alert.severity, alert.message
+                ));
+                // Latest warning per plugin stays in the footer for a minute
+                if alert.severity >= AlertSeverity::Warning {
+                    let tone = match alert.severity {
+                        AlertSeverity::Critical => Tone::Error,
+                        _ => Tone::Warn,
+                    };
+                    self.set_status_segment(
+                        StatusSegment::new(
+                            format!("plugin:{}", alert.plugin_id),
+                            format!("⚠ {}", alert.plugin_id),
+                        )
+                        .tone(tone)
+                        .tooltip(alert.message.clone()),
+                        Some(Duration::from_secs(60)),
+                    );
+                }
+                if alert.severity == AlertSeverity::Critical {
+                    self.push_toast(ToastLevel::Error, format!("{}: {}", alert.plugin_id, alert.message));
                 }
             }
+            AppEvent::StatusSegment { segment, ttl_secs } => {
+                self.set_status_segment(segment, ttl_secs.map(Duration::from_secs));
+            }
+            AppEvent::ClearStatusSegment(id) => self.clear_status_segment(&id),
+            AppEvent::StakeDistribution(dist) => {
+                if let Some(alert) = self.stake.as_ref().and_then(|prev| dist.compare(prev, self.stake_alert_pp)) {
+                    let (level, verb) = if alert.concentrating() {
+                        (ToastLevel::Warn, "concentrating")
+                    } else {
+                        (ToastLevel::Info, "spreading")
+                    };
+                    self.push_toast(
+                        level,
+                        format!(
+                            "Stake {verb} at epoch {}: Nakamoto {} → {}, top {} {:+.1}pp",
+                            alert.epoch_height,
+                            alert.nakamoto_before,
+                            alert.nakamoto_after,
+                            alert.top_n,
+                            alert.share_change_pp,
+                        ),
+                    );
+                }
+                self.stake_previous = self.stake.replace(dist);
+            }
             AppEvent::NewBlock(block) => {
                 let height = block.height;
 
-                if self.loading_block == Some(height) {
+                self.startup_stage_done(crate::startup::StartupStage::Sources);
+
+                // Account feed, watches, anomalies and gas tracking follow the chain even while live updates are paused
+                self.account_feed.record_block(&block);
+                self.check_watches(&block);
+                self.check_anomalies(&block);
+                if let Some(price) = block.gas_price {
+                    if let Some(spike) = self.gas_oracle.record(height, price) {
+                        self.push_toast(
+                            ToastLevel::Warn,
+                            format!(
+                                "Gas spike at #{}: {} ({:.1}× median {})",
+                                spike.height,
+                                crate::gas_oracle::format_gas_price(spike.price),
+                                spike.multiple,
+                                crate::gas_oracle::format_gas_price(spike.median),
+                            ),
+                        );
+                    }
+                }
+
+                if self.loading_block == Some(height) {
                     self.loading_block = None;
+                    self.touch_blocks();
                 }
 
                 // If live updates are paused, drop blocks that are strictly in the future
@@ -1743,7 +2768,12 @@ impl App {
                 // of the selected anchor; the user can re-enable by pressing ← in the
                 // Blocks pane.
                 if !self.live_updates_paused {
-                    let anchor_height = self.current_block().map(|b| b.height);
+                    // A warm-start anchor is from the last session; the live tip is
+                    // expected to be far ahead of it
+                    let anchor_height = self
+                        .current_block()
+                        .map(|b| b.height)
+                        .filter(|h| !self.stale_heights.contains(h));
                     if let Some(anchor_h) = anchor_height {
                         let ahead = height.saturating_sub(anchor_h);
                         if ahead > FRONT_WINDOW {
@@ -1764,6 +2794,19 @@ impl App {
 
     fn push_block(&mut self, b: BlockRow) {
         let height = b.height;
+        self.filter_cache.forget(height);
+        self.touch_blocks();
+
+        // A fresh copy of a warm-start row refreshes it in place
+        if self.stale_heights.remove(&height) {
+            if let Some(idx) = self.blocks.iter().position(|existing| existing.height == height) {
+                self.blocks[idx] = b;
+                if self.sel_block_height == Some(height) {
+                    self.validate_and_refresh_tx(BlockChangeReason::AutoFollow);
+                }
+                return;
+            }
+        }
 
         // Log state BEFORE push
         self.log_debug(format!(
@@ -1861,10 +2904,12 @@ impl App {
 
     // ----- Search methods -----
     pub fn start_search(&mut self) {
+        self.count_feature("overlay.search");
         self.input_mode = InputMode::Search;
         self.search_query.clear();
         self.search_results.clear();
         self.search_selection = 0;
+        self.clear_search_preview();
     }
 
     pub fn search_query(&self) -> &str {
@@ -1890,6 +2935,7 @@ impl App {
     pub fn set_search_results(&mut self, results: Vec<crate::history::HistoryHit>) {
         self.search_results = results;
         self.search_selection = 0;
+        self.clear_search_preview();
     }
 
     pub fn search_up(&mut self) {
@@ -1909,33 +2955,261 @@ impl App {
         self.search_query.clear();
         self.search_results.clear();
         self.search_selection = 0;
+        self.clear_search_preview();
     }
 
     pub fn get_selected_search_result(&self) -> Option<&crate::history::HistoryHit> {
         self.search_results.get(self.search_selection)
     }
 
-    pub fn display_tx_from_json(&mut self, raw_json: &str) {
-        // Parse and display transaction from raw JSON
-        if let Ok(tx) = serde_json::from_str::<serde_json::Value>(raw_json) {
-            self.set_details_json(pretty(&tx, 2));
+    fn clear_search_preview(&mut self) {
+        self.search_preview.set_text(String::new());
+        self.search_preview_hash = None;
+    }
+
+    /// Hash of the highlighted result if its preview hasn't been loaded yet
+    pub fn search_preview_pending(&self) -> Option<String> {
+        let hit = self.get_selected_search_result()?;
+        if self.search_preview_hash.as_deref() == Some(hit.hash.as_str()) {
+            None
+        } else {
+            Some(hit.hash.clone())
         }
     }
 
+    /// Fill the preview with the parsed details of `hash` (raw JSON from History)
+    pub fn set_search_preview(&mut self, hash: String, raw_json: Option<&str>) {
+        let text = match raw_json {
+            Some(raw) => crate::safe_text::raw_details(raw, self.json_format),
+            None => format!("Transaction {hash} not found in history"),
+        };
+        self.search_preview.set_text(text);
+        self.search_preview_hash = Some(hash);
+    }
+
+    /// Get the search preview buffer (for rendering)
+    pub fn search_preview(&self) -> &DetailsBuffer {
+        &self.search_preview
+    }
+
+    /// Colored preview window (viewport from the last frame)
+    pub fn search_preview_colored_window(&mut self) -> Vec<ratatui::text::Line<'static>> {
+        let theme = self.theme;
+        self.search_preview
+            .colored_window(self.search_preview_viewport_lines, &theme)
+    }
+
+    /// Set search preview viewport size (called by renderer)
+    pub fn set_search_preview_viewport_lines(&mut self, n: usize) {
+        self.search_preview_viewport_lines = n.max(1);
+    }
+
+    /// Scroll the search preview (PageUp/PageDown in the overlay)
+    pub fn scroll_search_preview(&mut self, delta: isize) {
+        self.search_preview
+            .scroll_lines(delta, self.search_preview_viewport_lines);
+    }
+
+    pub fn search_preview_viewport_lines(&self) -> usize {
+        self.search_preview_viewport_lines
+    }
+
+    pub fn display_tx_from_json(&mut self, raw_json: &str) {
+        // Parse and display transaction from raw JSON (malformed data shows under a warning)
+        self.set_details_json(crate::safe_text::raw_details(raw_json, self.json_format));
+    }
+
     // ----- Marks methods -----
-    pub fn open_marks(&mut self, marks_list: Vec<crate::types::Mark>) {
+    pub fn open_marks(&mut self, marks_list: Vec<crate::types::Mark>, trash: Vec<crate::types::Mark>) {
+        self.count_feature("overlay.marks");
         self.marks_list = marks_list;
+        self.marks_trash = trash;
         self.marks_selection = 0;
         self.input_mode = InputMode::Marks;
     }
 
+    /// Refresh the overlay lists after a delete/restore, keeping the selection in range
+    pub fn refresh_marks(&mut self, marks_list: Vec<crate::types::Mark>, trash: Vec<crate::types::Mark>) {
+        self.marks_list = marks_list;
+        self.marks_trash = trash;
+        let total = self.marks_list.len() + self.marks_trash.len();
+        self.marks_selection = self.marks_selection.min(total.saturating_sub(1));
+    }
+
     pub fn marks_list(&self) -> &[crate::types::Mark] {
         &self.marks_list
     }
 
+    pub fn marks_trash(&self) -> &[crate::types::Mark] {
+        &self.marks_trash
+    }
+
+    // ----- Risk browser -----
+    /// Open the Risk browser (Ctrl+R); the runtime loads results for the empty
+    /// query, i.e. recent high-risk txs
+    pub fn open_risk(&mut self) {
+        self.count_feature("overlay.risk");
+        self.input_mode = InputMode::Risk;
+        self.risk_query.clear();
+        self.risk_results.clear();
+        self.risk_selection = 0;
+    }
+
+    pub fn close_risk(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.risk_query.clear();
+        self.risk_results.clear();
+        self.risk_selection = 0;
+    }
+
+    pub fn risk_query(&self) -> &str {
+        &self.risk_query
+    }
+
+    pub fn risk_add_char(&mut self, c: char) {
+        self.risk_query.push(c);
+    }
+
+    pub fn risk_backspace(&mut self) {
+        self.risk_query.pop();
+    }
+
+    pub fn set_risk_results(&mut self, results: Vec<crate::history::RiskHit>) {
+        self.risk_results = results;
+        self.risk_selection = self.risk_selection.min(self.risk_results.len().saturating_sub(1));
+    }
+
+    pub fn risk_results(&self) -> &[crate::history::RiskHit] {
+        &self.risk_results
+    }
+
+    pub fn risk_selection(&self) -> usize {
+        self.risk_selection
+    }
+
+    pub fn risk_up(&mut self) {
+        self.risk_selection = self.risk_selection.saturating_sub(1);
+    }
+
+    pub fn risk_down(&mut self) {
+        if self.risk_selection + 1 < self.risk_results.len() {
+            self.risk_selection += 1;
+        }
+    }
+
+    pub fn get_selected_risk(&self) -> Option<&crate::history::RiskHit> {
+        self.risk_results.get(self.risk_selection)
+    }
+
+    /// Jump from an analysis to its tx: select it in the Txs pane when its block
+    /// is still in the ring, else show History's copy (`raw_json`) in Details
+    pub fn open_risk_hit(&mut self, hash: &str, height: Option<u64>, raw_json: Option<&str>) {
+        if self.jump_to_tx(hash, height, raw_json) {
+            self.close_risk();
+        }
+    }
+
+    /// Select `hash` in the Txs pane when its block is in the ring, else show
+    /// History's copy in Details; false (with a toast) when neither has it
+    fn jump_to_tx(&mut self, hash: &str, height: Option<u64>, raw_json: Option<&str>) -> bool {
+        if let Some(height) = height.filter(|h| self.blocks.iter().any(|b| b.height == *h)) {
+            self.sel_block_height = Some(height);
+            self.follow_blocks_latest = false;
+            self.ensure_block_window_by_chain(height);
+            self.validate_and_refresh_tx(BlockChangeReason::ManualNav);
+            let (txs, _, _) = self.txs();
+            if let Some(idx) = txs.iter().position(|t| t.hash == hash) {
+                self.sel_tx = idx;
+                self.select_tx();
+                self.pane = 1;
+                return true;
+            }
+        }
+        match raw_json {
+            Some(raw) => {
+                self.display_tx_from_json(raw);
+                self.pane = 2;
+                true
+            }
+            None => {
+                self.push_toast(
+                    ToastLevel::Warn,
+                    format!("{} is not in History", &hash[..hash.len().min(8)]),
+                );
+                false
+            }
+        }
+    }
+
+    // ----- Account feed -----
+    /// Open the account feed (Ctrl+A); the runtime merges History's events in
+    pub fn open_account_feed(&mut self) {
+        self.count_feature("overlay.account_feed");
+        self.input_mode = InputMode::Accounts;
+        self.account_feed_selection = 0;
+    }
+
+    /// Close the feed (the query is kept for next time)
+    pub fn close_account_feed(&mut self) {
+        self.input_mode = InputMode::Normal;
+    }
+
+    pub fn merge_account_events(&mut self, events: Vec<crate::account_feed::AccountEvent>) {
+        self.account_feed.merge(events);
+    }
+
+    pub fn account_feed_query(&self) -> &str {
+        &self.account_feed_query
+    }
+
+    pub fn account_feed_add_char(&mut self, c: char) {
+        self.account_feed_query.push(c);
+        self.account_feed_selection = 0;
+    }
+
+    pub fn account_feed_backspace(&mut self) {
+        self.account_feed_query.pop();
+        self.account_feed_selection = 0;
+    }
+
+    /// Events matching the current query, newest first
+    pub fn account_feed_events(&self) -> Vec<&crate::account_feed::AccountEvent> {
+        let query = crate::account_feed::FeedQuery::parse(&self.account_feed_query);
+        self.account_feed.filtered(&query)
+    }
+
+    pub fn account_feed_selection(&self) -> usize {
+        self.account_feed_selection
+    }
+
+    pub fn account_feed_up(&mut self) {
+        self.account_feed_selection = self.account_feed_selection.saturating_sub(1);
+    }
+
+    pub fn account_feed_down(&mut self) {
+        if self.account_feed_selection + 1 < self.account_feed_events().len() {
+            self.account_feed_selection += 1;
+        }
+    }
+
+    /// (hash, height) of the highlighted event
+    pub fn selected_account_event(&self) -> Option<(String, u64)> {
+        self.account_feed_events()
+            .get(self.account_feed_selection)
+            .map(|e| (e.hash.clone(), e.height))
+    }
+
+    /// Jump to the tx behind an event (see `open_risk_hit`)
+    pub fn open_account_event(&mut self, hash: &str, height: u64, raw_json: Option<&str>) {
+        if self.jump_to_tx(hash, Some(height), raw_json) {
+            self.close_account_feed();
+        }
+    }
+
     pub fn close_marks(&mut self) {
         self.input_mode = InputMode::Normal;
         self.marks_list.clear();
+        self.marks_trash.clear();
         self.marks_selection = 0;
     }
 
@@ -1950,13 +3224,20 @@ impl App {
     }
 
     pub fn marks_down(&mut self) {
-        if self.marks_selection + 1 < self.marks_list.len() {
+        if self.marks_selection + 1 < self.marks_list.len() + self.marks_trash.len() {
             self.marks_selection += 1;
         }
     }
 
-    pub fn get_selected_mark(&self) -> Option<&crate::types::Mark> {
-        self.marks_list.get(self.marks_selection)
+    /// Selected mark and whether it sits in the trash section
+    pub fn get_selected_mark(&self) -> Option<(&crate::types::Mark, bool)> {
+        match self.marks_list.get(self.marks_selection) {
+            Some(mark) => Some((mark, false)),
+            None => self
+                .marks_trash
+                .get(self.marks_selection - self.marks_list.len())
+                .map(|mark| (mark, true)),
+        }
     }
 
     pub fn current_context(&self) -> (u8, Option<u64>, Option<String>) {
@@ -1986,190 +3267,1909 @@ impl App {
         }
     }
 
-    // ----- Web/egui helper methods -----
+    // ----- Pin board -----
+    pub fn open_board(&mut self, marks: Vec<crate::types::Mark>) {
+        self.count_feature("overlay.board");
+        self.board_marks = marks;
+        self.board_selection = 0;
+        self.board_note = None;
+        self.input_mode = InputMode::Board;
+    }
 
-    /// Get count of blocks (for display)
-    pub fn blocks_len(&self) -> usize {
-        self.blocks.len()
+    pub fn close_board(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.board_marks.clear();
+        self.board_note = None;
     }
 
-    /// Get count of transactions in current block
-    pub fn txs_len(&self) -> usize {
-        self.current_block().map_or(0, |b| b.transactions.len())
+    /// Pinned marks, notes and copies, oldest first
+    pub fn board_items(&self) -> Vec<crate::pin_board::PinItem> {
+        self.pin_board.items(&self.board_marks)
     }
 
-    /// Get current block selection index (0-based)
-    pub fn sel_block(&self) -> usize {
-        if let Some(height) = self.sel_block_height {
-            self.find_block_index(Some(height)).unwrap_or(0)
-        } else {
-            0 // Auto-follow mode: newest block is at index 0
-        }
+    pub fn board_selection(&self) -> usize {
+        self.board_selection
     }
 
-    /// Select block by index with clamping
-    pub fn select_block_clamped(&mut self, idx: usize) {
-        if idx < self.blocks.len() {
-            self.select_block_row(idx);
+    pub fn board_up(&mut self) {
+        self.board_selection = self.board_selection.saturating_sub(1);
+    }
+
+    pub fn board_down(&mut self) {
+        if self.board_selection + 1 < self.board_items().len() {
+            self.board_selection += 1;
         }
     }
 
-    /// Select transaction by index with clamping
-    pub fn select_tx_clamped(&mut self, idx: usize) {
-        if let Some(b) = self.current_block() {
-            if idx < b.transactions.len() {
-                self.select_tx_row(idx);
-            }
+    pub fn board_start_note(&mut self) {
+        self.board_note = Some(String::new());
+    }
+
+    pub fn board_note_draft(&self) -> Option<&str> {
+        self.board_note.as_deref()
+    }
+
+    pub fn board_note_add_char(&mut self, c: char) {
+        if let Some(note) = self.board_note.as_mut() {
+            note.push(c);
         }
     }
 
-    /// Set filter query and recompile
-    pub fn set_filter_query(&mut self, query: String) {
-        self.filter_query = query;
-        self.filter_compiled = compile_filter(&self.filter_query);
-        self.validate_and_refresh_tx(BlockChangeReason::FilterChange);
+    pub fn board_note_backspace(&mut self) {
+        if let Some(note) = self.board_note.as_mut() {
+            note.pop();
+        }
     }
 
-    // ----- Details buffer API -----
+    pub fn board_note_cancel(&mut self) {
+        self.board_note = None;
+    }
 
-    /// Set Details pane content (replaces full buffer)
-    pub fn set_details_json(&mut self, json: String) {
-        self.details_buf.set_text(json);
+    /// Add the typed note, attached to the current block / tx
+    pub fn board_note_commit(&mut self) {
+        let Some(text) = self.board_note.take() else {
+            return;
+        };
+        let text = text.trim().to_string();
+        if text.is_empty() {
+            return;
+        }
+        let (_, height, tx_hash) = self.current_context();
+        self.pin_board
+            .add_note(text, height, tx_hash, chrono::Utc::now().timestamp_millis());
+        // Newest items sort last
+        self.board_selection = self.board_items().len().saturating_sub(1);
     }
 
-    /// Set viewport size (called by renderer based on pane height)
-    pub fn set_details_viewport_lines(&mut self, n: usize) {
-        self.details_viewport_lines = n.max(1);
+    /// Remove the selected note or copy (marks are unpinned in the marks overlay)
+    pub fn board_delete_selected(&mut self) {
+        let Some(item) = self.board_items().get(self.board_selection).cloned() else {
+            return;
+        };
+        if item.kind == crate::pin_board::PinKind::Mark {
+            self.show_toast("Unpin marks in the marks overlay (Shift+M)".to_string());
+            return;
+        }
+        self.pin_board.remove(&item);
+        self.board_selection = self.board_selection.min(self.board_items().len().saturating_sub(1));
     }
 
-    /// Get viewport size (for key handling)
-    pub fn details_viewport_lines(&self) -> usize {
-        self.details_viewport_lines
+    /// Jump to the selected item's block / tx and close the board
+    pub fn board_jump_selected(&mut self) {
+        let Some(item) = self.board_items().get(self.board_selection).cloned() else {
+            return;
+        };
+        if item.height.is_none() {
+            self.show_toast("Nothing to jump to".to_string());
+            return;
+        }
+        let mark = crate::types::Mark {
+            label: item.title,
+            pane: if item.tx_hash.is_some() { 1 } else { 0 },
+            height: item.height,
+            tx_hash: item.tx_hash,
+            when_ms: item.at_ms,
+            pinned: false,
+        };
+        self.jump_to_mark(&mark);
+        self.close_board();
+    }
+
+    /// Markdown report of the board with `marks` pinned (for export), and its items
+    pub fn board_export(&self, marks: &[crate::types::Mark]) -> (String, Vec<crate::pin_board::PinItem>) {
+        let items = self.pin_board.items(marks);
+        let markdown = crate::pin_board::to_markdown(
+            &items,
+            chrono::Utc::now().timestamp_millis(),
+            &self.time_display,
+        );
+        (markdown, items)
+    }
+
+    /// Keep a copied payload on the pin board (last `pin_board::MAX_COPIES`)
+    pub fn record_copy(&mut self, title: &str, body: String) {
+        self.count_feature("copy");
+        let (_, height, tx_hash) = self.current_context();
+        self.pin_board.add_copy(
+            title.to_string(),
+            body,
+            height,
+            tx_hash,
+            chrono::Utc::now().timestamp_millis(),
+        );
     }
 
-    /// Get windowed view of Details (for rendering)
-    pub fn details_window(&self) -> String {
-        self.details_buf.window(self.details_viewport_lines)
+    // ----- Exports -----
+    pub fn open_exports(&mut self, mut records: Vec<crate::exports::ExportRecord>) {
+        self.count_feature("overlay.exports");
+        for record in &mut records {
+            record.missing = !record.exists();
+        }
+        self.exports = records;
+        self.exports_selection = 0;
+        self.input_mode = InputMode::Exports;
     }
 
-    /// Check if details content was truncated
-    pub fn details_truncated(&self) -> bool {
-        self.details_buf.truncated()
+    pub fn close_exports(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.exports.clear();
     }
 
-    /// Get full Details text (for copy operations)
-    pub fn details_full_text(&self) -> &str {
-        self.details_buf.full_text()
+    /// Recorded exports, newest first
+    pub fn exports(&self) -> &[crate::exports::ExportRecord] {
+        &self.exports
     }
 
-    /// Get details as pretty-printed string (legacy compatibility)
-    pub fn details_pretty_string(&self) -> String {
-        self.details_buf.full_text().to_string()
+    pub fn exports_selection(&self) -> usize {
+        self.exports_selection
     }
 
-    /// Get details as raw JSON string (legacy compatibility)
-    pub fn details_raw_string(&self) -> String {
-        self.details_buf.full_text().to_string()
+    pub fn exports_up(&mut self) {
+        self.exports_selection = self.exports_selection.saturating_sub(1);
     }
 
-    /// Scroll Details by delta lines
-    pub fn scroll_details_lines(&mut self, delta: isize) {
-        self.details_buf.scroll_lines(delta, self.details_viewport_lines);
+    pub fn exports_down(&mut self) {
+        if self.exports_selection + 1 < self.exports.len() {
+            self.exports_selection += 1;
+        }
     }
 
-    /// Jump to top of Details
-    pub fn details_home(&mut self) {
-        self.details_buf.scroll_to_top();
+    pub fn selected_export(&self) -> Option<&crate::exports::ExportRecord> {
+        self.exports.get(self.exports_selection)
     }
 
-    /// Jump to bottom of Details
-    pub fn details_end(&mut self) {
-        self.details_buf.scroll_to_bottom(self.details_viewport_lines);
+    /// Drop the selected record from the list; returns its History id (the file stays)
+    pub fn exports_remove_selected(&mut self) -> Option<i64> {
+        if self.exports_selection >= self.exports.len() {
+            return None;
+        }
+        let record = self.exports.remove(self.exports_selection);
+        self.exports_selection = self.exports_selection.min(self.exports.len().saturating_sub(1));
+        self.show_toast(format!("Forgot export {} (file kept)", record.path));
+        Some(record.id)
+    }
+
+    /// Re-run the selected digest export with the same parameters (runtime side);
+    /// pin board and report exports are re-run by the caller, which holds the marks
+    pub fn exports_rerun_selected(&mut self) -> Option<crate::exports::ExportRecord> {
+        let record = self.selected_export()?.clone();
+        if let crate::exports::ExportParams::Digest { .. } = record.params {
+            self.push_toast(ToastLevel::Info, format!("Re-running {}…", record.params.label()));
+            self.pending_export_rerun = Some(record);
+            return None;
+        }
+        Some(record)
     }
 
-    /// Get scroll info for status display
-    pub fn details_scroll_info(&self) -> (usize, usize) {
-        (self.details_buf.current_scroll_line(), self.details_buf.total_lines())
+    /// Take a requested digest re-run (runtime side)
+    pub fn take_export_rerun(&mut self) -> Option<crate::exports::ExportRecord> {
+        self.pending_export_rerun.take()
     }
 
-    /// Get JSON for currently focused pane (for copy operation)
-    pub fn focused_json_string(&self) -> Option<String> {
-        Some(self.get_copy_content())
+    /// A file was written and recorded: toast, and list it first if the overlay is open
+    pub fn export_recorded(&mut self, record: crate::exports::ExportRecord) {
+        self.push_toast(
+            ToastLevel::Success,
+            format!("Exported {} to {}", record.params.label(), record.path),
+        );
+        if self.input_mode == InputMode::Exports {
+            self.exports.insert(0, record);
+            self.exports_selection = 0;
+        }
     }
 
-    /// Get display-ready list of blocks (filtered if filter active)
-    pub fn blocks_for_display(&self) -> Vec<&BlockRow> {
-        let (blocks, _sel, _total) = self.filtered_blocks();
-        blocks
+    // ----- Watches -----
+    /// Rules saved by an earlier session
+    pub fn set_watch_rules(&mut self, rules: Vec<crate::watch::WatchRule>) {
+        // Watches added before History finished loading are kept (and saved)
+        let added: Vec<String> = self.watches.rules().iter().map(|r| r.query.clone()).collect();
+        self.watches.load(rules);
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        for query in added {
+            if self.watches.add(&query, now_ms).is_ok() {
+                self.watches_dirty = true;
+            }
+        }
     }
 
-    /// Get display-ready list of transactions from current block (filtered)
-    pub fn txs_for_display(&self) -> Vec<&TxLite> {
-        if let Some(b) = self.current_block() {
-            b.transactions
-                .iter()
-                .filter(|tx| {
-                    // Apply text filter
-                    if filter::is_empty(&self.filter_compiled) {
-                        return true;
-                    }
-                    // Convert TxLite to JSON for filtering (same pattern as txs() method)
-                    let v = json!({
-                        "hash": tx.hash,
-                        "signer_id": tx.signer_id.as_deref().unwrap_or(""),
-                        "receiver_id": tx.receiver_id.as_deref().unwrap_or("")
-                    });
-                    tx_matches_filter(&v, &self.filter_compiled)
-                })
-                .collect()
-        } else {
-            Vec::new()
+    /// Keep the current filter running against new blocks
+    pub fn watch_current_filter(&mut self) {
+        let query = self.filter_query.clone();
+        match self.watches.add(&query, chrono::Utc::now().timestamp_millis()) {
+            Ok(rule) => {
+                let msg = format!("👁 Watching \"{}\" (Ctrl+W to manage)", rule.query);
+                self.count_feature("watch.add");
+                self.watches_dirty = true;
+                self.push_toast(ToastLevel::Success, msg);
+            }
+            Err(e) => self.push_toast(ToastLevel::Warn, e.to_string()),
         }
     }
 
-    /// Get lightweight block data for display (owned copy)
-    /// Returns None if index out of bounds
-    pub fn block_lite(&self, idx: usize) -> Option<BlockLite> {
-        self.blocks_for_display().get(idx).map(|b| BlockLite {
-            height: b.height,
-            tx_count: b.tx_count,
-            when: b.when.clone(),
-            time_utc: format_timestamp_utc(b.timestamp),
-        })
+    pub fn open_watches(&mut self) {
+        self.count_feature("overlay.watches");
+        self.watch_selection = 0;
+        self.watch_webhook = None;
+        self.input_mode = InputMode::Watches;
     }
 
-    /// Get lightweight transaction data for display (owned copy)
-    /// Returns None if index out of bounds
-    pub fn tx_lite(&self, idx: usize) -> Option<TxLite> {
-        self.txs_for_display().get(idx).map(|tx| (*tx).clone())
+    /// Close the manager; the unseen badge clears
+    pub fn close_watches(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.watch_webhook = None;
+        self.watches.mark_seen();
     }
 
-    /// Get count of filtered blocks
-    pub fn filtered_blocks_len(&self) -> usize {
-        self.blocks_for_display().len()
+    pub fn watch_rules(&self) -> &[crate::watch::WatchRule] {
+        self.watches.rules()
     }
 
-    /// Get count of filtered transactions in current block
-    pub fn filtered_txs_len(&self) -> usize {
-        self.txs_for_display().len()
+    pub fn watch_selection(&self) -> usize {
+        self.watch_selection
     }
 
-    /// Handle mouse click at terminal coordinates
-    pub fn handle_mouse_click(&mut self, col: u16, row: u16) {
-        // Determine which pane was clicked based on layout
-        // The layout is approximately:
-        // - Header: 2 rows
-        // - Filter bar: 1-3 rows (let's assume 2 average)
-        // - Top 30%: Blocks (left 50%) + Txs (right 50%)
-        // - Bottom 70%: Details
+    pub fn watch_up(&mut self) {
+        self.watch_selection = self.watch_selection.saturating_sub(1);
+    }
 
-        // Skip header and filter (approximately 4 rows)
-        if row < 4 {
+    pub fn watch_down(&mut self) {
+        if self.watch_selection + 1 < self.watches.rules().len() {
+            self.watch_selection += 1;
+        }
+    }
+
+    pub fn watch_delete_selected(&mut self) {
+        let Some(id) = self.watches.rules().get(self.watch_selection).map(|r| r.id) else {
             return;
+        };
+        if let Some(rule) = self.watches.remove(id) {
+            self.watches_dirty = true;
+            self.push_toast(ToastLevel::Info, format!("Stopped watching \"{}\"", rule.query));
         }
+        self.watch_selection = self.watch_selection.min(self.watches.rules().len().saturating_sub(1));
+    }
 
-        // Get terminal size from last draw (this is approximate)
-        // In real implementation, we'd pass the actual layout areas
+    /// Apply the selected rule's query as the filter and close the manager
+    pub fn watch_apply_selected(&mut self) {
+        let Some(query) = self.watches.rules().get(self.watch_selection).map(|r| r.query.clone()) else {
+            return;
+        };
+        self.close_watches();
+        self.set_filter_query(query);
+    }
+
+    /// Start editing the selected rule's webhook (prefilled with the current URL)
+    pub fn watch_start_webhook(&mut self) {
+        if let Some(rule) = self.watches.rules().get(self.watch_selection) {
+            self.watch_webhook = Some(rule.webhook.clone().unwrap_or_default());
+        }
+    }
+
+    pub fn watch_webhook_draft(&self) -> Option<&str> {
+        self.watch_webhook.as_deref()
+    }
+
+    pub fn watch_webhook_add_char(&mut self, c: char) {
+        if let Some(url) = self.watch_webhook.as_mut() {
+            url.push(c);
+        }
+    }
+
+    pub fn watch_webhook_backspace(&mut self) {
+        if let Some(url) = self.watch_webhook.as_mut() {
+            url.pop();
+        }
+    }
+
+    pub fn watch_webhook_cancel(&mut self) {
+        self.watch_webhook = None;
+    }
+
+    /// Save the typed webhook (blank clears it); only http(s) URLs are accepted
+    pub fn watch_webhook_commit(&mut self) {
+        let Some(url) = self.watch_webhook.take() else {
+            return;
+        };
+        let url = url.trim().to_string();
+        let is_http = url.starts_with("http://") || url.starts_with("https://");
+        if !url.is_empty() && !is_http {
+            self.push_toast(ToastLevel::Warn, "Webhook must be an http(s) URL".to_string());
+            return;
+        }
+        let Some(id) = self.watches.rules().get(self.watch_selection).map(|r| r.id) else {
+            return;
+        };
+        let cleared = url.is_empty();
+        if self.watches.set_webhook(id, Some(url)) {
+            self.watches_dirty = true;
+            let msg = if cleared { "Webhook removed" } else { "Webhook set" };
+            self.push_toast(ToastLevel::Success, msg.to_string());
+        }
+    }
+
+    /// Check a new block against the watch rules: toast + badge per rule hit,
+    /// webhooks queued for the runtime
+    fn check_watches(&mut self, block: &BlockRow) {
+        for hit in self.watches.check_block(block) {
+            self.watches_dirty = true;
+            let plural = if hit.count == 1 { "" } else { "es" };
+            self.push_toast(
+                ToastLevel::Info,
+                format!("👁 \"{}\": {} new match{plural} in #{}", hit.query, hit.count, hit.height),
+            );
+            if hit.webhook.is_some() {
+                self.watch_hooks.push(hit);
+            }
+        }
+    }
+
+    /// Hits whose webhooks are due (runtime side)
+    pub fn take_watch_hooks(&mut self) -> Vec<crate::watch::WatchHit> {
+        std::mem::take(&mut self.watch_hooks)
+    }
+
+    /// Rules to save when they changed (added, removed, new hits)
+    pub fn take_watch_rules_change(&mut self) -> Option<Vec<crate::watch::WatchRule>> {
+        std::mem::take(&mut self.watches_dirty).then(|| self.watches.rules().to_vec())
+    }
+
+    // ----- Endpoint settings -----
+    /// Record the endpoints the runtime started with (no switch is requested)
+    pub fn set_endpoints(&mut self, endpoints: Endpoints) {
+        self.endpoints = endpoints;
+    }
+
+    pub fn endpoints(&self) -> &Endpoints {
+        &self.endpoints
+    }
+
+    /// Replace the archival fetch channel after the archival task was respawned
+    pub fn set_archival_sender(&mut self, tx: Option<tokio::sync::mpsc::UnboundedSender<u64>>) {
+        self.archival_fetch_tx = tx;
+        self.loading_block = None; // In-flight request died with the old task
+        self.touch_blocks();
+    }
+
+    /// Ask the runtime to switch endpoints; it cancels the source task and
+    /// respawns it against the new URLs while the block buffer is kept.
+    pub fn request_endpoints(&mut self, endpoints: Endpoints) {
+        let near_node_url = endpoints.near_node_url.trim().to_string();
+        let archival_rpc_url = endpoints
+            .archival_rpc_url
+            .map(|u| u.trim().to_string())
+            .filter(|u| !u.is_empty());
+
+        let checks = std::iter::once(("RPC URL", near_node_url.as_str()))
+            .chain(archival_rpc_url.as_deref().map(|u| ("Archival URL", u)));
+        for (name, url) in checks {
+            if let Err(e) = crate::config::validate_url(url, name) {
+                self.push_toast(ToastLevel::Error, e.to_string());
+                return;
+            }
+        }
+
+        let endpoints = Endpoints {
+            near_node_url,
+            archival_rpc_url,
+        };
+        if endpoints == self.endpoints {
+            self.show_toast("Endpoints unchanged".to_string());
+            return;
+        }
+        self.log_debug(format!(
+            "[endpoints] switch requested: rpc={} archival={:?}",
+            endpoints.near_node_url, endpoints.archival_rpc_url
+        ));
+        self.pending_endpoints = Some(endpoints);
+    }
+
+    /// Take a requested endpoint switch (runtime side). The returned endpoints
+    /// become current immediately.
+    pub fn take_endpoint_change(&mut self) -> Option<Endpoints> {
+        let endpoints = self.pending_endpoints.take()?;
+        self.endpoints = endpoints.clone();
+        self.push_toast(
+            ToastLevel::Success,
+            format!("Switched RPC to {}", endpoints.near_node_url),
+        );
+        Some(endpoints)
+    }
+
+    // ----- Source health -----
+    /// Sources the runtime is running (called at start and after every respawn);
+    /// past reports are dropped, switched-off sources stay off
+    pub fn set_sources(&mut self, kinds: &[crate::source_health::SourceKind]) {
+        let mut sources: Vec<_> = kinds
+            .iter()
+            .map(|&kind| crate::source_health::SourceHealth::new(kind))
+            .collect();
+        for s in &mut sources {
+            s.enabled = self.sources.iter().find(|o| o.kind == s.kind).is_none_or(|o| o.enabled);
+        }
+        self.sources = sources;
+        self.sources_selection = self.sources_selection.min(self.sources.len().saturating_sub(1));
+    }
+
+    /// Age of the newest block that watermarks the Blocks pane stale (STALE_AFTER_SECS, 0 = off)
+    pub fn set_stale_after(&mut self, secs: u64) {
+        self.stale_after_secs = secs;
+    }
+
+    /// `STALE — last block 94s ago` over the Blocks pane while the newest
+    /// buffered block is older than the threshold (feed stalled or paused)
+    pub fn stale_watermark(&self) -> Option<String> {
+        let newest = self.blocks.first()?;
+        crate::source_health::stale_watermark(
+            newest.timestamp,
+            chrono::Utc::now().timestamp_millis(),
+            self.stale_after_secs,
+        )
+    }
+
+    /// Live feed phase (connecting / first sync / live / stalled) for loading states
+    pub fn source_phase(&self) -> crate::source_health::SourcePhase {
+        let has_blocks = self.blocks.iter().any(|b| !self.stale_heights.contains(&b.height));
+        crate::source_health::phase(&self.sources, has_blocks, Instant::now())
+    }
+
+    pub fn sources(&self) -> &[crate::source_health::SourceHealth] {
+        &self.sources
+    }
+
+    /// Current badge per configured source (footer, sources overlay, web snapshot)
+    pub fn source_badges(&self) -> Vec<crate::source_health::SourceBadge> {
+        let now = Instant::now();
+        self.sources.iter().map(|s| s.badge(now)).collect()
+    }
+
+    // ----- Status bar -----
+    /// Add or replace (by id) a footer segment; `ttl` drops it automatically
+    pub fn set_status_segment(&mut self, segment: StatusSegment, ttl: Option<Duration>) {
+        self.status_bar.set(Instant::now(), segment, ttl);
+    }
+
+    pub fn clear_status_segment(&mut self, id: &str) {
+        self.status_bar.remove(id);
+    }
+
+    /// Footer indicators shared by every renderer, followed by contributed
+    /// segments. Key hints, toasts and FPS are added by the TUI footer itself.
+    pub fn status_segments(&self, pinned_marks: usize) -> Vec<StatusSegment> {
+        let mut segs = Vec::new();
+        segs.push(
+            StatusSegment::new("core.blocks", format!("Blocks {}", self.blocks.len()))
+                .priority(status_bar::PRIORITY_LOW),
+        );
+        let txs_total = self.current_block().map_or(0, |b| b.transactions.len());
+        segs.push(
+            StatusSegment::new("core.txs", format!("Txs {txs_total}"))
+                .priority(status_bar::PRIORITY_LOW),
+        );
+        if let Some(height) = self.selected_block_height() {
+            segs.push(
+                StatusSegment::new("core.block", format!("Block #{height}"))
+                    .priority(status_bar::PRIORITY_LOW),
+            );
+        }
+        for anomaly in self.anomaly.active() {
+            segs.push(
+                StatusSegment::new(
+                    format!("core.anomaly.{}", anomaly.metric.label().replace(' ', "_")),
+                    format!("⚠ {} {:.1}σ", anomaly.metric.label(), anomaly.sigma),
+                )
+                .tone(Tone::Warn)
+                .priority(status_bar::PRIORITY_HIGH)
+                .tooltip(format!("Since #{}: {}", anomaly.height, anomaly.message())),
+            );
+        }
+        if let Some(text) = self.startup.as_ref().and_then(|(_, p)| p.status_text()) {
+            segs.push(
+                StatusSegment::new("core.startup", text)
+                    .tone(Tone::Muted)
+                    .priority(status_bar::PRIORITY_HIGH),
+            );
+        }
+        if pinned_marks > 0 {
+            segs.push(
+                StatusSegment::new("core.pinned", format!("★ {pinned_marks}"))
+                    .tone(Tone::Accent)
+                    .action(SegmentAction::shift("M")),
+            );
+        }
+        if !self.watches.is_empty() {
+            let unseen = self.watches.unseen();
+            let seg = if unseen > 0 {
+                StatusSegment::new("core.watch", format!("👁 {unseen} new")).tone(Tone::Accent)
+            } else {
+                StatusSegment::new("core.watch", format!("👁 {}", self.watches.rules().len()))
+                    .tone(Tone::Muted)
+                    .priority(status_bar::PRIORITY_LOW)
+            };
+            segs.push(seg.action(SegmentAction::ctrl("w")));
+        }
+        if self.owned_only {
+            segs.push(
+                StatusSegment::new("core.owned", "◆ owned")
+                    .tone(Tone::Accent)
+                    .priority(status_bar::PRIORITY_HIGH)
+                    .action(SegmentAction::ctrl("u")),
+            );
+        }
+        let filter_pending = self.filter_pending();
+        if filter_pending > 0 {
+            segs.push(
+                StatusSegment::new("core.filtering", format!("⟳ filtering {filter_pending}"))
+                    .tone(Tone::Muted)
+                    .priority(status_bar::PRIORITY_HIGH),
+            );
+        }
+        for badge in self.source_badges() {
+            use crate::source_health::HealthStatus;
+            let tone = match badge.status {
+                HealthStatus::Ok => Tone::Success,
+                HealthStatus::Degraded => Tone::Warn,
+                HealthStatus::Down => Tone::Error,
+                HealthStatus::Unknown | HealthStatus::Disabled => Tone::Muted,
+            };
+            let mut seg = StatusSegment::new(
+                format!("core.source.{}", badge.kind.label().to_lowercase()),
+                format!("● {} {}", badge.kind.label(), badge.status.label()),
+            )
+            .tone(tone)
+            .priority(status_bar::PRIORITY_CRITICAL)
+            .action(SegmentAction::ctrl("s"));
+            if let Some((age, e)) = badge.last_error {
+                seg = seg.tooltip(format!("{}: {}s ago: {e}", badge.kind.label(), age.as_secs()));
+            }
+            segs.push(seg);
+        }
+        if self.debug_visible() {
+            segs.push(
+                StatusSegment::new("core.debug", "[DEBUG]")
+                    .tone(Tone::Debug)
+                    .priority(status_bar::PRIORITY_HIGH)
+                    .action(SegmentAction::ctrl("d")),
+            );
+        }
+        if self.telemetry.enabled() {
+            segs.push(
+                StatusSegment::new("core.telemetry", "◉ telemetry")
+                    .tone(Tone::Muted)
+                    .priority(status_bar::PRIORITY_LOW)
+                    .action(SegmentAction::ctrl("t")),
+            );
+        }
+        segs.extend(self.status_bar.segments(Instant::now()).cloned());
+        segs
+    }
+
+    fn record_source_status(
+        &mut self,
+        kind: crate::source_health::SourceKind,
+        report: crate::source_health::SourceReport,
+    ) {
+        use crate::source_health::HealthStatus;
+        let now = Instant::now();
+        let Some(source) = self.sources.iter_mut().find(|s| s.kind == kind) else {
+            return;
+        };
+        let before = source.status(now);
+        source.record(now, report);
+        let after = source.status(now);
+        if before == after {
+            return;
+        }
+        let err = source.last_error(now).map(|(_, e)| e.to_string()).unwrap_or_default();
+        self.log_debug(format!("[sources] {} {} -> {}", kind.label(), before.label(), after.label()));
+        match after {
+            HealthStatus::Down => {
+                self.push_toast(ToastLevel::Error, format!("{} source down: {err}", kind.label()));
+            }
+            HealthStatus::Ok if before == HealthStatus::Down => {
+                self.push_toast(ToastLevel::Success, format!("{} source recovered", kind.label()));
+            }
+            _ => {}
+        }
+    }
+
+    /// Sources switched off in the overlay (the runtime spawns without them)
+    pub fn disabled_sources(&self) -> HashSet<crate::source_health::SourceKind> {
+        self.sources.iter().filter(|s| !s.enabled).map(|s| s.kind).collect()
+    }
+
+    /// Toggled sources awaiting a respawn (runtime side)
+    pub fn take_sources_change(&mut self) -> Option<HashSet<crate::source_health::SourceKind>> {
+        std::mem::take(&mut self.sources_changed).then(|| self.disabled_sources())
+    }
+
+    /// Respawn the block sources now (r while the first fetch is failing)
+    pub fn retry_sources(&mut self) {
+        self.sources_changed = true;
+        self.push_toast(ToastLevel::Info, "Retrying block sources…".to_string());
+    }
+
+    /// Open the sources overlay (Ctrl+S)
+    pub fn open_sources(&mut self) {
+        self.count_feature("overlay.sources");
+        if self.sources.is_empty() {
+            self.show_toast("No block sources running".to_string());
+            return;
+        }
+        self.input_mode = InputMode::Sources;
+    }
+
+    pub fn close_sources(&mut self) {
+        self.input_mode = InputMode::Normal;
+    }
+
+    pub fn sources_selection(&self) -> usize {
+        self.sources_selection
+    }
+
+    pub fn sources_up(&mut self) {
+        self.sources_selection = self.sources_selection.saturating_sub(1);
+    }
+
+    pub fn sources_down(&mut self) {
+        if self.sources_selection + 1 < self.sources.len() {
+            self.sources_selection += 1;
+        }
+    }
+
+    /// Switch the highlighted source off / back on for this session
+    pub fn toggle_selected_source(&mut self) {
+        let Some(source) = self.sources.get_mut(self.sources_selection) else {
+            return;
+        };
+        source.enabled = !source.enabled;
+        let (label, enabled) = (source.kind.label(), source.enabled);
+        self.sources_changed = true;
+        self.show_toast(format!(
+            "{label} source {} (this session)",
+            if enabled { "enabled" } else { "disabled" }
+        ));
+    }
+
+    /// Open the endpoint settings overlay, prefilled with the current URLs
+    pub fn open_endpoints(&mut self) {
+        self.count_feature("overlay.endpoints");
+        self.endpoint_draft = EndpointDraft {
+            near_node_url: self.endpoints.near_node_url.clone(),
+            archival_rpc_url: self.endpoints.archival_rpc_url.clone().unwrap_or_default(),
+            field: 0,
+        };
+        self.input_mode = InputMode::Endpoints;
+    }
+
+    pub fn close_endpoints(&mut self) {
+        self.input_mode = InputMode::Normal;
+    }
+
+    pub fn endpoint_draft(&self) -> &EndpointDraft {
+        &self.endpoint_draft
+    }
+
+    fn endpoint_field_mut(&mut self) -> &mut String {
+        match self.endpoint_draft.field {
+            0 => &mut self.endpoint_draft.near_node_url,
+            _ => &mut self.endpoint_draft.archival_rpc_url,
+        }
+    }
+
+    pub fn endpoint_add_char(&mut self, ch: char) {
+        self.endpoint_field_mut().push(ch);
+    }
+
+    pub fn endpoint_backspace(&mut self) {
+        self.endpoint_field_mut().pop();
+    }
+
+    pub fn endpoint_next_field(&mut self) {
+        self.endpoint_draft.field = (self.endpoint_draft.field + 1) % 2;
+    }
+
+    /// Apply the overlay draft (Enter)
+    pub fn apply_endpoints(&mut self) {
+        let draft = std::mem::take(&mut self.endpoint_draft);
+        self.input_mode = InputMode::Normal;
+        self.request_endpoints(Endpoints {
+            near_node_url: draft.near_node_url,
+            archival_rpc_url: Some(draft.archival_rpc_url),
+        });
+    }
+
+    // ----- Web/egui helper methods -----
+
+    /// Get count of blocks (for display)
+    pub fn blocks_len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Get count of transactions in current block
+    pub fn txs_len(&self) -> usize {
+        self.current_block().map_or(0, |b| b.transactions.len())
+    }
+
+    /// Get current block selection index (0-based)
+    pub fn sel_block(&self) -> usize {
+        if let Some(height) = self.sel_block_height {
+            self.find_block_index(Some(height)).unwrap_or(0)
+        } else {
+            0 // Auto-follow mode: newest block is at index 0
+        }
+    }
+
+    /// Select block by index with clamping
+    pub fn select_block_clamped(&mut self, idx: usize) {
+        if idx < self.blocks.len() {
+            self.select_block_row(idx);
+        }
+    }
+
+    /// Select transaction by index with clamping
+    pub fn select_tx_clamped(&mut self, idx: usize) {
+        if let Some(b) = self.current_block() {
+            if idx < b.transactions.len() {
+                self.select_tx_row(idx);
+            }
+        }
+    }
+
+    /// Set filter query and recompile
+    pub fn set_filter_query(&mut self, query: String) {
+        self.filter_query = query;
+        self.filter_compiled = compile_filter(&self.filter_query);
+        self.on_filter_changed();
+        self.validate_and_refresh_tx(BlockChangeReason::FilterChange);
+    }
+
+    /// Append a negated term for the selected tx's `field` to the current filter
+    /// (e.g. `-signer:spam.near`), hiding it and everything like it.
+    pub fn exclude_selected(&mut self, field: FilterField) {
+        self.count_feature("filter.exclude");
+        let (txs, sel, _) = self.txs();
+        let value = txs.get(sel).and_then(|tx| match field {
+            FilterField::Signer => tx.signer_id.clone(),
+            FilterField::Receiver => tx.receiver_id.clone(),
+            FilterField::Acct => tx.signer_id.clone().or_else(|| tx.receiver_id.clone()),
+            FilterField::Hash | FilterField::Free | FilterField::Raw => Some(tx.hash.clone()),
+            FilterField::Method => tx.actions.as_ref().and_then(|actions| {
+                actions.iter().find_map(|a| match a {
+                    ActionSummary::FunctionCall { method_name, .. } => Some(method_name.clone()),
+                    _ => None,
+                })
+            }),
+            FilterField::Action => tx
+                .actions
+                .as_ref()
+                .and_then(|actions| actions.first())
+                .map(|a| filter::action_type_name(a).to_string()),
+            FilterField::Bridge => crate::bridge::detect(tx).map(|_| "any".to_string()),
+        });
+
+        let Some(value) = value.filter(|v| !v.is_empty()) else {
+            self.push_toast(ToastLevel::Warn, format!("No {} to exclude", field.key()));
+            return;
+        };
+
+        let term = format!("-{}:{}", field.key(), value.to_lowercase());
+        if self.filter_query.split_whitespace().any(|t| t == term) {
+            self.push_toast(ToastLevel::Warn, format!("Already hiding {} {}", field.key(), value));
+            return;
+        }
+        let query = if self.filter_query.trim().is_empty() {
+            term
+        } else {
+            format!("{} {}", self.filter_query.trim_end(), term)
+        };
+        self.set_filter_query(query);
+        self.show_toast(format!("Hiding {} {}", field.key(), value));
+    }
+
+    // ----- Related txs -----
+    /// Buffer txs (ring and cache, filter ignored) within reach of `height`
+    fn nearby_candidates(&self, height: u64) -> Vec<crate::related::Candidate> {
+        let near = |b: &&BlockRow| b.height.abs_diff(height) <= crate::related::NEARBY_BLOCKS;
+        self.blocks
+            .iter()
+            .chain(self.cached_blocks.values())
+            .filter(near)
+            .flat_map(|b| {
+                b.transactions
+                    .iter()
+                    .map(|tx| crate::related::Candidate::from_tx(tx, b.height))
+            })
+            .collect()
+    }
+
+    /// Take the History lookup for the tx just opened (runtime side)
+    pub fn take_related_request(&mut self) -> Option<crate::related::RelatedQuery> {
+        self.pending_related.take()
+    }
+
+    /// Merge History's candidates; Details is redrawn in place if it still shows the tx
+    fn show_related(&mut self, hash: String, stored: Vec<crate::history::TxPersist>) {
+        let Some((tx, target)) = self.related_for.take() else {
+            return;
+        };
+        if target.hash != hash {
+            self.related_for = Some((tx, target));
+            return;
+        }
+        let mut candidates = self.nearby_candidates(target.height);
+        candidates.extend(stored.iter().map(crate::related::Candidate::from_stored));
+        self.related = crate::related::rank(&target, candidates);
+        let top = self.details_buf.current_scroll_line();
+        self.render_tx_details(&tx, target.height);
+        self.details_buf.scroll_to_line(top, self.details_viewport_lines);
+        self.related_for = Some((tx, target));
+    }
+
+    /// Related txs of the tx in Details (numbered from 1, as listed there)
+    pub fn related(&self) -> &[crate::related::RelatedTx] {
+        if self.related_for.is_some() {
+            &self.related
+        } else {
+            &[]
+        }
+    }
+
+    /// Open related tx `n` (1-based): selected in Txs when its block is loaded, else from History
+    pub fn open_related(&mut self, n: usize) {
+        let Some(r) = self.related().get(n.wrapping_sub(1)).cloned() else {
+            return;
+        };
+        self.count_feature("view.related");
+        let raw_json = r.raw_json.or_else(|| {
+            self.blocks
+                .iter()
+                .chain(self.cached_blocks.values())
+                .flat_map(|b| &b.transactions)
+                .find(|t| t.hash == r.hash)
+                .and_then(|t| serde_json::to_string(t).ok())
+        });
+        self.jump_to_tx(&r.hash, Some(r.height), raw_json.as_deref());
+    }
+
+    // ----- HTML report -----
+    /// Report the selected tx (tx list or Details focused) or block (`Shift+R`);
+    /// the runtime adds receipts and writes the file
+    pub fn request_report(&mut self, marks: &[crate::types::Mark]) {
+        self.count_feature("export.report_html");
+        let Some(height) = self.current_block().map(|b| b.height) else {
+            self.push_toast(ToastLevel::Warn, "No block selected".to_string());
+            return;
+        };
+        let tx_hash = if self.pane > 0 {
+            let (txs, sel, _) = self.txs();
+            txs.get(sel).map(|t| t.hash.clone())
+        } else {
+            None
+        };
+        let params = crate::exports::ExportParams::Report { height, tx_hash };
+        let path = std::path::PathBuf::from(params.file_name(&crate::exports::stamp()));
+        self.queue_report(params, path, marks);
+    }
+
+    /// Re-run a report export (exports overlay) while its block is still loaded
+    pub fn rerun_report(&mut self, record: &crate::exports::ExportRecord, marks: &[crate::types::Mark]) {
+        let path = record.rerun_path(&crate::exports::stamp());
+        self.queue_report(record.params.clone(), path, marks);
+    }
+
+    fn queue_report(
+        &mut self,
+        params: crate::exports::ExportParams,
+        path: std::path::PathBuf,
+        marks: &[crate::types::Mark],
+    ) {
+        let crate::exports::ExportParams::Report { height, tx_hash } = &params else {
+            return;
+        };
+        let Some(block) = self.block_by_height(*height) else {
+            self.push_toast(ToastLevel::Warn, format!("Block #{height} is no longer loaded"));
+            return;
+        };
+        let mut report = crate::html_report::Report {
+            generated: self.time_display.at(chrono::Utc::now().timestamp_millis()),
+            ..Default::default()
+        };
+        let (signer, rows) = match tx_hash {
+            Some(hash) => {
+                let Some(tx) = block.transactions.iter().find(|t| &t.hash == hash) else {
+                    self.push_toast(ToastLevel::Warn, format!("Tx {} is no longer loaded", &hash[..hash.len().min(8)]));
+                    return;
+                };
+                report.title = format!("Transaction {hash}");
+                report.facts = vec![
+                    ("Hash".into(), hash.clone()),
+                    ("Block".into(), format!("#{height} · {}", self.block_when(block))),
+                    ("Signer".into(), tx.signer_id.clone().unwrap_or_else(|| "—".into())),
+                    ("Receiver".into(), tx.receiver_id.clone().unwrap_or_else(|| "—".into())),
+                ];
+                let raw = serde_json::to_string(tx).unwrap_or_default();
+                report.details = crate::safe_text::raw_details(&raw, self.json_format);
+                (tx.signer_id.clone(), 1)
+            }
+            None => {
+                report.title = format!("Block #{height}");
+                report.facts = vec![
+                    ("Hash".into(), block.hash.clone()),
+                    ("Time".into(), self.block_when(block)),
+                    ("Transactions".into(), block.tx_count.to_string()),
+                ];
+                if let Some(gas) = block.gas_used {
+                    report.facts.push(("Gas used".into(), crate::anomaly::Metric::GasUsed.format(gas as f64)));
+                }
+                let value = serde_json::to_value(block).unwrap_or_default();
+                report.details = crate::json_pretty::pretty(&value, self.json_format);
+                (None, block.tx_count)
+            }
+        };
+
+        // Marks and notes on this block (block report) or tx (tx report)
+        let about = |h: Option<u64>, tx: &Option<String>| match tx_hash {
+            Some(hash) => tx.as_ref() == Some(hash) || (tx.is_none() && h == Some(*height)),
+            None => h == Some(*height),
+        };
+        for mark in marks.iter().filter(|m| about(m.height, &m.tx_hash)) {
+            report.annotations.push((
+                crate::pin_board::PinKind::Mark.label().to_string(),
+                format!("{} · {}", mark.label, self.time_display.at(mark.when_ms)),
+            ));
+        }
+        for item in self.pin_board.items(&[]) {
+            if item.kind == crate::pin_board::PinKind::Note && about(item.height, &item.tx_hash) {
+                report.annotations.push((
+                    item.kind.label().to_string(),
+                    format!("{} · {}", item.title, self.time_display.at(item.at_ms)),
+                ));
+            }
+        }
+
+        self.show_toast(format!("Writing {}…", params.label()));
+        self.pending_report = Some(crate::html_report::ReportJob {
+            report,
+            params,
+            path,
+            signer,
+            rows,
+        });
+    }
+
+    /// Take a gathered report (runtime side)
+    pub fn take_report_request(&mut self) -> Option<crate::html_report::ReportJob> {
+        self.pending_report.take()
+    }
+
+    // ----- Tx collection -----
+    /// Shift+I: the collection when there is one, else the import box
+    pub fn open_collection(&mut self) {
+        if self.collection.is_some() {
+            self.count_feature("overlay.collection");
+            self.input_mode = InputMode::Collection;
+        } else {
+            self.open_import();
+        }
+    }
+
+    pub fn close_collection(&mut self) {
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Open the import box (a new import replaces the collection on submit)
+    pub fn open_import(&mut self) {
+        self.count_feature("overlay.import");
+        self.import_draft.clear();
+        self.input_mode = InputMode::Import;
+    }
+
+    /// Esc in the import box: back to the collection, if any
+    pub fn close_import(&mut self) {
+        self.import_draft.clear();
+        self.input_mode = if self.collection.is_some() {
+            InputMode::Collection
+        } else {
+            InputMode::Normal
+        };
+    }
+
+    pub fn import_draft(&self) -> &str {
+        &self.import_draft
+    }
+
+    pub fn import_add_char(&mut self, c: char) {
+        self.import_draft.push(c);
+    }
+
+    /// Bracketed paste into the import box (newlines kept)
+    pub fn import_paste(&mut self, text: &str) {
+        if !self.import_draft.is_empty() && !self.import_draft.ends_with(char::is_whitespace) {
+            self.import_draft.push('\n');
+        }
+        self.import_draft.push_str(text);
+    }
+
+    pub fn import_backspace(&mut self) {
+        self.import_draft.pop();
+    }
+
+    /// Enter in the import box: parse the draft into a new collection and queue
+    /// its hashes for resolution
+    pub fn import_submit(&mut self) {
+        let (mut hashes, rejected) = crate::collection::parse_hashes(&self.import_draft);
+        if hashes.is_empty() {
+            self.push_toast(ToastLevel::Warn, "No tx hashes found in the pasted text".to_string());
+            return;
+        }
+        if let Some(total) = crate::collection::cap_import(&mut hashes) {
+            self.push_toast(
+                ToastLevel::Warn,
+                format!(
+                    "Importing the first {} of {total} hashes",
+                    crate::collection::MAX_IMPORT
+                ),
+            );
+        }
+        self.import_hashes(hashes, rejected.len());
+        self.import_draft.clear();
+    }
+
+    /// Replace the collection with `hashes` (all pending) and show it
+    fn import_hashes(&mut self, hashes: Vec<String>, rejected: usize) {
+        self.show_toast(format!("Resolving {} txs…", hashes.len()));
+        self.collection = Some(crate::collection::Collection::new(hashes.clone(), rejected));
+        self.pending_import = Some(hashes);
+        self.input_mode = InputMode::Collection;
+    }
+
+    /// Re-run a collection export (exports overlay): re-import its hashes;
+    /// `e` exports once they are resolved
+    pub fn rerun_collection(&mut self, record: &crate::exports::ExportRecord) {
+        if let crate::exports::ExportParams::Collection { hashes } = &record.params {
+            self.import_hashes(hashes.clone(), 0);
+        }
+    }
+
+    /// Take hashes awaiting resolution (runtime side)
+    pub fn take_import_request(&mut self) -> Option<Vec<String>> {
+        self.pending_import.take()
+    }
+
+    /// One hash resolved (results for a replaced collection are dropped)
+    pub fn collection_resolved(&mut self, resolution: crate::collection::Resolution) {
+        if let Some(collection) = &mut self.collection {
+            collection.apply(resolution);
+        }
+    }
+
+    pub fn collection(&self) -> Option<&crate::collection::Collection> {
+        self.collection.as_ref()
+    }
+
+    pub fn collection_up(&mut self) {
+        if let Some(collection) = &mut self.collection {
+            collection.up();
+        }
+    }
+
+    pub fn collection_down(&mut self) {
+        if let Some(collection) = &mut self.collection {
+            collection.down();
+        }
+    }
+
+    pub fn collection_toggle_checked(&mut self) {
+        if let Some(collection) = &mut self.collection {
+            collection.toggle_checked();
+        }
+    }
+
+    pub fn collection_toggle_all(&mut self) {
+        if let Some(collection) = &mut self.collection {
+            collection.toggle_all();
+        }
+    }
+
+    /// Enter: open the selected tx in the Txs pane / Details
+    pub fn collection_open_selected(&mut self) {
+        let Some(item) = self.collection.as_ref().and_then(|c| c.selected()).cloned() else {
+            return;
+        };
+        let resolved = item.resolved();
+        let height = resolved.and_then(|r| r.height);
+        let raw_json = resolved.and_then(|r| r.raw_json.clone());
+        if self.jump_to_tx(&item.hash, height, raw_json.as_deref()) {
+            self.close_collection();
+        }
+    }
+
+    /// Bulk-mark targets: (hash, height) of checked (or all) found txs
+    pub fn collection_mark_targets(&self) -> Vec<(String, Option<u64>)> {
+        self.collection
+            .as_ref()
+            .map(|c| {
+                c.targets()
+                    .into_iter()
+                    .filter_map(|i| i.resolved().map(|r| (i.hash.clone(), r.height)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Markdown of checked (or all) items and the export record for `path`
+    pub fn collection_export(
+        &mut self,
+        path: &std::path::Path,
+    ) -> Option<(String, crate::exports::ExportRecord)> {
+        let collection = self.collection.as_ref()?;
+        let now = chrono::Utc::now().timestamp_millis();
+        let markdown = collection.to_markdown(now, &self.time_display);
+        let hashes: Vec<String> = collection.targets().iter().map(|i| i.hash.clone()).collect();
+        let record = crate::exports::ExportRecord::new(
+            path,
+            crate::exports::ExportParams::Collection {
+                hashes: hashes.clone(),
+            },
+            hashes.len(),
+            now,
+        );
+        self.count_feature("export.collection_md");
+        Some((markdown, record))
+    }
+
+    // ----- Tx timeline -----
+    /// Ask the runtime to trace the selected tx's receipts (`t`)
+    pub fn request_timeline(&mut self) {
+        self.count_feature("view.timeline");
+        let (txs, sel, _) = self.txs();
+        let Some(tx) = txs.get(sel) else {
+            self.push_toast(ToastLevel::Warn, "No transaction selected".to_string());
+            return;
+        };
+        let Some(signer) = tx.signer_id.clone() else {
+            self.push_toast(ToastLevel::Warn, "Timeline needs the tx signer".to_string());
+            return;
+        };
+        let hash = tx.hash.clone();
+        self.show_toast(format!("Tracing {}…", &hash[..hash.len().min(8)]));
+        self.pending_timeline = Some((hash, signer));
+    }
+
+    /// Take a requested trace (runtime side): `(tx hash, signer)`
+    pub fn take_timeline_request(&mut self) -> Option<(String, String)> {
+        self.pending_timeline.take()
+    }
+
+    /// Render a finished trace into Details if its tx is still selected
+    fn show_timeline(&mut self, hash: String, result: Result<crate::timeline::TxTimeline, String>) {
+        match result {
+            Ok(timeline) => {
+                let (txs, sel, _) = self.txs();
+                if txs.get(sel).map(|t| t.hash.as_str()) != Some(hash.as_str()) {
+                    self.log_debug(format!("[timeline] {hash} no longer selected, dropped"));
+                    return;
+                }
+                let msg = match timeline.parked_yields() {
+                    0 => format!("Timeline: {} events", timeline.events.len()),
+                    n => format!("Timeline: {} events · {n} waiting on a yield", timeline.events.len()),
+                };
+                self.push_toast(ToastLevel::Success, msg);
+                self.render_timeline(timeline, false);
+            }
+            Err(e) => self.push_toast(ToastLevel::Error, format!("Timeline failed: {e}")),
+        }
+    }
+
+    /// Whether Details shows a timeline with failed outcomes (`e` expands them)
+    pub fn timeline_has_errors(&self) -> bool {
+        self.timeline.as_ref().is_some_and(|(t, _)| t.has_errors())
+    }
+
+    /// Show / hide the original JSON under each failed timeline outcome (`e`)
+    pub fn toggle_timeline_errors(&mut self) {
+        if let Some((timeline, expanded)) = self.timeline.take() {
+            self.render_timeline(timeline, !expanded);
+        }
+    }
+
+    fn render_timeline(&mut self, timeline: crate::timeline::TxTimeline, expand_errors: bool) {
+        self.set_details_json(timeline.render_lines(expand_errors, self.json_format).join("\n"));
+        // set_details_json drops any timeline; this one stays for `e`
+        self.timeline = Some((timeline, expand_errors));
+    }
+
+    // ----- Gas profiler -----
+    /// Ask the runtime for the selected tx's receipt gas tree (`G`)
+    pub fn request_gas_profile(&mut self) {
+        self.count_feature("view.gas_profile");
+        let (txs, sel, _) = self.txs();
+        let Some(tx) = txs.get(sel) else {
+            self.push_toast(ToastLevel::Warn, "No transaction selected".to_string());
+            return;
+        };
+        let Some(signer) = tx.signer_id.clone() else {
+            self.push_toast(ToastLevel::Warn, "Gas profile needs the tx signer".to_string());
+            return;
+        };
+        let hash = tx.hash.clone();
+        self.show_toast(format!("Profiling gas for {}…", &hash[..hash.len().min(8)]));
+        self.pending_gas_profile = Some((hash, signer));
+    }
+
+    // ----- Contract digest -----
+    /// Digest the selected tx's receiver (Ctrl+G); the runtime builds it from History
+    pub fn request_digest(&mut self) {
+        self.count_feature("overlay.digest");
+        let (txs, sel, _) = self.txs();
+        let Some(contract) = txs.get(sel).and_then(|t| t.receiver_id.clone()) else {
+            self.push_toast(ToastLevel::Warn, "Select a transaction to digest its receiver".to_string());
+            return;
+        };
+        self.input_mode = InputMode::Digest;
+        self.request_digest_for(contract);
+    }
+
+    fn request_digest_for(&mut self, contract: String) {
+        self.digest = None;
+        self.digest_scroll = 0;
+        self.digest_target = Some((contract.clone(), self.digest_window));
+        self.pending_digest = Some((contract, self.digest_window));
+    }
+
+    /// Rebuild the open digest over the next window (1h / 24h / 7d)
+    pub fn digest_cycle_window(&mut self) {
+        self.digest_window = crate::digest::next_window(self.digest_window);
+        if let Some((contract, _)) = self.digest_target.clone() {
+            self.request_digest_for(contract);
+        }
+    }
+
+    /// Take a requested digest (runtime side): `(contract, window secs)`
+    pub fn take_digest_request(&mut self) -> Option<(String, u64)> {
+        self.pending_digest.take()
+    }
+
+    /// Show a built digest if it is still the one the overlay waits for
+    fn show_digest(&mut self, digest: crate::digest::ContractDigest) {
+        let wanted = self
+            .digest_target
+            .as_ref()
+            .is_some_and(|(c, w)| *c == digest.contract && *w == digest.window_secs);
+        if self.input_mode != InputMode::Digest || !wanted {
+            self.log_debug(format!("[digest] {} no longer requested, dropped", digest.contract));
+            return;
+        }
+        self.digest = Some(digest);
+    }
+
+    /// `(contract, window secs)` of the open digest
+    pub fn digest_target(&self) -> Option<(&str, u64)> {
+        self.digest_target.as_ref().map(|(c, w)| (c.as_str(), *w))
+    }
+
+    /// Built digest (`None` while the runtime works on it)
+    pub fn digest(&self) -> Option<&crate::digest::ContractDigest> {
+        self.digest.as_ref()
+    }
+
+    pub fn digest_scroll(&self) -> u16 {
+        self.digest_scroll
+    }
+
+    pub fn digest_scroll_by(&mut self, delta: i32) {
+        self.digest_scroll = (self.digest_scroll as i32 + delta).max(0) as u16;
+    }
+
+    pub fn close_digest(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.digest_target = None;
+        self.pending_digest = None;
+        self.digest = None;
+    }
+
+    // ----- Contract state inspector -----
+    /// Inspect the selected tx's receiver storage (Ctrl+K)
+    pub fn open_state_view(&mut self) {
+        self.count_feature("overlay.state");
+        let (txs, sel, _) = self.txs();
+        let Some(tx) = txs.get(sel) else {
+            self.push_toast(ToastLevel::Warn, "Select a transaction to inspect its receiver".to_string());
+            return;
+        };
+        let Some(account) = tx.receiver_id.clone() else {
+            self.push_toast(ToastLevel::Warn, "Select a transaction to inspect its receiver".to_string());
+            return;
+        };
+        // The Holders tab ranks the signer among the receiver's token holders
+        let signer = tx.signer_id.clone();
+        self.input_mode = InputMode::State;
+        self.holders_tab = Some(crate::holders::HoldersTab {
+            token: account.clone(),
+            account: signer,
+            ..Default::default()
+        });
+        self.holders_tab_open = false;
+        self.request_state(account, Vec::new());
+    }
+
+    fn request_state(&mut self, account: String, prefix: Vec<u8>) {
+        let mut view = crate::state_view::StateInspector::new(account.clone(), prefix.clone());
+        view.json_format = self.json_format;
+        self.state_view = Some(view);
+        self.pending_state = Some((account, prefix));
+    }
+
+    /// Take a requested `view_state` (runtime side): `(account, key prefix)`
+    pub fn take_state_request(&mut self) -> Option<(String, Vec<u8>)> {
+        self.pending_state.take()
+    }
+
+    fn show_state(
+        &mut self,
+        account: String,
+        prefix: Vec<u8>,
+        result: Result<crate::state_view::ContractState, String>,
+    ) {
+        let wanted = self
+            .state_view
+            .as_ref()
+            .is_some_and(|v| v.account == account && v.prefix == prefix);
+        if !wanted {
+            self.log_debug(format!("[state] {account} no longer inspected, dropped"));
+            return;
+        }
+        if let Err(e) = &result {
+            self.log_debug(format!("[state] view_state {account} failed: {e}"));
+        }
+        if let Some(view) = self.state_view.as_mut() {
+            view.result = Some(result);
+        }
+    }
+
+    pub fn state_view(&self) -> Option<&crate::state_view::StateInspector> {
+        self.state_view.as_ref()
+    }
+
+    /// Tree navigation, paging and prefix editing for the open inspector
+    pub fn state_view_mut(&mut self) -> Option<&mut crate::state_view::StateInspector> {
+        self.state_view.as_mut()
+    }
+
+    /// Refetch under the typed prefix (`0x…` for hex)
+    pub fn state_prefix_commit(&mut self) {
+        let Some(view) = self.state_view.as_mut() else {
+            return;
+        };
+        let Some(draft) = view.prefix_draft.take() else {
+            return;
+        };
+        match crate::state_view::parse_prefix(&draft) {
+            Ok(prefix) => {
+                let account = view.account.clone();
+                self.request_state(account, prefix);
+            }
+            Err(e) => self.push_toast(ToastLevel::Error, e.to_string()),
+        }
+    }
+
+    pub fn close_state_view(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.state_view = None;
+        self.pending_state = None;
+        self.holders_tab = None;
+        self.holders_tab_open = false;
+        self.pending_holders = None;
+    }
+
+    // ----- History browser -----
+    /// Open the History browser (Ctrl+B); the live buffer, selection and filter are untouched.
+    /// Returns the page to load, if none is on screen yet.
+    pub fn open_history_browser(&mut self) -> Option<crate::history_browser::BrowseCursor> {
+        self.count_feature("overlay.history_browser");
+        self.input_mode = InputMode::HistoryBrowser;
+        self.history_browser
+            .blocks
+            .is_empty()
+            .then_some(crate::history_browser::BrowseCursor::Newest)
+    }
+
+    /// Close the overlay; range, page and selection are kept for the next visit
+    pub fn close_history_browser(&mut self) {
+        self.input_mode = InputMode::Normal;
+    }
+
+    pub fn history_browser(&self) -> &crate::history_browser::HistoryBrowser {
+        &self.history_browser
+    }
+
+    pub fn history_browser_mut(&mut self) -> &mut crate::history_browser::HistoryBrowser {
+        &mut self.history_browser
+    }
+
+    // ----- FT holders (inspector tab) -----
+    /// Switch the inspector between State and Holders; the first visit looks the holders up
+    pub fn toggle_holders_tab(&mut self) {
+        self.holders_tab_open = !self.holders_tab_open;
+        if self.holders_tab_open && self.holders_tab.as_ref().is_some_and(|t| t.result.is_none()) {
+            self.count_feature("view.holders");
+            self.request_holders(false);
+        }
+    }
+
+    /// Holders tab when it is the one showing
+    pub fn holders_tab(&self) -> Option<&crate::holders::HoldersTab> {
+        self.holders_tab.as_ref().filter(|_| self.holders_tab_open)
+    }
+
+    pub fn holders_tab_mut(&mut self) -> Option<&mut crate::holders::HoldersTab> {
+        self.holders_tab.as_mut().filter(|_| self.holders_tab_open)
+    }
+
+    /// Look the tab's token up: from the cache when fresh (unless `refresh`),
+    /// otherwise queued for the runtime
+    pub fn request_holders(&mut self, refresh: bool) {
+        let Some(tab) = self.holders_tab.as_mut() else {
+            return;
+        };
+        if refresh {
+            self.holders_cache.invalidate(&tab.token);
+        }
+        tab.result = self
+            .holders_cache
+            .get(&tab.token, tab.account.as_deref(), Instant::now())
+            .cloned()
+            .map(Ok);
+        tab.selection = 0;
+        if tab.result.is_none() {
+            self.pending_holders = Some((tab.token.clone(), tab.account.clone()));
+        }
+    }
+
+    /// Take a queued lookup (runtime side) once the request spacing allows: `(token, account)`
+    pub fn take_holders_request(&mut self) -> Option<(String, Option<String>)> {
+        if self.pending_holders.is_none() || !self.holders_cache.try_request(Instant::now()) {
+            return None;
+        }
+        self.pending_holders.take()
+    }
+
+    fn show_holders(
+        &mut self,
+        token: String,
+        account: Option<String>,
+        result: Result<crate::holders::TokenHolders, String>,
+    ) {
+        match &result {
+            Ok(holders) => self.holders_cache.insert(holders.clone(), Instant::now()),
+            Err(e) => self.log_debug(format!("[holders] {token} lookup failed: {e}")),
+        }
+        let Some(tab) = self
+            .holders_tab
+            .as_mut()
+            .filter(|t| t.token == token && t.account == account)
+        else {
+            return;
+        };
+        tab.result = Some(result);
+        tab.selection = 0;
+    }
+
+    /// Jump to the selected holder: filter on the account and close the inspector
+    pub fn holders_jump_selected(&mut self) {
+        let Some(account) = self
+            .holders_tab()
+            .and_then(|t| t.selected())
+            .map(|h| h.account.clone())
+        else {
+            return;
+        };
+        self.close_state_view();
+        self.set_filter_query(format!("acct:{account}"));
+        self.push_toast(ToastLevel::Info, format!("Filter: acct:{account}"));
+    }
+
+    // ----- Telemetry -----
+    pub fn set_telemetry(&mut self, telemetry: crate::telemetry::Telemetry) {
+        self.telemetry = telemetry;
+    }
+
+    pub fn telemetry(&self) -> &crate::telemetry::Telemetry {
+        &self.telemetry
+    }
+
+    pub fn telemetry_mut(&mut self) -> &mut crate::telemetry::Telemetry {
+        &mut self.telemetry
+    }
+
+    /// Count one use of a feature (only when telemetry is opted in)
+    pub fn count_feature(&mut self, counter: &'static str) {
+        self.telemetry
+            .record(counter, chrono::Utc::now().timestamp_millis());
+    }
+
+    fn count_pane(&mut self) {
+        self.count_feature(match self.pane {
+            0 => "pane.blocks",
+            1 => "pane.txs",
+            _ => "pane.details",
+        });
+    }
+
+    /// Preview of the telemetry report (Ctrl+T)
+    pub fn open_telemetry(&mut self) {
+        self.telemetry_scroll = 0;
+        self.input_mode = InputMode::Telemetry;
+    }
+
+    pub fn telemetry_scroll(&self) -> u16 {
+        self.telemetry_scroll
+    }
+
+    pub fn telemetry_scroll_by(&mut self, delta: i32) {
+        self.telemetry_scroll = (self.telemetry_scroll as i32 + delta).max(0) as u16;
+    }
+
+    /// Drop the in-memory counters (the runtime clears the saved copy)
+    pub fn purge_telemetry(&mut self) {
+        self.telemetry.purge();
+        self.telemetry_scroll = 0;
+        self.push_toast(ToastLevel::Success, "Telemetry counters purged".to_string());
+    }
+
+    pub fn close_telemetry(&mut self) {
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Take a requested profile (runtime side): `(tx hash, signer)`
+    pub fn take_gas_profile_request(&mut self) -> Option<(String, String)> {
+        self.pending_gas_profile.take()
+    }
+
+    /// Render a fetched profile into Details if its tx is still selected
+    fn show_gas_profile(&mut self, hash: String, result: Result<crate::gas_profile::GasProfile, String>) {
+        match result {
+            Ok(profile) => {
+                let (txs, sel, _) = self.txs();
+                if txs.get(sel).map(|t| t.hash.as_str()) != Some(hash.as_str()) {
+                    self.log_debug(format!("[gas_profile] {hash} no longer selected, dropped"));
+                    return;
+                }
+                self.push_toast(
+                    ToastLevel::Success,
+                    format!(
+                        "Gas profile: {} over {} receipts",
+                        crate::gas_profile::format_tgas(profile.total_gas()),
+                        profile.nodes.len() - 1
+                    ),
+                );
+                self.render_gas_profile(profile);
+            }
+            Err(e) => self.push_toast(ToastLevel::Error, format!("Gas profile failed: {e}")),
+        }
+    }
+
+    /// Whether Details currently shows a gas profile (digits drill in / out)
+    pub fn gas_profile_active(&self) -> bool {
+        self.gas_profile.is_some()
+    }
+
+    /// Drill into the `n`th hop under the current focus (1-9)
+    pub fn gas_profile_drill(&mut self, n: usize) {
+        let Some(mut profile) = self.gas_profile.take() else {
+            return;
+        };
+        if !profile.drill(n) {
+            self.show_toast(format!("No hop [{n}] here"));
+        }
+        self.render_gas_profile(profile);
+    }
+
+    /// Back out one level (0)
+    pub fn gas_profile_back(&mut self) {
+        let Some(mut profile) = self.gas_profile.take() else {
+            return;
+        };
+        profile.back();
+        self.render_gas_profile(profile);
+    }
+
+    fn render_gas_profile(&mut self, profile: crate::gas_profile::GasProfile) {
+        self.set_details_json(profile.render_lines().join("\n"));
+        // set_details_json drops any profile; this one stays live for drill-down
+        self.gas_profile = Some(profile);
+    }
+
+    // ----- Warm start -----
+    /// Seed the ring with the last session's blocks (newest first); rows stay
+    /// marked stale until a live or archival copy of the same height arrives
+    pub fn load_warm_blocks(&mut self, mut blocks: Vec<BlockRow>) {
+        if !self.blocks.is_empty() || blocks.is_empty() {
+            return;
+        }
+        blocks.sort_by(|a, b| b.height.cmp(&a.height));
+        blocks.truncate(self.keep_blocks);
+        self.stale_heights = blocks.iter().map(|b| b.height).collect();
+        let count = blocks.len();
+        self.blocks = blocks;
+        self.touch_blocks();
+        self.show_toast(format!("Warm start: {count} blocks from last session (stale until refreshed)"));
+    }
+
+    // ----- Staged startup (native) -----
+
+    /// Track startup stages from `started` (process start); shown in the status bar until done
+    pub fn begin_startup(&mut self, started: Instant) {
+        self.startup = Some((started, crate::startup::StartupProgress::default()));
+    }
+
+    /// A startup stage finished; logs the timings once all have
+    pub fn startup_stage_done(&mut self, stage: crate::startup::StartupStage) {
+        let Some((started, progress)) = self.startup.as_mut() else {
+            return;
+        };
+        if !progress.complete(stage, started.elapsed()) || !progress.is_complete() {
+            return;
+        }
+        let frame = progress.elapsed(crate::startup::StartupStage::Frame).unwrap_or_default();
+        let summary = progress.summary();
+        self.startup = None;
+        if frame > crate::startup::FIRST_FRAME_TARGET {
+            log::warn!("[startup] first frame took {}ms ({summary})", frame.as_millis());
+        } else {
+            log::info!("[startup] {summary}");
+        }
+        self.log_debug(format!("[startup] {summary}"));
+    }
+
+    /// Last session's state, read from History after the first frame
+    fn apply_startup_load(&mut self, load: crate::startup::StartupLoad) {
+        self.load_warm_blocks(load.warm_blocks);
+        self.set_watch_rules(load.watch_rules);
+        // Counters from earlier sessions keep accumulating on top of this one's
+        if let Some((since_ms, counters)) = load.telemetry {
+            self.telemetry.restore(since_ms, counters);
+        }
+        if let Some(hint) = load.fetch_hint {
+            self.push_toast(ToastLevel::Warn, hint);
+        }
+        self.startup_stage_done(crate::startup::StartupStage::History);
+    }
+
+    /// Blocks to persist for the next warm start
+    pub fn warm_blocks(&self) -> &[BlockRow] {
+        &self.blocks
+    }
+
+    /// True if this row came from the last session and hasn't been re-fetched
+    pub fn is_block_stale(&self, height: u64) -> bool {
+        self.stale_heights.contains(&height)
+    }
+
+    // ----- Details buffer API -----
+
+    /// Set Details pane content (replaces full buffer)
+    pub fn set_details_json(&mut self, json: String) {
+        self.gas_profile = None;
+        self.timeline = None;
+        self.related_for = None;
+        self.details_buf.set_text(json);
+    }
+
+    /// Set viewport size (called by renderer based on pane height)
+    pub fn set_details_viewport_lines(&mut self, n: usize) {
+        self.details_viewport_lines = n.max(1);
+    }
+
+    /// Get viewport size (for key handling)
+    pub fn details_viewport_lines(&self) -> usize {
+        self.details_viewport_lines
+    }
+
+    /// Get windowed view of Details (for rendering)
+    pub fn details_window(&self) -> String {
+        self.details_buf.window(self.details_viewport_lines)
+    }
+
+    /// Colored window of Details for the TUI (chunks cached around the viewport)
+    pub fn details_colored_window(&mut self) -> Vec<ratatui::text::Line<'static>> {
+        let theme = self.theme;
+        self.details_buf
+            .colored_window(self.details_viewport_lines, &theme)
+    }
+
+    /// Check if details content was truncated
+    pub fn details_truncated(&self) -> bool {
+        self.details_buf.truncated()
+    }
+
+    /// Get full Details text (for copy operations)
+    pub fn details_full_text(&self) -> &str {
+        self.details_buf.full_text()
+    }
+
+    /// Get details as pretty-printed string (legacy compatibility)
+    pub fn details_pretty_string(&self) -> String {
+        self.details_buf.full_text().to_string()
+    }
+
+    /// Get details as raw JSON string (legacy compatibility)
+    pub fn details_raw_string(&self) -> String {
+        self.details_buf.full_text().to_string()
+    }
+
+    /// Scroll Details by delta lines
+    pub fn scroll_details_lines(&mut self, delta: isize) {
+        self.details_buf.scroll_lines(delta, self.details_viewport_lines);
+    }
+
+    /// Jump to top of Details
+    pub fn details_home(&mut self) {
+        self.details_buf.scroll_to_top();
+    }
+
+    /// Jump to bottom of Details
+    pub fn details_end(&mut self) {
+        self.details_buf.scroll_to_bottom(self.details_viewport_lines);
+    }
+
+    /// Get scroll info for status display
+    pub fn details_scroll_info(&self) -> (usize, usize) {
+        (self.details_buf.current_scroll_line(), self.details_buf.total_lines())
+    }
+
+    /// Jump so that 1-based `line` is at the top of Details
+    pub fn details_goto_line(&mut self, line: usize) {
+        self.details_buf
+            .scroll_to_line(line.saturating_sub(1), self.details_viewport_lines);
+    }
+
+    /// Jump to `percent` (0-100) of the Details document
+    pub fn details_goto_percent(&mut self, percent: usize) {
+        let total = self.details_buf.total_lines();
+        let line = total.saturating_sub(1) * percent.min(100) / 100;
+        self.details_buf
+            .scroll_to_line(line, self.details_viewport_lines);
+    }
+
+    /// `}` / `{`: next / previous top-level JSON key (paragraph for plain text)
+    pub fn details_jump_section(&mut self, forward: bool) {
+        if let Some(line) = self.details_buf.section_start(forward) {
+            self.details_buf
+                .scroll_to_line(line, self.details_viewport_lines);
+        }
+    }
+
+    /// Jump prompt being typed (`:` or a digit in Details)
+    pub fn details_goto_draft(&self) -> Option<&str> {
+        self.details_goto.as_deref()
+    }
+
+    pub fn details_goto_start(&mut self, prefill: &str) {
+        self.details_goto = Some(prefill.to_string());
+    }
+
+    pub fn details_goto_add_char(&mut self, c: char) {
+        if let Some(draft) = self.details_goto.as_mut() {
+            if c.is_ascii_digit() && draft.len() < 9 {
+                draft.push(c);
+            }
+        }
+    }
+
+    /// Backspace; an empty prompt closes
+    pub fn details_goto_backspace(&mut self) {
+        if let Some(draft) = self.details_goto.as_mut() {
+            if draft.pop().is_none() {
+                self.details_goto = None;
+            }
+        }
+    }
+
+    /// Jump to the typed line (Enter) or percentage (`%`) and close the prompt
+    pub fn details_goto_commit(&mut self, percent: bool) {
+        let Some(n) = self.details_goto.take().and_then(|d| d.parse::<usize>().ok()) else {
+            return;
+        };
+        if percent {
+            self.details_goto_percent(n);
+        } else {
+            self.details_goto_line(n);
+        }
+    }
+
+    pub fn details_goto_cancel(&mut self) {
+        self.details_goto = None;
+    }
+
+    /// Get JSON for currently focused pane (for copy operation)
+    pub fn focused_json_string(&self) -> Option<String> {
+        Some(self.get_copy_content())
+    }
+
+    /// Get display-ready list of blocks (filtered if filter active)
+    pub fn blocks_for_display(&self) -> Vec<&BlockRow> {
+        let (blocks, _sel, _total) = self.filtered_blocks();
+        blocks
+    }
+
+    /// Get display-ready list of transactions from current block (filtered)
+    pub fn txs_for_display(&self) -> Vec<&TxLite> {
+        if let Some(b) = self.current_block() {
+            b.transactions
+                .iter()
+                .zip(self.block_filter_mask(b))
+                .filter(|(_, pass)| *pass)
+                .map(|(tx, _)| tx)
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Get lightweight block data for display (owned copy)
+    /// Returns None if index out of bounds
+    pub fn block_lite(&self, idx: usize) -> Option<BlockLite> {
+        self.blocks_for_display().get(idx).map(|b| BlockLite {
+            height: b.height,
+            tx_count: b.tx_count,
+            when: self.block_when(b),
+            time: self.time_display.time(b.timestamp as i64),
+        })
+    }
+
+    /// Get lightweight transaction data for display (owned copy)
+    /// Returns None if index out of bounds
+    pub fn tx_lite(&self, idx: usize) -> Option<TxLite> {
+        self.txs_for_display().get(idx).map(|tx| (*tx).clone())
+    }
+
+    /// Get count of filtered blocks
+    pub fn filtered_blocks_len(&self) -> usize {
+        self.blocks_for_display().len()
+    }
+
+    /// Get count of filtered transactions in current block
+    pub fn filtered_txs_len(&self) -> usize {
+        self.txs_for_display().len()
+    }
+
+    /// Handle mouse click at terminal coordinates
+    pub fn handle_mouse_click(&mut self, col: u16, row: u16) {
+        // Determine which pane was clicked based on layout
+        // The layout is approximately:
+        // - Header: 2 rows
+        // - Filter bar: 1-3 rows (let's assume 2 average)
+        // - Top 30%: Blocks (left 50%) + Txs (right 50%)
+        // - Bottom 70%: Details
+
+        // Skip header and filter (approximately 4 rows)
+        if row < 4 {
+            return;
+        }
+
+        // Get terminal size from last draw (this is approximate)
+        // In real implementation, we'd pass the actual layout areas
         let term_height: u16 = 40; // Default assumption, will be resized dynamically
         let body_start: u16 = 4;
         let body_height = term_height.saturating_sub(body_start + 1); // -1 for footer
@@ -2247,29 +5247,8 @@ impl App {
 pub struct BlockLite {
     pub height: u64,
     pub tx_count: usize,
-    pub when: String,     // Relative time ("2s ago")
-    pub time_utc: String, // UTC timestamp
-}
-
-/// Format timestamp as UTC string
-fn format_timestamp_utc(timestamp: u64) -> String {
-    #[cfg(not(target_arch = "wasm32"))]
-    {
-        use chrono::{TimeZone, Utc};
-        let secs = (timestamp / 1_000_000_000) as i64;
-        let nsecs = (timestamp % 1_000_000_000) as u32;
-        match Utc.timestamp_opt(secs, nsecs) {
-            chrono::LocalResult::Single(dt) => dt.format("%H:%M:%S").to_string(),
-            _ => format!("{}s", secs),
-        }
-    }
-
-    #[cfg(target_arch = "wasm32")]
-    {
-        // Simplified timestamp for WASM (just show seconds)
-        let secs = (timestamp / 1_000_000_000) as i64;
-        format!("{}s", secs)
-    }
+    pub when: String, // Full timestamp in the current zone
+    pub time: String, // Time of day in the current zone
 }
 
 impl App {
@@ -1,6 +1,11 @@
 // Native-only archival fetch task (uses tokio full runtime + blocking I/O)
 #[cfg(feature = "native")]
-use crate::{config::Config, rpc_utils::fetch_block_with_txs, types::AppEvent};
+use crate::{
+    config::Config,
+    rpc_utils::fetch_block_with_txs,
+    source_health::{SourceKind, SourceReport},
+    types::AppEvent,
+};
 #[cfg(feature = "native")]
 use anyhow::Result;
 #[cfg(feature = "native")]
@@ -58,6 +63,10 @@ pub async fn run_archival_fetch(
                     "[Archival] Successfully fetched block #{} ({} txs)",
                     height, block.tx_count
                 );
+                let _ = block_tx.send(AppEvent::SourceStatus {
+                    kind: SourceKind::Archival,
+                    report: SourceReport::Ok,
+                });
                 // Send block via existing event channel
                 if let Err(e) = block_tx.send(AppEvent::NewBlock(block)) {
                     log::error!("[Archival] Failed to send block: {e}");
@@ -66,8 +75,11 @@ pub async fn run_archival_fetch(
             }
             Err(e) => {
                 log::error!("[Archival] Failed to fetch block #{height}: {e}");
-                // TODO: Send error event to App so it can show toast notification
-                // For now, just log the error
+                // Surfaces as the Archival source badge (and a toast once it's down)
+                let _ = block_tx.send(AppEvent::SourceStatus {
+                    kind: SourceKind::Archival,
+                    report: SourceReport::Error(format!("#{height}: {e:#}")),
+                });
             }
         }
     }
@@ -165,6 +165,11 @@ fn parse_block_row_from_rpc(result: &serde_json::Value, height: u64) -> Result<B
         .get("prev_hash")
         .and_then(|v| v.as_str())
         .map(|s| s.to_string());
+    let gas_price = header
+        .get("gas_price")
+        .and_then(|v| v.as_str())
+        .and_then(|s| s.parse::<u128>().ok());
+    let gas_used = crate::rpc_utils::chunks_gas_used(&result);
 
     Ok(BlockRow {
         height,
@@ -175,5 +180,7 @@ fn parse_block_row_from_rpc(result: &serde_json::Value, height: u64) -> Result<B
         tx_count,
         when,
         transactions,
+        gas_price,
+        gas_used,
     })
 }
@@ -0,0 +1,99 @@
+//! History backfill (`nearx backfill [--outcomes]`)
+//!
+//! Brings stored txs up to what History keeps today, so search, exports and
+//! the account views work offline. Rows written before signer / receiver /
+//! actions were stored get them decoded from their `raw_json`, and final
+//! statuses already in `receipt_outcomes` are copied onto the tx rows. With
+//! `--outcomes`, the newest txs still without a status get their final outcome
+//! over RPC (through the outcome cache, which stores the receipts too).
+
+use anyhow::Result;
+
+use crate::config::{BackfillArgs, Config};
+use crate::history::{BackfillStats, History};
+use crate::outcome_cache::Outcomes;
+
+/// Outcome fetches in flight at once
+const CONCURRENCY: usize = 4;
+
+/// Progress line on stderr every N outcomes
+const PROGRESS_EVERY: usize = 100;
+
+/// Fetch final outcomes for up to `limit` txs without one; returns (fetched, failed)
+async fn fetch_outcomes(cfg: &Config, history: &History, limit: usize) -> (usize, usize) {
+    let pending = history.txs_without_status(limit).await;
+    if pending.is_empty() {
+        return (0, 0);
+    }
+    eprintln!("nearx backfill: fetching {} outcomes", pending.len());
+    let url = cfg.archival_or_rpc_url();
+    let outcomes = Outcomes::new(Some(history.clone()));
+
+    let mut set = tokio::task::JoinSet::new();
+    let (mut fetched, mut failed) = (0, 0);
+    let mut pending = pending.into_iter();
+    loop {
+        // Keep CONCURRENCY fetches in flight
+        while set.len() < CONCURRENCY {
+            let Some((hash, signer)) = pending.next() else {
+                break;
+            };
+            let (outcomes, url, token) = (
+                outcomes.clone(),
+                url.clone(),
+                cfg.fastnear_auth_token.clone(),
+            );
+            let timeout_ms = cfg.rpc_timeout_ms;
+            set.spawn(async move {
+                let res = outcomes
+                    .tx_status(&url, &hash, &signer, "FINAL", timeout_ms, token.as_deref())
+                    .await;
+                (hash, res)
+            });
+        }
+        let Some(joined) = set.join_next().await else {
+            break;
+        };
+        match joined {
+            Ok((_, Ok(_))) => fetched += 1,
+            Ok((hash, Err(e))) => {
+                // One line for the first error; the summary has the count
+                if failed == 0 {
+                    eprintln!("nearx backfill: {hash}: {e:#}");
+                }
+                failed += 1;
+            }
+            Err(_) => failed += 1,
+        }
+        if (fetched + failed) % PROGRESS_EVERY == 0 {
+            eprintln!("nearx backfill: {} outcomes", fetched + failed);
+        }
+    }
+    (fetched, failed)
+}
+
+/// Run the backfill against the History database
+pub async fn run(cfg: &Config, args: &BackfillArgs) -> Result<BackfillStats> {
+    let db_path = cfg.history_db_path();
+    let history = History::start(&db_path)?;
+
+    let stats = history.backfill_columns().await;
+    let mut summary = format!(
+        "Backfilled {db_path}: {} txs decoded, {} statuses from stored outcomes",
+        stats.decoded, stats.statuses
+    );
+    if args.outcomes {
+        let (fetched, failed) = fetch_outcomes(cfg, &history, args.limit).await;
+        summary.push_str(&format!(", {fetched} outcomes fetched"));
+        if failed > 0 {
+            summary.push_str(&format!(" ({failed} failed; run again to retry)"));
+        }
+    }
+
+    // Outcomes are written behind the fetches: wait for the writer to drain
+    if !history.shutdown(std::time::Duration::from_secs(30)).await {
+        eprintln!("nearx backfill: History writer did not finish in time");
+    }
+    println!("{summary}");
+    Ok(stats)
+}
@@ -6,7 +6,9 @@
 //!
 //! ## Endpoints
 //! - GET /health - Health check
-//! - POST /rpc - Generic JSON-RPC proxy (auto-injects auth token)
+//! - POST /rpc - Generic JSON-RPC proxy (auto-injects auth token); plugins'
+//!   `EXPERIMENTAL_tx_status` / `EXPERIMENTAL_receipt` calls are answered from
+//!   the receipt outcome cache when possible
 //! - GET /api/latest - Get latest finalized block height
 //! - GET /api/block/:height - Fetch block with all chunks and transactions
 //! - GET /api/blocks?from=N&limit=M - Batch fetch blocks (for initial load)
@@ -27,7 +29,7 @@ use axum::{
 use serde::{Deserialize, Serialize};
 use tower_http::cors::{Any, CorsLayer};
 
-use nearx::{rpc_utils::fetch_block_with_txs, types::BlockRow};
+use nearx::{outcome_cache::Outcomes, rpc_utils::fetch_block_with_txs, types::BlockRow};
 
 /// Application state shared across handlers
 #[derive(Clone)]
@@ -36,6 +38,7 @@ struct AppState {
     auth_token: Option<String>,
     timeout_ms: u64,
     chunk_concurrency: usize,
+    outcomes: Outcomes,
 }
 
 /// Query parameters for batch block fetching
@@ -116,6 +119,7 @@ async fn main() -> anyhow::Result<()> {
         auth_token,
         timeout_ms,
         chunk_concurrency,
+        outcomes: Outcomes::default(),
     };
 
     // Build router
@@ -152,11 +156,34 @@ async fn rpc_proxy_handler(
     log::debug!("Proxying JSON-RPC request");
 
     // Parse the incoming JSON to validate it
-    let _json: serde_json::Value = serde_json::from_str(&body).map_err(|e| {
+    let json: serde_json::Value = serde_json::from_str(&body).map_err(|e| {
         log::error!("Invalid JSON in request body: {}", e);
         StatusCode::BAD_REQUEST
     })?;
 
+    // Receipt outcomes already known (final, or fresh enough) skip the node
+    let method = json["method"].as_str().unwrap_or_default();
+    let params = &json["params"];
+    let wait_until = params["wait_until"].as_str().unwrap_or("EXECUTED_OPTIMISTIC");
+    let cached = match method {
+        "EXPERIMENTAL_tx_status" => params["tx_hash"]
+            .as_str()
+            .and_then(|hash| state.outcomes.cached_status(hash, wait_until)),
+        "EXPERIMENTAL_receipt" => params["receipt_id"]
+            .as_str()
+            .and_then(|id| state.outcomes.cached_receipt(id))
+            .and_then(|o| o.body),
+        _ => None,
+    };
+    if let Some(result) = cached {
+        log::debug!("{} answered from the outcome cache", method);
+        let reply = serde_json::json!({"jsonrpc": "2.0", "id": json["id"], "result": result});
+        return Response::builder()
+            .header("Content-Type", "application/json")
+            .body(Body::from(reply.to_string()))
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
     // Create HTTP client
     let client = reqwest::Client::new();
 
@@ -185,6 +212,12 @@ async fn rpc_proxy_handler(
         StatusCode::BAD_GATEWAY
     })?;
 
+    if status.is_success() && method == "EXPERIMENTAL_tx_status" {
+        if let Ok(reply) = serde_json::from_slice::<serde_json::Value>(&body_bytes) {
+            state.outcomes.record(&reply["result"], wait_until);
+        }
+    }
+
     // Build response with same status code
     let response = Response::builder()
         .status(status)
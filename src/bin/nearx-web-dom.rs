@@ -14,18 +14,26 @@
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::spawn_local;
 
-use tokio::sync::mpsc::{error::TryRecvError, unbounded_channel, UnboundedReceiver};
+use futures::future::{AbortHandle, Abortable};
+use tokio::sync::mpsc::{error::TryRecvError, unbounded_channel, UnboundedReceiver, UnboundedSender};
 use web_time::{Duration, Instant};
 
+use nearx::outcome_cache::Outcomes;
+use nearx::snapshot_cache::SnapshotCache;
 use nearx::ui_snapshot::{apply_ui_action, UiAction, UiSnapshot};
-use nearx::{App, AppEvent, Config, Source};
+use nearx::{source_health::SourceKind, App, AppEvent, Config, Endpoints};
 
 /// Wasm-exposed app wrapper. JS owns an instance of this and communicates via JSON.
 #[wasm_bindgen]
 pub struct WasmApp {
     app: App,
+    event_tx: UnboundedSender<AppEvent>,
     event_rx: UnboundedReceiver<AppEvent>,
+    config: Config,
+    sources: AbortHandle, // RPC poller + archival fetch (aborted on endpoint change)
     last_tick: Instant,  // For on_tick() throttling
+    snapshots: SnapshotCache, // Blocks / Txs rows reused between snapshots
+    outcomes: Outcomes,       // Receipt outcomes for the tracer / gas profiler
 }
 
 impl Default for WasmApp {
@@ -68,84 +76,67 @@ impl WasmApp {
             "acct:intents.near".to_string()
         };
 
-        // Initialize archival fetch channel (WASM version)
-        let (archival_tx, archival_rx) = unbounded_channel::<u64>();
-        let archival_fetch_tx = Some(archival_tx);
-
         // Build config for the RPC poller.
-        let cfg_default_filter = default_filter.clone();
-        let cfg_fps = fps;
-        let cfg_fps_choices = fps_choices.clone();
-        let cfg_keep_blocks = keep_blocks;
-
-        spawn_local(async move {
-            let config = Config {
-                source: Source::Rpc,
-                ws_url: "".to_string(),
-                ws_fetch_blocks: false,
-                render_fps: cfg_fps,
-                render_fps_choices: cfg_fps_choices,
-                poll_interval_ms: option_env!("POLL_INTERVAL_MS")
-                    .and_then(|s| s.parse().ok())
-                    .unwrap_or(1000),
-                poll_max_catchup: 5,
-                poll_chunk_concurrency: 4,
-                keep_blocks: cfg_keep_blocks,
-                near_node_url: option_env!("NEAR_NODE_URL")
-                    .unwrap_or("https://rpc.mainnet.fastnear.com/")
-                    .to_string(),
-                near_node_url_explicit: false,
-                archival_rpc_url: option_env!("ARCHIVAL_RPC_URL")
-                    .map(|s| s.to_string()),
-                rpc_timeout_ms: 8_000,
-                rpc_retries: 2,
-                fastnear_auth_token: {
-                    let token = nearx::config::fastnear_token();
-                    if token.is_empty() { None } else { Some(token) }
-                },
-                default_filter: cfg_default_filter,
-                theme: nearx::theme::Theme::default(),
-            };
-
-            log::info!(
-                "[WasmApp] RPC poller start - endpoint: {}",
-                config.near_node_url
-            );
-
-            // Spawn WASM archival fetch task if archival URL configured
-            if let Some(archival_url) = config.archival_rpc_url.clone() {
-                let auth_token = config.fastnear_auth_token.clone();
-                let archival_event_tx = event_tx.clone();
-
-                spawn_local(async move {
-                    nearx::archival_fetch_wasm::run_archival_fetch_wasm(
-                        archival_rx,
-                        archival_event_tx,
-                        archival_url,
-                        auth_token,
-                    ).await;
-                });
-
-                log::info!("[WasmApp] Archival fetch task spawned");
-            }
+        let config = Config {
+            render_fps: fps,
+            render_fps_choices: fps_choices.clone(),
+            poll_interval_ms: option_env!("POLL_INTERVAL_MS")
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(1000),
+            keep_blocks,
+            near_node_url: option_env!("NEAR_NODE_URL")
+                .unwrap_or("https://rpc.mainnet.fastnear.com/")
+                .to_string(),
+            archival_rpc_url: option_env!("ARCHIVAL_RPC_URL")
+                .map(|s| s.to_string()),
+            fastnear_auth_token: {
+                let token = nearx::config::fastnear_token();
+                if token.is_empty() { None } else { Some(token) }
+            },
+            default_filter: default_filter.clone(),
+            theme_variant: web_theme_variant(),
+            theme: nearx::theme::Theme::for_variant(web_theme_variant()),
+            theme_follow_system: web_system_themes(),
+            fastnear_api_url: option_env!("FASTNEAR_API_URL")
+                .unwrap_or(nearx::holders::DEFAULT_API_URL)
+                .to_string(),
+            // Browser: no WebSocket source, block ring persistence or plugin alerts
+            ws_fetch_blocks: false,
+            warm_start: false,
+            plugin_alert_marks: false,
+            ..Config::default()
+        };
 
-            if let Err(e) = nearx::source_rpc::run_rpc(&config, event_tx).await {
-                log::error!("[WasmApp] RPC poller error: {e}");
-            }
-        });
+        let (sources, archival_fetch_tx) = spawn_sources(&config, &event_tx);
 
-        let app = App::new(
+        let mut app = App::new(
             fps,
             fps_choices,
             keep_blocks,
             default_filter,
             archival_fetch_tx,
         );
+        app.set_theme(nearx::theme::Theme::for_variant(web_theme_variant()));
+        app.set_follow_system_theme(web_system_themes());
+        app.set_endpoints(Endpoints {
+            near_node_url: config.near_node_url.clone(),
+            archival_rpc_url: config.archival_rpc_url.clone(),
+        });
+        // Only the RPC poller reports health in the browser
+        app.set_sources(&[SourceKind::Rpc]);
+        if let Some(appearance) = nearx::platform::system_appearance() {
+            app.set_system_appearance(appearance);
+        }
 
         WasmApp {
             app,
+            event_tx,
             event_rx,
+            config,
+            sources,
             last_tick: Instant::now(),
+            snapshots: SnapshotCache::default(),
+            outcomes: Outcomes::default(),
         }
     }
 
@@ -153,7 +144,7 @@ impl WasmApp {
     #[wasm_bindgen]
     pub fn snapshot_json(&mut self) -> String {
         self.drain_events();
-        let snap = UiSnapshot::from_app(&self.app);
+        let snap = UiSnapshot::build(&self.app, &mut self.snapshots);
         serde_json::to_string(&snap).unwrap_or_else(|e| {
             log::error!("Failed to serialize UiSnapshot: {e}");
             "{}".to_string()
@@ -172,7 +163,7 @@ impl WasmApp {
             }
         }
 
-        let snap = UiSnapshot::from_app(&self.app);
+        let snap = UiSnapshot::build(&self.app, &mut self.snapshots);
         serde_json::to_string(&snap).unwrap_or_else(|e| {
             log::error!("Failed to serialize UiSnapshot after action: {e}");
             "{}".to_string()
@@ -185,6 +176,22 @@ impl WasmApp {
         self.app.set_details_viewport_lines(lines as usize);
     }
 
+    /// OS dark/light changed (`prefers-color-scheme` listener or Tauri `nearx://theme`).
+    /// No-op unless built with THEME_FOLLOW_SYSTEM=true.
+    #[wasm_bindgen(js_name = "setSystemAppearance")]
+    pub fn set_system_appearance_js(&mut self, dark: bool) {
+        if !self.app.follows_system_theme() {
+            return;
+        }
+        let appearance = if dark {
+            nearx::theme::Appearance::Dark
+        } else {
+            nearx::theme::Appearance::Light
+        };
+        self.app.set_system_appearance(appearance);
+        apply_theme_to_dom(self.app.theme());
+    }
+
     /// Get clipboard content for the currently focused pane (called only on 'c' key).
     #[wasm_bindgen(js_name = "getClipboardContent")]
     pub fn get_clipboard_content(&mut self) -> String {
@@ -206,8 +213,80 @@ pub fn start() {
     wasm_logger::init(wasm_logger::Config::default());
 
     // Apply theme CSS vars to :root for TUI-consistent styling
-    let theme = nearx::theme::Theme::default();
-    apply_theme_to_dom(&theme);
+    let variant = match (web_system_themes(), nearx::platform::system_appearance()) {
+        (Some(themes), Some(appearance)) => themes.variant_for(appearance),
+        _ => web_theme_variant(),
+    };
+    apply_theme_to_dom(&nearx::theme::Theme::for_variant(variant));
+}
+
+/// Theme variant from compile-time THEME (invalid values fall back to dark).
+fn web_theme_variant() -> nearx::theme::ThemeVariant {
+    option_env!("THEME")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or_default()
+}
+
+/// Dark/light pair from compile-time THEME_FOLLOW_SYSTEM / THEME / THEME_LIGHT.
+fn web_system_themes() -> Option<nearx::theme::SystemThemes> {
+    if option_env!("THEME_FOLLOW_SYSTEM") != Some("true") {
+        return None;
+    }
+    Some(nearx::theme::SystemThemes {
+        dark: web_theme_variant(),
+        light: option_env!("THEME_LIGHT")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(nearx::theme::ThemeVariant::Light),
+    })
+}
+
+/// Spawn the RPC poller (and archival fetch task, when configured) under one
+/// abort handle. Returns the archival request channel for the app.
+fn spawn_sources(
+    config: &Config,
+    event_tx: &UnboundedSender<AppEvent>,
+) -> (AbortHandle, Option<UnboundedSender<u64>>) {
+    let (handle, registration) = AbortHandle::new_pair();
+    let config = config.clone();
+    let event_tx = event_tx.clone();
+
+    // Archival fetch channel (WASM version)
+    let (archival_tx, archival_rx) = unbounded_channel::<u64>();
+    let archival_fetch_tx = config.archival_rpc_url.is_some().then_some(archival_tx);
+
+    spawn_local(async move {
+        let run = async move {
+            log::info!(
+                "[WasmApp] RPC poller start - endpoint: {}",
+                config.near_node_url
+            );
+
+            // Archival fetch runs alongside the poller so both stop together
+            let archival = async {
+                if let Some(archival_url) = config.archival_rpc_url.clone() {
+                    nearx::archival_fetch_wasm::run_archival_fetch_wasm(
+                        archival_rx,
+                        event_tx.clone(),
+                        archival_url,
+                        config.fastnear_auth_token.clone(),
+                    )
+                    .await;
+                    log::info!("[WasmApp] Archival fetch task ended");
+                }
+            };
+            let poller = async {
+                if let Err(e) = nearx::source_rpc::run_rpc(&config, event_tx.clone()).await {
+                    log::error!("[WasmApp] RPC poller error: {e}");
+                }
+            };
+            futures::join!(archival, poller);
+        };
+        if Abortable::new(run, registration).await.is_err() {
+            log::info!("[WasmApp] RPC poller stopped (endpoint change)");
+        }
+    });
+
+    (handle, archival_fetch_tx)
 }
 
 #[allow(unused_variables)]
@@ -257,6 +336,51 @@ impl WasmApp {
             }
         }
 
+        // Endpoint hotplug / retry (r): stop the running poller and start a fresh one
+        let endpoints = self.app.take_endpoint_change();
+        let retry = self.app.take_sources_change().is_some();
+        if endpoints.is_some() || retry {
+            self.sources.abort();
+            if let Some(endpoints) = endpoints {
+                self.config.near_node_url = endpoints.near_node_url;
+                self.config.archival_rpc_url = endpoints.archival_rpc_url;
+            }
+            let (sources, archival_fetch_tx) = spawn_sources(&self.config, &self.event_tx);
+            self.sources = sources;
+            self.app.set_archival_sender(archival_fetch_tx);
+            self.app.set_sources(&[SourceKind::Rpc]);
+        }
+
+        // Tx timeline: trace receipts in the background, result arrives as an event
+        if let Some((hash, signer)) = self.app.take_timeline_request() {
+            let url = self.config.archival_or_rpc_url();
+            let timeout_ms = self.config.rpc_timeout_ms;
+            let token = self.config.fastnear_auth_token.clone();
+            let event_tx = self.event_tx.clone();
+            let outcomes = self.outcomes.clone();
+            spawn_local(async move {
+                let result = nearx::timeline::trace(&outcomes, &url, &hash, &signer, timeout_ms, token.as_deref())
+                    .await
+                    .map_err(|e| format!("{e:#}"));
+                let _ = event_tx.send(AppEvent::TxTimeline { hash, result });
+            });
+        }
+
+        // Gas profile: receipt gas tree, result arrives as an event
+        if let Some((hash, signer)) = self.app.take_gas_profile_request() {
+            let url = self.config.archival_or_rpc_url();
+            let timeout_ms = self.config.rpc_timeout_ms;
+            let token = self.config.fastnear_auth_token.clone();
+            let event_tx = self.event_tx.clone();
+            let outcomes = self.outcomes.clone();
+            spawn_local(async move {
+                let result = nearx::gas_profile::profile(&outcomes, &url, &hash, &signer, timeout_ms, token.as_deref())
+                    .await
+                    .map_err(|e| format!("{e:#}"));
+                let _ = event_tx.send(AppEvent::GasProfile { hash, result });
+            });
+        }
+
         // Periodic housekeeping: backfill chain-walking, etc.
         // Call on_tick() at most every 100ms to throttle archival requests
         let now = Instant::now();
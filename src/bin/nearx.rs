@@ -3,27 +3,35 @@
 use anyhow::{Context, Result};
 use crossterm::{
     event::{
-        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
-        KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+        MouseEventKind,
     },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::{
+    collections::HashSet,
     io,
     time::{Duration, Instant},
 };
-use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
-use tokio::task::JoinHandle;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 
 use nearx::{
-    app::{App, InputMode},
+    app::{App, Endpoints, InputMode, ZoomedPane},
     archival_fetch,
-    config::{load, Source},
-    marks::JumpMarks,
-    platform::{BlockPersist, History, TxPersist},
-    source_rpc, source_ws,
+    config::{load, Command, Config, Source},
+    exports::{ExportParams, ExportRecord},
+    marks::{AlertMarkPolicy, JumpMarks},
+    mirror::MirrorPublisher,
+    supervisor::{Supervisor, SHUTDOWN_TIMEOUT},
+    platform::{BlockPersist, History},
+    source_health::SourceKind,
+    startup::{StartupLoad, StartupStage},
+    status_bar::SegmentAction,
+    source_graphql, source_rpc, source_ws,
+    toast::ToastLevel,
     types::AppEvent,
     ui,
     ui_snapshot::{apply_ui_action, UiAction},
@@ -32,22 +40,100 @@ use nearx::{
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    // Startup stages are timed from here (first frame, History, first block)
+    let started = Instant::now();
+
     // Load .env file if it exists (safe to ignore if not found)
     #[cfg(feature = "native")]
     {
         let _ = dotenvy::dotenv();
     }
 
+    // ratacat.toml fills in what CLI / env / .env leave unset, then is watched
+    let config_path = nearx::config_file::path();
+    let env_live_keys = nearx::config_file::env_live_keys();
+    let config_table = nearx::config_file::export_env(&config_path)?;
+
     let cfg = load().context("Failed to load configuration")?;
+    let config_baseline = nearx::config_file::LiveBaseline::new(&cfg, env_live_keys);
 
-    // Initialize SQLite history (non-blocking)
-    let db_path = std::env::var("SQLITE_DB_PATH").unwrap_or_else(|_| "./nearx_history.db".into());
-    let history = History::start(&db_path)?;
+    // One-shot: export the UI contract schema (no terminal, no sources)
+    if let Some(dir) = cfg.emit_schema.as_deref() {
+        nearx::schema::write_schema_files(dir)?;
+        println!("Wrote UI contract schema to {}", dir.display());
+        return Ok(());
+    }
+
+    // One-shot: `nearx query ...` (no terminal; grep-style exit status)
+    if let Some(Command::Query(args)) = cfg.command.as_ref() {
+        let code = match nearx::query::run(&cfg, args).await {
+            Ok(0) => 1,
+            Ok(_) => 0,
+            Err(e) => {
+                eprintln!("nearx query: {e:#}");
+                2
+            }
+        };
+        std::process::exit(code);
+    }
+
+    // One-shot: `nearx doctor` (connectivity + local setup report)
+    if let Some(Command::Doctor) = cfg.command.as_ref() {
+        let code = match nearx::doctor::run(&cfg).await {
+            Ok(0) => 0,
+            Ok(_) => 1,
+            Err(e) => {
+                eprintln!("nearx doctor: {e:#}");
+                1
+            }
+        };
+        std::process::exit(code);
+    }
 
-    // terminal
+    // One-shot: `nearx fetch FROM..TO` / `--resume` (range download into History)
+    if let Some(Command::Fetch(args)) = cfg.command.as_ref() {
+        let code = match nearx::fetch::run(&cfg, args).await {
+            Ok(_) => 0,
+            Err(e) => {
+                eprintln!("nearx fetch: {e:#}");
+                1
+            }
+        };
+        std::process::exit(code);
+    }
+
+    // One-shot: `nearx backfill [--outcomes]` (decoded columns and outcomes in History)
+    if let Some(Command::Backfill(args)) = cfg.command.as_ref() {
+        let code = match nearx::backfill::run(&cfg, args).await {
+            Ok(_) => 0,
+            Err(e) => {
+                eprintln!("nearx backfill: {e:#}");
+                1
+            }
+        };
+        std::process::exit(code);
+    }
+
+    // One-shot: `nearx digest <contract>` (markdown summary from History)
+    if let Some(Command::Digest(args)) = cfg.command.as_ref() {
+        let code = match nearx::digest::run(&cfg, args).await {
+            Ok(()) => 0,
+            Err(e) => {
+                eprintln!("nearx digest: {e:#}");
+                1
+            }
+        };
+        std::process::exit(code);
+    }
+
+    nearx::theme::set_monochrome(cfg.monochrome);
+    nearx::clipboard::set_policy(cfg.clipboard.clone(), cfg.clipboard_file.clone());
+
+    // terminal first: the first frame goes up before anything slow runs
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    // Bracketed paste: a pasted hash list arrives whole, not as keys (Shift+I import)
+    execute!(stdout, EnterAlternateScreen, EnableBracketedPaste)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
     terminal.clear()?;
@@ -55,39 +141,45 @@ async fn main() -> Result<()> {
     // app + channels
     let (tx, rx) = unbounded_channel::<AppEvent>();
 
-    // Archival fetch channel (optional, only if archival URL configured)
-    let (archival_tx, archival_rx) = unbounded_channel::<u64>();
-    let archival_task: Option<JoinHandle<Result<()>>> = if cfg.archival_rpc_url.is_some() {
-        let cfg_arch = cfg.clone();
-        let tx_arch = tx.clone();
-        Some(tokio::spawn(async move {
-            archival_fetch::run_archival_fetch(cfg_arch, archival_rx, tx_arch).await
-        }))
-    } else {
-        None
-    };
+    // Background tasks stop through cancellation tokens, in order on quit
+    let mut background = Supervisor::new("background");
 
     let mut app = App::new(
         cfg.render_fps,
         cfg.render_fps_choices.clone(),
         cfg.keep_blocks,
         cfg.default_filter.clone(),
-        if cfg.archival_rpc_url.is_some() {
-            Some(archival_tx)
-        } else {
-            None
-        },
+        None, // archival sender arrives with the sources, after the first frame
     );
+    app.begin_startup(started);
+    app.set_debug_log_file(Some("nearx_debug.log".into()));
+    app.set_endpoints(Endpoints {
+        near_node_url: cfg.near_node_url.clone(),
+        archival_rpc_url: cfg.archival_rpc_url.clone(),
+    });
+    app.set_sources(&Sources::kinds(&cfg));
+    app.set_theme(cfg.theme);
+    app.set_json_format(cfg.json_format);
+    app.set_follow_system_theme(cfg.theme_follow_system);
+    app.set_gas_policy(cfg.gas_window_blocks, cfg.gas_spike_multiple);
+    app.set_anomaly_policy(cfg.anomaly_sigma, cfg.anomaly_incidents);
+    app.set_stale_after(cfg.stale_after_secs);
+    app.set_time_display(cfg.time_display());
+    app.set_stake_alert_pp(cfg.stake_alert_pp);
+    app.set_telemetry(nearx::telemetry::Telemetry::new(cfg.telemetry, cfg.telemetry_url.clone()));
 
     // Apply deep link route from CLI args (if provided)
-    // Example: ./nearx nearx://v1/tx/ABC123
-    {
+    // Example: ./nearx nearx://v1/tx/ABC123, or ./nearx --route "tx/ABC123"
+    if let Some(link) = &cfg.route {
+        app.apply_route_link(link);
+        log::info!("Applied --route: {:?}", link.route);
+    } else {
         let args: Vec<String> = std::env::args().collect();
         for arg in args.iter().skip(1) {
             // Check if argument looks like a deep link
             if arg.starts_with("nearx://") || arg.starts_with("/v1/") || arg.contains("#/v1/") {
-                if let Some(route) = nearx::router::parse(arg) {
-                    app.apply_route(&route);
+                if let Some(link) = nearx::router::parse_link(arg) {
+                    app.apply_route_link(&link);
                     log::info!("Applied deep link route from CLI: {arg}");
                     break; // Only process first route
                 }
@@ -95,49 +187,414 @@ async fn main() -> Result<()> {
         }
     }
 
-    // source task
-    let cfg_clone = cfg.clone();
-    let history_clone_tx = tx.clone();
-    let source_task: JoinHandle<Result<()>> = match cfg.source {
-        Source::Ws => {
-            tokio::spawn(async move { source_ws::run_ws(&cfg_clone, history_clone_tx).await })
-        }
-        Source::Rpc => {
-            tokio::spawn(async move { source_rpc::run_rpc(&cfg_clone, history_clone_tx).await })
+    // First frame: empty panes and the staged startup line; keys work from here
+    terminal.draw(|f| ui::draw(f, &mut app, &[]))?;
+    app.startup_stage_done(StartupStage::Frame);
+
+    // SQLite history: the worker opens the database off this thread, and last
+    // session's state (warm blocks, marks, watches, telemetry counters, an
+    // interrupted fetch) arrives later as one StartupLoaded event
+    let db_path = cfg.history_db_path();
+    let history = History::start(&db_path)?;
+    spawn_startup_load(&history, cfg.warm_start, tx.clone());
+
+    // Block source + archival fetch, side by side (respawned when endpoints change at runtime)
+    let mut sources = Sources::new(tx.clone(), background.child("sources"));
+    app.set_archival_sender(sources.spawn(&cfg));
+
+    // Owned accounts: credentials watcher pushes the full account set on every change
+    spawn_credentials_watcher(&mut background, tx.clone());
+
+    // Live config reload (theme, fps, keep_blocks, default filter, labels)
+    spawn_config_watcher(&mut background, config_path, config_table, config_baseline, tx.clone());
+
+    // Validator stake summary, once per epoch
+    spawn_stake_poller(&mut background, &cfg, tx.clone());
+
+    // OS dark/light watcher (best-effort, only when following the system theme)
+    if cfg.theme_follow_system.is_some() {
+        spawn_appearance_watcher(&mut background, tx.clone());
+    }
+
+    // jump marks (restored with the rest of the startup load)
+    let mut jump_marks = JumpMarks::new(history.clone());
+    jump_marks.set_alert_policy(AlertMarkPolicy {
+        enabled: cfg.plugin_alert_marks,
+        per_plugin_cap: cfg.plugin_alert_mark_cap,
+        ..Default::default()
+    });
+
+    // Dev/testing fault injection: say so, or the resulting gaps look like real outages
+    if !cfg.chaos.is_empty() {
+        app.push_toast(
+            ToastLevel::Warn,
+            format!("CHAOS: injecting source faults ({})", cfg.chaos.describe().join("; ")),
+        );
+    }
+
+    // Read-only shared view for web viewers (?mirror=ws://host:port)
+    let mirror = match cfg.mirror_listen {
+        Some(addr) => match nearx::mirror::serve(addr).await {
+            Ok(mirror) => {
+                app.push_toast(
+                    ToastLevel::Info,
+                    format!("Mirror: sharing this view read-only on ws://{}", mirror.addr()),
+                );
+                Some(mirror)
+            }
+            Err(e) => {
+                app.push_toast(ToastLevel::Error, format!("{e:#}"));
+                None
+            }
+        },
+        None => None,
+    };
+
+    // Local control channel (scripts / native host); kept until exit, which removes the socket
+    let _control = match &cfg.control_socket {
+        Some(path) => {
+            match nearx::control::serve(path, cfg.control_token.clone(), cfg.control_allow.clone(), tx.clone())
+                .await
+            {
+                Ok(control) => {
+                    let token = match control.token_file() {
+                        Some(file) => format!("token in {}", file.display()),
+                        None => "CONTROL_TOKEN".to_string(),
+                    };
+                    app.push_toast(
+                        ToastLevel::Info,
+                        format!("Control: accepting {} on {} ({token})", cfg.control_allow.join(", "), path.display()),
+                    );
+                    Some(control)
+                }
+                Err(e) => {
+                    app.push_toast(ToastLevel::Error, format!("{e:#}"));
+                    None
+                }
+            }
         }
+        None => None,
     };
 
-    // jump marks
-    let mut jump_marks = JumpMarks::new(history.clone());
-    jump_marks.load_from_persistence().await;
+    // Plugin bus (alerts from out-of-process plugins); kept until exit, which removes the socket
+    #[cfg(unix)]
+    let _plugins = match &cfg.plugin_socket {
+        Some(path) => match nearx::plugin_bus::serve(path, tx.clone()).await {
+            Ok(bus) => {
+                app.push_toast(ToastLevel::Info, format!("Plugins: listening on {}", bus.path().display()));
+                Some(bus)
+            }
+            Err(e) => {
+                app.push_toast(ToastLevel::Error, format!("{e:#}"));
+                None
+            }
+        },
+        None => None,
+    };
+    #[cfg(not(unix))]
+    if cfg.plugin_socket.is_some() {
+        app.push_toast(ToastLevel::Warn, "PLUGIN_SOCKET is Unix-only; plugin alerts are off".to_string());
+    }
 
     // main loop
-    let mouse_enabled =
-        run_loop(&mut app, &mut terminal, rx, history, jump_marks).await?;
+    let mut cfg = cfg;
+    let mouse_enabled = run_loop(
+        &mut app,
+        &mut terminal,
+        rx,
+        &mut cfg,
+        &mut sources,
+        history.clone(),
+        jump_marks,
+        mirror,
+    )
+    .await?;
 
-    // cleanup
-    source_task.abort();
-    if let Some(task) = archival_task {
-        task.abort();
-    }
+    // restore the terminal first; shutdown may take a moment
     if mouse_enabled {
         execute!(terminal.backend_mut(), DisableMouseCapture)?;
     }
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(terminal.backend_mut(), DisableBracketedPaste, LeaveAlternateScreen)?;
+
+    // Shutdown under one deadline: stop the block producers, then watchers and
+    // pollers, save session state, and drain the History writer last so no
+    // queued persist is lost
+    let deadline = tokio::time::Instant::now() + SHUTDOWN_TIMEOUT;
+    sources.tasks.shutdown(deadline).await;
+    background.shutdown(deadline).await;
+    if cfg.warm_start {
+        history.save_warm_blocks(app.warm_blocks()).await;
+    }
+    flush_telemetry(&mut app, &history).await;
+    drop(app);
+    let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+    if !history.shutdown(remaining).await {
+        log::warn!("[shutdown] history writer did not finish in time; queued writes may be lost");
+    }
     Ok(())
 }
 
+/// Send the telemetry report (opted in with a URL set), then save what is left
+async fn flush_telemetry(app: &mut App, history: &History) {
+    let telemetry = app.telemetry_mut();
+    if telemetry.enabled() && !telemetry.is_empty() {
+        if let Some(url) = telemetry.url().map(str::to_string) {
+            match nearx::telemetry::send(&url, &telemetry.payload()).await {
+                Ok(()) => telemetry.purge(),
+                Err(e) => log::warn!("telemetry not sent: {e:#}"),
+            }
+        }
+    }
+    let saved = telemetry
+        .since_ms()
+        .filter(|_| !telemetry.is_empty())
+        .map(|since_ms| (since_ms, telemetry.counters().clone()));
+    history.set_telemetry(saved).await;
+}
+
+/// Read last session's state from History off the UI loop (arrives as `StartupLoaded`)
+fn spawn_startup_load(history: &History, warm_start: bool, tx: UnboundedSender<AppEvent>) {
+    let history = history.clone();
+    tokio::spawn(async move {
+        let warm_blocks = async {
+            if warm_start {
+                history.load_warm_blocks().await
+            } else {
+                Vec::new()
+            }
+        };
+        let (warm_blocks, marks, watch_rules, telemetry, fetch_job) = tokio::join!(
+            warm_blocks,
+            JumpMarks::load_persisted(&history),
+            history.watch_rules(),
+            history.telemetry(),
+            history.fetch_job(),
+        );
+        let load = StartupLoad {
+            warm_blocks,
+            marks,
+            watch_rules,
+            telemetry,
+            fetch_hint: fetch_job.map(|job| nearx::fetch::resume_hint(&job)),
+        };
+        let _ = tx.send(AppEvent::StartupLoaded(Box::new(load)));
+    });
+}
+
+/// Forward account sets from `~/.near-credentials/<network>` into the app event stream
+fn spawn_credentials_watcher(tasks: &mut Supervisor, tx: UnboundedSender<AppEvent>) {
+    let base_dir = std::env::var("NEAR_CREDENTIALS_DIR")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|_| {
+            std::env::var("HOME")
+                .map(std::path::PathBuf::from)
+                .unwrap_or_default()
+                .join(".near-credentials")
+        });
+    let network = std::env::var("NEAR_NETWORK").unwrap_or_else(|_| "mainnet".into());
+    let (creds_tx, mut creds_rx) = unbounded_channel();
+    tasks.spawn("credentials watcher", async move {
+        if let Err(e) =
+            nearx::credentials::start_credentials_watcher(base_dir, network, creds_tx).await
+        {
+            log::warn!("credentials watcher unavailable: {e:#}");
+            return;
+        }
+        while let Some(accounts) = creds_rx.recv().await {
+            if tx.send(AppEvent::OwnedAccounts(accounts)).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+/// Forward `ratacat.toml` reloads into the app event stream
+fn spawn_config_watcher(
+    tasks: &mut Supervisor,
+    path: std::path::PathBuf,
+    initial: toml::Table,
+    baseline: nearx::config_file::LiveBaseline,
+    tx: UnboundedSender<AppEvent>,
+) {
+    let (reload_tx, mut reload_rx) = unbounded_channel();
+    tasks.spawn("config watcher", async move {
+        if let Err(e) =
+            nearx::config_file::start_config_watcher(path, initial, baseline, reload_tx).await
+        {
+            log::warn!("config file watcher unavailable: {e:#}");
+            return;
+        }
+        while let Some(reload) = reload_rx.recv().await {
+            if tx.send(AppEvent::ConfigReload(reload)).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+/// Running block source and (optional) archival fetch tasks
+/// Where `kind`'s task sends its events: the app channel, or a fault-injecting
+/// shim in front of it when `CHAOS` has a profile for the source
+fn source_sender(
+    tasks: &mut Supervisor,
+    cfg: &Config,
+    kind: SourceKind,
+    tx: &UnboundedSender<AppEvent>,
+) -> UnboundedSender<AppEvent> {
+    match cfg.chaos.profile(kind) {
+        Some(profile) => nearx::chaos::spawn_shim(tasks, kind, profile, tx.clone()),
+        None => tx.clone(),
+    }
+}
+
+struct Sources {
+    tx: UnboundedSender<AppEvent>,
+    tasks: Supervisor,
+    /// Switched off from the sources overlay (kept across respawns)
+    disabled: HashSet<SourceKind>,
+}
+
+impl Sources {
+    fn new(tx: UnboundedSender<AppEvent>, tasks: Supervisor) -> Self {
+        Self {
+            tx,
+            tasks,
+            disabled: HashSet::new(),
+        }
+    }
+
+    /// Spawn the block source for `cfg`, plus the archival fetch task when an
+    /// archival URL is configured (its request channel is returned for the app).
+    /// With WS switched off, RPC polling takes over as the live source.
+    fn spawn(&mut self, cfg: &Config) -> Option<UnboundedSender<u64>> {
+        let disabled = &self.disabled;
+        let mut cfg_source = cfg.clone();
+        match cfg.source {
+            Source::Ws if !disabled.contains(&SourceKind::Ws) => {
+                cfg_source.ws_fetch_blocks &= !disabled.contains(&SourceKind::Rpc);
+                let tx_source = source_sender(&mut self.tasks, cfg, SourceKind::Ws, &self.tx);
+                self.tasks.spawn("ws source", async move {
+                    if let Err(e) = source_ws::run_ws(&cfg_source, tx_source).await {
+                        log::warn!("ws source stopped: {e:#}");
+                    }
+                });
+            }
+            Source::GraphQl => {
+                if !disabled.contains(&SourceKind::GraphQl) {
+                    let tx_source = source_sender(&mut self.tasks, cfg, SourceKind::GraphQl, &self.tx);
+                    self.tasks.spawn("graphql source", async move {
+                        if let Err(e) = source_graphql::run_graphql(&cfg_source, tx_source).await {
+                            log::warn!("graphql source stopped: {e:#}");
+                        }
+                    });
+                }
+            }
+            _ if !disabled.contains(&SourceKind::Rpc) => {
+                let tx_source = source_sender(&mut self.tasks, cfg, SourceKind::Rpc, &self.tx);
+                self.tasks.spawn("rpc source", async move {
+                    if let Err(e) = source_rpc::run_rpc(&cfg_source, tx_source).await {
+                        log::warn!("rpc source stopped: {e:#}");
+                    }
+                });
+            }
+            _ => {}
+        }
+
+        let archival_on = cfg.archival_rpc_url.is_some() && !disabled.contains(&SourceKind::Archival);
+        if !archival_on {
+            return None;
+        }
+        let (archival_tx, archival_rx) = unbounded_channel::<u64>();
+        let cfg_arch = cfg.clone();
+        let tx_arch = source_sender(&mut self.tasks, cfg, SourceKind::Archival, &self.tx);
+        self.tasks.spawn("archival fetch", async move {
+            if let Err(e) = archival_fetch::run_archival_fetch(cfg_arch, archival_rx, tx_arch).await {
+                log::warn!("archival fetch stopped: {e:#}");
+            }
+        });
+        Some(archival_tx)
+    }
+
+    /// Sources `cfg` configures, in badge order (whether or not switched off)
+    fn kinds(cfg: &Config) -> Vec<SourceKind> {
+        let mut kinds = match cfg.source {
+            Source::Ws => vec![SourceKind::Ws, SourceKind::Rpc],
+            Source::Rpc => vec![SourceKind::Rpc],
+            Source::GraphQl => vec![SourceKind::GraphQl],
+        };
+        if cfg.archival_rpc_url.is_some() {
+            kinds.push(SourceKind::Archival);
+        }
+        kinds
+    }
+
+    /// Cancel the running tasks and spawn fresh ones against `cfg`
+    fn respawn(&mut self, cfg: &Config) -> Option<UnboundedSender<u64>> {
+        self.tasks.restart();
+        self.spawn(cfg)
+    }
+}
+
+/// Poll the OS appearance and forward changes to the app
+fn spawn_appearance_watcher(tasks: &mut Supervisor, tx: UnboundedSender<AppEvent>) {
+    tasks.spawn("appearance watcher", async move {
+        let mut last = None;
+        loop {
+            let now = tokio::task::spawn_blocking(nearx::platform::system_appearance)
+                .await
+                .ok()
+                .flatten();
+            if let Some(appearance) = now.filter(|a| last != Some(*a)) {
+                last = Some(appearance);
+                if tx.send(AppEvent::SystemAppearance(appearance)).is_err() {
+                    break;
+                }
+            }
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        }
+    })
+}
+
+/// Poll the validators RPC and forward a stake summary whenever the epoch changes
+fn spawn_stake_poller(tasks: &mut Supervisor, cfg: &Config, tx: UnboundedSender<AppEvent>) {
+    let url = cfg.near_node_url.clone();
+    let (timeout_ms, token) = (cfg.rpc_timeout_ms, cfg.fastnear_auth_token.clone());
+    tasks.spawn("stake poller", async move {
+        let mut last_epoch = None;
+        loop {
+            match nearx::stake::fetch(&url, timeout_ms, token.as_deref()).await {
+                Ok(dist) if last_epoch != Some(dist.epoch_start_height) => {
+                    last_epoch = Some(dist.epoch_start_height);
+                    if tx.send(AppEvent::StakeDistribution(dist)).is_err() {
+                        break;
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("validators poll failed: {e:#}"),
+            }
+            tokio::time::sleep(Duration::from_secs(nearx::stake::POLL_SECS)).await;
+        }
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn run_loop(
     app: &mut App,
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     mut rx: UnboundedReceiver<AppEvent>,
+    cfg: &mut Config,
+    sources: &mut Sources,
     history: History,
     mut jump_marks: JumpMarks,
+    mut mirror: Option<MirrorPublisher>,
 ) -> Result<bool> {
     let mut last_frame = Instant::now();
+    let mut mirror_viewers = 0;
     let mut mouse_enabled = false;
     let mut dbl = DblClick::new(Duration::from_millis(280));
+    // Receipt outcomes shared by the tracer and gas profiler
+    let outcomes = nearx::outcome_cache::Outcomes::new(Some(history.clone()));
 
     loop {
         // frame budget (coalesced renders)
@@ -167,40 +624,258 @@ async fn run_loop(
                 }
                 Event::Mouse(m) => {
                     if mouse_enabled && app.ui_flags().mouse_map {
-                        handle_mouse(app, m, terminal, &mut dbl)?;
+                        let size = terminal.size()?;
+                        let on_footer = m.row + 1 == size.height
+                            && matches!(m.kind, MouseEventKind::Down(MouseButton::Left));
+                        if on_footer {
+                            // Footer segments act as their key
+                            let marks_list = jump_marks.list();
+                            if let Some(action) = ui::footer_action(app, &marks_list, size.width, m.column) {
+                                if let Some(k) = segment_key_event(&action) {
+                                    handle_key(app, k, &history, &mut jump_marks).await;
+                                }
+                            }
+                        } else {
+                            handle_mouse(app, m, terminal, &mut dbl)?;
+                        }
                     }
                 }
+                Event::Paste(text) => match app.input_mode() {
+                    InputMode::Import => app.import_paste(&text),
+                    InputMode::Filter => text.chars().filter(|c| !c.is_control()).for_each(|c| app.filter_add_char(c)),
+                    InputMode::Search => text.chars().filter(|c| !c.is_control()).for_each(|c| app.search_add_char(c)),
+                    _ => {}
+                },
                 _ => {}
             }
         }
         while let Ok(ev) = rx.try_recv() {
             // Persist blocks to history
             if let AppEvent::NewBlock(ref block) = ev {
-                let persist = BlockPersist {
-                    height: block.height,
-                    hash: block.hash.clone(),
-                    ts_ms: block.timestamp as i64,
-                    txs: block
-                        .transactions
-                        .iter()
-                        .map(|tx| TxPersist {
-                            hash: tx.hash.clone(),
-                            height: block.height,
-                            signer: None,
-                            receiver: None,
-                            actions_json: None,
-                            raw_json: Some(
-                                serde_json::to_string(&serde_json::json!({"hash": tx.hash}))
-                                    .unwrap_or_default(),
-                            ),
-                        })
-                        .collect(),
-                };
-                history.persist_block(persist);
+                history.persist_block(BlockPersist::from_block(block));
+            }
+            // Keep tx-analyzer results for the Risk browser (Ctrl+R)
+            if let AppEvent::TxAnalysis(ref analysis) = ev {
+                history.put_analysis(analysis.clone());
+            }
+            // Startup load: marks go to the jump list, the rest to the app
+            let ev = match ev {
+                AppEvent::StartupLoaded(mut load) => {
+                    jump_marks.restore_persisted(std::mem::take(&mut load.marks));
+                    AppEvent::StartupLoaded(load)
+                }
+                ev => ev,
+            };
+            // Pin high-severity plugin alerts so they stay navigable via the marks overlay
+            if let AppEvent::PluginAlert(ref alert) = ev {
+                if let Some(label) = jump_marks.add_alert_mark(alert).await {
+                    app.log_debug(format!("[plugin:{}] pinned mark {label}", alert.plugin_id));
+                }
             }
             app.on_event(ev);
         }
 
+        // Endpoint hotplug: cancel the running source and respawn it against the
+        // new URLs. The block buffer is kept; the new source resyncs from its head.
+        if let Some(endpoints) = app.take_endpoint_change() {
+            cfg.near_node_url = endpoints.near_node_url;
+            cfg.near_node_url_explicit = true;
+            cfg.archival_rpc_url = endpoints.archival_rpc_url;
+            app.set_archival_sender(sources.respawn(cfg));
+            app.set_sources(&Sources::kinds(cfg));
+            log::info!("Block source respawned against {}", cfg.near_node_url);
+        }
+
+        // Source toggles (sources overlay): respawn without the switched-off ones
+        if let Some(disabled) = app.take_sources_change() {
+            sources.disabled = disabled;
+            app.set_archival_sender(sources.respawn(cfg));
+            app.set_sources(&Sources::kinds(cfg));
+        }
+
+        // Watches: save changed rules, post hits to their webhooks off the UI loop
+        if let Some(rules) = app.take_watch_rules_change() {
+            history.put_watch_rules(rules);
+        }
+        for hit in app.take_watch_hooks() {
+            let Some(url) = hit.webhook.clone() else {
+                continue;
+            };
+            tokio::spawn(async move {
+                if let Err(e) = nearx::watch::send_webhook(&url, &hit).await {
+                    log::warn!("watch {} webhook failed: {e:#}", hit.rule_id);
+                }
+            });
+        }
+
+        // Tx timeline: trace receipts off the UI loop (archival node when available)
+        if let Some((hash, signer)) = app.take_timeline_request() {
+            let url = cfg.archival_or_rpc_url();
+            let (timeout_ms, token) = (cfg.rpc_timeout_ms, cfg.fastnear_auth_token.clone());
+            let (outcomes, events) = (outcomes.clone(), sources.tx.clone());
+            tokio::spawn(async move {
+                let result = nearx::timeline::trace(&outcomes, &url, &hash, &signer, timeout_ms, token.as_deref())
+                    .await
+                    .map_err(|e| format!("{e:#}"));
+                let _ = events.send(AppEvent::TxTimeline { hash, result });
+            });
+        }
+
+        // Gas profile: same tx-status fetch, built into a receipt gas tree
+        if let Some((hash, signer)) = app.take_gas_profile_request() {
+            let url = cfg.archival_or_rpc_url();
+            let (timeout_ms, token) = (cfg.rpc_timeout_ms, cfg.fastnear_auth_token.clone());
+            let (outcomes, events) = (outcomes.clone(), sources.tx.clone());
+            tokio::spawn(async move {
+                let result = nearx::gas_profile::profile(&outcomes, &url, &hash, &signer, timeout_ms, token.as_deref())
+                    .await
+                    .map_err(|e| format!("{e:#}"));
+                let _ = events.send(AppEvent::GasProfile { hash, result });
+            });
+        }
+
+        // Related txs: History's candidates near the tx opened in Details
+        if let Some(query) = app.take_related_request() {
+            let (history, events) = (history.clone(), sources.tx.clone());
+            tokio::spawn(async move {
+                let txs = history
+                    .related_txs(&query, nearx::related::HISTORY_CANDIDATES)
+                    .await;
+                let _ = events.send(AppEvent::RelatedTxs { hash: query.hash, txs });
+            });
+        }
+
+        // Contract digest: History aggregation plus a sampled outcome fetch, off the UI loop
+        if let Some((contract, window_secs)) = app.take_digest_request() {
+            let url = cfg.archival_or_rpc_url();
+            let (timeout_ms, token) = (cfg.rpc_timeout_ms, cfg.fastnear_auth_token.clone());
+            let (history, events) = (history.clone(), sources.tx.clone());
+            tokio::spawn(async move {
+                let digest = nearx::digest::generate(
+                    &history,
+                    &url,
+                    timeout_ms,
+                    token.as_deref(),
+                    &contract,
+                    window_secs,
+                    nearx::digest::DEFAULT_SAMPLE,
+                )
+                .await;
+                let _ = events.send(AppEvent::ContractDigest(digest));
+            });
+        }
+
+        // Export re-run (exports overlay): rebuild the digest for a window ending now
+        if let Some(record) = app.take_export_rerun() {
+            if let ExportParams::Digest { contract, window_secs, sample } = record.params.clone() {
+                let url = cfg.archival_or_rpc_url();
+                let (timeout_ms, token) = (cfg.rpc_timeout_ms, cfg.fastnear_auth_token.clone());
+                let (history, events, times) = (history.clone(), sources.tx.clone(), app.time_display().clone());
+                let filter = record.filter.clone().unwrap_or_default();
+                tokio::spawn(async move {
+                    let digest = nearx::digest::generate(
+                        &history,
+                        &url,
+                        timeout_ms,
+                        token.as_deref(),
+                        &contract,
+                        window_secs,
+                        sample,
+                    )
+                    .await;
+                    let path = record.rerun_path(&nearx::exports::stamp());
+                    let now = chrono::Utc::now().timestamp_millis();
+                    let fresh = nearx::exports::digest_record(&path, &digest, sample, now).with_filter(&filter);
+                    let result = match std::fs::write(&path, digest.to_markdown(&times)) {
+                        Ok(()) => Ok(history.put_export(fresh).await),
+                        Err(e) => Err(format!("writing {}: {e}", path.display())),
+                    };
+                    let _ = events.send(AppEvent::ExportWritten(result));
+                });
+            }
+        }
+
+        // HTML report (Shift+R or a re-run): receipts and logs for a tx, then write
+        if let Some(mut job) = app.take_report_request() {
+            let url = cfg.archival_or_rpc_url();
+            let (timeout_ms, token) = (cfg.rpc_timeout_ms, cfg.fastnear_auth_token.clone());
+            let (outcomes, history, events) = (outcomes.clone(), history.clone(), sources.tx.clone());
+            let theme = *app.theme();
+            tokio::spawn(async move {
+                if let ExportParams::Report { tx_hash: Some(hash), .. } = &job.params {
+                    let status = match &job.signer {
+                        Some(signer) => outcomes
+                            .tx_status(&url, hash, signer, "FINAL", timeout_ms, token.as_deref())
+                            .await
+                            .map_err(|e| format!("{e:#}")),
+                        None => Err("tx signer unknown".to_string()),
+                    };
+                    match status {
+                        Ok(status) => job.report.receipts = nearx::html_report::receipts_from_status(&status),
+                        Err(e) => job.report.receipts_note = Some(format!("Receipts unavailable: {e}")),
+                    }
+                }
+                let html = nearx::html_report::render(&job.report, &theme);
+                let now = chrono::Utc::now().timestamp_millis();
+                let record = ExportRecord::new(&job.path, job.params, job.rows, now);
+                let result = match std::fs::write(&job.path, html) {
+                    Ok(()) => Ok(history.put_export(record).await),
+                    Err(e) => Err(format!("writing {}: {e}", job.path.display())),
+                };
+                let _ = events.send(AppEvent::ExportWritten(result));
+            });
+        }
+
+        // Tx collection import: History, then FastNEAR, then outcomes; results stream back
+        if let Some(hashes) = app.take_import_request() {
+            let params = nearx::collection::ResolveParams {
+                rpc_url: cfg.archival_or_rpc_url(),
+                tx_api_url: cfg.fastnear_tx_api_url.clone(),
+                timeout_ms: cfg.rpc_timeout_ms,
+                auth_token: cfg.fastnear_auth_token.clone(),
+            };
+            let (outcomes, history, events) = (outcomes.clone(), history.clone(), sources.tx.clone());
+            tokio::spawn(async move {
+                nearx::collection::resolve(&history, &outcomes, &params, hashes, |resolution| {
+                    let _ = events.send(AppEvent::CollectionResolved(resolution));
+                })
+                .await;
+            });
+        }
+
+        // State inspector: view_state off the UI loop
+        if let Some((account, prefix)) = app.take_state_request() {
+            let url = cfg.near_node_url.clone();
+            let (timeout_ms, token) = (cfg.rpc_timeout_ms, cfg.fastnear_auth_token.clone());
+            let events = sources.tx.clone();
+            tokio::spawn(async move {
+                let result = nearx::state_view::fetch(&url, &account, &prefix, timeout_ms, token.as_deref())
+                    .await
+                    .map_err(|e| format!("{e:#}"));
+                let _ = events.send(AppEvent::ContractState { account, prefix, result });
+            });
+        }
+
+        // Holders tab: FastNEAR lookups (the app spaces them out)
+        if let Some((token, account)) = app.take_holders_request() {
+            let (api_url, rpc_url) = (cfg.fastnear_api_url.clone(), cfg.near_node_url.clone());
+            let (timeout_ms, auth) = (cfg.rpc_timeout_ms, cfg.fastnear_auth_token.clone());
+            let events = sources.tx.clone();
+            tokio::spawn(async move {
+                let result = nearx::holders::fetch(
+                    &api_url,
+                    &rpc_url,
+                    &token,
+                    account.as_deref(),
+                    timeout_ms,
+                    auth.as_deref(),
+                )
+                .await
+                .map_err(|e| format!("{e:#}"));
+                let _ = events.send(AppEvent::TokenHolders { token, account, result });
+            });
+        }
+
         // Periodic housekeeping (backfill chain, etc).
         app.on_tick(Instant::now());
 
@@ -209,6 +884,15 @@ async fn run_loop(
             terminal.draw(|f| ui::draw(f, app, &marks_list))?;
             last_frame = Instant::now();
         }
+        if let Some(mirror) = mirror.as_mut() {
+            mirror.publish(app);
+            // Let the operator know when people start or stop watching
+            let viewers = mirror.viewers();
+            if viewers != mirror_viewers {
+                mirror_viewers = viewers;
+                app.push_toast(ToastLevel::Info, format!("Mirror: {viewers} watching"));
+            }
+        }
         if app.quit_flag() {
             break;
         }
@@ -227,6 +911,19 @@ fn handle_mouse(
             let (col, row) = (mouse.column, mouse.row);
             let size = terminal.size()?;
 
+            // Zoomed list pane fills the body: clicks select rows in that pane
+            match app.zoomed_pane() {
+                Some(ZoomedPane::Blocks) => {
+                    app.select_block_row((row as i32 - 2).max(0) as usize);
+                    return Ok(());
+                }
+                Some(ZoomedPane::Txs) => {
+                    app.select_tx_row((row as i32 - 2).max(0) as usize);
+                    return Ok(());
+                }
+                _ => {}
+            }
+
             let mid_row = (size.height as i32) / 2;
             let mid_col = (size.width as i32) / 2;
 
@@ -274,6 +971,30 @@ fn handle_mouse(
     Ok(())
 }
 
+/// Key event for a clicked footer segment
+fn segment_key_event(action: &SegmentAction) -> Option<KeyEvent> {
+    let code = match action.code.as_str() {
+        "Tab" => KeyCode::Tab,
+        "Enter" => KeyCode::Enter,
+        "Escape" => KeyCode::Esc,
+        s => {
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => KeyCode::Char(c),
+                _ => return None,
+            }
+        }
+    };
+    let mut modifiers = KeyModifiers::NONE;
+    if action.ctrl {
+        modifiers |= KeyModifiers::CONTROL;
+    }
+    if action.shift {
+        modifiers |= KeyModifiers::SHIFT;
+    }
+    Some(KeyEvent::new(code, modifiers))
+}
+
 /// Convert crossterm KeyEvent to UiAction::Key (generic keyboard input)
 /// Returns None for TUI-specific commands (quit, marks, search, filter modes, etc.)
 fn key_event_to_ui_action(k: KeyEvent) -> Option<UiAction> {
@@ -304,6 +1025,37 @@ fn key_event_to_ui_action(k: KeyEvent) -> Option<UiAction> {
     })
 }
 
+/// Write an export file and record it in History (toast either way)
+async fn save_export(app: &mut App, history: &History, contents: &str, record: ExportRecord) {
+    if let Err(e) = std::fs::write(&record.path, contents) {
+        app.push_toast(ToastLevel::Error, format!("Export failed: {e}"));
+        return;
+    }
+    let record = history.put_export(record).await;
+    app.export_recorded(record);
+}
+
+/// Export the pin board (with the pinned `marks`) as markdown to `path`
+async fn export_board(app: &mut App, history: &History, path: &std::path::Path, marks: &[nearx::types::Mark]) {
+    let (markdown, items) = app.board_export(marks);
+    let now = chrono::Utc::now().timestamp_millis();
+    let mut record = ExportRecord::new(path, ExportParams::PinBoard, items.len(), now).with_filter(app.filter_query());
+    if let (Some(first), Some(last)) = (items.first(), items.last()) {
+        record = record.with_range(first.at_ms, last.at_ms);
+    }
+    app.count_feature("export.pinboard_md");
+    save_export(app, history, &markdown, record).await;
+}
+
+/// Fetch one History browser page for the current range
+async fn load_history_page(app: &mut App, history: &History, cursor: nearx::history_browser::BrowseCursor) {
+    let range = app.history_browser().range.clone();
+    let blocks = history
+        .browse_blocks(&range, cursor, nearx::history_browser::PAGE_BLOCKS)
+        .await;
+    app.history_browser_mut().set_page(cursor, blocks);
+}
+
 async fn handle_key(app: &mut App, k: KeyEvent, history: &History, jump_marks: &mut JumpMarks) {
     // Handle filter input mode separately
     if app.input_mode() == InputMode::Filter {
@@ -317,6 +1069,21 @@ async fn handle_key(app: &mut App, k: KeyEvent, history: &History, jump_marks: &
         return;
     }
 
+    // Handle endpoint settings overlay (Tab switches field, Enter applies)
+    if app.input_mode() == InputMode::Endpoints {
+        match k.code {
+            KeyCode::Char(c) => app.endpoint_add_char(c),
+            KeyCode::Backspace => app.endpoint_backspace(),
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                app.endpoint_next_field()
+            }
+            KeyCode::Enter => app.apply_endpoints(),
+            KeyCode::Esc => app.close_endpoints(),
+            _ => {}
+        }
+        return;
+    }
+
     // Handle search input mode
     if app.input_mode() == InputMode::Search {
         match k.code {
@@ -339,9 +1106,122 @@ async fn handle_key(app: &mut App, k: KeyEvent, history: &History, jump_marks: &
             }
             KeyCode::Up => app.search_up(),
             KeyCode::Down => app.search_down(),
+            KeyCode::PageUp => {
+                let n = app.search_preview_viewport_lines() as isize;
+                app.scroll_search_preview(-n);
+            }
+            KeyCode::PageDown => {
+                let n = app.search_preview_viewport_lines() as isize;
+                app.scroll_search_preview(n);
+            }
             KeyCode::Esc => app.close_search(),
             _ => {}
         }
+
+        // Lazily load the preview for the highlighted result
+        if app.input_mode() == InputMode::Search {
+            if let Some(hash) = app.search_preview_pending() {
+                let raw_json = history.get_tx(hash.clone()).await;
+                app.set_search_preview(hash, raw_json.as_deref());
+            }
+        }
+        return;
+    }
+
+    // Handle Risk browser (typing re-queries; Enter jumps to the tx)
+    if app.input_mode() == InputMode::Risk {
+        match k.code {
+            KeyCode::Char(c) => app.risk_add_char(c),
+            KeyCode::Backspace => app.risk_backspace(),
+            KeyCode::Up => app.risk_up(),
+            KeyCode::Down => app.risk_down(),
+            KeyCode::Enter => {
+                if let Some(hit) = app.get_selected_risk() {
+                    let (hash, height) = (hit.analysis.hash.clone(), hit.height);
+                    let raw_json = history.get_tx(hash.clone()).await;
+                    app.open_risk_hit(&hash, height, raw_json.as_deref());
+                }
+                return;
+            }
+            KeyCode::Esc => {
+                app.close_risk();
+                return;
+            }
+            _ => return,
+        }
+        let results = history.list_analyses(app.risk_query(), 200).await;
+        app.set_risk_results(results);
+        return;
+    }
+
+    // Account feed: type to filter, Enter jumps to the tx
+    if app.input_mode() == InputMode::Accounts {
+        match k.code {
+            KeyCode::Char(c) => app.account_feed_add_char(c),
+            KeyCode::Backspace => app.account_feed_backspace(),
+            KeyCode::Up => app.account_feed_up(),
+            KeyCode::Down => app.account_feed_down(),
+            KeyCode::Enter => {
+                if let Some((hash, height)) = app.selected_account_event() {
+                    let raw_json = history.get_tx(hash.clone()).await;
+                    app.open_account_event(&hash, height, raw_json.as_deref());
+                }
+            }
+            KeyCode::Esc => app.close_account_feed(),
+            _ => {}
+        }
+        return;
+    }
+
+    // History browser: type a range, Enter loads it, PgDn/PgUp page older/newer
+    if app.input_mode() == InputMode::HistoryBrowser {
+        let browser = app.history_browser_mut();
+        let cursor = match k.code {
+            KeyCode::Char(c) => {
+                browser.query.push(c);
+                None
+            }
+            KeyCode::Backspace => {
+                browser.query.pop();
+                None
+            }
+            KeyCode::Enter => browser.apply_query(),
+            KeyCode::Up => {
+                browser.move_by(-1);
+                None
+            }
+            KeyCode::Down => {
+                browser.move_by(1);
+                None
+            }
+            KeyCode::Tab => {
+                browser.cycle_focus();
+                None
+            }
+            KeyCode::PageDown => browser.older(),
+            KeyCode::PageUp => browser.newer(),
+            KeyCode::Home => Some(nearx::history_browser::BrowseCursor::Newest),
+            KeyCode::Esc => {
+                app.close_history_browser();
+                None
+            }
+            _ => None,
+        };
+        if let Some(cursor) = cursor {
+            load_history_page(app, history, cursor).await;
+        }
+        return;
+    }
+
+    // Sources overlay: toggle block sources for this session
+    if app.input_mode() == InputMode::Sources {
+        match k.code {
+            KeyCode::Up => app.sources_up(),
+            KeyCode::Down => app.sources_down(),
+            KeyCode::Enter | KeyCode::Char(' ') => app.toggle_selected_source(),
+            KeyCode::Esc => app.close_sources(),
+            _ => {}
+        }
         return;
     }
 
@@ -361,21 +1241,38 @@ async fn handle_key(app: &mut App, k: KeyEvent, history: &History, jump_marks: &
         match k.code {
             KeyCode::Up => app.marks_up(),
             KeyCode::Down => app.marks_down(),
-            KeyCode::Enter => {
+            KeyCode::Enter => match app.get_selected_mark().map(|(m, t)| (m.clone(), t)) {
                 // Jump to selected mark
-                if let Some(mark) = app.get_selected_mark().cloned() {
+                Some((mark, false)) => {
                     app.jump_to_mark(&mark);
                     app.close_marks();
                 }
-            }
+                // Trash entry: restore it
+                Some((mark, true)) => {
+                    jump_marks.restore(&mark.label).await;
+                    app.refresh_marks(jump_marks.list(), jump_marks.trashed());
+                    app.show_toast(format!("Restored mark {}", mark.label));
+                }
+                None => {}
+            },
             KeyCode::Char('d') => {
-                // Delete selected mark
-                if let Some(mark) = app.get_selected_mark() {
+                // Delete selected mark (to the trash); in the trash, delete for good
+                if let Some((mark, trashed)) = app.get_selected_mark() {
                     let label = mark.label.clone();
-                    jump_marks.remove_by_label(&label).await;
-                    // Reload marks list
-                    let marks_list = jump_marks.list();
-                    app.open_marks(marks_list);
+                    if trashed {
+                        jump_marks.purge_by_label(&label).await;
+                        app.show_toast(format!("Mark {label} deleted permanently"));
+                    } else {
+                        jump_marks.remove_by_label(&label).await;
+                        app.show_toast(format!("Mark {label} moved to trash (u to undo)"));
+                    }
+                    app.refresh_marks(jump_marks.list(), jump_marks.trashed());
+                }
+            }
+            KeyCode::Char('u') => {
+                if let Some(label) = jump_marks.undo_delete().await {
+                    app.refresh_marks(jump_marks.list(), jump_marks.trashed());
+                    app.show_toast(format!("Restored mark {label}"));
                 }
             }
             KeyCode::Esc => app.close_marks(),
@@ -384,6 +1281,246 @@ async fn handle_key(app: &mut App, k: KeyEvent, history: &History, jump_marks: &
         return;
     }
 
+    // Handle contract digest overlay mode
+    if app.input_mode() == InputMode::Digest {
+        match k.code {
+            KeyCode::Up => app.digest_scroll_by(-1),
+            KeyCode::Down => app.digest_scroll_by(1),
+            KeyCode::PageUp => app.digest_scroll_by(-10),
+            KeyCode::PageDown => app.digest_scroll_by(10),
+            KeyCode::Char('w') => app.digest_cycle_window(),
+            KeyCode::Char('e') => {
+                // Export as markdown to the working directory
+                if let Some(digest) = app.digest() {
+                    let markdown = digest.to_markdown(app.time_display());
+                    let path = ExportParams::Digest {
+                        contract: digest.contract.clone(),
+                        window_secs: digest.window_secs,
+                        sample: nearx::digest::DEFAULT_SAMPLE,
+                    }
+                    .file_name(&nearx::exports::stamp());
+                    let now = chrono::Utc::now().timestamp_millis();
+                    let record = nearx::exports::digest_record(
+                        std::path::Path::new(&path),
+                        digest,
+                        nearx::digest::DEFAULT_SAMPLE,
+                        now,
+                    )
+                    .with_filter(app.filter_query());
+                    app.count_feature("export.digest_md");
+                    save_export(app, history, &markdown, record).await;
+                }
+            }
+            KeyCode::Esc => app.close_digest(),
+            _ => {}
+        }
+        return;
+    }
+
+    // Handle telemetry preview mode
+    if app.input_mode() == InputMode::Telemetry {
+        match k.code {
+            KeyCode::Up => app.telemetry_scroll_by(-1),
+            KeyCode::Down => app.telemetry_scroll_by(1),
+            KeyCode::PageUp => app.telemetry_scroll_by(-10),
+            KeyCode::PageDown => app.telemetry_scroll_by(10),
+            KeyCode::Char('x') => {
+                app.purge_telemetry();
+                history.set_telemetry(None).await;
+            }
+            KeyCode::Esc => app.close_telemetry(),
+            _ => {}
+        }
+        return;
+    }
+
+    // Handle state inspector overlay mode
+    if app.input_mode() == InputMode::State {
+        if k.code == KeyCode::Tab && app.state_view().is_some_and(|v| v.prefix_draft.is_none()) {
+            app.toggle_holders_tab();
+            return;
+        }
+        if let Some(tab) = app.holders_tab_mut() {
+            match k.code {
+                KeyCode::Up => tab.up(),
+                KeyCode::Down => tab.down(),
+                KeyCode::Enter => app.holders_jump_selected(),
+                KeyCode::Char('r') => app.request_holders(true),
+                KeyCode::Esc => app.close_state_view(),
+                _ => {}
+            }
+            return;
+        }
+        let Some(view) = app.state_view_mut() else {
+            return;
+        };
+        if let Some(draft) = view.prefix_draft.as_mut() {
+            match k.code {
+                KeyCode::Enter => app.state_prefix_commit(),
+                KeyCode::Esc => view.prefix_draft = None,
+                KeyCode::Backspace => {
+                    draft.pop();
+                }
+                KeyCode::Char(c) => draft.push(c),
+                _ => {}
+            }
+            return;
+        }
+        match k.code {
+            KeyCode::Up => view.up(),
+            KeyCode::Down => view.down(),
+            KeyCode::Enter | KeyCode::Char(' ') => view.toggle(),
+            KeyCode::PageDown | KeyCode::Char(']') => view.next_page(),
+            KeyCode::PageUp | KeyCode::Char('[') => view.prev_page(),
+            KeyCode::Char('/') => view.prefix_draft = Some(view.prefix_label()),
+            KeyCode::Esc => app.close_state_view(),
+            _ => {}
+        }
+        return;
+    }
+
+    // Handle pin board overlay mode
+    if app.input_mode() == InputMode::Board {
+        if app.board_note_draft().is_some() {
+            match k.code {
+                KeyCode::Enter => app.board_note_commit(),
+                KeyCode::Esc => app.board_note_cancel(),
+                KeyCode::Backspace => app.board_note_backspace(),
+                KeyCode::Char(c) => app.board_note_add_char(c),
+                _ => {}
+            }
+            return;
+        }
+        match k.code {
+            KeyCode::Up => app.board_up(),
+            KeyCode::Down => app.board_down(),
+            KeyCode::Enter => app.board_jump_selected(),
+            KeyCode::Char('a') => app.board_start_note(),
+            KeyCode::Char('d') => app.board_delete_selected(),
+            KeyCode::Char('e') => {
+                // Export as markdown to the working directory
+                let path = ExportParams::PinBoard.file_name(&nearx::exports::stamp());
+                export_board(app, history, std::path::Path::new(&path), &jump_marks.list()).await;
+            }
+            KeyCode::Esc => app.close_board(),
+            _ => {}
+        }
+        return;
+    }
+
+    // Handle exports overlay mode
+    if app.input_mode() == InputMode::Exports {
+        match k.code {
+            KeyCode::Up => app.exports_up(),
+            KeyCode::Down => app.exports_down(),
+            KeyCode::Enter => {
+                // Re-run with the same parameters (digests are rebuilt off the UI loop)
+                if let Some(record) = app.exports_rerun_selected() {
+                    if let ExportParams::Report { .. } = record.params {
+                        app.rerun_report(&record, &jump_marks.list());
+                    } else if let ExportParams::Collection { .. } = record.params {
+                        app.rerun_collection(&record);
+                    } else {
+                        let path = record.rerun_path(&nearx::exports::stamp());
+                        export_board(app, history, &path, &jump_marks.list()).await;
+                    }
+                }
+            }
+            KeyCode::Char('o') => {
+                if let Some(folder) = app.selected_export().map(|r| r.folder()) {
+                    if !nearx::platform::open_folder(&folder) {
+                        app.push_toast(ToastLevel::Warn, format!("Could not open {}", folder.display()));
+                    }
+                }
+            }
+            KeyCode::Char('d') => {
+                if let Some(id) = app.exports_remove_selected() {
+                    history.del_export(id);
+                }
+            }
+            KeyCode::Esc => app.close_exports(),
+            _ => {}
+        }
+        return;
+    }
+
+    // Handle tx import box (pasted text arrives as Event::Paste)
+    if app.input_mode() == InputMode::Import {
+        match k.code {
+            KeyCode::Enter => app.import_submit(),
+            KeyCode::Esc => app.close_import(),
+            KeyCode::Backspace => app.import_backspace(),
+            KeyCode::Char(c) => app.import_add_char(c),
+            _ => {}
+        }
+        return;
+    }
+
+    // Handle tx collection overlay
+    if app.input_mode() == InputMode::Collection {
+        match k.code {
+            KeyCode::Up => app.collection_up(),
+            KeyCode::Down => app.collection_down(),
+            KeyCode::Enter => app.collection_open_selected(),
+            KeyCode::Char(' ') => app.collection_toggle_checked(),
+            KeyCode::Char('a') => app.collection_toggle_all(),
+            KeyCode::Char('m') => {
+                let targets = app.collection_mark_targets();
+                let added = jump_marks.add_import_marks(&targets).await;
+                app.show_toast(format!(
+                    "Marked {added} txs ({} already marked or unresolved)",
+                    app.collection().map_or(0, |c| c.targets().len()) - added
+                ));
+            }
+            KeyCode::Char('e') => {
+                let path = std::path::PathBuf::from(
+                    ExportParams::Collection { hashes: Vec::new() }.file_name(&nearx::exports::stamp()),
+                );
+                if let Some((markdown, record)) = app.collection_export(&path) {
+                    save_export(app, history, &markdown, record).await;
+                }
+            }
+            KeyCode::Char('i') => app.open_import(),
+            KeyCode::Esc => app.close_collection(),
+            _ => {}
+        }
+        return;
+    }
+
+    // Handle watch manager overlay mode
+    if app.input_mode() == InputMode::Watches {
+        if app.watch_webhook_draft().is_some() {
+            match k.code {
+                KeyCode::Enter => app.watch_webhook_commit(),
+                KeyCode::Esc => app.watch_webhook_cancel(),
+                KeyCode::Backspace => app.watch_webhook_backspace(),
+                KeyCode::Char(c) => app.watch_webhook_add_char(c),
+                _ => {}
+            }
+            return;
+        }
+        match k.code {
+            KeyCode::Up => app.watch_up(),
+            KeyCode::Down => app.watch_down(),
+            KeyCode::Enter => app.watch_apply_selected(),
+            KeyCode::Char('w') => app.watch_start_webhook(),
+            KeyCode::Char('d') => app.watch_delete_selected(),
+            KeyCode::Esc => app.close_watches(),
+            _ => {}
+        }
+        return;
+    }
+
+    // Details jump prompt: every key goes to the shared handler (digits, %, Enter)
+    if app.details_goto_draft().is_some() {
+        if let Some(action) = key_event_to_ui_action(k) {
+            apply_ui_action(app, action);
+        } else {
+            app.details_goto_cancel();
+        }
+        return;
+    }
+
     // Normal mode keys
     // TUI-specific commands first (quit, marks, search, FPS, filter)
     match (k.code, k.modifiers) {
@@ -401,17 +1538,70 @@ async fn handle_key(app: &mut App, k: KeyEvent, history: &History, jump_marks: &
             app.start_search();
         }
 
+        // Owned-accounts filter (accounts from the credentials dir)
+        (KeyCode::Char('u'), KeyModifiers::CONTROL) => {
+            app.toggle_owned_only();
+        }
+
+        // Risk browser (stored tx-analyzer analyses, high-risk first view)
+        (KeyCode::Char('r'), KeyModifiers::CONTROL) => {
+            app.open_risk();
+            let results = history.list_analyses("", 200).await;
+            app.set_risk_results(results);
+        }
+
+        // Account creation / deletion feed (live blocks + History)
+        (KeyCode::Char('a'), KeyModifiers::CONTROL) => {
+            app.open_account_feed();
+            let events = history
+                .account_txs(nearx::account_feed::HISTORY_LIMIT)
+                .await
+                .iter()
+                .flat_map(nearx::account_feed::events_from_stored)
+                .collect();
+            app.merge_account_events(events);
+        }
+
+        // History browser (stored blocks by height / date, independent of the live pane)
+        (KeyCode::Char('b'), KeyModifiers::CONTROL) => {
+            if let Some(cursor) = app.open_history_browser() {
+                load_history_page(app, history, cursor).await;
+            }
+        }
+
+        // Block source health + per-source toggles
+        (KeyCode::Char('s'), KeyModifiers::CONTROL) => {
+            app.open_sources();
+        }
+
+        // Telemetry preview (exactly what would be sent) + purge
+        (KeyCode::Char('t'), KeyModifiers::CONTROL) => {
+            app.open_telemetry();
+        }
+
+        (KeyCode::Char('k'), KeyModifiers::CONTROL) => {
+            // Key-value state of the selected tx's receiver (view_state)
+            app.open_state_view();
+        }
+        (KeyCode::Char('g'), KeyModifiers::CONTROL) => {
+            // Activity digest of the selected tx's receiver (from History)
+            app.request_digest();
+        }
+        // Endpoint settings (hot-swap RPC / archival URLs)
+        (KeyCode::Char('e'), KeyModifiers::CONTROL) => {
+            app.open_endpoints();
+        }
+
+        // Validator stake concentration panel
+        (KeyCode::Char('v'), KeyModifiers::NONE) => {
+            app.toggle_stake_panel();
+        }
+
         // Filter mode (TUI-specific)
         (KeyCode::Char('/'), _) | (KeyCode::Char('f'), KeyModifiers::NONE) => {
             app.start_filter();
         }
 
-        // Escape clears filter if non-empty
-        (KeyCode::Esc, _) => {
-            if !app.filter_query().is_empty() {
-                app.clear_filter();
-            }
-        }
         // Jump marks
         (KeyCode::Char('m'), _) => {
             // Set mark with auto-label
@@ -457,7 +1647,39 @@ async fn handle_key(app: &mut App, k: KeyEvent, history: &History, jump_marks: &
         (KeyCode::Char('M'), KeyModifiers::SHIFT) => {
             // Open marks overlay
             let marks_list = jump_marks.list();
-            app.open_marks(marks_list);
+            app.open_marks(marks_list, jump_marks.trashed());
+        }
+        (KeyCode::Char('B'), KeyModifiers::SHIFT) => {
+            // Open the pin board (pinned marks, notes, recent copies)
+            app.open_board(jump_marks.list());
+        }
+        (KeyCode::Char('E'), KeyModifiers::SHIFT) => {
+            // Open the exports registry
+            let records = history.exports(nearx::exports::LIST_LIMIT).await;
+            app.open_exports(records);
+        }
+        (KeyCode::Char('R'), KeyModifiers::SHIFT) => {
+            // Standalone HTML report of the selected block / tx
+            app.request_report(&jump_marks.list());
+        }
+        (KeyCode::Char('I'), KeyModifiers::SHIFT) => {
+            // Tx collection (paste a list of tx hashes to import)
+            app.open_collection();
+        }
+        (KeyCode::Char('W'), KeyModifiers::SHIFT) => {
+            // Turn the current filter into a standing watch
+            app.watch_current_filter();
+        }
+        (KeyCode::Char('w'), KeyModifiers::CONTROL) => {
+            // Open the watch manager
+            app.open_watches();
+        }
+        (KeyCode::Char('u'), KeyModifiers::NONE) => {
+            // Undo the last mark deletion (this session)
+            match jump_marks.undo_delete().await {
+                Some(label) => app.show_toast(format!("Restored mark {label}")),
+                None => app.show_toast("Nothing to undo".to_string()),
+            }
         }
         (KeyCode::Char('\''), _) => {
             // Quick jump feature (jump-pending mode) not yet implemented
@@ -0,0 +1,300 @@
+//! Bridge transfer recognition (Rainbow Bridge, Wormhole)
+//!
+//! Classifies function calls against the known mainnet bridge contracts into
+//! lock / mint / burn / unlock flows and, when the counterpart chain's tx hash
+//! is present in the call args, carries it along as provenance. Logs are not
+//! available on `TxLite`, so only args are inspected.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::near_args::{decode_args_base64, DecodedArgs};
+use crate::types::{ActionSummary, TxLite};
+
+/// Rainbow Bridge ERC-20 connector (bridged tokens are `<addr>.factory.bridge.near`)
+const RAINBOW_FACTORY: &str = "factory.bridge.near";
+/// Rainbow Bridge NEAR connector (eNEAR on Ethereum)
+const RAINBOW_ENEAR: &str = "e-near.bridge.near";
+/// Rainbow Bridge ETH connector (nETH lives in the Aurora engine)
+const RAINBOW_ETH: &str = "aurora";
+/// Wormhole token bridge (Portal)
+const WORMHOLE_TOKEN_BRIDGE: &str = "contract.portalbridge.near";
+
+/// Wormhole chain id of NEAR
+const WORMHOLE_CHAIN_NEAR: u16 = 15;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BridgeProtocol {
+    Rainbow,
+    Wormhole,
+}
+
+/// What happens to the asset on NEAR
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BridgeFlow {
+    Lock,
+    Mint,
+    Burn,
+    Unlock,
+}
+
+impl BridgeFlow {
+    /// In = asset arrives on NEAR (mint/unlock), out = leaves NEAR (lock/burn)
+    pub fn is_inbound(self) -> bool {
+        matches!(self, BridgeFlow::Mint | BridgeFlow::Unlock)
+    }
+}
+
+/// A recognized bridge leg of a NEAR transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BridgeTransfer {
+    pub protocol: BridgeProtocol,
+    pub flow: BridgeFlow,
+    /// Other side of the bridge ("Ethereum", "Solana", ...)
+    pub chain: String,
+    /// Counterpart chain tx hash, when present in args
+    pub counterpart_tx: Option<String>,
+}
+
+impl BridgeTransfer {
+    /// "bridge in from Ethereum (tx 0x1234…abcd)"
+    pub fn summary(&self) -> String {
+        let dir = if self.flow.is_inbound() {
+            format!("bridge in from {}", self.chain)
+        } else {
+            format!("bridge out to {}", self.chain)
+        };
+        match &self.counterpart_tx {
+            Some(h) if h.len() > 14 => format!("{dir} (tx {}…{})", &h[..6], &h[h.len() - 4..]),
+            Some(h) => format!("{dir} (tx {h})"),
+            None => dir,
+        }
+    }
+
+    /// Lowercased words matched by `bridge:` filter terms
+    pub fn filter_words(&self) -> String {
+        let protocol = match self.protocol {
+            BridgeProtocol::Rainbow => "rainbow",
+            BridgeProtocol::Wormhole => "wormhole",
+        };
+        let flow = match self.flow {
+            BridgeFlow::Lock => "lock",
+            BridgeFlow::Mint => "mint",
+            BridgeFlow::Burn => "burn",
+            BridgeFlow::Unlock => "unlock",
+        };
+        let dir = if self.flow.is_inbound() { "in" } else { "out" };
+        format!("{protocol} {flow} {dir} {}", self.chain.to_lowercase())
+    }
+}
+
+/// Recognize the first bridge call in a transaction (delegate actions included)
+pub fn detect(tx: &TxLite) -> Option<BridgeTransfer> {
+    let receiver = tx.receiver_id.as_deref()?;
+    detect_actions(receiver, tx.actions.as_deref()?)
+}
+
+fn detect_actions(receiver: &str, actions: &[ActionSummary]) -> Option<BridgeTransfer> {
+    actions.iter().find_map(|a| match a {
+        ActionSummary::FunctionCall {
+            method_name,
+            args_decoded,
+            ..
+        } => {
+            let args = match args_decoded {
+                DecodedArgs::Json(v) => Some(v),
+                _ => None,
+            };
+            detect_call(receiver, method_name, args)
+        }
+        ActionSummary::Delegate {
+            receiver_id,
+            actions,
+            ..
+        } => detect_actions(receiver_id, actions),
+        _ => None,
+    })
+}
+
+/// Recognize a raw RPC transaction (`receiver_id` + `actions[].FunctionCall{method_name,args}`)
+pub fn detect_json(tx: &Value) -> Option<BridgeTransfer> {
+    let receiver = tx.get("receiver_id")?.as_str()?;
+    tx.get("actions")?.as_array()?.iter().find_map(|a| {
+        let fc = a.get("FunctionCall")?;
+        let method = fc.get("method_name")?.as_str()?;
+        let args = match decode_args_base64(fc.get("args").and_then(|v| v.as_str()), 0) {
+            DecodedArgs::Json(v) => Some(v),
+            _ => None,
+        };
+        detect_call(receiver, method, args.as_ref())
+    })
+}
+
+/// Classify one function call by contract + method
+pub fn detect_call(receiver: &str, method: &str, args: Option<&Value>) -> Option<BridgeTransfer> {
+    let rainbow = |flow| BridgeTransfer {
+        protocol: BridgeProtocol::Rainbow,
+        flow,
+        chain: "Ethereum".to_string(),
+        counterpart_tx: args.and_then(find_evm_tx_hash),
+    };
+
+    match (receiver, method) {
+        (RAINBOW_FACTORY, "deposit") => Some(rainbow(BridgeFlow::Mint)),
+        (r, "withdraw") if r.ends_with(".factory.bridge.near") => Some(rainbow(BridgeFlow::Burn)),
+        (RAINBOW_ENEAR, "migrate_to_ethereum") => Some(rainbow(BridgeFlow::Lock)),
+        (RAINBOW_ENEAR, "finalise_eth_to_near_transfer") => Some(rainbow(BridgeFlow::Unlock)),
+        (RAINBOW_ETH, "deposit") => Some(rainbow(BridgeFlow::Mint)),
+        (RAINBOW_ETH, "withdraw") => Some(rainbow(BridgeFlow::Burn)),
+
+        (WORMHOLE_TOKEN_BRIDGE, "submit_vaa") => {
+            let vaa = args.and_then(|a| a.get("vaa")).and_then(|v| v.as_str());
+            let (emitter, token_chain) = vaa.and_then(parse_vaa_chains).unzip();
+            let token_chain = token_chain.flatten();
+            // Tokens native to NEAR come back unlocked; everything else is minted wrapped
+            let flow = if token_chain == Some(WORMHOLE_CHAIN_NEAR) {
+                BridgeFlow::Unlock
+            } else {
+                BridgeFlow::Mint
+            };
+            Some(wormhole(flow, emitter, args))
+        }
+        (WORMHOLE_TOKEN_BRIDGE, "send_transfer_near") => {
+            Some(wormhole(BridgeFlow::Lock, target_chain(args), args))
+        }
+        (WORMHOLE_TOKEN_BRIDGE, "send_transfer_wormhole_token") => {
+            Some(wormhole(BridgeFlow::Burn, target_chain(args), args))
+        }
+        // NEP-141 tokens are bridged out by transferring them to the token bridge
+        (_, "ft_transfer_call")
+            if args.and_then(|a| a.get("receiver_id")).and_then(|v| v.as_str())
+                == Some(WORMHOLE_TOKEN_BRIDGE) =>
+        {
+            Some(wormhole(BridgeFlow::Lock, None, args))
+        }
+        _ => None,
+    }
+}
+
+fn wormhole(flow: BridgeFlow, chain: Option<u16>, args: Option<&Value>) -> BridgeTransfer {
+    BridgeTransfer {
+        protocol: BridgeProtocol::Wormhole,
+        flow,
+        chain: chain.map_or_else(|| "Wormhole".to_string(), wormhole_chain_name),
+        counterpart_tx: args.and_then(find_evm_tx_hash),
+    }
+}
+
+fn target_chain(args: Option<&Value>) -> Option<u16> {
+    let v = args?.get("chain")?;
+    v.as_u64()
+        .or_else(|| v.as_str().and_then(|s| s.parse().ok()))
+        .and_then(|c| u16::try_from(c).ok())
+}
+
+/// Wormhole chain ids of the commonly bridged networks
+pub fn wormhole_chain_name(id: u16) -> String {
+    match id {
+        1 => "Solana".into(),
+        2 => "Ethereum".into(),
+        4 => "BSC".into(),
+        5 => "Polygon".into(),
+        6 => "Avalanche".into(),
+        10 => "Fantom".into(),
+        15 => "NEAR".into(),
+        21 => "Sui".into(),
+        22 => "Aptos".into(),
+        23 => "Arbitrum".into(),
+        24 => "Optimism".into(),
+        30 => "Base".into(),
+        n => format!("Wormhole chain {n}"),
+    }
+}
+
+/// (emitter chain, token chain) from a hex-encoded VAA carrying a token transfer
+/// (payload 1 or 3). Token chain is `None` for other payloads.
+fn parse_vaa_chains(vaa_hex: &str) -> Option<(u16, Option<u16>)> {
+    let bytes = decode_hex(vaa_hex.trim_start_matches("0x"))?;
+    let be16 = |at: usize| Some(u16::from_be_bytes([*bytes.get(at)?, *bytes.get(at + 1)?]));
+
+    // Header: version(1) guardian_set(4) signature_count(1) signatures(66 each)
+    let body = 6 + 66 * *bytes.get(5)? as usize;
+    // Body: timestamp(4) nonce(4) emitter_chain(2) emitter(32) sequence(8) consistency(1)
+    let emitter_chain = be16(body + 8)?;
+    let payload = body + 51;
+    // Token transfer: id(1) amount(32) token_address(32) token_chain(2)
+    let token_chain = match bytes.get(payload) {
+        Some(1) | Some(3) => be16(payload + 65),
+        _ => None,
+    };
+    Some((emitter_chain, token_chain))
+}
+
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    if s.len() % 2 != 0 {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// First `0x` + 64 hex string anywhere in the args (EVM tx hashes)
+fn find_evm_tx_hash(v: &Value) -> Option<String> {
+    match v {
+        Value::String(s) => {
+            let hex = s.strip_prefix("0x")?;
+            (hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit())).then(|| s.to_lowercase())
+        }
+        Value::Array(items) => items.iter().find_map(find_evm_tx_hash),
+        Value::Object(map) => map.values().find_map(find_evm_tx_hash),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn rainbow_flows_and_provenance() {
+        let eth_tx = format!("0x{}", "ab".repeat(32));
+        let t = detect_call("factory.bridge.near", "deposit", Some(&json!({"eth_tx": eth_tx})))
+            .unwrap();
+        assert_eq!(t.flow, BridgeFlow::Mint);
+        assert_eq!(t.counterpart_tx.as_deref(), Some(eth_tx.as_str()));
+        assert_eq!(t.summary(), "bridge in from Ethereum (tx 0xabab…abab)");
+
+        let out = detect_call("6b175474e89094c44da98b954eedeac495271d0f.factory.bridge.near", "withdraw", None)
+            .unwrap();
+        assert_eq!(out.summary(), "bridge out to Ethereum");
+        assert_eq!(out.filter_words(), "rainbow burn out ethereum");
+        assert!(detect_call("token.near", "withdraw", None).is_none());
+    }
+
+    #[test]
+    fn wormhole_vaa_chains() {
+        // 1 signature, emitter chain 1 (Solana), token transfer of a NEAR-native token
+        let mut vaa = vec![1u8, 0, 0, 0, 3, 1];
+        vaa.extend([0u8; 66]);
+        vaa.extend([0u8; 8]);
+        vaa.extend(1u16.to_be_bytes());
+        vaa.extend([0u8; 41]);
+        vaa.push(1);
+        vaa.extend([0u8; 64]);
+        vaa.extend(WORMHOLE_CHAIN_NEAR.to_be_bytes());
+        let hex: String = vaa.iter().map(|b| format!("{b:02x}")).collect();
+
+        let t = detect_call(WORMHOLE_TOKEN_BRIDGE, "submit_vaa", Some(&json!({ "vaa": hex }))).unwrap();
+        assert_eq!(t.flow, BridgeFlow::Unlock);
+        assert_eq!(t.summary(), "bridge in from Solana");
+
+        let out = detect_call(WORMHOLE_TOKEN_BRIDGE, "send_transfer_near", Some(&json!({"chain": 2})))
+            .unwrap();
+        assert_eq!(out.summary(), "bridge out to Ethereum");
+    }
+}
@@ -0,0 +1,377 @@
+//! Fault injection for block sources (`CHAOS`, dev/testing only)
+//!
+//! Delays, reorders and drops the events a source task hands to the app, so
+//! the app's handling of late, shuffled and missing blocks can be exercised
+//! locally without an unreliable network. Each source task gets its own
+//! profile; the runtime puts a forwarding shim between the task and the app
+//! channel (`spawn_shim`). Health reports (`SourceStatus`) are never touched,
+//! so the footer badges stay truthful.
+//!
+//! Syntax: groups separated by `;` or spaces, each `[source:]key=value,...`.
+//! A group without a source applies to every source; source groups override
+//! its keys. Sources: `ws`, `rpc`, `graphql`, `archival`.
+//! - `latency=MS` - fixed delay before an event is delivered
+//! - `jitter=MS` - extra random delay, 0 to MS (order is kept)
+//! - `reorder=P` - chance (0-1) an event is held back past the ones after it
+//! - `drop=P` - chance (0-1) an event is discarded
+//!
+//! e.g. `CHAOS="latency=200,jitter=300 rpc:drop=0.05,reorder=0.1"`
+
+use anyhow::{anyhow, Result};
+
+use crate::source_health::SourceKind;
+
+/// Extra hold for a reordered event, on top of latency + jitter (ms)
+pub const REORDER_HOLD_MS: u64 = 1_500;
+
+/// Faults for one source (all zero = pass-through)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ChaosProfile {
+    pub latency_ms: u64,
+    pub jitter_ms: u64,
+    pub reorder: f64,
+    pub drop: f64,
+}
+
+impl ChaosProfile {
+    pub fn is_noop(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Parsed `CHAOS` setting: a profile per source
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ChaosSpec {
+    profiles: Vec<(SourceKind, ChaosProfile)>,
+}
+
+impl ChaosSpec {
+    /// Profile for `kind`, None when it runs unmodified
+    pub fn profile(&self, kind: SourceKind) -> Option<ChaosProfile> {
+        self.profiles
+            .iter()
+            .find(|(k, _)| *k == kind)
+            .map(|(_, p)| *p)
+            .filter(|p| !p.is_noop())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.profiles.iter().all(|(_, p)| p.is_noop())
+    }
+
+    /// One line per affected source, for the startup warning
+    pub fn describe(&self) -> Vec<String> {
+        ALL_KINDS
+            .iter()
+            .filter_map(|&k| self.profile(k).map(|p| (k, p)))
+            .map(|(k, p)| {
+                format!(
+                    "{}: latency {}ms, jitter {}ms, reorder {}, drop {}",
+                    k.label(),
+                    p.latency_ms,
+                    p.jitter_ms,
+                    p.reorder,
+                    p.drop
+                )
+            })
+            .collect()
+    }
+}
+
+const ALL_KINDS: [SourceKind; 4] = [
+    SourceKind::Ws,
+    SourceKind::Rpc,
+    SourceKind::GraphQl,
+    SourceKind::Archival,
+];
+
+/// Parse the `CHAOS` setting (empty = off)
+pub fn parse(s: &str) -> Result<ChaosSpec> {
+    let mut base = (ChaosProfile::default(), Vec::new());
+    let mut overrides: Vec<(SourceKind, Vec<(String, String)>)> = Vec::new();
+    for group in s.split([';', ' ']).filter(|g| !g.trim().is_empty()) {
+        let (kind, body) = match group.split_once(':') {
+            Some((src, body)) => (Some(parse_kind(src)?), body),
+            None => (None, group),
+        };
+        let mut pairs = Vec::new();
+        for pair in body.split(',').filter(|p| !p.is_empty()) {
+            let (k, v) = pair
+                .split_once('=')
+                .ok_or_else(|| anyhow!("Invalid CHAOS entry '{pair}'. Expected key=value"))?;
+            pairs.push((k.trim().to_lowercase(), v.trim().to_string()));
+        }
+        match kind {
+            Some(kind) => overrides.push((kind, pairs)),
+            None => base.1.extend(pairs),
+        }
+    }
+    apply(&mut base.0, &base.1)?;
+
+    let mut profiles: Vec<(SourceKind, ChaosProfile)> =
+        ALL_KINDS.iter().map(|&k| (k, base.0)).collect();
+    for (kind, pairs) in overrides {
+        if let Some((_, p)) = profiles.iter_mut().find(|(k, _)| *k == kind) {
+            apply(p, &pairs)?;
+        }
+    }
+    Ok(ChaosSpec { profiles })
+}
+
+fn parse_kind(s: &str) -> Result<SourceKind> {
+    match s.trim().to_lowercase().as_str() {
+        "ws" => Ok(SourceKind::Ws),
+        "rpc" => Ok(SourceKind::Rpc),
+        "graphql" => Ok(SourceKind::GraphQl),
+        "archival" => Ok(SourceKind::Archival),
+        other => Err(anyhow!(
+            "Invalid CHAOS source '{other}'. Expected ws, rpc, graphql or archival"
+        )),
+    }
+}
+
+fn apply(p: &mut ChaosProfile, pairs: &[(String, String)]) -> Result<()> {
+    for (k, v) in pairs {
+        match k.as_str() {
+            "latency" => p.latency_ms = parse_ms(k, v)?,
+            "jitter" => p.jitter_ms = parse_ms(k, v)?,
+            "reorder" => p.reorder = parse_chance(k, v)?,
+            "drop" => p.drop = parse_chance(k, v)?,
+            _ => {
+                return Err(anyhow!(
+                    "Invalid CHAOS key '{k}'. Expected latency, jitter, reorder or drop"
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+fn parse_ms(k: &str, v: &str) -> Result<u64> {
+    v.trim_end_matches("ms")
+        .parse()
+        .map_err(|_| anyhow!("CHAOS {k} must be milliseconds, got '{v}'"))
+}
+
+fn parse_chance(k: &str, v: &str) -> Result<f64> {
+    v.parse::<f64>()
+        .ok()
+        .filter(|p| (0.0..=1.0).contains(p))
+        .ok_or_else(|| anyhow!("CHAOS {k} must be a probability in [0, 1], got '{v}'"))
+}
+
+/// What happens to one event
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fate {
+    Drop,
+    /// Deliver this many ms after it was sent
+    Deliver(u64),
+}
+
+#[cfg(feature = "native")]
+pub use shim::{spawn_shim, Injector};
+
+#[cfg(feature = "native")]
+mod shim {
+    use std::cmp::Reverse;
+    use std::collections::{BinaryHeap, HashMap};
+
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+    use tokio::time::{sleep_until, Duration, Instant};
+
+    use super::{ChaosProfile, Fate, REORDER_HOLD_MS};
+    use crate::source_health::SourceKind;
+    use crate::supervisor::Supervisor;
+    use crate::types::AppEvent;
+
+    /// Decides each event's fate. In-order events never overtake each other
+    /// (jitter only stretches the gaps); reordered ones don't hold up the rest.
+    pub struct Injector {
+        profile: ChaosProfile,
+        rng: StdRng,
+        /// Latest in-order delivery, in ms since the shim started
+        last_due_ms: u64,
+    }
+
+    impl Injector {
+        pub fn new(profile: ChaosProfile, seed: u64) -> Self {
+            Self {
+                profile,
+                rng: StdRng::seed_from_u64(seed),
+                last_due_ms: 0,
+            }
+        }
+
+        /// Fate of an event sent at `now_ms` (ms since the shim started)
+        pub fn decide(&mut self, now_ms: u64) -> Fate {
+            let p = self.profile;
+            if self.rng.gen_bool(p.drop) {
+                return Fate::Drop;
+            }
+            let delay = p.latency_ms + self.rng.gen_range(0..=p.jitter_ms);
+            if self.rng.gen_bool(p.reorder) {
+                return Fate::Deliver(delay + p.jitter_ms + REORDER_HOLD_MS);
+            }
+            let due = (now_ms + delay).max(self.last_due_ms);
+            self.last_due_ms = due;
+            Fate::Deliver(due - now_ms)
+        }
+    }
+
+    /// Put a fault-injecting shim in front of `out` for `kind`'s source task:
+    /// returns the sender the task should use. The shim runs in `tasks`, so it
+    /// stops with the source.
+    pub fn spawn_shim(
+        tasks: &mut Supervisor,
+        kind: SourceKind,
+        profile: ChaosProfile,
+        out: UnboundedSender<AppEvent>,
+    ) -> UnboundedSender<AppEvent> {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let name = match kind {
+            SourceKind::Ws => "chaos ws",
+            SourceKind::Rpc => "chaos rpc",
+            SourceKind::GraphQl => "chaos graphql",
+            SourceKind::Archival => "chaos archival",
+        };
+        tasks.spawn(name, async move {
+            let start = Instant::now();
+            let mut injector = Injector::new(profile, rand::random());
+            // (due, seq) → event; seq keeps equal due times in send order
+            let mut pending: BinaryHeap<Reverse<(Instant, u64)>> = BinaryHeap::new();
+            let mut events = HashMap::new();
+            let mut seq = 0u64;
+            loop {
+                let next_due = pending.peek().map(|Reverse((due, _))| *due);
+                tokio::select! {
+                    ev = rx.recv() => {
+                        let Some(ev) = ev else { break };
+                        if matches!(ev, AppEvent::SourceStatus { .. }) {
+                            let _ = out.send(ev);
+                            continue;
+                        }
+                        let now_ms = start.elapsed().as_millis() as u64;
+                        match injector.decide(now_ms) {
+                            Fate::Drop => log::debug!("chaos {}: dropped an event", kind.label()),
+                            Fate::Deliver(ms) => {
+                                seq += 1;
+                                pending.push(Reverse((Instant::now() + Duration::from_millis(ms), seq)));
+                                events.insert(seq, ev);
+                            }
+                        }
+                    }
+                    _ = sleep_until(next_due.unwrap_or_else(Instant::now)), if next_due.is_some() => {
+                        let now = Instant::now();
+                        while let Some(Reverse((due, id))) = pending.peek().copied() {
+                            if due > now {
+                                break;
+                            }
+                            pending.pop();
+                            if let Some(ev) = events.remove(&id) {
+                                let _ = out.send(ev);
+                            }
+                        }
+                    }
+                }
+            }
+        });
+        tx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = "latency=200,jitter=50ms rpc:drop=0.1;archival:latency=0,jitter=0";
+
+    #[test]
+    fn empty_setting_is_off() {
+        assert!(parse("").unwrap().is_empty());
+        assert!(parse(" ; ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn global_terms_apply_to_every_source() {
+        let spec = parse(SPEC).unwrap();
+        for kind in [SourceKind::Ws, SourceKind::GraphQl] {
+            let p = spec.profile(kind).unwrap();
+            assert_eq!((p.latency_ms, p.jitter_ms, p.drop), (200, 50, 0.0));
+        }
+    }
+
+    #[test]
+    fn source_groups_override_the_global_terms() {
+        let spec = parse(SPEC).unwrap();
+        let rpc = spec.profile(SourceKind::Rpc).unwrap();
+        assert_eq!((rpc.latency_ms, rpc.jitter_ms, rpc.drop), (200, 50, 0.1));
+    }
+
+    #[test]
+    fn source_zeroed_by_its_group_runs_unmodified() {
+        let spec = parse(SPEC).unwrap();
+        assert_eq!(spec.profile(SourceKind::Archival), None);
+        assert_eq!(spec.describe().len(), 3);
+        assert!(!spec.describe().iter().any(|l| l.starts_with("Archival")));
+    }
+
+    #[test]
+    fn unknown_sources_and_keys_are_rejected() {
+        assert!(parse("smtp:drop=0.1").is_err());
+        assert!(parse("speed=1").is_err());
+        assert!(parse("latency").is_err());
+    }
+
+    #[test]
+    fn out_of_range_values_are_rejected() {
+        assert!(parse("drop=2").is_err());
+        assert!(parse("reorder=-0.1").is_err());
+        assert!(parse("latency=fast").is_err());
+    }
+
+    #[cfg(feature = "native")]
+    fn injector(profile: ChaosProfile) -> Injector {
+        Injector::new(profile, 7)
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn jitter_never_lets_in_order_events_overtake() {
+        let mut inj = injector(ChaosProfile {
+            latency_ms: 100,
+            jitter_ms: 500,
+            ..Default::default()
+        });
+        let mut last = 0;
+        for now in (0..2000).step_by(10) {
+            let Fate::Deliver(ms) = inj.decide(now) else {
+                panic!("dropped")
+            };
+            assert!(ms >= 100 && now + ms >= last);
+            last = now + ms;
+        }
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn reordered_events_are_held_back() {
+        let mut inj = injector(ChaosProfile {
+            latency_ms: 100,
+            reorder: 1.0,
+            ..Default::default()
+        });
+        assert_eq!(inj.decide(0), Fate::Deliver(100 + REORDER_HOLD_MS));
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn drop_discards_events() {
+        let mut inj = injector(ChaosProfile {
+            drop: 1.0,
+            ..Default::default()
+        });
+        assert_eq!(inj.decide(0), Fate::Drop);
+    }
+}
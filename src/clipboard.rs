@@ -0,0 +1,261 @@
+//! Clipboard backend chain
+//!
+//! The system clipboard needs a display server, so copies used to fail
+//! silently over SSH and in headless sessions. A copy now walks a chain of
+//! backends until one takes the text: the native clipboard, an OSC 52 escape
+//! sequence (the terminal emulator sets its own clipboard, even across SSH and
+//! tmux), then a plain file. `CLIPBOARD` picks the chain; the copy toast names
+//! the backend that was used and why earlier ones were skipped.
+
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+use base64::Engine;
+
+/// Largest payload sent over OSC 52 (many terminals drop longer sequences)
+pub const OSC52_MAX_BYTES: usize = 100_000;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ClipboardBackend {
+    /// System clipboard (X11/Wayland, macOS, Windows)
+    Native,
+    /// Escape sequence asking the terminal to set its clipboard
+    Osc52,
+    /// Write to `CLIPBOARD_FILE`
+    File,
+}
+
+impl ClipboardBackend {
+    pub fn name(self) -> &'static str {
+        match self {
+            ClipboardBackend::Native => "native",
+            ClipboardBackend::Osc52 => "osc52",
+            ClipboardBackend::File => "file",
+        }
+    }
+}
+
+impl std::str::FromStr for ClipboardBackend {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "native" | "system" => Ok(ClipboardBackend::Native),
+            "osc52" | "osc" => Ok(ClipboardBackend::Osc52),
+            "file" => Ok(ClipboardBackend::File),
+            _ => Err(anyhow::anyhow!(
+                "Invalid clipboard backend '{s}'. Valid options: auto, native, osc52, file"
+            )),
+        }
+    }
+}
+
+/// Parse `CLIPBOARD`: `auto` (empty chain, decided per session) or a comma
+/// list tried in order, e.g. `osc52,file`
+pub fn parse_chain(s: &str) -> anyhow::Result<Vec<ClipboardBackend>> {
+    if s.trim().eq_ignore_ascii_case("auto") {
+        return Ok(Vec::new());
+    }
+    let mut chain = Vec::new();
+    for part in s.split(',').filter(|p| !p.trim().is_empty()) {
+        let backend = part.parse()?;
+        if !chain.contains(&backend) {
+            chain.push(backend);
+        }
+    }
+    if chain.is_empty() {
+        return Err(anyhow::anyhow!(
+            "CLIPBOARD is empty. Valid options: auto, native, osc52, file"
+        ));
+    }
+    Ok(chain)
+}
+
+/// `auto`: native first locally; over SSH the remote clipboard is useless
+/// (or missing), so OSC 52 goes first
+pub fn auto_chain(ssh: bool) -> Vec<ClipboardBackend> {
+    use ClipboardBackend::*;
+    if ssh {
+        vec![Osc52, Native, File]
+    } else {
+        vec![Native, Osc52, File]
+    }
+}
+
+/// Default fallback file (`CLIPBOARD_FILE` overrides)
+pub fn default_file() -> PathBuf {
+    std::env::temp_dir().join("nearx-clipboard.txt")
+}
+
+/// OSC 52 "set clipboard" sequence, wrapped for tmux passthrough when asked;
+/// `None` when the payload is over `OSC52_MAX_BYTES`
+pub fn osc52_sequence(content: &str, tmux: bool) -> Option<String> {
+    if content.len() > OSC52_MAX_BYTES {
+        return None;
+    }
+    let b64 = base64::engine::general_purpose::STANDARD.encode(content);
+    let seq = format!("\x1b]52;c;{b64}\x07");
+    Some(if tmux {
+        // tmux forwards DCS passthrough with inner ESCs doubled
+        format!("\x1bPtmux;{}\x1b\\", seq.replace('\x1b', "\x1b\x1b"))
+    } else {
+        seq
+    })
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(not(feature = "native"), allow(dead_code))]
+struct ClipboardPolicy {
+    /// Empty = `auto_chain`
+    chain: Vec<ClipboardBackend>,
+    file: PathBuf,
+}
+
+static POLICY: RwLock<Option<ClipboardPolicy>> = RwLock::new(None);
+
+/// Set the backend chain and fallback file (once at startup from `Config`)
+pub fn set_policy(chain: Vec<ClipboardBackend>, file: Option<PathBuf>) {
+    let policy = ClipboardPolicy {
+        chain,
+        file: file.unwrap_or_else(default_file),
+    };
+    if let Ok(mut slot) = POLICY.write() {
+        *slot = Some(policy);
+    }
+}
+
+/// Outcome of one copy: the backend that took the text and the ones that failed first
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CopyReport {
+    pub backend: Option<ClipboardBackend>,
+    /// Where the text went when the file backend took it
+    pub file: Option<PathBuf>,
+    pub failures: Vec<(ClipboardBackend, String)>,
+}
+
+impl CopyReport {
+    pub fn ok(&self) -> bool {
+        self.backend.is_some()
+    }
+
+    /// Toast suffix: "osc52 (native: no display)", "file /tmp/x.txt", or the failures
+    pub fn summary(&self) -> String {
+        let failures = self
+            .failures
+            .iter()
+            .map(|(b, e)| format!("{}: {e}", b.name()))
+            .collect::<Vec<_>>()
+            .join("; ");
+        let Some(backend) = self.backend else {
+            return failures;
+        };
+        let used = match &self.file {
+            Some(path) => format!("file {}", path.display()),
+            None => backend.name().to_string(),
+        };
+        if failures.is_empty() {
+            used
+        } else {
+            format!("{used} ({failures})")
+        }
+    }
+}
+
+/// Copy through the configured chain, stopping at the first backend that succeeds
+#[cfg(feature = "native")]
+pub fn copy(content: &str) -> CopyReport {
+    let policy = POLICY.read().ok().and_then(|p| p.clone());
+    let (chain, file) = match policy {
+        Some(p) => (p.chain, p.file),
+        None => (Vec::new(), default_file()),
+    };
+    let chain = if chain.is_empty() {
+        auto_chain(
+            std::env::var_os("SSH_TTY").is_some() || std::env::var_os("SSH_CONNECTION").is_some(),
+        )
+    } else {
+        chain
+    };
+
+    let mut report = CopyReport::default();
+    for backend in chain {
+        let result = match backend {
+            ClipboardBackend::Native => copy_native(content),
+            ClipboardBackend::Osc52 => copy_osc52(content),
+            ClipboardBackend::File => std::fs::write(&file, content).map_err(|e| e.to_string()),
+        };
+        match result {
+            Ok(()) => {
+                report.backend = Some(backend);
+                if backend == ClipboardBackend::File {
+                    report.file = Some(file);
+                }
+                return report;
+            }
+            Err(e) => report.failures.push((backend, e)),
+        }
+    }
+    report
+}
+
+#[cfg(feature = "native")]
+fn copy_native(content: &str) -> Result<(), String> {
+    use copypasta::{ClipboardContext, ClipboardProvider};
+    let mut ctx = ClipboardContext::new().map_err(|e| e.to_string())?;
+    ctx.set_contents(content.to_string())
+        .map_err(|e| e.to_string())
+}
+
+/// Write the sequence straight to the terminal (the TUI owns stdout; the
+/// sequence prints nothing). Delivery can't be confirmed, so this "succeeds"
+/// whenever stdout is a terminal.
+#[cfg(feature = "native")]
+fn copy_osc52(content: &str) -> Result<(), String> {
+    use std::io::{IsTerminal, Write};
+    let mut out = std::io::stdout();
+    if !out.is_terminal() {
+        return Err("stdout is not a terminal".into());
+    }
+    let seq = osc52_sequence(content, std::env::var_os("TMUX").is_some())
+        .ok_or_else(|| format!("over {} KB", OSC52_MAX_BYTES / 1000))?;
+    out.write_all(seq.as_bytes())
+        .and_then(|_| out.flush())
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_chains_and_builds_osc52() {
+        use ClipboardBackend::*;
+        assert_eq!(parse_chain("auto").unwrap(), vec![]);
+        assert_eq!(parse_chain("OSC52, file,osc52").unwrap(), vec![Osc52, File]);
+        assert!(parse_chain("pbcopy").is_err());
+        assert!(parse_chain(",").is_err());
+        assert_eq!(auto_chain(true)[0], Osc52);
+
+        assert_eq!(osc52_sequence("hi", false).unwrap(), "\x1b]52;c;aGk=\x07");
+        assert_eq!(
+            osc52_sequence("hi", true).unwrap(),
+            "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\"
+        );
+        assert_eq!(
+            osc52_sequence(&"x".repeat(OSC52_MAX_BYTES + 1), false),
+            None
+        );
+
+        let report = CopyReport {
+            backend: Some(Osc52),
+            file: None,
+            failures: vec![(Native, "no display".into())],
+        };
+        assert_eq!(report.summary(), "osc52 (native: no display)");
+        let failed = CopyReport {
+            failures: vec![(File, "permission denied".into())],
+            ..CopyReport::default()
+        };
+        assert!(!failed.ok());
+        assert_eq!(failed.summary(), "file: permission denied");
+    }
+}
@@ -0,0 +1,607 @@
+//! Tx hash import into a temporary collection
+//!
+//! Shift+I opens an import box: paste a list of tx hashes (one per line, as
+//! copied from a spreadsheet column; commas, tabs and explorer URLs work too).
+//! Each hash is resolved from History first, then the FastNEAR tx API; txs
+//! whose signer is known get their outcome over RPC (through the outcome
+//! cache) unless History already stored it. The collection overlay lists them
+//! with their status as results come in; checked items (or all) can be
+//! exported as markdown or turned into jump marks (`import#<n>`). The
+//! collection lasts for the session.
+
+use std::collections::HashSet;
+
+use serde_json::Value;
+
+use crate::time_display::TimeDisplay;
+
+/// Hashes accepted per import (the rest are dropped with a warning)
+pub const MAX_IMPORT: usize = 500;
+/// Hashes per FastNEAR tx API request
+pub const FASTNEAR_BATCH: usize = 20;
+/// Outcome lookups in flight at once
+pub const RESOLVE_CONCURRENCY: usize = 4;
+/// FastNEAR tx API base (`FASTNEAR_TX_API_URL` overrides)
+pub const DEFAULT_TX_API_URL: &str = "https://tx.main.fastnear.com";
+/// Mark label prefix for bulk marking (`import#1`, `import#2`, ...)
+pub const MARK_PREFIX: &str = "import";
+
+const BASE58: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Whether `s` looks like a base58 tx hash (32 bytes)
+fn is_tx_hash(s: &str) -> bool {
+    (32..=44).contains(&s.len()) && s.chars().all(|c| BASE58.contains(c))
+}
+
+/// Hashes in pasted text, in order without duplicates, and the tokens that
+/// weren't hashes. Explorer URLs contribute their last path segment.
+pub fn parse_hashes(text: &str) -> (Vec<String>, Vec<String>) {
+    let mut seen = HashSet::new();
+    let (mut hashes, mut rejected) = (Vec::new(), Vec::new());
+    for token in text.split(|c: char| c.is_whitespace() || matches!(c, ',' | ';')) {
+        let token = token.trim_matches(|c: char| matches!(c, '"' | '\'' | '`'));
+        if token.is_empty() {
+            continue;
+        }
+        let candidate = token
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or(token)
+            .split(['?', '#'])
+            .next()
+            .unwrap_or_default();
+        if is_tx_hash(candidate) {
+            if seen.insert(candidate.to_string()) {
+                hashes.push(candidate.to_string());
+            }
+        } else {
+            rejected.push(token.to_string());
+        }
+    }
+    (hashes, rejected)
+}
+
+/// Keep the first `MAX_IMPORT` hashes; returns how many there were when some
+/// had to be dropped
+pub fn cap_import(hashes: &mut Vec<String>) -> Option<usize> {
+    let total = hashes.len();
+    (total > MAX_IMPORT).then(|| {
+        hashes.truncate(MAX_IMPORT);
+        total
+    })
+}
+
+/// Where a hash was found
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ResolvedVia {
+    History,
+    FastNear,
+}
+
+impl ResolvedVia {
+    pub fn label(self) -> &'static str {
+        match self {
+            ResolvedVia::History => "history",
+            ResolvedVia::FastNear => "fastnear",
+        }
+    }
+}
+
+/// Final execution outcome of a resolved tx
+#[derive(Clone, Debug, PartialEq)]
+pub enum TxOutcome {
+    Success,
+    /// Decoded failure summary
+    Failure(String),
+}
+
+/// What resolving one hash found
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Resolved {
+    pub via: Option<ResolvedVia>,
+    pub height: Option<u64>,
+    pub signer: Option<String>,
+    pub receiver: Option<String>,
+    /// `None` when the outcome lookup failed or the signer is unknown
+    pub outcome: Option<TxOutcome>,
+    /// Stored tx JSON (History hits), for opening in Details
+    pub raw_json: Option<String>,
+}
+
+/// One hash's result, as sent back to the app
+#[derive(Clone, Debug, PartialEq)]
+pub struct Resolution {
+    pub hash: String,
+    /// `None` = found nowhere; `Err` = every lookup failed
+    pub result: Result<Option<Resolved>, String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ItemStatus {
+    Pending,
+    Found(Resolved),
+    NotFound,
+    Error(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CollectionItem {
+    pub hash: String,
+    pub status: ItemStatus,
+    /// Checked for bulk export / marking
+    pub checked: bool,
+}
+
+impl CollectionItem {
+    /// Status column: `pending…`, `✓ success`, `✗ ActionError: …`, `found`, `not found`
+    pub fn status_text(&self) -> String {
+        match &self.status {
+            ItemStatus::Pending => "pending…".to_string(),
+            ItemStatus::Found(r) => match &r.outcome {
+                Some(TxOutcome::Success) => "✓ success".to_string(),
+                Some(TxOutcome::Failure(summary)) => format!("✗ {summary}"),
+                None => "found".to_string(),
+            },
+            ItemStatus::NotFound => "not found".to_string(),
+            ItemStatus::Error(e) => format!("error: {e}"),
+        }
+    }
+
+    pub fn resolved(&self) -> Option<&Resolved> {
+        match &self.status {
+            ItemStatus::Found(r) => Some(r),
+            _ => None,
+        }
+    }
+}
+
+/// Per-status counts for the overlay title
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CollectionCounts {
+    pub pending: usize,
+    pub success: usize,
+    pub failed: usize,
+    pub unknown: usize,
+    pub missing: usize,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Collection {
+    pub items: Vec<CollectionItem>,
+    pub selection: usize,
+    /// Non-hash tokens skipped at import
+    pub rejected: usize,
+}
+
+impl Collection {
+    pub fn new(hashes: Vec<String>, rejected: usize) -> Self {
+        Self {
+            items: hashes
+                .into_iter()
+                .map(|hash| CollectionItem {
+                    hash,
+                    status: ItemStatus::Pending,
+                    checked: false,
+                })
+                .collect(),
+            selection: 0,
+            rejected,
+        }
+    }
+
+    /// Record a resolution; false when the hash isn't in the collection
+    pub fn apply(&mut self, resolution: Resolution) -> bool {
+        let Some(item) = self.items.iter_mut().find(|i| i.hash == resolution.hash) else {
+            return false;
+        };
+        item.status = match resolution.result {
+            Ok(Some(resolved)) => ItemStatus::Found(resolved),
+            Ok(None) => ItemStatus::NotFound,
+            Err(e) => ItemStatus::Error(e),
+        };
+        true
+    }
+
+    pub fn counts(&self) -> CollectionCounts {
+        let mut counts = CollectionCounts::default();
+        for item in &self.items {
+            match &item.status {
+                ItemStatus::Pending => counts.pending += 1,
+                ItemStatus::Found(r) => match r.outcome {
+                    Some(TxOutcome::Success) => counts.success += 1,
+                    Some(TxOutcome::Failure(_)) => counts.failed += 1,
+                    None => counts.unknown += 1,
+                },
+                ItemStatus::NotFound | ItemStatus::Error(_) => counts.missing += 1,
+            }
+        }
+        counts
+    }
+
+    pub fn selected(&self) -> Option<&CollectionItem> {
+        self.items.get(self.selection)
+    }
+
+    pub fn up(&mut self) {
+        self.selection = self.selection.saturating_sub(1);
+    }
+
+    pub fn down(&mut self) {
+        if self.selection + 1 < self.items.len() {
+            self.selection += 1;
+        }
+    }
+
+    pub fn toggle_checked(&mut self) {
+        if let Some(item) = self.items.get_mut(self.selection) {
+            item.checked = !item.checked;
+        }
+    }
+
+    /// Check all, or uncheck all when everything is checked already
+    pub fn toggle_all(&mut self) {
+        let check = !self.items.iter().all(|i| i.checked);
+        for item in &mut self.items {
+            item.checked = check;
+        }
+    }
+
+    /// Checked items, or every item when none is checked (bulk actions)
+    pub fn targets(&self) -> Vec<&CollectionItem> {
+        let checked: Vec<_> = self.items.iter().filter(|i| i.checked).collect();
+        if checked.is_empty() {
+            self.items.iter().collect()
+        } else {
+            checked
+        }
+    }
+
+    /// Markdown table of `targets()` with status, block, signer / receiver and deep links
+    pub fn to_markdown(&self, generated_ms: i64, times: &TimeDisplay) -> String {
+        let items = self.targets();
+        let mut out = format!(
+            "# NEARx tx collection — {}\n\n{} txs\n\n| Tx | Status | Block | Signer | Receiver | Found in |\n|---|---|---|---|---|---|\n",
+            times.at(generated_ms),
+            items.len()
+        );
+        for item in items {
+            let r = item.resolved();
+            let cell = |v: Option<String>| v.unwrap_or_else(|| "—".to_string()).replace('|', "\\|");
+            out.push_str(&format!(
+                "| [`{}`]({}) | {} | {} | {} | {} | {} |\n",
+                item.hash,
+                crate::router::RouteV1::Tx {
+                    hash: item.hash.clone()
+                }
+                .url(),
+                cell(Some(item.status_text())),
+                cell(r.and_then(|r| r.height).map(|h| format!("#{h}"))),
+                cell(r.and_then(|r| r.signer.clone())),
+                cell(r.and_then(|r| r.receiver.clone())),
+                cell(r.and_then(|r| r.via).map(|v| v.label().to_string())),
+            ));
+        }
+        out
+    }
+}
+
+/// Outcome from a `tx` / `EXPERIMENTAL_tx_status` response (`None` while pending)
+pub fn outcome_from_status(status: &Value) -> Option<TxOutcome> {
+    let status = &status["status"];
+    if status.get("SuccessValue").is_some() || status.get("SuccessReceiptId").is_some() {
+        return Some(TxOutcome::Success);
+    }
+    status
+        .get("Failure")
+        .map(|failure| TxOutcome::Failure(crate::exec_error::summarize(failure)))
+}
+
+/// One tx from a FastNEAR `/v0/transactions` response
+fn parse_fastnear_tx(tx: &Value) -> Option<(String, Resolved)> {
+    let t = &tx["transaction"];
+    let hash = t["hash"].as_str()?.to_string();
+    let outcome = &tx["execution_outcome"];
+    Some((
+        hash,
+        Resolved {
+            via: Some(ResolvedVia::FastNear),
+            height: outcome["block_height"].as_u64(),
+            signer: t["signer_id"].as_str().map(str::to_string),
+            receiver: t["receiver_id"].as_str().map(str::to_string),
+            outcome: None,
+            raw_json: None,
+        },
+    ))
+}
+
+/// Look `hashes` up in the FastNEAR tx API (hashes it doesn't know are absent)
+pub async fn fastnear_lookup(
+    api_url: &str,
+    hashes: &[String],
+    timeout_ms: u64,
+    auth_token: Option<&str>,
+) -> anyhow::Result<Vec<(String, Resolved)>> {
+    let url = format!("{}/v0/transactions", api_url.trim_end_matches('/'));
+    let mut req = reqwest::Client::new()
+        .post(&url)
+        .json(&serde_json::json!({ "tx_hashes": hashes }))
+        .timeout(std::time::Duration::from_millis(timeout_ms));
+    if let Some(token) = auth_token {
+        req = req.header("Authorization", format!("Bearer {token}"));
+    }
+    let res = req.send().await?;
+    if !res.status().is_success() {
+        anyhow::bail!("FastNEAR tx API HTTP {}", res.status().as_u16());
+    }
+    let body: Value = res.json().await?;
+    Ok(body["transactions"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(parse_fastnear_tx)
+        .collect())
+}
+
+/// Endpoints and credentials for resolving an import
+#[derive(Clone, Debug)]
+pub struct ResolveParams {
+    pub rpc_url: String,
+    pub tx_api_url: String,
+    pub timeout_ms: u64,
+    pub auth_token: Option<String>,
+}
+
+/// Resolve `hashes` (History, then FastNEAR, then outcomes over RPC), handing
+/// each result to `send` as soon as it is final
+#[cfg(feature = "native")]
+pub async fn resolve(
+    history: &crate::history::History,
+    outcomes: &crate::outcome_cache::Outcomes,
+    params: &ResolveParams,
+    hashes: Vec<String>,
+    send: impl Fn(Resolution),
+) {
+    let token = params.auth_token.as_deref();
+    let mut found: Vec<(String, Resolved)> = history
+        .find_txs(hashes.clone())
+        .await
+        .into_iter()
+        .map(|(hit, raw_json)| {
+            let resolved = Resolved {
+                via: Some(ResolvedVia::History),
+                height: Some(hit.height),
+                signer: hit.signer,
+                receiver: hit.receiver,
+                // Stored once fetched before; no RPC needed then
+                outcome: hit.status.map(|s| match s.failure {
+                    None => TxOutcome::Success,
+                    Some(summary) => TxOutcome::Failure(summary),
+                }),
+                raw_json,
+            };
+            (hit.hash, resolved)
+        })
+        .collect();
+
+    let missing: Vec<String> = hashes
+        .iter()
+        .filter(|h| !found.iter().any(|(f, _)| f == *h))
+        .cloned()
+        .collect();
+    for batch in missing.chunks(FASTNEAR_BATCH) {
+        match fastnear_lookup(&params.tx_api_url, batch, params.timeout_ms, token).await {
+            Ok(txs) => {
+                for hash in batch {
+                    if !txs.iter().any(|(h, _)| h == hash) {
+                        send(Resolution {
+                            hash: hash.clone(),
+                            result: Ok(None),
+                        });
+                    }
+                }
+                found.extend(txs);
+            }
+            Err(e) => {
+                for hash in batch {
+                    send(Resolution {
+                        hash: hash.clone(),
+                        result: Err(format!("{e:#}")),
+                    });
+                }
+            }
+        }
+    }
+
+    // Outcomes for everything with a known signer that History has no status for
+    let mut set = tokio::task::JoinSet::new();
+    for (hash, mut resolved) in found {
+        let Some(signer) = resolved.signer.clone().filter(|_| resolved.outcome.is_none()) else {
+            send(Resolution {
+                hash,
+                result: Ok(Some(resolved)),
+            });
+            continue;
+        };
+        let (outcomes, url, token) = (
+            outcomes.clone(),
+            params.rpc_url.clone(),
+            params.auth_token.clone(),
+        );
+        let timeout_ms = params.timeout_ms;
+        set.spawn(async move {
+            if let Ok(status) = outcomes
+                .tx_status(&url, &hash, &signer, "FINAL", timeout_ms, token.as_deref())
+                .await
+            {
+                resolved.outcome = outcome_from_status(&status);
+            }
+            Resolution {
+                hash,
+                result: Ok(Some(resolved)),
+            }
+        });
+        if set.len() >= RESOLVE_CONCURRENCY {
+            if let Some(Ok(resolution)) = set.join_next().await {
+                send(resolution);
+            }
+        }
+    }
+    while let Some(res) = set.join_next().await {
+        if let Ok(resolution) = res {
+            send(resolution);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const H1: &str = "9Zp9cbqKq2B3mB1wHtbDqyNkXWYy3xGbnZ7b7zJY5CqE";
+    const H2: &str = "4p9KX6hVQv5Fjz3o2UnAafVv6oFyQfgVL8kqbBaWbBkz";
+
+    fn collection() -> Collection {
+        Collection::new(vec![H1.to_string(), H2.to_string()], 0)
+    }
+
+    fn found(outcome: Option<TxOutcome>) -> Resolved {
+        Resolved {
+            via: Some(ResolvedVia::History),
+            height: Some(120),
+            signer: Some("alice.near".into()),
+            receiver: Some("intents.near".into()),
+            outcome,
+            raw_json: None,
+        }
+    }
+
+    /// H1 succeeded, H2 wasn't found
+    fn resolved() -> Collection {
+        let mut c = collection();
+        c.apply(Resolution {
+            hash: H1.into(),
+            result: Ok(Some(found(Some(TxOutcome::Success)))),
+        });
+        c.apply(Resolution {
+            hash: H2.into(),
+            result: Ok(None),
+        });
+        c
+    }
+
+    #[test]
+    fn pasted_lists_csv_and_explorer_urls_yield_unique_hashes() {
+        let pasted = format!(
+            "{H1}\r\n\"{H2}\",{H1}\nhttps://nearblocks.io/txns/{H2}?tab=execution\n`{H1}`;{H2}/"
+        );
+        assert_eq!(parse_hashes(&pasted).0, [H1, H2]);
+    }
+
+    #[test]
+    fn non_hash_tokens_are_reported() {
+        let (hashes, rejected) = parse_hashes(&format!("tx_hash\n{H1}\nnot-a-hash 0xdeadbeef\n"));
+        assert_eq!(hashes, [H1]);
+        assert_eq!(rejected, ["tx_hash", "not-a-hash", "0xdeadbeef"]);
+    }
+
+    #[test]
+    fn imports_over_the_limit_keep_the_first_max_import() {
+        let b58: Vec<char> = BASE58.chars().collect();
+        let mut hashes: Vec<String> = (0..MAX_IMPORT + 3)
+            .map(|i| format!("{}{}{}", "1".repeat(42), b58[i / 58], b58[i % 58]))
+            .collect();
+        let first = hashes[0].clone();
+        assert_eq!(parse_hashes(&hashes.join("\n")).0.len(), MAX_IMPORT + 3);
+
+        assert_eq!(cap_import(&mut hashes), Some(MAX_IMPORT + 3));
+        assert_eq!((hashes.len(), hashes[0].as_str()), (MAX_IMPORT, first.as_str()));
+        assert_eq!(cap_import(&mut hashes), None);
+        assert_eq!(hashes.len(), MAX_IMPORT);
+    }
+
+    #[test]
+    fn new_items_start_pending() {
+        let c = collection();
+        assert_eq!(c.counts().pending, 2);
+        assert_eq!(c.items[0].status_text(), "pending…");
+    }
+
+    #[test]
+    fn resolutions_update_status_and_counts() {
+        let mut c = resolved();
+        let counts = c.counts();
+        assert_eq!((counts.success, counts.missing, counts.pending), (1, 1, 0));
+        assert_eq!(c.items[0].status_text(), "✓ success");
+
+        c.apply(Resolution {
+            hash: H1.into(),
+            result: Ok(Some(found(None))),
+        });
+        c.apply(Resolution {
+            hash: H2.into(),
+            result: Err("timeout".into()),
+        });
+        let counts = c.counts();
+        assert_eq!((counts.unknown, counts.missing), (1, 1));
+    }
+
+    #[test]
+    fn resolution_for_an_unknown_hash_is_ignored() {
+        let mut c = collection();
+        assert!(!c.apply(Resolution {
+            hash: "other".into(),
+            result: Ok(None),
+        }));
+        assert_eq!(c.counts().pending, 2);
+    }
+
+    #[test]
+    fn outcome_comes_from_the_final_status_only() {
+        let failure = json!({"status": {"Failure": {"ActionError": {"index": 0, "kind": {"AccountDoesNotExist": {"account_id": "bob.near"}}}}}});
+        assert!(matches!(outcome_from_status(&failure), Some(TxOutcome::Failure(_))));
+        assert_eq!(
+            outcome_from_status(&json!({"status": {"SuccessValue": ""}})),
+            Some(TxOutcome::Success)
+        );
+        assert_eq!(outcome_from_status(&json!({"status": "Started"})), None);
+    }
+
+    #[test]
+    fn bulk_targets_are_the_checked_items_or_all() {
+        let mut c = resolved();
+        assert_eq!(c.targets().len(), 2);
+        c.down();
+        c.toggle_checked();
+        let targets: Vec<&str> = c.targets().iter().map(|i| i.hash.as_str()).collect();
+        assert_eq!(targets, [H2]);
+
+        c.toggle_all();
+        assert_eq!(c.targets().len(), 2);
+        c.toggle_all();
+        assert!(c.items.iter().all(|i| !i.checked));
+    }
+
+    #[test]
+    fn markdown_export_lists_targets_with_deep_links() {
+        let mut c = resolved();
+        c.down();
+        c.toggle_checked();
+        let md = c.to_markdown(0, &TimeDisplay::default());
+        assert!(md.contains("1 txs"));
+        assert!(md.contains(&format!("| [`{H2}`](nearx://v1/tx/{H2}) | not found |")));
+        assert!(!md.contains(&format!("nearx://v1/tx/{H1}")));
+    }
+
+    #[test]
+    fn fastnear_rows_resolve_height_and_accounts() {
+        let fastnear = json!({"transaction": {"hash": H1, "signer_id": "alice.near", "receiver_id": "bob.near"},
+            "execution_outcome": {"block_height": 99}});
+        let (hash, resolved) = parse_fastnear_tx(&fastnear).unwrap();
+        assert_eq!((hash.as_str(), resolved.height), (H1, Some(99)));
+        assert_eq!(resolved.via, Some(ResolvedVia::FastNear));
+        assert_eq!(resolved.receiver.as_deref(), Some("bob.near"));
+        assert!(parse_fastnear_tx(&json!({"transaction": {}})).is_none());
+    }
+}
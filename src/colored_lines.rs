@@ -0,0 +1,198 @@
+//! Lazily colorized lines for the Details buffer
+//!
+//! Colorizing a whole document up front (or re-colorizing the visible window
+//! every frame) scales with document size and frame rate. Instead, lines are
+//! colorized in fixed chunks the first time the viewport (± a margin) touches
+//! them, and only the most recently used chunks are kept. A multi-MB document
+//! then costs its text, its line index and at most `MAX_CHUNKS * CHUNK_LINES`
+//! colored lines.
+
+use std::collections::VecDeque;
+
+use ratatui::text::Line;
+
+use crate::theme::Theme;
+
+/// Lines colorized together
+pub const CHUNK_LINES: usize = 128;
+/// Chunks kept (least recently used dropped first)
+pub const MAX_CHUNKS: usize = 12;
+/// Lines above and below the viewport colorized ahead of scrolling
+const MARGIN_LINES: usize = 64;
+
+#[derive(Debug, Default)]
+pub struct ColoredLines {
+    /// JSON gets syntax colors; anything else is plain lines
+    json: bool,
+    /// (chunk index, its lines), most recently used last
+    chunks: VecDeque<(usize, Vec<Line<'static>>)>,
+}
+
+impl ColoredLines {
+    /// Forget cached chunks (new document)
+    pub fn reset(&mut self, json: bool) {
+        self.json = json;
+        self.chunks.clear();
+    }
+
+    /// Whether the document is colorized as JSON
+    pub fn is_json(&self) -> bool {
+        self.json
+    }
+
+    pub fn cached_chunks(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Colored lines `start..end` of `text` (`offsets` = byte start of each line).
+    /// Chunks within the margin around the range are colorized too.
+    pub fn lines(
+        &mut self,
+        text: &str,
+        offsets: &[usize],
+        start: usize,
+        end: usize,
+        theme: &Theme,
+    ) -> Vec<Line<'static>> {
+        let total = offsets.len();
+        let end = end.min(total);
+        if start >= end {
+            return Vec::new();
+        }
+        let first = start.saturating_sub(MARGIN_LINES) / CHUNK_LINES;
+        let last = ((end + MARGIN_LINES).min(total) - 1) / CHUNK_LINES;
+        for idx in first..=last {
+            self.ensure(idx, text, offsets, theme);
+        }
+
+        let mut out = Vec::with_capacity(end - start);
+        for idx in start / CHUNK_LINES..=(end - 1) / CHUNK_LINES {
+            let chunk_start = idx * CHUNK_LINES;
+            let Some((_, lines)) = self.chunks.iter().find(|(i, _)| *i == idx) else {
+                continue;
+            };
+            let from = start.max(chunk_start) - chunk_start;
+            let to = end.min(chunk_start + lines.len()) - chunk_start;
+            out.extend(lines[from..to].iter().cloned());
+        }
+        out
+    }
+
+    /// Colorize chunk `idx` if it isn't cached, and mark it most recently used
+    fn ensure(&mut self, idx: usize, text: &str, offsets: &[usize], theme: &Theme) {
+        if let Some(pos) = self.chunks.iter().position(|(i, _)| *i == idx) {
+            let chunk = self.chunks.remove(pos).expect("position is in range");
+            self.chunks.push_back(chunk);
+            return;
+        }
+
+        let first = idx * CHUNK_LINES;
+        let last = (first + CHUNK_LINES).min(offsets.len());
+        let from = offsets[first];
+        let to = offsets.get(last).copied().unwrap_or(text.len());
+        let slice = &text[from..to];
+
+        let mut lines = if self.json {
+            crate::json_syntax::colorize_json(slice, theme)
+        } else {
+            slice.lines().map(|l| Line::from(l.to_string())).collect()
+        };
+        // Keep one entry per indexed line (the colorizer drops trailing blank lines)
+        lines.resize(last - first, Line::default());
+
+        self.chunks.push_back((idx, lines));
+        while self.chunks.len() > MAX_CHUNKS {
+            self.chunks.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offsets(text: &str) -> Vec<usize> {
+        std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect()
+    }
+
+    fn json_doc(lines: usize) -> String {
+        (0..lines).map(|i| format!("  \"k{i}\": {i},\n")).collect()
+    }
+
+    fn plain(line: &Line<'_>) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    fn json_cache() -> ColoredLines {
+        let mut cache = ColoredLines::default();
+        cache.reset(true);
+        cache
+    }
+
+    #[test]
+    fn colorizes_only_the_chunks_around_the_window() {
+        let text = json_doc(5_000);
+        let mut cache = json_cache();
+
+        let lines = cache.lines(&text, &offsets(&text), 1_000, 1_040, &Theme::default());
+        assert_eq!(lines.len(), 40);
+        assert_eq!(plain(&lines[0]), "  \"k1000\": 1000,");
+        // 936..1104 with the margin: chunks 7 and 8 only
+        assert_eq!(cache.cached_chunks(), 2);
+    }
+
+    #[test]
+    fn window_across_a_chunk_boundary_keeps_line_alignment() {
+        let text = json_doc(5_000);
+        let mut cache = json_cache();
+
+        let lines = cache.lines(&text, &offsets(&text), 1_020, 1_030, &Theme::default());
+        assert_eq!(lines.len(), 10);
+        assert_eq!(plain(&lines[0]), "  \"k1020\": 1020,");
+        assert_eq!(plain(&lines[9]), "  \"k1029\": 1029,");
+    }
+
+    #[test]
+    fn least_recently_used_chunks_are_evicted() {
+        let text = json_doc(5_000);
+        let offs = offsets(&text);
+        let theme = Theme::default();
+        let mut cache = json_cache();
+
+        for start in (0..4_900).step_by(100) {
+            cache.lines(&text, &offs, start, start + 40, &theme);
+        }
+        assert_eq!(cache.cached_chunks(), MAX_CHUNKS);
+        // An evicted chunk is colorized again on demand
+        let lines = cache.lines(&text, &offs, 0, 5, &theme);
+        assert_eq!(plain(&lines[4]), "  \"k4\": 4,");
+    }
+
+    #[test]
+    fn non_json_text_is_kept_as_plain_lines() {
+        let text = "first line\n  {not json\n\nlast";
+        let mut cache = ColoredLines::default();
+        cache.reset(false);
+        assert!(!cache.is_json());
+
+        let lines = cache.lines(text, &offsets(text), 0, 10, &Theme::default());
+        let plain: Vec<String> = lines.iter().map(plain).collect();
+        assert_eq!(plain, ["first line", "  {not json", "", "last"]);
+        assert!(lines.iter().all(|l| l.spans.len() <= 1));
+    }
+
+    #[test]
+    fn truncated_document_stops_at_the_last_indexed_line() {
+        // DetailsBuffer stops indexing at MAX_LINES; the text runs on past it
+        let text = json_doc(300);
+        let mut offs = offsets(&text);
+        offs.truncate(200);
+        let mut cache = json_cache();
+
+        let lines = cache.lines(&text, &offs, 150, 300, &Theme::default());
+        assert_eq!(lines.len(), 50);
+        assert_eq!(plain(&lines[49]), "  \"k199\": 199,");
+    }
+}
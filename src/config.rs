@@ -6,6 +6,7 @@ use std::env;
 pub enum Source {
     Ws,
     Rpc,
+    GraphQl,
 }
 
 impl std::str::FromStr for Source {
@@ -14,7 +15,8 @@ impl std::str::FromStr for Source {
         match s.to_lowercase().as_str() {
             "ws" | "websocket" => Ok(Source::Ws),
             "rpc" => Ok(Source::Rpc),
-            _ => Err(anyhow!("Invalid source '{s}'. Valid options: ws, rpc")),
+            "graphql" | "gql" => Ok(Source::GraphQl),
+            _ => Err(anyhow!("Invalid source '{s}'. Valid options: ws, rpc, graphql")),
         }
     }
 }
@@ -24,6 +26,7 @@ impl std::fmt::Display for Source {
         match self {
             Source::Ws => write!(f, "ws"),
             Source::Rpc => write!(f, "rpc"),
+            Source::GraphQl => write!(f, "graphql"),
         }
     }
 }
@@ -37,7 +40,7 @@ impl std::fmt::Display for Source {
 #[command(version = env!("CARGO_PKG_VERSION"))]
 #[command(about = "NEAR Blockchain Transaction Viewer", long_about = None)]
 pub struct CliArgs {
-    /// Data source: ws (WebSocket) or rpc (NEAR RPC)
+    /// Data source: ws (WebSocket), rpc (NEAR RPC) or graphql (GraphQL indexer)
     #[arg(short, long, env = "SOURCE", value_parser = clap::value_parser!(Source))]
     pub source: Option<Source>,
 
@@ -57,6 +60,22 @@ pub struct CliArgs {
     #[arg(long, env = "FASTNEAR_AUTH_TOKEN")]
     pub fastnear_auth_token: Option<String>,
 
+    /// GraphQL indexer endpoint (SOURCE=graphql)
+    #[arg(long, env = "GRAPHQL_URL")]
+    pub graphql_url: Option<String>,
+
+    /// GraphQL query returning the newest indexed height as `latest` (see source_graphql)
+    #[arg(long, env = "GRAPHQL_LATEST_QUERY")]
+    pub graphql_latest_query: Option<String>,
+
+    /// GraphQL query returning `blocks` with heights in $from..=$to and their transactions
+    #[arg(long, env = "GRAPHQL_BLOCKS_QUERY")]
+    pub graphql_blocks_query: Option<String>,
+
+    /// Extra GraphQL request headers: "Name: value; Name2: value"
+    #[arg(long, env = "GRAPHQL_HEADERS")]
+    pub graphql_headers: Option<String>,
+
     /// Archival RPC endpoint URL for fetching historical blocks
     #[arg(long, env = "ARCHIVAL_RPC_URL")]
     pub archival_rpc_url: Option<String>,
@@ -106,9 +125,229 @@ pub struct CliArgs {
     #[arg(long, env = "WATCH_ACCOUNTS")]
     pub watch_accounts: Option<String>,
 
-    /// Color theme: nord, dos-blue, amber-crt, green-phosphor
+    /// Color theme: dark, light, deuteranopia, protanopia, tritanopia, high-contrast
     #[arg(long, env = "THEME")]
     pub theme: Option<String>,
+
+    /// Theme used for a light OS appearance when following the system (default: light)
+    #[arg(long, env = "THEME_LIGHT")]
+    pub theme_light: Option<String>,
+
+    /// Follow the OS dark/light appearance at runtime (THEME = dark side, THEME_LIGHT = light side)
+    #[arg(long, env = "THEME_FOLLOW_SYSTEM")]
+    pub theme_follow_system: Option<bool>,
+
+    /// JSON indentation for details and copies: 2, 4, compact
+    #[arg(long, env = "JSON_INDENT")]
+    pub json_indent: Option<String>,
+
+    /// Sort JSON object keys alphabetically in details and copies (true/false)
+    #[arg(long, env = "JSON_SORT_KEYS")]
+    pub json_sort_keys: Option<bool>,
+
+    /// Auto-create pinned marks for high-severity plugin alerts (true/false)
+    #[arg(long, env = "PLUGIN_ALERT_MARKS")]
+    pub plugin_alert_marks: Option<bool>,
+
+    /// Max auto-created alert marks kept per plugin (1-35)
+    #[arg(long, env = "PLUGIN_ALERT_MARK_CAP")]
+    pub plugin_alert_mark_cap: Option<usize>,
+
+    /// Accept alerts from out-of-process plugins on this Unix socket path
+    #[arg(long, env = "PLUGIN_SOCKET")]
+    pub plugin_socket: Option<std::path::PathBuf>,
+
+    /// Monochrome rendering: bold/dim/underline only, no colors (true/false; NO_COLOR implies true)
+    #[arg(long, env = "MONOCHROME")]
+    pub monochrome: Option<bool>,
+
+    /// Blocks in the rolling gas price window (10-10000)
+    #[arg(long, env = "GAS_WINDOW_BLOCKS")]
+    pub gas_window_blocks: Option<usize>,
+
+    /// Warn when a block's gas price exceeds this multiple of the rolling median (1.1-100)
+    #[arg(long, env = "GAS_SPIKE_MULTIPLE")]
+    pub gas_spike_multiple: Option<f64>,
+
+    /// Warn when a block's tx count or gas used sits this many standard deviations above its rolling baseline (0 = off, 2-50)
+    #[arg(long, env = "ANOMALY_SIGMA")]
+    pub anomaly_sigma: Option<f64>,
+
+    /// Watermark the Blocks pane stale once the newest block is this many seconds old (0 = off, 10-86400)
+    #[arg(long, env = "STALE_AFTER_SECS")]
+    pub stale_after_secs: Option<u64>,
+
+    /// Pin each finished tx volume / gas anomaly to the pin board as an incident with its block range (true/false)
+    #[arg(long, env = "ANOMALY_INCIDENTS")]
+    pub anomaly_incidents: Option<bool>,
+
+    /// Alert when the top-10 validators' stake share moves this many percentage points between epochs (0.1-50)
+    #[arg(long, env = "STAKE_ALERT_PP")]
+    pub stake_alert_pp: Option<f64>,
+
+    /// Restore the last session's block window at startup, marked stale until refreshed (true/false)
+    #[arg(long, env = "WARM_START")]
+    pub warm_start: Option<bool>,
+
+    /// Count anonymous feature usage (panes, filter fields, exports), previewable with Ctrl+T (true/false)
+    #[arg(long, env = "TELEMETRY")]
+    pub telemetry: Option<bool>,
+
+    /// Where the telemetry report is POSTed on exit (unset = nothing is sent)
+    #[arg(long, env = "TELEMETRY_URL")]
+    pub telemetry_url: Option<String>,
+
+    /// Clipboard backends tried in order: auto, or a comma list of native, osc52, file
+    #[arg(long, env = "CLIPBOARD")]
+    pub clipboard: Option<String>,
+
+    /// File written by the `file` clipboard backend (default: nearx-clipboard.txt in the temp dir)
+    #[arg(long, env = "CLIPBOARD_FILE")]
+    pub clipboard_file: Option<std::path::PathBuf>,
+
+    /// FastNEAR Explorer API base for token holder lookups
+    #[arg(long, env = "FASTNEAR_API_URL")]
+    pub fastnear_api_url: Option<String>,
+
+    /// FastNEAR tx API base for resolving imported tx hashes
+    #[arg(long, env = "FASTNEAR_TX_API_URL")]
+    pub fastnear_tx_api_url: Option<String>,
+
+    /// Serve a read-only mirror of the UI over WebSocket: PORT (localhost) or HOST:PORT
+    #[arg(long, env = "MIRROR_LISTEN")]
+    pub mirror_listen: Option<String>,
+
+    /// Timezone for displayed times: utc, local, or an offset like +05:30 (T cycles at runtime)
+    #[arg(long, env = "TIMEZONE")]
+    pub timezone: Option<String>,
+
+    /// strftime pattern for full timestamps (block rows, Details, exports)
+    #[arg(long, env = "TIME_FORMAT")]
+    pub time_format: Option<String>,
+
+    /// Accept UiActions from local tools on this Unix socket path (named pipe on Windows, e.g. \\.\pipe\nearx)
+    #[arg(long, env = "CONTROL_SOCKET")]
+    pub control_socket: Option<std::path::PathBuf>,
+
+    /// Token control clients must send (unset = random, written to <socket>.token)
+    #[arg(long, env = "CONTROL_TOKEN")]
+    pub control_token: Option<String>,
+
+    /// Action types the control socket accepts: comma list of UiAction types, or all
+    #[arg(long, env = "CONTROL_ALLOW")]
+    pub control_allow: Option<String>,
+
+    /// Dev/testing: inject faults into the block sources, e.g. "latency=200,jitter=300 rpc:drop=0.05,reorder=0.1"
+    #[arg(long, env = "CHAOS")]
+    pub chaos: Option<String>,
+
+    /// Start in a view, like a deep link: tx/<hash>, block/<height>, account/<id>, home;
+    /// `?filter=<query>` adds filter terms (e.g. "account/alice.near?filter=method:ft_transfer")
+    #[arg(long, value_name = "ROUTE")]
+    pub route: Option<String>,
+
+    /// Write JSON Schema + TypeScript definitions for UiSnapshot/UiAction to DIR and exit
+    #[arg(long, value_name = "DIR")]
+    pub emit_schema: Option<std::path::PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// One-shot subcommands (run without the UI)
+#[derive(clap::Subcommand, Clone, Debug)]
+pub enum Command {
+    /// Search History + recent blocks with the filter grammar, print matches and exit
+    ///
+    /// Exit status: 0 = matches found, 1 = no matches, 2 = error
+    Query(QueryArgs),
+    /// Check RPC / WebSocket / archival endpoints, History DB and deep-link registration
+    ///
+    /// Exit status: 0 = no failed checks, 1 = at least one check failed
+    Doctor,
+    /// Download a block range into History; an interrupted run can be resumed
+    ///
+    /// Exit status: 0 = range complete, 1 = error (progress so far is kept)
+    Fetch(FetchArgs),
+    /// Summarize one contract's activity in History (callers, methods, gas,
+    /// deposits, sampled failure rate) as markdown
+    ///
+    /// Exit status: 0 = digest written, 1 = error
+    Digest(DigestArgs),
+    /// Fill in stored txs: decode signer / receiver / actions where missing and
+    /// keep final statuses on the tx rows; `--outcomes` fetches missing ones over RPC
+    ///
+    /// Exit status: 0 = done, 1 = error (work so far is kept)
+    Backfill(BackfillArgs),
+}
+
+#[derive(clap::Args, Clone, Debug)]
+pub struct BackfillArgs {
+    /// Also fetch final outcomes (status, receipts) over RPC for txs without one
+    #[arg(long)]
+    pub outcomes: bool,
+
+    /// Newest txs whose outcome is fetched with `--outcomes`
+    #[arg(long, default_value_t = 1000)]
+    pub limit: usize,
+}
+
+#[derive(clap::Args, Clone, Debug)]
+pub struct DigestArgs {
+    /// Receiver account to summarize (e.g. intents.near)
+    pub contract: String,
+
+    /// Window ending now: `<N>{s,m,h,d,w}`
+    #[arg(long, default_value = "24h")]
+    pub since: String,
+
+    /// Newest txs whose outcome is fetched over RPC for the failure rate (0 = skip)
+    #[arg(long, default_value_t = crate::digest::DEFAULT_SAMPLE)]
+    pub sample: usize,
+
+    /// Write the markdown here instead of stdout
+    #[arg(long)]
+    pub out: Option<std::path::PathBuf>,
+}
+
+#[derive(clap::Args, Clone, Debug)]
+pub struct FetchArgs {
+    /// Inclusive height range `FROM..TO` (e.g. 120000000..120000500)
+    #[arg(required_unless_present = "resume", conflicts_with = "resume")]
+    pub range: Option<String>,
+
+    /// Continue the interrupted fetch recorded in History, re-verifying
+    /// already-downloaded heights by block hash
+    #[arg(long)]
+    pub resume: bool,
+}
+
+#[derive(clap::Args, Clone, Debug)]
+pub struct QueryArgs {
+    /// Filter query, plus `since:<N>{s,m,h,d,w}` (e.g. "signer:alice.near method:ft_transfer since:1h")
+    pub query: String,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = QueryFormat::Text)]
+    pub format: QueryFormat,
+
+    /// Maximum matches to print
+    #[arg(long, default_value_t = 100)]
+    pub limit: usize,
+
+    /// Recent blocks to scan over RPC in addition to History (0 = History only)
+    #[arg(long, default_value_t = 20)]
+    pub backfill: u64,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QueryFormat {
+    /// One aligned line per match
+    Text,
+    /// Single JSON array
+    Json,
+    /// One JSON object per line
+    Jsonl,
 }
 
 #[derive(Clone, Debug)]
@@ -125,12 +364,108 @@ pub struct Config {
     pub near_node_url: String,
     pub near_node_url_explicit: bool, // true if set via env var or CLI
     pub archival_rpc_url: Option<String>,
+    pub graphql_url: Option<String>,      // GraphQL indexer (SOURCE=graphql)
+    pub graphql_latest_query: String,     // Template returning `latest` height
+    pub graphql_blocks_query: String,     // Template returning `blocks` for $from..=$to
+    pub graphql_headers: Vec<(String, String)>, // Extra request headers (auth)
     pub rpc_timeout_ms: u64,
     #[allow(dead_code)]
     pub rpc_retries: u32,
     pub fastnear_auth_token: Option<String>,
     pub default_filter: String,
+    pub theme_variant: crate::theme::ThemeVariant,
     pub theme: crate::theme::Theme,
+    pub theme_follow_system: Option<crate::theme::SystemThemes>, // Some = follow OS appearance
+    pub json_format: crate::json_pretty::JsonFormat,
+    pub plugin_alert_marks: bool,
+    pub plugin_alert_mark_cap: usize,
+    pub plugin_socket: Option<std::path::PathBuf>, // Plugin bus (None = off)
+    pub monochrome: bool, // NO_COLOR / MONOCHROME: emphasis via bold/dim/underline only
+    pub gas_window_blocks: usize, // Gas panel percentiles are over the last N live blocks
+    pub gas_spike_multiple: f64,  // Spike alert threshold (× rolling median)
+    pub anomaly_sigma: f64,       // Tx volume / gas used anomaly threshold (σ above EWMA baseline, 0 = off)
+    pub anomaly_incidents: bool,  // Finished anomalies become pin board incidents
+    pub stale_after_secs: u64,    // Blocks pane stale watermark threshold (0 = off)
+    pub stake_alert_pp: f64,      // Stake concentration alert (top-10 share change, pp)
+    pub warm_start: bool, // Persist the block ring on exit, reload it (stale) on startup
+    pub telemetry: bool,  // Opt-in usage counters
+    pub telemetry_url: Option<String>, // Report destination (None = preview only)
+    pub clipboard: Vec<crate::clipboard::ClipboardBackend>, // Backend chain (empty = auto)
+    pub clipboard_file: Option<std::path::PathBuf>, // File backend target (None = temp dir)
+    pub mirror_listen: Option<std::net::SocketAddr>, // Shared-view WebSocket server (None = off)
+    pub timezone: crate::time_display::TimeZonePref, // Zone for displayed times (T cycles)
+    pub time_format: String,                          // strftime pattern for full timestamps
+    pub chaos: crate::chaos::ChaosSpec, // Dev/testing fault injection per source (empty = off)
+    pub control_socket: Option<std::path::PathBuf>, // Local control channel (None = off)
+    pub control_token: Option<String>, // None = generated per run, written next to the socket
+    pub control_allow: Vec<String>,    // UiAction types the control channel accepts
+    pub fastnear_api_url: String, // Holders tab lookups (FastNEAR Explorer API)
+    pub fastnear_tx_api_url: String, // Tx collection import lookups (FastNEAR tx API)
+    pub cli_live_keys: Vec<String>, // ratacat.toml live keys given as flags (reloads leave them alone)
+    pub route: Option<crate::router::RouteLink>, // Startup view (`--route`, same router as deep links)
+    pub emit_schema: Option<std::path::PathBuf>, // One-shot: export UI contract schema and exit
+    pub command: Option<Command>,                 // One-shot subcommand (`nearx query ...`)
+}
+
+/// Built-in defaults (what `load` gives with no flags and no env), for hosts that
+/// build a `Config` in code: `Config { near_node_url, ..Config::default() }`
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            source: Source::Rpc,
+            ws_url: "ws://127.0.0.1:63736".to_string(),
+            ws_fetch_blocks: true,
+            render_fps: 20,
+            render_fps_choices: vec![20, 30, 60],
+            poll_interval_ms: 1000,
+            poll_max_catchup: 5,
+            poll_chunk_concurrency: 4,
+            keep_blocks: 100,
+            near_node_url: "https://rpc.mainnet.fastnear.com/".to_string(),
+            near_node_url_explicit: false,
+            archival_rpc_url: None,
+            graphql_url: None,
+            graphql_latest_query: crate::source_graphql::DEFAULT_LATEST_QUERY.to_string(),
+            graphql_blocks_query: crate::source_graphql::DEFAULT_BLOCKS_QUERY.to_string(),
+            graphql_headers: Vec::new(),
+            rpc_timeout_ms: 8000,
+            rpc_retries: 2,
+            fastnear_auth_token: None,
+            default_filter: "acct:intents.near".to_string(),
+            theme_variant: Default::default(),
+            theme: Default::default(),
+            theme_follow_system: None,
+            json_format: Default::default(),
+            plugin_alert_marks: true,
+            plugin_alert_mark_cap: 5,
+            plugin_socket: None,
+            monochrome: false,
+            gas_window_blocks: crate::gas_oracle::DEFAULT_GAS_WINDOW,
+            gas_spike_multiple: crate::gas_oracle::DEFAULT_GAS_SPIKE_MULTIPLE,
+            anomaly_sigma: crate::anomaly::DEFAULT_ANOMALY_SIGMA,
+            anomaly_incidents: false,
+            stale_after_secs: crate::source_health::DEFAULT_STALE_AFTER_SECS,
+            stake_alert_pp: crate::stake::DEFAULT_STAKE_ALERT_PP,
+            warm_start: true,
+            telemetry: false,
+            telemetry_url: None,
+            clipboard: Vec::new(),
+            clipboard_file: None,
+            mirror_listen: None,
+            timezone: Default::default(),
+            time_format: crate::time_display::DEFAULT_TIME_FORMAT.to_string(),
+            chaos: Default::default(),
+            control_socket: None,
+            control_token: None,
+            control_allow: crate::control::DEFAULT_ALLOW.iter().map(|t| t.to_string()).collect(),
+            fastnear_api_url: crate::holders::DEFAULT_API_URL.to_string(),
+            fastnear_tx_api_url: crate::collection::DEFAULT_TX_API_URL.to_string(),
+            cli_live_keys: Vec::new(),
+            route: None,
+            emit_schema: None,
+            command: None,
+        }
+    }
 }
 
 /// Validate that a value is within a given range (inclusive)
@@ -146,7 +481,7 @@ where
 }
 
 /// Parse comma-separated FPS list and validate each value
-fn parse_fps_list(s: &str) -> Vec<u32> {
+pub(crate) fn parse_fps_list(s: &str) -> Vec<u32> {
     s.split(',')
         .filter_map(|v| v.trim().parse::<u32>().ok())
         .filter(|n| (1..=120).contains(n))
@@ -156,7 +491,32 @@ fn parse_fps_list(s: &str) -> Vec<u32> {
 /// Load configuration from CLI args and environment variables
 /// Priority: CLI args > Environment variables > Defaults
 pub fn load() -> Result<Config> {
-    let args = CliArgs::parse();
+    from_cli(CliArgs::parse())
+}
+
+/// Same as `load`, from an explicit argument list (embedders: `["nearx", "--source", "rpc"]`).
+/// `--help` and bad flags come back as errors instead of exiting the process.
+pub fn load_from<I, T>(args: I) -> Result<Config>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString> + Clone,
+{
+    from_cli(CliArgs::try_parse_from(args)?)
+}
+
+fn from_cli(args: CliArgs) -> Result<Config> {
+    // Live config-file keys given as flags: they outrank the file, also on reload
+    let cli_live_keys = [
+        ("theme", args.theme.is_some()),
+        ("render_fps", args.render_fps.is_some()),
+        ("render_fps_choices", args.render_fps_choices.is_some()),
+        ("keep_blocks", args.keep_blocks.is_some()),
+        ("default_filter", args.default_filter.is_some() || args.watch_accounts.is_some()),
+    ]
+    .into_iter()
+    .filter(|(_, set)| *set)
+    .map(|(key, _)| key.to_string())
+    .collect();
 
     // Source (with fallback to env var DEFAULT)
     let source = args.source.unwrap_or_else(|| {
@@ -184,6 +544,28 @@ pub fn load() -> Result<Config> {
         validate_url(url, "ARCHIVAL_RPC_URL")?;
     }
 
+    // GraphQL indexer source (templates alias their fields to the expected shape)
+    let graphql_url = args.graphql_url.or_else(|| env::var("GRAPHQL_URL").ok());
+    if let Some(ref url) = graphql_url {
+        validate_url(url, "GRAPHQL_URL")?;
+    } else if source == Source::GraphQl {
+        return Err(anyhow!("SOURCE=graphql needs GRAPHQL_URL (the indexer's GraphQL endpoint)"));
+    }
+    let graphql_latest_query = args
+        .graphql_latest_query
+        .or_else(|| env::var("GRAPHQL_LATEST_QUERY").ok())
+        .unwrap_or_else(|| crate::source_graphql::DEFAULT_LATEST_QUERY.to_string());
+    let graphql_blocks_query = args
+        .graphql_blocks_query
+        .or_else(|| env::var("GRAPHQL_BLOCKS_QUERY").ok())
+        .unwrap_or_else(|| crate::source_graphql::DEFAULT_BLOCKS_QUERY.to_string());
+    let graphql_headers = args
+        .graphql_headers
+        .or_else(|| env::var("GRAPHQL_HEADERS").ok())
+        .map(|s| crate::source_graphql::parse_headers(&s))
+        .transpose()?
+        .unwrap_or_default();
+
     let ws_url = args
         .ws_url
         .or_else(|| env::var("WS_URL").ok())
@@ -285,8 +667,215 @@ pub fn load() -> Result<Config> {
             .unwrap_or_else(|| "acct:intents.near".to_string())
     };
 
-    // Use default theme (theme selection not implemented yet)
-    let theme = crate::theme::Theme::default();
+    // Theme variant (color-vision / high-contrast palettes)
+    let theme_variant: crate::theme::ThemeVariant = args
+        .theme
+        .or_else(|| env::var("THEME").ok())
+        .map(|s| s.parse())
+        .transpose()?
+        .unwrap_or_default();
+    let theme = crate::theme::Theme::for_variant(theme_variant);
+
+    // Follow OS dark/light: THEME is the dark side, THEME_LIGHT the light side
+    let theme_follow_system = args
+        .theme_follow_system
+        .or_else(|| {
+            env::var("THEME_FOLLOW_SYSTEM")
+                .ok()
+                .map(|s| s.to_lowercase() == "true")
+        })
+        .unwrap_or(false);
+    let theme_follow_system = if theme_follow_system {
+        let light = args
+            .theme_light
+            .or_else(|| env::var("THEME_LIGHT").ok())
+            .map(|s| s.parse())
+            .transpose()?
+            .unwrap_or(crate::theme::ThemeVariant::Light);
+        Some(crate::theme::SystemThemes {
+            dark: theme_variant,
+            light,
+        })
+    } else {
+        None
+    };
+
+    // Plain-text JSON formatting (details pane + clipboard)
+    let json_format = crate::json_pretty::JsonFormat {
+        indent: args
+            .json_indent
+            .or_else(|| env::var("JSON_INDENT").ok())
+            .map(|s| s.parse())
+            .transpose()?
+            .unwrap_or_default(),
+        sort_keys: args
+            .json_sort_keys
+            .or_else(|| {
+                env::var("JSON_SORT_KEYS")
+                    .ok()
+                    .map(|s| s.to_lowercase() == "true")
+            })
+            .unwrap_or(false),
+    };
+
+    // Opt-in telemetry (off unless asked for; only sent when a URL is configured)
+    let telemetry = args
+        .telemetry
+        .or_else(|| {
+            env::var("TELEMETRY")
+                .ok()
+                .map(|s| s.to_lowercase() == "true")
+        })
+        .unwrap_or(false);
+    let telemetry_url = args.telemetry_url.or_else(|| env::var("TELEMETRY_URL").ok());
+    if let Some(ref url) = telemetry_url {
+        validate_url(url, "TELEMETRY_URL")?;
+    }
+
+    // Clipboard backend chain (auto: native → OSC 52 → file, OSC 52 first over SSH)
+    let clipboard = args
+        .clipboard
+        .or_else(|| env::var("CLIPBOARD").ok())
+        .map(|s| crate::clipboard::parse_chain(&s))
+        .transpose()?
+        .unwrap_or_default();
+    let clipboard_file = args
+        .clipboard_file
+        .or_else(|| env::var("CLIPBOARD_FILE").ok().map(Into::into));
+
+    // FastNEAR Explorer API (Holders tab in the state inspector)
+    let fastnear_api_url = args
+        .fastnear_api_url
+        .or_else(|| env::var("FASTNEAR_API_URL").ok())
+        .unwrap_or_else(|| crate::holders::DEFAULT_API_URL.to_string());
+    validate_url(&fastnear_api_url, "FASTNEAR_API_URL")?;
+
+    // FastNEAR tx API (tx collection import, Shift+I)
+    let fastnear_tx_api_url = args
+        .fastnear_tx_api_url
+        .or_else(|| env::var("FASTNEAR_TX_API_URL").ok())
+        .unwrap_or_else(|| crate::collection::DEFAULT_TX_API_URL.to_string());
+    validate_url(&fastnear_tx_api_url, "FASTNEAR_TX_API_URL")?;
+
+    // Read-only shared view (web build connects with ?mirror=ws://host:port)
+    let mirror_listen = args
+        .mirror_listen
+        .or_else(|| env::var("MIRROR_LISTEN").ok())
+        .filter(|s| !s.trim().is_empty())
+        .map(|s| crate::mirror::parse_listen(&s))
+        .transpose()?;
+
+    // Displayed times: zone + full-timestamp format
+    let timezone = args
+        .timezone
+        .or_else(|| env::var("TIMEZONE").ok())
+        .map(|s| crate::time_display::parse_zone(&s))
+        .transpose()?
+        .unwrap_or_default();
+    let time_format = args
+        .time_format
+        .or_else(|| env::var("TIME_FORMAT").ok())
+        .map(|s| crate::time_display::parse_format(&s))
+        .transpose()?
+        .unwrap_or_else(|| crate::time_display::DEFAULT_TIME_FORMAT.to_string());
+
+    // Local control channel (scripts, native host → running TUI)
+    let control_socket = args
+        .control_socket
+        .or_else(|| env::var("CONTROL_SOCKET").ok().map(Into::into))
+        .filter(|p: &std::path::PathBuf| !p.as_os_str().is_empty());
+    let control_token = args
+        .control_token
+        .or_else(|| env::var("CONTROL_TOKEN").ok())
+        .filter(|t| !t.is_empty());
+    let control_allow = match args.control_allow.or_else(|| env::var("CONTROL_ALLOW").ok()) {
+        Some(s) => crate::control::parse_allow(&s)?,
+        None => crate::control::DEFAULT_ALLOW.iter().map(|t| t.to_string()).collect(),
+    };
+
+    // Startup view: rejected up front rather than silently ignored
+    let route = args
+        .route
+        .map(|r| {
+            crate::router::parse_cli(&r).ok_or_else(|| {
+                anyhow!("Invalid --route '{r}'. Expected tx/<hash>, block/<height>, account/<id> or home, optionally with ?filter=<query>")
+            })
+        })
+        .transpose()?;
+
+    // Fault injection for testing the sources' failure handling (off unless set)
+    let chaos = args
+        .chaos
+        .or_else(|| env::var("CHAOS").ok())
+        .map(|s| crate::chaos::parse(&s))
+        .transpose()?
+        .unwrap_or_default();
+
+    // Plugin alert → pinned mark integration
+    let plugin_alert_marks = args
+        .plugin_alert_marks
+        .or_else(|| {
+            env::var("PLUGIN_ALERT_MARKS")
+                .ok()
+                .map(|s| s.to_lowercase() == "true")
+        })
+        .unwrap_or(true);
+    let plugin_alert_mark_cap = args
+        .plugin_alert_mark_cap
+        .or_else(|| {
+            env::var("PLUGIN_ALERT_MARK_CAP")
+                .ok()
+                .and_then(|s| s.parse().ok())
+        })
+        .unwrap_or(5);
+    let plugin_alert_mark_cap =
+        validate_in_range(plugin_alert_mark_cap, 1, 35, "PLUGIN_ALERT_MARK_CAP")?;
+    let plugin_socket = args
+        .plugin_socket
+        .or_else(|| env::var("PLUGIN_SOCKET").ok().map(Into::into))
+        .filter(|p: &std::path::PathBuf| !p.as_os_str().is_empty());
+
+    let gas_window_blocks = args
+        .gas_window_blocks
+        .or_else(|| env::var("GAS_WINDOW_BLOCKS").ok().and_then(|s| s.parse().ok()))
+        .unwrap_or(crate::gas_oracle::DEFAULT_GAS_WINDOW);
+    let gas_window_blocks = validate_in_range(gas_window_blocks, 10, 10000, "GAS_WINDOW_BLOCKS")?;
+    let gas_spike_multiple = args
+        .gas_spike_multiple
+        .or_else(|| env::var("GAS_SPIKE_MULTIPLE").ok().and_then(|s| s.parse().ok()))
+        .unwrap_or(crate::gas_oracle::DEFAULT_GAS_SPIKE_MULTIPLE);
+    let gas_spike_multiple =
+        validate_in_range(gas_spike_multiple, 1.1, 100.0, "GAS_SPIKE_MULTIPLE")?;
+    let anomaly_sigma = args
+        .anomaly_sigma
+        .or_else(|| env::var("ANOMALY_SIGMA").ok().and_then(|s| s.parse().ok()))
+        .unwrap_or(crate::anomaly::DEFAULT_ANOMALY_SIGMA);
+    if anomaly_sigma != 0.0 {
+        validate_in_range(anomaly_sigma, 2.0, 50.0, "ANOMALY_SIGMA")?;
+    }
+    let stale_after_secs = args
+        .stale_after_secs
+        .or_else(|| env::var("STALE_AFTER_SECS").ok().and_then(|s| s.parse().ok()))
+        .unwrap_or(crate::source_health::DEFAULT_STALE_AFTER_SECS);
+    if stale_after_secs != 0 {
+        validate_in_range(stale_after_secs, 10, 86_400, "STALE_AFTER_SECS")?;
+    }
+    let stake_alert_pp = args
+        .stake_alert_pp
+        .or_else(|| env::var("STAKE_ALERT_PP").ok().and_then(|s| s.parse().ok()))
+        .unwrap_or(crate::stake::DEFAULT_STAKE_ALERT_PP);
+    let stake_alert_pp = validate_in_range(stake_alert_pp, 0.1, 50.0, "STAKE_ALERT_PP")?;
+
+    // Monochrome: explicit setting wins; otherwise honor the NO_COLOR convention
+    // (https://no-color.org: set and non-empty disables color)
+    let monochrome = args
+        .monochrome
+        .or_else(|| {
+            env::var("MONOCHROME")
+                .ok()
+                .map(|s| s.to_lowercase() == "true")
+        })
+        .unwrap_or_else(|| env::var("NO_COLOR").is_ok_and(|v| !v.is_empty()));
 
     // Build and return config
     Ok(Config {
@@ -309,6 +898,10 @@ pub fn load() -> Result<Config> {
         near_node_url,
         near_node_url_explicit,
         archival_rpc_url,
+        graphql_url,
+        graphql_latest_query,
+        graphql_blocks_query,
+        graphql_headers,
         rpc_timeout_ms,
         rpc_retries,
         fastnear_auth_token: args.fastnear_auth_token.or_else(|| {
@@ -316,12 +909,57 @@ pub fn load() -> Result<Config> {
             if token.is_empty() { None } else { Some(token) }
         }),
         default_filter,
+        theme_variant,
         theme,
+        theme_follow_system,
+        json_format,
+        plugin_alert_marks,
+        plugin_alert_mark_cap,
+        plugin_socket,
+        monochrome,
+        gas_window_blocks,
+        gas_spike_multiple,
+        anomaly_sigma,
+        anomaly_incidents: args
+            .anomaly_incidents
+            .or_else(|| {
+                env::var("ANOMALY_INCIDENTS")
+                    .ok()
+                    .map(|s| s.to_lowercase() == "true")
+            })
+            .unwrap_or(false),
+        stale_after_secs,
+        stake_alert_pp,
+        warm_start: args
+            .warm_start
+            .or_else(|| {
+                env::var("WARM_START")
+                    .ok()
+                    .map(|s| s.to_lowercase() == "true")
+            })
+            .unwrap_or(true),
+        telemetry,
+        telemetry_url,
+        clipboard,
+        clipboard_file,
+        mirror_listen,
+        timezone,
+        time_format,
+        chaos,
+        control_socket,
+        control_token,
+        control_allow,
+        fastnear_api_url,
+        fastnear_tx_api_url,
+        cli_live_keys,
+        route,
+        emit_schema: args.emit_schema,
+        command: args.command,
     })
 }
 
 /// Validate URL format (basic check)
-fn validate_url(url: &str, name: &str) -> Result<()> {
+pub(crate) fn validate_url(url: &str, name: &str) -> Result<()> {
     if url.is_empty() {
         return Err(anyhow!("{name} cannot be empty"));
     }
@@ -360,6 +998,24 @@ pub fn fastnear_token() -> String {
 
 /// Print current configuration (useful for debugging)
 impl Config {
+    /// Zone + format for displayed times, as configured
+    pub fn time_display(&self) -> crate::time_display::TimeDisplay {
+        crate::time_display::TimeDisplay::new(self.timezone, self.time_format.clone())
+    }
+
+    /// History database path (`SQLITE_DB_PATH`, default `./nearx_history.db`)
+    pub fn history_db_path(&self) -> String {
+        env::var("SQLITE_DB_PATH").unwrap_or_else(|_| "./nearx_history.db".into())
+    }
+
+    /// RPC for old blocks and outcomes: `ARCHIVAL_RPC_URL` when set, since regular
+    /// nodes prune them, else `NEAR_NODE_URL`
+    pub fn archival_or_rpc_url(&self) -> String {
+        self.archival_rpc_url
+            .clone()
+            .unwrap_or_else(|| self.near_node_url.clone())
+    }
+
     #[allow(dead_code)]
     pub fn print_summary(&self) {
         eprintln!("Ratacat Configuration:");
@@ -377,6 +1033,11 @@ impl Config {
                 eprintln!("  RPC Timeout: {}ms", self.rpc_timeout_ms);
                 eprintln!("  RPC Retries: {}", self.rpc_retries);
             }
+            Source::GraphQl => {
+                eprintln!("  GraphQL URL: {}", self.graphql_url.as_deref().unwrap_or("-"));
+                eprintln!("  Poll Interval: {}ms", self.poll_interval_ms);
+                eprintln!("  Max Catchup: {} blocks", self.poll_max_catchup);
+            }
         }
         eprintln!("  Render FPS: {}", self.render_fps);
         eprintln!("  Keep Blocks: {}", self.keep_blocks);
@@ -0,0 +1,370 @@
+//! `ratacat.toml`: optional config file with live reload
+//!
+//! Keys are the environment variable names in lower case (`render_fps`,
+//! `near_node_url`, ...) plus a `[labels]` table (account → display name).
+//! At start-up the file fills in the variables that the environment and
+//! `.env` leave unset, so the priority stays CLI > env > file > defaults.
+//! While running, the file is watched: changes to the live keys apply at
+//! once, changes to any other key are reported as needing a restart. Live
+//! keys set via CLI or env keep that value on reload, and a live key removed
+//! from the file goes back to its start-up value.
+
+use anyhow::{anyhow, Result};
+use std::collections::BTreeMap;
+
+/// Used when `CONFIG_FILE` is unset (a missing file is fine)
+pub const DEFAULT_PATH: &str = "ratacat.toml";
+
+/// Keys applied without a restart
+pub const LIVE_KEYS: &[&str] = &[
+    "theme",
+    "render_fps",
+    "render_fps_choices",
+    "keep_blocks",
+    "default_filter",
+    "labels",
+];
+
+/// Config file path: `CONFIG_FILE`, else `./ratacat.toml`
+pub fn path() -> std::path::PathBuf {
+    std::env::var("CONFIG_FILE")
+        .unwrap_or_else(|_| DEFAULT_PATH.to_string())
+        .into()
+}
+
+/// Live settings from the file (`None` = key absent)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LiveSettings {
+    pub theme: Option<crate::theme::ThemeVariant>,
+    pub render_fps: Option<u32>,
+    pub render_fps_choices: Option<Vec<u32>>,
+    pub keep_blocks: Option<usize>,
+    pub default_filter: Option<String>,
+    /// Account id (lowercase) → label shown in the Txs pane
+    pub labels: BTreeMap<String, String>,
+}
+
+/// One reload: the new live settings and which keys changed
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfigReload {
+    pub settings: LiveSettings,
+    /// Changed live keys (the app applies only these)
+    pub applied: Vec<String>,
+    /// Changed keys that only take effect after a restart
+    pub restart: Vec<String>,
+    /// Changed live keys left alone because CLI or env set them
+    pub ignored: Vec<String>,
+}
+
+/// What reloads are resolved against: live values at start-up and the live
+/// keys that CLI or env set (the file never overrides those)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LiveBaseline {
+    pub startup: LiveSettings,
+    pub pinned: Vec<String>,
+}
+
+impl LiveBaseline {
+    /// From the loaded config; `env_keys` from `env_live_keys` (before `export_env`)
+    pub fn new(cfg: &crate::config::Config, env_keys: Vec<String>) -> Self {
+        let mut pinned = env_keys;
+        for key in &cfg.cli_live_keys {
+            if !pinned.contains(key) {
+                pinned.push(key.clone());
+            }
+        }
+        Self {
+            startup: LiveSettings {
+                theme: Some(cfg.theme_variant),
+                render_fps: Some(cfg.render_fps),
+                render_fps_choices: Some(cfg.render_fps_choices.clone()),
+                keep_blocks: Some(cfg.keep_blocks),
+                default_filter: Some(cfg.default_filter.clone()),
+                labels: BTreeMap::new(),
+            },
+            pinned,
+        }
+    }
+}
+
+impl ConfigReload {
+    /// Move pinned keys to `ignored` and give removed keys their start-up value
+    /// (`labels` only ever come from the file: removing the table clears them)
+    pub fn against(mut self, base: &LiveBaseline) -> Self {
+        let (ignored, applied) = self
+            .applied
+            .into_iter()
+            .partition(|key| base.pinned.contains(key));
+        self.ignored = ignored;
+        self.applied = applied;
+
+        let (s, start) = (&mut self.settings, &base.startup);
+        if s.theme.is_none() {
+            s.theme = start.theme;
+        }
+        if s.render_fps.is_none() {
+            s.render_fps = start.render_fps;
+        }
+        if s.render_fps_choices.is_none() {
+            s.render_fps_choices = start.render_fps_choices.clone();
+        }
+        if s.keep_blocks.is_none() {
+            s.keep_blocks = start.keep_blocks;
+        }
+        if s.default_filter.is_none() {
+            s.default_filter = start.default_filter.clone();
+        }
+        self
+    }
+}
+
+/// Live keys the environment (or `.env`) sets. Call before `export_env`, which
+/// fills the environment from the file.
+pub fn env_live_keys() -> Vec<String> {
+    LIVE_KEYS
+        .iter()
+        .filter(|key| match **key {
+            "labels" => false,
+            // WATCH_ACCOUNTS builds the default filter too
+            "default_filter" => {
+                std::env::var_os("DEFAULT_FILTER").is_some() || std::env::var_os("WATCH_ACCOUNTS").is_some()
+            }
+            key => std::env::var_os(key.to_uppercase()).is_some(),
+        })
+        .map(|key| key.to_string())
+        .collect()
+}
+
+/// `render_fps = 30` and `render_fps = "30"` both work, like env values
+fn scalar(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(s) => s.clone(),
+        toml::Value::Array(items) => items.iter().map(scalar).collect::<Vec<_>>().join(","),
+        other => other.to_string(),
+    }
+}
+
+pub fn parse(text: &str) -> Result<toml::Table> {
+    text.parse::<toml::Table>().map_err(|e| anyhow!("{e}"))
+}
+
+/// Validate the live keys (same ranges as start-up)
+pub fn live_settings(table: &toml::Table) -> Result<LiveSettings> {
+    let get = |key: &str| table.get(key).map(scalar);
+    let number = |key: &str, min: u64, max: u64| -> Result<Option<u64>> {
+        get(key)
+            .map(|v| match v.trim().parse::<u64>() {
+                Ok(n) if (min..=max).contains(&n) => Ok(n),
+                _ => Err(anyhow!("{key} must be in range [{min}, {max}], got {v}")),
+            })
+            .transpose()
+    };
+
+    let render_fps_choices = get("render_fps_choices")
+        .map(|v| {
+            let choices = crate::config::parse_fps_list(&v);
+            if choices.is_empty() {
+                Err(anyhow!("render_fps_choices must contain at least one valid value (1-120)"))
+            } else {
+                Ok(choices)
+            }
+        })
+        .transpose()?;
+    let labels = match table.get("labels") {
+        None => BTreeMap::new(),
+        Some(toml::Value::Table(t)) => t
+            .iter()
+            .map(|(account, label)| (account.to_lowercase(), scalar(label)))
+            .collect(),
+        Some(_) => return Err(anyhow!("labels must be a table: [labels] \"alice.near\" = \"Alice\"")),
+    };
+
+    Ok(LiveSettings {
+        theme: get("theme").map(|v| v.parse()).transpose()?,
+        render_fps: number("render_fps", 1, 120)?.map(|n| n as u32),
+        render_fps_choices,
+        keep_blocks: number("keep_blocks", 10, 10000)?.map(|n| n as usize),
+        default_filter: get("default_filter"),
+        labels,
+    })
+}
+
+/// Compare two versions of the file
+pub fn diff(prev: &toml::Table, next: &toml::Table) -> Result<ConfigReload> {
+    let settings = live_settings(next)?;
+    let mut applied = Vec::new();
+    let mut restart = Vec::new();
+    let keys: std::collections::BTreeSet<&String> = prev.keys().chain(next.keys()).collect();
+    for key in keys {
+        if prev.get(key) == next.get(key) {
+            continue;
+        }
+        if LIVE_KEYS.contains(&key.as_str()) {
+            applied.push(key.clone());
+        } else {
+            restart.push(key.clone());
+        }
+    }
+    Ok(ConfigReload {
+        settings,
+        applied,
+        restart,
+        ignored: Vec::new(),
+    })
+}
+
+/// Export the file's scalar keys as environment variables (upper case) unless
+/// already set, like `.env`. Call before `config::load`. Returns the table
+/// read (empty when there is no file).
+#[cfg(feature = "native")]
+pub fn export_env(path: &std::path::Path) -> Result<toml::Table> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(toml::Table::new()),
+        Err(e) => return Err(anyhow!("{}: {e}", path.display())),
+    };
+    let table = parse(&text).map_err(|e| anyhow!("{}: {e}", path.display()))?;
+    for (key, value) in &table {
+        if value.is_table() {
+            continue;
+        }
+        let var = key.to_uppercase();
+        if std::env::var_os(&var).is_none() {
+            std::env::set_var(var, scalar(value));
+        }
+    }
+    Ok(table)
+}
+
+/// Watch `path` (starting from `initial`, the table read at start-up) and
+/// send a `ConfigReload` for every change (resolved against `baseline`), or
+/// the parse/validation error. The initial labels are sent right away.
+#[cfg(feature = "native")]
+pub async fn start_config_watcher(
+    path: std::path::PathBuf,
+    initial: toml::Table,
+    baseline: LiveBaseline,
+    tx: tokio::sync::mpsc::UnboundedSender<std::result::Result<ConfigReload, String>>,
+) -> Result<()> {
+    use notify::{Error as NotifyError, Event, EventKind, RecursiveMode, Watcher};
+
+    if let Ok(settings) = live_settings(&initial) {
+        if !settings.labels.is_empty() {
+            let _ = tx.send(Ok(ConfigReload {
+                settings,
+                applied: vec!["labels".to_string()],
+                restart: Vec::new(),
+                ignored: Vec::new(),
+            }));
+        }
+    }
+
+    // Watch the directory: editors replace the file rather than write in place
+    let dir = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p.to_path_buf(),
+        _ => std::path::PathBuf::from("."),
+    };
+    let file_name = path.file_name().map(|n| n.to_os_string());
+    let (notify_tx, mut notify_rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |res: Result<Event, NotifyError>| {
+        if let Ok(event) = res {
+            let _ = notify_tx.send(event);
+        }
+    })?;
+    watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+
+    tokio::spawn(async move {
+        let _watcher = watcher; // dropped (and stopped) with the task
+        let mut prev = initial;
+        while let Some(event) = notify_rx.recv().await {
+            let ours = event.paths.iter().any(|p| p.file_name() == file_name.as_deref());
+            if !ours || !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)) {
+                continue;
+            }
+            // Debounce: wait a bit for file writes to complete, then drain the burst
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+            while notify_rx.try_recv().is_ok() {}
+
+            let next = match tokio::fs::read_to_string(&path).await {
+                Ok(text) => parse(&text),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(toml::Table::new()),
+                Err(e) => Err(anyhow!("{e}")),
+            };
+            let reload = next.and_then(|next| {
+                let reload = diff(&prev, &next)?.against(&baseline);
+                prev = next;
+                Ok(reload)
+            });
+            match reload {
+                Ok(r) if r.applied.is_empty() && r.restart.is_empty() && r.ignored.is_empty() => {}
+                Ok(r) => {
+                    if tx.send(Ok(r)).is_err() {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    if tx.send(Err(format!("{}: {e:#}", path.display()))).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_splits_live_and_restart_keys() {
+        let prev = parse("theme = \"dark\"\nsource = \"rpc\"\nkeep_blocks = 100").unwrap();
+        let next = parse(
+            "theme = \"light\"\nsource = \"ws\"\nkeep_blocks = 100\nrender_fps = \"30\"\n\
+             [labels]\n\"Intents.near\" = \"Intents\"",
+        )
+        .unwrap();
+        let reload = diff(&prev, &next).unwrap();
+        assert_eq!(reload.applied, ["labels", "render_fps", "theme"]);
+        assert_eq!(reload.restart, ["source"]);
+        assert_eq!(reload.settings.theme, Some(crate::theme::ThemeVariant::Light));
+        assert_eq!(reload.settings.render_fps, Some(30));
+        assert_eq!(reload.settings.keep_blocks, Some(100));
+        assert_eq!(reload.settings.labels["intents.near"], "Intents");
+
+        let bad = parse("keep_blocks = 5").unwrap();
+        assert!(diff(&prev, &bad).is_err());
+    }
+
+    fn baseline(pinned: &[&str]) -> LiveBaseline {
+        LiveBaseline {
+            startup: LiveSettings {
+                theme: Some(crate::theme::ThemeVariant::HighContrast),
+                render_fps: Some(60),
+                keep_blocks: Some(200),
+                ..Default::default()
+            },
+            pinned: pinned.iter().map(|k| k.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn keys_set_via_cli_or_env_are_not_overridden() {
+        let prev = parse("render_fps = 30").unwrap();
+        let next = parse("render_fps = 30\ntheme = \"light\"\nkeep_blocks = 500").unwrap();
+        let reload = diff(&prev, &next).unwrap().against(&baseline(&["theme"]));
+        assert_eq!(reload.applied, ["keep_blocks"]);
+        assert_eq!(reload.ignored, ["theme"]);
+        assert_eq!(reload.settings.keep_blocks, Some(500));
+    }
+
+    #[test]
+    fn removed_keys_fall_back_to_startup_values() {
+        let prev = parse("theme = \"light\"\nrender_fps = 30").unwrap();
+        let reload = diff(&prev, &toml::Table::new()).unwrap().against(&baseline(&[]));
+        assert_eq!(reload.applied, ["render_fps", "theme"]);
+        assert_eq!(reload.settings.theme, Some(crate::theme::ThemeVariant::HighContrast));
+        assert_eq!(reload.settings.render_fps, Some(60));
+    }
+}
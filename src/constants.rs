@@ -23,6 +23,12 @@ pub mod app {
     /// Duration to show toast notifications (seconds)
     pub const TOAST_DURATION_SECS: u64 = 2;
 
+    /// Toasts shown at once (more wait in the queue)
+    pub const TOAST_MAX_VISIBLE: usize = 3;
+
+    /// Expired toasts kept for the toast history overlay
+    pub const TOAST_HISTORY_LEN: usize = 100;
+
     /// Maximum number of debug log lines to retain in memory
     pub const MAX_DEBUG_LOG_LINES: usize = 500;
 
@@ -45,6 +51,13 @@ pub mod app {
     /// from the archival RPC endpoint. This enables smooth navigation through
     /// historical blocks without per-block fetch latency.
     pub const ARCHIVAL_CONTEXT_BLOCKS: u64 = 50;
+
+    /// Idle time after the last Blocks pane move before the window fill runs
+    ///
+    /// Holding ↑/↓ (or PgDn) moves the selection on every key repeat, but the
+    /// archival backfill and context caching around it wait until navigation
+    /// pauses this long, so a held key doesn't flood the archival endpoint.
+    pub const NAV_SETTLE_MS: u64 = 150;
 }
 
 /// User-facing message strings
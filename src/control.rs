@@ -0,0 +1,425 @@
+//! Local control channel for the native TUI (`CONTROL_SOCKET`)
+//!
+//! External tools (shell scripts, the browser extension through the native
+//! host) drive a running TUI by sending `UiAction`s over a Unix socket (a
+//! named pipe on Windows). One JSON request per line, one reply per line:
+//!
+//! ```text
+//! → {"token":"…","action":{"type":"SetFilter","text":"acct:alice.near"}}
+//! ← {"ok":true}
+//! → {"token":"…","action":{"type":"OpenRoute","link":"nearx://v1/tx/AbC…"}}
+//! ← {"ok":true}
+//! → {"token":"…","action":{"type":"SetEndpoints","near_node_url":"…"}}
+//! ← {"ok":false,"error":"action SetEndpoints is not allowed (CONTROL_ALLOW)"}
+//! ```
+//!
+//! Every request carries the token (`CONTROL_TOKEN`, or a random one written
+//! next to the socket as `<socket>.token`, readable by the owner only), and
+//! only action types on the allowlist (`CONTROL_ALLOW`) are accepted. Accepted
+//! actions reach the app as `AppEvent::Control`, exactly as if a frontend had
+//! sent them.
+
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+use crate::ui_snapshot::UiAction;
+
+/// Action types accepted when `CONTROL_ALLOW` is unset: navigation only
+pub const DEFAULT_ALLOW: [&str; 5] = [
+    "SetFilter",
+    "FocusPane",
+    "SelectBlock",
+    "SelectTx",
+    "OpenRoute",
+];
+
+/// Every `UiAction` type name (what `CONTROL_ALLOW` may list)
+fn action_types() -> &'static [&'static str] {
+    <UiAction as strum::VariantNames>::VARIANTS
+}
+
+/// Longest request line accepted (bytes)
+pub const MAX_REQUEST_BYTES: usize = 64 * 1024;
+
+/// Parse `CONTROL_ALLOW`: a comma list of action types, or `all`
+pub fn parse_allow(s: &str) -> Result<Vec<String>> {
+    if s.trim().eq_ignore_ascii_case("all") {
+        return Ok(action_types().iter().map(|t| t.to_string()).collect());
+    }
+    s.split(',')
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(|t| {
+            action_types()
+                .iter()
+                .find(|a| a.eq_ignore_ascii_case(t))
+                .map(|a| a.to_string())
+                .ok_or_else(|| anyhow!("Invalid CONTROL_ALLOW entry '{t}'. Expected UiAction types (e.g. SetFilter,OpenRoute) or all"))
+        })
+        .collect()
+}
+
+/// Check one request line: token, then allowlist, then the action itself
+pub fn authorize(line: &str, token: &str, allow: &[String]) -> Result<UiAction, String> {
+    let req: Value = serde_json::from_str(line).map_err(|e| format!("invalid JSON: {e}"))?;
+    let given = req.get("token").and_then(Value::as_str).unwrap_or_default();
+    if !same_token(given, token) {
+        return Err("bad token".to_string());
+    }
+    let action = req.get("action").ok_or("missing action")?;
+    let kind = action
+        .get("type")
+        .and_then(Value::as_str)
+        .ok_or("action has no type")?;
+    if !allow.iter().any(|a| a == kind) {
+        return Err(format!("action {kind} is not allowed (CONTROL_ALLOW)"));
+    }
+    serde_json::from_value(action.clone()).map_err(|e| format!("invalid {kind} action: {e}"))
+}
+
+/// Token comparison that doesn't stop at the first differing byte
+fn same_token(given: &str, token: &str) -> bool {
+    given.len() == token.len()
+        && given
+            .bytes()
+            .zip(token.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+/// Reply line for a request's outcome
+pub fn reply(outcome: &Result<UiAction, String>) -> String {
+    match outcome {
+        Ok(_) => r#"{"ok":true}"#.to_string(),
+        Err(e) => serde_json::json!({ "ok": false, "error": e }).to_string(),
+    }
+}
+
+#[cfg(feature = "native")]
+pub use server::{serve, ControlServer};
+
+#[cfg(feature = "native")]
+mod server {
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+
+    use anyhow::{Context, Result};
+    use tokio::io::{
+        AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader,
+    };
+    use tokio::sync::mpsc::UnboundedSender;
+    use tokio::task::JoinHandle;
+
+    use super::{authorize, reply, MAX_REQUEST_BYTES};
+    use crate::types::AppEvent;
+
+    /// Running control channel; dropping it stops accepting and removes the
+    /// socket and generated token file
+    pub struct ControlServer {
+        path: PathBuf,
+        token_file: Option<PathBuf>,
+        accept: JoinHandle<()>,
+    }
+
+    impl ControlServer {
+        pub fn path(&self) -> &Path {
+            &self.path
+        }
+
+        /// Where the generated token was written (None with `CONTROL_TOKEN`)
+        pub fn token_file(&self) -> Option<&Path> {
+            self.token_file.as_deref()
+        }
+    }
+
+    impl Drop for ControlServer {
+        fn drop(&mut self) {
+            self.accept.abort();
+            #[cfg(unix)]
+            let _ = std::fs::remove_file(&self.path);
+            if let Some(file) = &self.token_file {
+                let _ = std::fs::remove_file(file);
+            }
+        }
+    }
+
+    struct Shared {
+        token: String,
+        allow: Vec<String>,
+        events: UnboundedSender<AppEvent>,
+    }
+
+    /// Listen on `path` and forward accepted actions to `events`. Without a
+    /// configured token, a random one is written to `<path>.token`.
+    pub async fn serve(
+        path: &Path,
+        token: Option<String>,
+        allow: Vec<String>,
+        events: UnboundedSender<AppEvent>,
+    ) -> Result<ControlServer> {
+        let generated = token.is_none();
+        let token = token.unwrap_or_else(random_token);
+        let shared = Arc::new(Shared {
+            token: token.clone(),
+            allow,
+            events,
+        });
+        // Bind first: a second instance must not overwrite the live one's token
+        let mut server = ControlServer {
+            path: path.to_path_buf(),
+            token_file: None,
+            accept: listen(path, shared).await?,
+        };
+        if generated {
+            let file = PathBuf::from(format!("{}.token", path.display()));
+            write_private(&file, &token)
+                .with_context(|| format!("control: cannot write {}", file.display()))?;
+            server.token_file = Some(file);
+        }
+        Ok(server)
+    }
+
+    #[cfg(unix)]
+    async fn listen(path: &Path, shared: Arc<Shared>) -> Result<JoinHandle<()>> {
+        use std::os::unix::fs::PermissionsExt;
+        use tokio::net::{UnixListener, UnixStream};
+
+        // A socket left behind by a crashed run is replaced; a live one is not
+        if path.exists() {
+            if UnixStream::connect(path).await.is_ok() {
+                anyhow::bail!("control: {} is in use by another instance", path.display());
+            }
+            let _ = std::fs::remove_file(path);
+        }
+        let listener = UnixListener::bind(path)
+            .with_context(|| format!("control: cannot listen on {}", path.display()))?;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+
+        Ok(tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, _)) => {
+                        tokio::spawn(serve_client(stream, shared.clone()));
+                    }
+                    Err(e) => {
+                        log::warn!("control: accept failed: {e}");
+                        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                    }
+                }
+            }
+        }))
+    }
+
+    #[cfg(windows)]
+    async fn listen(path: &Path, shared: Arc<Shared>) -> Result<JoinHandle<()>> {
+        use tokio::net::windows::named_pipe::ServerOptions;
+
+        let name = path.as_os_str().to_os_string();
+        let mut server = ServerOptions::new()
+            .first_pipe_instance(true)
+            .reject_remote_clients(true)
+            .create(&name)
+            .with_context(|| format!("control: cannot create pipe {}", path.display()))?;
+
+        Ok(tokio::spawn(async move {
+            loop {
+                if let Err(e) = server.connect().await {
+                    log::warn!("control: accept failed: {e}");
+                    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                    continue;
+                }
+                // Open the next instance before handing this one off
+                let next = match ServerOptions::new()
+                    .reject_remote_clients(true)
+                    .create(&name)
+                {
+                    Ok(next) => next,
+                    Err(e) => {
+                        log::warn!("control: cannot create pipe instance: {e}");
+                        return;
+                    }
+                };
+                let client = std::mem::replace(&mut server, next);
+                tokio::spawn(serve_client(client, shared.clone()));
+            }
+        }))
+    }
+
+    /// Answer one client's requests until it disconnects
+    async fn serve_client<S>(stream: S, shared: Arc<Shared>)
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let (read, mut write) = tokio::io::split(stream);
+        let mut lines = BufReader::new(read);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            // Bounded read: a client can't make us buffer an endless line
+            let mut limited = (&mut lines).take(MAX_REQUEST_BYTES as u64 + 1);
+            match limited.read_line(&mut line).await {
+                Ok(0) | Err(_) => break,
+                Ok(_) if line.len() > MAX_REQUEST_BYTES => {
+                    let _ = write
+                        .write_all(b"{\"ok\":false,\"error\":\"request too long\"}\n")
+                        .await;
+                    break;
+                }
+                Ok(_) => {}
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            let outcome = authorize(line.trim(), &shared.token, &shared.allow);
+            let mut answer = reply(&outcome);
+            answer.push('\n');
+            if let Ok(action) = outcome {
+                if shared.events.send(AppEvent::Control(action)).is_err() {
+                    break;
+                }
+            } else {
+                log::debug!("control: rejected request: {}", answer.trim());
+            }
+            if write.write_all(answer.as_bytes()).await.is_err() {
+                break;
+            }
+        }
+    }
+
+    fn random_token() -> String {
+        use rand::Rng;
+        let bytes: [u8; 24] = rand::thread_rng().gen();
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    /// Write `contents` readable by the owner only
+    fn write_private(path: &Path, contents: &str) -> std::io::Result<()> {
+        use std::io::Write;
+        let mut opts = std::fs::OpenOptions::new();
+        opts.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            opts.mode(0o600);
+        }
+        opts.open(path)?.write_all(contents.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = "s3cret";
+
+    fn navigation() -> Vec<String> {
+        parse_allow("SetFilter, openroute").unwrap()
+    }
+
+    fn request(token: &str, action: &str) -> String {
+        format!(r#"{{"token":"{token}","action":{action}}}"#)
+    }
+
+    #[test]
+    fn action_types_are_the_serde_tags_of_ui_action() {
+        assert!(action_types().contains(&"SetEndpoints"));
+        for kind in action_types() {
+            let err = serde_json::from_value::<UiAction>(serde_json::json!({ "type": kind }))
+                .err()
+                .map(|e| e.to_string())
+                .unwrap_or_default();
+            assert!(!err.contains("unknown variant"), "{kind}: {err}");
+        }
+        assert!(DEFAULT_ALLOW.iter().all(|t| action_types().contains(t)));
+    }
+
+    #[test]
+    fn parse_allow_matches_type_names_case_insensitively() {
+        assert_eq!(navigation(), ["SetFilter", "OpenRoute"]);
+        assert_eq!(parse_allow("ALL").unwrap().len(), action_types().len());
+        assert!(parse_allow("SetFilter,Reboot").is_err());
+    }
+
+    #[test]
+    fn empty_allowlist_denies_every_action() {
+        let allow = parse_allow(" , ").unwrap();
+        assert!(allow.is_empty());
+        let denied = authorize(
+            &request(TOKEN, r#"{"type":"SetFilter","text":""}"#),
+            TOKEN,
+            &allow,
+        );
+        assert!(denied.unwrap_err().contains("not allowed"));
+    }
+
+    #[test]
+    fn allowed_action_with_the_token_is_accepted() {
+        let ok = authorize(
+            &request(TOKEN, r#"{"type":"SetFilter","text":"acct:a.near"}"#),
+            TOKEN,
+            &navigation(),
+        );
+        assert!(matches!(ok, Ok(UiAction::SetFilter { ref text }) if text == "acct:a.near"));
+        assert_eq!(reply(&ok), r#"{"ok":true}"#);
+    }
+
+    #[test]
+    fn token_is_checked_before_the_allowlist() {
+        let bad = authorize(
+            &request("s3cres", r#"{"type":"SetEndpoints","near_node_url":"http://x"}"#),
+            TOKEN,
+            &navigation(),
+        );
+        assert_eq!(bad.unwrap_err(), "bad token");
+        assert_eq!(
+            authorize(r#"{"action":{"type":"SetFilter","text":""}}"#, TOKEN, &navigation())
+                .unwrap_err(),
+            "bad token"
+        );
+    }
+
+    #[test]
+    fn tokens_of_a_different_length_never_match() {
+        assert!(same_token(TOKEN, TOKEN));
+        assert!(!same_token("s3cre", TOKEN));
+        assert!(!same_token("s3crett", TOKEN));
+        assert!(!same_token("", TOKEN));
+    }
+
+    #[test]
+    fn action_outside_the_allowlist_is_denied() {
+        let denied = authorize(
+            &request(TOKEN, r#"{"type":"SetEndpoints","near_node_url":"http://x"}"#),
+            TOKEN,
+            &navigation(),
+        );
+        assert_eq!(
+            denied.unwrap_err(),
+            "action SetEndpoints is not allowed (CONTROL_ALLOW)"
+        );
+    }
+
+    #[test]
+    fn malformed_payloads_are_rejected() {
+        let allow = navigation();
+        let missing_field = authorize(&request(TOKEN, r#"{"type":"SetFilter"}"#), TOKEN, &allow);
+        assert!(missing_field.unwrap_err().starts_with("invalid SetFilter action"));
+        assert_eq!(
+            authorize(&request(TOKEN, r#"{"text":"x"}"#), TOKEN, &allow).unwrap_err(),
+            "action has no type"
+        );
+        assert_eq!(
+            authorize(r#"{"token":"s3cret"}"#, TOKEN, &allow).unwrap_err(),
+            "missing action"
+        );
+    }
+
+    #[test]
+    fn invalid_json_gets_a_json_error_reply() {
+        let outcome = authorize("not json", TOKEN, &navigation());
+        assert!(outcome.as_ref().unwrap_err().starts_with("invalid JSON"));
+        let reply: Value = serde_json::from_str(&reply(&outcome)).unwrap();
+        assert_eq!(reply["ok"], false);
+        assert!(reply["error"].as_str().unwrap().starts_with("invalid JSON"));
+    }
+}
@@ -11,7 +11,8 @@
 //!
 //! ## Output Format
 //!
-//! - Pretty-printed JSON string (human-friendly in chats/issues)
+//! - Pretty-printed JSON string (human-friendly in chats/issues), using the
+//!   same indent / key order as the Details pane (`JSON_INDENT`, `JSON_SORT_KEYS`)
 //! - No trailing newline (clipboard-friendly)
 //!
 //! ## Usage
@@ -24,6 +25,8 @@
 //! }
 //! ```
 
+use crate::clipboard::CopyReport;
+use crate::json_pretty::JsonFormat;
 use crate::platform;
 use crate::App;
 use serde_json::Value;
@@ -75,18 +78,14 @@ pub fn payload_for(app: &App, pane: CopyPane) -> Option<Value> {
     }
 }
 
-/// Pretty-print JSON value, without a trailing newline.
+/// Pretty-print JSON value (app's indent / key order), without a trailing newline.
 #[inline]
-fn pretty_no_newline(v: &Value) -> String {
-    match serde_json::to_string_pretty(v) {
-        Ok(mut s) => {
-            if s.ends_with('\n') {
-                s.pop();
-            }
-            s
-        }
-        Err(_) => String::new(),
+fn pretty_no_newline(v: &Value, fmt: JsonFormat) -> String {
+    let mut s = crate::json_pretty::pretty(v, fmt);
+    if s.ends_with('\n') {
+        s.pop();
     }
+    s
 }
 
 /// Returns the string that would be copied for the current focus, if any.
@@ -94,7 +93,7 @@ fn pretty_no_newline(v: &Value) -> String {
 /// This is useful for testing or preview without actually writing to clipboard.
 pub fn current_text(app: &App) -> Option<String> {
     let pane = focused_pane(app);
-    payload_for(app, pane).map(|v| pretty_no_newline(&v))
+    payload_for(app, pane).map(|v| pretty_no_newline(&v, app.json_format()))
 }
 
 /// Copies the current pane payload to the clipboard.
@@ -118,9 +117,14 @@ pub fn current_text(app: &App) -> Option<String> {
 /// let _ = copy_api::copy_current(&app);
 /// ```
 pub fn copy_current(app: &App) -> bool {
+    copy_current_report(app).is_some_and(|r| r.ok())
+}
+
+/// Like `copy_current`, with the backend that took the text (`None` = nothing to copy)
+pub fn copy_current_report(app: &App) -> Option<CopyReport> {
     match current_text(app) {
-        Some(s) if !s.is_empty() => platform::copy_to_clipboard(&s),
-        _ => false,
+        Some(s) if !s.is_empty() => Some(platform::copy_with_report(&s)),
+        _ => None,
     }
 }
 
@@ -141,7 +145,7 @@ mod tests {
     #[test]
     fn test_pretty_no_newline() {
         let json = serde_json::json!({"test": "value"});
-        let result = pretty_no_newline(&json);
+        let result = pretty_no_newline(&json, JsonFormat::default());
         assert!(!result.ends_with('\n'), "Should not have trailing newline");
         assert!(result.contains("\"test\""), "Should contain JSON content");
     }
@@ -0,0 +1,189 @@
+//! Deep link canonicalization shared by every entry point
+//!
+//! `nearx://` links reach NEARx through the Tauri shell (OS URL handler,
+//! single-instance argv), the native messaging host (browser extension) and
+//! the in-app router (`location.hash`, CLI args). All of them go through
+//! `canonicalize` / `parse_event` first, so one link yields one route no
+//! matter how it was spelled:
+//!
+//! - Scheme aliases: `nearx:`, `web+nearx:`, `near:` (legacy), `web+near:`, any case
+//! - Missing or repeated slashes: `nearx:v1/tx/A`, `nearx:////v1//tx/A/`
+//! - Percent-encoding: path segments and query pairs are decoded once and
+//!   re-encoded minimally (`%41` → `A`, space → `%20`)
+//! - Case folding: the host and route keyword (`V1/TX` → `v1/tx`) and account
+//!   ids (always lowercase on NEAR); tx hashes are base58 and keep their case
+//! - Query pairs sorted by key (last duplicate wins), fragment dropped
+//!
+//! Invalid percent-encoded UTF-8 and unknown schemes are rejected (`None`).
+//! The canonical form is a fixed point: `canonicalize(canonicalize(x)) ==
+//! canonicalize(x)`, which the property tests and `fuzz/` target check.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Schemes accepted on input (lowercase); output is always `nearx://`
+const SCHEMES: [&str; 4] = ["nearx", "web+nearx", "near", "web+near"];
+
+/// A parsed deep link, as emitted to the Tauri frontend (`deep-link` event)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DeepLinkEvent {
+    /// Canonical URL
+    pub url: String,
+    pub scheme: String,
+    /// First component: the resource (`v1`, `tx`, `account`, `open`, `nearx`)
+    pub host: String,
+    /// Remaining path segments, decoded
+    pub path: Vec<String>,
+    /// Decoded `?k=v` pairs
+    pub query: BTreeMap<String, String>,
+}
+
+/// Whether `raw` starts with one of the accepted deep link schemes
+pub fn has_scheme(raw: &str) -> bool {
+    raw.trim()
+        .split_once(':')
+        .is_some_and(|(scheme, _)| SCHEMES.iter().any(|s| scheme.eq_ignore_ascii_case(s)))
+}
+
+/// Canonical `nearx://host/seg/...?k=v` form of a deep link (None if malformed)
+pub fn canonicalize(raw: &str) -> Option<String> {
+    parse_event(raw).map(|ev| ev.url)
+}
+
+/// Canonicalize and split a deep link into host / path / query
+pub fn parse_event(raw: &str) -> Option<DeepLinkEvent> {
+    if !has_scheme(raw) {
+        return None;
+    }
+    let (_, rest) = raw.trim().split_once(':')?;
+
+    let rest = rest.split_once('#').map_or(rest, |(before, _)| before);
+    let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+
+    let mut segments = path
+        .split('/')
+        .filter(|seg| !seg.is_empty())
+        .map(|seg| urlencoding::decode(seg).ok().map(|d| d.into_owned()))
+        .collect::<Option<Vec<String>>>()?;
+    if segments.is_empty() {
+        return None;
+    }
+    fold_case(&mut segments);
+
+    let mut pairs = BTreeMap::new();
+    for pair in query.split('&').filter(|p| !p.is_empty()) {
+        let (k, v) = pair.split_once('=').unwrap_or((pair, ""));
+        let k = decode_query(k)?;
+        if !k.is_empty() {
+            pairs.insert(k, decode_query(v)?);
+        }
+    }
+
+    let mut url = format!(
+        "nearx://{}",
+        segments
+            .iter()
+            .map(|seg| urlencoding::encode(seg))
+            .collect::<Vec<_>>()
+            .join("/")
+    );
+    if !pairs.is_empty() {
+        let query: Vec<String> = pairs
+            .iter()
+            .map(|(k, v)| format!("{}={}", urlencoding::encode(k), urlencoding::encode(v)))
+            .collect();
+        url.push('?');
+        url.push_str(&query.join("&"));
+    }
+
+    let host = segments.remove(0);
+    Some(DeepLinkEvent {
+        url,
+        scheme: "nearx".into(),
+        host,
+        path: segments,
+        query: pairs,
+    })
+}
+
+/// Lowercase the host, the route keyword (after `v1`) and account ids
+fn fold_case(segments: &mut [String]) {
+    segments[0].make_ascii_lowercase();
+    let keyword = if segments[0] == "v1" { 1 } else { 0 };
+    if let Some(seg) = segments.get_mut(keyword) {
+        seg.make_ascii_lowercase();
+    }
+    if segments.get(keyword).is_some_and(|k| k == "account") {
+        if let Some(id) = segments.get_mut(keyword + 1) {
+            id.make_ascii_lowercase();
+        }
+    }
+}
+
+/// Form-style query decoding (`+` is a space)
+fn decode_query(s: &str) -> Option<String> {
+    urlencoding::decode(&s.replace('+', " "))
+        .ok()
+        .map(|d| d.into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn spellings_share_one_canonical_form() {
+        let canonical = Some("nearx://v1/tx/AbC123".to_string());
+        for raw in [
+            "nearx://v1/tx/AbC123",
+            "  NEARX://V1/TX/AbC123/  ",
+            "nearx:v1/tx/AbC123",
+            "nearx:////v1//tx/AbC123",
+            "web+nearx:v1/tx/AbC123",
+            "near://v1/tx/%41bC123#frag",
+        ] {
+            assert_eq!(canonicalize(raw), canonical, "{raw}");
+        }
+
+        let ev = parse_event("nearx://V1/Account/Alice.NEAR?b=2&a=x+y&b=3").unwrap();
+        assert_eq!(ev.host, "v1");
+        assert_eq!(ev.path, vec!["account", "alice.near"]);
+        assert_eq!(ev.query.get("a").map(String::as_str), Some("x y"));
+        assert_eq!(ev.url, "nearx://v1/account/alice.near?a=x%20y&b=3");
+
+        assert_eq!(parse_event("nearx://a%2Fb").unwrap().host, "a/b");
+        assert_eq!(
+            canonicalize("nearx://a%2Fb").as_deref(),
+            Some("nearx://a%2Fb")
+        );
+
+        assert!(canonicalize("https://v1/tx/A").is_none());
+        assert!(canonicalize("nearx://").is_none());
+        assert!(canonicalize("nearx://v1/tx/%FF").is_none()); // not UTF-8
+    }
+
+    proptest! {
+        #[test]
+        fn canonical_form_is_a_fixed_point(raw in any::<String>()) {
+            if let Some(canonical) = canonicalize(&raw) {
+                prop_assert_eq!(canonicalize(&canonical), Some(canonical.clone()));
+                prop_assert_eq!(parse_event(&canonical), parse_event(&raw));
+            }
+        }
+
+        #[test]
+        fn url_like_inputs_are_a_fixed_point(
+            scheme in prop::sample::select(vec!["nearx:", "NEARX://", "web+nearx:", "near:///"]),
+            segs in prop::collection::vec("[a-zA-Z0-9._%/ +-]{0,8}", 0..5),
+            query in "[a-zA-Z0-9=&%+]{0,12}",
+        ) {
+            let raw = format!("{scheme}{}?{query}", segs.join("/"));
+            if let Some(canonical) = canonicalize(&raw) {
+                prop_assert_eq!(canonicalize(&canonical), Some(canonical.clone()));
+                prop_assert_eq!(parse_event(&canonical), parse_event(&raw));
+            }
+        }
+    }
+}
@@ -0,0 +1,463 @@
+//! Per-contract activity digest (`nearx digest <contract>`, Ctrl+G in the TUI)
+//!
+//! Aggregates the txs History holds for one receiver over a time window:
+//! unique callers, top methods, attached gas, deposits and the large ones.
+//! History keeps no execution outcomes, so the failure rate comes from the
+//! final status of the newest txs, fetched over RPC (a sample). The digest
+//! renders as markdown, which is both the TUI view and the export.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::history::ContractTx;
+use crate::time_display::TimeDisplay;
+use crate::util_text::{format_gas, format_near};
+
+/// Deposits at or above this show up individually (100 NEAR)
+pub const LARGE_DEPOSIT_YOCTO: u128 = 100 * 10u128.pow(24);
+/// Windows cycled with `w` in the TUI view (the first is the default)
+pub const WINDOW_CHOICES: &[u64] = &[86_400, 3_600, 604_800];
+/// Txs read from History per digest (newest first)
+pub const MAX_TXS: usize = 50_000;
+/// Newest txs whose outcome is fetched for the failure rate
+pub const DEFAULT_SAMPLE: usize = 25;
+const TOP_N: usize = 5;
+#[cfg(feature = "native")]
+const SAMPLE_CONCURRENCY: usize = 8;
+
+/// `Vec<ActionSummary>` entry as stored in History (only the fields we sum)
+#[derive(Deserialize)]
+struct StoredAction {
+    #[serde(rename = "type")]
+    kind: String,
+    method_name: Option<String>,
+    gas: Option<u64>,
+    deposit: Option<u128>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LargeDeposit {
+    pub hash: String,
+    pub signer: String,
+    /// Method the deposit was attached to (`None` = plain transfer)
+    pub method: Option<String>,
+    pub amount: u128,
+    pub ts_ms: i64,
+}
+
+/// Outcomes of the newest txs (History has none)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FailureSample {
+    pub checked: usize,
+    pub failed: usize,
+    /// Gas burnt by the checked txs (all receipts)
+    pub gas_burnt: u64,
+    /// Decoded failure summaries, most common first
+    pub reasons: Vec<(String, usize)>,
+    /// Txs whose status could not be fetched
+    pub lookup_errors: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContractDigest {
+    pub contract: String,
+    pub window_secs: u64,
+    pub until_ms: i64,
+    pub tx_count: usize,
+    /// More than `MAX_TXS` txs in the window (the oldest were left out)
+    pub truncated: bool,
+    /// Oldest and newest block heights seen
+    pub heights: Option<(u64, u64)>,
+    pub unique_callers: usize,
+    pub top_callers: Vec<(String, usize)>,
+    pub calls: usize,
+    pub top_methods: Vec<(String, usize)>,
+    /// Gas attached to function calls
+    pub gas_attached: u64,
+    pub deposit_total: u128,
+    /// Largest first
+    pub large_deposits: Vec<LargeDeposit>,
+    pub failures: Option<FailureSample>,
+}
+
+/// `3600` → `1h`, `86400` → `24h`, `604800` → `7d`
+pub fn window_label(secs: u64) -> String {
+    match secs {
+        s if s >= 2 * 86_400 && s % 86_400 == 0 => format!("{}d", s / 86_400),
+        s if s >= 3_600 && s % 3_600 == 0 => format!("{}h", s / 3_600),
+        s if s >= 60 && s % 60 == 0 => format!("{}m", s / 60),
+        s => format!("{s}s"),
+    }
+}
+
+/// Window after `current` in `WINDOW_CHOICES`
+pub fn next_window(current: u64) -> u64 {
+    let idx = WINDOW_CHOICES.iter().position(|w| *w == current).map_or(0, |i| i + 1);
+    WINDOW_CHOICES[idx % WINDOW_CHOICES.len()]
+}
+
+/// Highest counts first, ties by name
+fn top(counts: HashMap<String, usize>, n: usize) -> Vec<(String, usize)> {
+    let mut v: Vec<_> = counts.into_iter().collect();
+    v.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    v.truncate(n);
+    v
+}
+
+/// Aggregate History rows (`txs` newest first, as `History::contract_txs` returns them)
+pub fn build(contract: &str, window_secs: u64, until_ms: i64, txs: &[ContractTx]) -> ContractDigest {
+    let mut callers: HashMap<String, usize> = HashMap::new();
+    let mut methods: HashMap<String, usize> = HashMap::new();
+    let mut calls = 0;
+    let mut gas_attached = 0u64;
+    let mut deposit_total = 0u128;
+    let mut large_deposits = Vec::new();
+
+    for tx in txs {
+        let signer = tx.signer.clone().unwrap_or_else(|| "?".into());
+        *callers.entry(signer.clone()).or_default() += 1;
+        let actions: Vec<StoredAction> = tx
+            .actions_json
+            .as_deref()
+            .and_then(|j| serde_json::from_str(j).ok())
+            .unwrap_or_default();
+        for action in actions {
+            if action.kind == "FunctionCall" {
+                calls += 1;
+                gas_attached = gas_attached.saturating_add(action.gas.unwrap_or(0));
+                if let Some(method) = &action.method_name {
+                    *methods.entry(method.clone()).or_default() += 1;
+                }
+            }
+            let deposit = action.deposit.unwrap_or(0);
+            deposit_total = deposit_total.saturating_add(deposit);
+            if deposit >= LARGE_DEPOSIT_YOCTO {
+                large_deposits.push(LargeDeposit {
+                    hash: tx.hash.clone(),
+                    signer: signer.clone(),
+                    method: action.method_name,
+                    amount: deposit,
+                    ts_ms: tx.ts_ms,
+                });
+            }
+        }
+    }
+    large_deposits.sort_by(|a, b| b.amount.cmp(&a.amount));
+    large_deposits.truncate(TOP_N * 2);
+
+    ContractDigest {
+        contract: contract.to_string(),
+        window_secs,
+        until_ms,
+        tx_count: txs.len(),
+        truncated: txs.len() >= MAX_TXS,
+        heights: txs
+            .last()
+            .zip(txs.first())
+            .map(|(oldest, newest)| (oldest.height, newest.height)),
+        unique_callers: callers.len(),
+        top_callers: top(callers, TOP_N),
+        calls,
+        top_methods: top(methods, TOP_N),
+        gas_attached,
+        deposit_total,
+        large_deposits,
+        failures: None,
+    }
+}
+
+/// Fold a `tx_status` result into the sample
+pub fn record_outcome(sample: &mut FailureSample, status: &serde_json::Value) {
+    sample.checked += 1;
+    let burnt = |o: &serde_json::Value| o["outcome"]["gas_burnt"].as_u64().unwrap_or(0);
+    sample.gas_burnt = sample
+        .gas_burnt
+        .saturating_add(burnt(&status["transaction_outcome"]))
+        .saturating_add(
+            status["receipts_outcome"]
+                .as_array()
+                .map_or(0, |r| r.iter().map(burnt).sum()),
+        );
+    if let Some(err) = crate::exec_error::decode(&status["status"]) {
+        sample.failed += 1;
+        match sample.reasons.iter_mut().find(|(s, _)| *s == err.summary) {
+            Some((_, n)) => *n += 1,
+            None => sample.reasons.push((err.summary, 1)),
+        }
+        sample.reasons.sort_by(|a, b| b.1.cmp(&a.1));
+    }
+}
+
+impl ContractDigest {
+    pub fn to_markdown(&self, times: &TimeDisplay) -> String {
+        let until = times.at(self.until_ms);
+        let mut out = format!(
+            "# {} — last {} (until {until})\n\n",
+            self.contract,
+            window_label(self.window_secs)
+        );
+        if self.tx_count == 0 {
+            out.push_str("_No transactions in History for this window._\n");
+            return out;
+        }
+
+        out.push_str(&format!("- **Transactions:** {}", self.tx_count));
+        if let Some((from, to)) = self.heights {
+            out.push_str(&format!(" (blocks #{from}..#{to})"));
+        }
+        if self.truncated {
+            out.push_str(&format!(" — newest {MAX_TXS} only"));
+        }
+        out.push('\n');
+        out.push_str(&format!("- **Unique callers:** {}\n", self.unique_callers));
+        out.push_str(&format!(
+            "- **Function calls:** {} · {} attached\n",
+            self.calls,
+            format_gas(self.gas_attached)
+        ));
+        out.push_str(&format!("- **Deposits:** {}\n", format_near(self.deposit_total)));
+        match &self.failures {
+            Some(f) if f.checked > 0 => {
+                out.push_str(&format!(
+                    "- **Failure rate:** {:.0}% ({} of the newest {} failed) · {} burnt",
+                    f.failed as f64 * 100.0 / f.checked as f64,
+                    f.failed,
+                    f.checked,
+                    format_gas(f.gas_burnt)
+                ));
+                if f.lookup_errors > 0 {
+                    out.push_str(&format!(" · {} status lookups failed", f.lookup_errors));
+                }
+                out.push('\n');
+            }
+            Some(f) if f.lookup_errors > 0 => out.push_str(&format!(
+                "- **Failure rate:** unknown ({} status lookups failed)\n",
+                f.lookup_errors
+            )),
+            _ => out.push_str("- **Failure rate:** not sampled\n"),
+        }
+
+        let table = |out: &mut String, title: &str, rows: &[(String, usize)]| {
+            if rows.is_empty() {
+                return;
+            }
+            out.push_str(&format!("\n## {title}\n\n"));
+            for (name, n) in rows {
+                out.push_str(&format!("- `{name}` × {n}\n"));
+            }
+        };
+        table(&mut out, "Top methods", &self.top_methods);
+        table(&mut out, "Top callers", &self.top_callers);
+        if let Some(f) = self.failures.as_ref().filter(|f| !f.reasons.is_empty()) {
+            table(&mut out, "Failures (sample)", &f.reasons);
+        }
+
+        if !self.large_deposits.is_empty() {
+            out.push_str(&format!(
+                "\n## Large deposits (≥ {})\n\n",
+                format_near(LARGE_DEPOSIT_YOCTO)
+            ));
+            for d in &self.large_deposits {
+                let method = d.method.as_deref().map(|m| format!(" → `{m}`")).unwrap_or_default();
+                out.push_str(&format!(
+                    "- {} from `{}`{method} · tx `{}`\n",
+                    format_near(d.amount),
+                    d.signer,
+                    d.hash
+                ));
+            }
+        }
+        out
+    }
+}
+
+/// Digest `contract` over the last `window_secs` from History, sampling the
+/// newest `sample` outcomes from `url` (0 = skip)
+#[cfg(feature = "native")]
+pub async fn generate(
+    history: &crate::history::History,
+    url: &str,
+    timeout_ms: u64,
+    auth_token: Option<&str>,
+    contract: &str,
+    window_secs: u64,
+    sample: usize,
+) -> ContractDigest {
+    let until_ms = chrono::Utc::now().timestamp_millis();
+    let since_ms = until_ms - window_secs as i64 * 1000;
+    let txs = history.contract_txs(contract, since_ms, MAX_TXS).await;
+    let mut digest = build(contract, window_secs, until_ms, &txs);
+
+    if sample > 0 && !txs.is_empty() {
+        let mut failures = FailureSample::default();
+        let mut record = |result: Option<anyhow::Result<serde_json::Value>>| match result {
+            Some(Ok(status)) => record_outcome(&mut failures, &status),
+            _ => failures.lookup_errors += 1,
+        };
+        let mut set = tokio::task::JoinSet::new();
+        for tx in txs.iter().filter(|tx| tx.signer.is_some()).take(sample) {
+            let (url, hash, signer) = (url.to_string(), tx.hash.clone(), tx.signer.clone().unwrap_or_default());
+            let token = auth_token.map(str::to_string);
+            set.spawn(async move {
+                crate::rpc_utils::get_tx_status(&url, &hash, &signer, timeout_ms, token.as_deref()).await
+            });
+            if set.len() >= SAMPLE_CONCURRENCY {
+                record(set.join_next().await.and_then(Result::ok));
+            }
+        }
+        while let Some(res) = set.join_next().await {
+            record(res.ok());
+        }
+        digest.failures = Some(failures);
+    }
+    digest
+}
+
+/// `nearx digest <contract>`: print (or write) the markdown digest
+#[cfg(feature = "native")]
+pub async fn run(cfg: &crate::config::Config, args: &crate::config::DigestArgs) -> anyhow::Result<()> {
+    use anyhow::{anyhow, Context};
+
+    let window_secs = crate::query::parse_duration_secs(&args.since)
+        .ok_or_else(|| anyhow!("Invalid --since '{}'. Use e.g. 1h, 24h, 7d", args.since))?;
+    let db_path = cfg.history_db_path();
+    if !std::path::Path::new(&db_path).exists() {
+        return Err(anyhow!("no History at {db_path} (run the TUI or `nearx fetch` first)"));
+    }
+    let history = crate::history::History::start(&db_path)?;
+    let url = cfg.archival_or_rpc_url();
+    let contract = args.contract.to_lowercase();
+    let digest = generate(
+        &history,
+        &url,
+        cfg.rpc_timeout_ms,
+        cfg.fastnear_auth_token.as_deref(),
+        &contract,
+        window_secs,
+        args.sample,
+    )
+    .await;
+
+    let markdown = digest.to_markdown(&cfg.time_display());
+    match &args.out {
+        Some(path) => {
+            std::fs::write(path, &markdown).with_context(|| format!("writing {}", path.display()))?;
+            let now = chrono::Utc::now().timestamp_millis();
+            history
+                .put_export(crate::exports::digest_record(path, &digest, args.sample, now))
+                .await;
+            eprintln!("Wrote digest of {contract} to {}", path.display());
+        }
+        None => print!("{markdown}"),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const NEAR: u128 = 10u128.pow(24);
+
+    fn row(hash: &str, height: u64, signer: &str, actions: String) -> ContractTx {
+        ContractTx {
+            hash: hash.into(),
+            height,
+            ts_ms: 0,
+            signer: Some(signer.into()),
+            actions_json: Some(format!("[{actions}]")),
+        }
+    }
+
+    // Deposits past u64 are plain integers in History (serde_json writes u128 as is)
+    fn call(method: &str, deposit: u128) -> String {
+        format!(
+            r#"{{"type":"FunctionCall","method_name":"{method}","args_decoded":null,"gas":30000000000000,"deposit":{deposit}}}"#
+        )
+    }
+
+    /// Three txs (newest first): two alice, one bob with a 500 NEAR storage_deposit
+    fn digest() -> ContractDigest {
+        let txs = vec![
+            row("c", 12, "alice.near", call("ft_transfer", 1)),
+            row(
+                "b",
+                11,
+                "bob.near",
+                format!("{},{}", call("ft_transfer", 1), call("storage_deposit", 500 * NEAR)),
+            ),
+            row("a", 10, "alice.near", format!(r#"{{"type":"Transfer","deposit":{}}}"#, 2 * NEAR)),
+        ];
+        build("token.near", 86_400, 0, &txs)
+    }
+
+    /// One success and one failed tx (5 + 7 gas burnt)
+    fn sample() -> FailureSample {
+        let mut sample = FailureSample::default();
+        record_outcome(&mut sample, &json!({"status": {"SuccessValue": ""}}));
+        record_outcome(
+            &mut sample,
+            &json!({"status": {"Failure": {"ActionError": {"index": 0, "kind": {"FunctionCallError": {"MethodResolveError": "MethodNotFound"}}}}},
+                    "transaction_outcome": {"outcome": {"gas_burnt": 5}},
+                    "receipts_outcome": [{"outcome": {"gas_burnt": 7}}]}),
+        );
+        sample
+    }
+
+    #[test]
+    fn counts_txs_heights_and_callers() {
+        let d = digest();
+        assert_eq!(d.tx_count, 3);
+        assert_eq!(d.heights, Some((10, 12)));
+        assert_eq!(d.unique_callers, 2);
+        assert_eq!(d.top_callers[0], ("alice.near".to_string(), 2));
+    }
+
+    #[test]
+    fn counts_calls_by_method_with_attached_gas() {
+        let d = digest();
+        assert_eq!(d.calls, 3);
+        assert_eq!(d.top_methods, [("ft_transfer".to_string(), 2), ("storage_deposit".to_string(), 1)]);
+        assert_eq!(d.gas_attached, 90_000_000_000_000);
+    }
+
+    #[test]
+    fn sums_deposits_past_u64_and_lists_large_ones() {
+        let d = digest();
+        assert_eq!(d.deposit_total, 502 * NEAR + 2);
+        assert_eq!(d.large_deposits.len(), 1);
+        assert_eq!(d.large_deposits[0].method.as_deref(), Some("storage_deposit"));
+    }
+
+    #[test]
+    fn empty_window_has_no_heights() {
+        let d = build("token.near", 3_600, 0, &[]);
+        assert_eq!((d.tx_count, d.heights, d.calls), (0, None, 0));
+    }
+
+    #[test]
+    fn failure_sample_counts_failures_reasons_and_burnt_gas() {
+        let sample = sample();
+        assert_eq!((sample.checked, sample.failed, sample.gas_burnt), (2, 1, 12));
+        assert_eq!(sample.reasons, [("Action #0: method not found".to_string(), 1)]);
+    }
+
+    #[test]
+    fn markdown_reports_window_failure_rate_and_methods() {
+        let md = ContractDigest { failures: Some(sample()), ..digest() }.to_markdown(&TimeDisplay::default());
+        assert!(md.starts_with("# token.near — last 24h"));
+        assert!(md.contains("- **Failure rate:** 50% (1 of the newest 2 failed)"));
+        assert!(md.contains("- `ft_transfer` × 2"));
+    }
+
+    #[test]
+    fn windows_cycle_through_the_choices() {
+        assert_eq!(next_window(86_400), 3_600);
+        assert_eq!(next_window(604_800), 86_400);
+        assert_eq!(next_window(42), 86_400);
+        assert_eq!(window_label(604_800), "7d");
+        assert_eq!(window_label(86_400), "24h");
+        assert_eq!(window_label(90), "90s");
+    }
+}
@@ -0,0 +1,287 @@
+//! Diagnostics mode (`nearx doctor`)
+//!
+//! Runs without a UI: probes the configured RPC / WebSocket / archival / GraphQL
+//! endpoints (with latency), validates the History database and looks up the `nearx://`
+//! handler, then prints a report. Exit status is 0 when nothing failed, 1 otherwise.
+
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use rusqlite::{Connection, OpenFlags};
+
+use crate::config::{Config, Source};
+use crate::rpc_utils::{get_block_by_height, get_latest_block};
+
+/// Blocks behind the tip an archival node must still serve (regular RPC nodes
+/// garbage-collect after ~5 epochs, about 216k blocks)
+const ARCHIVAL_PROBE_DEPTH: u64 = 500_000;
+/// Tip older than this is reported as lagging
+const MAX_TIP_LAG: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+    Skip,
+}
+
+impl CheckStatus {
+    fn icon(self) -> &'static str {
+        match self {
+            CheckStatus::Ok => "✓",
+            CheckStatus::Warn => "!",
+            CheckStatus::Fail => "✗",
+            CheckStatus::Skip => "–",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl Check {
+    fn new(name: &'static str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Run every check and print the report. Returns the number of failed checks.
+pub async fn run(cfg: &Config) -> Result<usize> {
+    let db_path = cfg.history_db_path();
+    let checks = vec![
+        check_rpc(cfg).await,
+        check_ws(cfg).await,
+        check_archival(cfg).await,
+        check_graphql(cfg).await,
+        check_history(&db_path),
+        check_deep_links(),
+    ];
+
+    let mut out = std::io::stdout().lock();
+    print_report(cfg, &checks, &mut out)?;
+    Ok(checks.iter().filter(|c| c.status == CheckStatus::Fail).count())
+}
+
+pub fn print_report(cfg: &Config, checks: &[Check], out: &mut impl Write) -> Result<()> {
+    let source = match cfg.source {
+        Source::Ws => "ws",
+        Source::Rpc => "rpc",
+        Source::GraphQl => "graphql",
+    };
+    writeln!(out, "NEARx doctor (nearx {}, source: {source})", env!("CARGO_PKG_VERSION"))?;
+    writeln!(out)?;
+    for c in checks {
+        writeln!(out, "  {} {:<10} {}", c.status.icon(), c.name, c.detail)?;
+    }
+
+    let count = |s: CheckStatus| checks.iter().filter(|c| c.status == s).count();
+    writeln!(out)?;
+    writeln!(
+        out,
+        "{} ok, {} warning(s), {} failed, {} skipped",
+        count(CheckStatus::Ok),
+        count(CheckStatus::Warn),
+        count(CheckStatus::Fail),
+        count(CheckStatus::Skip)
+    )?;
+    Ok(())
+}
+
+fn ms(d: Duration) -> String {
+    format!("{} ms", d.as_millis())
+}
+
+async fn check_rpc(cfg: &Config) -> Check {
+    let url = &cfg.near_node_url;
+    let started = Instant::now();
+    let block = match get_latest_block(url, cfg.rpc_timeout_ms, cfg.fastnear_auth_token.as_deref()).await {
+        Ok(b) => b,
+        Err(e) => return Check::new("RPC", CheckStatus::Fail, format!("{url}  {e:#}")),
+    };
+    let latency = started.elapsed();
+
+    let height = block["header"]["height"].as_u64().unwrap_or_default();
+    let ts_ns = block["header"]["timestamp_nanosec"]
+        .as_str()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or_default();
+    let now_ns = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default() as u64;
+    let lag = Duration::from_nanos(now_ns.saturating_sub(ts_ns));
+
+    let status = if lag > MAX_TIP_LAG {
+        CheckStatus::Warn
+    } else {
+        CheckStatus::Ok
+    };
+    Check::new(
+        "RPC",
+        status,
+        format!(
+            "{url}  {}  final #{height} ({:.1}s behind wall clock)",
+            ms(latency),
+            lag.as_secs_f64()
+        ),
+    )
+}
+
+async fn check_ws(cfg: &Config) -> Check {
+    let url = &cfg.ws_url;
+    let started = Instant::now();
+    let timeout = Duration::from_millis(cfg.rpc_timeout_ms);
+    let err = match tokio::time::timeout(timeout, tokio_tungstenite::connect_async(url.as_str())).await {
+        Ok(Ok((mut ws, _))) => {
+            let latency = started.elapsed();
+            let _ = ws.close(None).await;
+            return Check::new("WebSocket", CheckStatus::Ok, format!("{url}  {} handshake", ms(latency)));
+        }
+        Ok(Err(e)) => e.to_string(),
+        Err(_) => format!("no handshake within {}", ms(timeout)),
+    };
+    // Only fatal when WS is the active block source
+    let status = match cfg.source {
+        Source::Ws => CheckStatus::Fail,
+        Source::Rpc | Source::GraphQl => CheckStatus::Warn,
+    };
+    Check::new("WebSocket", status, format!("{url}  {err}"))
+}
+
+async fn check_archival(cfg: &Config) -> Check {
+    let Some(url) = cfg.archival_rpc_url.as_deref() else {
+        return Check::new("Archival", CheckStatus::Skip, "not configured (ARCHIVAL_RPC_URL)");
+    };
+    let token = cfg.fastnear_auth_token.as_deref();
+    let tip = match get_latest_block(url, cfg.rpc_timeout_ms, token).await {
+        Ok(b) => b["header"]["height"].as_u64().unwrap_or_default(),
+        Err(e) => return Check::new("Archival", CheckStatus::Fail, format!("{url}  {e:#}")),
+    };
+
+    let probe = tip.saturating_sub(ARCHIVAL_PROBE_DEPTH);
+    let started = Instant::now();
+    match get_block_by_height(url, probe, cfg.rpc_timeout_ms, token).await {
+        Ok(_) => Check::new(
+            "Archival",
+            CheckStatus::Ok,
+            format!("{url}  {}  serves #{probe} ({ARCHIVAL_PROBE_DEPTH} blocks back)", ms(started.elapsed())),
+        ),
+        Err(e) => Check::new(
+            "Archival",
+            CheckStatus::Fail,
+            format!("{url}  can't serve #{probe}, not an archival node? ({e:#})"),
+        ),
+    }
+}
+
+/// Run both query templates against the newest height, so a schema mismatch
+/// shows up here with its path rather than as a down badge in the TUI
+async fn check_graphql(cfg: &Config) -> Check {
+    let Some(url) = cfg.graphql_url.as_deref() else {
+        return Check::new("GraphQL", CheckStatus::Skip, "not configured (GRAPHQL_URL)");
+    };
+    let started = Instant::now();
+    let probe = async {
+        let latest = crate::source_graphql::latest_height(cfg).await?;
+        let blocks = crate::source_graphql::fetch_blocks(cfg, latest, latest).await?;
+        anyhow::Ok((latest, blocks))
+    };
+    match probe.await {
+        Ok((latest, blocks)) => {
+            let txs: usize = blocks.iter().map(|b| b.tx_count).sum();
+            let status = if blocks.is_empty() {
+                CheckStatus::Warn
+            } else {
+                CheckStatus::Ok
+            };
+            Check::new(
+                "GraphQL",
+                status,
+                format!(
+                    "{url}  {}  latest #{latest}, blocks query returned {} block(s) / {txs} tx(s)",
+                    ms(started.elapsed()),
+                    blocks.len()
+                ),
+            )
+        }
+        Err(e) => Check::new("GraphQL", CheckStatus::Fail, format!("{url}  {e:#}")),
+    }
+}
+
+fn check_history(db_path: &str) -> Check {
+    if !std::path::Path::new(db_path).exists() {
+        return Check::new(
+            "History",
+            CheckStatus::Warn,
+            format!("{db_path}  not created yet (the TUI creates it on first run)"),
+        );
+    }
+    let inspect = || -> Result<String> {
+        let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let integrity: String = conn.query_row("PRAGMA quick_check", [], |r| r.get(0))?;
+        if integrity != "ok" {
+            anyhow::bail!("quick_check: {integrity}");
+        }
+        let count = |table: &str| -> Result<i64> {
+            Ok(conn.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |r| r.get(0))?)
+        };
+        Ok(format!(
+            "{} blocks, {} txs, {} marks",
+            count("blocks")?,
+            count("txs")?,
+            count("marks")?
+        ))
+    };
+    match inspect() {
+        Ok(summary) => Check::new("History", CheckStatus::Ok, format!("{db_path}  {summary}")),
+        Err(e) => Check::new("History", CheckStatus::Fail, format!("{db_path}  {e:#}")),
+    }
+}
+
+fn check_deep_links() -> Check {
+    match crate::platform::deep_link_handler() {
+        Some(handler) => Check::new("Deep links", CheckStatus::Ok, format!("nearx:// → {handler}")),
+        None => Check::new(
+            "Deep links",
+            CheckStatus::Warn,
+            "nearx:// not registered (install the desktop app to open links from the TUI)",
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_check_reads_counts_and_flags_missing_db() {
+        let dir = std::env::temp_dir().join(format!("nearx-doctor-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("history.db");
+        let path = path.to_str().unwrap();
+
+        assert_eq!(check_history(path).status, CheckStatus::Warn);
+
+        let conn = Connection::open(path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE blocks(height INTEGER PRIMARY KEY);
+             CREATE TABLE txs(hash TEXT PRIMARY KEY);
+             CREATE TABLE marks(label TEXT PRIMARY KEY);
+             INSERT INTO blocks VALUES (1), (2);",
+        )
+        .unwrap();
+        drop(conn);
+        let check = check_history(path);
+        assert_eq!(check.status, CheckStatus::Ok);
+        assert!(check.detail.ends_with("2 blocks, 0 txs, 0 marks"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
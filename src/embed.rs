@@ -0,0 +1,200 @@
+//! Embedder API (`nearx::embed::Explorer`)
+//!
+//! The explorer core (blocks, filter, selection, details, toasts, overlays)
+//! behind a small surface for Rust apps that draw it themselves: build it from
+//! a `Config`, feed it `AppEvent`s, send it `UiAction`s and render the
+//! `UiSnapshot` it hands back. Nothing here touches a terminal or a window, and
+//! debug lines stay in memory (`App::set_debug_log_file` opts into a file).
+//!
+//! Builds without crossterm, SQLite, the clipboard backends or the near-* crates
+//! (ratatui stays, without its terminal backend, for Details' styled lines):
+//!
+//! ```toml
+//! nearx = { version = "0.3", default-features = false, features = ["embed"] }
+//! ```
+//!
+//! ```no_run
+//! # fn main() -> anyhow::Result<()> {
+//! use nearx::embed::Explorer;
+//! use nearx::UiAction;
+//!
+//! let cfg = nearx::config::load_from(["nearx", "--source", "rpc"])?;
+//! let mut explorer = Explorer::new(&cfg);
+//! // Hand `explorer.events()` to a block source (e.g. `nearx::source_rpc`)
+//! let tx = explorer.events();
+//! # drop(tx);
+//! explorer.send(UiAction::SetFilter { text: "receiver:intents.near".into() });
+//! let snapshot = explorer.snapshot();
+//! println!("{} blocks", snapshot.blocks_total);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Side requests the native binary serves (timeline, digests, exports …) stay
+//! on `App` as `take_*_request()`; `app_mut()` reaches them.
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+use crate::app::{App, Endpoints};
+use crate::config::Config;
+use crate::snapshot_cache::SnapshotCache;
+use crate::types::AppEvent;
+use crate::ui_snapshot::{apply_ui_action, UiAction, UiSnapshot};
+
+/// Explorer core for embedding: events in, actions in, snapshots out
+pub struct Explorer {
+    app: App,
+    events_tx: UnboundedSender<AppEvent>,
+    events_rx: UnboundedReceiver<AppEvent>,
+    snapshots: SnapshotCache,
+}
+
+impl Explorer {
+    /// Core configured like the native binary (filter, theme, time display, policies)
+    pub fn new(cfg: &Config) -> Self {
+        let mut app = App::new(
+            cfg.render_fps,
+            cfg.render_fps_choices.clone(),
+            cfg.keep_blocks,
+            cfg.default_filter.clone(),
+            None,
+        );
+        app.set_endpoints(Endpoints {
+            near_node_url: cfg.near_node_url.clone(),
+            archival_rpc_url: cfg.archival_rpc_url.clone(),
+        });
+        app.set_theme(cfg.theme);
+        app.set_json_format(cfg.json_format);
+        app.set_gas_policy(cfg.gas_window_blocks, cfg.gas_spike_multiple);
+        app.set_anomaly_policy(cfg.anomaly_sigma, cfg.anomaly_incidents);
+        app.set_stale_after(cfg.stale_after_secs);
+        app.set_time_display(cfg.time_display());
+        app.set_stake_alert_pp(cfg.stake_alert_pp);
+        if let Some(link) = &cfg.route {
+            app.apply_route_link(link);
+        }
+
+        let (events_tx, events_rx) = unbounded_channel();
+        Self {
+            app,
+            events_tx,
+            events_rx,
+            snapshots: SnapshotCache::default(),
+        }
+    }
+
+    /// Sender for source tasks; queued events are applied on `pump` / `snapshot`
+    pub fn events(&self) -> UnboundedSender<AppEvent> {
+        self.events_tx.clone()
+    }
+
+    /// Apply one event now
+    pub fn feed(&mut self, ev: AppEvent) {
+        self.app.on_event(ev);
+    }
+
+    /// Apply every queued event; returns how many were applied
+    pub fn pump(&mut self) -> usize {
+        let mut applied = 0;
+        while let Ok(ev) = self.events_rx.try_recv() {
+            self.app.on_event(ev);
+            applied += 1;
+        }
+        applied
+    }
+
+    /// Timers (toasts, block window fill, backward chain walk); call every frame or so
+    pub fn tick(&mut self) {
+        self.app.on_tick(Instant::now());
+    }
+
+    /// Apply a user action (same dispatcher as the web and Tauri frontends)
+    pub fn send(&mut self, action: UiAction) {
+        apply_ui_action(&mut self.app, action);
+    }
+
+    /// Pump queued events, then build the snapshot (unchanged rows are reused)
+    pub fn snapshot(&mut self) -> UiSnapshot {
+        self.pump();
+        UiSnapshot::build(&self.app, &mut self.snapshots)
+    }
+
+    /// Rows the host shows in Details (drives paging and scroll clamping)
+    pub fn set_details_viewport_lines(&mut self, lines: usize) {
+        self.app.set_details_viewport_lines(lines);
+    }
+
+    /// Quit was requested (`AppEvent::Quit`)
+    pub fn should_quit(&self) -> bool {
+        self.app.quit_flag()
+    }
+
+    /// Escape hatch: the underlying `App`
+    pub fn app(&self) -> &App {
+        &self.app
+    }
+
+    /// Escape hatch: the underlying `App` (side requests, setters not mirrored here)
+    pub fn app_mut(&mut self) -> &mut App {
+        &mut self.app
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::fixtures::{block, tx};
+    use crate::types::BlockRow;
+
+    fn to(height: u64, receiver: &str) -> BlockRow {
+        block(
+            height,
+            vec![tx(&format!("tx{height}"), "alice.near", receiver)],
+        )
+    }
+
+    #[test]
+    fn queued_events_are_applied_on_snapshot() {
+        let mut explorer = Explorer::new(&Config::default());
+        assert_eq!(explorer.snapshot().blocks_total, 0);
+
+        let tx = explorer.events();
+        tx.send(AppEvent::NewBlock(to(10, "intents.near"))).unwrap();
+        tx.send(AppEvent::NewBlock(to(11, "wrap.near"))).unwrap();
+        assert_eq!(explorer.snapshot().blocks_total, 2);
+        assert_eq!(explorer.pump(), 0);
+
+        explorer.feed(AppEvent::NewBlock(to(12, "wrap.near")));
+        assert_eq!(explorer.snapshot().blocks_total, 3);
+    }
+
+    #[test]
+    fn filter_action_narrows_the_blocks_pane() {
+        let mut explorer = Explorer::new(&Config::default());
+        explorer.feed(AppEvent::NewBlock(to(10, "intents.near")));
+        explorer.feed(AppEvent::NewBlock(to(11, "wrap.near")));
+
+        explorer.send(UiAction::SetFilter {
+            text: "receiver:intents.near".into(),
+        });
+        let snap = explorer.snapshot();
+        assert_eq!(snap.filter_query, "receiver:intents.near");
+        let heights: Vec<u64> = snap.blocks.iter().map(|b| b.height).collect();
+        assert_eq!(heights, vec![10]);
+    }
+
+    #[test]
+    fn quit_event_is_reported_to_the_host() {
+        let mut explorer = Explorer::new(&Config::default());
+        assert!(!explorer.should_quit());
+        explorer.events().send(AppEvent::Quit).unwrap();
+        explorer.pump();
+        assert!(explorer.should_quit());
+    }
+}
@@ -0,0 +1,220 @@
+//! Execution error decoder
+//!
+//! Turns the nested `Failure` JSON of an execution outcome (`ActionError` →
+//! `FunctionCallError` → `HostError` → ...) into a one-line summary such as
+//! `Action #0: panicked: Not enough balance`. The original JSON is kept for
+//! views that can expand it.
+
+use serde_json::Value;
+
+/// Decoded failure of one execution outcome
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecError {
+    pub summary: String,
+    /// The `Failure` payload as returned by the node
+    pub raw: Value,
+}
+
+/// Decode an outcome's `status` (`None` unless it is a `Failure`)
+pub fn decode(status: &Value) -> Option<ExecError> {
+    let raw = status.get("Failure")?;
+    Some(ExecError {
+        summary: summarize(raw),
+        raw: raw.clone(),
+    })
+}
+
+/// One-line summary of a `Failure` payload
+pub fn summarize(failure: &Value) -> String {
+    if let Some(action) = failure.get("ActionError") {
+        let kind = action_error(&action["kind"]);
+        return match action["index"].as_u64() {
+            Some(i) => format!("Action #{i}: {kind}"),
+            None => kind,
+        };
+    }
+    if let Some(tx) = failure.get("InvalidTxError") {
+        return format!("Invalid tx: {}", generic(tx));
+    }
+    generic(failure)
+}
+
+fn action_error(kind: &Value) -> String {
+    if let Some(fc) = kind.get("FunctionCallError") {
+        return function_call_error(fc);
+    }
+    let (variant, fields) = variant(kind);
+    let account = fields["account_id"].as_str().unwrap_or("account");
+    match variant {
+        "AccountDoesNotExist" => format!("account {account} does not exist"),
+        "AccountAlreadyExists" => format!("account {account} already exists"),
+        "LackBalanceForState" => match fields["amount"].as_str() {
+            Some(amount) => format!("{account} lacks balance for storage ({amount} yocto needed)"),
+            None => format!("{account} lacks balance for storage"),
+        },
+        "ActorNoPermission" => format!(
+            "{} may not act on {account}",
+            fields["actor_id"].as_str().unwrap_or("actor")
+        ),
+        _ => generic(kind),
+    }
+}
+
+fn function_call_error(fc: &Value) -> String {
+    // Current nodes flatten most errors into a message string
+    if let Some(msg) = fc.get("ExecutionError").and_then(Value::as_str) {
+        return execution_message(msg);
+    }
+    let (variant, inner) = variant(fc);
+    match variant {
+        "MethodResolveError" => match inner.as_str().unwrap_or_default() {
+            "MethodNotFound" => "method not found".to_string(),
+            "MethodEmptyName" => "empty method name".to_string(),
+            "MethodInvalidSignature" => "invalid method signature".to_string(),
+            other => format!("method resolve error: {}", humanize(other)),
+        },
+        "CompilationError" => match inner.get("CodeDoesNotExist") {
+            Some(c) => format!(
+                "no contract deployed on {}",
+                c["account_id"].as_str().unwrap_or("receiver")
+            ),
+            None => format!("contract compilation failed ({})", generic(inner)),
+        },
+        "HostError" => host_error(inner),
+        "WasmTrap" => format!("wasm trap: {}", generic(inner)),
+        "LinkError" => format!("link error: {}", inner["msg"].as_str().unwrap_or("?")),
+        _ => generic(fc),
+    }
+}
+
+fn host_error(host: &Value) -> String {
+    let (variant, fields) = variant(host);
+    match variant {
+        "GuestPanic" => format!("panicked: {}", fields["panic_msg"].as_str().unwrap_or("?")),
+        "GasExceeded" => "out of gas (prepaid gas used up)".to_string(),
+        "GasLimitExceeded" => "exceeded gas limit (max gas per call)".to_string(),
+        _ => format!("host error: {}", generic(host)),
+    }
+}
+
+/// `ExecutionError` strings, e.g. `Smart contract panicked: panicked at 'msg', src/lib.rs:1:2`
+fn execution_message(msg: &str) -> String {
+    if let Some(rest) = msg.strip_prefix("Smart contract panicked: ") {
+        return format!("panicked: {}", panic_message(rest));
+    }
+    match msg {
+        "Exceeded the prepaid gas." => "out of gas (prepaid gas used up)".to_string(),
+        "Exceeded the maximum amount of gas allowed to burn per contract." => {
+            "exceeded gas limit (max gas per call)".to_string()
+        }
+        _ => msg.trim_end_matches('.').to_string(),
+    }
+}
+
+/// Strip Rust's `panicked at` location wrapper (old and new formats)
+fn panic_message(panic: &str) -> &str {
+    if let Some(rest) = panic.strip_prefix("panicked at '") {
+        return rest.rsplit_once("', ").map_or(rest, |(msg, _)| msg);
+    }
+    if panic.starts_with("panicked at ") {
+        if let Some((_, msg)) = panic.split_once(":\n") {
+            return msg.trim();
+        }
+    }
+    panic
+}
+
+/// Variant name and payload of an externally tagged enum value
+fn variant(v: &Value) -> (&str, &Value) {
+    match v {
+        Value::Object(map) if map.len() == 1 => {
+            let (k, inner) = map.iter().next().expect("one entry");
+            (k.as_str(), inner)
+        }
+        Value::String(s) => (s.as_str(), &Value::Null),
+        _ => ("", &Value::Null),
+    }
+}
+
+/// Fallback: variant names as words, one level of nesting, scalar fields appended
+fn generic(v: &Value) -> String {
+    let (name, inner) = variant(v);
+    if name.is_empty() {
+        return v.to_string();
+    }
+    let mut out = humanize(name);
+    match inner {
+        Value::Object(map) if map.len() == 1 && !map.values().all(Value::is_string) => {
+            out.push_str(": ");
+            out.push_str(&generic(inner));
+        }
+        Value::Object(map) => {
+            let fields: Vec<String> = map
+                .iter()
+                .filter(|(_, v)| !v.is_object() && !v.is_array())
+                .map(|(k, v)| format!("{k}={}", v.as_str().map_or_else(|| v.to_string(), str::to_string)))
+                .collect();
+            if !fields.is_empty() {
+                out.push_str(&format!(" ({})", fields.join(", ")));
+            }
+        }
+        Value::String(s) => {
+            out.push_str(": ");
+            out.push_str(&humanize(s));
+        }
+        _ => {}
+    }
+    out
+}
+
+/// `AccountDoesNotExist` → `account does not exist`
+fn humanize(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 8);
+    for (i, ch) in name.chars().enumerate() {
+        if ch.is_uppercase() && i > 0 {
+            out.push(' ');
+        }
+        out.extend(ch.to_lowercase());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fc(err: Value) -> Value {
+        json!({"Failure": {"ActionError": {"index": 0, "kind": {"FunctionCallError": err}}}})
+    }
+
+    #[test]
+    fn summarizes_common_failures() {
+        let s = |status: Value| decode(&status).unwrap().summary;
+        assert_eq!(
+            s(fc(json!({"MethodResolveError": "MethodNotFound"}))),
+            "Action #0: method not found"
+        );
+        assert_eq!(
+            s(fc(json!({"HostError": {"GuestPanic": {"panic_msg": "ERR_NOT_ENOUGH"}}}))),
+            "Action #0: panicked: ERR_NOT_ENOUGH"
+        );
+        assert_eq!(
+            s(fc(json!({"ExecutionError": "Smart contract panicked: panicked at 'Not enough balance', src/lib.rs:10:5"}))),
+            "Action #0: panicked: Not enough balance"
+        );
+        assert_eq!(
+            s(fc(json!({"ExecutionError": "Exceeded the prepaid gas."}))),
+            "Action #0: out of gas (prepaid gas used up)"
+        );
+        assert_eq!(
+            s(json!({"Failure": {"ActionError": {"index": 1, "kind": {"AccountDoesNotExist": {"account_id": "bob.near"}}}}})),
+            "Action #1: account bob.near does not exist"
+        );
+        assert_eq!(
+            s(json!({"Failure": {"InvalidTxError": {"InvalidNonce": {"tx_nonce": 5, "ak_nonce": 7}}}})),
+            "Invalid tx: invalid nonce (ak_nonce=7, tx_nonce=5)"
+        );
+        assert!(decode(&json!({"SuccessValue": ""})).is_none());
+    }
+}
@@ -0,0 +1,300 @@
+//! Registry of generated exports (pin board / digest / collection markdown, HTML reports)
+//!
+//! Every file NEARx writes is recorded in History with what produced it: the
+//! kind and its parameters, the filter active at the time, the time range it
+//! covers and its row count. The exports overlay (Shift+E) lists them, re-runs
+//! one with the same parameters into a new file next to the original, and
+//! opens the containing folder.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::time_display::TimeDisplay;
+
+/// Records listed in the overlay (newest first)
+pub const LIST_LIMIT: usize = 200;
+
+/// What produced an export, with what re-running it needs
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ExportParams {
+    /// Pin board markdown (re-running exports the current board)
+    PinBoard,
+    /// Contract digest markdown over a window ending at export time
+    Digest {
+        contract: String,
+        window_secs: u64,
+        sample: usize,
+    },
+    /// Standalone HTML report of a block, or of one of its txs
+    Report {
+        height: u64,
+        #[serde(default)]
+        tx_hash: Option<String>,
+    },
+    /// Imported tx collection markdown (re-running re-imports the hashes)
+    Collection { hashes: Vec<String> },
+}
+
+impl ExportParams {
+    pub fn label(&self) -> String {
+        match self {
+            ExportParams::PinBoard => "pin board".to_string(),
+            ExportParams::Digest {
+                contract,
+                window_secs,
+                ..
+            } => format!(
+                "digest {contract} ({})",
+                crate::digest::window_label(*window_secs)
+            ),
+            ExportParams::Report {
+                tx_hash: Some(hash),
+                ..
+            } => format!("report tx {}", short_hash(hash)),
+            ExportParams::Report { height, .. } => format!("report #{height}"),
+            ExportParams::Collection { hashes } => format!("collection ({} txs)", hashes.len()),
+        }
+    }
+
+    /// File name for an export made at `stamp` (`%Y%m%d-%H%M%S`, local time)
+    pub fn file_name(&self, stamp: &str) -> String {
+        match self {
+            ExportParams::PinBoard => format!("nearx-pinboard-{stamp}.md"),
+            ExportParams::Digest { contract, .. } => format!("nearx-digest-{contract}-{stamp}.md"),
+            ExportParams::Report {
+                tx_hash: Some(hash),
+                ..
+            } => format!("nearx-report-tx-{}-{stamp}.html", short_hash(hash)),
+            ExportParams::Report { height, .. } => format!("nearx-report-{height}-{stamp}.html"),
+            ExportParams::Collection { .. } => format!("nearx-collection-{stamp}.md"),
+        }
+    }
+}
+
+fn short_hash(hash: &str) -> &str {
+    hash.get(..8).unwrap_or(hash)
+}
+
+/// One export, as stored in History
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExportRecord {
+    /// History row id (0 until stored)
+    #[serde(default)]
+    pub id: i64,
+    /// Absolute path of the written file
+    pub path: String,
+    pub params: ExportParams,
+    /// Filter query active when the export was made (TUI; `None` when empty)
+    #[serde(default)]
+    pub filter: Option<String>,
+    /// Time range the content covers (unix ms)
+    #[serde(default)]
+    pub range_ms: Option<(i64, i64)>,
+    /// Items / txs in the export
+    pub rows: usize,
+    pub created_ms: i64,
+    /// File no longer on disk (checked when the overlay opens)
+    #[serde(skip)]
+    pub missing: bool,
+}
+
+impl ExportRecord {
+    /// Record a file just written to `path` (made absolute against the working directory)
+    pub fn new(path: &Path, params: ExportParams, rows: usize, created_ms: i64) -> Self {
+        let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+        Self {
+            id: 0,
+            path: path.display().to_string(),
+            params,
+            filter: None,
+            range_ms: None,
+            rows,
+            created_ms,
+            missing: false,
+        }
+    }
+
+    pub fn with_filter(mut self, filter: &str) -> Self {
+        let filter = filter.trim();
+        self.filter = (!filter.is_empty()).then(|| filter.to_string());
+        self
+    }
+
+    pub fn with_range(mut self, from_ms: i64, to_ms: i64) -> Self {
+        self.range_ms = Some((from_ms, to_ms));
+        self
+    }
+
+    /// Folder the file was written to
+    pub fn folder(&self) -> PathBuf {
+        Path::new(&self.path)
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
+    }
+
+    /// Where a re-run writes: same folder, fresh timestamped name
+    pub fn rerun_path(&self, stamp: &str) -> PathBuf {
+        self.folder().join(self.params.file_name(stamp))
+    }
+
+    /// Whether the file is still there
+    pub fn exists(&self) -> bool {
+        Path::new(&self.path).exists()
+    }
+
+    /// `12 rows · 10:00:00 → 11:00:00 · filter signer:alice.near`
+    pub fn details(&self, times: &TimeDisplay) -> String {
+        let mut parts = vec![format!("{} rows", self.rows)];
+        if let Some((from, to)) = self.range_ms {
+            parts.push(format!("{} → {}", times.at(from), times.at(to)));
+        }
+        if let Some(filter) = &self.filter {
+            parts.push(format!("filter {filter}"));
+        }
+        parts.join(" · ")
+    }
+}
+
+/// Record of a digest written to `path`
+pub fn digest_record(
+    path: &Path,
+    digest: &crate::digest::ContractDigest,
+    sample: usize,
+    created_ms: i64,
+) -> ExportRecord {
+    let params = ExportParams::Digest {
+        contract: digest.contract.clone(),
+        window_secs: digest.window_secs,
+        sample,
+    };
+    let from_ms = digest.until_ms - (digest.window_secs as i64) * 1000;
+    ExportRecord::new(path, params, digest.tx_count, created_ms)
+        .with_range(from_ms, digest.until_ms)
+}
+
+/// Local timestamp used in export file names
+pub fn stamp() -> String {
+    chrono::Local::now().format("%Y%m%d-%H%M%S").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STAMP: &str = "20261017-120000";
+
+    fn digest_params() -> ExportParams {
+        ExportParams::Digest {
+            contract: "intents.near".into(),
+            window_secs: 86_400,
+            sample: 25,
+        }
+    }
+
+    fn record() -> ExportRecord {
+        let params = digest_params();
+        ExportRecord::new(Path::new(&params.file_name(STAMP)), params, 42, 1_000)
+    }
+
+    #[test]
+    fn file_names_carry_kind_subject_and_stamp() {
+        let report = ExportParams::Report {
+            height: 123,
+            tx_hash: None,
+        };
+        let tx = ExportParams::Report {
+            height: 123,
+            tx_hash: Some("ABCDEFGHIJK".into()),
+        };
+        let collection = ExportParams::Collection { hashes: vec![] };
+        assert_eq!(digest_params().file_name(STAMP), "nearx-digest-intents.near-20261017-120000.md");
+        assert_eq!(report.file_name(STAMP), "nearx-report-123-20261017-120000.html");
+        assert_eq!(tx.file_name(STAMP), "nearx-report-tx-ABCDEFGH-20261017-120000.html");
+        assert_eq!(collection.file_name(STAMP), "nearx-collection-20261017-120000.md");
+        assert_eq!(ExportParams::PinBoard.file_name(STAMP), "nearx-pinboard-20261017-120000.md");
+    }
+
+    #[test]
+    fn labels_describe_each_kind() {
+        assert_eq!(digest_params().label(), "digest intents.near (24h)");
+        let tx = ExportParams::Report {
+            height: 123,
+            tx_hash: Some("ABCDEFGHIJK".into()),
+        };
+        assert_eq!(tx.label(), "report tx ABCDEFGH");
+        let block = ExportParams::Report {
+            height: 123,
+            tx_hash: None,
+        };
+        assert_eq!(block.label(), "report #123");
+        let collection = ExportParams::Collection {
+            hashes: vec!["A".into(), "B".into()],
+        };
+        assert_eq!(collection.label(), "collection (2 txs)");
+    }
+
+    #[test]
+    fn records_store_an_absolute_path() {
+        assert!(Path::new(&record().path).is_absolute());
+    }
+
+    #[test]
+    fn blank_filter_is_not_recorded() {
+        assert_eq!(record().with_filter("  ").filter, None);
+        let filtered = record().with_filter(" method:ft_transfer ");
+        assert_eq!(filtered.filter.as_deref(), Some("method:ft_transfer"));
+        assert_eq!(
+            filtered.details(&TimeDisplay::default()),
+            "42 rows · filter method:ft_transfer"
+        );
+    }
+
+    #[test]
+    fn rerun_writes_next_to_the_original() {
+        let record = record();
+        assert_eq!(
+            record.rerun_path("20261018-090000"),
+            record
+                .folder()
+                .join("nearx-digest-intents.near-20261018-090000.md")
+        );
+    }
+
+    #[test]
+    fn records_round_trip_through_json() {
+        let record = record()
+            .with_filter("method:ft_transfer")
+            .with_range(0, 1_000);
+        let back: ExportRecord =
+            serde_json::from_str(&serde_json::to_string(&record).unwrap()).unwrap();
+        assert_eq!(back, record);
+    }
+
+    #[test]
+    fn params_parse_with_optional_fields_left_out() {
+        assert_eq!(
+            serde_json::from_str::<ExportParams>(r#"{"kind":"pin_board"}"#).unwrap(),
+            ExportParams::PinBoard
+        );
+        let report: ExportParams = serde_json::from_str(r#"{"kind":"report","height":123}"#).unwrap();
+        assert_eq!(
+            report,
+            ExportParams::Report {
+                height: 123,
+                tx_hash: None
+            }
+        );
+    }
+
+    #[test]
+    fn digest_record_covers_the_digest_window() {
+        let digest = crate::digest::build("intents.near", 3_600, 7_200_000, &[]);
+        let record = digest_record(Path::new("digest.md"), &digest, 25, 9);
+        assert_eq!(record.range_ms, Some((3_600_000, 7_200_000)));
+        assert_eq!((record.rows, record.created_ms), (0, 9));
+        assert_eq!(record.params.label(), "digest intents.near (1h)");
+    }
+}
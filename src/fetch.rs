@@ -0,0 +1,190 @@
+//! Block range download (`nearx fetch FROM..TO`)
+//!
+//! Downloads blocks (with their txs) into History, oldest first. Progress is
+//! recorded in History after every block, so an interrupted run (quit, crash,
+//! Ctrl+C) continues with `nearx fetch --resume` instead of starting over; the
+//! TUI offers that on its next start. Resuming first re-checks the heights
+//! already downloaded against the node by block hash and re-fetches any that
+//! differ or are missing.
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::config::{Config, FetchArgs};
+use crate::history::{BlockPersist, FetchJob, History};
+use crate::rpc_utils::{fetch_block_with_txs, get_block_by_height};
+
+/// Progress line on stderr every N heights
+const PROGRESS_EVERY: u64 = 100;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FetchStats {
+    pub fetched: u64,
+    /// Heights without a block (skipped by the chain)
+    pub empty: u64,
+    /// Already-downloaded heights whose stored hash matched the node
+    pub verified: u64,
+    /// Already-downloaded heights re-fetched (hash mismatch or missing row)
+    pub repaired: u64,
+}
+
+/// `FROM..TO` or `FROM..=TO`, inclusive either way
+pub fn parse_range(s: &str) -> Result<(u64, u64)> {
+    let (from, to) = s
+        .split_once("..=")
+        .or_else(|| s.split_once(".."))
+        .ok_or_else(|| anyhow!("Invalid range '{s}'. Use FROM..TO, e.g. 120000000..120000500"))?;
+    let parse = |v: &str| {
+        v.trim()
+            .trim_start_matches('#')
+            .parse::<u64>()
+            .map_err(|_| anyhow!("Invalid height '{v}' in range '{s}'"))
+    };
+    let (from, to) = (parse(from)?, parse(to)?);
+    if from > to {
+        bail!("Range '{s}' is empty (FROM must be <= TO)");
+    }
+    Ok((from, to))
+}
+
+/// Start-up notice for an unfinished job
+pub fn resume_hint(job: &FetchJob) -> String {
+    let total = job.to - job.from + 1;
+    let done = job.next.saturating_sub(job.from).min(total);
+    format!(
+        "Interrupted fetch #{}..#{} ({done}/{total} blocks) · resume with `nearx fetch --resume`",
+        job.from, job.to
+    )
+}
+
+/// Download and store one height; `false` when the chain skipped it
+async fn fetch_one(cfg: &Config, url: &str, history: &History, height: u64) -> Result<bool> {
+    let token = cfg.fastnear_auth_token.as_deref();
+    match fetch_block_with_txs(url, height, cfg.rpc_timeout_ms, cfg.poll_chunk_concurrency, token).await {
+        Ok(block) => {
+            history.persist_block(BlockPersist::from_block(&block));
+            Ok(true)
+        }
+        // Skipped heights are normal; anything else (timeouts, auth) stops the run
+        Err(e) if format!("{e:#}").contains("UNKNOWN_BLOCK") => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Re-check heights `job.from..job.next` against the node, re-fetching
+/// stale or missing rows
+async fn verify_downloaded(
+    cfg: &Config,
+    url: &str,
+    history: &History,
+    job: &FetchJob,
+    stats: &mut FetchStats,
+) -> Result<()> {
+    if job.next <= job.from {
+        return Ok(());
+    }
+    let last = job.next - 1;
+    let stored: std::collections::HashMap<u64, String> =
+        history.block_hashes(job.from, last).await.into_iter().collect();
+    eprintln!(
+        "nearx fetch: verifying {} downloaded blocks in #{}..#{last}",
+        stored.len(),
+        job.from
+    );
+
+    let token = cfg.fastnear_auth_token.as_deref();
+    for height in job.from..=last {
+        let matches = match stored.get(&height) {
+            Some(hash) => {
+                let header = get_block_by_height(url, height, cfg.rpc_timeout_ms, token).await?;
+                header["header"]["hash"].as_str() == Some(hash.as_str())
+            }
+            None => false,
+        };
+        if matches {
+            stats.verified += 1;
+        } else if fetch_one(cfg, url, history, height).await? {
+            stats.repaired += 1;
+        } else {
+            stats.empty += 1;
+        }
+    }
+    Ok(())
+}
+
+/// Run a new range download, or resume the recorded one
+pub async fn run(cfg: &Config, args: &FetchArgs) -> Result<FetchStats> {
+    let db_path = cfg.history_db_path();
+    let history = History::start(&db_path)?;
+    let url = cfg.archival_or_rpc_url();
+
+    let mut stats = FetchStats::default();
+    let mut job = match (&args.range, history.fetch_job().await) {
+        (None, Some(job)) => {
+            eprintln!("nearx fetch: resuming #{}..#{} at #{}", job.from, job.to, job.next);
+            verify_downloaded(cfg, &url, &history, &job, &mut stats).await?;
+            job
+        }
+        (None, None) => bail!("no interrupted fetch recorded in {db_path}"),
+        (Some(range), previous) => {
+            if let Some(prev) = previous {
+                eprintln!(
+                    "nearx fetch: discarding unfinished #{}..#{} (was at #{})",
+                    prev.from, prev.to, prev.next
+                );
+            }
+            let (from, to) = parse_range(range)?;
+            let job = FetchJob {
+                from,
+                to,
+                next: from,
+                started_ms: chrono::Utc::now().timestamp_millis(),
+            };
+            history.set_fetch_job(Some(job.clone())).await;
+            job
+        }
+    };
+
+    while job.next <= job.to {
+        if fetch_one(cfg, &url, &history, job.next).await? {
+            stats.fetched += 1;
+        } else {
+            stats.empty += 1;
+        }
+        job.next += 1;
+        history.set_fetch_job(Some(job.clone())).await;
+        if (job.next - job.from) % PROGRESS_EVERY == 0 {
+            eprintln!("nearx fetch: #{} / #{}", job.next - 1, job.to);
+        }
+    }
+
+    history.set_fetch_job(None).await;
+    println!(
+        "Fetched #{}..#{}: {} blocks, {} empty heights, {} verified, {} re-fetched",
+        job.from, job.to, stats.fetched, stats.empty, stats.verified, stats.repaired
+    );
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_ranges_and_hints_progress() {
+        assert_eq!(parse_range("100..200").unwrap(), (100, 200));
+        assert_eq!(parse_range("#100..=#100").unwrap(), (100, 100));
+        assert!(parse_range("200..100").is_err());
+        assert!(parse_range("100-200").is_err());
+
+        let job = FetchJob {
+            from: 100,
+            to: 199,
+            next: 150,
+            started_ms: 0,
+        };
+        assert_eq!(
+            resume_hint(&job),
+            "Interrupted fetch #100..#199 (50/100 blocks) · resume with `nearx fetch --resume`"
+        );
+    }
+}
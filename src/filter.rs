@@ -1,3 +1,7 @@
+use crate::types::{ActionSummary, BlockRow, TxLite};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
 #[derive(Default, Debug, Clone)]
 pub struct CompiledFilter {
     pub signer: Vec<String>,
@@ -8,22 +12,114 @@ pub struct CompiledFilter {
     pub raw: Vec<String>,
     pub hash: Vec<String>,
     pub free: Vec<String>,
+    /// Bridge legs (`bridge:any`, `bridge:in`, `bridge:wormhole`, `bridge:ethereum`, ...)
+    pub bridge: Vec<String>,
+    /// Negated terms (`-signer:spam.near`, `!method:ping`): a tx matching ANY is hidden
+    pub exclude: Vec<(FilterField, String)>,
+    /// Owned-accounts restriction (Ctrl+U, lowercase): signer or receiver must be
+    /// exactly one of these. Not part of the query text; set by the app.
+    pub owned: Vec<String>,
+}
+
+impl CompiledFilter {
+    /// Fields with at least one positive term, in declaration order
+    pub fn fields_used(&self) -> Vec<FilterField> {
+        [
+            (FilterField::Signer, &self.signer),
+            (FilterField::Receiver, &self.receiver),
+            (FilterField::Acct, &self.acct),
+            (FilterField::Action, &self.action),
+            (FilterField::Method, &self.method),
+            (FilterField::Raw, &self.raw),
+            (FilterField::Hash, &self.hash),
+            (FilterField::Free, &self.free),
+            (FilterField::Bridge, &self.bridge),
+        ]
+        .into_iter()
+        .filter(|(_, terms)| !terms.is_empty())
+        .map(|(field, _)| field)
+        .collect()
+    }
+}
+
+/// Filter keys (shared by the parser and `UiAction::ExcludeSelected`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum FilterField {
+    Signer,
+    Receiver,
+    Acct,
+    Action,
+    Method,
+    Raw,
+    Hash,
+    Free,
+    Bridge,
+}
+
+impl FilterField {
+    /// Canonical query key (`signer`, `receiver`, ...)
+    pub fn key(self) -> &'static str {
+        match self {
+            FilterField::Signer => "signer",
+            FilterField::Receiver => "receiver",
+            FilterField::Acct => "acct",
+            FilterField::Action => "action",
+            FilterField::Method => "method",
+            FilterField::Raw => "raw",
+            FilterField::Hash => "hash",
+            FilterField::Free => "free",
+            FilterField::Bridge => "bridge",
+        }
+    }
+
+    fn from_key(k: &str) -> Option<Self> {
+        match &*k.to_lowercase() {
+            "acct" | "account" => Some(FilterField::Acct),
+            "signer" => Some(FilterField::Signer),
+            "receiver" | "rcv" => Some(FilterField::Receiver),
+            "action" => Some(FilterField::Action),
+            "method" => Some(FilterField::Method),
+            "raw" => Some(FilterField::Raw),
+            "hash" | "tx" | "txn" | "transaction" => Some(FilterField::Hash),
+            "bridge" => Some(FilterField::Bridge),
+            _ => None,
+        }
+    }
 }
 
 pub fn compile_filter(q: &str) -> CompiledFilter {
     let mut f = CompiledFilter::default();
     for tok in q.split_whitespace() {
+        // Leading '-' or '!' negates the term
+        let (negated, tok) = match tok.strip_prefix('-').or_else(|| tok.strip_prefix('!')) {
+            Some(rest) if !rest.is_empty() => (true, rest),
+            _ => (false, tok),
+        };
+
         let mut it = tok.splitn(2, ':');
-        if let (Some(k), Some(v)) = (it.next(), it.next()) {
-            push(&mut f, k, v);
+        let terms: Vec<(FilterField, String)> = if let (Some(k), Some(v)) = (it.next(), it.next()) {
+            parse_term(k, v)
         } else if !tok.is_empty() {
             // Smart auto-detection for bare tokens
-            if is_likely_hash(tok) {
-                f.hash.push(tok.to_lowercase());
+            let field = if is_likely_hash(tok) {
+                FilterField::Hash
             } else if is_likely_account(tok) {
-                f.acct.push(tok.to_lowercase());
+                FilterField::Acct
             } else {
-                f.free.push(tok.to_lowercase());
+                FilterField::Free
+            };
+            vec![(field, tok.to_lowercase())]
+        } else {
+            Vec::new()
+        };
+
+        if negated {
+            f.exclude.extend(terms);
+        } else {
+            for (field, v) in terms {
+                values_mut(&mut f, field).push(v);
             }
         }
     }
@@ -44,25 +140,30 @@ fn is_likely_account(tok: &str) -> bool {
     (tok.len() == 64 && tok.chars().all(|c| c.is_ascii_hexdigit()))
 }
 
-fn push(f: &mut CompiledFilter, k: &str, v: &str) {
+fn parse_term(k: &str, v: &str) -> Vec<(FilterField, String)> {
     // Split comma-separated values (comma = OR logic)
-    let values: Vec<String> = v
+    let values = v
         .split(',')
         .map(|s| s.trim().to_lowercase())
-        .filter(|s| !s.is_empty())
-        .collect();
+        .filter(|s| !s.is_empty());
+
+    match FilterField::from_key(k) {
+        Some(field) => values.map(|v| (field, v)).collect(),
+        None => values.map(|v| (FilterField::Free, format!("{k}:{v}"))).collect(),
+    }
+}
 
-    match &*k.to_lowercase() {
-        "acct" | "account" => f.acct.extend(values),
-        "signer" => f.signer.extend(values),
-        "receiver" | "rcv" => f.receiver.extend(values),
-        "action" => f.action.extend(values),
-        "method" => f.method.extend(values),
-        "raw" => f.raw.extend(values),
-        "hash" | "tx" | "txn" | "transaction" => f.hash.extend(values),
-        _ => f
-            .free
-            .extend(values.into_iter().map(|v| format!("{k}:{v}"))),
+fn values_mut(f: &mut CompiledFilter, field: FilterField) -> &mut Vec<String> {
+    match field {
+        FilterField::Signer => &mut f.signer,
+        FilterField::Receiver => &mut f.receiver,
+        FilterField::Acct => &mut f.acct,
+        FilterField::Action => &mut f.action,
+        FilterField::Method => &mut f.method,
+        FilterField::Raw => &mut f.raw,
+        FilterField::Hash => &mut f.hash,
+        FilterField::Free => &mut f.free,
+        FilterField::Bridge => &mut f.bridge,
     }
 }
 
@@ -70,47 +171,161 @@ pub fn tx_matches_filter(tx: &serde_json::Value, f: &CompiledFilter) -> bool {
     if is_empty(f) {
         return true;
     }
+    haystack_matches(&TxHaystack::from_json(tx), f)
+}
 
-    let signer = tx
-        .pointer("/signer_id")
-        .and_then(|v| v.as_str())
-        .unwrap_or("")
-        .to_lowercase();
-    let receiver = tx
-        .pointer("/receiver_id")
-        .and_then(|v| v.as_str())
-        .unwrap_or("")
-        .to_lowercase();
-    let hash = tx
-        .pointer("/hash")
-        .and_then(|v| v.as_str())
-        .unwrap_or("")
-        .to_lowercase();
-
-    let actions = tx
-        .pointer("/actions")
-        .and_then(|v| v.as_array())
-        .cloned()
-        .unwrap_or_default();
-    let action_types: Vec<String> = actions
-        .iter()
-        .map(|a| {
-            a.as_object()
-                .and_then(|o| o.keys().next().cloned())
-                .unwrap_or_default()
-                .to_lowercase()
-        })
-        .collect();
-    let methods: Vec<String> = actions
-        .iter()
-        .filter_map(|a| {
-            a.pointer("/FunctionCall/method_name")
+/// Sets at least this large are evaluated in parallel (native); below it the
+/// thread-pool hand-off costs more than it saves
+pub const PARALLEL_MIN_TXS: usize = 256;
+
+/// `tx_matches_filter` for each tx, in input order
+pub fn txs_matching_filter(txs: &[serde_json::Value], f: &CompiledFilter) -> Vec<bool> {
+    if is_empty(f) {
+        return vec![true; txs.len()];
+    }
+    par_map(txs, |tx| haystack_matches(&TxHaystack::from_json(tx), f))
+}
+
+/// `haystack_matches` for each haystack, in input order
+pub fn haystacks_matching(haystacks: &[TxHaystack], f: &CompiledFilter) -> Vec<bool> {
+    par_map(haystacks, |h| haystack_matches(h, f))
+}
+
+/// Order-preserving map; rayon on native for sets of `PARALLEL_MIN_TXS` or more
+fn par_map<T: Sync, R: Send>(items: &[T], op: impl Fn(&T) -> R + Sync + Send) -> Vec<R> {
+    #[cfg(feature = "native")]
+    if items.len() >= PARALLEL_MIN_TXS {
+        use rayon::prelude::*;
+        return items.par_iter().map(op).collect();
+    }
+    items.iter().map(op).collect()
+}
+
+/// Lowercased per-transaction search fields, built once and reused for every
+/// filter evaluation (avoids re-serializing and re-lowercasing on each frame).
+#[derive(Debug, Clone, Default)]
+pub struct TxHaystack {
+    signer: String,
+    receiver: String,
+    hash: String,
+    action_types: Vec<String>,
+    methods: Vec<String>,
+    raw: String,
+    /// `BridgeTransfer::filter_words` (empty when not a bridge tx)
+    bridge: String,
+}
+
+impl TxHaystack {
+    /// Build from a JSON transaction (`signer_id`, `receiver_id`, `hash`, `actions`).
+    /// Actions may be RPC-shaped (`{"FunctionCall": {...}}`) or stored `ActionSummary`s
+    /// (`{"type": "FunctionCall", "method_name": ...}`).
+    pub fn from_json(tx: &serde_json::Value) -> Self {
+        let field = |p: &str| {
+            tx.pointer(p)
                 .and_then(|v| v.as_str())
-                .map(|s| s.to_lowercase())
-        })
-        .collect();
+                .unwrap_or("")
+                .to_lowercase()
+        };
+        let actions = tx
+            .pointer("/actions")
+            .and_then(|v| v.as_array())
+            .map(|a| a.as_slice())
+            .unwrap_or_default();
+        let action_types = actions.iter().map(json_action_type).collect();
+        let methods = actions
+            .iter()
+            .filter_map(|a| {
+                a.pointer("/FunctionCall/method_name")
+                    .or_else(|| a.get("method_name"))
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_lowercase())
+            })
+            .collect();
+
+        Self {
+            signer: field("/signer_id"),
+            receiver: field("/receiver_id"),
+            hash: field("/hash"),
+            action_types,
+            methods,
+            raw: tx.to_string().to_lowercase(),
+            bridge: crate::bridge::detect_json(tx)
+                .map(|b| b.filter_words())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Build from a `TxLite` (includes action types and method names)
+    pub fn from_tx(tx: &TxLite) -> Self {
+        let mut action_types = Vec::new();
+        let mut methods = Vec::new();
+        if let Some(actions) = tx.actions.as_ref() {
+            collect_actions(actions, &mut action_types, &mut methods);
+        }
+        let signer = tx.signer_id.as_deref().unwrap_or("").to_lowercase();
+        let receiver = tx.receiver_id.as_deref().unwrap_or("").to_lowercase();
+        let hash = tx.hash.to_lowercase();
+        let raw = serde_json::to_string(tx)
+            .map(|s| s.to_lowercase())
+            .unwrap_or_else(|_| format!("{hash} {signer} {receiver}"));
+
+        Self {
+            signer,
+            receiver,
+            hash,
+            action_types,
+            methods,
+            raw,
+            bridge: crate::bridge::detect(tx)
+                .map(|b| b.filter_words())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Lowercased action type of a JSON action (`type` tag, else the RPC enum key)
+fn json_action_type(a: &serde_json::Value) -> String {
+    a.get("type")
+        .and_then(|t| t.as_str())
+        .map(str::to_string)
+        .or_else(|| a.as_object().and_then(|o| o.keys().next().cloned()))
+        .unwrap_or_default()
+        .to_lowercase()
+}
 
-    let raw = tx.to_string().to_lowercase();
+/// Lowercased action type name as matched by `action:` terms
+pub fn action_type_name(a: &ActionSummary) -> &'static str {
+    match a {
+        ActionSummary::CreateAccount => "createaccount",
+        ActionSummary::DeployContract { .. } => "deploycontract",
+        ActionSummary::FunctionCall { .. } => "functioncall",
+        ActionSummary::Transfer { .. } => "transfer",
+        ActionSummary::Stake { .. } => "stake",
+        ActionSummary::AddKey { .. } => "addkey",
+        ActionSummary::DeleteKey { .. } => "deletekey",
+        ActionSummary::DeleteAccount { .. } => "deleteaccount",
+        ActionSummary::Delegate { .. } => "delegate",
+    }
+}
+
+fn collect_actions(actions: &[ActionSummary], types: &mut Vec<String>, methods: &mut Vec<String>) {
+    for a in actions {
+        match a {
+            ActionSummary::FunctionCall { method_name, .. } => {
+                methods.push(method_name.to_lowercase());
+            }
+            ActionSummary::Delegate { actions, .. } => collect_actions(actions, types, methods),
+            _ => {}
+        }
+        types.push(action_type_name(a).to_string());
+    }
+}
+
+/// Evaluate a compiled filter against a precomputed haystack
+pub fn haystack_matches(h: &TxHaystack, f: &CompiledFilter) -> bool {
+    if is_empty(f) {
+        return true;
+    }
 
     let any = |vals: &[String], hay: &str| vals.is_empty() || vals.iter().any(|v| hay.contains(v));
     let any_in = |vals: &[String], arr: &[String]| {
@@ -118,37 +333,224 @@ pub fn tx_matches_filter(tx: &serde_json::Value, f: &CompiledFilter) -> bool {
     };
 
     // acct matches signer OR receiver
-    if !(any(&f.acct, &signer) || any(&f.acct, &receiver)) {
+    if !(any(&f.acct, &h.signer) || any(&f.acct, &h.receiver)) {
         return false;
     }
-    if !any(&f.signer, &signer) {
+    if !any(&f.signer, &h.signer) {
         return false;
     }
-    if !any(&f.receiver, &receiver) {
+    if !any(&f.receiver, &h.receiver) {
         return false;
     }
-    if !any_in(&f.action, &action_types) {
+    if !any_in(&f.action, &h.action_types) {
         return false;
     }
-    if !any_in(&f.method, &methods) {
+    if !any_in(&f.method, &h.methods) {
         return false;
     }
-    if !any(&f.raw, &raw) {
+    if !any(&f.raw, &h.raw) {
         return false;
     }
     // hash field check (exact match on full hash)
-    if !any(&f.hash, &hash) {
+    if !any(&f.hash, &h.hash) {
+        return false;
+    }
+
+    if !f.bridge.is_empty() && !f.bridge.iter().any(|v| field_matches(h, FilterField::Bridge, v)) {
         return false;
     }
 
     // free text matches signer/receiver/hash/methods
-    if !f.free.is_empty() {
-        let hay = [signer, receiver, hash, methods.join(" ")].join(" ");
-        if !f.free.iter().any(|v| hay.contains(v)) {
-            return false;
+    if !f.free.is_empty() && !f.free.iter().any(|v| field_matches(h, FilterField::Free, v)) {
+        return false;
+    }
+
+    if !f.owned.is_empty() && !f.owned.iter().any(|a| h.signer == *a || h.receiver == *a) {
+        return false;
+    }
+
+    // Exclusions win over everything else
+    !is_excluded(h, f)
+}
+
+/// Whether any negated term (`-key:value`) matches the haystack
+pub fn is_excluded(h: &TxHaystack, f: &CompiledFilter) -> bool {
+    f.exclude.iter().any(|(field, v)| field_matches(h, *field, v))
+}
+
+/// Whether one field of the haystack contains `v`
+fn field_matches(h: &TxHaystack, field: FilterField, v: &str) -> bool {
+    match field {
+        FilterField::Signer => h.signer.contains(v),
+        FilterField::Receiver => h.receiver.contains(v),
+        FilterField::Acct => h.signer.contains(v) || h.receiver.contains(v),
+        FilterField::Action => h.action_types.iter().any(|a| a.contains(v)),
+        FilterField::Method => h.methods.iter().any(|m| m.contains(v)),
+        FilterField::Raw => h.raw.contains(v),
+        FilterField::Hash => h.hash.contains(v),
+        // `any`/`*` = any bridge leg, otherwise a word prefix (`eth` → ethereum)
+        FilterField::Bridge => {
+            !h.bridge.is_empty()
+                && (v == "any" || v == "*" || h.bridge.split(' ').any(|w| w.starts_with(v)))
+        }
+        FilterField::Free => {
+            h.signer.contains(v)
+                || h.receiver.contains(v)
+                || h.hash.contains(v)
+                || h.methods.iter().any(|m| m.contains(v))
         }
     }
-    true
+}
+
+// ---------- Budgeted evaluation cache ----------
+
+/// Maximum haystack builds + filter evaluations per tick. Keeps pathological
+/// filters (huge OR lists over thousands of txs) from stalling a frame.
+pub const FILTER_EVAL_BUDGET_PER_TICK: usize = 2_000;
+
+/// Per-block filter results with stale-while-revalidate semantics.
+///
+/// Haystacks are built once per block. When the filter changes, existing
+/// counts are kept (stale) and recomputed incrementally under a per-tick
+/// budget, so the UI keeps rendering the previous answer until the new one
+/// is ready. Revalidation runs on the UI thread from `on_tick` (the budget
+/// bounds each tick), so it works the same on native and WASM.
+#[derive(Debug, Default)]
+pub struct FilterCache {
+    haystacks: HashMap<u64, Vec<TxHaystack>>,
+    /// height -> (generation the count was computed for, matching tx count)
+    counts: HashMap<u64, (u64, usize)>,
+    generation: u64,
+}
+
+impl FilterCache {
+    /// Mark all counts stale (call when the filter changes)
+    pub fn invalidate(&mut self) {
+        self.generation += 1;
+    }
+
+    /// Last known matching-tx count for a block (may be stale)
+    pub fn count(&self, height: u64) -> Option<usize> {
+        self.counts.get(&height).map(|&(_, c)| c)
+    }
+
+    /// Whether a block's count is missing or from an older filter
+    pub fn is_stale(&self, height: u64) -> bool {
+        self.counts
+            .get(&height)
+            .is_none_or(|&(gen, _)| gen != self.generation)
+    }
+
+    /// Evaluate one tx of an indexed block (None if the block isn't indexed yet)
+    pub fn tx_matches(&self, height: u64, idx: usize, f: &CompiledFilter) -> Option<bool> {
+        self.haystacks
+            .get(&height)
+            .and_then(|h| h.get(idx))
+            .map(|h| haystack_matches(h, f))
+    }
+
+    /// Evaluate every tx of a block, in order (indexed blocks use the cached haystacks)
+    pub fn block_matches(&self, block: &BlockRow, f: &CompiledFilter) -> Vec<bool> {
+        match self.haystacks.get(&block.height) {
+            Some(hay) if hay.len() == block.transactions.len() => haystacks_matching(hay, f),
+            _ => par_map(&block.transactions, |tx| haystack_matches(&TxHaystack::from_tx(tx), f)),
+        }
+    }
+
+    /// Forget a block (its contents may have been replaced)
+    pub fn forget(&mut self, height: u64) {
+        self.haystacks.remove(&height);
+        self.counts.remove(&height);
+    }
+
+    /// Drop entries for blocks that are no longer held
+    pub fn retain_heights(&mut self, keep: &std::collections::HashSet<u64>) {
+        self.haystacks.retain(|h, _| keep.contains(h));
+        self.counts.retain(|h, _| keep.contains(h));
+    }
+
+    /// Number of blocks (among `blocks`) whose counts are stale
+    pub fn pending<'a>(&self, blocks: impl IntoIterator<Item = &'a BlockRow>) -> usize {
+        blocks
+            .into_iter()
+            .filter(|b| self.is_stale(b.height))
+            .count()
+    }
+
+    /// Revalidate stale blocks in order until `budget` tx evaluations are spent.
+    /// Returns the number of evaluations used.
+    pub fn revalidate<'a>(
+        &mut self,
+        blocks: impl IntoIterator<Item = &'a BlockRow>,
+        f: &CompiledFilter,
+        budget: usize,
+    ) -> usize {
+        let mut spent = 0;
+        for block in blocks {
+            if spent >= budget {
+                break;
+            }
+            if !self.is_stale(block.height) {
+                continue;
+            }
+            let hay = self.haystacks.entry(block.height).or_insert_with(|| {
+                spent += block.transactions.len();
+                par_map(&block.transactions, TxHaystack::from_tx)
+            });
+            let count = haystacks_matching(hay, f).into_iter().filter(|m| *m).count();
+            spent += hay.len();
+            self.counts.insert(block.height, (self.generation, count));
+        }
+        spent
+    }
+
+    /// Up to `n` matching and `n` non-matching txs as (height, index), in block
+    /// order, stopping once both are full or `budget` evaluations are spent
+    pub fn sample<'a>(
+        &self,
+        blocks: impl IntoIterator<Item = &'a BlockRow>,
+        f: &CompiledFilter,
+        n: usize,
+        budget: usize,
+    ) -> FilterSamples {
+        let mut out = FilterSamples::default();
+        'blocks: for block in blocks {
+            let cached = self
+                .haystacks
+                .get(&block.height)
+                .filter(|h| h.len() == block.transactions.len());
+            for (idx, tx) in block.transactions.iter().enumerate() {
+                if out.matching.len() >= n && out.other.len() >= n {
+                    break 'blocks;
+                }
+                if out.scanned >= budget {
+                    out.truncated = true;
+                    break 'blocks;
+                }
+                let hit = match cached {
+                    Some(hay) => haystack_matches(&hay[idx], f),
+                    None => haystack_matches(&TxHaystack::from_tx(tx), f),
+                };
+                out.scanned += 1;
+                let bucket = if hit { &mut out.matching } else { &mut out.other };
+                if bucket.len() < n {
+                    bucket.push((block.height, idx));
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Result of `FilterCache::sample` (examples for the filter preview panel)
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FilterSamples {
+    pub matching: Vec<(u64, usize)>,
+    pub other: Vec<(u64, usize)>,
+    /// Transactions evaluated
+    pub scanned: usize,
+    /// Stopped at the budget before both lists were full
+    pub truncated: bool,
 }
 
 pub fn is_empty(f: &CompiledFilter) -> bool {
@@ -160,4 +562,191 @@ pub fn is_empty(f: &CompiledFilter) -> bool {
         && f.raw.is_empty()
         && f.hash.is_empty()
         && f.free.is_empty()
+        && f.bridge.is_empty()
+        && f.exclude.is_empty()
+        && f.owned.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::types::fixtures::tx;
+
+    /// Block of txs from `signers` to intents.near
+    fn block(height: u64, signers: &[&str]) -> BlockRow {
+        let txs = signers
+            .iter()
+            .enumerate()
+            .map(|(i, s)| tx(&format!("tx{height}_{i}"), s, "intents.near"))
+            .collect();
+        crate::types::fixtures::block(height, txs)
+    }
+
+    #[test]
+    fn haystack_matches_like_json_path() {
+        let tx = block(1, &["Alice.near"]).transactions.remove(0);
+        let f = compile_filter("signer:alice");
+        let v = serde_json::json!({"hash": tx.hash, "signer_id": "Alice.near", "receiver_id": "intents.near"});
+        assert!(tx_matches_filter(&v, &f));
+        assert!(haystack_matches(&TxHaystack::from_tx(&tx), &f));
+        assert!(!haystack_matches(&TxHaystack::from_tx(&tx), &compile_filter("signer:bob")));
+    }
+
+    #[test]
+    fn txlite_haystack_covers_actions_and_serialized_tx() {
+        let mut tx = block(1, &["alice.near"]).transactions.remove(0);
+        tx.nonce = Some(77);
+        tx.actions = Some(vec![ActionSummary::Delegate {
+            sender_id: "alice.near".into(),
+            receiver_id: "relayer.near".into(),
+            actions: vec![ActionSummary::FunctionCall {
+                method_name: "ft_transfer".into(),
+                _args_base64: String::new(),
+                args_decoded: crate::near_args::DecodedArgs::Json(
+                    serde_json::json!({"memo": "Invoice-42"}),
+                ),
+                gas: 0,
+                deposit: 0,
+            }],
+        }]);
+        let hay = TxHaystack::from_tx(&tx);
+
+        // action:/method: see the actions, including delegated ones
+        for q in ["action:delegate", "action:functioncall", "method:ft_transfer"] {
+            assert!(haystack_matches(&hay, &compile_filter(q)), "{q}");
+        }
+        assert!(!haystack_matches(&hay, &compile_filter("action:transfer,stake")));
+        // raw: searches the whole serialized TxLite (nonce, decoded args), lowercased
+        for q in ["raw:invoice-42", "raw:\"nonce\":77", "raw:relayer.near"] {
+            assert!(haystack_matches(&hay, &compile_filter(q)), "{q}");
+        }
+        // Free text stays on accounts, hash and method names
+        assert!(haystack_matches(&hay, &compile_filter("ft_transfer")));
+        assert!(!haystack_matches(&hay, &compile_filter("invoice-42")));
+        // Without actions, action:/method: terms match nothing
+        let bare = TxHaystack::from_tx(&block(1, &["alice.near"]).transactions[0]);
+        assert!(!haystack_matches(&bare, &compile_filter("method:ft_transfer")));
+    }
+
+    #[test]
+    fn negated_terms_exclude() {
+        let mut txs = block(1, &["spam.near", "alice.near"]).transactions;
+        let alice = txs.pop().unwrap();
+        let spam = txs.pop().unwrap();
+
+        let f = compile_filter("acct:intents.near -signer:spam.near");
+        assert_eq!(f.exclude, vec![(FilterField::Signer, "spam.near".to_string())]);
+        assert!(!haystack_matches(&TxHaystack::from_tx(&spam), &f));
+        assert!(haystack_matches(&TxHaystack::from_tx(&alice), &f));
+
+        // Exclusion alone (and '!' prefix, bare account auto-detection)
+        let f = compile_filter("!spam.near");
+        assert!(!is_empty(&f));
+        assert!(!haystack_matches(&TxHaystack::from_tx(&spam), &f));
+        assert!(haystack_matches(&TxHaystack::from_tx(&alice), &f));
+    }
+
+    #[test]
+    fn owned_accounts_match_exactly_and_and_with_query() {
+        let mut txs = block(1, &["bob.near", "notbob.near"]).transactions;
+        let other = txs.pop().unwrap();
+        let bob = txs.pop().unwrap();
+
+        let mut f = compile_filter("");
+        f.owned = vec!["bob.near".into()];
+        assert!(!is_empty(&f));
+        assert!(haystack_matches(&TxHaystack::from_tx(&bob), &f));
+        assert!(!haystack_matches(&TxHaystack::from_tx(&other), &f));
+
+        let mut f = compile_filter("receiver:nobody.near");
+        f.owned = vec!["bob.near".into()];
+        assert!(!haystack_matches(&TxHaystack::from_tx(&bob), &f));
+    }
+
+    #[test]
+    fn bridge_terms() {
+        let plain = block(1, &["alice.near"]).transactions.remove(0);
+        let mut deposit = plain.clone();
+        deposit.receiver_id = Some("factory.bridge.near".into());
+        deposit.actions = Some(vec![ActionSummary::FunctionCall {
+            method_name: "deposit".into(),
+            _args_base64: String::new(),
+            args_decoded: crate::near_args::DecodedArgs::Empty,
+            gas: 0,
+            deposit: 0,
+        }]);
+
+        let hay = TxHaystack::from_tx(&deposit);
+        for q in ["bridge:any", "bridge:in", "bridge:eth", "bridge:rainbow,wormhole"] {
+            assert!(haystack_matches(&hay, &compile_filter(q)), "{q}");
+        }
+        assert!(!haystack_matches(&hay, &compile_filter("bridge:out")));
+        assert!(!haystack_matches(&hay, &compile_filter("-bridge:any")));
+        assert!(!haystack_matches(&TxHaystack::from_tx(&plain), &compile_filter("bridge:any")));
+    }
+
+    #[test]
+    fn large_sets_keep_input_order() {
+        let signers: Vec<String> = (0..1000)
+            .map(|i| if i % 7 == 0 { "alice.near".into() } else { format!("u{i}.near") })
+            .collect();
+        let refs: Vec<&str> = signers.iter().map(String::as_str).collect();
+        let b = block(1, &refs);
+        let f = compile_filter("signer:alice");
+        let expected: Vec<bool> = (0..1000).map(|i| i % 7 == 0).collect();
+
+        assert_eq!(FilterCache::default().block_matches(&b, &f), expected);
+        let json: Vec<serde_json::Value> =
+            b.transactions.iter().map(|t| serde_json::to_value(t).unwrap()).collect();
+        assert_eq!(txs_matching_filter(&json, &f), expected);
+    }
+
+    #[test]
+    fn cache_serves_stale_counts_until_revalidated() {
+        let blocks = vec![block(2, &["alice.near", "bob.near"]), block(1, &["bob.near"])];
+        let mut cache = FilterCache::default();
+
+        cache.revalidate(&blocks, &compile_filter("signer:bob"), usize::MAX);
+        assert_eq!(cache.count(2), Some(1));
+        assert_eq!(cache.count(1), Some(1));
+
+        // New filter: counts stay (stale) until budget allows recomputation
+        cache.invalidate();
+        let alice = compile_filter("signer:alice");
+        assert_eq!(cache.pending(&blocks), 2);
+        cache.revalidate(&blocks, &alice, 1);
+        assert_eq!(cache.count(2), Some(1));
+        assert!(!cache.is_stale(2));
+        assert!(cache.is_stale(1));
+        assert_eq!(cache.count(1), Some(1)); // stale answer
+
+        cache.revalidate(&blocks, &alice, usize::MAX);
+        assert_eq!(cache.count(1), Some(0));
+        assert_eq!(cache.pending(&blocks), 0);
+    }
+
+    #[test]
+    fn sample_splits_matching_and_other() {
+        let blocks = vec![
+            block(3, &["alice.near", "bob.near", "alice.near"]),
+            block(2, &["carol.near", "alice.near"]),
+        ];
+        let cache = FilterCache::default();
+
+        let s = cache.sample(&blocks, &compile_filter("signer:alice"), 2, usize::MAX);
+        assert_eq!(s.matching, vec![(3, 0), (3, 2)]);
+        assert_eq!(s.other, vec![(3, 1), (2, 0)]);
+        assert_eq!(s.scanned, 4); // stops once both lists are full
+        assert!(!s.truncated);
+
+        // Negation flips the split
+        let s = cache.sample(&blocks, &compile_filter("-signer:alice"), 3, usize::MAX);
+        assert_eq!(s.matching, vec![(3, 1), (2, 0)]);
+        assert_eq!(s.other.len(), 3);
+
+        let s = cache.sample(&blocks, &compile_filter("signer:alice"), 3, 2);
+        assert_eq!(s.scanned, 2);
+        assert!(s.truncated);
+    }
 }
@@ -0,0 +1,148 @@
+//! Gas price oracle
+//!
+//! Keeps the gas price of the last N live blocks and answers percentile
+//! questions over that window ("p90 over last 100 blocks"). A block whose
+//! price exceeds `spike_multiple` × the rolling median raises a spike; the
+//! alert fires once on the way up and re-arms when the price falls back.
+
+use std::collections::VecDeque;
+
+/// Default rolling window (blocks)
+pub const DEFAULT_GAS_WINDOW: usize = 100;
+/// Default spike threshold as a multiple of the rolling median
+pub const DEFAULT_GAS_SPIKE_MULTIPLE: f64 = 2.0;
+/// Samples needed before the median is trusted for spike detection
+const MIN_SPIKE_SAMPLES: usize = 10;
+
+/// Gas price jumped above `multiple` × the rolling median
+#[derive(Debug, Clone, PartialEq)]
+pub struct GasSpike {
+    pub height: u64,
+    pub price: u128,
+    pub median: u128,
+    pub multiple: f64,
+}
+
+/// Current price plus percentiles over the window
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasGuidance {
+    pub height: u64,
+    pub current: u128,
+    pub p50: u128,
+    pub p90: u128,
+    pub max: u128,
+    pub samples: usize,
+    pub window: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct GasOracle {
+    window: usize,
+    spike_multiple: f64,
+    /// (height, yoctoNEAR per gas), oldest first
+    samples: VecDeque<(u64, u128)>,
+    spiking: bool,
+}
+
+impl Default for GasOracle {
+    fn default() -> Self {
+        Self::new(DEFAULT_GAS_WINDOW, DEFAULT_GAS_SPIKE_MULTIPLE)
+    }
+}
+
+impl GasOracle {
+    pub fn new(window: usize, spike_multiple: f64) -> Self {
+        Self {
+            window: window.max(1),
+            spike_multiple,
+            samples: VecDeque::with_capacity(window.max(1)),
+            spiking: false,
+        }
+    }
+
+    /// Record a live block's gas price. Heights at or below the newest sample
+    /// (backfill, duplicates) are ignored so the window stays "the last N blocks".
+    pub fn record(&mut self, height: u64, price: u128) -> Option<GasSpike> {
+        if self.samples.back().is_some_and(|&(h, _)| height <= h) {
+            return None;
+        }
+
+        // Compare against the window before this block joins it
+        let spike = match self.percentile(50) {
+            Some(median) if self.samples.len() >= MIN_SPIKE_SAMPLES && median > 0 => {
+                let multiple = price as f64 / median as f64;
+                let above = multiple > self.spike_multiple;
+                let fire = above && !self.spiking;
+                self.spiking = above;
+                fire.then_some(GasSpike {
+                    height,
+                    price,
+                    median,
+                    multiple,
+                })
+            }
+            _ => None,
+        };
+
+        self.samples.push_back((height, price));
+        while self.samples.len() > self.window {
+            self.samples.pop_front();
+        }
+        spike
+    }
+
+    /// Nearest-rank percentile (0-100) of prices in the window
+    pub fn percentile(&self, p: u8) -> Option<u128> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let mut prices: Vec<u128> = self.samples.iter().map(|&(_, price)| price).collect();
+        prices.sort_unstable();
+        let rank = (p.min(100) as usize * prices.len()).div_ceil(100).max(1);
+        Some(prices[rank - 1])
+    }
+
+    pub fn guidance(&self) -> Option<GasGuidance> {
+        let &(height, current) = self.samples.back()?;
+        Some(GasGuidance {
+            height,
+            current,
+            p50: self.percentile(50)?,
+            p90: self.percentile(90)?,
+            max: self.percentile(100)?,
+            samples: self.samples.len(),
+            window: self.window,
+        })
+    }
+}
+
+/// yoctoNEAR per gas as milliNEAR per Tgas (1e8 yocto/gas = 0.1000 mⓃ/Tgas)
+pub fn format_gas_price(price: u128) -> String {
+    format!("{:.4} mⓃ/Tgas", price as f64 / 1e9)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles_and_edge_triggered_spikes() {
+        let mut oracle = GasOracle::new(10, 2.0);
+        for h in 1..=10u64 {
+            assert_eq!(oracle.record(h, 100_000_000 + h as u128), None);
+        }
+        let g = oracle.guidance().unwrap();
+        assert_eq!((g.p50, g.p90, g.max), (100_000_005, 100_000_009, 100_000_010));
+
+        // Backfill never enters the window
+        assert_eq!(oracle.record(3, 1), None);
+
+        let spike = oracle.record(11, 300_000_000).unwrap();
+        assert_eq!(spike.median, 100_000_005);
+        // Still above: no repeat alert until it drops back
+        assert_eq!(oracle.record(12, 300_000_000), None);
+        assert_eq!(oracle.record(13, 100_000_000), None);
+        assert!(oracle.record(14, 400_000_000).is_some());
+        assert_eq!(oracle.guidance().unwrap().samples, 10);
+    }
+}
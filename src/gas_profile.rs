@@ -0,0 +1,344 @@
+//! Receipt gas profiler
+//!
+//! Builds the receipt tree of a tx from `EXPERIMENTAL_tx_status` (each
+//! outcome's `receipt_ids` are its children) and renders it flamegraph-style
+//! into the Details pane: one proportional bar per receipt, sized by the gas
+//! burnt in its whole subtree. Children are ordered heaviest first, so `[1]`
+//! is always the dominant hop; drilling in re-scales the bars to that subtree.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+use crate::exec_error;
+use crate::outcome_cache::Outcomes;
+use crate::timeline::{receipt_detail, status_key};
+
+/// Width of the proportional bar column (cells)
+const BAR_WIDTH: usize = 30;
+/// Partial cells for sub-cell precision (1/8 .. 7/8)
+const EIGHTHS: [&str; 7] = ["▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+#[derive(Debug, Clone)]
+pub struct GasNode {
+    pub executor: String,
+    /// `→ receiver` for the tx itself, else method name / action type
+    pub detail: String,
+    pub status: String,
+    /// Failure summary (see `exec_error`)
+    pub error: Option<String>,
+    pub refund: bool,
+    /// Gas burnt by this outcome alone
+    pub gas_burnt: u64,
+    /// Gas burnt by this outcome and everything it spawned
+    pub total: u64,
+    /// Heaviest subtree first
+    pub children: Vec<usize>,
+}
+
+impl GasNode {
+    fn label(&self) -> String {
+        let mut label = format!("{} {}", self.executor, self.detail);
+        if self.refund {
+            label.push_str(" ↩");
+        }
+        if let Some(err) = &self.error {
+            label.push_str(&format!(" ✗ {err}"));
+        }
+        label
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GasProfile {
+    pub hash: String,
+    /// Node 0 is the signed tx (conversion to its first receipt)
+    pub nodes: Vec<GasNode>,
+    /// Drill-down path from the root (node indices, root excluded)
+    focus: Vec<usize>,
+}
+
+fn outcome_gas(outcome: &Value) -> u64 {
+    outcome["gas_burnt"].as_u64().unwrap_or_default()
+}
+
+fn outcome_receipt_ids(outcome: &Value) -> Vec<&str> {
+    outcome["receipt_ids"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect()
+}
+
+/// `12.34 Tgas`
+pub fn format_tgas(gas: u64) -> String {
+    format!("{:.2} Tgas", gas as f64 / 1e12)
+}
+
+/// Proportional bar padded to `width` cells
+fn bar(share: f64, width: usize) -> String {
+    let eighths = (share.clamp(0.0, 1.0) * (width * 8) as f64).round() as usize;
+    let (full, rem) = (eighths / 8, eighths % 8);
+    let mut s = "█".repeat(full);
+    let mut cells = full;
+    if rem > 0 {
+        s.push_str(EIGHTHS[rem - 1]);
+        cells += 1;
+    }
+    s.push_str(&" ".repeat(width.saturating_sub(cells)));
+    s
+}
+
+fn percent(part: u64, whole: u64) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 * 100.0 / whole as f64
+    }
+}
+
+impl GasProfile {
+    /// Build the receipt tree from an `EXPERIMENTAL_tx_status` result
+    pub fn from_status(status: &Value) -> Result<Self> {
+        let tx = &status["transaction"];
+        let hash = tx["hash"]
+            .as_str()
+            .ok_or_else(|| anyhow!("tx status has no transaction hash"))?
+            .to_string();
+        let tx_outcome = &status["transaction_outcome"]["outcome"];
+
+        let bodies: HashMap<&str, &Value> = status["receipts"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|r| Some((r["receipt_id"].as_str()?, r)))
+            .collect();
+
+        let mut nodes = vec![GasNode {
+            executor: tx["signer_id"].as_str().unwrap_or_default().to_string(),
+            detail: format!("→ {}", tx["receiver_id"].as_str().unwrap_or_default()),
+            status: status_key(tx_outcome),
+            error: exec_error::decode(&tx_outcome["status"]).map(|e| e.summary),
+            refund: false,
+            gas_burnt: outcome_gas(tx_outcome),
+            total: 0,
+            children: Vec::new(),
+        }];
+        let mut spawned = vec![outcome_receipt_ids(tx_outcome)];
+        let mut index: HashMap<&str, usize> = HashMap::new();
+
+        let outcomes = status["receipts_outcome"].as_array().map(Vec::as_slice).unwrap_or_default();
+        for ro in outcomes {
+            let id = ro["id"].as_str().unwrap_or_default();
+            let outcome = &ro["outcome"];
+            let body = bodies.get(id);
+            index.insert(id, nodes.len());
+            nodes.push(GasNode {
+                executor: outcome["executor_id"].as_str().unwrap_or_default().to_string(),
+                detail: body.map(|r| receipt_detail(r)).unwrap_or_default(),
+                status: status_key(outcome),
+                error: exec_error::decode(&outcome["status"]).map(|e| e.summary),
+                refund: body.is_some_and(|r| r["predecessor_id"].as_str() == Some("system")),
+                gas_burnt: outcome_gas(outcome),
+                total: 0,
+                children: Vec::new(),
+            });
+            spawned.push(outcome_receipt_ids(outcome));
+        }
+
+        // Every receipt has exactly one parent; the first claim wins, which
+        // also keeps a malformed response from forming a cycle
+        let mut has_parent = vec![false; nodes.len()];
+        has_parent[0] = true;
+        for (parent, ids) in spawned.iter().enumerate() {
+            for id in ids {
+                if let Some(&child) = index.get(id) {
+                    if !has_parent[child] {
+                        has_parent[child] = true;
+                        nodes[parent].children.push(child);
+                    }
+                }
+            }
+        }
+        // Outcomes whose parent wasn't returned hang off the tx
+        for (i, claimed) in has_parent.iter().enumerate() {
+            if !claimed {
+                nodes[0].children.push(i);
+            }
+        }
+
+        let mut profile = Self {
+            hash,
+            nodes,
+            focus: Vec::new(),
+        };
+        profile.sum_subtree(0);
+        Ok(profile)
+    }
+
+    /// Fill `total` bottom-up and order children heaviest first
+    fn sum_subtree(&mut self, i: usize) -> u64 {
+        let children = std::mem::take(&mut self.nodes[i].children);
+        let mut weighted: Vec<(u64, usize)> = children.into_iter().map(|c| (self.sum_subtree(c), c)).collect();
+        weighted.sort_by(|a, b| b.0.cmp(&a.0));
+
+        let node = &mut self.nodes[i];
+        node.total = node.gas_burnt + weighted.iter().map(|(g, _)| g).sum::<u64>();
+        node.children = weighted.into_iter().map(|(_, c)| c).collect();
+        node.total
+    }
+
+    pub fn total_gas(&self) -> u64 {
+        self.nodes[0].total
+    }
+
+    /// Node the view is scaled to
+    pub fn focus_node(&self) -> usize {
+        self.focus.last().copied().unwrap_or(0)
+    }
+
+    /// Drill into the `n`th (1-based) child of the focused node
+    pub fn drill(&mut self, n: usize) -> bool {
+        let focused = &self.nodes[self.focus_node()];
+        match n.checked_sub(1).and_then(|i| focused.children.get(i)) {
+            Some(&child) => {
+                self.focus.push(child);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Back out one level
+    pub fn back(&mut self) -> bool {
+        self.focus.pop().is_some()
+    }
+
+    /// Receipt burning the most gas on its own (the dominant hop)
+    fn heaviest(&self) -> Option<usize> {
+        (1..self.nodes.len()).max_by_key(|&i| self.nodes[i].gas_burnt)
+    }
+
+    /// Header plus one bar row per receipt in the focused subtree
+    pub fn render_lines(&self) -> Vec<String> {
+        let total = self.total_gas();
+        let mut lines = vec![
+            format!("Gas profile for {}", self.hash),
+            format!("Total {} burnt across {} receipts", format_tgas(total), self.nodes.len() - 1),
+        ];
+        if let Some(h) = self.heaviest() {
+            let node = &self.nodes[h];
+            lines.push(format!(
+                "Heaviest hop: {} ({:.1}% of tx)",
+                node.label(),
+                percent(node.gas_burnt, total)
+            ));
+        }
+        if !self.focus.is_empty() {
+            let path: Vec<String> = self.focus.iter().map(|&i| self.nodes[i].label()).collect();
+            lines.push(format!(
+                "Focus: tx › {}  ({:.1}% of tx)",
+                path.join(" › "),
+                percent(self.nodes[self.focus_node()].total, total)
+            ));
+        }
+        lines.push("1-9 drill into a hop · 0 back out · G refresh".to_string());
+        lines.push(String::new());
+
+        let focused = self.focus_node();
+        let scale = self.nodes[focused].total;
+        let mut stack = vec![(focused, 0usize, None::<usize>)];
+        while let Some((i, depth, slot)) = stack.pop() {
+            let node = &self.nodes[i];
+            let slot = match slot {
+                Some(n) if n <= 9 => format!("[{n}]"),
+                _ => "   ".to_string(),
+            };
+            lines.push(format!(
+                "{slot} {}  {:>5.1}%  {:>11}  self {:>11}  {}{}",
+                bar(if scale == 0 { 0.0 } else { node.total as f64 / scale as f64 }, BAR_WIDTH),
+                percent(node.total, scale),
+                format_tgas(node.total),
+                format_tgas(node.gas_burnt),
+                "  ".repeat(depth),
+                node.label()
+            ));
+            // Reverse so the heaviest child pops first; only the focus's direct children are numbered
+            for (n, &child) in node.children.iter().enumerate().rev() {
+                stack.push((child, depth + 1, (i == focused).then_some(n + 1)));
+            }
+        }
+        lines
+    }
+}
+
+/// Fetch `hash` (signed by `signer`, through `outcomes`) and build its receipt gas tree
+pub async fn profile(
+    outcomes: &Outcomes,
+    url: &str,
+    hash: &str,
+    signer: &str,
+    timeout_ms: u64,
+    auth_token: Option<&str>,
+) -> Result<GasProfile> {
+    let status = outcomes
+        .tx_status(url, hash, signer, "FINAL", timeout_ms, auth_token)
+        .await?;
+    GasProfile::from_status(&status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn builds_tree_heaviest_first_and_drills() {
+        let status = json!({
+            "transaction": {"hash": "TX", "signer_id": "alice.near", "receiver_id": "token.near"},
+            "transaction_outcome": {"outcome": {"executor_id": "alice.near", "gas_burnt": 2_000_000_000_000u64,
+                "receipt_ids": ["R1"], "status": {"SuccessReceiptId": "R1"}}},
+            "receipts_outcome": [
+                {"id": "R1", "outcome": {"executor_id": "token.near", "gas_burnt": 3_000_000_000_000u64,
+                    "receipt_ids": ["R2", "R3"], "status": {"SuccessValue": ""}}},
+                {"id": "R2", "outcome": {"executor_id": "alice.near", "gas_burnt": 0,
+                    "receipt_ids": [], "status": {"SuccessValue": ""}}},
+                {"id": "R3", "outcome": {"executor_id": "dex.near", "gas_burnt": 5_000_000_000_000u64,
+                    "receipt_ids": [], "status": {"Failure": {"ActionError": {"index": 0,
+                    "kind": {"FunctionCallError": {"MethodResolveError": "MethodNotFound"}}}}}}}
+            ],
+            "receipts": [
+                {"receipt_id": "R1", "predecessor_id": "alice.near", "receipt": {"Action": {"actions": [{"FunctionCall": {"method_name": "ft_transfer_call"}}]}}},
+                {"receipt_id": "R2", "predecessor_id": "system", "receipt": {"Action": {"actions": [{"Transfer": {"deposit": "1"}}]}}},
+                {"receipt_id": "R3", "predecessor_id": "token.near", "receipt": {"Action": {"actions": [{"FunctionCall": {"method_name": "ft_on_transfer"}}]}}}
+            ]
+        });
+        let mut profile = GasProfile::from_status(&status).unwrap();
+        assert_eq!(profile.total_gas(), 10_000_000_000_000);
+        // R3 (5 Tgas) sorts ahead of the refund (0)
+        assert_eq!(profile.nodes[1].children, [3, 2]);
+
+        let lines = profile.render_lines();
+        assert_eq!(lines[1], "Total 10.00 Tgas burnt across 3 receipts");
+        assert_eq!(lines[2], "Heaviest hop: dex.near ft_on_transfer ✗ Action #0: method not found (50.0% of tx)");
+        assert!(lines[5].contains("100.0%") && lines[5].ends_with("alice.near → token.near"));
+        assert!(lines[6].starts_with("[1] ") && lines[6].contains(" 80.0%"));
+        assert!(lines[7].ends_with("    dex.near ft_on_transfer ✗ Action #0: method not found"));
+
+        assert!(!profile.drill(2));
+        assert!(profile.drill(1));
+        assert!(profile.drill(1));
+        assert_eq!(profile.focus_node(), 3);
+        assert!(profile.render_lines()[3].starts_with("Focus: tx › token.near ft_transfer_call › dex.near"));
+        assert!(profile.back() && profile.back() && !profile.back());
+    }
+
+    #[test]
+    fn bar_uses_partial_cells() {
+        assert_eq!(bar(0.5, 4), "██  ");
+        assert_eq!(bar(1.0 / 16.0, 2), "▏ ");
+        assert_eq!(bar(2.0, 2), "██");
+    }
+}
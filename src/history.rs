@@ -5,6 +5,13 @@
 
 use anyhow::Result;
 
+use crate::exports::ExportRecord;
+use crate::history_browser::{BrowseCursor, BrowseRange};
+use crate::outcome_cache::CachedOutcome;
+use crate::related::RelatedQuery;
+use crate::types::{BlockRow, TxAnalysis, HIGH_RISK_SCORE};
+use crate::watch::WatchRule;
+
 #[cfg(feature = "native")]
 use rusqlite::{params, Connection, Statement, ToSql};
 #[cfg(feature = "native")]
@@ -33,6 +40,33 @@ pub struct BlockPersist {
     pub txs: Vec<TxPersist>,
 }
 
+impl BlockPersist {
+    /// Row set for a block and its txs (signer, receiver and actions included,
+    /// so search and digests work on live and downloaded blocks alike)
+    pub fn from_block(block: &BlockRow) -> Self {
+        BlockPersist {
+            height: block.height,
+            hash: block.hash.clone(),
+            ts_ms: block.timestamp as i64,
+            txs: block
+                .transactions
+                .iter()
+                .map(|tx| TxPersist {
+                    hash: tx.hash.clone(),
+                    height: block.height,
+                    signer: tx.signer_id.clone(),
+                    receiver: tx.receiver_id.clone(),
+                    actions_json: tx
+                        .actions
+                        .as_ref()
+                        .and_then(|a| serde_json::to_string(a).ok()),
+                    raw_json: serde_json::to_string(tx).ok(),
+                })
+                .collect(),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct HistoryHit {
     pub hash: String,
@@ -41,6 +75,68 @@ pub struct HistoryHit {
     pub signer: Option<String>,
     pub receiver: Option<String>,
     pub methods: Option<String>,
+    /// Stored `ActionSummary` list as JSON (exclusion filters in `nearx query`)
+    pub actions_json: Option<String>,
+    /// Final outcome, once fetched (outcome cache or `nearx backfill --outcomes`)
+    pub status: Option<StoredStatus>,
+}
+
+/// Final tx outcome kept on the tx row (`txs.status`, `failure`, `receipt_ids`)
+#[derive(Clone, Debug, PartialEq)]
+pub struct StoredStatus {
+    /// Decoded failure summary; `None` = success
+    pub failure: Option<String>,
+    /// Receipts in execution order
+    pub receipt_ids: Vec<String>,
+}
+
+impl StoredStatus {
+    /// From the tx's own cached outcome body (`{transaction, status, receipt_ids}`);
+    /// `None` while the status isn't final
+    pub fn from_root(body: &serde_json::Value) -> Option<Self> {
+        let status = &body["status"];
+        let failure = if status.get("SuccessValue").is_some() || status.get("SuccessReceiptId").is_some() {
+            None
+        } else {
+            Some(crate::exec_error::summarize(status.get("Failure")?))
+        };
+        let receipt_ids = body["receipt_ids"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|id| id.as_str().map(str::to_string))
+            .collect();
+        Some(Self { failure, receipt_ids })
+    }
+
+    /// Read back from the `status`, `failure`, `receipt_ids` columns
+    #[cfg(feature = "native")]
+    fn from_columns(status: Option<String>, failure: Option<String>, receipt_ids: Option<String>) -> Option<Self> {
+        status?;
+        Some(Self {
+            failure,
+            receipt_ids: receipt_ids
+                .and_then(|ids| serde_json::from_str(&ids).ok())
+                .unwrap_or_default(),
+        })
+    }
+
+    /// `success` / `failure: <summary>`
+    pub fn label(&self) -> String {
+        match &self.failure {
+            None => "success".to_string(),
+            Some(summary) => format!("failure: {summary}"),
+        }
+    }
+}
+
+/// What `nearx backfill` filled in on stored txs
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BackfillStats {
+    /// Rows whose signer / receiver / actions were decoded from `raw_json`
+    pub decoded: usize,
+    /// Rows whose final status was taken from stored outcomes
+    pub statuses: usize,
 }
 
 #[derive(Clone, Debug)]
@@ -51,6 +147,77 @@ pub struct PersistedMark {
     pub tx: Option<String>,
     pub when_ms: i64,
     pub pinned: bool,
+    /// Soft-delete time (ms); `Some` = in the marks trash
+    pub deleted_at: Option<i64>,
+}
+
+/// Usage counters carried over between sessions: (period start in unix ms, counts)
+pub type TelemetryCounters = (i64, std::collections::BTreeMap<String, u64>);
+
+/// Range download in progress (`nearx fetch`); at most one is recorded
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FetchJob {
+    pub from: u64,
+    pub to: u64,
+    /// First height not yet downloaded (`to + 1` when complete)
+    pub next: u64,
+    pub started_ms: i64,
+}
+
+/// Stored tx to one receiver (contract digests)
+#[derive(Clone, Debug)]
+pub struct ContractTx {
+    pub hash: String,
+    pub height: u64,
+    pub ts_ms: i64,
+    pub signer: Option<String>,
+    /// Serialized `Vec<ActionSummary>`
+    pub actions_json: Option<String>,
+}
+
+/// Stored tx with a CreateAccount / DeleteAccount action (account feed)
+#[derive(Clone, Debug)]
+pub struct AccountTx {
+    pub hash: String,
+    pub height: u64,
+    pub ts_ms: i64,
+    pub signer: Option<String>,
+    pub receiver: Option<String>,
+    /// Serialized `Vec<ActionSummary>`
+    pub actions_json: Option<String>,
+}
+
+/// Stored tx-analyzer result, with the tx's block when History has the tx
+#[derive(Clone, Debug)]
+pub struct RiskHit {
+    pub height: Option<u64>,
+    pub ts_ms: i64,
+    pub analysis: TxAnalysis,
+}
+
+/// Risk browser query: `risk>N` / `risk>=N` plus free text over signer,
+/// receiver and pattern names. Without a `risk` term only high-risk txs show.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RiskQuery {
+    /// Inclusive minimum score
+    pub min_risk: u8,
+    pub terms: Vec<String>,
+}
+
+pub fn parse_risk_query(q: &str) -> RiskQuery {
+    let mut min_risk = HIGH_RISK_SCORE.saturating_add(1);
+    let mut terms = Vec::new();
+    for tok in q.split_whitespace() {
+        let lower = tok.to_lowercase();
+        if let Some(n) = lower.strip_prefix("risk>=").and_then(|n| n.parse::<u8>().ok()) {
+            min_risk = n;
+        } else if let Some(n) = lower.strip_prefix("risk>").and_then(|n| n.parse::<u8>().ok()) {
+            min_risk = n.saturating_add(1);
+        } else {
+            terms.push(lower);
+        }
+    }
+    RiskQuery { min_risk, terms }
 }
 
 // Native-only History implementation using SQLite
@@ -60,12 +227,24 @@ enum HistoryMsg {
     Search {
         query: String,
         limit: usize,
+        since_ms: Option<i64>,
         resp: oneshot::Sender<Vec<HistoryHit>>,
     },
     GetTx {
         hash: String,
         resp: oneshot::Sender<Option<String>>,
     },
+    FindTxs {
+        hashes: Vec<String>,
+        resp: oneshot::Sender<Vec<(HistoryHit, Option<String>)>>,
+    },
+    BackfillColumns {
+        resp: oneshot::Sender<BackfillStats>,
+    },
+    TxsWithoutStatus {
+        limit: usize,
+        resp: oneshot::Sender<Vec<(String, String)>>,
+    },
     ListMarks {
         resp: oneshot::Sender<Vec<PersistedMark>>,
     },
@@ -77,6 +256,11 @@ enum HistoryMsg {
         label: String,
         resp: oneshot::Sender<()>,
     },
+    TrashMark {
+        label: String,
+        deleted_at: Option<i64>,
+        resp: oneshot::Sender<()>,
+    },
     SetMarkPinned {
         label: String,
         pinned: bool,
@@ -86,6 +270,81 @@ enum HistoryMsg {
     ClearMarks {
         resp: oneshot::Sender<()>,
     },
+    SaveWarm {
+        rows: Vec<(u64, String)>,
+        resp: oneshot::Sender<()>,
+    },
+    LoadWarm {
+        resp: oneshot::Sender<Vec<String>>,
+    },
+    PutAnalysis(TxAnalysis),
+    PutOutcomes(Vec<CachedOutcome>),
+    TxOutcomes {
+        tx_hash: String,
+        resp: oneshot::Sender<Vec<CachedOutcome>>,
+    },
+    GetFetchJob {
+        resp: oneshot::Sender<Option<FetchJob>>,
+    },
+    SetFetchJob {
+        job: Option<FetchJob>,
+        resp: oneshot::Sender<()>,
+    },
+    BlockHashes {
+        from: u64,
+        to: u64,
+        resp: oneshot::Sender<Vec<(u64, String)>>,
+    },
+    BrowseBlocks {
+        range: BrowseRange,
+        cursor: BrowseCursor,
+        limit: usize,
+        resp: oneshot::Sender<Vec<BlockPersist>>,
+    },
+    RelatedTxs {
+        query: RelatedQuery,
+        limit: usize,
+        resp: oneshot::Sender<Vec<TxPersist>>,
+    },
+    ListAnalyses {
+        query: RiskQuery,
+        limit: usize,
+        resp: oneshot::Sender<Vec<RiskHit>>,
+    },
+    ContractTxs {
+        receiver: String,
+        since_ms: i64,
+        limit: usize,
+        resp: oneshot::Sender<Vec<ContractTx>>,
+    },
+    AccountTxs {
+        limit: usize,
+        resp: oneshot::Sender<Vec<AccountTx>>,
+    },
+    GetTelemetry {
+        resp: oneshot::Sender<Option<TelemetryCounters>>,
+    },
+    SetTelemetry {
+        counters: Option<TelemetryCounters>,
+        resp: oneshot::Sender<()>,
+    },
+    ListWatches {
+        resp: oneshot::Sender<Vec<WatchRule>>,
+    },
+    PutWatches(Vec<WatchRule>),
+    PutExport {
+        record: ExportRecord,
+        resp: oneshot::Sender<Option<i64>>,
+    },
+    ListExports {
+        limit: usize,
+        resp: oneshot::Sender<Vec<ExportRecord>>,
+    },
+    DelExport(i64),
+    /// Stop after everything queued before it; answered once the db is closed
+    Shutdown {
+        resp: oneshot::Sender<()>,
+    },
 }
 
 #[cfg(feature = "native")]
@@ -101,68 +360,51 @@ impl History {
         let path = db_path.to_string();
 
         tokio::spawn(async move {
-            // single worker connection off main thread
-            let _ = spawn_blocking(move || -> Result<()> {
+            // single worker connection off main thread; returns the shutdown
+            // reply so it is sent only after the connection has closed
+            let worker = spawn_blocking(move || -> Result<Option<oneshot::Sender<()>>> {
                 let conn = Connection::open(path)?;
                 // Enable WAL mode for concurrent read/write performance
                 conn.pragma_update(None, "journal_mode", "WAL")?;
                 conn.pragma_update(None, "synchronous", "NORMAL")?;
                 // Set busy timeout to avoid immediate lock failures
                 conn.pragma_update(None, "busy_timeout", 250)?;
-                conn.execute_batch(
-                    r#"
-                    CREATE TABLE IF NOT EXISTS blocks(
-                        height INTEGER PRIMARY KEY,
-                        hash   TEXT NOT NULL,
-                        ts_ms  INTEGER NOT NULL,
-                        tx_count INTEGER NOT NULL
-                    );
-                    CREATE TABLE IF NOT EXISTS txs(
-                        hash     TEXT PRIMARY KEY,
-                        height   INTEGER NOT NULL,
-                        signer   TEXT,
-                        receiver TEXT,
-                        actions_json TEXT,
-                        raw_json TEXT,
-                        FOREIGN KEY(height) REFERENCES blocks(height) ON DELETE CASCADE
-                    );
-                    CREATE INDEX IF NOT EXISTS idx_txs_signer   ON txs(signer);
-                    CREATE INDEX IF NOT EXISTS idx_txs_receiver ON txs(receiver);
-                    CREATE INDEX IF NOT EXISTS idx_txs_height   ON txs(height);
-                    CREATE INDEX IF NOT EXISTS idx_txs_hash     ON txs(hash);
-                    CREATE INDEX IF NOT EXISTS idx_blocks_height ON blocks(height);
-                    CREATE TABLE IF NOT EXISTS marks(
-                        label    TEXT PRIMARY KEY,
-                        pane     INTEGER NOT NULL,
-                        height   INTEGER,
-                        tx       TEXT,
-                        when_ms  INTEGER NOT NULL,
-                        pinned   INTEGER NOT NULL DEFAULT 0
-                    );
-                    CREATE INDEX IF NOT EXISTS idx_marks_pinned ON marks(pinned) WHERE pinned = 1;
-                "#,
-                )?;
+                init_schema(&conn)?;
 
                 let mut stmt_block = conn.prepare(
                     "INSERT OR REPLACE INTO blocks(height,hash,ts_ms,tx_count) VALUES (?,?,?,?)",
                 )?;
+                // Re-persisting a tx (fetch repair, reloads) keeps its stored outcome
                 let mut stmt_tx = conn.prepare(
-                    "INSERT OR REPLACE INTO txs(hash,height,signer,receiver,actions_json,raw_json) VALUES (?,?,?,?,?,?)",
+                    "INSERT OR REPLACE INTO txs(hash,height,signer,receiver,actions_json,raw_json,status,failure,receipt_ids)
+                     SELECT ?1,?2,?3,?4,?5,?6,o.status,o.failure,o.receipt_ids FROM (SELECT 1) LEFT JOIN txs o ON o.hash = ?1",
+                )?;
+                let mut stmt_fts_del = conn.prepare(
+                    "DELETE FROM txs_fts WHERE rowid = (SELECT rowid FROM txs WHERE hash = ?)",
+                )?;
+                let mut stmt_fts_ins = conn.prepare(
+                    "INSERT INTO txs_fts(rowid,signer,receiver,actions) VALUES (?,?,?,?)",
                 )?;
 
                 // Mark statements
                 let mut stmt_mark_upsert = conn.prepare(
-                    "INSERT OR REPLACE INTO marks(label,pane,height,tx,when_ms,pinned) VALUES (?,?,?,?,?,?)",
+                    "INSERT OR REPLACE INTO marks(label,pane,height,tx,when_ms,pinned,deleted_at) VALUES (?,?,?,?,?,?,?)",
                 )?;
                 let mut stmt_mark_del = conn.prepare(
                     "DELETE FROM marks WHERE label = ?",
                 )?;
+                let mut stmt_mark_trash = conn.prepare(
+                    "UPDATE marks SET deleted_at = ? WHERE label = ?",
+                )?;
                 let mut stmt_mark_set_pinned = conn.prepare(
                     "UPDATE marks SET pinned = ? WHERE label = ?",
                 )?;
                 let mut stmt_mark_clear = conn.prepare(
                     "DELETE FROM marks",
                 )?;
+                let mut stmt_analysis = conn.prepare(
+                    "INSERT OR REPLACE INTO analyses(hash,ts_ms,signer,receiver,risk_score,patterns,analysis_json) VALUES (?,?,?,?,?,?,?)",
+                )?;
 
                 while let Some(msg) = rx.blocking_recv() {
                     match msg {
@@ -175,6 +417,8 @@ impl History {
                                 b.txs.len() as i64
                             ])?;
                             for t in b.txs {
+                                // REPLACE assigns a new rowid, so drop the stale index row first
+                                stmt_fts_del.execute(params![t.hash])?;
                                 stmt_tx.execute(params![
                                     t.hash,
                                     b.height as i64,
@@ -183,17 +427,38 @@ impl History {
                                     t.actions_json,
                                     t.raw_json
                                 ])?;
+                                stmt_fts_ins.execute(params![
+                                    conn.last_insert_rowid(),
+                                    t.signer,
+                                    t.receiver,
+                                    t.actions_json
+                                ])?;
                             }
                             txc.commit()?;
                         }
-                        HistoryMsg::Search { query, limit, resp } => {
-                            let hits = search_db(&conn, &query, limit).unwrap_or_default();
+                        HistoryMsg::Search {
+                            query,
+                            limit,
+                            since_ms,
+                            resp,
+                        } => {
+                            let hits = search_db(&conn, &query, limit, since_ms).unwrap_or_default();
                             let _ = resp.send(hits);
                         }
                         HistoryMsg::GetTx { hash, resp } => {
                             let raw = get_tx_db(&conn, &hash).unwrap_or(None);
                             let _ = resp.send(raw);
                         }
+                        HistoryMsg::FindTxs { hashes, resp } => {
+                            let found = find_txs_db(&conn, &hashes).unwrap_or_default();
+                            let _ = resp.send(found);
+                        }
+                        HistoryMsg::BackfillColumns { resp } => {
+                            let _ = resp.send(backfill_columns_db(&conn).unwrap_or_default());
+                        }
+                        HistoryMsg::TxsWithoutStatus { limit, resp } => {
+                            let _ = resp.send(txs_without_status_db(&conn, limit).unwrap_or_default());
+                        }
                         HistoryMsg::ListMarks { resp } => {
                             let marks = list_marks_db(&conn).unwrap_or_default();
                             let _ = resp.send(marks);
@@ -206,6 +471,10 @@ impl History {
                             let _ = del_mark_db(&conn, &mut stmt_mark_del, &label);
                             let _ = resp.send(());
                         }
+                        HistoryMsg::TrashMark { label, deleted_at, resp } => {
+                            let _ = stmt_mark_trash.execute(params![deleted_at, label]);
+                            let _ = resp.send(());
+                        }
                         HistoryMsg::SetMarkPinned { label, pinned, resp } => {
                             let _ = set_mark_pinned_db(&conn, &mut stmt_mark_set_pinned, &label, pinned);
                             let _ = resp.send(());
@@ -214,11 +483,86 @@ impl History {
                             let _ = clear_marks_db(&conn, &mut stmt_mark_clear);
                             let _ = resp.send(());
                         }
+                        HistoryMsg::SaveWarm { rows, resp } => {
+                            let _ = save_warm_db(&conn, &rows);
+                            let _ = resp.send(());
+                        }
+                        HistoryMsg::LoadWarm { resp } => {
+                            let rows = load_warm_db(&conn).unwrap_or_default();
+                            let _ = resp.send(rows);
+                        }
+                        HistoryMsg::PutAnalysis(a) => {
+                            let _ = put_analysis_db(&mut stmt_analysis, &a);
+                        }
+                        HistoryMsg::PutOutcomes(outcomes) => {
+                            let _ = put_outcomes_db(&conn, &outcomes);
+                        }
+                        HistoryMsg::TxOutcomes { tx_hash, resp } => {
+                            let _ = resp.send(tx_outcomes_db(&conn, &tx_hash).unwrap_or_default());
+                        }
+                        HistoryMsg::BrowseBlocks { range, cursor, limit, resp } => {
+                            let _ = resp.send(browse_blocks_db(&conn, &range, cursor, limit).unwrap_or_default());
+                        }
+                        HistoryMsg::RelatedTxs { query, limit, resp } => {
+                            let _ = resp.send(related_txs_db(&conn, &query, limit).unwrap_or_default());
+                        }
+                        HistoryMsg::ListAnalyses { query, limit, resp } => {
+                            let hits = list_analyses_db(&conn, &query, limit).unwrap_or_default();
+                            let _ = resp.send(hits);
+                        }
+                        HistoryMsg::GetFetchJob { resp } => {
+                            let _ = resp.send(get_fetch_job_db(&conn).unwrap_or(None));
+                        }
+                        HistoryMsg::SetFetchJob { job, resp } => {
+                            let _ = set_fetch_job_db(&conn, job.as_ref());
+                            let _ = resp.send(());
+                        }
+                        HistoryMsg::BlockHashes { from, to, resp } => {
+                            let _ = resp.send(block_hashes_db(&conn, from, to).unwrap_or_default());
+                        }
+                        HistoryMsg::ContractTxs { receiver, since_ms, limit, resp } => {
+                            let rows = contract_txs_db(&conn, &receiver, since_ms, limit).unwrap_or_default();
+                            let _ = resp.send(rows);
+                        }
+                        HistoryMsg::AccountTxs { limit, resp } => {
+                            let _ = resp.send(account_txs_db(&conn, limit).unwrap_or_default());
+                        }
+                        HistoryMsg::ListWatches { resp } => {
+                            let _ = resp.send(list_watches_db(&conn).unwrap_or_default());
+                        }
+                        HistoryMsg::PutWatches(rules) => {
+                            let _ = put_watches_db(&conn, &rules);
+                        }
+                        HistoryMsg::PutExport { record, resp } => {
+                            let _ = resp.send(put_export_db(&conn, &record).ok());
+                        }
+                        HistoryMsg::ListExports { limit, resp } => {
+                            let _ = resp.send(list_exports_db(&conn, limit).unwrap_or_default());
+                        }
+                        HistoryMsg::DelExport(id) => {
+                            let _ = conn.execute("DELETE FROM exports WHERE id = ?", params![id]);
+                        }
+                        HistoryMsg::GetTelemetry { resp } => {
+                            let _ = resp.send(get_telemetry_db(&conn).unwrap_or(None));
+                        }
+                        HistoryMsg::SetTelemetry { counters, resp } => {
+                            let _ = set_telemetry_db(&conn, counters.as_ref());
+                            let _ = resp.send(());
+                        }
+                        HistoryMsg::Shutdown { resp } => return Ok(Some(resp)),
                     }
                 }
-                Ok(())
+                Ok(None)
             })
             .await;
+            match worker {
+                Ok(Ok(Some(resp))) => {
+                    let _ = resp.send(());
+                }
+                Ok(Ok(None)) => {}
+                Ok(Err(e)) => log::error!("history writer stopped: {e:#}"),
+                Err(e) => log::error!("history writer panicked: {e}"),
+            }
         });
 
         Ok(Self { tx })
@@ -229,12 +573,18 @@ impl History {
     }
 
     pub async fn search(&self, query: String, limit: usize) -> Vec<HistoryHit> {
+        self.search_since(query, limit, None).await
+    }
+
+    /// `search` limited to blocks at or after `since_ms` (filtered in SQL, before ranking)
+    pub async fn search_since(&self, query: String, limit: usize, since_ms: Option<i64>) -> Vec<HistoryHit> {
         let (resp_tx, resp_rx) = oneshot::channel();
         if self
             .tx
             .send(HistoryMsg::Search {
                 query,
                 limit,
+                since_ms,
                 resp: resp_tx,
             })
             .is_err()
@@ -259,6 +609,49 @@ impl History {
         resp_rx.await.ok().flatten()
     }
 
+    /// Stored txs among `hashes`, with their raw JSON (tx collection import)
+    pub async fn find_txs(&self, hashes: Vec<String>) -> Vec<(HistoryHit, Option<String>)> {
+        let (resp_tx, resp_rx) = oneshot::channel();
+        if self
+            .tx
+            .send(HistoryMsg::FindTxs {
+                hashes,
+                resp: resp_tx,
+            })
+            .is_err()
+        {
+            return Vec::new();
+        }
+        resp_rx.await.unwrap_or_default()
+    }
+
+    /// Decode signer / receiver / actions from `raw_json` where they are missing,
+    /// and fill final statuses from stored outcomes (`nearx backfill`)
+    pub async fn backfill_columns(&self) -> BackfillStats {
+        let (resp_tx, resp_rx) = oneshot::channel();
+        if self
+            .tx
+            .send(HistoryMsg::BackfillColumns { resp: resp_tx })
+            .is_err()
+        {
+            return BackfillStats::default();
+        }
+        resp_rx.await.unwrap_or_default()
+    }
+
+    /// Newest stored `(hash, signer)` without a final status (signer known)
+    pub async fn txs_without_status(&self, limit: usize) -> Vec<(String, String)> {
+        let (resp_tx, resp_rx) = oneshot::channel();
+        if self
+            .tx
+            .send(HistoryMsg::TxsWithoutStatus { limit, resp: resp_tx })
+            .is_err()
+        {
+            return Vec::new();
+        }
+        resp_rx.await.unwrap_or_default()
+    }
+
     pub async fn list_marks(&self) -> Vec<PersistedMark> {
         let (resp_tx, resp_rx) = oneshot::channel();
         if self
@@ -289,6 +682,17 @@ impl History {
         let _ = resp_rx.await;
     }
 
+    /// Move a mark to the trash (`Some(ms)`) or restore it (`None`)
+    pub async fn trash_mark(&self, label: String, deleted_at: Option<i64>) {
+        let (resp_tx, resp_rx) = oneshot::channel();
+        let _ = self.tx.send(HistoryMsg::TrashMark {
+            label,
+            deleted_at,
+            resp: resp_tx,
+        });
+        let _ = resp_rx.await;
+    }
+
     pub async fn set_mark_pinned(&self, label: String, pinned: bool) {
         let (resp_tx, resp_rx) = oneshot::channel();
         let _ = self.tx.send(HistoryMsg::SetMarkPinned {
@@ -305,91 +709,470 @@ impl History {
         let _ = self.tx.send(HistoryMsg::ClearMarks { resp: resp_tx });
         let _ = resp_rx.await;
     }
-}
-
-// Search query parser: signer: receiver: acct: method: action: from: to: hash: + free text
-#[cfg(feature = "native")]
-struct SearchQuery {
-    signer: Vec<String>,
-    receiver: Vec<String>,
-    acct: Vec<String>,
-    method: Vec<String>,
-    action: Vec<String>,
-    hash: Vec<String>,
-    from_height: Option<i64>,
-    to_height: Option<i64>,
-    free: Vec<String>,
-}
 
-#[cfg(feature = "native")]
-fn parse_search_query(q: &str) -> SearchQuery {
-    let mut sq = SearchQuery {
-        signer: vec![],
-        receiver: vec![],
-        acct: vec![],
-        method: vec![],
-        action: vec![],
-        hash: vec![],
-        from_height: None,
-        to_height: None,
-        free: vec![],
-    };
+    /// Replace the warm-start snapshot with `blocks` (the in-memory ring at exit)
+    pub async fn save_warm_blocks(&self, blocks: &[BlockRow]) {
+        let rows = blocks
+            .iter()
+            .filter_map(|b| Some((b.height, serde_json::to_string(b).ok()?)))
+            .collect();
+        let (resp_tx, resp_rx) = oneshot::channel();
+        let _ = self.tx.send(HistoryMsg::SaveWarm {
+            rows,
+            resp: resp_tx,
+        });
+        let _ = resp_rx.await;
+    }
 
-    for tok in q.split_whitespace() {
-        if let Some((k, v)) = tok.split_once(':') {
-            let k = k.to_lowercase();
-            let v = v.to_lowercase();
-            match k.as_str() {
-                "signer" => sq.signer.push(v),
-                "receiver" | "rcv" => sq.receiver.push(v),
-                "acct" | "account" => sq.acct.push(v),
-                "method" => sq.method.push(v),
-                "action" => sq.action.push(v),
-                "hash" => sq.hash.push(v),
-                "from" => sq.from_height = v.parse().ok(),
-                "to" => sq.to_height = v.parse().ok(),
-                _ => sq.free.push(tok.to_lowercase()),
-            }
-        } else if !tok.is_empty() {
-            sq.free.push(tok.to_lowercase());
+    /// Last session's block ring, newest first (rows that fail to decode are skipped)
+    pub async fn load_warm_blocks(&self) -> Vec<BlockRow> {
+        let (resp_tx, resp_rx) = oneshot::channel();
+        if self
+            .tx
+            .send(HistoryMsg::LoadWarm { resp: resp_tx })
+            .is_err()
+        {
+            return Vec::new();
         }
+        resp_rx
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|json| serde_json::from_str(json).ok())
+            .collect()
     }
-    sq
-}
 
-#[cfg(feature = "native")]
-fn search_db(conn: &Connection, query: &str, limit: usize) -> Result<Vec<HistoryHit>> {
-    let sq = parse_search_query(query);
-    let mut sql = String::from("SELECT t.hash, t.height, b.ts_ms, t.signer, t.receiver, t.actions_json FROM txs t JOIN blocks b ON b.height = t.height");
-    let mut where_clauses = Vec::new();
-    let mut params_vec: Vec<Box<dyn ToSql>> = Vec::new();
+    /// Store a tx-analyzer result (replaces an earlier analysis of the same tx)
+    pub fn put_analysis(&self, analysis: TxAnalysis) {
+        let _ = self.tx.send(HistoryMsg::PutAnalysis(analysis));
+    }
 
-    // acct: signer OR receiver
-    if !sq.acct.is_empty() {
-        for a in &sq.acct {
-            where_clauses.push("(LOWER(t.signer) LIKE ? OR LOWER(t.receiver) LIKE ?)".to_string());
-            let pattern = format!("%{a}%");
-            params_vec.push(Box::new(pattern.clone()));
-            params_vec.push(Box::new(pattern));
-        }
+    /// Keep final receipt outcomes (replaces earlier rows for the same ids)
+    pub fn put_outcomes(&self, outcomes: Vec<CachedOutcome>) {
+        let _ = self.tx.send(HistoryMsg::PutOutcomes(outcomes));
     }
 
-    // signer
-    if !sq.signer.is_empty() {
-        let clause = format!(
-            "({})",
-            vec!["LOWER(t.signer) LIKE ?"; sq.signer.len()].join(" OR ")
-        );
-        where_clauses.push(clause);
-        for s in &sq.signer {
-            params_vec.push(Box::new(format!("%{s}%")));
+    /// Stored outcomes of `tx_hash`: its own and its receipts'
+    pub async fn tx_outcomes(&self, tx_hash: &str) -> Vec<CachedOutcome> {
+        let (resp_tx, resp_rx) = oneshot::channel();
+        if self
+            .tx
+            .send(HistoryMsg::TxOutcomes {
+                tx_hash: tx_hash.to_string(),
+                resp: resp_tx,
+            })
+            .is_err()
+        {
+            return Vec::new();
         }
+        resp_rx.await.unwrap_or_default()
     }
 
-    // receiver
-    if !sq.receiver.is_empty() {
-        let clause = format!(
-            "({})",
+    /// The recorded range download, if one was started and not finished
+    pub async fn fetch_job(&self) -> Option<FetchJob> {
+        let (resp_tx, resp_rx) = oneshot::channel();
+        if self
+            .tx
+            .send(HistoryMsg::GetFetchJob { resp: resp_tx })
+            .is_err()
+        {
+            return None;
+        }
+        resp_rx.await.ok().flatten()
+    }
+
+    /// Record range download progress (`None` clears it). Resolves after every
+    /// earlier `persist_block` has been written, so `next` never runs ahead.
+    pub async fn set_fetch_job(&self, job: Option<FetchJob>) {
+        let (resp_tx, resp_rx) = oneshot::channel();
+        let _ = self.tx.send(HistoryMsg::SetFetchJob {
+            job,
+            resp: resp_tx,
+        });
+        let _ = resp_rx.await;
+    }
+
+    /// Standing watch rules, oldest first
+    pub async fn watch_rules(&self) -> Vec<WatchRule> {
+        let (resp_tx, resp_rx) = oneshot::channel();
+        if self
+            .tx
+            .send(HistoryMsg::ListWatches { resp: resp_tx })
+            .is_err()
+        {
+            return Vec::new();
+        }
+        resp_rx.await.unwrap_or_default()
+    }
+
+    /// Replace the stored watch rules
+    pub fn put_watch_rules(&self, rules: Vec<WatchRule>) {
+        let _ = self.tx.send(HistoryMsg::PutWatches(rules));
+    }
+
+    /// Record a written export; returns it with its row id
+    pub async fn put_export(&self, mut record: ExportRecord) -> ExportRecord {
+        let (resp_tx, resp_rx) = oneshot::channel();
+        if self
+            .tx
+            .send(HistoryMsg::PutExport {
+                record: record.clone(),
+                resp: resp_tx,
+            })
+            .is_ok()
+        {
+            record.id = resp_rx.await.ok().flatten().unwrap_or_default();
+        }
+        record
+    }
+
+    /// Recorded exports, newest first
+    pub async fn exports(&self, limit: usize) -> Vec<ExportRecord> {
+        let (resp_tx, resp_rx) = oneshot::channel();
+        if self
+            .tx
+            .send(HistoryMsg::ListExports { limit, resp: resp_tx })
+            .is_err()
+        {
+            return Vec::new();
+        }
+        resp_rx.await.unwrap_or_default()
+    }
+
+    /// Forget an export record (the file stays)
+    pub fn del_export(&self, id: i64) {
+        let _ = self.tx.send(HistoryMsg::DelExport(id));
+    }
+
+    /// Usage counters saved by an earlier session (opt-in telemetry)
+    pub async fn telemetry(&self) -> Option<TelemetryCounters> {
+        let (resp_tx, resp_rx) = oneshot::channel();
+        if self
+            .tx
+            .send(HistoryMsg::GetTelemetry { resp: resp_tx })
+            .is_err()
+        {
+            return None;
+        }
+        resp_rx.await.ok().flatten()
+    }
+
+    /// Replace the saved usage counters (`None` purges them)
+    pub async fn set_telemetry(&self, counters: Option<TelemetryCounters>) {
+        let (resp_tx, resp_rx) = oneshot::channel();
+        let _ = self.tx.send(HistoryMsg::SetTelemetry {
+            counters,
+            resp: resp_tx,
+        });
+        let _ = resp_rx.await;
+    }
+
+    /// Write everything queued so far, close the database and stop the
+    /// writer (the last step on quit). False when `timeout` ran out first.
+    pub async fn shutdown(&self, timeout: std::time::Duration) -> bool {
+        let (resp_tx, resp_rx) = oneshot::channel();
+        if self.tx.send(HistoryMsg::Shutdown { resp: resp_tx }).is_err() {
+            return true;
+        }
+        matches!(tokio::time::timeout(timeout, resp_rx).await, Ok(Ok(())))
+    }
+
+    /// Stored `(height, hash)` for blocks in `from..=to`
+    pub async fn block_hashes(&self, from: u64, to: u64) -> Vec<(u64, String)> {
+        let (resp_tx, resp_rx) = oneshot::channel();
+        if self
+            .tx
+            .send(HistoryMsg::BlockHashes {
+                from,
+                to,
+                resp: resp_tx,
+            })
+            .is_err()
+        {
+            return Vec::new();
+        }
+        resp_rx.await.unwrap_or_default()
+    }
+
+    /// One page of stored blocks (with their txs) for the History browser, newest first
+    pub async fn browse_blocks(&self, range: &BrowseRange, cursor: BrowseCursor, limit: usize) -> Vec<BlockPersist> {
+        let (resp_tx, resp_rx) = oneshot::channel();
+        if self
+            .tx
+            .send(HistoryMsg::BrowseBlocks {
+                range: range.clone(),
+                cursor,
+                limit,
+                resp: resp_tx,
+            })
+            .is_err()
+        {
+            return Vec::new();
+        }
+        resp_rx.await.unwrap_or_default()
+    }
+
+    /// Related-tx candidates near a tx: same accounts, or its hash / ids in the stored JSON
+    pub async fn related_txs(&self, query: &RelatedQuery, limit: usize) -> Vec<TxPersist> {
+        let (resp_tx, resp_rx) = oneshot::channel();
+        if self
+            .tx
+            .send(HistoryMsg::RelatedTxs {
+                query: query.clone(),
+                limit,
+                resp: resp_tx,
+            })
+            .is_err()
+        {
+            return Vec::new();
+        }
+        resp_rx.await.unwrap_or_default()
+    }
+
+    /// Newest analyses matching a Risk browser query
+    pub async fn list_analyses(&self, query: &str, limit: usize) -> Vec<RiskHit> {
+        let (resp_tx, resp_rx) = oneshot::channel();
+        if self
+            .tx
+            .send(HistoryMsg::ListAnalyses {
+                query: parse_risk_query(query),
+                limit,
+                resp: resp_tx,
+            })
+            .is_err()
+        {
+            return Vec::new();
+        }
+        resp_rx.await.unwrap_or_default()
+    }
+
+    /// Txs to `receiver` in blocks at or after `since_ms`, newest first
+    pub async fn contract_txs(&self, receiver: &str, since_ms: i64, limit: usize) -> Vec<ContractTx> {
+        let (resp_tx, resp_rx) = oneshot::channel();
+        if self
+            .tx
+            .send(HistoryMsg::ContractTxs {
+                receiver: receiver.to_string(),
+                since_ms,
+                limit,
+                resp: resp_tx,
+            })
+            .is_err()
+        {
+            return Vec::new();
+        }
+        resp_rx.await.unwrap_or_default()
+    }
+
+    /// Newest txs that create or delete an account
+    pub async fn account_txs(&self, limit: usize) -> Vec<AccountTx> {
+        let (resp_tx, resp_rx) = oneshot::channel();
+        if self.tx.send(HistoryMsg::AccountTxs { limit, resp: resp_tx }).is_err() {
+            return Vec::new();
+        }
+        resp_rx.await.unwrap_or_default()
+    }
+}
+
+// Search query parser: signer: receiver: acct: method: action: from: to: hash: + free text
+#[cfg(feature = "native")]
+struct SearchQuery {
+    signer: Vec<String>,
+    receiver: Vec<String>,
+    acct: Vec<String>,
+    method: Vec<String>,
+    action: Vec<String>,
+    hash: Vec<String>,
+    from_height: Option<i64>,
+    to_height: Option<i64>,
+    free: Vec<String>,
+}
+
+#[cfg(feature = "native")]
+fn parse_search_query(q: &str) -> SearchQuery {
+    let mut sq = SearchQuery {
+        signer: vec![],
+        receiver: vec![],
+        acct: vec![],
+        method: vec![],
+        action: vec![],
+        hash: vec![],
+        from_height: None,
+        to_height: None,
+        free: vec![],
+    };
+
+    for tok in q.split_whitespace() {
+        if let Some((k, v)) = tok.split_once(':') {
+            let k = k.to_lowercase();
+            let v = v.to_lowercase();
+            match k.as_str() {
+                "signer" => sq.signer.push(v),
+                "receiver" | "rcv" => sq.receiver.push(v),
+                "acct" | "account" => sq.acct.push(v),
+                "method" => sq.method.push(v),
+                "action" => sq.action.push(v),
+                "hash" => sq.hash.push(v),
+                "from" => sq.from_height = v.parse().ok(),
+                "to" => sq.to_height = v.parse().ok(),
+                _ => sq.free.push(tok.to_lowercase()),
+            }
+        } else if !tok.is_empty() {
+            sq.free.push(tok.to_lowercase());
+        }
+    }
+    sq
+}
+
+/// Most hits one search returns
+pub const MAX_SEARCH_RESULTS: usize = 500;
+
+// ----- ranking -----
+/// Rows fetched per chunk while collecting candidates (keyset paginated, newest first)
+#[cfg(feature = "native")]
+const SEARCH_CHUNK: usize = 500;
+/// Upper bound on ranked candidates per search
+#[cfg(feature = "native")]
+const SEARCH_MAX_CANDIDATES: usize = 2_000;
+/// Stop scanning further chunks after this long once `limit` candidates are in hand
+#[cfg(feature = "native")]
+const SEARCH_BUDGET: std::time::Duration = std::time::Duration::from_millis(150);
+
+#[cfg(feature = "native")]
+/// Score weights: FTS relevance (normalized 0..1) vs. recency decay (0..1)
+const RANK_RELEVANCE_WEIGHT: f64 = 0.6;
+#[cfg(feature = "native")]
+const RANK_RECENCY_WEIGHT: f64 = 0.4;
+#[cfg(feature = "native")]
+/// Recency halves every 6 hours, measured from the newest candidate
+const RANK_RECENCY_HALF_LIFE_MS: f64 = 6.0 * 3_600_000.0;
+#[cfg(feature = "native")]
+/// A query term equal to the full tx hash always wins
+const RANK_EXACT_HASH_BOOST: f64 = 10.0;
+
+#[cfg(feature = "native")]
+/// A filtered row awaiting ranking
+struct Candidate {
+    hit: HistoryHit,
+    /// Negated bm25 (higher = more relevant); 0.0 when the query has no text terms
+    relevance: f64,
+}
+
+#[cfg(feature = "native")]
+/// Order candidates by relevance + recency (+ exact-hash boost), keep the best `limit`,
+/// then group hits by account (signer, else receiver): groups are ordered by their best
+/// hit and keep score order inside.
+fn rank_candidates(cands: Vec<Candidate>, hash_terms: &[String], limit: usize) -> Vec<HistoryHit> {
+    let newest = cands.iter().map(|c| c.hit.ts_ms).max().unwrap_or(0);
+    let max_rel = cands.iter().map(|c| c.relevance).fold(0.0, f64::max);
+
+    let mut scored: Vec<(f64, HistoryHit)> = cands
+        .into_iter()
+        .map(|c| {
+            let rel = if max_rel > 0.0 { c.relevance / max_rel } else { 0.0 };
+            let age_ms = (newest - c.hit.ts_ms).max(0) as f64;
+            let recency = 0.5f64.powf(age_ms / RANK_RECENCY_HALF_LIFE_MS);
+            let lower_hash = c.hit.hash.to_lowercase();
+            let boost = if hash_terms.contains(&lower_hash) { RANK_EXACT_HASH_BOOST } else { 0.0 };
+            (RANK_RELEVANCE_WEIGHT * rel + RANK_RECENCY_WEIGHT * recency + boost, c.hit)
+        })
+        .collect();
+    scored.sort_by(|a, b| {
+        b.0.total_cmp(&a.0)
+            .then_with(|| b.1.height.cmp(&a.1.height))
+            .then_with(|| a.1.hash.cmp(&b.1.hash))
+    });
+    scored.truncate(limit);
+
+    // Stable grouping: first appearance of an account fixes its group position
+    let account = |h: &HistoryHit| h.signer.clone().or_else(|| h.receiver.clone()).unwrap_or_default();
+    let mut group_rank: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    for (_, hit) in &scored {
+        let next = group_rank.len();
+        group_rank.entry(account(hit)).or_insert(next);
+    }
+    let mut hits: Vec<(usize, usize, HistoryHit)> = scored
+        .into_iter()
+        .enumerate()
+        .map(|(i, (_, hit))| (group_rank[&account(&hit)], i, hit))
+        .collect();
+    hits.sort_by_key(|(group, i, _)| (*group, *i));
+    hits.into_iter().map(|(_, _, hit)| hit).collect()
+}
+
+/// FTS5 match expression (prefix OR of every text token), `None` if nothing to match
+#[cfg(feature = "native")]
+fn fts_match_expr(sq: &SearchQuery) -> Option<String> {
+    let tokens: Vec<String> = sq
+        .free
+        .iter()
+        .chain(&sq.signer)
+        .chain(&sq.receiver)
+        .chain(&sq.acct)
+        .chain(&sq.method)
+        .chain(&sq.action)
+        .flat_map(|term| {
+            term.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+                .filter(|t| !t.is_empty())
+                .map(|t| format!("\"{}\"*", t.replace('"', "")))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    (!tokens.is_empty()).then(|| tokens.join(" OR "))
+}
+
+/// bm25 relevance (negated, higher = better) for the given rowids
+#[cfg(feature = "native")]
+fn fts_relevance(
+    conn: &Connection,
+    expr: &str,
+    rowids: &[i64],
+) -> Result<std::collections::HashMap<i64, f64>> {
+    let ids = serde_json::to_string(rowids)?;
+    // Accounts weigh more than action payload text
+    let mut stmt = conn.prepare(
+        "SELECT rowid, bm25(txs_fts, 3.0, 3.0, 1.0) FROM txs_fts
+         WHERE txs_fts MATCH ? AND rowid IN (SELECT value FROM json_each(?))",
+    )?;
+    let rows = stmt.query_map(params![expr, ids], |row| {
+        Ok((row.get::<_, i64>(0)?, -row.get::<_, f64>(1)?))
+    })?;
+    Ok(rows.flatten().collect())
+}
+
+#[cfg(feature = "native")]
+fn search_db(conn: &Connection, query: &str, limit: usize, since_ms: Option<i64>) -> Result<Vec<HistoryHit>> {
+    let sq = parse_search_query(query);
+    let limit = std::cmp::min(limit, MAX_SEARCH_RESULTS);
+    let mut where_clauses: Vec<String> = Vec::new();
+    let mut params_vec: Vec<Box<dyn ToSql>> = Vec::new();
+
+    // acct: signer OR receiver
+    if !sq.acct.is_empty() {
+        for a in &sq.acct {
+            where_clauses.push("(LOWER(t.signer) LIKE ? OR LOWER(t.receiver) LIKE ?)".to_string());
+            let pattern = format!("%{a}%");
+            params_vec.push(Box::new(pattern.clone()));
+            params_vec.push(Box::new(pattern));
+        }
+    }
+
+    // signer
+    if !sq.signer.is_empty() {
+        let clause = format!(
+            "({})",
+            vec!["LOWER(t.signer) LIKE ?"; sq.signer.len()].join(" OR ")
+        );
+        where_clauses.push(clause);
+        for s in &sq.signer {
+            params_vec.push(Box::new(format!("%{s}%")));
+        }
+    }
+
+    // receiver
+    if !sq.receiver.is_empty() {
+        let clause = format!(
+            "({})",
             vec!["LOWER(t.receiver) LIKE ?"; sq.receiver.len()].join(" OR ")
         );
         where_clauses.push(clause);
@@ -420,6 +1203,12 @@ fn search_db(conn: &Connection, query: &str, limit: usize) -> Result<Vec<History
         params_vec.push(Box::new(to_h));
     }
 
+    // since: block timestamp lower bound
+    if let Some(since) = since_ms {
+        where_clauses.push("b.ts_ms >= ?".to_string());
+        params_vec.push(Box::new(since));
+    }
+
     // method/action: LIKE on actions_json
     if !sq.method.is_empty() {
         let clause = format!(
@@ -451,33 +1240,199 @@ fn search_db(conn: &Connection, query: &str, limit: usize) -> Result<Vec<History
         }
     }
 
-    if !where_clauses.is_empty() {
-        sql.push_str(" WHERE ");
-        sql.push_str(&where_clauses.join(" AND "));
+    // Collect candidates newest-first in keyset-paginated chunks
+    let started = std::time::Instant::now();
+    let mut rowids = Vec::new();
+    let mut cands: Vec<Candidate> = Vec::new();
+    let mut cursor: Option<(i64, String)> = None;
+    loop {
+        let mut clauses = where_clauses.clone();
+        let mut chunk_params: Vec<&dyn ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
+        if let Some((h, hash)) = &cursor {
+            clauses.push("(t.height < ? OR (t.height = ? AND t.hash > ?))".to_string());
+            chunk_params.extend([h as &dyn ToSql, h, hash]);
+        }
+        let mut sql = String::from("SELECT t.rowid, t.hash, t.height, b.ts_ms, t.signer, t.receiver, t.actions_json, t.status, t.failure, t.receipt_ids FROM txs t JOIN blocks b ON b.height = t.height");
+        if !clauses.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&clauses.join(" AND "));
+        }
+        sql.push_str(" ORDER BY t.height DESC, t.hash LIMIT ?");
+        let chunk = SEARCH_CHUNK as i64;
+        chunk_params.push(&chunk);
+
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(chunk_params.as_slice(), |row| {
+            let actions_json: Option<String> = row.get(6)?;
+            let methods = actions_json.as_ref().map(|aj| summarize_methods(aj));
+
+            Ok((
+                row.get::<_, i64>(0)?,
+                HistoryHit {
+                    hash: row.get(1)?,
+                    height: row.get::<_, i64>(2)? as u64,
+                    ts_ms: row.get(3)?,
+                    signer: row.get(4)?,
+                    receiver: row.get(5)?,
+                    methods,
+                    actions_json,
+                    status: StoredStatus::from_columns(row.get(7)?, row.get(8)?, row.get(9)?),
+                },
+            ))
+        })?;
+
+        let before = cands.len();
+        for (rowid, hit) in rows.flatten() {
+            cursor = Some((hit.height as i64, hit.hash.clone()));
+            rowids.push(rowid);
+            cands.push(Candidate { hit, relevance: 0.0 });
+        }
+
+        let exhausted = cands.len() - before < SEARCH_CHUNK;
+        let over_budget = cands.len() >= limit && started.elapsed() >= SEARCH_BUDGET;
+        if exhausted || over_budget || cands.len() >= SEARCH_MAX_CANDIDATES {
+            break;
+        }
     }
 
-    sql.push_str(" ORDER BY t.height DESC, t.hash LIMIT ?");
-    params_vec.push(Box::new(std::cmp::min(limit, 500) as i64));
+    if let Some(expr) = fts_match_expr(&sq) {
+        // Relevance is best-effort: a failed MATCH leaves the recency ordering intact
+        if let Ok(scores) = fts_relevance(conn, &expr, &rowids) {
+            for (cand, rowid) in cands.iter_mut().zip(&rowids) {
+                cand.relevance = scores.get(rowid).copied().unwrap_or(0.0);
+            }
+        }
+    }
 
-    let mut stmt = conn.prepare(&sql)?;
-    let param_refs: Vec<&dyn ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
+    let hash_terms: Vec<String> = sq.hash.iter().chain(&sq.free).cloned().collect();
+    Ok(rank_candidates(cands, &hash_terms, limit))
+}
 
-    let rows = stmt.query_map(param_refs.as_slice(), |row| {
-        let actions_json: Option<String> = row.get(5)?;
-        let methods = actions_json.as_ref().map(|aj| summarize_methods(aj));
+/// Create the tables and bring databases written by older versions up to date
+#[cfg(feature = "native")]
+fn init_schema(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS blocks(
+            height INTEGER PRIMARY KEY,
+            hash   TEXT NOT NULL,
+            ts_ms  INTEGER NOT NULL,
+            tx_count INTEGER NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS txs(
+            hash     TEXT PRIMARY KEY,
+            height   INTEGER NOT NULL,
+            signer   TEXT,
+            receiver TEXT,
+            actions_json TEXT,
+            raw_json TEXT,
+            FOREIGN KEY(height) REFERENCES blocks(height) ON DELETE CASCADE
+        );
+        CREATE INDEX IF NOT EXISTS idx_txs_signer   ON txs(signer);
+        CREATE INDEX IF NOT EXISTS idx_txs_receiver ON txs(receiver);
+        CREATE INDEX IF NOT EXISTS idx_txs_height   ON txs(height);
+        CREATE INDEX IF NOT EXISTS idx_txs_hash     ON txs(hash);
+        CREATE INDEX IF NOT EXISTS idx_blocks_height ON blocks(height);
+        CREATE TABLE IF NOT EXISTS marks(
+            label    TEXT PRIMARY KEY,
+            pane     INTEGER NOT NULL,
+            height   INTEGER,
+            tx       TEXT,
+            when_ms  INTEGER NOT NULL,
+            pinned   INTEGER NOT NULL DEFAULT 0,
+            deleted_at INTEGER
+        );
+        CREATE INDEX IF NOT EXISTS idx_marks_pinned ON marks(pinned) WHERE pinned = 1;
+        CREATE TABLE IF NOT EXISTS warm_blocks(
+            height INTEGER PRIMARY KEY,
+            row_json TEXT NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS analyses(
+            hash       TEXT PRIMARY KEY,
+            ts_ms      INTEGER NOT NULL,
+            signer     TEXT,
+            receiver   TEXT,
+            risk_score INTEGER NOT NULL,
+            patterns   TEXT,
+            analysis_json TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_analyses_risk ON analyses(risk_score, ts_ms);
+        CREATE TABLE IF NOT EXISTS receipt_outcomes(
+            receipt_id   TEXT PRIMARY KEY,
+            tx_hash      TEXT NOT NULL,
+            outcome_json TEXT NOT NULL,
+            body_json    TEXT,
+            fetched_ms   INTEGER NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_receipt_outcomes_tx ON receipt_outcomes(tx_hash);
+        CREATE TABLE IF NOT EXISTS exports(
+            id          INTEGER PRIMARY KEY AUTOINCREMENT,
+            created_ms  INTEGER NOT NULL,
+            record_json TEXT NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS watch_rules(
+            id        INTEGER PRIMARY KEY,
+            rule_json TEXT NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS telemetry(
+            id       INTEGER PRIMARY KEY CHECK (id = 1),
+            since_ms INTEGER NOT NULL,
+            counters TEXT NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS fetch_job(
+            id         INTEGER PRIMARY KEY CHECK (id = 1),
+            from_h     INTEGER NOT NULL,
+            to_h       INTEGER NOT NULL,
+            next_h     INTEGER NOT NULL,
+            started_ms INTEGER NOT NULL
+        );
+        CREATE VIRTUAL TABLE IF NOT EXISTS txs_fts USING fts5(
+            signer, receiver, actions,
+            tokenize = "unicode61 tokenchars '_-'"
+        );
+    "#,
+    )?;
 
-        Ok(HistoryHit {
-            hash: row.get(0)?,
-            height: row.get::<_, i64>(1)? as u64,
-            ts_ms: row.get(2)?,
-            signer: row.get(3)?,
-            receiver: row.get(4)?,
-            methods,
-        })
-    })?;
+    // Columns added after the first release; older databases lack them
+    add_column_if_missing(conn, "marks", "deleted_at", "INTEGER")?; // soft delete
+    add_column_if_missing(conn, "txs", "status", "TEXT")?; // 'success' / 'failure' once final
+    add_column_if_missing(conn, "txs", "failure", "TEXT")?; // decoded failure summary
+    add_column_if_missing(conn, "txs", "receipt_ids", "TEXT")?; // JSON array, execution order
 
-    let hits: Vec<HistoryHit> = rows.flatten().collect();
-    Ok(hits)
+    // Relevance index shares rowids with `txs`; build it once for databases
+    // written before it existed
+    let fts_empty: bool =
+        conn.query_row("SELECT NOT EXISTS(SELECT 1 FROM txs_fts)", [], |r| r.get(0))?;
+    if fts_empty {
+        conn.execute_batch(
+            "INSERT INTO txs_fts(rowid, signer, receiver, actions)
+             SELECT rowid, signer, receiver, actions_json FROM txs",
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "native")]
+fn add_column_if_missing(conn: &Connection, table: &str, column: &str, decl: &str) -> Result<()> {
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM pragma_table_info(?) WHERE name = ?)",
+        params![table, column],
+        |r| r.get(0),
+    )?;
+    if !exists {
+        conn.execute_batch(&format!("ALTER TABLE {table} ADD COLUMN {column} {decl}"))?;
+    }
+    Ok(())
+}
+
+/// `raw_json` as text: TEXT rows as written, BLOB / invalid UTF-8 sanitized
+#[cfg(feature = "native")]
+fn raw_json_col(row: &rusqlite::Row<'_>, idx: usize) -> rusqlite::Result<Option<String>> {
+    use rusqlite::types::ValueRef;
+    Ok(match row.get_ref(idx)? {
+        ValueRef::Text(bytes) | ValueRef::Blob(bytes) => Some(crate::safe_text::decode_lossy(bytes.to_vec())),
+        _ => None,
+    })
 }
 
 #[cfg(feature = "native")]
@@ -485,12 +1440,41 @@ fn get_tx_db(conn: &Connection, hash: &str) -> Result<Option<String>> {
     let mut stmt = conn.prepare("SELECT raw_json FROM txs WHERE hash = ?")?;
     let mut rows = stmt.query(params![hash])?;
     if let Some(row) = rows.next()? {
-        let raw: Option<String> = row.get(0)?;
-        return Ok(raw);
+        // Bytes, not String: a row with invalid UTF-8 still opens (sanitized)
+        return Ok(raw_json_col(row, 0)?);
     }
     Ok(None)
 }
 
+#[cfg(feature = "native")]
+fn find_txs_db(conn: &Connection, hashes: &[String]) -> Result<Vec<(HistoryHit, Option<String>)>> {
+    let mut stmt = conn.prepare(
+        "SELECT t.hash, t.height, b.ts_ms, t.signer, t.receiver, t.actions_json, t.raw_json, \
+         t.status, t.failure, t.receipt_ids FROM txs t JOIN blocks b ON b.height = t.height WHERE t.hash = ?",
+    )?;
+    let mut found = Vec::new();
+    for hash in hashes {
+        let mut rows = stmt.query(params![hash])?;
+        if let Some(row) = rows.next()? {
+            let actions_json: Option<String> = row.get(5)?;
+            found.push((
+                HistoryHit {
+                    hash: row.get(0)?,
+                    height: row.get::<_, i64>(1)? as u64,
+                    ts_ms: row.get(2)?,
+                    signer: row.get(3)?,
+                    receiver: row.get(4)?,
+                    methods: actions_json.as_ref().map(|aj| summarize_methods(aj)),
+                    actions_json,
+                    status: StoredStatus::from_columns(row.get(7)?, row.get(8)?, row.get(9)?),
+                },
+                raw_json_col(row, 6)?,
+            ));
+        }
+    }
+    Ok(found)
+}
+
 #[cfg(feature = "native")]
 fn summarize_methods(actions_json: &str) -> String {
     if let Ok(actions) = serde_json::from_str::<Vec<serde_json::Value>>(actions_json) {
@@ -500,6 +1484,12 @@ fn summarize_methods(actions_json: &str) -> String {
                 if let Some(method) = fc.get("method_name").and_then(|m| m.as_str()) {
                     methods.push(method.to_string());
                 }
+            } else if let Some(kind) = a.get("type").and_then(|t| t.as_str()) {
+                // Stored `ActionSummary` (internally tagged)
+                match a.get("method_name").and_then(|m| m.as_str()) {
+                    Some(method) => methods.push(method.to_string()),
+                    None => methods.push(kind.to_string()),
+                }
             } else if let Some(obj) = a.as_object() {
                 if let Some(action_type) = obj.keys().next() {
                     methods.push(action_type.to_string());
@@ -515,7 +1505,7 @@ fn summarize_methods(actions_json: &str) -> String {
 #[cfg(feature = "native")]
 fn list_marks_db(conn: &Connection) -> Result<Vec<PersistedMark>> {
     let mut stmt = conn.prepare(
-        "SELECT label, pane, height, tx, when_ms, pinned FROM marks ORDER BY when_ms DESC",
+        "SELECT label, pane, height, tx, when_ms, pinned, deleted_at FROM marks ORDER BY when_ms DESC",
     )?;
     let mut rows = stmt.query([])?;
     let mut marks = Vec::new();
@@ -527,6 +1517,7 @@ fn list_marks_db(conn: &Connection) -> Result<Vec<PersistedMark>> {
             tx: row.get(3)?,
             when_ms: row.get(4)?,
             pinned: row.get::<_, i64>(5)? != 0,
+            deleted_at: row.get(6)?,
         });
     }
     Ok(marks)
@@ -541,6 +1532,7 @@ fn put_mark_db(_conn: &Connection, stmt: &mut Statement, mark: &PersistedMark) -
         &mark.tx,
         mark.when_ms,
         mark.pinned as i64,
+        mark.deleted_at,
     ])?;
     Ok(())
 }
@@ -568,6 +1560,489 @@ fn clear_marks_db(_conn: &Connection, stmt: &mut Statement) -> Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "native")]
+fn save_warm_db(conn: &Connection, rows: &[(u64, String)]) -> Result<()> {
+    let txc = conn.unchecked_transaction()?;
+    txc.execute("DELETE FROM warm_blocks", [])?;
+    {
+        let mut stmt = txc.prepare("INSERT INTO warm_blocks(height,row_json) VALUES (?,?)")?;
+        for (height, json) in rows {
+            stmt.execute(params![*height as i64, json])?;
+        }
+    }
+    txc.commit()?;
+    Ok(())
+}
+
+#[cfg(feature = "native")]
+fn load_warm_db(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT row_json FROM warm_blocks ORDER BY height DESC")?;
+    let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+    Ok(rows.flatten().collect())
+}
+
+#[cfg(feature = "native")]
+fn put_analysis_db(stmt: &mut Statement, a: &TxAnalysis) -> Result<()> {
+    stmt.execute(params![
+        &a.hash,
+        chrono::Utc::now().timestamp_millis(),
+        &a.signer,
+        &a.receiver,
+        a.risk_score as i64,
+        a.patterns_detected.join(","),
+        serde_json::to_string(a)?,
+    ])?;
+    Ok(())
+}
+
+#[cfg(feature = "native")]
+fn list_analyses_db(conn: &Connection, query: &RiskQuery, limit: usize) -> Result<Vec<RiskHit>> {
+    let mut sql = String::from(
+        "SELECT t.height, a.ts_ms, a.analysis_json FROM analyses a
+         LEFT JOIN txs t ON t.hash = a.hash
+         WHERE a.risk_score >= ?",
+    );
+    let mut params_vec: Vec<Box<dyn ToSql>> = vec![Box::new(query.min_risk as i64)];
+    for term in &query.terms {
+        sql.push_str(" AND (lower(a.signer) LIKE ? OR lower(a.receiver) LIKE ? OR lower(a.patterns) LIKE ?)");
+        let like = format!("%{term}%");
+        for _ in 0..3 {
+            params_vec.push(Box::new(like.clone()));
+        }
+    }
+    sql.push_str(" ORDER BY a.ts_ms DESC LIMIT ?");
+    params_vec.push(Box::new(limit.min(500) as i64));
+
+    let mut stmt = conn.prepare(&sql)?;
+    let params_refs: Vec<&dyn ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
+    let rows = stmt.query_map(params_refs.as_slice(), |row| {
+        Ok((
+            row.get::<_, Option<i64>>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, String>(2)?,
+        ))
+    })?;
+    Ok(rows
+        .flatten()
+        .filter_map(|(height, ts_ms, json)| {
+            Some(RiskHit {
+                height: height.map(|h| h as u64),
+                ts_ms,
+                analysis: serde_json::from_str(&json).ok()?,
+            })
+        })
+        .collect())
+}
+
+#[cfg(feature = "native")]
+fn get_fetch_job_db(conn: &Connection) -> Result<Option<FetchJob>> {
+    let mut stmt = conn.prepare("SELECT from_h, to_h, next_h, started_ms FROM fetch_job WHERE id = 1")?;
+    let mut rows = stmt.query([])?;
+    if let Some(row) = rows.next()? {
+        return Ok(Some(FetchJob {
+            from: row.get::<_, i64>(0)? as u64,
+            to: row.get::<_, i64>(1)? as u64,
+            next: row.get::<_, i64>(2)? as u64,
+            started_ms: row.get(3)?,
+        }));
+    }
+    Ok(None)
+}
+
+#[cfg(feature = "native")]
+fn set_fetch_job_db(conn: &Connection, job: Option<&FetchJob>) -> Result<()> {
+    match job {
+        Some(j) => conn.execute(
+            "INSERT OR REPLACE INTO fetch_job(id,from_h,to_h,next_h,started_ms) VALUES (1,?,?,?,?)",
+            params![j.from as i64, j.to as i64, j.next as i64, j.started_ms],
+        )?,
+        None => conn.execute("DELETE FROM fetch_job", [])?,
+    };
+    Ok(())
+}
+
+#[cfg(feature = "native")]
+fn put_export_db(conn: &Connection, record: &ExportRecord) -> Result<i64> {
+    conn.execute(
+        "INSERT INTO exports(created_ms,record_json) VALUES (?,?)",
+        params![record.created_ms, serde_json::to_string(record)?],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+#[cfg(feature = "native")]
+fn list_exports_db(conn: &Connection, limit: usize) -> Result<Vec<ExportRecord>> {
+    let mut stmt = conn.prepare("SELECT id, record_json FROM exports ORDER BY id DESC LIMIT ?")?;
+    let rows = stmt.query_map(params![limit as i64], |row| {
+        Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+    })?;
+    Ok(rows
+        .flatten()
+        .filter_map(|(id, json)| {
+            let mut record: ExportRecord = serde_json::from_str(&json).ok()?;
+            record.id = id;
+            Some(record)
+        })
+        .collect())
+}
+
+#[cfg(feature = "native")]
+fn list_watches_db(conn: &Connection) -> Result<Vec<WatchRule>> {
+    let mut stmt = conn.prepare("SELECT rule_json FROM watch_rules ORDER BY id")?;
+    let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+    Ok(rows
+        .flatten()
+        .filter_map(|json| serde_json::from_str(&json).ok())
+        .collect())
+}
+
+#[cfg(feature = "native")]
+fn put_watches_db(conn: &Connection, rules: &[WatchRule]) -> Result<()> {
+    let txc = conn.unchecked_transaction()?;
+    txc.execute("DELETE FROM watch_rules", [])?;
+    {
+        let mut stmt = txc.prepare_cached("INSERT INTO watch_rules(id,rule_json) VALUES (?,?)")?;
+        for rule in rules {
+            stmt.execute(params![rule.id, serde_json::to_string(rule)?])?;
+        }
+    }
+    txc.commit()?;
+    Ok(())
+}
+
+#[cfg(feature = "native")]
+fn get_telemetry_db(conn: &Connection) -> Result<Option<TelemetryCounters>> {
+    let mut stmt = conn.prepare("SELECT since_ms, counters FROM telemetry WHERE id = 1")?;
+    let mut rows = stmt.query([])?;
+    if let Some(row) = rows.next()? {
+        let counters: String = row.get(1)?;
+        return Ok(Some((row.get(0)?, serde_json::from_str(&counters)?)));
+    }
+    Ok(None)
+}
+
+#[cfg(feature = "native")]
+fn set_telemetry_db(conn: &Connection, counters: Option<&TelemetryCounters>) -> Result<()> {
+    match counters {
+        Some((since_ms, counts)) => conn.execute(
+            "INSERT OR REPLACE INTO telemetry(id,since_ms,counters) VALUES (1,?,?)",
+            params![since_ms, serde_json::to_string(counts)?],
+        )?,
+        None => conn.execute("DELETE FROM telemetry", [])?,
+    };
+    Ok(())
+}
+
+#[cfg(feature = "native")]
+fn put_outcomes_db(conn: &Connection, outcomes: &[CachedOutcome]) -> Result<()> {
+    let txc = conn.unchecked_transaction()?;
+    {
+        let mut stmt = txc.prepare_cached(
+            "INSERT OR REPLACE INTO receipt_outcomes(receipt_id,tx_hash,outcome_json,body_json,fetched_ms) VALUES (?,?,?,?,?)",
+        )?;
+        for o in outcomes.iter().filter(|o| o.is_final) {
+            stmt.execute(params![
+                o.id,
+                o.tx_hash,
+                o.outcome.to_string(),
+                o.body.as_ref().map(|b| b.to_string()),
+                o.fetched_ms
+            ])?;
+        }
+        // The tx's own entry carries its final status: keep it on the tx row too
+        for o in outcomes.iter().filter(|o| o.is_final && o.id == o.tx_hash) {
+            if let Some(status) = o.body.as_ref().and_then(StoredStatus::from_root) {
+                set_tx_status(&txc, &o.tx_hash, &status)?;
+            }
+        }
+    }
+    txc.commit()?;
+    Ok(())
+}
+
+#[cfg(feature = "native")]
+fn set_tx_status(conn: &Connection, hash: &str, status: &StoredStatus) -> Result<()> {
+    conn.prepare_cached("UPDATE txs SET status = ?, failure = ?, receipt_ids = ? WHERE hash = ?")?
+        .execute(params![
+            if status.failure.is_some() { "failure" } else { "success" },
+            status.failure,
+            serde_json::to_string(&status.receipt_ids)?,
+            hash
+        ])?;
+    Ok(())
+}
+
+#[cfg(feature = "native")]
+fn backfill_columns_db(conn: &Connection) -> Result<BackfillStats> {
+    let mut stats = BackfillStats::default();
+    let txc = conn.unchecked_transaction()?;
+
+    // Rows written before signer / receiver / actions were stored: decode raw_json
+    let undecoded: Vec<(i64, Option<String>)> = txc
+        .prepare(
+            "SELECT rowid, raw_json FROM txs WHERE raw_json IS NOT NULL
+             AND (signer IS NULL OR receiver IS NULL OR actions_json IS NULL)",
+        )?
+        .query_map([], |row| Ok((row.get(0)?, raw_json_col(row, 1)?)))?
+        .flatten()
+        .collect();
+    for (rowid, raw) in undecoded {
+        let Some(Ok(value)) = raw.map(|r| serde_json::from_str::<serde_json::Value>(&r)) else {
+            continue;
+        };
+        // Our TxLite rows, or RPC-shaped `{transaction: {...}}`
+        let tx = value.get("transaction").unwrap_or(&value);
+        let signer = tx["signer_id"].as_str();
+        let receiver = tx["receiver_id"].as_str();
+        let actions = tx.get("actions").filter(|a| !a.is_null()).map(|a| a.to_string());
+        if signer.is_none() && receiver.is_none() && actions.is_none() {
+            continue;
+        }
+        // Only rows where raw_json fills a gap (e.g. TxLite without actions keeps NULL)
+        let changed = txc.execute(
+            "UPDATE txs SET signer = COALESCE(signer, ?1), receiver = COALESCE(receiver, ?2),
+             actions_json = COALESCE(actions_json, ?3) WHERE rowid = ?4
+             AND ((signer IS NULL AND ?1 IS NOT NULL) OR (receiver IS NULL AND ?2 IS NOT NULL)
+                  OR (actions_json IS NULL AND ?3 IS NOT NULL))",
+            params![signer, receiver, actions, rowid],
+        )?;
+        if changed == 0 {
+            continue;
+        }
+        txc.execute("DELETE FROM txs_fts WHERE rowid = ?", params![rowid])?;
+        txc.execute(
+            "INSERT INTO txs_fts(rowid,signer,receiver,actions)
+             SELECT rowid, signer, receiver, actions_json FROM txs WHERE rowid = ?",
+            params![rowid],
+        )?;
+        stats.decoded += 1;
+    }
+
+    // Outcomes stored before the tx row kept its status
+    let roots: Vec<(String, String)> = txc
+        .prepare(
+            "SELECT t.hash, r.body_json FROM txs t JOIN receipt_outcomes r ON r.receipt_id = t.hash
+             WHERE t.status IS NULL AND r.body_json IS NOT NULL",
+        )?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .flatten()
+        .collect();
+    for (hash, body) in roots {
+        let status = serde_json::from_str(&body).ok().and_then(|b| StoredStatus::from_root(&b));
+        if let Some(status) = status {
+            set_tx_status(&txc, &hash, &status)?;
+            stats.statuses += 1;
+        }
+    }
+
+    txc.commit()?;
+    Ok(stats)
+}
+
+#[cfg(feature = "native")]
+fn txs_without_status_db(conn: &Connection, limit: usize) -> Result<Vec<(String, String)>> {
+    let mut stmt = conn.prepare(
+        "SELECT hash, signer FROM txs WHERE status IS NULL AND signer IS NOT NULL
+         ORDER BY height DESC LIMIT ?",
+    )?;
+    let rows = stmt.query_map(params![limit as i64], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    Ok(rows.flatten().collect())
+}
+
+#[cfg(feature = "native")]
+fn tx_outcomes_db(conn: &Connection, tx_hash: &str) -> Result<Vec<CachedOutcome>> {
+    let mut stmt = conn.prepare(
+        "SELECT receipt_id, outcome_json, body_json, fetched_ms FROM receipt_outcomes WHERE tx_hash = ?",
+    )?;
+    let rows = stmt.query_map(params![tx_hash], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, Option<String>>(2)?,
+            row.get::<_, i64>(3)?,
+        ))
+    })?;
+    Ok(rows
+        .flatten()
+        .filter_map(|(id, outcome, body, fetched_ms)| {
+            Some(CachedOutcome {
+                id,
+                tx_hash: tx_hash.to_string(),
+                outcome: serde_json::from_str(&outcome).ok()?,
+                body: body.and_then(|b| serde_json::from_str(&b).ok()),
+                is_final: true,
+                fetched_ms,
+            })
+        })
+        .collect())
+}
+
+#[cfg(feature = "native")]
+fn block_hashes_db(conn: &Connection, from: u64, to: u64) -> Result<Vec<(u64, String)>> {
+    let mut stmt =
+        conn.prepare("SELECT height, hash FROM blocks WHERE height BETWEEN ? AND ? ORDER BY height")?;
+    let rows = stmt.query_map(params![from as i64, to as i64], |row| {
+        Ok((row.get::<_, i64>(0)? as u64, row.get::<_, String>(1)?))
+    })?;
+    Ok(rows.flatten().collect())
+}
+
+#[cfg(feature = "native")]
+fn browse_blocks_db(
+    conn: &Connection,
+    range: &BrowseRange,
+    cursor: BrowseCursor,
+    limit: usize,
+) -> Result<Vec<BlockPersist>> {
+    let (mut lo, mut hi) = (
+        range.min_height.map_or(0, |h| h as i64),
+        range.max_height.map_or(i64::MAX, |h| h as i64),
+    );
+    match cursor {
+        BrowseCursor::Newest => {}
+        BrowseCursor::Before(h) => hi = hi.min(h as i64 - 1),
+        BrowseCursor::After(h) => lo = lo.max(h as i64 + 1),
+    }
+    // Walk up from the cursor for a newer page, then flip to newest first
+    let order = if matches!(cursor, BrowseCursor::After(_)) { "ASC" } else { "DESC" };
+    let mut stmt = conn.prepare(&format!(
+        "SELECT height, hash, ts_ms FROM blocks
+         WHERE height BETWEEN ? AND ? AND ts_ms BETWEEN ? AND ?
+         ORDER BY height {order} LIMIT ?"
+    ))?;
+    let rows = stmt.query_map(
+        params![
+            lo,
+            hi,
+            range.min_ts_ms.unwrap_or(i64::MIN),
+            range.max_ts_ms.unwrap_or(i64::MAX),
+            limit.min(500) as i64
+        ],
+        |row| {
+            Ok(BlockPersist {
+                height: row.get::<_, i64>(0)? as u64,
+                hash: row.get(1)?,
+                ts_ms: row.get(2)?,
+                txs: Vec::new(),
+            })
+        },
+    )?;
+    let mut blocks: Vec<BlockPersist> = rows.flatten().collect();
+    if order == "ASC" {
+        blocks.reverse();
+    }
+    let (Some(newest), Some(oldest)) = (blocks.first(), blocks.last()) else {
+        return Ok(blocks);
+    };
+
+    let mut stmt = conn.prepare(
+        "SELECT hash, height, signer, receiver, actions_json, raw_json FROM txs
+         WHERE height BETWEEN ? AND ? ORDER BY rowid",
+    )?;
+    let txs = stmt.query_map(params![oldest.height as i64, newest.height as i64], |row| {
+        Ok(TxPersist {
+            hash: row.get(0)?,
+            height: row.get::<_, i64>(1)? as u64,
+            signer: row.get(2)?,
+            receiver: row.get(3)?,
+            actions_json: row.get(4)?,
+            raw_json: raw_json_col(row, 5)?,
+        })
+    })?;
+    for tx in txs.flatten() {
+        if let Some(b) = blocks.iter_mut().find(|b| b.height == tx.height) {
+            b.txs.push(tx);
+        }
+    }
+    Ok(blocks)
+}
+
+#[cfg(feature = "native")]
+fn related_txs_db(conn: &Connection, query: &RelatedQuery, limit: usize) -> Result<Vec<TxPersist>> {
+    let window = crate::related::NEARBY_BLOCKS;
+    let mut params_vec: Vec<Box<dyn ToSql>> = vec![
+        Box::new(query.height.saturating_sub(window) as i64),
+        Box::new(query.height.saturating_add(window).min(i64::MAX as u64) as i64),
+        Box::new(query.hash.clone()),
+    ];
+    let mut terms = Vec::new();
+    for account in &query.accounts {
+        terms.push("signer = ? OR receiver = ?");
+        params_vec.push(Box::new(account.clone()));
+        params_vec.push(Box::new(account.clone()));
+    }
+    for id in std::iter::once(&query.hash).chain(&query.ids) {
+        terms.push("raw_json LIKE ?");
+        params_vec.push(Box::new(format!("%{id}%")));
+    }
+    let sql = format!(
+        "SELECT hash, height, signer, receiver, actions_json, raw_json FROM txs
+         WHERE height BETWEEN ? AND ? AND hash != ? AND ({})
+         ORDER BY abs(height - ?) LIMIT ?",
+        terms.join(" OR ")
+    );
+    params_vec.push(Box::new(query.height as i64));
+    params_vec.push(Box::new(limit.min(2000) as i64));
+
+    let mut stmt = conn.prepare(&sql)?;
+    let params_refs: Vec<&dyn ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
+    let rows = stmt.query_map(params_refs.as_slice(), |row| {
+        Ok(TxPersist {
+            hash: row.get(0)?,
+            height: row.get::<_, i64>(1)? as u64,
+            signer: row.get(2)?,
+            receiver: row.get(3)?,
+            actions_json: row.get(4)?,
+            raw_json: raw_json_col(row, 5)?,
+        })
+    })?;
+    Ok(rows.flatten().collect())
+}
+
+#[cfg(feature = "native")]
+fn contract_txs_db(conn: &Connection, receiver: &str, since_ms: i64, limit: usize) -> Result<Vec<ContractTx>> {
+    let mut stmt = conn.prepare(
+        "SELECT t.hash, t.height, b.ts_ms, t.signer, t.actions_json
+         FROM txs t JOIN blocks b ON b.height = t.height
+         WHERE t.receiver = ? AND b.ts_ms >= ?
+         ORDER BY t.height DESC LIMIT ?",
+    )?;
+    let rows = stmt.query_map(params![receiver, since_ms, limit as i64], |row| {
+        Ok(ContractTx {
+            hash: row.get(0)?,
+            height: row.get::<_, i64>(1)? as u64,
+            ts_ms: row.get(2)?,
+            signer: row.get(3)?,
+            actions_json: row.get(4)?,
+        })
+    })?;
+    Ok(rows.flatten().collect())
+}
+
+#[cfg(feature = "native")]
+fn account_txs_db(conn: &Connection, limit: usize) -> Result<Vec<AccountTx>> {
+    // Action types are whole tokens in the FTS index ("type":"CreateAccount")
+    let mut stmt = conn.prepare(
+        "SELECT t.hash, t.height, b.ts_ms, t.signer, t.receiver, t.actions_json
+         FROM txs_fts f
+         JOIN txs t ON t.rowid = f.rowid
+         JOIN blocks b ON b.height = t.height
+         WHERE txs_fts MATCH 'actions:CreateAccount OR actions:DeleteAccount'
+         ORDER BY t.height DESC LIMIT ?",
+    )?;
+    let rows = stmt.query_map(params![limit as i64], |row| {
+        Ok(AccountTx {
+            hash: row.get(0)?,
+            height: row.get::<_, i64>(1)? as u64,
+            ts_ms: row.get(2)?,
+            signer: row.get(3)?,
+            receiver: row.get(4)?,
+            actions_json: row.get(5)?,
+        })
+    })?;
+    Ok(rows.flatten().collect())
+}
+
 // Web stub implementation (in-memory only, no persistence)
 #[cfg(not(feature = "native"))]
 #[derive(Clone)]
@@ -589,6 +2064,18 @@ impl History {
         None
     }
 
+    pub async fn find_txs(&self, _hashes: Vec<String>) -> Vec<(HistoryHit, Option<String>)> {
+        Vec::new()
+    }
+
+    pub async fn backfill_columns(&self) -> BackfillStats {
+        BackfillStats::default()
+    }
+
+    pub async fn txs_without_status(&self, _limit: usize) -> Vec<(String, String)> {
+        Vec::new()
+    }
+
     pub async fn list_marks(&self) -> Vec<PersistedMark> {
         Vec::new()
     }
@@ -597,8 +2084,216 @@ impl History {
 
     pub async fn del_mark(&self, _label: &str) {}
 
+    pub async fn trash_mark(&self, _label: &str, _deleted_at: Option<i64>) {}
+
     pub async fn set_mark_pinned(&self, _label: &str, _pinned: bool) {}
 
     #[allow(dead_code)]
     pub async fn clear_marks(&self) {}
+
+    pub async fn save_warm_blocks(&self, _blocks: &[BlockRow]) {}
+
+    pub async fn load_warm_blocks(&self) -> Vec<BlockRow> {
+        Vec::new()
+    }
+
+    pub fn put_analysis(&self, _analysis: TxAnalysis) {}
+
+    pub fn put_outcomes(&self, _outcomes: Vec<CachedOutcome>) {}
+
+    pub async fn tx_outcomes(&self, _tx_hash: &str) -> Vec<CachedOutcome> {
+        Vec::new()
+    }
+
+    pub async fn fetch_job(&self) -> Option<FetchJob> {
+        None
+    }
+
+    pub async fn set_fetch_job(&self, _job: Option<FetchJob>) {}
+
+    pub async fn watch_rules(&self) -> Vec<WatchRule> {
+        Vec::new()
+    }
+
+    pub fn put_watch_rules(&self, _rules: Vec<WatchRule>) {}
+
+    pub async fn put_export(&self, record: ExportRecord) -> ExportRecord {
+        record
+    }
+
+    pub async fn exports(&self, _limit: usize) -> Vec<ExportRecord> {
+        Vec::new()
+    }
+
+    pub fn del_export(&self, _id: i64) {}
+
+    pub async fn telemetry(&self) -> Option<TelemetryCounters> {
+        None
+    }
+
+    pub async fn set_telemetry(&self, _counters: Option<TelemetryCounters>) {}
+
+    pub async fn shutdown(&self, _timeout: std::time::Duration) -> bool {
+        true
+    }
+
+    pub async fn block_hashes(&self, _from: u64, _to: u64) -> Vec<(u64, String)> {
+        Vec::new()
+    }
+
+    pub async fn browse_blocks(&self, _range: &BrowseRange, _cursor: BrowseCursor, _limit: usize) -> Vec<BlockPersist> {
+        Vec::new()
+    }
+
+    pub async fn related_txs(&self, _query: &RelatedQuery, _limit: usize) -> Vec<TxPersist> {
+        Vec::new()
+    }
+
+    pub async fn list_analyses(&self, _query: &str, _limit: usize) -> Vec<RiskHit> {
+        Vec::new()
+    }
+
+    pub async fn contract_txs(&self, _receiver: &str, _since_ms: i64, _limit: usize) -> Vec<ContractTx> {
+        Vec::new()
+    }
+
+    pub async fn account_txs(&self, _limit: usize) -> Vec<AccountTx> {
+        Vec::new()
+    }
+}
+
+#[cfg(all(test, feature = "native"))]
+mod tests {
+    use super::*;
+
+    fn cand(hash: &str, ts_ms: i64, signer: &str, relevance: f64) -> Candidate {
+        Candidate {
+            hit: HistoryHit {
+                hash: hash.into(),
+                height: ts_ms as u64,
+                ts_ms,
+                signer: Some(signer.into()),
+                receiver: None,
+                methods: None,
+                actions_json: None,
+                status: None,
+            },
+            relevance,
+        }
+    }
+
+    #[test]
+    fn ranks_by_relevance_recency_and_exact_hash() {
+        let hour = 3_600_000;
+        let cands = vec![
+            cand("new", 10 * hour, "a.near", 0.0),
+            cand("relevant", 9 * hour, "b.near", 5.0),
+            cand("Exact", hour, "c.near", 0.0),
+        ];
+        let order: Vec<_> = rank_candidates(cands, &["exact".into()], 10)
+            .into_iter()
+            .map(|h| h.hash)
+            .collect();
+        assert_eq!(order, ["Exact", "relevant", "new"]);
+    }
+
+    #[test]
+    fn parses_risk_threshold_and_terms() {
+        assert_eq!(
+            parse_risk_query(""),
+            RiskQuery { min_risk: HIGH_RISK_SCORE + 1, terms: vec![] }
+        );
+        assert_eq!(
+            parse_risk_query("risk>40 ref-finance MEV"),
+            RiskQuery { min_risk: 41, terms: vec!["ref-finance".into(), "mev".into()] }
+        );
+        assert_eq!(parse_risk_query("RISK>=0").min_risk, 0);
+        assert_eq!(parse_risk_query("risk>x").terms, ["risk>x"]);
+    }
+
+    #[test]
+    fn groups_hits_by_account() {
+        let cands = vec![
+            cand("a1", 100, "a.near", 1.0),
+            cand("b1", 99, "b.near", 0.9),
+            cand("a2", 10, "a.near", 0.1),
+        ];
+        let order: Vec<_> = rank_candidates(cands, &[], 10).into_iter().map(|h| h.hash).collect();
+        assert_eq!(order, ["a1", "a2", "b1"]);
+    }
+
+    #[test]
+    fn migrates_old_databases_and_backfills_decoded_columns_and_statuses() {
+        let conn = Connection::open_in_memory().unwrap();
+        // Schema as written by an older version: no outcome columns, stub rows
+        conn.execute_batch(
+            "CREATE TABLE txs(hash TEXT PRIMARY KEY, height INTEGER NOT NULL, signer TEXT,
+                receiver TEXT, actions_json TEXT, raw_json TEXT);
+             INSERT INTO txs(hash, height, raw_json) VALUES
+                ('T1', 7, '{\"hash\":\"T1\",\"signer_id\":\"alice.near\",\"receiver_id\":\"intents.near\",\"actions\":[{\"type\":\"CreateAccount\"}]}'),
+                ('T2', 7, '{\"hash\":\"T2\"}');",
+        )
+        .unwrap();
+        init_schema(&conn).unwrap();
+        init_schema(&conn).unwrap(); // idempotent
+        conn.execute("INSERT INTO blocks VALUES (7, 'B7', 1000, 2)", []).unwrap();
+
+        // A final outcome stored before tx rows kept their status
+        let root = serde_json::json!({
+            "status": {"Failure": {"ActionError": {"index": 0, "kind": {"AccountDoesNotExist": {"account_id": "bob.near"}}}}},
+            "receipt_ids": ["R1"],
+        });
+        conn.execute(
+            "INSERT INTO receipt_outcomes VALUES ('T1', 'T1', '{}', ?, 0)",
+            params![root.to_string()],
+        )
+        .unwrap();
+
+        let stats = backfill_columns_db(&conn).unwrap();
+        assert_eq!(stats, BackfillStats { decoded: 1, statuses: 1 });
+        assert_eq!(backfill_columns_db(&conn).unwrap(), BackfillStats::default());
+
+        let found = find_txs_db(&conn, &["T1".into(), "T2".into()]).unwrap();
+        let (t1, t2) = (&found[0].0, &found[1].0);
+        assert_eq!(t1.signer.as_deref(), Some("alice.near"));
+        let status = t1.status.as_ref().unwrap();
+        assert_eq!(status.receipt_ids, ["R1"]);
+        assert_eq!(status.label(), "failure: Action #0: account bob.near does not exist");
+        assert_eq!((t2.signer.as_deref(), t2.status.as_ref()), (None, None));
+        assert_eq!(txs_without_status_db(&conn, 10).unwrap(), []);
+
+        // Live fetches (outcome cache) keep the tx row's status current
+        let ok = CachedOutcome {
+            id: "T1".into(),
+            tx_hash: "T1".into(),
+            outcome: serde_json::json!({}),
+            body: Some(serde_json::json!({"status": {"SuccessValue": ""}, "receipt_ids": []})),
+            is_final: true,
+            fetched_ms: 1,
+        };
+        put_outcomes_db(&conn, &[ok]).unwrap();
+        let found = find_txs_db(&conn, &["T1".into()]).unwrap();
+        assert_eq!(found[0].0.status.as_ref().map(StoredStatus::label).as_deref(), Some("success"));
+
+        let hits = search_db(&conn, "alice.near", 10, None).unwrap();
+        assert!(hits[0].status.is_some());
+        // `since` is applied in SQL (block ts 1000)
+        assert!(search_db(&conn, "alice.near", 10, Some(2_000)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn backfill_skips_rows_whose_raw_json_adds_nothing() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        // TxLite without actions: signer / receiver stored, actions_json stays NULL
+        conn.execute_batch(
+            "INSERT INTO blocks VALUES (7, 'B7', 1000, 1);
+             INSERT INTO txs(hash, height, signer, receiver, raw_json) VALUES
+                ('T1', 7, 'alice.near', 'intents.near',
+                 '{\"hash\":\"T1\",\"signer_id\":\"alice.near\",\"receiver_id\":\"intents.near\",\"actions\":null}');",
+        )
+        .unwrap();
+        assert_eq!(backfill_columns_db(&conn).unwrap(), BackfillStats::default());
+        assert_eq!(backfill_columns_db(&conn).unwrap(), BackfillStats::default());
+    }
 }
@@ -0,0 +1,310 @@
+//! History browser (Ctrl+B): page through the SQLite store by height / date
+//!
+//! A split view over stored blocks (blocks · txs · details) with its own range,
+//! page, selection and details scroll. It never touches the live Blocks pane,
+//! so research on old data leaves auto-follow, the selection and the filter
+//! exactly as they were. Pages are fetched by the native runtime
+//! (`History::browse_blocks`) and handed over with `set_page`.
+//!
+//! Range syntax (tokens combine, all bounds inclusive, dates in UTC):
+//! - `120000000..120000500`, `120000000..`, `..120000500` - heights
+//! - `120000500` - start at that height and page towards older blocks
+//! - `2024-05-01`, `2024-05-01..2024-05-03`, `2024-05-01T12:00..` - dates / times
+
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::history::BlockPersist;
+use crate::json_pretty::JsonFormat;
+
+/// Blocks per page
+pub const PAGE_BLOCKS: usize = 50;
+
+/// Inclusive block filter (None = unbounded)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BrowseRange {
+    pub min_height: Option<u64>,
+    pub max_height: Option<u64>,
+    pub min_ts_ms: Option<i64>,
+    pub max_ts_ms: Option<i64>,
+}
+
+/// Which page of the range to load
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BrowseCursor {
+    /// Newest blocks in the range
+    Newest,
+    /// Blocks below this height (older page)
+    Before(u64),
+    /// Blocks above this height (newer page)
+    After(u64),
+}
+
+/// Parse the range text (empty = the whole store)
+pub fn parse_range(q: &str) -> Result<BrowseRange, String> {
+    let mut range = BrowseRange::default();
+    for tok in q.split_whitespace() {
+        let (lo, hi) = match tok.split_once("..") {
+            Some((lo, hi)) => (lo, hi),
+            None if tok.contains('-') => (tok, tok), // a single day
+            None => ("", tok),                       // start height
+        };
+        if [lo, hi].iter().all(|s| s.is_empty()) {
+            return Err(format!("empty range: {tok}"));
+        }
+        if [lo, hi].iter().any(|s| s.contains('-')) {
+            range.min_ts_ms = parse_time(lo, false)?.or(range.min_ts_ms);
+            range.max_ts_ms = parse_time(hi, true)?.or(range.max_ts_ms);
+        } else {
+            range.min_height = parse_height(lo)?.or(range.min_height);
+            range.max_height = parse_height(hi)?.or(range.max_height);
+        }
+    }
+    if let (Some(lo), Some(hi)) = (range.min_height, range.max_height) {
+        if lo > hi {
+            return Err(format!("height range is reversed: {lo}..{hi}"));
+        }
+    }
+    if let (Some(lo), Some(hi)) = (range.min_ts_ms, range.max_ts_ms) {
+        if lo > hi {
+            return Err("date range is reversed".to_string());
+        }
+    }
+    Ok(range)
+}
+
+fn parse_height(s: &str) -> Result<Option<u64>, String> {
+    if s.is_empty() {
+        return Ok(None);
+    }
+    s.replace('_', "")
+        .parse()
+        .map(Some)
+        .map_err(|_| format!("not a block height: {s}"))
+}
+
+/// `YYYY-MM-DD` (start or end of the day) or `YYYY-MM-DDTHH:MM` as unix ms
+fn parse_time(s: &str, end: bool) -> Result<Option<i64>, String> {
+    if s.is_empty() {
+        return Ok(None);
+    }
+    if let Ok(dt) = NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M") {
+        let ms = dt.and_utc().timestamp_millis();
+        return Ok(Some(if end { ms + 59_999 } else { ms }));
+    }
+    let day = NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|_| format!("not a date: {s}"))?;
+    let start = day
+        .and_hms_opt(0, 0, 0)
+        .unwrap_or_default()
+        .and_utc()
+        .timestamp_millis();
+    Ok(Some(if end { start + 86_400_000 - 1 } else { start }))
+}
+
+/// Column with keyboard focus
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BrowseFocus {
+    Blocks,
+    Txs,
+    Details,
+}
+
+/// History browser state (owned by `App` while the overlay is open)
+#[derive(Clone, Debug)]
+pub struct HistoryBrowser {
+    /// Range text being edited
+    pub query: String,
+    /// Range of the page on screen
+    pub range: BrowseRange,
+    pub error: Option<String>,
+    /// Current page, newest first
+    pub blocks: Vec<BlockPersist>,
+    pub sel_block: usize,
+    pub sel_tx: usize,
+    pub focus: BrowseFocus,
+    pub details_scroll: usize,
+    /// Shown when paging ran off the end of the range
+    pub note: Option<&'static str>,
+}
+
+impl Default for HistoryBrowser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HistoryBrowser {
+    pub fn new() -> Self {
+        Self {
+            query: String::new(),
+            range: BrowseRange::default(),
+            error: None,
+            blocks: Vec::new(),
+            sel_block: 0,
+            sel_tx: 0,
+            focus: BrowseFocus::Blocks,
+            details_scroll: 0,
+            note: None,
+        }
+    }
+
+    /// Parse the edited range; the page to load, or None (error shown) if invalid
+    pub fn apply_query(&mut self) -> Option<BrowseCursor> {
+        match parse_range(&self.query) {
+            Ok(range) => {
+                self.range = range;
+                self.error = None;
+                Some(BrowseCursor::Newest)
+            }
+            Err(e) => {
+                self.error = Some(e);
+                None
+            }
+        }
+    }
+
+    pub fn older(&self) -> Option<BrowseCursor> {
+        self.blocks.last().map(|b| BrowseCursor::Before(b.height))
+    }
+
+    pub fn newer(&self) -> Option<BrowseCursor> {
+        self.blocks.first().map(|b| BrowseCursor::After(b.height))
+    }
+
+    /// Install a fetched page. Paging past either end keeps the current page.
+    pub fn set_page(&mut self, cursor: BrowseCursor, blocks: Vec<BlockPersist>) {
+        self.note = None;
+        if blocks.is_empty() {
+            match cursor {
+                BrowseCursor::Before(_) => self.note = Some("no older blocks in range"),
+                BrowseCursor::After(_) => self.note = Some("no newer blocks in range"),
+                BrowseCursor::Newest => {
+                    self.note = Some("no stored blocks in range");
+                    self.blocks.clear();
+                }
+            }
+            return;
+        }
+        self.blocks = blocks;
+        // Coming from a newer page, land next to it; otherwise at the top
+        self.sel_block = match cursor {
+            BrowseCursor::After(_) => self.blocks.len() - 1,
+            _ => 0,
+        };
+        self.sel_tx = 0;
+        self.details_scroll = 0;
+    }
+
+    pub fn selected_block(&self) -> Option<&BlockPersist> {
+        self.blocks.get(self.sel_block)
+    }
+
+    pub fn cycle_focus(&mut self) {
+        self.focus = match self.focus {
+            BrowseFocus::Blocks => BrowseFocus::Txs,
+            BrowseFocus::Txs => BrowseFocus::Details,
+            BrowseFocus::Details => BrowseFocus::Blocks,
+        };
+    }
+
+    /// Move the focused column's selection (or scroll Details) by `delta`
+    pub fn move_by(&mut self, delta: isize) {
+        let step = |i: usize, len: usize| i.saturating_add_signed(delta).min(len.saturating_sub(1));
+        match self.focus {
+            BrowseFocus::Blocks => {
+                let sel = step(self.sel_block, self.blocks.len());
+                if sel != self.sel_block {
+                    self.sel_block = sel;
+                    self.sel_tx = 0;
+                    self.details_scroll = 0;
+                }
+            }
+            BrowseFocus::Txs => {
+                let len = self.selected_block().map_or(0, |b| b.txs.len());
+                let sel = step(self.sel_tx, len);
+                if sel != self.sel_tx {
+                    self.sel_tx = sel;
+                    self.details_scroll = 0;
+                }
+            }
+            BrowseFocus::Details => {
+                self.details_scroll = self.details_scroll.saturating_add_signed(delta);
+            }
+        }
+    }
+
+    /// Details text: the selected tx's stored JSON, or a block summary when it has none
+    pub fn details(&self, fmt: JsonFormat) -> String {
+        let Some(block) = self.selected_block() else {
+            return String::new();
+        };
+        let Some(tx) = block.txs.get(self.sel_tx) else {
+            return format!("Block #{} ({})\nno transactions", block.height, block.hash);
+        };
+        tx.raw_json
+            .as_deref()
+            .map(|raw| crate::safe_text::raw_details(raw, fmt))
+            .unwrap_or_else(|| {
+                format!(
+                    "{}\n{} → {}",
+                    tx.hash,
+                    tx.signer.as_deref().unwrap_or("?"),
+                    tx.receiver.as_deref().unwrap_or("?")
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::fixtures::{stored_block, stored_tx};
+
+    /// Stored block `height` with `txs` txs
+    fn block(height: u64, txs: usize) -> BlockPersist {
+        let txs = (0..txs)
+            .map(|i| stored_tx(&format!("tx{height}_{i}"), height, "alice.near", "bob.near"))
+            .collect();
+        stored_block(height, txs)
+    }
+
+    #[test]
+    fn parses_ranges_and_pages_without_losing_place() {
+        assert_eq!(parse_range(""), Ok(BrowseRange::default()));
+        let r = parse_range("100..200").unwrap();
+        assert_eq!((r.min_height, r.max_height), (Some(100), Some(200)));
+        assert_eq!(parse_range("150").unwrap().max_height, Some(150));
+        assert_eq!(parse_range("1_000..").unwrap().min_height, Some(1000));
+
+        let day = parse_range("2024-05-01").unwrap();
+        assert_eq!(day.min_ts_ms, Some(1_714_521_600_000));
+        assert_eq!(day.max_ts_ms, Some(1_714_521_600_000 + 86_400_000 - 1));
+        let r = parse_range("2024-05-01T12:00.. 100..").unwrap();
+        assert_eq!(r.min_ts_ms, Some(1_714_521_600_000 + 12 * 3_600_000));
+        assert_eq!((r.max_ts_ms, r.min_height), (None, Some(100)));
+        assert!(parse_range("200..100").is_err());
+        assert!(parse_range("abc").is_err());
+        assert!(parse_range("..").is_err());
+
+        let mut b = HistoryBrowser::new();
+        b.query = "oops..".into();
+        assert_eq!(b.apply_query(), None);
+        assert!(b.error.is_some());
+        b.query = "..300".into();
+        assert_eq!(b.apply_query(), Some(BrowseCursor::Newest));
+
+        b.set_page(BrowseCursor::Newest, vec![block(300, 2), block(299, 0)]);
+        b.focus = BrowseFocus::Txs;
+        b.move_by(5);
+        assert_eq!(b.sel_tx, 1);
+        assert_eq!(b.older(), Some(BrowseCursor::Before(299)));
+
+        // Past the oldest block: the page stays, with a note
+        b.set_page(BrowseCursor::Before(299), Vec::new());
+        assert_eq!((b.blocks.len(), b.sel_tx), (2, 1));
+        assert!(b.note.is_some());
+
+        b.set_page(BrowseCursor::After(298), vec![block(300, 2), block(299, 0)]);
+        assert_eq!((b.sel_block, b.sel_tx), (1, 0));
+        assert!(b.details(JsonFormat::default()).contains("no transactions"));
+    }
+}
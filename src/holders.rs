@@ -0,0 +1,342 @@
+//! FT holder lookups (FastNEAR Explorer API)
+//!
+//! The state inspector's Holders tab (Tab in Ctrl+K) ranks a fungible
+//! token's largest holders from `/v1/ft/{token}/top`, and shows the balance
+//! and rank of the selected tx's signer from `/v1/account/{signer}/ft`.
+//! `ft_metadata` (over RPC, best effort) scales the raw balances. Results are
+//! cached for `CACHE_TTL_SECS`, and requests are spaced `MIN_INTERVAL_MS`
+//! apart so paging through txs doesn't hammer the API.
+
+use std::collections::HashMap;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+
+#[cfg(target_arch = "wasm32")]
+use web_time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+
+/// FastNEAR API base (`FASTNEAR_API_URL` overrides)
+pub const DEFAULT_API_URL: &str = "https://api.fastnear.com";
+/// How long a lookup is reused before it is fetched again
+pub const CACHE_TTL_SECS: u64 = 120;
+/// Minimum spacing between API lookups (later ones wait their turn)
+pub const MIN_INTERVAL_MS: u64 = 1_500;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Holder {
+    pub account: String,
+    /// Raw balance (smallest unit)
+    pub balance: u128,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FtMetadata {
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+/// One lookup: the token's top holders and, optionally, one account's balance
+#[derive(Clone, Debug, PartialEq)]
+pub struct TokenHolders {
+    pub token: String,
+    /// Largest first
+    pub holders: Vec<Holder>,
+    pub metadata: Option<FtMetadata>,
+    /// Account looked up alongside, with its balance (`None` = lookup failed)
+    pub account: Option<(String, Option<u128>)>,
+}
+
+impl TokenHolders {
+    /// 1-based rank among the listed holders
+    pub fn rank(&self, account: &str) -> Option<usize> {
+        self.holders
+            .iter()
+            .position(|h| h.account == account)
+            .map(|i| i + 1)
+    }
+
+    /// Balance with the token's decimals and symbol (raw when metadata is unknown)
+    pub fn format_balance(&self, raw: u128) -> String {
+        match &self.metadata {
+            Some(m) => format!("{} {}", format_amount(raw, m.decimals), m.symbol),
+            None => raw.to_string(),
+        }
+    }
+}
+
+/// `raw / 10^decimals`, digits grouped, at most 4 fractional digits
+pub fn format_amount(raw: u128, decimals: u8) -> String {
+    let scale = 10u128.checked_pow(decimals as u32).unwrap_or(u128::MAX);
+    let whole = raw / scale;
+    let frac = raw % scale;
+    let digits = whole.to_string();
+    let mut grouped = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    let shown = decimals.min(4) as u32;
+    if shown == 0 || frac == 0 {
+        return grouped;
+    }
+    let frac = frac / 10u128.pow(decimals as u32 - shown);
+    let frac = format!("{frac:0width$}", width = shown as usize);
+    let frac = frac.trim_end_matches('0');
+    if frac.is_empty() {
+        grouped
+    } else {
+        format!("{grouped}.{frac}")
+    }
+}
+
+/// Parse `/v1/ft/{token}/top`
+pub fn parse_top(v: &Value) -> Result<Vec<Holder>> {
+    let accounts = v["accounts"]
+        .as_array()
+        .ok_or_else(|| anyhow!("FastNEAR API: no holder list in response"))?;
+    let mut holders: Vec<Holder> = accounts
+        .iter()
+        .filter_map(|a| {
+            Some(Holder {
+                account: a["account_id"].as_str()?.to_string(),
+                balance: a["balance"].as_str()?.parse().ok()?,
+            })
+        })
+        .collect();
+    holders.sort_by(|a, b| b.balance.cmp(&a.balance));
+    Ok(holders)
+}
+
+/// `token`'s balance in a `/v1/account/{account}/ft` response (0 when not held)
+pub fn parse_account_balance(v: &Value, token: &str) -> Option<u128> {
+    let tokens = v["tokens"].as_array()?;
+    Some(
+        tokens
+            .iter()
+            .find(|t| t["contract_id"].as_str() == Some(token))
+            .and_then(|t| t["balance"].as_str()?.parse().ok())
+            .unwrap_or(0),
+    )
+}
+
+/// Decode an `ft_metadata` call result (JSON bytes)
+fn parse_metadata(result: &Value) -> Option<FtMetadata> {
+    let bytes: Vec<u8> = result["result"]
+        .as_array()?
+        .iter()
+        .filter_map(|b| b.as_u64().map(|b| b as u8))
+        .collect();
+    let meta: Value = serde_json::from_slice(&bytes).ok()?;
+    Some(FtMetadata {
+        symbol: meta["symbol"].as_str()?.to_string(),
+        decimals: meta["decimals"].as_u64()?.min(38) as u8,
+    })
+}
+
+async fn api_get(url: &str, timeout_ms: u64, auth_token: Option<&str>) -> Result<Value> {
+    let mut req = reqwest::Client::new()
+        .get(url)
+        .timeout(std::time::Duration::from_millis(timeout_ms));
+    if let Some(token) = auth_token {
+        req = req.header("Authorization", format!("Bearer {token}"));
+    }
+    let res = req.send().await?;
+    match res.status().as_u16() {
+        200..=299 => Ok(res.json().await?),
+        404 => Err(anyhow!(
+            "not indexed by the FastNEAR API (not an FT contract?)"
+        )),
+        429 => Err(anyhow!("FastNEAR API rate limit hit, try again shortly")),
+        code => Err(anyhow!("FastNEAR API HTTP {code}")),
+    }
+}
+
+/// Top holders of `token` (plus `account`'s balance when given)
+pub async fn fetch(
+    api_url: &str,
+    rpc_url: &str,
+    token: &str,
+    account: Option<&str>,
+    timeout_ms: u64,
+    auth_token: Option<&str>,
+) -> Result<TokenHolders> {
+    let api = api_url.trim_end_matches('/');
+    let top = api_get(&format!("{api}/v1/ft/{token}/top"), timeout_ms, auth_token).await?;
+    let holders = parse_top(&top)?;
+
+    let account = match account {
+        Some(account) => {
+            let balance = api_get(
+                &format!("{api}/v1/account/{account}/ft"),
+                timeout_ms,
+                auth_token,
+            )
+            .await
+            .ok()
+            .and_then(|v| parse_account_balance(&v, token));
+            Some((account.to_string(), balance))
+        }
+        None => None,
+    };
+
+    let body = json!({"jsonrpc":"2.0","id":"nearx","method":"query","params":{
+        "request_type": "call_function",
+        "finality": "final",
+        "account_id": token,
+        "method_name": "ft_metadata",
+        "args_base64": "",
+    }});
+    let metadata = crate::rpc_utils::rpc_post(rpc_url, &body, timeout_ms, auth_token)
+        .await
+        .ok()
+        .and_then(|r| parse_metadata(&r));
+
+    Ok(TokenHolders {
+        token: token.to_string(),
+        holders,
+        metadata,
+        account,
+    })
+}
+
+/// Cached lookups keyed by (token, account), with request spacing
+#[derive(Debug, Default)]
+pub struct HoldersCache {
+    entries: HashMap<(String, Option<String>), (Instant, TokenHolders)>,
+    last_request: Option<Instant>,
+}
+
+impl HoldersCache {
+    /// A lookup younger than `CACHE_TTL_SECS`
+    pub fn get(&self, token: &str, account: Option<&str>, now: Instant) -> Option<&TokenHolders> {
+        let key = (token.to_string(), account.map(str::to_string));
+        self.entries
+            .get(&key)
+            .filter(|(at, _)| now.duration_since(*at) < Duration::from_secs(CACHE_TTL_SECS))
+            .map(|(_, h)| h)
+    }
+
+    pub fn insert(&mut self, holders: TokenHolders, now: Instant) {
+        let key = (
+            holders.token.clone(),
+            holders.account.as_ref().map(|(a, _)| a.clone()),
+        );
+        self.entries
+            .retain(|_, (at, _)| now.duration_since(*at) < Duration::from_secs(CACHE_TTL_SECS));
+        self.entries.insert(key, (now, holders));
+    }
+
+    pub fn invalidate(&mut self, token: &str) {
+        self.entries.retain(|(t, _), _| t != token);
+    }
+
+    /// Claim the next request slot; false while the last one is under `MIN_INTERVAL_MS` old
+    pub fn try_request(&mut self, now: Instant) -> bool {
+        let spaced = self
+            .last_request
+            .is_none_or(|t| now.duration_since(t) >= Duration::from_millis(MIN_INTERVAL_MS));
+        if spaced {
+            self.last_request = Some(now);
+        }
+        spaced
+    }
+}
+
+/// Holders tab state (the inspected contract and the selected tx's signer)
+#[derive(Clone, Debug, Default)]
+pub struct HoldersTab {
+    pub token: String,
+    pub account: Option<String>,
+    /// `None` while the lookup is queued or in flight
+    pub result: Option<std::result::Result<TokenHolders, String>>,
+    pub selection: usize,
+}
+
+impl HoldersTab {
+    pub fn holders(&self) -> &[Holder] {
+        match &self.result {
+            Some(Ok(h)) => &h.holders,
+            _ => &[],
+        }
+    }
+
+    pub fn up(&mut self) {
+        self.selection = self.selection.saturating_sub(1);
+    }
+
+    pub fn down(&mut self) {
+        if self.selection + 1 < self.holders().len() {
+            self.selection += 1;
+        }
+    }
+
+    pub fn selected(&self) -> Option<&Holder> {
+        self.holders().get(self.selection)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_ranks_and_caches_holders() {
+        let top = json!({"token_id": "usdt.tether-token.near", "accounts": [
+            {"account_id": "b.near", "balance": "2500000"},
+            {"account_id": "a.near", "balance": "1234567890123"},
+            {"account_id": "bad.near"}
+        ]});
+        let holders = parse_top(&top).unwrap();
+        assert_eq!(holders.len(), 2);
+        assert_eq!(holders[0].account, "a.near");
+        assert!(parse_top(&json!({"error": "x"})).is_err());
+
+        let acct = json!({"account_id": "b.near", "tokens": [
+            {"contract_id": "wrap.near", "balance": "7"},
+            {"contract_id": "usdt.tether-token.near", "balance": "2500000"}
+        ]});
+        assert_eq!(
+            parse_account_balance(&acct, "usdt.tether-token.near"),
+            Some(2_500_000)
+        );
+        assert_eq!(parse_account_balance(&acct, "other.near"), Some(0));
+        assert_eq!(parse_account_balance(&json!({}), "wrap.near"), None);
+
+        let meta = serde_json::to_vec(&json!({"symbol": "USDt", "decimals": 6})).unwrap();
+        let th = TokenHolders {
+            token: "usdt.tether-token.near".into(),
+            holders,
+            metadata: parse_metadata(&json!({ "result": meta })),
+            account: Some(("b.near".into(), Some(2_500_000))),
+        };
+        assert_eq!(th.rank("b.near"), Some(2));
+        assert_eq!(th.rank("c.near"), None);
+        assert_eq!(th.format_balance(1_234_567_890_123), "1,234,567.8901 USDt");
+        assert_eq!(th.format_balance(2_500_000), "2.5 USDt");
+        assert_eq!(format_amount(1_000, 0), "1,000");
+
+        let t0 = Instant::now();
+        let mut cache = HoldersCache::default();
+        assert!(cache.try_request(t0));
+        assert!(!cache.try_request(t0 + Duration::from_millis(100)));
+        assert!(cache.try_request(t0 + Duration::from_millis(MIN_INTERVAL_MS)));
+        cache.insert(th.clone(), t0);
+        assert!(cache
+            .get("usdt.tether-token.near", Some("b.near"), t0)
+            .is_some());
+        assert!(cache.get("usdt.tether-token.near", None, t0).is_none());
+        let stale = t0 + Duration::from_secs(CACHE_TTL_SECS);
+        assert!(cache
+            .get("usdt.tether-token.near", Some("b.near"), stale)
+            .is_none());
+        cache.invalidate("usdt.tether-token.near");
+        assert!(cache
+            .get("usdt.tether-token.near", Some("b.near"), t0)
+            .is_none());
+    }
+}
@@ -0,0 +1,293 @@
+//! Standalone HTML report of one block or tx, for attaching to tickets
+//!
+//! Shift+R renders the selected block (or, with the tx list focused, the
+//! selected tx) into a single `.html` file: no scripts, no external assets,
+//! colors from the active theme. The Details view is highlighted from the
+//! same tokens as the TUI ([`crate::json_syntax::tokenize_json`]); tx reports
+//! add each receipt with its status and logs from `EXPERIMENTAL_tx_status`,
+//! and any marks / pin board notes on the block or tx are listed as
+//! annotations. Reports are recorded in the exports overlay like other exports.
+
+use std::path::PathBuf;
+
+use serde_json::Value;
+
+use crate::exports::ExportParams;
+use crate::json_syntax::{tokenize_json, TokenKind};
+use crate::theme::Theme;
+
+/// One receipt in a tx report
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReceiptSection {
+    pub id: String,
+    pub executor: String,
+    /// `success`, `success → value`, `failure: …`
+    pub status: String,
+    pub gas_burnt: Option<u64>,
+    pub logs: Vec<String>,
+}
+
+/// Everything a report shows, gathered by the app (receipts filled in by the runtime)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Report {
+    /// `Block #123` / `Transaction ABCD…`
+    pub title: String,
+    /// Label / value pairs shown above the details
+    pub facts: Vec<(String, String)>,
+    /// Details pane text (pretty JSON, or the warning banner for malformed data)
+    pub details: String,
+    pub receipts: Vec<ReceiptSection>,
+    /// Why receipts are missing (fetch failed), shown in their place
+    pub receipts_note: Option<String>,
+    /// `(kind, text)`: marks and notes on this block / tx
+    pub annotations: Vec<(String, String)>,
+    /// Footer timestamp
+    pub generated: String,
+}
+
+/// A report the app gathered, for the runtime to add receipts to and write
+#[derive(Clone, Debug)]
+pub struct ReportJob {
+    pub report: Report,
+    pub params: ExportParams,
+    pub path: PathBuf,
+    /// Signer of the reported tx (needed for `EXPERIMENTAL_tx_status`)
+    pub signer: Option<String>,
+    /// Txs covered (1 for a tx report)
+    pub rows: usize,
+}
+
+/// Receipts (status and logs) from an `EXPERIMENTAL_tx_status` / `tx` response
+pub fn receipts_from_status(status: &Value) -> Vec<ReceiptSection> {
+    status["receipts_outcome"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|r| {
+            let outcome = &r["outcome"];
+            ReceiptSection {
+                id: r["id"].as_str().unwrap_or_default().to_string(),
+                executor: outcome["executor_id"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                status: outcome_status(&outcome["status"]),
+                gas_burnt: outcome["gas_burnt"].as_u64(),
+                logs: outcome["logs"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|l| l.as_str().map(str::to_string))
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+fn outcome_status(status: &Value) -> String {
+    if let Some(value) = status.get("SuccessValue").and_then(Value::as_str) {
+        return if value.is_empty() {
+            "success".to_string()
+        } else {
+            format!("success → {value}")
+        };
+    }
+    if let Some(id) = status.get("SuccessReceiptId").and_then(Value::as_str) {
+        return format!("success → receipt {id}");
+    }
+    if let Some(failure) = status.get("Failure") {
+        return format!("failure: {failure}");
+    }
+    match status.as_str() {
+        Some(s) => s.to_string(),
+        None => status.to_string(),
+    }
+}
+
+/// Escape text for HTML element content and attribute values
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            ch => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Details text as highlighted HTML, one line per row
+fn details_html(details: &str) -> String {
+    let mut out = String::new();
+    for row in tokenize_json(details) {
+        for token in row {
+            let class = match token.kind {
+                TokenKind::Key => Some("k"),
+                TokenKind::String => Some("s"),
+                TokenKind::Number => Some("n"),
+                TokenKind::Literal => Some("b"),
+                TokenKind::Punct => Some("p"),
+                TokenKind::Text => None,
+            };
+            match class {
+                Some(class) => out.push_str(&format!(
+                    "<span class=\"{class}\">{}</span>",
+                    escape(&token.text)
+                )),
+                None => out.push_str(&escape(&token.text)),
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+const STYLE: &str = "\
+body{margin:0;padding:24px;background:var(--bg);color:var(--fg);font:14px/1.5 system-ui,sans-serif}
+h1{margin:0 0 12px;color:var(--accent-strong);font-size:20px}
+h2{margin:24px 0 8px;color:var(--accent);font-size:16px}
+table{border-collapse:collapse}
+td{padding:2px 16px 2px 0;vertical-align:top}
+td:first-child{color:var(--fg-dim)}
+pre{margin:0;padding:12px;background:var(--json-bg);border:1px solid var(--border);overflow-x:auto;font:13px/1.4 ui-monospace,monospace;white-space:pre-wrap;word-break:break-all}
+.k{color:var(--json-key)}.s{color:var(--json-string)}.n{color:var(--json-number)}.b{color:var(--json-bool)}.p{color:var(--json-struct)}
+.receipt{margin:8px 0;padding:8px 12px;background:var(--panel);border-left:3px solid var(--success)}
+.receipt.failed{border-left-color:var(--error)}
+.dim{color:var(--fg-dim)}
+code{font-family:ui-monospace,monospace}
+footer{margin-top:32px;color:var(--fg-dim);font-size:12px}
+";
+
+/// Render `report` as a self-contained HTML document in `theme`'s colors
+pub fn render(report: &Report, theme: &Theme) -> String {
+    let vars: String = theme
+        .to_css_vars()
+        .iter()
+        .map(|(name, value)| format!("{name}:{value};"))
+        .collect();
+    let title = escape(&report.title);
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>NEARx · {title}</title>\n<style>\n:root{{{vars}}}\n{STYLE}</style>\n</head>\n<body>\n\
+         <h1>{title}</h1>\n"
+    );
+
+    if !report.facts.is_empty() {
+        out.push_str("<table>\n");
+        for (label, value) in &report.facts {
+            out.push_str(&format!(
+                "<tr><td>{}</td><td><code>{}</code></td></tr>\n",
+                escape(label),
+                escape(value)
+            ));
+        }
+        out.push_str("</table>\n");
+    }
+
+    out.push_str("<h2>Details</h2>\n<pre>");
+    out.push_str(&details_html(&report.details));
+    out.push_str("</pre>\n");
+
+    if !report.receipts.is_empty() || report.receipts_note.is_some() {
+        out.push_str(&format!("<h2>Receipts ({})</h2>\n", report.receipts.len()));
+    }
+    if let Some(note) = &report.receipts_note {
+        out.push_str(&format!("<p class=\"dim\">{}</p>\n", escape(note)));
+    }
+    for receipt in &report.receipts {
+        let failed = if receipt.status.starts_with("failure") {
+            " failed"
+        } else {
+            ""
+        };
+        out.push_str(&format!(
+            "<div class=\"receipt{failed}\"><code>{}</code> on <code>{}</code> · {}",
+            escape(&receipt.id),
+            escape(&receipt.executor),
+            escape(&receipt.status)
+        ));
+        if let Some(gas) = receipt.gas_burnt {
+            out.push_str(&format!(
+                " <span class=\"dim\">· {:.2} Tgas</span>",
+                gas as f64 / 1e12
+            ));
+        }
+        if !receipt.logs.is_empty() {
+            out.push_str("\n<pre>");
+            for log in &receipt.logs {
+                out.push_str(&escape(&crate::safe_text::escape_controls(log)));
+                out.push('\n');
+            }
+            out.push_str("</pre>");
+        }
+        out.push_str("</div>\n");
+    }
+
+    if !report.annotations.is_empty() {
+        out.push_str("<h2>Annotations</h2>\n<ul>\n");
+        for (kind, text) in &report.annotations {
+            out.push_str(&format!(
+                "<li><span class=\"dim\">{}</span> {}</li>\n",
+                escape(kind),
+                escape(text)
+            ));
+        }
+        out.push_str("</ul>\n");
+    }
+
+    out.push_str(&format!(
+        "<footer>Generated by NEARx · {}</footer>\n</body>\n</html>\n",
+        escape(&report.generated)
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn renders_escaped_highlighted_details_receipts_and_annotations() {
+        let status = json!({
+            "receipts_outcome": [
+                {"id": "R1", "outcome": {"executor_id": "intents.near", "gas_burnt": 2_500_000_000_000u64,
+                    "status": {"SuccessValue": ""}, "logs": ["EVENT_JSON:{\"standard\":\"nep245\"}"]}},
+                {"id": "R2", "outcome": {"executor_id": "bob.near", "logs": [],
+                    "status": {"Failure": {"ActionError": {"index": 0}}}}}
+            ]
+        });
+        let receipts = receipts_from_status(&status);
+        assert_eq!(receipts.len(), 2);
+        assert_eq!(receipts[0].status, "success");
+        assert_eq!(receipts[0].logs.len(), 1);
+        assert!(receipts[1].status.starts_with("failure: "));
+
+        let report = Report {
+            title: "Transaction <ABC>".into(),
+            facts: vec![("Signer".into(), "alice.near".into())],
+            details: "{\n  \"memo\": \"<script>\",\n  \"amount\": 5\n}".into(),
+            receipts,
+            annotations: vec![("✎ note".into(), "refund & retry".into())],
+            generated: "2026-10-17 12:00:00 UTC".into(),
+            ..Report::default()
+        };
+        let html = render(&report, &Theme::default());
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("--json-key:#"));
+        assert!(html.contains("<h1>Transaction &lt;ABC&gt;</h1>"));
+        assert!(html.contains("<span class=\"k\">&quot;memo&quot;</span>"));
+        assert!(html.contains("<span class=\"s\">&quot;&lt;script&gt;&quot;</span>"));
+        assert!(html.contains("<span class=\"n\">5</span>"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("<h2>Receipts (2)</h2>"));
+        assert!(html.contains("class=\"receipt failed\""));
+        assert!(html.contains("· 2.50 Tgas"));
+        assert!(html.contains("EVENT_JSON:{&quot;standard&quot;:&quot;nep245&quot;}"));
+        assert!(html.contains("refund &amp; retry"));
+    }
+}
@@ -1,5 +1,6 @@
 use crate::theme::Theme;
 use ratatui::text::Line;
+use serde::Serialize;
 use serde_json::Value;
 
 /// Format JSON with syntax highlighting (colored Spans)
@@ -8,11 +9,102 @@ pub fn pretty_colored(v: &Value, _space: usize, theme: &Theme) -> Vec<Line<'stat
     crate::json_renderer::render_json(v, theme)
 }
 
+/// Indentation style for plain-text JSON (details pane and clipboard)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum JsonIndent {
+    #[default]
+    Two,
+    Four,
+    Compact,
+}
+
+impl JsonIndent {
+    pub fn name(self) -> &'static str {
+        match self {
+            JsonIndent::Two => "2",
+            JsonIndent::Four => "4",
+            JsonIndent::Compact => "compact",
+        }
+    }
+}
+
+impl std::str::FromStr for JsonIndent {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "2" | "two" => Ok(JsonIndent::Two),
+            "4" | "four" => Ok(JsonIndent::Four),
+            "compact" | "0" | "none" => Ok(JsonIndent::Compact),
+            _ => Err(anyhow::anyhow!(
+                "Invalid JSON indent '{s}'. Valid options: 2, 4, compact"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for JsonIndent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Plain-text JSON formatting settings (`JSON_INDENT`, `JSON_SORT_KEYS`)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct JsonFormat {
+    pub indent: JsonIndent,
+    /// Sort object keys alphabetically (recursively) instead of keeping source order
+    pub sort_keys: bool,
+}
+
 /// Format JSON as plain text (no colors)
-pub fn pretty(v: &Value, _space: usize) -> String {
-    // For now, continue using serde_json for plain text formatting
-    // Our new renderer ensures proper formatting when colors are needed
-    serde_json::to_string_pretty(v).unwrap_or_else(|_| "{}".to_string())
+///
+/// Structs keep their field order unless `sort_keys` is set. Parsed `Value`s
+/// come out in `serde_json::Map` order (already alphabetical without
+/// serde_json's `preserve_order`, which NEARx doesn't enable).
+pub fn pretty<T: Serialize + ?Sized>(v: &T, fmt: JsonFormat) -> String {
+    if fmt.sort_keys {
+        let sorted = serde_json::to_value(v).map(|v| sort_keys(&v)).unwrap_or(Value::Null);
+        return pretty_unsorted(&sorted, fmt.indent);
+    }
+    pretty_unsorted(v, fmt.indent)
+}
+
+fn pretty_unsorted<T: Serialize + ?Sized>(v: &T, indent: JsonIndent) -> String {
+    let indent: &[u8] = match indent {
+        JsonIndent::Two => b"  ",
+        JsonIndent::Four => b"    ",
+        JsonIndent::Compact => {
+            return serde_json::to_string(v).unwrap_or_default();
+        }
+    };
+    let mut out = Vec::new();
+    let mut ser = serde_json::Serializer::with_formatter(
+        &mut out,
+        serde_json::ser::PrettyFormatter::with_indent(indent),
+    );
+    // Fall back to the compact form rather than an empty object
+    match v.serialize(&mut ser) {
+        Ok(()) => String::from_utf8(out).unwrap_or_default(),
+        Err(_) => serde_json::to_string(v).unwrap_or_default(),
+    }
+}
+
+/// Recursively sort object keys
+fn sort_keys(v: &Value) -> Value {
+    match v {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k.clone(), sort_keys(v)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.iter().map(sort_keys).collect()),
+        other => other.clone(),
+    }
 }
 
 /// Format JSON with truncation for massive payloads (prevents stack overflow)
@@ -22,13 +114,13 @@ pub fn pretty(v: &Value, _space: usize) -> String {
 ///
 /// # Arguments
 /// * `v` - JSON value to format
-/// * `space` - Indentation spaces per level (typically 2)
+/// * `fmt` - Indentation / key-order settings
 /// * `max_bytes` - Maximum output size in bytes (e.g., 100 * 1024 for 100KB)
 ///
 /// # Returns
 /// Formatted JSON string, truncated with "... (truncated)" footer if oversized
-pub fn pretty_safe(v: &Value, space: usize, max_bytes: usize) -> String {
-    let formatted = pretty(v, space);
+pub fn pretty_safe(v: &Value, fmt: JsonFormat, max_bytes: usize) -> String {
+    let formatted = pretty(v, fmt);
 
     if formatted.len() > max_bytes {
         let truncated = &formatted[..max_bytes];
@@ -46,3 +138,56 @@ pub fn pretty_safe(v: &Value, space: usize, max_bytes: usize) -> String {
         formatted
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Serialize)]
+    struct Unsorted {
+        b: u8,
+        a: Vec<bool>,
+    }
+
+    #[test]
+    fn indent_styles() {
+        let v = Unsorted { b: 1, a: vec![true] };
+        let two = pretty(&v, JsonFormat::default());
+        assert_eq!(two, "{\n  \"b\": 1,\n  \"a\": [\n    true\n  ]\n}");
+
+        let four = JsonFormat {
+            indent: JsonIndent::Four,
+            sort_keys: false,
+        };
+        assert!(pretty(&v, four).starts_with("{\n    \"b\": 1"));
+
+        let compact = JsonFormat {
+            indent: JsonIndent::Compact,
+            sort_keys: false,
+        };
+        assert_eq!(pretty(&v, compact), r#"{"b":1,"a":[true]}"#);
+    }
+
+    #[test]
+    fn sort_keys_is_recursive() {
+        let v = json!({"z": {"y": 1, "x": 2}, "a": [{"d": 0, "c": 0}]});
+        let fmt = JsonFormat {
+            indent: JsonIndent::Compact,
+            sort_keys: true,
+        };
+        assert_eq!(pretty(&v, fmt), r#"{"a":[{"c":0,"d":0}],"z":{"x":2,"y":1}}"#);
+        assert_eq!("4".parse::<JsonIndent>().unwrap(), JsonIndent::Four);
+        assert!("3".parse::<JsonIndent>().is_err());
+    }
+
+    #[test]
+    fn sort_keys_reorders_struct_fields() {
+        let v = Unsorted { b: 1, a: vec![] };
+        let fmt = JsonFormat {
+            indent: JsonIndent::Compact,
+            sort_keys: true,
+        };
+        assert_eq!(pretty(&v, fmt), r#"{"a":[],"b":1}"#);
+    }
+}
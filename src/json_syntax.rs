@@ -1,7 +1,7 @@
 use crate::theme::Theme;
 /// JSON syntax highlighting for ratatui
 /// Produces colored Span/Line objects with WCAG AAA compliant colors
-use ratatui::style::{Color, Style};
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 
 /// Parse JSON string and produce colored Lines for ratatui rendering
@@ -15,14 +15,77 @@ use ratatui::text::{Line, Span};
 /// Uses ANSI colors for maximum terminal compatibility.
 pub fn colorize_json(json_str: &str, _theme: &Theme) -> Vec<Line<'static>> {
 
-    // Use subtle RGB colors - "more white" for elegant appearance
-    let (key_color, string_color, number_color, boolean_color, struct_color) = (
-        Color::Rgb(180, 220, 230),  // Very light cyan for keys
-        Color::Rgb(210, 230, 180),  // Very light green for string values
-        Color::Rgb(230, 220, 180),  // Very light amber for numbers
-        Color::Rgb(220, 210, 240),  // Very light purple for booleans
-        Color::Rgb(240, 240, 230),  // Off-white for structure (brackets, colons, etc)
-    );
+    // Use subtle RGB colors - "more white" for elegant appearance.
+    // Monochrome (NO_COLOR): keys bold, booleans/null underlined, structure dim.
+    let (key_style, string_style, number_style, boolean_style, struct_style) =
+        if crate::theme::monochrome() {
+            (
+                Style::default().add_modifier(Modifier::BOLD),
+                Style::default(),
+                Style::default(),
+                Style::default().add_modifier(Modifier::UNDERLINED),
+                Style::default().add_modifier(Modifier::DIM),
+            )
+        } else {
+            (
+                Style::default().fg(Color::Rgb(180, 220, 230)), // Very light cyan for keys
+                Style::default().fg(Color::Rgb(210, 230, 180)), // Very light green for string values
+                Style::default().fg(Color::Rgb(230, 220, 180)), // Very light amber for numbers
+                Style::default().fg(Color::Rgb(220, 210, 240)), // Very light purple for booleans
+                Style::default().fg(Color::Rgb(240, 240, 230)), // Off-white for structure (brackets, colons, etc)
+            )
+        };
+    tokenize_json(json_str)
+        .into_iter()
+        .map(|row| {
+            Line::from(
+                row.into_iter()
+                    .map(|token| match token.kind {
+                        TokenKind::Key => Span::styled(token.text, key_style),
+                        TokenKind::String => Span::styled(token.text, string_style),
+                        TokenKind::Number => Span::styled(token.text, number_style),
+                        TokenKind::Literal => Span::styled(token.text, boolean_style),
+                        TokenKind::Punct => Span::styled(token.text, struct_style),
+                        TokenKind::Text => Span::raw(token.text),
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect()
+}
+
+/// What a [`JsonToken`] is, for whichever renderer styles it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    Key,
+    String,
+    Number,
+    /// `true`, `false`, `null`
+    Literal,
+    /// Braces, brackets, colons, commas
+    Punct,
+    /// Whitespace and anything unrecognized, shown as-is
+    Text,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JsonToken {
+    pub kind: TokenKind,
+    pub text: String,
+}
+
+impl JsonToken {
+    fn new(kind: TokenKind, text: impl Into<String>) -> Self {
+        Self {
+            kind,
+            text: text.into(),
+        }
+    }
+}
+
+/// Split (possibly malformed) pretty-printed JSON into rows of tokens; shared
+/// by the TUI colorizer and the HTML report so both highlight the same way
+pub fn tokenize_json(json_str: &str) -> Vec<Vec<JsonToken>> {
     let mut lines = Vec::new();
     let mut current_line = Vec::new();
     let mut chars = json_str.chars().peekable();
@@ -31,72 +94,74 @@ pub fn colorize_json(json_str: &str, _theme: &Theme) -> Vec<Line<'static>> {
         match ch {
             // Whitespace - preserve as-is
             ' ' | '\t' => {
-                current_line.push(Span::raw(ch.to_string()));
+                current_line.push(JsonToken::new(TokenKind::Text, ch));
             }
 
             // Newline - finish current line
             '\n' => {
-                lines.push(Line::from(std::mem::take(&mut current_line)));
+                lines.push(std::mem::take(&mut current_line));
             }
 
             // String (could be key or value)
             '"' => {
-                let (string_content, is_key) = parse_string(&mut chars);
-                let color = if is_key { key_color } else { string_color };
-
+                let (string_content, is_key, closed) = parse_string(&mut chars);
+                let kind = if is_key { TokenKind::Key } else { TokenKind::String };
+                let close = if closed { "\"" } else { "" };
 
-                current_line.push(Span::styled(
-                    format!("\"{string_content}\""),
-                    Style::default().fg(color),
-                ));
+                current_line.push(JsonToken::new(kind, format!("\"{string_content}{close}")));
             }
 
             // Numbers
             '-' | '0'..='9' => {
                 let number = parse_number(ch, &mut chars);
-                current_line.push(Span::styled(number, Style::default().fg(number_color)));
+                current_line.push(JsonToken::new(TokenKind::Number, number));
             }
 
             // Booleans and null
             't' | 'f' | 'n' => {
                 let keyword = parse_keyword(ch, &mut chars);
-                current_line.push(Span::styled(keyword, Style::default().fg(boolean_color)));
+                current_line.push(JsonToken::new(TokenKind::Literal, keyword));
             }
 
             // Structural characters (braces, brackets, colons, commas)
             '{' | '}' | '[' | ']' | ':' | ',' => {
-                current_line.push(Span::styled(
-                    ch.to_string(),
-                    Style::default().fg(struct_color),
-                ));
+                current_line.push(JsonToken::new(TokenKind::Punct, ch));
             }
 
             // Unknown - render as-is
             _ => {
-                current_line.push(Span::raw(ch.to_string()));
+                current_line.push(JsonToken::new(TokenKind::Text, ch));
             }
         }
     }
 
     // Add final line if non-empty
     if !current_line.is_empty() {
-        lines.push(Line::from(current_line));
+        lines.push(current_line);
     }
 
     // Remove any trailing empty lines (defensive cleanup)
-    while lines.last().is_some_and(|line| line.spans.is_empty()) {
+    while lines.last().is_some_and(|line| line.is_empty()) {
         lines.pop();
     }
 
     lines
 }
 
-/// Parse a JSON string and determine if it's a key (followed by :) or value
-fn parse_string(chars: &mut std::iter::Peekable<std::str::Chars>) -> (String, bool) {
+/// Parse a JSON string and determine if it's a key (followed by :) or value,
+/// and whether it was closed on this line
+fn parse_string(chars: &mut std::iter::Peekable<std::str::Chars>) -> (String, bool, bool) {
     let mut content = String::new();
     let mut escaped = false;
+    let mut closed = false;
 
-    for ch in chars.by_ref() {
+    while let Some(&ch) = chars.peek() {
+        // Unterminated string (malformed or truncated input): end it at the
+        // line break so the following lines keep their own rows
+        if ch == '\n' {
+            return (content, false, false);
+        }
+        chars.next();
         if escaped {
             content.push(ch);
             escaped = false;
@@ -104,6 +169,7 @@ fn parse_string(chars: &mut std::iter::Peekable<std::str::Chars>) -> (String, bo
             content.push(ch);
             escaped = true;
         } else if ch == '"' {
+            closed = true;
             break;
         } else {
             content.push(ch);
@@ -111,28 +177,18 @@ fn parse_string(chars: &mut std::iter::Peekable<std::str::Chars>) -> (String, bo
     }
 
     // Look ahead to see if this is a key (followed by whitespace + colon)
-    let is_key = skip_whitespace_and_check_colon(chars);
+    let is_key = closed && colon_follows(chars);
 
-    (content, is_key)
+    (content, is_key, closed)
 }
 
-/// Skip whitespace and check if next non-whitespace char is a colon
-fn skip_whitespace_and_check_colon(chars: &mut std::iter::Peekable<std::str::Chars>) -> bool {
-    let mut peeked_chars = Vec::new();
-    let mut found_colon = false;
-
-    while let Some(&ch) = chars.peek() {
-        if ch == ' ' || ch == '\t' || ch == '\n' {
-            peeked_chars.push(chars.next().unwrap());
-        } else if ch == ':' {
-            found_colon = true;
-            break;
-        } else {
-            break;
-        }
-    }
-
-    found_colon
+/// Check (without consuming anything) whether the next non-whitespace char
+/// on this line is a colon; consuming would drop the line break after a value
+fn colon_follows(chars: &std::iter::Peekable<std::str::Chars>) -> bool {
+    chars
+        .clone()
+        .find(|ch| *ch != ' ' && *ch != '\t')
+        .is_some_and(|ch| ch == ':')
 }
 
 /// Parse a number (integer or float)
@@ -189,6 +245,23 @@ mod tests {
         assert_eq!(lines.len(), 5);
     }
 
+    #[test]
+    fn malformed_input_keeps_one_row_per_line() {
+        let theme = Theme::default();
+        let text = |line: &Line| line.spans.iter().map(|s| s.content.as_ref()).collect::<String>();
+
+        // A string value ending a line keeps its line break
+        let lines = colorize_json("{\n  \"name\": \"Alice\"\n}", &theme);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(text(&lines[1]), "  \"name\": \"Alice\"");
+
+        // Unterminated string (truncated document) ends at the line break
+        let lines = colorize_json("{\n  \"memo\": \"cut off\n  \"next\": 1\n}", &theme);
+        assert_eq!(lines.len(), 4);
+        assert_eq!(text(&lines[1]), "  \"memo\": \"cut off");
+        assert_eq!(text(&lines[2]), "  \"next\": 1");
+    }
+
     #[test]
     fn test_colors_applied() {
         let theme = Theme::default();
@@ -20,20 +20,107 @@
 //! ```bash
 //! make web-release
 //! ```
+//!
+//! To embed the explorer core in another Rust app (no terminal deps):
+//! ```bash
+//! cargo build --no-default-features --features embed
+//! ```
+//! See `nearx::embed::Explorer`.
 
 // Core modules (available on all platforms)
 pub mod config;
+pub mod config_file;
 pub mod constants;
 pub mod json_auto_parse;
 pub mod json_pretty;
 pub mod json_renderer;
 pub mod json_syntax;
+pub mod colored_lines;
+pub mod safe_text;
 pub mod types;
 pub mod util_text;
 
 // RPC utilities (same direct JSON-RPC implementation for both native and web)
 pub mod rpc_utils;
 
+// Per-tx event timeline (receipt tracer over RPC)
+pub mod timeline;
+
+// NEP-519 yield / resume detection (timeline section with wait + timeout countdown)
+pub mod yields;
+
+// Execution error decoder (Failure JSON -> one-line summary)
+pub mod exec_error;
+
+// Per-contract activity digests over History (markdown view / export)
+pub mod digest;
+
+// CreateAccount / DeleteAccount feed (live blocks + History)
+pub mod account_feed;
+
+// Contract state inspector (view_state key-value tree)
+pub mod state_view;
+
+// FT top holders / account rank via the FastNEAR API (inspector Holders tab)
+pub mod holders;
+
+// Receipt outcome cache keyed by receipt id (memory + History, finality-aware)
+pub mod outcome_cache;
+
+// Receipt gas profiler (flamegraph-style view of a tx's receipt tree)
+pub mod gas_profile;
+
+// Rolling gas price percentiles + spike detection
+pub mod gas_oracle;
+
+// Rolling EWMA / z-score anomaly detection on tx volume and gas used
+pub mod anomaly;
+
+// Validator stake concentration (Nakamoto coefficient, top-N shares) per epoch
+pub mod stake;
+
+// Related-transaction suggestions in Details (buffer + History)
+pub mod related;
+
+// Split-view History browser (stored blocks by height / date range)
+pub mod history_browser;
+
+// Session pin board (pinned marks, notes, copies -> markdown report)
+pub mod pin_board;
+
+// Registry of written exports (re-run with the same parameters)
+pub mod exports;
+
+// Standalone HTML report of a block / tx (Shift+R)
+pub mod html_report;
+
+// Pasted tx hash import into a temporary collection (Shift+I)
+pub mod collection;
+
+// Standing watch rules (filter -> toast + badge + webhook on new matches)
+pub mod watch;
+
+// Staged startup (first frame before History reads and source bring-up)
+pub mod startup;
+
+// Per-source health badges (WS / RPC / GraphQL / archival)
+pub mod source_health;
+
+// Local control socket / named pipe: UiActions from scripts and the native host
+pub mod control;
+
+// Timezone (UTC / local / offset, T) and timestamp format for every view
+pub mod time_display;
+
+// Source fault injection for testing (CHAOS: latency / jitter / reorder / drop)
+pub mod chaos;
+
+// Footer status bar segments (core indicators + contributed segments)
+pub mod status_bar;
+
+// Opt-in anonymous feature-usage counters (preview + purge with Ctrl+T)
+pub mod telemetry;
+
 // Theme system (available on all platforms, with platform-specific helpers)
 pub mod theme;
 
@@ -45,13 +132,25 @@ pub mod ui_core;
 pub mod pane_frame;
 
 pub mod app;
+pub mod bridge;
 pub mod filter;
 pub mod near_args;
+pub mod toast;
 pub mod ui;
 
+// Contract method tailing (native-host TailMethod bridge)
+pub mod tail;
+
+// Deep link canonicalization (shared with the Tauri shell and native host)
+pub mod deeplink;
+
 // Deep link router (available on all platforms)
 pub mod router;
 
+// Release artifact signature checks (Tauri sidecar spawn)
+#[cfg(feature = "native")]
+pub mod release_verify;
+
 // UI feature flags (available on all platforms)
 pub mod flags;
 
@@ -61,9 +160,27 @@ pub mod debug;
 // UI snapshot types for DOM-based rendering (all platforms)
 pub mod ui_snapshot;
 
+// Incremental UiSnapshot building (reuses unchanged Blocks / Txs rows)
+pub mod snapshot_cache;
+
+// Background task supervision: cancellation tokens + ordered shutdown (native)
+#[cfg(feature = "native")]
+pub mod supervisor;
+
+// Embedder API: explorer core without a terminal (`embed` feature, part of `native`)
+#[cfg(feature = "embed")]
+pub mod embed;
+
+// Read-only shared view: UiSnapshot frames over WebSocket (server native, viewer in web/app.js)
+pub mod mirror;
+
 // Pure TUI renderer (draws from UiSnapshot)
 pub mod ui_tui_snapshot;
 
+// JSON Schema / TypeScript export of the UI contract
+#[cfg(feature = "schema")]
+pub mod schema;
+
 // History module (has native-only implementation internally)
 pub mod history;
 
@@ -78,12 +195,31 @@ pub mod archival_fetch_wasm;
 
 pub mod source_rpc;
 
+// GraphQL indexer polling (query templates from config)
+pub mod source_graphql;
+
 #[cfg(feature = "native")]
 pub mod credentials;
 
 #[cfg(feature = "native")]
 pub mod marks;
 
+// Plugin bus: alerts from out-of-process plugins (nearx-plugin-core IPC)
+#[cfg(all(feature = "native", unix))]
+pub mod plugin_bus;
+
+#[cfg(feature = "native")]
+pub mod query;
+
+#[cfg(feature = "native")]
+pub mod doctor;
+
+#[cfg(feature = "native")]
+pub mod fetch;
+
+#[cfg(feature = "native")]
+pub mod backfill;
+
 // Platform abstraction layer
 pub mod platform;
 
@@ -107,11 +243,14 @@ pub mod util;
 
 // Copy functionality (shared across all targets)
 pub mod copy_api;
+
+// Clipboard backend chain (native → OSC 52 → file)
+pub mod clipboard;
 pub mod copy_payload;
 
 
 // Re-export commonly used types
-pub use app::{App, BlockLite, InputMode};
+pub use app::{App, BlockLite, Endpoints, InputMode};
 pub use config::{Config, Source};
 pub use types::{AppEvent, BlockRow, Mark, TxLite};
 
@@ -3,32 +3,72 @@
 //! This module is only available on native targets (depends on persistent history).
 
 use crate::history::{History, PersistedMark};
-use crate::types::Mark;
+use crate::types::{AlertSeverity, Mark, PluginAlert};
 
 const LABELS: &[&str] = &[
     "1", "2", "3", "4", "5", "6", "7", "8", "9", "a", "b", "c", "d", "e", "f", "g", "h", "i", "j",
     "k", "l", "m", "n", "o", "p", "q", "r", "s", "t", "u", "v", "w", "x", "y", "z",
 ];
 
+/// Auto-marking policy for plugin alerts (`PLUGIN_ALERT_MARKS`, `PLUGIN_ALERT_MARK_CAP`)
+#[derive(Clone, Copy, Debug)]
+pub struct AlertMarkPolicy {
+    pub enabled: bool,
+    /// Max auto-created marks kept per plugin (oldest evicted first)
+    pub per_plugin_cap: usize,
+    /// Alerts below this severity never create marks
+    pub min_severity: AlertSeverity,
+}
+
+impl Default for AlertMarkPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            per_plugin_cap: 5,
+            min_severity: AlertSeverity::Critical,
+        }
+    }
+}
+
 pub struct JumpMarks {
     marks: Vec<Mark>,
+    /// Soft-deleted this session, oldest deletion first (`u` restores the last)
+    trash: Vec<Mark>,
     cursor: usize,
     history: History,
+    alert_policy: AlertMarkPolicy,
 }
 
 impl JumpMarks {
     pub fn new(history: History) -> Self {
         Self {
             marks: Vec::new(),
+            trash: Vec::new(),
             cursor: 0,
             history,
+            alert_policy: AlertMarkPolicy::default(),
+        }
+    }
+
+    pub fn set_alert_policy(&mut self, policy: AlertMarkPolicy) {
+        self.alert_policy = policy;
+    }
+
+    /// Read persisted marks; anything still in the trash from an earlier session is purged
+    pub async fn load_persisted(history: &History) -> Vec<PersistedMark> {
+        let (trashed, persisted): (Vec<_>, Vec<_>) =
+            history.list_marks().await.into_iter().partition(|p| p.deleted_at.is_some());
+        for p in trashed {
+            history.del_mark(p.label).await;
         }
+        persisted
     }
 
-    pub async fn load_from_persistence(&mut self) {
-        let persisted = self.history.list_marks().await;
-        self.marks = persisted
+    /// Take in marks read at startup; marks set meanwhile keep their labels
+    pub fn restore_persisted(&mut self, persisted: Vec<PersistedMark>) {
+        let restored: Vec<Mark> = persisted
             .into_iter()
+            .filter(|p| !self.marks.iter().any(|m| m.label == p.label))
             .map(|p| Mark {
                 label: p.label,
                 pane: p.pane,
@@ -38,6 +78,7 @@ impl JumpMarks {
                 pinned: p.pinned,
             })
             .collect();
+        self.marks.extend(restored);
     }
 
     pub fn list(&self) -> Vec<Mark> {
@@ -51,6 +92,12 @@ impl JumpMarks {
     }
 
     pub fn next_auto_label(&self) -> String {
+        // Prefer labels free in both lists so trashed marks stay restorable
+        for &label in LABELS {
+            if !self.marks.iter().chain(&self.trash).any(|m| m.label == label) {
+                return label.to_string();
+            }
+        }
         for &label in LABELS {
             if !self.marks.iter().any(|m| m.label == label) {
                 return label.to_string();
@@ -90,6 +137,9 @@ impl JumpMarks {
             pinned,
         };
 
+        // Reusing a trashed mark's label overwrites its row, so it can't come back
+        self.trash.retain(|m| m.label != label);
+
         // Update or add
         if let Some(pos) = self.marks.iter().position(|m| m.label == label) {
             self.marks[pos] = mark;
@@ -107,16 +157,58 @@ impl JumpMarks {
             tx: tx_hash,
             when_ms: now,
             pinned,
+            deleted_at: None,
         };
         self.history.put_mark(persisted).await;
     }
 
+    /// Soft delete: the mark moves to the trash and can be restored this session
     pub async fn remove_by_label(&mut self, label: &str) {
+        let Some(pos) = self.marks.iter().position(|m| m.label == label) else {
+            return;
+        };
+        let mark = self.marks.remove(pos);
+        self.clamp_cursor();
+        // A trashed mark with the same label is superseded
+        self.trash.retain(|m| m.label != label);
+        self.trash.push(mark);
+        let now = chrono::Utc::now().timestamp_millis();
+        self.history.trash_mark(label.to_string(), Some(now)).await;
+    }
+
+    /// Hard delete (alert mark eviction, purging from the trash)
+    pub async fn purge_by_label(&mut self, label: &str) {
         self.marks.retain(|m| m.label != label);
+        self.trash.retain(|m| m.label != label);
+        self.clamp_cursor();
+        self.history.del_mark(label.to_string()).await;
+    }
+
+    /// Trashed marks, most recently deleted first
+    pub fn trashed(&self) -> Vec<Mark> {
+        self.trash.iter().rev().cloned().collect()
+    }
+
+    /// Restore the most recently deleted mark (`u`). Returns its label.
+    pub async fn undo_delete(&mut self) -> Option<String> {
+        let label = self.trash.last()?.label.clone();
+        self.restore(&label).await
+    }
+
+    /// Restore a trashed mark. Returns its label.
+    pub async fn restore(&mut self, label: &str) -> Option<String> {
+        let pos = self.trash.iter().position(|m| m.label == label)?;
+        let mark = self.trash.remove(pos);
+        self.history.trash_mark(mark.label.clone(), None).await;
+        let restored = mark.label.clone();
+        self.marks.push(mark);
+        Some(restored)
+    }
+
+    fn clamp_cursor(&mut self) {
         if self.cursor >= self.marks.len() && self.cursor > 0 {
-            self.cursor = self.marks.len() - 1;
+            self.cursor = self.marks.len().saturating_sub(1);
         }
-        self.history.del_mark(label.to_string()).await;
     }
 
     pub fn next_mark(&mut self) -> Option<Mark> {
@@ -185,4 +277,121 @@ impl JumpMarks {
                 .await;
         }
     }
+
+    /// Create (or refresh) a pinned mark for a high-severity plugin alert.
+    ///
+    /// Marks are labeled `<plugin_id>#<n>` so they never collide with the
+    /// single-character auto labels. Returns the label when a mark was written.
+    pub async fn add_alert_mark(&mut self, alert: &PluginAlert) -> Option<String> {
+        let policy = self.alert_policy;
+        if !policy.enabled || policy.per_plugin_cap == 0 || alert.severity < policy.min_severity {
+            return None;
+        }
+
+        // Only alerts tied to a location are navigable
+        let (pane, height, tx_hash) = match (&alert.tx_hash, alert.block_height) {
+            (Some(hash), height) => (1, height, Some(hash.clone())),
+            (None, Some(height)) => (0, Some(height), None),
+            (None, None) => return None,
+        };
+
+        let label = match self.find_by_context(pane, height, tx_hash.as_deref()) {
+            Some(existing) if alert_mark_seq(&existing, &alert.plugin_id).is_some() => existing,
+            _ => {
+                for old in alert_marks_to_evict(&self.marks, &alert.plugin_id, policy.per_plugin_cap) {
+                    self.purge_by_label(&old).await;
+                }
+                next_alert_label(&self.marks, &alert.plugin_id)
+            }
+        };
+
+        self.add_or_replace(label.clone(), pane, height, tx_hash).await;
+        self.set_pinned(&label, true).await;
+        Some(label)
+    }
+
+    /// Mark each `(tx_hash, height)` from a tx collection as `import#<n>`,
+    /// skipping txs that already have a mark. Returns how many were added.
+    pub async fn add_import_marks(&mut self, txs: &[(String, Option<u64>)]) -> usize {
+        let mut added = 0;
+        for (hash, height) in txs {
+            if self.find_by_context(1, *height, Some(hash)).is_some() {
+                continue;
+            }
+            let label = next_alert_label(&self.marks, crate::collection::MARK_PREFIX);
+            self.add_or_replace(label, 1, *height, Some(hash.clone())).await;
+            added += 1;
+        }
+        added
+    }
+}
+
+/// Sequence number of an alert mark label (`<plugin_id>#<n>`)
+fn alert_mark_seq(label: &str, plugin_id: &str) -> Option<u64> {
+    label
+        .strip_prefix(plugin_id)
+        .and_then(|rest| rest.strip_prefix('#'))
+        .and_then(|n| n.parse().ok())
+}
+
+fn next_alert_label(marks: &[Mark], plugin_id: &str) -> String {
+    let next = marks
+        .iter()
+        .filter_map(|m| alert_mark_seq(&m.label, plugin_id))
+        .max()
+        .map_or(1, |n| n + 1);
+    format!("{plugin_id}#{next}")
+}
+
+/// Oldest alert marks for `plugin_id` that must go to make room for one more
+fn alert_marks_to_evict(marks: &[Mark], plugin_id: &str, cap: usize) -> Vec<String> {
+    let mut own: Vec<&Mark> = marks
+        .iter()
+        .filter(|m| alert_mark_seq(&m.label, plugin_id).is_some())
+        .collect();
+    if own.len() < cap {
+        return Vec::new();
+    }
+    own.sort_by_key(|m| m.when_ms);
+    own.iter()
+        .take(own.len() + 1 - cap)
+        .map(|m| m.label.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mark(label: &str, when_ms: i64) -> Mark {
+        Mark {
+            label: label.to_string(),
+            pane: 1,
+            height: Some(1),
+            tx_hash: None,
+            when_ms,
+            pinned: true,
+        }
+    }
+
+    #[test]
+    fn alert_labels_are_per_plugin() {
+        let marks = vec![mark("a", 0), mark("tx-analyzer#2", 1), mark("tx-analyzer-x#7", 2)];
+        assert_eq!(next_alert_label(&marks, "tx-analyzer"), "tx-analyzer#3");
+        assert_eq!(next_alert_label(&marks, "validator-monitor"), "validator-monitor#1");
+    }
+
+    #[test]
+    fn cap_evicts_oldest_of_same_plugin() {
+        let marks = vec![
+            mark("p#1", 30),
+            mark("p#2", 10),
+            mark("p#3", 20),
+            mark("q#1", 0),
+            mark("1", 0),
+        ];
+        assert_eq!(alert_marks_to_evict(&marks, "p", 3), vec!["p#2".to_string()]);
+        assert_eq!(alert_marks_to_evict(&marks, "p", 2), vec!["p#2", "p#3"]);
+        assert!(alert_marks_to_evict(&marks, "q", 3).is_empty());
+    }
 }
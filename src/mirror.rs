@@ -0,0 +1,224 @@
+//! Read-only shared view
+//!
+//! With `MIRROR_LISTEN` set, the native app serves its live `UiSnapshot` over
+//! WebSocket so a team can watch one operator's session during an incident.
+//! The web build connects with `?mirror=ws://host:port` and renders the frames
+//! instead of running its own sources; nothing a viewer sends is applied.
+//!
+//! Frames are `{"v":1,"seq":N,"snapshot":{...}}`, published at most every
+//! `PUBLISH_MS` and only when the snapshot changed. A viewer that connects
+//! gets the latest frame straight away.
+
+use std::net::SocketAddr;
+
+use anyhow::{anyhow, Result};
+
+/// Frame format version (bumped on incompatible envelope changes)
+pub const PROTOCOL_VERSION: u32 = 1;
+/// Minimum interval between published frames
+pub const PUBLISH_MS: u64 = 250;
+
+/// Parse `MIRROR_LISTEN`: `host:port`, or a bare port (bound to localhost;
+/// use `0.0.0.0:PORT` to share on the network)
+pub fn parse_listen(s: &str) -> Result<SocketAddr> {
+    let s = s.trim();
+    if let Ok(port) = s.parse::<u16>() {
+        return Ok(SocketAddr::from(([127, 0, 0, 1], port)));
+    }
+    s.parse().map_err(|_| {
+        anyhow!("Invalid MIRROR_LISTEN '{s}'. Expected PORT or HOST:PORT (e.g. 0.0.0.0:7878)")
+    })
+}
+
+/// Wrap a serialized snapshot in the frame envelope
+pub fn frame(seq: u64, snapshot_json: &str) -> String {
+    format!(r#"{{"v":{PROTOCOL_VERSION},"seq":{seq},"snapshot":{snapshot_json}}}"#)
+}
+
+#[cfg(feature = "native")]
+pub use server::{serve, MirrorPublisher};
+
+#[cfg(feature = "native")]
+mod server {
+    use std::net::SocketAddr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    use anyhow::{Context, Result};
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio::sync::watch;
+    use tokio::task::JoinHandle;
+    use tokio_tungstenite::tungstenite::Message;
+
+    use super::{frame, PUBLISH_MS};
+    use crate::snapshot_cache::SnapshotCache;
+    use crate::ui_snapshot::UiSnapshot;
+    use crate::App;
+
+    /// Handle the UI loop publishes through; dropping it stops the server
+    pub struct MirrorPublisher {
+        addr: SocketAddr,
+        frames: watch::Sender<Arc<str>>,
+        viewers: Arc<AtomicUsize>,
+        last_json: String,
+        last_publish: Option<Instant>,
+        seq: u64,
+        snapshots: SnapshotCache,
+        accept: JoinHandle<()>,
+    }
+
+    impl MirrorPublisher {
+        pub fn addr(&self) -> SocketAddr {
+            self.addr
+        }
+
+        /// Viewers connected right now
+        pub fn viewers(&self) -> usize {
+            self.viewers.load(Ordering::Relaxed)
+        }
+
+        /// Snapshot `app` and send it to viewers when it changed (throttled)
+        pub fn publish(&mut self, app: &App) {
+            if self
+                .last_publish
+                .is_some_and(|t| t.elapsed() < Duration::from_millis(PUBLISH_MS))
+            {
+                return;
+            }
+            self.last_publish = Some(Instant::now());
+            // Nobody watching: skip the serialization, but send a fresh
+            // frame to whoever connects next
+            if self.frames.receiver_count() == 0 {
+                if !self.last_json.is_empty() {
+                    self.last_json.clear();
+                    self.frames.send_replace(Arc::from(""));
+                }
+                return;
+            }
+            let json = match serde_json::to_string(&UiSnapshot::build(app, &mut self.snapshots)) {
+                Ok(json) => json,
+                Err(e) => {
+                    log::error!("mirror: failed to serialize UiSnapshot: {e}");
+                    return;
+                }
+            };
+            if json == self.last_json {
+                return;
+            }
+            self.seq += 1;
+            self.frames.send_replace(frame(self.seq, &json).into());
+            self.last_json = json;
+        }
+    }
+
+    impl Drop for MirrorPublisher {
+        fn drop(&mut self) {
+            self.accept.abort();
+        }
+    }
+
+    /// Bind `addr` and accept viewers in the background
+    pub async fn serve(addr: SocketAddr) -> Result<MirrorPublisher> {
+        let listener = TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("mirror: cannot listen on {addr}"))?;
+        let addr = listener.local_addr()?;
+        let (frames, _) = watch::channel::<Arc<str>>(Arc::from(""));
+        let viewers = Arc::new(AtomicUsize::new(0));
+
+        let accept = {
+            let frames = frames.clone();
+            let viewers = viewers.clone();
+            tokio::spawn(async move {
+                loop {
+                    let (stream, peer) = match listener.accept().await {
+                        Ok(conn) => conn,
+                        Err(e) => {
+                            log::warn!("mirror: accept failed: {e}");
+                            tokio::time::sleep(Duration::from_millis(100)).await;
+                            continue;
+                        }
+                    };
+                    let rx = frames.subscribe();
+                    let viewers = viewers.clone();
+                    tokio::spawn(async move {
+                        viewers.fetch_add(1, Ordering::Relaxed);
+                        log::info!("mirror: viewer {peer} connected");
+                        if let Err(e) = stream_frames(stream, rx).await {
+                            log::debug!("mirror: viewer {peer}: {e:#}");
+                        }
+                        viewers.fetch_sub(1, Ordering::Relaxed);
+                        log::info!("mirror: viewer {peer} disconnected");
+                    });
+                }
+            })
+        };
+
+        Ok(MirrorPublisher {
+            addr,
+            frames,
+            viewers,
+            last_json: String::new(),
+            last_publish: None,
+            seq: 0,
+            snapshots: SnapshotCache::default(),
+            accept,
+        })
+    }
+
+    /// Send the latest frame, then every new one, until the viewer leaves.
+    /// Incoming messages are read (so pings and close are answered) and dropped.
+    async fn stream_frames(stream: TcpStream, mut rx: watch::Receiver<Arc<str>>) -> Result<()> {
+        let ws = tokio_tungstenite::accept_async(stream).await?;
+        let (mut sink, mut incoming) = ws.split();
+        let mut send_latest = true;
+        loop {
+            if send_latest {
+                let latest = rx.borrow_and_update().clone();
+                if !latest.is_empty() {
+                    sink.send(Message::Text(latest.to_string())).await?;
+                }
+            }
+            tokio::select! {
+                changed = rx.changed() => {
+                    if changed.is_err() {
+                        break;
+                    }
+                    send_latest = true;
+                }
+                msg = incoming.next() => match msg {
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Ok(_)) => send_latest = false,
+                    Some(Err(e)) => return Err(e.into()),
+                },
+            }
+        }
+        let _ = sink.close().await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_listen_and_frames() {
+        assert_eq!(
+            parse_listen("7878").unwrap(),
+            "127.0.0.1:7878".parse().unwrap()
+        );
+        assert_eq!(
+            parse_listen(" 0.0.0.0:9000 ").unwrap(),
+            "0.0.0.0:9000".parse().unwrap()
+        );
+        assert!(parse_listen("localhost").is_err());
+
+        let f: serde_json::Value = serde_json::from_str(&frame(3, r#"{"pane":1}"#)).unwrap();
+        assert_eq!(f["v"], PROTOCOL_VERSION);
+        assert_eq!(f["seq"], 3);
+        assert_eq!(f["snapshot"]["pane"], 1);
+    }
+}
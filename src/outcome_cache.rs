@@ -0,0 +1,350 @@
+//! Receipt outcome cache keyed by receipt id (memory + History)
+//!
+//! The receipt tracer and the gas profiler both start from
+//! `EXPERIMENTAL_tx_status`, and reopening a tx (or tracing one it spawned)
+//! used to refetch every receipt. Each outcome is kept under its id (the tx's
+//! own outcome under the tx hash), and a tx's status is reassembled from them
+//! when every listed receipt is still usable.
+//!
+//! Invalidation follows finality: outcomes from a `FINAL` answer never change,
+//! so they are kept and written to History; anything earlier (`INCLUDED_FINAL`
+//! while receipts are still running) is only reused for `NONFINAL_TTL_MS`, is
+//! never served to a caller that asked for `FINAL`, and never replaces a final
+//! entry. `nearx-proxy` answers plugins' `EXPERIMENTAL_tx_status` and
+//! `EXPERIMENTAL_receipt` calls from the same cache.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::history::History;
+use crate::rpc_utils::get_tx_status_until;
+
+/// How long a not-yet-final outcome is reused (ms)
+pub const NONFINAL_TTL_MS: i64 = 3_000;
+
+/// Outcomes kept in memory before the oldest are evicted
+pub const MEMORY_CAP: usize = 4_096;
+
+/// One execution outcome, keyed by its receipt id (the tx hash for the tx's own)
+#[derive(Clone, Debug, PartialEq)]
+pub struct CachedOutcome {
+    pub id: String,
+    pub tx_hash: String,
+    /// `ExecutionOutcomeWithId` as returned in `receipts_outcome`
+    pub outcome: Value,
+    /// Receipt body from `receipts`; for the tx's own entry, the transaction,
+    /// its status and the ordered receipt ids
+    pub body: Option<Value>,
+    pub is_final: bool,
+    pub fetched_ms: i64,
+}
+
+impl CachedOutcome {
+    fn usable(&self, need_final: bool, now_ms: i64) -> bool {
+        self.is_final || (!need_final && now_ms - self.fetched_ms < NONFINAL_TTL_MS)
+    }
+}
+
+/// In-memory outcomes by receipt id
+#[derive(Debug)]
+pub struct OutcomeCache {
+    entries: HashMap<String, CachedOutcome>,
+    cap: usize,
+}
+
+impl Default for OutcomeCache {
+    fn default() -> Self {
+        Self::new(MEMORY_CAP)
+    }
+}
+
+impl OutcomeCache {
+    pub fn new(cap: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            cap: cap.max(1),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Store the outcomes of a tx-status answer fetched with `wait_until`;
+    /// returns the final ones (what History should keep)
+    pub fn ingest(&mut self, status: &Value, wait_until: &str, now_ms: i64) -> Vec<CachedOutcome> {
+        let Some(tx_outcome) = status.get("transaction_outcome") else {
+            return Vec::new();
+        };
+        let Some(tx_hash) = tx_outcome["id"]
+            .as_str()
+            .or_else(|| status["transaction"]["hash"].as_str())
+        else {
+            return Vec::new();
+        };
+        let is_final = match status["final_execution_status"].as_str() {
+            Some(s) => s == "FINAL",
+            None => wait_until == "FINAL",
+        };
+
+        let outcomes = status["receipts_outcome"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        let bodies: HashMap<&str, &Value> = status["receipts"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|r| Some((r["receipt_id"].as_str()?, r)))
+            .collect();
+        let entry = |id: &str, outcome: Value, body: Option<Value>| CachedOutcome {
+            id: id.to_string(),
+            tx_hash: tx_hash.to_string(),
+            outcome,
+            body,
+            is_final,
+            fetched_ms: now_ms,
+        };
+
+        let receipt_ids: Vec<&str> = outcomes.iter().filter_map(|o| o["id"].as_str()).collect();
+        let root = json!({
+            "transaction": status["transaction"],
+            "status": status["status"],
+            "receipt_ids": receipt_ids,
+        });
+        let mut fresh = vec![entry(tx_hash, tx_outcome.clone(), Some(root))];
+        for o in &outcomes {
+            if let Some(id) = o["id"].as_str() {
+                fresh.push(entry(id, o.clone(), bodies.get(id).map(|b| (*b).clone())));
+            }
+        }
+
+        let mut finals = Vec::new();
+        for e in fresh {
+            // A final outcome is never replaced by an earlier view of it
+            if self
+                .entries
+                .get(&e.id)
+                .is_some_and(|old| old.is_final && !e.is_final)
+            {
+                continue;
+            }
+            if e.is_final {
+                finals.push(e.clone());
+            }
+            self.entries.insert(e.id.clone(), e);
+        }
+        self.evict(now_ms);
+        finals
+    }
+
+    /// Add outcomes read back from History (all final)
+    pub fn load(&mut self, stored: Vec<CachedOutcome>, now_ms: i64) {
+        for e in stored {
+            self.entries.insert(e.id.clone(), e);
+        }
+        self.evict(now_ms);
+    }
+
+    /// One receipt's cached outcome (final, or not-final and still fresh)
+    pub fn receipt(&self, id: &str, now_ms: i64) -> Option<&CachedOutcome> {
+        self.entries.get(id).filter(|e| e.usable(false, now_ms))
+    }
+
+    /// Reassemble `tx_hash`'s tx status, None if the tx or any of its
+    /// receipts is missing or stale (or not final when `need_final`)
+    pub fn status(&self, tx_hash: &str, need_final: bool, now_ms: i64) -> Option<Value> {
+        let root = self
+            .entries
+            .get(tx_hash)
+            .filter(|e| e.usable(need_final, now_ms))?;
+        let body = root.body.as_ref()?;
+        let mut is_final = root.is_final;
+        let mut outcomes = Vec::new();
+        let mut receipts = Vec::new();
+        for id in body["receipt_ids"]
+            .as_array()?
+            .iter()
+            .filter_map(Value::as_str)
+        {
+            let e = self
+                .entries
+                .get(id)
+                .filter(|e| e.usable(need_final, now_ms))?;
+            is_final &= e.is_final;
+            outcomes.push(e.outcome.clone());
+            receipts.extend(e.body.clone());
+        }
+        Some(json!({
+            "final_execution_status": if is_final { "FINAL" } else { "INCLUDED_FINAL" },
+            "status": body["status"],
+            "transaction": body["transaction"],
+            "transaction_outcome": root.outcome,
+            "receipts_outcome": outcomes,
+            "receipts": receipts,
+        }))
+    }
+
+    /// Drop expired not-final entries, then the oldest past the cap
+    fn evict(&mut self, now_ms: i64) {
+        if self.entries.len() <= self.cap {
+            return;
+        }
+        self.entries.retain(|_, e| e.usable(false, now_ms));
+        if self.entries.len() > self.cap {
+            // Down to 3/4 so a full cache doesn't sort on every insert
+            let mut ages: Vec<(i64, String)> = self
+                .entries
+                .values()
+                .map(|e| (e.fetched_ms, e.id.clone()))
+                .collect();
+            ages.sort();
+            let excess = self.entries.len() - self.cap * 3 / 4;
+            for (_, id) in ages.into_iter().take(excess) {
+                self.entries.remove(&id);
+            }
+        }
+    }
+}
+
+/// Shared cache handle for the tx-status fetchers, backed by History when given
+#[derive(Clone, Default)]
+pub struct Outcomes {
+    mem: Arc<Mutex<OutcomeCache>>,
+    history: Option<History>,
+}
+
+impl Outcomes {
+    pub fn new(history: Option<History>) -> Self {
+        Self {
+            mem: Arc::default(),
+            history,
+        }
+    }
+
+    fn now_ms() -> i64 {
+        chrono::Utc::now().timestamp_millis()
+    }
+
+    fn mem(&self) -> std::sync::MutexGuard<'_, OutcomeCache> {
+        self.mem.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Cached status for `tx_hash` (memory only)
+    pub fn cached_status(&self, tx_hash: &str, wait_until: &str) -> Option<Value> {
+        self.mem()
+            .status(tx_hash, wait_until == "FINAL", Self::now_ms())
+    }
+
+    /// Cached outcome of one receipt (memory only)
+    pub fn cached_receipt(&self, receipt_id: &str) -> Option<CachedOutcome> {
+        self.mem().receipt(receipt_id, Self::now_ms()).cloned()
+    }
+
+    /// Record a tx-status answer fetched elsewhere (e.g. forwarded by the proxy)
+    pub fn record(&self, status: &Value, wait_until: &str) {
+        let finals = self.mem().ingest(status, wait_until, Self::now_ms());
+        if let Some(history) = self.history.as_ref().filter(|_| !finals.is_empty()) {
+            history.put_outcomes(finals);
+        }
+    }
+
+    /// `EXPERIMENTAL_tx_status` through the cache: memory, then History, then RPC
+    pub async fn tx_status(
+        &self,
+        url: &str,
+        tx_hash: &str,
+        signer: &str,
+        wait_until: &str,
+        timeout_ms: u64,
+        auth_token: Option<&str>,
+    ) -> Result<Value> {
+        if let Some(status) = self.cached_status(tx_hash, wait_until) {
+            return Ok(status);
+        }
+        if let Some(history) = &self.history {
+            let stored = history.tx_outcomes(tx_hash).await;
+            if !stored.is_empty() {
+                self.mem().load(stored, Self::now_ms());
+                if let Some(status) = self.cached_status(tx_hash, wait_until) {
+                    return Ok(status);
+                }
+            }
+        }
+        let status =
+            get_tx_status_until(url, tx_hash, signer, wait_until, timeout_ms, auth_token).await?;
+        self.record(&status, wait_until);
+        Ok(status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(final_status: &str) -> Value {
+        json!({
+            "final_execution_status": final_status,
+            "status": {"SuccessValue": ""},
+            "transaction": {"hash": "TX", "signer_id": "alice.near"},
+            "transaction_outcome": {"id": "TX", "block_hash": "B1", "outcome": {"receipt_ids": ["R1"]}},
+            "receipts_outcome": [
+                {"id": "R1", "block_hash": "B2", "outcome": {"receipt_ids": ["R2"]}},
+                {"id": "R2", "block_hash": "B3", "outcome": {"receipt_ids": []}}
+            ],
+            "receipts": [{"receipt_id": "R1", "receiver_id": "token.near"}]
+        })
+    }
+
+    #[test]
+    fn reassembles_by_receipt_id_and_respects_finality() {
+        let mut cache = OutcomeCache::new(16);
+
+        // Not final yet: reused briefly, never for a FINAL caller
+        assert!(cache
+            .ingest(&status("INCLUDED_FINAL"), "INCLUDED_FINAL", 0)
+            .is_empty());
+        assert!(cache.status("TX", false, 1_000).is_some());
+        assert!(cache.status("TX", true, 1_000).is_none());
+        assert!(cache.status("TX", false, NONFINAL_TTL_MS).is_none());
+
+        let finals = cache.ingest(&status("FINAL"), "FINAL", 5_000);
+        assert_eq!(finals.len(), 3);
+        let s = cache.status("TX", true, 1_000_000).unwrap();
+        assert_eq!(s["final_execution_status"], "FINAL");
+        assert_eq!(s["receipts_outcome"][1]["id"], "R2");
+        assert_eq!(s["receipts"].as_array().unwrap().len(), 1);
+        assert_eq!(
+            cache
+                .receipt("R1", 1_000_000)
+                .unwrap()
+                .body
+                .as_ref()
+                .unwrap()["receiver_id"],
+            "token.near"
+        );
+
+        // A later non-final view doesn't downgrade, and a missing receipt misses
+        cache.ingest(&status("INCLUDED_FINAL"), "INCLUDED_FINAL", 6_000);
+        assert!(cache.status("TX", true, 1_000_000).is_some());
+        cache.entries.remove("R2");
+        assert!(cache.status("TX", false, 1_000_000).is_none());
+
+        // History rows come back final; eviction keeps the newest under the cap
+        let mut small = OutcomeCache::new(4);
+        small.load(finals, 5_000);
+        let mut other = status("FINAL");
+        other["transaction_outcome"]["id"] = json!("TX2");
+        other["receipts_outcome"] = json!([{"id": "R9", "outcome": {}}]);
+        small.ingest(&other, "FINAL", 9_000);
+        assert!(small.len() <= 4);
+        assert!(small.status("TX2", true, 9_000).is_some());
+    }
+}
@@ -0,0 +1,233 @@
+//! Pin board: a per-session scratchpad for an investigation
+//!
+//! Pinned jump marks, free-text notes and the most recent copied payloads in
+//! one time-ordered list (Shift+B). Notes and copies remember the block / tx
+//! selected when they were made; the board exports as a markdown report with
+//! `nearx://` deep links. Nothing here is persisted: marks keep their own
+//! storage, notes and copies end with the session.
+
+use std::collections::VecDeque;
+
+use crate::router::RouteV1;
+use crate::time_display::TimeDisplay;
+use crate::types::Mark;
+
+/// Copies kept on the board (oldest dropped first)
+pub const MAX_COPIES: usize = 20;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PinKind {
+    Mark,
+    Note,
+    Copy,
+}
+
+impl PinKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            PinKind::Mark => "★ mark",
+            PinKind::Note => "✎ note",
+            PinKind::Copy => "⧉ copy",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PinItem {
+    pub kind: PinKind,
+    pub at_ms: i64,
+    /// Mark label, note text, or what was copied
+    pub title: String,
+    /// Copied payload
+    pub body: Option<String>,
+    pub height: Option<u64>,
+    pub tx_hash: Option<String>,
+}
+
+impl PinItem {
+    /// Deep link to the tx (preferred) or block this item points at
+    pub fn deep_link(&self) -> Option<String> {
+        match (&self.tx_hash, self.height) {
+            (Some(hash), _) => Some(RouteV1::Tx { hash: hash.clone() }.url()),
+            (None, Some(height)) => Some(RouteV1::Block { height }.url()),
+            (None, None) => None,
+        }
+    }
+
+    /// `#123 · tx ABCDEFGH` (empty when the item has no context)
+    pub fn context(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(h) = self.height {
+            parts.push(format!("#{h}"));
+        }
+        if let Some(hash) = &self.tx_hash {
+            parts.push(format!("tx {}", &hash[..hash.len().min(8)]));
+        }
+        parts.join(" · ")
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PinBoard {
+    notes: Vec<PinItem>,
+    copies: VecDeque<PinItem>,
+}
+
+impl PinBoard {
+    pub fn add_note(
+        &mut self,
+        text: String,
+        height: Option<u64>,
+        tx_hash: Option<String>,
+        at_ms: i64,
+    ) {
+        self.notes.push(PinItem {
+            kind: PinKind::Note,
+            at_ms,
+            title: text,
+            body: None,
+            height,
+            tx_hash,
+        });
+    }
+
+    pub fn add_copy(
+        &mut self,
+        title: String,
+        body: String,
+        height: Option<u64>,
+        tx_hash: Option<String>,
+        at_ms: i64,
+    ) {
+        if self.copies.len() == MAX_COPIES {
+            self.copies.pop_front();
+        }
+        self.copies.push_back(PinItem {
+            kind: PinKind::Copy,
+            at_ms,
+            title,
+            body: Some(body),
+            height,
+            tx_hash,
+        });
+    }
+
+    /// Drop a note or copy (marks are managed in the marks overlay)
+    pub fn remove(&mut self, item: &PinItem) -> bool {
+        let before = self.notes.len() + self.copies.len();
+        self.notes.retain(|n| n != item);
+        self.copies.retain(|c| c != item);
+        before != self.notes.len() + self.copies.len()
+    }
+
+    /// Pinned marks, notes and copies, oldest first
+    pub fn items(&self, marks: &[Mark]) -> Vec<PinItem> {
+        let mut items: Vec<PinItem> = marks
+            .iter()
+            .filter(|m| m.pinned)
+            .map(|m| PinItem {
+                kind: PinKind::Mark,
+                at_ms: m.when_ms,
+                title: m.label.clone(),
+                body: None,
+                height: m.height,
+                tx_hash: m.tx_hash.clone(),
+            })
+            .chain(self.notes.iter().cloned())
+            .chain(self.copies.iter().cloned())
+            .collect();
+        items.sort_by_key(|i| i.at_ms);
+        items
+    }
+}
+
+/// Markdown report of `items` (one bullet each, copies as fenced blocks)
+pub fn to_markdown(items: &[PinItem], generated_ms: i64, times: &TimeDisplay) -> String {
+    let date = times.at(generated_ms);
+    let mut out = format!("# NEARx investigation — {date}\n\n");
+    if items.is_empty() {
+        out.push_str("_Nothing pinned._\n");
+    }
+    for item in items {
+        let mut line = format!("- **{}** {}", times.time(item.at_ms), item.kind.label());
+        match item.kind {
+            PinKind::Mark => line.push_str(&format!(" `{}`", item.title)),
+            PinKind::Note | PinKind::Copy => line.push_str(&format!(" — {}", item.title)),
+        }
+        if let Some(h) = item.height {
+            line.push_str(&format!(" · block #{h}"));
+        }
+        if let Some(hash) = &item.tx_hash {
+            line.push_str(&format!(" · tx `{hash}`"));
+        }
+        if let Some(link) = item.deep_link() {
+            line.push_str(&format!(" · [{link}]({link})"));
+        }
+        out.push_str(&line);
+        out.push('\n');
+        if let Some(body) = &item.body {
+            out.push_str("\n  ```json\n");
+            for l in body.lines() {
+                out.push_str("  ");
+                out.push_str(l);
+                out.push('\n');
+            }
+            out.push_str("  ```\n\n");
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_display::TimeZonePref;
+
+    #[test]
+    fn merges_in_time_order_and_exports_links() {
+        let marks = vec![
+            Mark {
+                label: "a".into(),
+                pane: 1,
+                height: Some(100),
+                tx_hash: Some("TXA".into()),
+                when_ms: 2_000,
+                pinned: true,
+            },
+            Mark {
+                label: "b".into(),
+                pane: 0,
+                height: Some(101),
+                tx_hash: None,
+                when_ms: 500,
+                pinned: false, // not on the board
+            },
+        ];
+        let mut board = PinBoard::default();
+        board.add_note("suspicious relay".into(), Some(100), None, 1_000);
+        board.add_copy(
+            "Copied transaction".into(),
+            "{\n  \"a\": 1\n}".into(),
+            None,
+            Some("TXB".into()),
+            3_000,
+        );
+
+        let items = board.items(&marks);
+        let kinds: Vec<_> = items.iter().map(|i| i.kind).collect();
+        assert_eq!(kinds, [PinKind::Note, PinKind::Mark, PinKind::Copy]);
+        assert_eq!(
+            items[0].deep_link().as_deref(),
+            Some("nearx://v1/block/100")
+        );
+
+        let md = to_markdown(&items, 0, &TimeDisplay::new(TimeZonePref::Utc, "%Y-%m-%d".into()));
+        assert!(md.starts_with("# NEARx investigation — 1970-01-01 UTC"));
+        assert!(md.contains("✎ note — suspicious relay · block #100 · [nearx://v1/block/100]"));
+        assert!(md.contains("★ mark `a` · block #100 · tx `TXA` · [nearx://v1/tx/TXA]"));
+        assert!(md.contains("  ```json\n  {\n    \"a\": 1\n  }\n  ```"));
+
+        assert!(board.remove(&items[0]));
+        assert_eq!(board.items(&marks).len(), 2);
+    }
+}
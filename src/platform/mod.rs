@@ -15,14 +15,124 @@ mod web;
 // 2. Web is used only when native is not available (e.g., WASM-only builds)
 
 #[cfg(feature = "native")]
-pub use native::{copy_to_clipboard, History};
+pub use native::{copy_to_clipboard, copy_with_report, History};
 
 #[cfg(all(feature = "dom-web", not(feature = "native")))]
-pub use web::{copy_to_clipboard, History};
+pub use web::{copy_to_clipboard, copy_with_report, History};
+
+// 3. Neither (embedders, `--features embed`): the host app owns the clipboard
+
+/// No clipboard without a platform feature; embedders copy `CopyPayload`s themselves
+#[cfg(not(any(feature = "native", feature = "dom-web")))]
+pub fn copy_to_clipboard(_content: &str) -> bool {
+    false
+}
+
+/// Empty report (no backend tried) without a platform feature
+#[cfg(not(any(feature = "native", feature = "dom-web")))]
+pub fn copy_with_report(_content: &str) -> crate::clipboard::CopyReport {
+    crate::clipboard::CopyReport::default()
+}
 
 // Re-export types that are common across platforms
 pub use crate::history::{BlockPersist, HistoryHit, TxPersist};
 
+use crate::theme::Appearance;
+
+/// Best-effort OS dark/light appearance (`None` when nothing conclusive is found).
+///
+/// - macOS: `defaults read -g AppleInterfaceStyle` ("Dark", or unset for light)
+/// - Linux: GNOME `color-scheme`, then the terminal's `COLORFGBG`
+/// - Windows: `AppsUseLightTheme` registry value
+///
+/// Spawns a short-lived process on desktop OSes; call off the UI thread.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn system_appearance() -> Option<Appearance> {
+    use std::process::Command;
+
+    let run = |cmd: &str, args: &[&str]| -> Option<String> {
+        Command::new(cmd)
+            .args(args)
+            .output()
+            .ok()
+            .map(|o| String::from_utf8_lossy(&o.stdout).to_lowercase())
+    };
+
+    #[cfg(target_os = "macos")]
+    {
+        // The key is absent in light mode, so any successful run is conclusive
+        if let Some(out) = run("defaults", &["read", "-g", "AppleInterfaceStyle"]) {
+            return Some(if out.contains("dark") {
+                Appearance::Dark
+            } else {
+                Appearance::Light
+            });
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let key = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Themes\Personalize";
+        if let Some(out) = run("reg", &["query", key, "/v", "AppsUseLightTheme"]) {
+            if out.contains("0x1") {
+                return Some(Appearance::Light);
+            }
+            if out.contains("0x0") {
+                return Some(Appearance::Dark);
+            }
+        }
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        let scheme = run(
+            "gsettings",
+            &["get", "org.gnome.desktop.interface", "color-scheme"],
+        );
+        if let Some(out) = scheme {
+            if out.contains("prefer-dark") {
+                return Some(Appearance::Dark);
+            }
+            if out.contains("prefer-light") {
+                return Some(Appearance::Light);
+            }
+        }
+    }
+
+    #[allow(unreachable_code)]
+    std::env::var("COLORFGBG")
+        .ok()
+        .and_then(|v| appearance_from_colorfgbg(&v))
+}
+
+/// `prefers-color-scheme` media query (browser / Tauri webview)
+#[cfg(target_arch = "wasm32")]
+pub fn system_appearance() -> Option<Appearance> {
+    #[cfg(feature = "dom-web")]
+    {
+        let query = web_sys::window()?
+            .match_media("(prefers-color-scheme: dark)")
+            .ok()??;
+        return Some(if query.matches() {
+            Appearance::Dark
+        } else {
+            Appearance::Light
+        });
+    }
+    #[allow(unreachable_code)]
+    None
+}
+
+/// Parse `COLORFGBG` ("fg;bg" or "fg;default;bg"): ANSI background 7 or 9-15 is light
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+fn appearance_from_colorfgbg(v: &str) -> Option<Appearance> {
+    let bg: u8 = v.rsplit(';').next()?.trim().parse().ok()?;
+    Some(match bg {
+        7 | 9..=15 => Appearance::Light,
+        _ => Appearance::Dark,
+    })
+}
+
 /// Open a NEARx deep link (`nearx://…`) using the OS, to hand off to the desktop app.
 /// Returns true if the command was launched successfully.
 ///
@@ -60,6 +170,90 @@ pub fn open_deep_link(route_or_url: &str) -> bool {
     }
 }
 
+/// Best-effort lookup of the OS handler registered for `nearx://` links
+/// (`None` = nothing registered, or the OS couldn't be asked).
+///
+/// - macOS: LaunchServices `LSHandlers` (only lists handlers the user/app has claimed)
+/// - Windows: `HKCU` then `HKCR` `nearx\shell\open\command`
+/// - Linux: `xdg-mime query default x-scheme-handler/nearx`
+#[cfg(not(target_arch = "wasm32"))]
+pub fn deep_link_handler() -> Option<String> {
+    use std::process::Command;
+
+    let run = |cmd: &str, args: &[&str]| -> Option<String> {
+        let out = Command::new(cmd).args(args).output().ok()?;
+        out.status
+            .success()
+            .then(|| String::from_utf8_lossy(&out.stdout).into_owned())
+    };
+
+    #[cfg(target_os = "macos")]
+    {
+        let out = run(
+            "defaults",
+            &["read", "com.apple.LaunchServices/com.apple.launchservices.secure", "LSHandlers"],
+        )?;
+        // Entries are `{ LSHandlerRoleAll = "<bundle id>"; LSHandlerURLScheme = nearx; }`
+        let entry = out
+            .split('}')
+            .find(|e| e.to_lowercase().contains("lshandlerurlscheme = nearx;"))?;
+        return entry
+            .lines()
+            .find(|l| l.contains("LSHandlerRoleAll"))
+            .and_then(|l| l.split('"').nth(1))
+            .map(str::to_string)
+            .or_else(|| Some("registered".into()));
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        for key in [
+            r"HKCU\Software\Classes\nearx\shell\open\command",
+            r"HKCR\nearx\shell\open\command",
+        ] {
+            if let Some(out) = run("reg", &["query", key, "/ve"]) {
+                if let Some(cmd) = out.lines().find_map(|l| l.split("REG_SZ").nth(1)) {
+                    return Some(cmd.trim().to_string());
+                }
+            }
+        }
+        return None;
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        let out = run("xdg-mime", &["query", "default", "x-scheme-handler/nearx"])?;
+        let handler = out.trim();
+        (!handler.is_empty()).then(|| handler.to_string())
+    }
+}
+
+/// Open a folder in the OS file manager (exports overlay: "open containing folder").
+/// Returns true if the command was launched successfully.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn open_folder(path: &std::path::Path) -> bool {
+    use std::process::Command;
+
+    if !path.is_dir() {
+        return false;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        Command::new("open").arg(path).spawn().is_ok()
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        Command::new("explorer").arg(path).spawn().is_ok()
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        Command::new("xdg-open").arg(path).spawn().is_ok()
+    }
+}
+
 #[cfg(target_arch = "wasm32")]
 #[allow(unused_variables)]
 pub fn open_deep_link(_route_or_url: &str) -> bool {
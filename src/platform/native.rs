@@ -1,18 +1,20 @@
 //! Native platform implementation (uses tokio, copypasta, rusqlite)
 
+use crate::clipboard::CopyReport;
 use crate::history::History as HistoryImpl;
-use copypasta::{ClipboardContext, ClipboardProvider};
 
 // Re-export commonly used history types
 #[allow(unused_imports)]
 pub use crate::history::{BlockPersist, HistoryHit, TxPersist};
 
-/// Copy text to clipboard using copypasta
+/// Copy text through the clipboard backend chain (see `crate::clipboard`)
 pub fn copy_to_clipboard(content: &str) -> bool {
-    match ClipboardContext::new() {
-        Ok(mut ctx) => ctx.set_contents(content.to_string()).is_ok(),
-        Err(_) => false,
-    }
+    copy_with_report(content).ok()
+}
+
+/// Copy text and report which backend took it
+pub fn copy_with_report(content: &str) -> CopyReport {
+    crate::clipboard::copy(content)
 }
 
 // Re-export History type
@@ -3,6 +3,7 @@
 // Allow dead code when native feature is enabled (Tauri uses native, not web)
 #![cfg_attr(feature = "native", allow(dead_code))]
 
+use crate::clipboard::CopyReport;
 use crate::history::{BlockPersist, HistoryHit};
 use anyhow::Result;
 
@@ -13,6 +14,11 @@ pub fn copy_to_clipboard(_content: &str) -> bool {
     false
 }
 
+/// Copy report for the shared copy path (the JS bridge does the real copy)
+pub fn copy_with_report(_content: &str) -> CopyReport {
+    CopyReport::default()
+}
+
 /// In-memory history implementation for web
 /// (SQLite not available in WASM, IndexedDB would be future enhancement)
 pub struct History {
@@ -0,0 +1,170 @@
+//! Plugin bus for the native TUI (`PLUGIN_SOCKET`, Unix only)
+//!
+//! Out-of-process plugins connect with nearx-plugin-core's `IPCClient` and send
+//! `PluginMessage`s (length-prefixed bincode). A `PluginAlert` reaches the app
+//! as `AppEvent::PluginAlert`: a footer segment for warnings, a toast for
+//! critical alerts, and a pinned jump mark for those (`PLUGIN_ALERT_MARKS`).
+//! Other messages are ignored. The socket is owner-only (`0600`); there is no
+//! token, so anything that can open it can raise alerts.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use nearx_plugin_core::{IPCServer, PluginMessage};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::task::JoinHandle;
+
+use crate::types::{AlertSeverity, AppEvent, PluginAlert};
+
+/// App event for a plugin message (None = nothing the app shows)
+pub fn app_event(msg: PluginMessage) -> Option<AppEvent> {
+    match msg {
+        PluginMessage::PluginAlert {
+            plugin_id,
+            severity,
+            block_height,
+            tx_hash,
+            message,
+        } => Some(AppEvent::PluginAlert(PluginAlert {
+            plugin_id,
+            severity: match severity {
+                nearx_plugin_core::AlertSeverity::Info => AlertSeverity::Info,
+                nearx_plugin_core::AlertSeverity::Warning => AlertSeverity::Warning,
+                nearx_plugin_core::AlertSeverity::Critical => AlertSeverity::Critical,
+            },
+            block_height,
+            tx_hash,
+            message,
+        })),
+        _ => None,
+    }
+}
+
+/// Running plugin bus; dropping it stops accepting and removes the socket
+pub struct PluginBus {
+    path: PathBuf,
+    accept: JoinHandle<()>,
+}
+
+impl PluginBus {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for PluginBus {
+    fn drop(&mut self) {
+        self.accept.abort();
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Listen on `path` and forward plugin alerts to `events`
+pub async fn serve(path: &Path, events: UnboundedSender<AppEvent>) -> Result<PluginBus> {
+    use std::os::unix::fs::PermissionsExt;
+
+    // A socket left behind by a crashed run is replaced; a live one is not
+    if path.exists() && tokio::net::UnixStream::connect(path).await.is_ok() {
+        anyhow::bail!("plugins: {} is in use by another instance", path.display());
+    }
+    let server = IPCServer::bind_unix(path)
+        .await
+        .with_context(|| format!("plugins: cannot listen on {}", path.display()))?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+
+    let accept = tokio::spawn(async move {
+        loop {
+            match server.accept().await {
+                Ok(mut conn) => {
+                    let events = events.clone();
+                    tokio::spawn(async move {
+                        while let Some(msg) = conn.rx.recv().await {
+                            if let Some(ev) = app_event(msg) {
+                                if events.send(ev).is_err() {
+                                    break;
+                                }
+                            }
+                        }
+                    });
+                }
+                Err(e) => {
+                    log::warn!("plugins: accept failed: {e}");
+                    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                }
+            }
+        }
+    });
+
+    Ok(PluginBus {
+        path: path.to_path_buf(),
+        accept,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::History;
+    use crate::marks::JumpMarks;
+    use nearx_plugin_core::IPCClient;
+    use std::time::Duration;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    fn alert(severity: nearx_plugin_core::AlertSeverity) -> PluginMessage {
+        PluginMessage::PluginAlert {
+            plugin_id: "tx-analyzer".into(),
+            severity,
+            block_height: Some(42),
+            tx_hash: Some("AbC".into()),
+            message: "drain pattern".into(),
+        }
+    }
+
+    #[test]
+    fn only_alerts_become_app_events() {
+        let ping = PluginMessage::Ping {
+            timestamp: chrono::Utc::now(),
+        };
+        assert!(app_event(ping).is_none());
+        match app_event(alert(nearx_plugin_core::AlertSeverity::Warning)) {
+            Some(AppEvent::PluginAlert(a)) => {
+                assert_eq!(a.severity, AlertSeverity::Warning);
+                assert_eq!(a.tx_hash.as_deref(), Some("AbC"));
+            }
+            other => panic!("expected a plugin alert, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn critical_alert_from_a_plugin_pins_a_mark() {
+        let dir = std::env::temp_dir().join(format!("nearx-plugin-bus-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("plugins.sock");
+        let (tx, mut rx) = unbounded_channel();
+        let bus = serve(&path, tx).await.unwrap();
+
+        let client = IPCClient::connect_unix(&path).await.unwrap();
+        client
+            .send(alert(nearx_plugin_core::AlertSeverity::Critical))
+            .await
+            .unwrap();
+        let ev = tokio::time::timeout(Duration::from_secs(2), rx.recv())
+            .await
+            .expect("alert forwarded")
+            .unwrap();
+        let AppEvent::PluginAlert(alert) = ev else {
+            panic!("expected a plugin alert, got {ev:?}");
+        };
+
+        let mut marks = JumpMarks::new(History::start(":memory:").unwrap());
+        let label = marks.add_alert_mark(&alert).await;
+        assert_eq!(label.as_deref(), Some("tx-analyzer#1"));
+        let mark = marks.list().into_iter().find(|m| m.label == "tx-analyzer#1").unwrap();
+        assert!(mark.pinned);
+        assert_eq!((mark.pane, mark.height, mark.tx_hash.as_deref()), (1, Some(42), Some("AbC")));
+
+        drop(bus);
+        assert!(!path.exists());
+        let _ = std::fs::remove_dir(&dir);
+    }
+}
@@ -0,0 +1,319 @@
+//! One-shot query mode (`nearx query "<filter>" --format json`)
+//!
+//! Runs without a UI: searches SQLite History, scans the most recent blocks over
+//! RPC, prints the matches and exits with a grep-style status (0 = matches,
+//! 1 = none, 2 = error) so the crate can be used from shell pipelines and cron.
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::io::Write;
+
+use crate::config::{Config, QueryArgs, QueryFormat};
+use crate::filter::{compile_filter, haystack_matches, is_excluded, CompiledFilter, TxHaystack};
+use crate::history::{History, HistoryHit, MAX_SEARCH_RESULTS};
+use crate::rpc_utils::{fetch_block_with_txs, get_latest_block};
+use crate::types::{ActionSummary, BlockRow, TxLite};
+
+/// A single printed match
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryMatch {
+    pub hash: String,
+    pub height: u64,
+    pub ts_ms: i64,
+    pub signer: Option<String>,
+    pub receiver: Option<String>,
+    pub methods: Vec<String>,
+    /// Final outcome stored in History (`success` / `failure: …`), when known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// "history" or "rpc"
+    pub source: &'static str,
+}
+
+/// Parsed query: the filter grammar plus `since:<duration>`
+#[derive(Debug, Clone)]
+pub struct QuerySpec {
+    pub filter: CompiledFilter,
+    /// Positive terms only, forwarded to `History::search` (negations are post-filtered)
+    pub history_query: String,
+    /// Oldest accepted block timestamp (ms)
+    pub since_ms: Option<i64>,
+}
+
+impl QuerySpec {
+    fn in_window(&self, ts_ms: i64) -> bool {
+        self.since_ms.is_none_or(|since| ts_ms >= since)
+    }
+
+    /// History rows are already matched by SQL; apply `since:` and exclusions
+    fn accepts_hit(&self, hit: &HistoryHit) -> bool {
+        let actions = hit
+            .actions_json
+            .as_deref()
+            .and_then(|a| serde_json::from_str::<serde_json::Value>(a).ok());
+        let tx = serde_json::json!({
+            "hash": hit.hash,
+            "signer_id": hit.signer,
+            "receiver_id": hit.receiver,
+            "actions": actions,
+        });
+        self.in_window(hit.ts_ms) && !is_excluded(&TxHaystack::from_json(&tx), &self.filter)
+    }
+}
+
+/// Split `since:` out of a query and compile the rest
+pub fn parse_query(q: &str, now_ms: i64) -> Result<QuerySpec> {
+    let mut since_ms = None;
+    let mut filter_terms = Vec::new();
+    let mut history_terms = Vec::new();
+
+    for tok in q.split_whitespace() {
+        if let Some(dur) = tok.strip_prefix("since:") {
+            let secs = parse_duration_secs(dur)
+                .ok_or_else(|| anyhow!("Invalid since '{dur}'. Use e.g. 30s, 15m, 1h, 2d"))?;
+            since_ms = Some(now_ms - secs as i64 * 1000);
+            continue;
+        }
+        filter_terms.push(tok);
+        if !tok.starts_with('-') && !tok.starts_with('!') {
+            history_terms.push(tok);
+        }
+    }
+
+    Ok(QuerySpec {
+        filter: compile_filter(&filter_terms.join(" ")),
+        history_query: history_terms.join(" "),
+        since_ms,
+    })
+}
+
+/// `90s`, `15m`, `1h`, `2d`, `1w` (bare numbers are seconds)
+pub fn parse_duration_secs(s: &str) -> Option<u64> {
+    let s = s.trim().to_lowercase();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (num, unit) = s.split_at(split);
+    let n: u64 = num.parse().ok()?;
+    let mult = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 3_600,
+        "d" => 86_400,
+        "w" => 604_800,
+        _ => return None,
+    };
+    n.checked_mul(mult)
+}
+
+/// Run the query and print matches to stdout. Returns the number printed.
+pub async fn run(cfg: &Config, args: &QueryArgs) -> Result<usize> {
+    let spec = parse_query(&args.query, chrono::Utc::now().timestamp_millis())?;
+    let mut matches = Vec::new();
+    let mut seen = HashSet::new();
+    let mut sources_ok = 0;
+
+    // 1) History (only if a database already exists; never create one here)
+    let db_path = cfg.history_db_path();
+    if std::path::Path::new(&db_path).exists() {
+        let history = History::start(&db_path)?;
+        // Ask for the most History returns: exclusions drop rows after SQL, and
+        // `--limit` applies to the merged, sorted list below
+        let hits = history
+            .search_since(spec.history_query.clone(), MAX_SEARCH_RESULTS, spec.since_ms)
+            .await;
+        for hit in hits {
+            if spec.accepts_hit(&hit) && seen.insert(hit.hash.clone()) {
+                matches.push(match_from_hit(hit));
+            }
+        }
+        sources_ok += 1;
+    }
+
+    // 2) Targeted RPC backfill: newest blocks first, stop at the `since:` window
+    if args.backfill > 0 {
+        match backfill(cfg, &spec, args.backfill, &mut seen, &mut matches).await {
+            Ok(()) => sources_ok += 1,
+            Err(e) => eprintln!("nearx query: RPC backfill failed: {e:#}"),
+        }
+    }
+
+    if sources_ok == 0 {
+        return Err(anyhow!(
+            "no data source available (no History at {db_path} and RPC backfill failed or disabled)"
+        ));
+    }
+
+    matches.sort_by(|a, b| b.height.cmp(&a.height).then_with(|| a.hash.cmp(&b.hash)));
+    matches.truncate(args.limit);
+    print_matches(&matches, args.format)?;
+    Ok(matches.len())
+}
+
+async fn backfill(
+    cfg: &Config,
+    spec: &QuerySpec,
+    blocks: u64,
+    seen: &mut HashSet<String>,
+    out: &mut Vec<QueryMatch>,
+) -> Result<()> {
+    let token = cfg.fastnear_auth_token.as_deref();
+    let latest = get_latest_block(&cfg.near_node_url, cfg.rpc_timeout_ms, token).await?;
+    let latest_h = latest["header"]["height"]
+        .as_u64()
+        .ok_or_else(|| anyhow!("latest block has no height"))?;
+
+    for h in (latest_h.saturating_sub(blocks - 1)..=latest_h).rev() {
+        // Skipped heights (no block produced) are normal; keep walking
+        let Ok(block) = fetch_block_with_txs(
+            &cfg.near_node_url,
+            h,
+            cfg.rpc_timeout_ms,
+            cfg.poll_chunk_concurrency,
+            token,
+        )
+        .await
+        else {
+            continue;
+        };
+        if !spec.in_window(block.timestamp as i64) {
+            break;
+        }
+        for tx in &block.transactions {
+            if haystack_matches(&TxHaystack::from_tx(tx), &spec.filter) && seen.insert(tx.hash.clone()) {
+                out.push(match_from_tx(&block, tx));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn match_from_hit(hit: HistoryHit) -> QueryMatch {
+    QueryMatch {
+        methods: hit
+            .methods
+            .as_deref()
+            .unwrap_or_default()
+            .split(", ")
+            .filter(|m| !m.is_empty())
+            .map(str::to_string)
+            .collect(),
+        hash: hit.hash,
+        height: hit.height,
+        ts_ms: hit.ts_ms,
+        signer: hit.signer,
+        receiver: hit.receiver,
+        status: hit.status.map(|s| s.label()),
+        source: "history",
+    }
+}
+
+fn match_from_tx(block: &BlockRow, tx: &TxLite) -> QueryMatch {
+    let methods = tx
+        .actions
+        .iter()
+        .flatten()
+        .filter_map(|a| match a {
+            ActionSummary::FunctionCall { method_name, .. } => Some(method_name.clone()),
+            _ => None,
+        })
+        .collect();
+    QueryMatch {
+        hash: tx.hash.clone(),
+        height: block.height,
+        ts_ms: block.timestamp as i64,
+        signer: tx.signer_id.clone(),
+        receiver: tx.receiver_id.clone(),
+        methods,
+        status: None,
+        source: "rpc",
+    }
+}
+
+fn print_matches(matches: &[QueryMatch], format: QueryFormat) -> Result<()> {
+    let mut out = std::io::stdout().lock();
+    match format {
+        QueryFormat::Json => writeln!(out, "{}", serde_json::to_string_pretty(matches)?)?,
+        QueryFormat::Jsonl => {
+            for m in matches {
+                writeln!(out, "{}", serde_json::to_string(m)?)?;
+            }
+        }
+        QueryFormat::Text => {
+            for m in matches {
+                writeln!(
+                    out,
+                    "{:>10}  {:<44}  {} → {}  {}",
+                    m.height,
+                    m.hash,
+                    m.signer.as_deref().unwrap_or("?"),
+                    m.receiver.as_deref().unwrap_or("?"),
+                    m.methods.join(",")
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration_secs("90"), Some(90));
+        assert_eq!(parse_duration_secs("15m"), Some(900));
+        assert_eq!(parse_duration_secs("1H"), Some(3_600));
+        assert_eq!(parse_duration_secs("2d"), Some(172_800));
+        assert_eq!(parse_duration_secs("1y"), None);
+        assert_eq!(parse_duration_secs("h"), None);
+    }
+
+    #[test]
+    fn since_and_negations_stay_out_of_history_sql() {
+        let spec = parse_query("signer:alice.near -method:ping since:1h", 10_000_000).unwrap();
+        assert_eq!(spec.history_query, "signer:alice.near");
+        assert_eq!(spec.since_ms, Some(10_000_000 - 3_600_000));
+        assert_eq!(spec.filter.signer, vec!["alice.near".to_string()]);
+        assert_eq!(spec.filter.exclude.len(), 1);
+        assert!(parse_query("since:soon", 0).is_err());
+    }
+
+    #[test]
+    fn exclusions_see_stored_actions() {
+        let spec = parse_query("signer:alice.near -method:ping -action:transfer", 0).unwrap();
+        let hit = |actions: &str| HistoryHit {
+            hash: "T1".into(),
+            height: 1,
+            ts_ms: 0,
+            signer: Some("alice.near".into()),
+            receiver: Some("intents.near".into()),
+            methods: None,
+            actions_json: Some(actions.into()),
+            status: None,
+        };
+        let call = |m: &str| {
+            format!(r#"[{{"type":"FunctionCall","method_name":"{m}","args_decoded":null,"gas":1,"deposit":0}}]"#)
+        };
+        assert!(spec.accepts_hit(&hit(&call("ft_transfer"))));
+        assert!(!spec.accepts_hit(&hit(&call("ping"))));
+        assert!(!spec.accepts_hit(&hit(r#"[{"type":"Transfer","deposit":1}]"#)));
+    }
+
+    #[test]
+    fn since_window_drops_older_hits() {
+        let spec = parse_query("signer:alice.near since:1h", 10_000_000).unwrap();
+        let at = |ts_ms: i64| HistoryHit {
+            hash: "T1".into(),
+            height: 1,
+            ts_ms,
+            signer: Some("alice.near".into()),
+            receiver: None,
+            methods: None,
+            actions_json: None,
+            status: None,
+        };
+        assert!(spec.accepts_hit(&at(10_000_000 - 60_000)));
+        assert!(!spec.accepts_hit(&at(10_000_000 - 7_200_000)));
+    }
+}
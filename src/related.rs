@@ -0,0 +1,307 @@
+//! Related-transaction suggestions for Details
+//!
+//! When a tx is opened, txs in nearby blocks that share its signer or
+//! receiver, call back into it, or reference the same ids are ranked and
+//! listed under `related` in Details (`1`-`9` in Blocks / Txs open one).
+//! Candidates come from the live buffer right away and from History once the
+//! runtime's lookup (`History::related_txs`) comes back.
+//!
+//! Receipt ids are not part of the block feed, so ids are the hash-like
+//! strings (base58 32-byte or 64 hex) found in call args: yield `data_id`s and
+//! receipt / tx hashes handed between contracts. Two txs sharing one, or one
+//! naming the other's hash, are almost certainly linked.
+
+use std::collections::{BTreeSet, HashSet};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::history::TxPersist;
+use crate::types::TxLite;
+
+/// Blocks either side of the tx searched for candidates
+pub const NEARBY_BLOCKS: u64 = 100;
+/// Suggestions shown in Details (keys `1`-`9` open them)
+pub const RELATED_LIMIT: usize = 8;
+/// Rows History returns per lookup (nearest blocks first)
+pub const HISTORY_CANDIDATES: usize = 500;
+/// Ids per tx passed on to the History lookup
+const MAX_QUERY_IDS: usize = 8;
+
+const SHARED_ID: u32 = 8;
+const SAME_SIGNER: u32 = 4;
+const COUNTERPART: u32 = 3;
+const SAME_RECEIVER: u32 = 2;
+const SAME_METHOD: u32 = 1;
+
+/// A tx as the heuristic sees it (from the buffer or a History row)
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub hash: String,
+    pub height: u64,
+    pub signer: Option<String>,
+    pub receiver: Option<String>,
+    methods: BTreeSet<String>,
+    ids: BTreeSet<String>,
+    /// Stored JSON, for opening a tx that is no longer in the buffer
+    pub raw_json: Option<String>,
+}
+
+impl Candidate {
+    pub fn from_tx(tx: &TxLite, height: u64) -> Self {
+        let actions = serde_json::to_value(&tx.actions).unwrap_or_default();
+        Self::new(
+            &tx.hash,
+            height,
+            tx.signer_id.clone(),
+            tx.receiver_id.clone(),
+            &actions,
+            None,
+        )
+    }
+
+    pub fn from_stored(tx: &TxPersist) -> Self {
+        let actions = tx
+            .actions_json
+            .as_deref()
+            .and_then(|j| serde_json::from_str(j).ok())
+            .unwrap_or_default();
+        Self::new(
+            &tx.hash,
+            tx.height,
+            tx.signer.clone(),
+            tx.receiver.clone(),
+            &actions,
+            tx.raw_json.clone(),
+        )
+    }
+
+    fn new(
+        hash: &str,
+        height: u64,
+        signer: Option<String>,
+        receiver: Option<String>,
+        actions: &Value,
+        raw_json: Option<String>,
+    ) -> Self {
+        let mut c = Self {
+            hash: hash.to_string(),
+            height,
+            signer,
+            receiver,
+            methods: BTreeSet::new(),
+            ids: BTreeSet::new(),
+            raw_json,
+        };
+        c.walk(actions);
+        c.ids.remove(hash);
+        c
+    }
+
+    /// Collect method names and hash-like strings from the actions
+    fn walk(&mut self, v: &Value) {
+        match v {
+            Value::Object(map) => {
+                for (k, v) in map {
+                    match (k.as_str(), v) {
+                        ("method_name", Value::String(m)) => {
+                            self.methods.insert(m.clone());
+                        }
+                        _ => self.walk(v),
+                    }
+                }
+            }
+            Value::Array(items) => items.iter().for_each(|v| self.walk(v)),
+            Value::String(s) => {
+                if let Some(id) = as_id(s) {
+                    self.ids.insert(id);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A base58 32-byte hash (43-44 chars) as is, or 64 hex digits (optional
+/// `0x`) lowercased; None for anything else
+fn as_id(s: &str) -> Option<String> {
+    let hex = s.strip_prefix("0x").unwrap_or(s);
+    if hex.len() == 64 && hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Some(hex.to_ascii_lowercase());
+    }
+    let base58 = s
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric() && !matches!(b, b'0' | b'O' | b'I' | b'l'));
+    ((43..=44).contains(&s.len()) && base58).then(|| s.to_string())
+}
+
+/// A ranked suggestion
+#[derive(Debug, Clone, Serialize)]
+pub struct RelatedTx {
+    pub hash: String,
+    pub height: u64,
+    pub signer: Option<String>,
+    pub receiver: Option<String>,
+    /// Why it's related, strongest first
+    pub why: Vec<&'static str>,
+    pub score: u32,
+    #[serde(skip)]
+    pub raw_json: Option<String>,
+}
+
+/// Score one candidate against the target (None if unrelated)
+fn score(target: &Candidate, c: &Candidate) -> Option<RelatedTx> {
+    let mut why = Vec::new();
+    let mut score = 0;
+    let mut add = |points, reason| {
+        score += points;
+        why.push(reason);
+    };
+    if c.ids.contains(&target.hash)
+        || target.ids.contains(&c.hash)
+        || !target.ids.is_disjoint(&c.ids)
+    {
+        add(SHARED_ID, "shared id");
+    }
+    let same = |a: &Option<String>, b: &Option<String>| a.is_some() && a == b;
+    if same(&c.signer, &target.signer) {
+        add(SAME_SIGNER, "same signer");
+    }
+    if (same(&c.signer, &target.receiver) || same(&c.receiver, &target.signer))
+        && target.signer != target.receiver
+    {
+        add(COUNTERPART, "counterpart");
+    }
+    if same(&c.receiver, &target.receiver) {
+        add(SAME_RECEIVER, "same receiver");
+        if !target.methods.is_disjoint(&c.methods) {
+            add(SAME_METHOD, "same method");
+        }
+    }
+    (score > 0).then(|| RelatedTx {
+        hash: c.hash.clone(),
+        height: c.height,
+        signer: c.signer.clone(),
+        receiver: c.receiver.clone(),
+        why,
+        score,
+        raw_json: c.raw_json.clone(),
+    })
+}
+
+/// Rank candidates within `NEARBY_BLOCKS` of the target: score, then block
+/// distance. The first copy of a hash wins (buffer before History).
+pub fn rank(target: &Candidate, candidates: impl IntoIterator<Item = Candidate>) -> Vec<RelatedTx> {
+    let mut seen = HashSet::from([target.hash.clone()]);
+    let mut out: Vec<RelatedTx> = candidates
+        .into_iter()
+        .filter(|c| c.height.abs_diff(target.height) <= NEARBY_BLOCKS)
+        .filter(|c| seen.insert(c.hash.clone()))
+        .filter_map(|c| score(target, &c))
+        .collect();
+    out.sort_by_key(|r| (std::cmp::Reverse(r.score), r.height.abs_diff(target.height)));
+    out.truncate(RELATED_LIMIT);
+    out
+}
+
+/// What the runtime asks History for: txs near `height` touching the same
+/// accounts or mentioning the target's hash / ids
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelatedQuery {
+    pub hash: String,
+    pub height: u64,
+    pub accounts: Vec<String>,
+    pub ids: Vec<String>,
+}
+
+impl RelatedQuery {
+    pub fn for_target(target: &Candidate) -> Self {
+        let mut accounts: Vec<String> = [&target.signer, &target.receiver]
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect();
+        accounts.dedup();
+        Self {
+            hash: target.hash.clone(),
+            height: target.height,
+            accounts,
+            ids: target.ids.iter().take(MAX_QUERY_IDS).cloned().collect(),
+        }
+    }
+}
+
+/// One line per suggestion for the Details JSON (`#` is the key that opens it)
+pub fn to_json(related: &[RelatedTx]) -> Value {
+    related
+        .iter()
+        .enumerate()
+        .map(|(i, r)| {
+            serde_json::json!({
+                "#": i + 1,
+                "hash": r.hash,
+                "block": r.height,
+                "signer": r.signer,
+                "receiver": r.receiver,
+                "why": r.why.join(", "),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::fixtures::stored_tx;
+    use serde_json::json;
+
+    /// Candidate for a stored tx carrying `actions`
+    fn tx(hash: &str, height: u64, signer: &str, receiver: &str, actions: Value) -> Candidate {
+        Candidate::from_stored(&TxPersist {
+            actions_json: Some(actions.to_string()),
+            ..stored_tx(hash, height, signer, receiver)
+        })
+    }
+
+    #[test]
+    fn ranks_shared_ids_and_accounts_by_strength_then_distance() {
+        let data_id = "5h3Fk2dQ9vYp8mXr7bNw4cTz6aLs1eGu3jKo9pRt2Vx";
+        let call = |id: &str| {
+            json!([{ "type": "FunctionCall", "method_name": "respond",
+                     "args_decoded": { "Json": { "request": { "data_id": id } } } }])
+        };
+        let target = tx("T", 1000, "alice.near", "signer.near", call(data_id));
+        assert!(target.ids.contains(data_id));
+
+        let ranked = rank(
+            &target,
+            vec![
+                tx("A", 1001, "bob.near", "signer.near", json!([])),
+                tx("B", 1050, "solver.near", "other.near", call(data_id)),
+                tx("C", 1002, "alice.near", "x.near", json!([])),
+                tx("D", 1001, "signer.near", "alice.near", json!([])),
+                tx("E", 1003, "carol.near", "y.near", json!([])),
+                tx("F", 1500, "alice.near", "signer.near", json!([])),
+                tx("C", 990, "alice.near", "signer.near", json!([])), // duplicate hash
+                tx("T", 1000, "alice.near", "signer.near", json!([])), // itself
+            ],
+        );
+        let order: Vec<&str> = ranked.iter().map(|r| r.hash.as_str()).collect();
+        assert_eq!(order, ["B", "C", "D", "A"]);
+        assert_eq!(ranked[0].why, ["shared id"]);
+        assert_eq!(ranked[2].why, ["counterpart"]);
+
+        // A tx whose args name the target's hash is linked to it
+        let hash = "9QbXvFz4aRmW7tYk2NcE5pLs8dHu3gJo6iVr1wSx2Kc";
+        let target = tx(hash, 10, "a.near", "b.near", json!([]));
+        let resume = tx("R", 12, "z.near", "q.near", call(hash));
+        assert_eq!(rank(&target, vec![resume])[0].why, ["shared id"]);
+
+        let q = RelatedQuery::for_target(&tx("T", 5, "a.near", "a.near", call(data_id)));
+        assert_eq!(
+            (q.accounts, q.ids),
+            (vec!["a.near".to_string()], vec![data_id.to_string()])
+        );
+        assert_eq!(to_json(&ranked)[0]["#"], 1);
+    }
+}
@@ -0,0 +1,370 @@
+//! Release artifact verification (Tauri sidecar spawn)
+//!
+//! Release binaries ship with a detached signature next to them
+//! (`<artifact>.sig`): the base64 Ed25519 signature of the file's SHA-256
+//! digest, made with the release key. The desktop shell bundles the public key
+//! at build time (`NEARX_RELEASE_PUBKEY`) and refuses to run an artifact whose
+//! signature doesn't check out.
+//!
+//! The artifact is read once (`Artifact::load`): the bytes that were hashed
+//! are the bytes written to a private staging copy (`Artifact::stage`) and
+//! executed, so swapping the file after the check changes nothing.
+//!
+//! Signing a release (OpenSSL 3, Ed25519 private key in `release.pem`;
+//! the bundled key is the raw public key in base64):
+//!
+//! ```text
+//! openssl pkey -in release.pem -pubout -outform DER | tail -c 32 | base64
+//! openssl dgst -sha256 -binary nearx-native-host > digest.bin
+//! openssl pkeyutl -sign -rawin -inkey release.pem -in digest.bin | base64 > nearx-native-host.sig
+//! ```
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use base64::Engine;
+use ed25519_dalek::{Signature, VerifyingKey};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// Extension of the detached signature file
+pub const SIGNATURE_EXT: &str = "sig";
+
+/// Public half of the release signing key
+#[derive(Clone, Debug)]
+pub struct ReleaseKey {
+    key: VerifyingKey,
+}
+
+impl ReleaseKey {
+    /// Parse a base64 Ed25519 public key (`ed25519:` prefix optional)
+    pub fn parse(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let b64 = s.strip_prefix("ed25519:").unwrap_or(s);
+        let bytes: [u8; 32] = base64::engine::general_purpose::STANDARD
+            .decode(b64)
+            .ok()
+            .and_then(|b| b.try_into().ok())
+            .ok_or_else(|| anyhow!("release key must be 32 bytes of base64"))?;
+        let key =
+            VerifyingKey::from_bytes(&bytes).map_err(|e| anyhow!("invalid release key: {e}"))?;
+        Ok(Self { key })
+    }
+
+    /// Short id for logs and the About dialog (first 16 hex digits of the key's SHA-256)
+    pub fn fingerprint(&self) -> String {
+        hex(&Sha256::digest(self.key.as_bytes()))[..16].to_string()
+    }
+}
+
+/// Outcome of checking one artifact
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Verification {
+    /// Signature matches the release key
+    Verified { sha256: String },
+    /// Signature present but doesn't match: modified or foreign binary
+    Tampered { sha256: String },
+    /// No `.sig` next to the artifact
+    Unsigned { sha256: String },
+    /// No release key bundled in this build
+    NoKey { sha256: String },
+    /// Unreadable artifact or malformed signature
+    Error { error: String },
+}
+
+impl Verification {
+    /// Whether the artifact may run; unsigned / keyless artifacts only with
+    /// `allow_unverified` (development builds)
+    pub fn allows_run(&self, allow_unverified: bool) -> bool {
+        match self {
+            Verification::Verified { .. } => true,
+            Verification::Unsigned { .. } | Verification::NoKey { .. } => allow_unverified,
+            Verification::Tampered { .. } | Verification::Error { .. } => false,
+        }
+    }
+
+    /// One line for logs and the About dialog
+    pub fn summary(&self) -> String {
+        let short = |h: &str| h.chars().take(16).collect::<String>();
+        match self {
+            Verification::Verified { sha256 } => format!("verified (sha256 {}…)", short(sha256)),
+            Verification::Tampered { sha256 } => {
+                format!("signature mismatch (sha256 {}…)", short(sha256))
+            }
+            Verification::Unsigned { sha256 } => format!("unsigned (sha256 {}…)", short(sha256)),
+            Verification::NoKey { sha256 } => {
+                format!(
+                    "not checked: no release key in this build (sha256 {}…)",
+                    short(sha256)
+                )
+            }
+            Verification::Error { error } => format!("verification failed: {error}"),
+        }
+    }
+}
+
+/// `<artifact>.sig`
+pub fn signature_path(artifact: &Path) -> PathBuf {
+    let mut name = artifact.as_os_str().to_os_string();
+    name.push(".");
+    name.push(SIGNATURE_EXT);
+    PathBuf::from(name)
+}
+
+/// Check `digest` (the artifact's SHA-256) against a detached signature
+pub fn verify_digest(
+    digest: &[u8; 32],
+    signature: Option<&str>,
+    key: Option<&ReleaseKey>,
+) -> Verification {
+    let sha256 = hex(digest);
+    let Some(signature) = signature else {
+        return Verification::Unsigned { sha256 };
+    };
+    let Some(key) = key else {
+        return Verification::NoKey { sha256 };
+    };
+    // `base64` wraps long output; the signature may span lines
+    let signature: String = signature.split_whitespace().collect();
+    let sig = base64::engine::general_purpose::STANDARD
+        .decode(signature)
+        .ok()
+        .and_then(|b| Signature::from_slice(&b).ok());
+    match sig {
+        None => Verification::Error {
+            error: "malformed signature".to_string(),
+        },
+        Some(sig) if key.key.verify_strict(digest, &sig).is_ok() => {
+            Verification::Verified { sha256 }
+        }
+        Some(_) => Verification::Tampered { sha256 },
+    }
+}
+
+/// An artifact read into memory once, with the result of checking those bytes
+pub struct Artifact {
+    bytes: Vec<u8>,
+    verification: Verification,
+}
+
+impl Artifact {
+    /// Read `path` and check it against `<path>.sig`
+    pub fn load(path: &Path, key: Option<&ReleaseKey>) -> Self {
+        let bytes = match std::fs::read(path) {
+            Ok(b) => b,
+            Err(e) => {
+                return Self {
+                    bytes: Vec::new(),
+                    verification: Verification::Error {
+                        error: format!("cannot read {}: {e}", path.display()),
+                    },
+                }
+            }
+        };
+        let digest: [u8; 32] = Sha256::digest(&bytes).into();
+        let signature = std::fs::read_to_string(signature_path(path)).ok();
+        let verification = verify_digest(&digest, signature.as_deref(), key);
+        Self {
+            bytes,
+            verification,
+        }
+    }
+
+    pub fn verification(&self) -> &Verification {
+        &self.verification
+    }
+
+    pub fn into_verification(self) -> Verification {
+        self.verification
+    }
+
+    /// Write the checked bytes to `dir/name` for running: `dir` is made
+    /// owner-only and the copy owner-executable; an older copy is replaced
+    pub fn stage(&self, dir: &Path, name: &str) -> std::io::Result<PathBuf> {
+        if matches!(self.verification, Verification::Error { .. }) {
+            return Err(std::io::Error::other("artifact was not read"));
+        }
+        std::fs::create_dir_all(dir)?;
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+            std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))?;
+            options.mode(0o700);
+        }
+        let staged = dir.join(name);
+        match std::fs::remove_file(&staged) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+        let mut file = options.open(&staged)?;
+        file.write_all(&self.bytes)?;
+        file.sync_all()?;
+        Ok(staged)
+    }
+}
+
+/// Hash `path` and check it against `<path>.sig`
+pub fn verify_file(path: &Path, key: Option<&ReleaseKey>) -> Verification {
+    Artifact::load(path, key).into_verification()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    const BUILD: &[u8] = b"release build";
+
+    fn b64(bytes: &[u8]) -> String {
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    }
+
+    fn signing() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32])
+    }
+
+    fn release_key() -> ReleaseKey {
+        ReleaseKey::parse(&b64(signing().verifying_key().as_bytes())).unwrap()
+    }
+
+    fn digest(bytes: &[u8]) -> [u8; 32] {
+        Sha256::digest(bytes).into()
+    }
+
+    /// Base64 signature of `bytes` by the release key
+    fn signature(bytes: &[u8]) -> String {
+        b64(&signing().sign(&digest(bytes)).to_bytes())
+    }
+
+    /// `BUILD` signed by the release key, in its own directory
+    fn signed_artifact() -> (tempfile::TempDir, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let bin = dir.path().join("nearx-native-host");
+        std::fs::write(&bin, BUILD).unwrap();
+        std::fs::write(signature_path(&bin), signature(BUILD)).unwrap();
+        (dir, bin)
+    }
+
+    #[test]
+    fn release_key_parses_base64_with_or_without_prefix() {
+        let raw = b64(signing().verifying_key().as_bytes());
+        let key = ReleaseKey::parse(&format!("ed25519:{raw}")).unwrap();
+        assert_eq!(key.fingerprint(), release_key().fingerprint());
+        assert_eq!(key.fingerprint().len(), 16);
+        assert!(ReleaseKey::parse("c2hvcnQ=").is_err());
+        assert!(ReleaseKey::parse("not base64!").is_err());
+    }
+
+    #[test]
+    fn matching_signature_is_verified_and_may_run() {
+        let v = verify_digest(
+            &digest(BUILD),
+            Some(&signature(BUILD)),
+            Some(&release_key()),
+        );
+        assert!(matches!(v, Verification::Verified { .. }));
+        assert!(v.allows_run(false));
+    }
+
+    #[test]
+    fn signature_of_other_bytes_is_tampered_and_never_runs() {
+        let v = verify_digest(
+            &digest(b"release build + payload"),
+            Some(&signature(BUILD)),
+            Some(&release_key()),
+        );
+        assert!(matches!(v, Verification::Tampered { .. }));
+        assert!(!v.allows_run(true));
+    }
+
+    #[test]
+    fn unsigned_or_keyless_runs_only_when_unverified_is_allowed() {
+        let unsigned = verify_digest(&digest(BUILD), None, Some(&release_key()));
+        assert!(matches!(unsigned, Verification::Unsigned { .. }));
+        let no_key = verify_digest(&digest(BUILD), Some(&signature(BUILD)), None);
+        assert!(matches!(no_key, Verification::NoKey { .. }));
+        for v in [unsigned, no_key] {
+            assert!(v.allows_run(true) && !v.allows_run(false));
+        }
+    }
+
+    #[test]
+    fn malformed_signature_is_an_error() {
+        let v = verify_digest(&digest(BUILD), Some("not base64!"), Some(&release_key()));
+        assert!(matches!(v, Verification::Error { .. }));
+        assert!(!v.allows_run(true));
+    }
+
+    #[test]
+    fn verify_file_reads_the_detached_signature() {
+        let (_dir, bin) = signed_artifact();
+        assert!(matches!(
+            verify_file(&bin, Some(&release_key())),
+            Verification::Verified { .. }
+        ));
+        std::fs::remove_file(signature_path(&bin)).unwrap();
+        assert!(matches!(
+            verify_file(&bin, Some(&release_key())),
+            Verification::Unsigned { .. }
+        ));
+    }
+
+    #[test]
+    fn staged_copy_holds_the_checked_bytes_after_a_swap() {
+        let (dir, bin) = signed_artifact();
+        let artifact = Artifact::load(&bin, Some(&release_key()));
+        std::fs::write(&bin, b"release build + payload").unwrap();
+
+        let staged = artifact
+            .stage(&dir.path().join("run"), "nearx-native-host")
+            .unwrap();
+        assert_eq!(std::fs::read(&staged).unwrap(), BUILD);
+        assert!(matches!(
+            artifact.verification(),
+            Verification::Verified { .. }
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn staged_copy_and_its_directory_are_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = |p: &Path| std::fs::metadata(p).unwrap().permissions().mode() & 0o777;
+
+        let (dir, bin) = signed_artifact();
+        let run = dir.path().join("run");
+        let staged = Artifact::load(&bin, Some(&release_key()))
+            .stage(&run, "nearx-native-host")
+            .unwrap();
+        assert_eq!((mode(&run), mode(&staged)), (0o700, 0o700));
+    }
+
+    #[test]
+    fn restaging_replaces_the_previous_copy() {
+        let (dir, bin) = signed_artifact();
+        let run = dir.path().join("run");
+        std::fs::create_dir_all(&run).unwrap();
+        std::fs::write(run.join("nearx-native-host"), b"old copy").unwrap();
+
+        let staged = Artifact::load(&bin, Some(&release_key()))
+            .stage(&run, "nearx-native-host")
+            .unwrap();
+        assert_eq!(std::fs::read(staged).unwrap(), BUILD);
+    }
+
+    #[test]
+    fn unreadable_artifact_is_never_staged() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = Artifact::load(&dir.path().join("missing"), Some(&release_key()));
+        assert!(matches!(missing.verification(), Verification::Error { .. }));
+        assert!(missing.stage(&dir.path().join("run"), "missing").is_err());
+    }
+}
@@ -10,12 +10,19 @@
 //! - `nearx://v1/account/<id>` - Focus transactions pane, filter to account
 //! - `nearx://v1/home` - Clear filter, return to auto-follow
 //!
+//! Any route takes a `?filter=<query>` whose terms are added to the route's
+//! own filter (`nearx://v1/account/alice.near?filter=method:ft_transfer`).
+//! The native binary's `--route` flag feeds the same parser, with the `v1/`
+//! implied: `--route "tx/<hash>"`.
+//!
 //! ## Robust Parsing
 //!
-//! The parser handles various URL formats robustly:
-//! - Case-insensitive scheme: `NEARX://`, `nearx://`, `NEARx://`
+//! Links are canonicalized by `crate::deeplink` first (the same code the
+//! Tauri shell and native host use), so every entry point agrees:
+//! - Case-insensitive scheme and aliases: `NEARX://`, `web+nearx:`, `near://`
 //! - Single-slash variants: `nearx:/v1/...`
-//! - Multiple slashes: `nearx:////v1/...`
+//! - Multiple slashes: `nearx:////v1//tx/...`
+//! - Percent-encoding: `nearx://v1/account/alice%2Enear`
 //! - Query and fragment stripping: `nearx://v1/tx/ABC?utm=1#frag`
 //!
 //! ## Web Hash Formats
@@ -38,40 +45,7 @@
 //! }
 //! ```
 
-/// Strip query and fragment from URL path
-#[inline]
-fn strip_query_frag(s: &str) -> &str {
-    let bytes = s.as_bytes();
-    for (i, &b) in bytes.iter().enumerate() {
-        if b == b'?' || b == b'#' {
-            return &s[..i];
-        }
-    }
-    s
-}
-
-/// Extract path after nearx:// scheme (case-insensitive, handles variants)
-#[inline]
-fn after_nearx_scheme(raw: &str) -> Option<&str> {
-    // Accept nearx://, NEARX://, nearx:/, nearx:////...
-    let s = raw.trim();
-    if let Some(pos) = s.find("://") {
-        if s[..pos].eq_ignore_ascii_case("nearx") {
-            let mut rest = &s[pos + 3..];
-            while rest.starts_with('/') {
-                rest = &rest[1..];
-            }
-            return Some(rest);
-        }
-    } else if let Some(rest) = s.strip_prefix("nearx:") {
-        let mut r = rest;
-        while r.starts_with('/') {
-            r = &r[1..];
-        }
-        return Some(r);
-    }
-    None
-}
+use crate::deeplink;
 
 /// V1 route variants
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -86,6 +60,19 @@ pub enum RouteV1 {
     Home,
 }
 
+impl RouteV1 {
+    /// Canonical `nearx://v1/...` URL (parses back to the same route)
+    pub fn url(&self) -> String {
+        use urlencoding::encode;
+        match self {
+            RouteV1::Tx { hash } => format!("nearx://v1/tx/{}", encode(hash)),
+            RouteV1::Block { height } => format!("nearx://v1/block/{height}"),
+            RouteV1::Account { id } => format!("nearx://v1/account/{}", encode(id)),
+            RouteV1::Home => "nearx://v1/home".to_string(),
+        }
+    }
+}
+
 /// Versioned route container
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Route {
@@ -93,6 +80,13 @@ pub enum Route {
     V1(RouteV1),
 }
 
+/// A route plus the extra filter terms from its `?filter=` query
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RouteLink {
+    pub route: Route,
+    pub filter: Option<String>,
+}
+
 /// Parse a route from various URL formats
 ///
 /// Accepts:
@@ -106,84 +100,76 @@ pub enum Route {
 ///
 /// Returns `None` for invalid URLs or unsupported versions.
 pub fn parse(raw: &str) -> Option<Route> {
-    if raw.is_empty() {
-        return Some(Route::V1(RouteV1::Home));
-    }
+    parse_link(raw).map(|link| link.route)
+}
 
+/// [`parse`], keeping the `?filter=` query
+pub fn parse_link(raw: &str) -> Option<RouteLink> {
     let s = raw.trim();
+    if s.is_empty() {
+        return Some(RouteLink {
+            route: Route::V1(RouteV1::Home),
+            filter: None,
+        });
+    }
 
-    // Extract path component from various formats
-    let path = if let Some(rest) = after_nearx_scheme(s) {
-        // Robust scheme handling (case-insensitive, slash variants)
-        rest
-    } else if s.starts_with("#/deeplink/") {
-        // Encoded deep link: #/deeplink/<encodeURIComponent(nearx://...)>
-        // This format is only used in WASM (Tauri->Web bridge)
-        #[cfg(target_arch = "wasm32")]
-        {
-            if let Some(encoded) = s.strip_prefix("#/deeplink/") {
-                // Decode using web APIs - handle Result and convert JsString to String
-                match js_sys::decode_uri_component(encoded) {
-                    Ok(js_string) => {
-                        let decoded = String::from(js_string);
-                        // Recursively parse the decoded URL
-                        return parse(&decoded);
-                    }
-                    Err(_) => return None,
-                }
-            }
-        }
-        // Non-WASM: not supported (native TUI doesn't use hash routing)
-        return None;
-    } else if let Some(rest) = s.strip_prefix("#/") {
-        // Direct hash: #/v1/...
-        rest
-    } else if let Some(rest) = s.strip_prefix("/") {
-        // Path only: /v1/...
-        rest
-    } else if s.starts_with("v1/") {
-        // Already normalized
-        s
-    } else {
-        s
-    };
-
-    // Strip query parameters and fragments
-    let path = strip_query_frag(path);
+    // Encoded deep link: #/deeplink/<encodeURIComponent(nearx://...)> (Tauri->Web bridge)
+    if let Some(encoded) = s.strip_prefix("#/deeplink/") {
+        return parse_link(&urlencoding::decode(encoded).ok()?);
+    }
 
-    // Parse version and route: "v1/tx/ABC123" or "v1/block/12345" etc.
-    let mut segments = path.split('/').filter(|s| !s.is_empty());
+    // Hash (#/v1/...), path (/v1/...) and bare (v1/...) forms read as nearx://v1/...
+    let link = if deeplink::has_scheme(s) {
+        deeplink::parse_event(s)
+    } else {
+        let rest = s.strip_prefix("#/").unwrap_or(s);
+        deeplink::parse_event(&format!("nearx://{rest}"))
+    }?;
 
-    let version = segments.next()?.to_ascii_lowercase();
-    if version != "v1" {
+    if link.host != "v1" {
         return None; // Unsupported version
     }
 
-    let page = segments.next().unwrap_or("").to_ascii_lowercase();
-    match page.as_str() {
-        "" | "home" => Some(Route::V1(RouteV1::Home)),
-        "tx" => {
-            let hash = segments.next()?.to_string();
-            if hash.is_empty() {
-                None
-            } else {
-                Some(Route::V1(RouteV1::Tx { hash }))
-            }
-        }
-        "block" => {
-            let height_str = segments.next()?;
-            let height = height_str.parse::<u64>().ok()?;
-            Some(Route::V1(RouteV1::Block { height }))
-        }
-        "account" => {
-            let id = segments.next()?.to_string();
-            if id.is_empty() {
-                None
-            } else {
-                Some(Route::V1(RouteV1::Account { id }))
-            }
-        }
-        _ => None, // Unknown route
+    // Segments are decoded and case-folded already ("tx", "account", lowercase ids)
+    let mut segments = link.path.into_iter();
+    let page = segments.next().unwrap_or_default();
+    let route = match page.as_str() {
+        "" | "home" => RouteV1::Home,
+        "tx" => RouteV1::Tx {
+            hash: segments.next()?,
+        },
+        "block" => RouteV1::Block {
+            height: segments.next()?.parse::<u64>().ok()?,
+        },
+        "account" => RouteV1::Account {
+            id: segments.next()?,
+        },
+        _ => return None, // Unknown route
+    };
+    let filter = link
+        .query
+        .get("filter")
+        .map(|f| f.trim().to_string())
+        .filter(|f| !f.is_empty());
+    Some(RouteLink {
+        route: Route::V1(route),
+        filter,
+    })
+}
+
+/// `--route` argument: a deep link in any accepted form, or a bare route with
+/// `v1/` implied (`tx/<hash>`, `account/alice.near?filter=method:ft_transfer`)
+pub fn parse_cli(raw: &str) -> Option<RouteLink> {
+    let s = raw.trim();
+    if deeplink::has_scheme(s) || s.starts_with('#') {
+        return parse_link(s);
+    }
+    let bare = s.trim_start_matches('/');
+    let first = bare.split(['/', '?']).next().unwrap_or_default();
+    if first.eq_ignore_ascii_case("v1") {
+        parse_link(bare)
+    } else {
+        parse_link(&format!("v1/{bare}"))
     }
 }
 
@@ -323,4 +309,70 @@ mod tests {
             _ => panic!("Expected Block route"),
         }
     }
+
+    #[test]
+    fn test_parse_percent_encoded_and_bridge() {
+        let r = parse("nearx://v1/account/Alice%2Enear").unwrap();
+        assert_eq!(r, Route::V1(RouteV1::Account { id: "alice.near".to_string() }));
+
+        let r = parse("#/deeplink/nearx%3A%2F%2Fv1%2Fblock%2F7").unwrap();
+        assert_eq!(r, Route::V1(RouteV1::Block { height: 7 }));
+
+        // Legacy scheme alias resolves like the Tauri shell does
+        assert_eq!(parse("web+near:v1/TX/Xy"), parse("nearx://v1/tx/Xy"));
+    }
+
+    #[test]
+    fn cli_routes_imply_v1_and_keep_the_filter() {
+        let link = parse_cli("account/Alice.near?filter=method%3Aft_transfer+signer:bob.near").unwrap();
+        assert_eq!(
+            link.route,
+            Route::V1(RouteV1::Account {
+                id: "alice.near".to_string()
+            })
+        );
+        assert_eq!(
+            link.filter.as_deref(),
+            Some("method:ft_transfer signer:bob.near")
+        );
+
+        let tx = Route::V1(RouteV1::Tx {
+            hash: "ABC123".to_string(),
+        });
+        for raw in ["tx/ABC123", "/v1/tx/ABC123", "nearx://v1/tx/ABC123?filter=", "#/v1/tx/ABC123"] {
+            let link = parse_cli(raw).unwrap();
+            assert_eq!((link.route, link.filter), (tx.clone(), None), "{raw}");
+        }
+        assert_eq!(parse_cli("home").unwrap().route, Route::V1(RouteV1::Home));
+        assert_eq!(parse_cli("block/notanumber"), None);
+        assert_eq!(parse_cli("profile/alice.near"), None);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn routes_round_trip_through_their_url(
+            hash in "[1-9A-HJ-NP-Za-km-z]{1,44}",
+            height in proptest::num::u64::ANY,
+            id in "[a-z0-9_.-]{1,64}",
+        ) {
+            for route in [
+                RouteV1::Tx { hash: hash.clone() },
+                RouteV1::Block { height },
+                RouteV1::Account { id: id.clone() },
+                RouteV1::Home,
+            ] {
+                let url = route.url();
+                proptest::prop_assert_eq!(deeplink::canonicalize(&url), Some(url.clone()));
+                proptest::prop_assert_eq!(parse(&url), Some(Route::V1(route)));
+            }
+        }
+
+        #[test]
+        fn canonical_links_parse_to_the_same_route(raw in "(nearx|NEARX|near):[/]{0,3}[vV]1[/a-zA-Z0-9.%?#=]{0,24}") {
+            let canonical = deeplink::canonicalize(&raw);
+            if let Some(canonical) = canonical {
+                proptest::prop_assert_eq!(parse(&canonical), parse(&raw));
+            }
+        }
+    }
 }
@@ -18,7 +18,7 @@ use gloo_timers::future::sleep;
 
 static HTTP: OnceLock<reqwest::Client> = OnceLock::new();
 
-fn http_client() -> &'static reqwest::Client {
+pub(crate) fn http_client() -> &'static reqwest::Client {
     HTTP.get_or_init(|| {
         #[cfg(not(target_arch = "wasm32"))]
         {
@@ -69,7 +69,11 @@ pub async fn rpc_post(
                     .get("message")
                     .and_then(|m| m.as_str())
                     .unwrap_or("rpc error");
-                return Err(anyhow!("rpc {code} {msg}"));
+                // Structured cause (e.g. UNKNOWN_BLOCK for a skipped height) when the node sends one
+                return Err(match err["cause"]["name"].as_str() {
+                    Some(cause) => anyhow!("rpc {code} {msg} ({cause})"),
+                    None => anyhow!("rpc {code} {msg}"),
+                });
             }
             if let Some(r) = v.get("result") {
                 return Ok(r.clone());
@@ -138,6 +142,51 @@ pub async fn get_chunk(url: &str, hash: &str, t: u64, auth_token: Option<&str>)
     .await
 }
 
+/// Fetch a tx with all receipt outcomes and receipt bodies (`EXPERIMENTAL_tx_status`)
+pub async fn get_tx_status(
+    url: &str,
+    tx_hash: &str,
+    sender_account_id: &str,
+    t: u64,
+    auth_token: Option<&str>,
+) -> Result<Value> {
+    get_tx_status_until(url, tx_hash, sender_account_id, "FINAL", t, auth_token).await
+}
+
+/// `EXPERIMENTAL_tx_status` returning once the tx reaches `wait_until`;
+/// `INCLUDED_FINAL` answers with the outcomes known so far instead of
+/// blocking on receipts that haven't run (e.g. parked on a yield)
+pub async fn get_tx_status_until(
+    url: &str,
+    tx_hash: &str,
+    sender_account_id: &str,
+    wait_until: &str,
+    t: u64,
+    auth_token: Option<&str>,
+) -> Result<Value> {
+    rpc_post(
+        url,
+        &json!({"jsonrpc":"2.0","id":"nearx","method":"EXPERIMENTAL_tx_status","params":{
+            "tx_hash": tx_hash,
+            "sender_account_id": sender_account_id,
+            "wait_until": wait_until
+        }}),
+        t,
+        auth_token,
+    )
+    .await
+}
+
+pub async fn get_protocol_config(url: &str, t: u64, auth_token: Option<&str>) -> Result<Value> {
+    rpc_post(
+        url,
+        &json!({"jsonrpc":"2.0","id":"nearx","method":"EXPERIMENTAL_protocol_config","params":{"finality":"final"}}),
+        t,
+        auth_token,
+    )
+    .await
+}
+
 /// Extract transactions from a chunk JSON response
 fn extract_transactions_from_chunk(chunk: &Value, txs: &mut Vec<TxLite>) {
     if let Some(arr) = chunk["transactions"].as_array() {
@@ -230,6 +279,10 @@ pub async fn fetch_block_with_txs(
     let prev_hash = b["header"]["prev_hash"]
         .as_str()
         .map(|s| s.to_string());
+    let gas_price = b["header"]["gas_price"]
+        .as_str()
+        .and_then(|s| s.parse::<u128>().ok());
+    let gas_used = chunks_gas_used(&b);
 
     Ok(BlockRow {
         height,
@@ -240,10 +293,26 @@ pub async fn fetch_block_with_txs(
         tx_count: txs.len(),
         when,
         transactions: txs,
+        gas_price,
+        gas_used,
     })
 }
 
-fn chrono_fmt(nano: i64) -> String {
+/// Gas used by the chunks included in this `block` response (a missing chunk
+/// repeats the previous header, counted where it was included)
+pub fn chunks_gas_used(block: &Value) -> Option<u64> {
+    let height = block["header"]["height"].as_u64()?;
+    let chunks = block["chunks"].as_array()?;
+    Some(
+        chunks
+            .iter()
+            .filter(|c| c["height_included"].as_u64() == Some(height))
+            .filter_map(|c| c["gas_used"].as_u64())
+            .sum(),
+    )
+}
+
+pub(crate) fn chrono_fmt(nano: i64) -> String {
     use chrono::{Local, TimeZone, Timelike, Utc};
     let secs = nano / 1_000_000_000;
     let nsec = (nano % 1_000_000_000) as u32;
@@ -305,7 +374,7 @@ fn parse_transaction_detailed(tx_json: &Value) -> Option<TxDetailed> {
 }
 
 /// Parse actions array from transaction JSON
-fn parse_actions(actions_json: &[Value]) -> Vec<ActionSummary> {
+pub(crate) fn parse_actions(actions_json: &[Value]) -> Vec<ActionSummary> {
     actions_json
         .iter()
         .filter_map(|action| {
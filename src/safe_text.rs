@@ -0,0 +1,137 @@
+//! Resilient text for the Details pane
+//!
+//! Whatever a node, a contract or an old History row hands us, the pane shows
+//! something readable instead of going blank or garbling the terminal:
+//! - control characters (other than newline and tab) are shown escaped
+//!   (`\u001b`), so stray escape sequences can't move the cursor or recolor
+//! - stored data that isn't JSON is shown under a warning banner, as escaped
+//!   text, or as a hex preview when it is binary (invalid UTF-8 sequences were
+//!   replaced with U+FFFD when the row was read)
+
+use std::borrow::Cow;
+
+use crate::json_pretty::{pretty, JsonFormat};
+
+/// Bytes shown in a hex preview (the banner gives the full size)
+pub const HEX_PREVIEW_BYTES: usize = 4096;
+
+/// Share of replacement / control characters above which text is shown as hex
+const BINARY_RATIO: f32 = 0.10;
+
+/// Control characters other than newline and tab
+fn is_unsafe(ch: char) -> bool {
+    ch.is_control() && ch != '\n' && ch != '\t'
+}
+
+/// Escape control characters (`\r`, ESC, DEL, C1, ...) as `\uXXXX`
+pub fn escape_controls(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(is_unsafe) {
+        return Cow::Borrowed(text);
+    }
+    let mut out = String::with_capacity(text.len() + 16);
+    for ch in text.chars() {
+        if is_unsafe(ch) {
+            out.push_str(&format!("\\u{:04x}", ch as u32));
+        } else {
+            out.push(ch);
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// Decode stored bytes, replacing invalid UTF-8 sequences with U+FFFD
+pub fn decode_lossy(bytes: Vec<u8>) -> String {
+    String::from_utf8(bytes).unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned())
+}
+
+/// Whether text is mostly undecodable or control characters
+fn looks_binary(text: &str) -> bool {
+    let total = text.chars().count().max(1);
+    let bad = text
+        .chars()
+        .filter(|&ch| ch == char::REPLACEMENT_CHARACTER || is_unsafe(ch))
+        .count();
+    bad as f32 / total as f32 > BINARY_RATIO
+}
+
+/// `00000000  7b 22 61 22 3a 20 31 7d  |{"a": 1}|`, 16 bytes per line
+pub fn hex_preview(bytes: &[u8], max_bytes: usize) -> String {
+    let shown = &bytes[..bytes.len().min(max_bytes)];
+    let mut out = String::new();
+    for (i, row) in shown.chunks(16).enumerate() {
+        let hex: Vec<String> = row.iter().map(|b| format!("{b:02x}")).collect();
+        let ascii: String = row
+            .iter()
+            .map(|&b| {
+                if (0x20..=0x7e).contains(&b) {
+                    b as char
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        out.push_str(&format!(
+            "{:08x}  {:<47}  |{ascii}|\n",
+            i * 16,
+            hex.join(" ")
+        ));
+    }
+    if bytes.len() > shown.len() {
+        out.push_str(&format!("… {} more bytes\n", bytes.len() - shown.len()));
+    }
+    out
+}
+
+/// Details text for a stored tx: pretty JSON, or the raw data under a warning banner
+pub fn raw_details(raw: &str, fmt: JsonFormat) -> String {
+    let err = match serde_json::from_str::<serde_json::Value>(raw) {
+        Ok(v) => return pretty(&v, fmt),
+        Err(e) => e,
+    };
+    if raw.trim().is_empty() {
+        return "⚠ Stored transaction is empty".to_string();
+    }
+    if looks_binary(raw) {
+        return format!(
+            "⚠ Stored transaction is not JSON or text ({} bytes): hex preview\n\n{}",
+            raw.len(),
+            hex_preview(raw.as_bytes(), HEX_PREVIEW_BYTES)
+        );
+    }
+    format!(
+        "⚠ Stored transaction is not valid JSON ({err}): raw text\n\n{}",
+        escape_controls(raw)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn falls_back_to_escaped_text_or_hex_instead_of_a_blank_pane() {
+        let fmt = JsonFormat::default();
+        assert_eq!(raw_details(r#"{"a":1}"#, fmt), "{\n  \"a\": 1\n}");
+
+        assert!(matches!(escape_controls("plain\n\ttext"), Cow::Borrowed(_)));
+        assert_eq!(
+            escape_controls("red \u{1b}[31m\r\u{7f}"),
+            "red \\u001b[31m\\u000d\\u007f"
+        );
+
+        let text = raw_details("{\"a\": \"cut off \u{1b}[2J", fmt);
+        assert!(text.starts_with("⚠ Stored transaction is not valid JSON ("));
+        assert!(text.ends_with("\"cut off \\u001b[2J"));
+
+        let stored = decode_lossy(vec![0xff, 0xfe, 0x00, 0x01, b'{', 0x80, 0x81, 0x82]);
+        let text = raw_details(&stored, fmt);
+        assert!(text.starts_with("⚠ Stored transaction is not JSON or text ("));
+        assert!(text.contains("00000000  ef bf bd ef bf bd 00 01"));
+        assert!(!text.chars().any(is_unsafe));
+
+        let long = hex_preview(&[b'a'; 40], 32);
+        assert_eq!(long.lines().count(), 3);
+        assert!(long.starts_with("00000000  61 61"));
+        assert!(long.ends_with("… 8 more bytes\n"));
+    }
+}
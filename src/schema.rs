@@ -0,0 +1,200 @@
+//! UI contract schema export (`nearx --emit-schema <DIR>`)
+//!
+//! Generates JSON Schema (via schemars) and TypeScript definitions for the
+//! stable UI contract (`UiSnapshot` / `UiAction`) so DOM/Tauri frontends and
+//! extension authors can type-check against the Rust types.
+
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+use std::fmt::Write as _;
+use std::path::Path;
+
+use crate::ui_snapshot::{UiAction, UiSnapshot};
+
+/// File names written by [`write_schema_files`]
+pub const SNAPSHOT_SCHEMA_FILE: &str = "ui_snapshot.schema.json";
+pub const ACTION_SCHEMA_FILE: &str = "ui_action.schema.json";
+pub const TYPESCRIPT_FILE: &str = "nearx-ui.d.ts";
+
+/// JSON Schema for `UiSnapshot` (Rust → UI)
+pub fn snapshot_schema() -> Value {
+    schemars::schema_for!(UiSnapshot).to_value()
+}
+
+/// JSON Schema for `UiAction` (UI → Rust)
+pub fn action_schema() -> Value {
+    schemars::schema_for!(UiAction).to_value()
+}
+
+/// TypeScript definitions for the whole UI contract
+pub fn typescript_definitions() -> String {
+    let mut out = String::new();
+    out.push_str("// Generated by `nearx --emit-schema`. Do not edit by hand.\n");
+    out.push_str("// Source of truth: src/ui_snapshot.rs\n");
+
+    let mut emitted: Vec<String> = Vec::new();
+    for (name, schema) in [("UiSnapshot", snapshot_schema()), ("UiAction", action_schema())] {
+        emit_decl(&mut out, name, &schema);
+        emitted.push(name.to_string());
+
+        if let Some(defs) = schema.get("$defs").and_then(|d| d.as_object()) {
+            for (def_name, def) in defs {
+                if !emitted.contains(def_name) {
+                    emit_decl(&mut out, def_name, def);
+                    emitted.push(def_name.clone());
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Write both JSON Schemas and the TypeScript definitions into `dir`
+pub fn write_schema_files(dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(dir).with_context(|| format!("create {}", dir.display()))?;
+
+    let write = |file: &str, contents: String| -> Result<()> {
+        let path = dir.join(file);
+        std::fs::write(&path, contents).with_context(|| format!("write {}", path.display()))
+    };
+    write(
+        SNAPSHOT_SCHEMA_FILE,
+        serde_json::to_string_pretty(&snapshot_schema())?,
+    )?;
+    write(ACTION_SCHEMA_FILE, serde_json::to_string_pretty(&action_schema())?)?;
+    write(TYPESCRIPT_FILE, typescript_definitions())?;
+    Ok(())
+}
+
+// ---------- JSON Schema → TypeScript ----------
+
+fn emit_decl(out: &mut String, name: &str, schema: &Value) {
+    out.push('\n');
+    emit_doc(out, schema, "");
+    match schema.get("properties").and_then(|p| p.as_object()) {
+        Some(props) if schema.get("oneOf").is_none() => {
+            let _ = writeln!(out, "export interface {name} {{");
+            emit_props(out, props, required(schema), "  ");
+            out.push_str("}\n");
+        }
+        _ => {
+            let _ = writeln!(out, "export type {name} = {};", ts_type(schema, ""));
+        }
+    }
+}
+
+fn emit_props(out: &mut String, props: &Map<String, Value>, required: Vec<&str>, indent: &str) {
+    for (key, prop) in props {
+        emit_doc(out, prop, indent);
+        let opt = if required.contains(&key.as_str()) { "" } else { "?" };
+        let _ = writeln!(out, "{indent}{key}{opt}: {};", ts_type(prop, indent));
+    }
+}
+
+fn emit_doc(out: &mut String, schema: &Value, indent: &str) {
+    if let Some(desc) = schema.get("description").and_then(|d| d.as_str()) {
+        let desc = desc.split_whitespace().collect::<Vec<_>>().join(" ");
+        let _ = writeln!(out, "{indent}/** {desc} */");
+    }
+}
+
+fn required(schema: &Value) -> Vec<&str> {
+    schema
+        .get("required")
+        .and_then(|r| r.as_array())
+        .map(|r| r.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default()
+}
+
+fn ts_type(schema: &Value, indent: &str) -> String {
+    if let Some(r) = schema.get("$ref").and_then(|r| r.as_str()) {
+        return r.rsplit('/').next().unwrap_or("unknown").to_string();
+    }
+    if let Some(c) = schema.get("const") {
+        return c.to_string();
+    }
+    if let Some(variants) = schema.get("enum").and_then(|e| e.as_array()) {
+        return variants
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+            .join(" | ");
+    }
+    for key in ["oneOf", "anyOf"] {
+        if let Some(variants) = schema.get(key).and_then(|v| v.as_array()) {
+            return variants
+                .iter()
+                .map(|v| ts_type(v, indent))
+                .collect::<Vec<_>>()
+                .join(" | ");
+        }
+    }
+
+    match schema.get("type") {
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(|t| t.as_str())
+            .map(|t| primitive(t, schema, indent))
+            .collect::<Vec<_>>()
+            .join(" | "),
+        Some(Value::String(t)) => primitive(t, schema, indent),
+        _ => "unknown".to_string(),
+    }
+}
+
+fn primitive(t: &str, schema: &Value, indent: &str) -> String {
+    match t {
+        "string" => "string".to_string(),
+        "integer" | "number" => "number".to_string(),
+        "boolean" => "boolean".to_string(),
+        "null" => "null".to_string(),
+        "array" => {
+            let item = schema
+                .get("items")
+                .map(|i| ts_type(i, indent))
+                .unwrap_or_else(|| "unknown".to_string());
+            if item.contains(' ') {
+                format!("({item})[]")
+            } else {
+                format!("{item}[]")
+            }
+        }
+        "object" => match schema.get("properties").and_then(|p| p.as_object()) {
+            Some(props) => {
+                let inner = format!("{indent}  ");
+                let mut out = String::from("{\n");
+                emit_props(&mut out, props, required(schema), &inner);
+                out.push_str(indent);
+                out.push('}');
+                out
+            }
+            None => "Record<string, unknown>".to_string(),
+        },
+        _ => "unknown".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn action_schema_lists_variants() {
+        let text = action_schema().to_string();
+        for variant in ["SetFilter", "FocusPane", "Key", "CopyFocusedJson"] {
+            assert!(text.contains(variant), "missing {variant}");
+        }
+    }
+
+    #[test]
+    fn typescript_covers_contract() {
+        let ts = typescript_definitions();
+        assert!(ts.contains("export interface UiSnapshot {"));
+        assert!(ts.contains("export interface UiBlockRow {"));
+        assert!(ts.contains("blocks: UiBlockRow[];"));
+        assert!(ts.contains("selected_block_height?: number | null;")
+            || ts.contains("selected_block_height: number | null;"));
+        assert!(ts.contains("type: \"SetFilter\";"));
+        assert!(ts.contains("export type UiBlockSource = \"forward\" | \"backfill_pending\" | \"backfill_loading\";"));
+    }
+}
@@ -0,0 +1,410 @@
+//! Incremental `UiSnapshot` building
+//!
+//! Building the Blocks and Txs rows walks the whole (filtered) buffer and
+//! clones every hash, which on a large ring can take a good part of a frame.
+//! `App` bumps `blocks_rev` whenever those rows could change (buffer, cache,
+//! backfill slots, filter results), so a snapshot builder that owns a
+//! `SnapshotCache` rebuilds a section only when its key moved and otherwise
+//! hands out the previous rows again. Rows are shared (`Arc<[_]>`): a reuse
+//! costs a reference count, and a selection change copies the rows only while
+//! an earlier snapshot still holds them.
+//!
+//! Blocks rebuilds are also held to a frame budget: when the average rebuild
+//! costs more than `rebuild_budget_us`, a new `rev` is picked up at most once
+//! every `rebuild_us / budget` snapshots and the rows in between are the
+//! previous ones (a frame or two behind while blocks stream in). Moving to or
+//! from a cached block always rebuilds.
+//!
+//! Each section's rebuild cost is tracked as a moving average; a reuse counts
+//! that average as saved time. `P` shows the numbers in the profiler overlay.
+
+use std::sync::Arc;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+use crate::ui_snapshot::{UiBlockRow, UiTxRow};
+
+/// Weight of the newest sample in the rebuild-cost averages
+const EMA_ALPHA: f64 = 0.2;
+
+/// Default average Blocks rebuild time allowed per snapshot (µs): a quarter
+/// of a 60 fps frame
+pub const DEFAULT_REBUILD_BUDGET_US: f64 = 4_000.0;
+
+/// What the Blocks rows depend on besides the selection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlocksKey {
+    pub rev: u64,
+    /// Selected block when it's only in the cache (injected into the list)
+    pub viewing_cached: Option<u64>,
+}
+
+/// What the Txs rows depend on besides the selected row
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxsKey {
+    pub rev: u64,
+    pub block: Option<u64>,
+}
+
+/// Cost of one section: rebuilds, reuses and the average rebuild time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SectionStats {
+    pub rebuilt: u64,
+    pub reused: u64,
+    /// Moving average of a rebuild (µs)
+    pub rebuild_us: f64,
+    /// Estimated time saved by reuses (µs)
+    pub saved_us: f64,
+    /// Reuses of outdated rows to stay within the frame budget
+    pub deferred: u64,
+}
+
+impl SectionStats {
+    fn record_rebuild(&mut self, us: f64) {
+        self.rebuild_us = if self.rebuilt == 0 {
+            us
+        } else {
+            self.rebuild_us + EMA_ALPHA * (us - self.rebuild_us)
+        };
+        self.rebuilt += 1;
+    }
+
+    fn record_reuse(&mut self, us: f64) {
+        self.reused += 1;
+        self.saved_us += (self.rebuild_us - us).max(0.0);
+    }
+
+    /// Share of builds served from the cache (0-100)
+    pub fn reuse_pct(&self) -> f64 {
+        let total = self.rebuilt + self.reused;
+        if total == 0 {
+            0.0
+        } else {
+            self.reused as f64 * 100.0 / total as f64
+        }
+    }
+}
+
+/// Snapshot build timings
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SnapshotStats {
+    pub builds: u64,
+    /// Last whole snapshot (µs)
+    pub last_us: f64,
+    /// Moving average of a whole snapshot (µs)
+    pub avg_us: f64,
+    pub blocks: SectionStats,
+    pub txs: SectionStats,
+}
+
+impl SnapshotStats {
+    pub fn record_build(&mut self, us: f64) {
+        self.avg_us = if self.builds == 0 {
+            us
+        } else {
+            self.avg_us + EMA_ALPHA * (us - self.avg_us)
+        };
+        self.last_us = us;
+        self.builds += 1;
+    }
+
+    /// Estimated time saved per snapshot (µs)
+    pub fn saved_per_build_us(&self) -> f64 {
+        if self.builds == 0 {
+            0.0
+        } else {
+            (self.blocks.saved_us + self.txs.saved_us) / self.builds as f64
+        }
+    }
+
+    /// Estimated share of the full-rebuild cost saved (0-100)
+    pub fn saved_pct(&self) -> f64 {
+        let saved = self.saved_per_build_us();
+        let full = self.avg_us + saved;
+        if full <= 0.0 {
+            0.0
+        } else {
+            saved * 100.0 / full
+        }
+    }
+}
+
+/// Rows kept between snapshots, owned by whoever builds them (web app, mirror)
+#[derive(Debug)]
+pub struct SnapshotCache {
+    blocks: Option<(BlocksKey, Arc<[UiBlockRow]>, usize)>,
+    txs: Option<(TxsKey, Arc<[UiTxRow]>, usize)>,
+    /// Snapshots since the Blocks rows were last rebuilt
+    blocks_age: u32,
+    rebuild_budget_us: f64,
+    pub stats: SnapshotStats,
+}
+
+impl Default for SnapshotCache {
+    fn default() -> Self {
+        Self {
+            blocks: None,
+            txs: None,
+            blocks_age: 0,
+            rebuild_budget_us: DEFAULT_REBUILD_BUDGET_US,
+            stats: SnapshotStats::default(),
+        }
+    }
+}
+
+impl SnapshotCache {
+    /// Average Blocks rebuild time allowed per snapshot (µs; 0 = rebuild as
+    /// soon as the key moves)
+    pub fn set_rebuild_budget_us(&mut self, budget_us: f64) {
+        self.rebuild_budget_us = budget_us.max(0.0);
+    }
+
+    /// Blocks rows (and total) for `key`: reused with the selection re-marked,
+    /// or rebuilt with `build` when the key changed and the budget allows it
+    pub fn blocks(
+        &mut self,
+        key: BlocksKey,
+        selected_height: Option<u64>,
+        build: impl FnOnce() -> (Vec<UiBlockRow>, usize),
+    ) -> (Arc<[UiBlockRow]>, usize) {
+        let start = Instant::now();
+        self.blocks_age = self.blocks_age.saturating_add(1);
+        let defer = self.defer_blocks_rebuild();
+        match self.blocks.as_mut() {
+            Some((k, rows, total))
+                if *k == key || (defer && k.viewing_cached == key.viewing_cached) =>
+            {
+                if *k != key {
+                    self.stats.blocks.deferred += 1;
+                }
+                let sel = selected_block_index(rows, selected_height);
+                mark_selected(rows, sel, |r| r.is_selected, |r, on| r.is_selected = on);
+                let out = (rows.clone(), *total);
+                self.stats.blocks.record_reuse(elapsed_us(start));
+                out
+            }
+            _ => {
+                let (rows, total) = build();
+                let rows: Arc<[UiBlockRow]> = rows.into();
+                self.stats.blocks.record_rebuild(elapsed_us(start));
+                self.blocks = Some((key, rows.clone(), total));
+                self.blocks_age = 0;
+                (rows, total)
+            }
+        }
+    }
+
+    /// Over budget: rebuild only once per `rebuild_us / budget` snapshots
+    fn defer_blocks_rebuild(&self) -> bool {
+        let cost = self.stats.blocks.rebuild_us;
+        self.rebuild_budget_us > 0.0
+            && cost > self.rebuild_budget_us
+            && f64::from(self.blocks_age) * self.rebuild_budget_us < cost
+    }
+
+    /// Txs rows (and total) for `key`, like `blocks` (one block's rows: no budget)
+    pub fn txs(
+        &mut self,
+        key: TxsKey,
+        selected: usize,
+        build: impl FnOnce() -> (Vec<UiTxRow>, usize),
+    ) -> (Arc<[UiTxRow]>, usize) {
+        let start = Instant::now();
+        match self.txs.as_mut().filter(|(k, _, _)| *k == key) {
+            Some((_, rows, total)) => {
+                let sel = rows.iter().position(|r| r.index == selected);
+                mark_selected(rows, sel, |r| r.is_selected, |r, on| r.is_selected = on);
+                let out = (rows.clone(), *total);
+                self.stats.txs.record_reuse(elapsed_us(start));
+                out
+            }
+            None => {
+                let (rows, total) = build();
+                let rows: Arc<[UiTxRow]> = rows.into();
+                self.stats.txs.record_rebuild(elapsed_us(start));
+                self.txs = Some((key, rows.clone(), total));
+                (rows, total)
+            }
+        }
+    }
+}
+
+/// Flag row `sel` as the only selected one; rows are copied only when the
+/// selection actually moved while an earlier snapshot still shares them
+fn mark_selected<T: Clone>(
+    rows: &mut Arc<[T]>,
+    sel: Option<usize>,
+    is_selected: impl Fn(&T) -> bool,
+    set: impl Fn(&mut T, bool),
+) {
+    if rows
+        .iter()
+        .enumerate()
+        .all(|(i, row)| is_selected(row) == (Some(i) == sel))
+    {
+        return;
+    }
+    for (i, row) in Arc::make_mut(rows).iter_mut().enumerate() {
+        set(row, Some(i) == sel);
+    }
+}
+
+/// The forward row at `height`, else the first one (as `App::filtered_blocks` does)
+fn selected_block_index(rows: &[UiBlockRow], height: Option<u64>) -> Option<usize> {
+    use crate::ui_snapshot::UiBlockSource;
+    let forward = |r: &UiBlockRow| matches!(r.source, UiBlockSource::Forward);
+    height
+        .and_then(|h| rows.iter().position(|r| forward(r) && r.height == h))
+        .or_else(|| rows.iter().position(forward))
+}
+
+fn elapsed_us(start: Instant) -> f64 {
+    start.elapsed().as_secs_f64() * 1e6
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::fixtures::block_row;
+    use crate::ui_snapshot::UiBlockSource;
+    use std::cell::Cell;
+
+    #[test]
+    fn reuses_rows_until_the_key_moves() {
+        let mut cache = SnapshotCache::default();
+        let key = BlocksKey {
+            rev: 1,
+            viewing_cached: None,
+        };
+        let builds = Cell::new(0);
+        let build = || {
+            builds.set(builds.get() + 1);
+            (
+                vec![
+                    block_row(0, 10, UiBlockSource::Forward),
+                    block_row(1, 9, UiBlockSource::Forward),
+                    block_row(2, 8, UiBlockSource::BackfillPending),
+                ],
+                2,
+            )
+        };
+
+        let (rows, total) = cache.blocks(key, Some(9), build);
+        assert_eq!((rows.len(), total), (3, 2));
+        // Same key, new selection: no rebuild, selection re-marked
+        let (rows, _) = cache.blocks(key, Some(9), build);
+        assert!(rows[1].is_selected && !rows[0].is_selected);
+        let (rows, _) = cache.blocks(key, Some(8), build); // placeholder: falls back to the first row
+        assert!(rows[0].is_selected && !rows[2].is_selected);
+        assert_eq!(builds.get(), 1);
+
+        cache.blocks(BlocksKey { rev: 2, ..key }, None, build);
+        assert_eq!(builds.get(), 2);
+        assert_eq!(
+            (cache.stats.blocks.rebuilt, cache.stats.blocks.reused),
+            (2, 2)
+        );
+        assert_eq!(cache.stats.blocks.reuse_pct(), 50.0);
+
+        let tx_builds = Cell::new(0);
+        let key = TxsKey {
+            rev: 2,
+            block: Some(10),
+        };
+        let build_txs = || {
+            tx_builds.set(tx_builds.get() + 1);
+            let tx = |index| UiTxRow {
+                index,
+                hash: format!("t{index}"),
+                signer_id: String::new(),
+                receiver_id: String::new(),
+                is_selected: index == 0,
+                bridge: None,
+            };
+            (vec![tx(0), tx(1)], 2)
+        };
+        cache.txs(key, 0, build_txs);
+        let (rows, _) = cache.txs(key, 1, build_txs);
+        assert!(rows[1].is_selected && !rows[0].is_selected);
+        cache.txs(
+            TxsKey {
+                block: Some(9),
+                ..key
+            },
+            0,
+            build_txs,
+        );
+        assert_eq!(tx_builds.get(), 2);
+    }
+
+    #[test]
+    fn reuse_shares_rows_and_copies_only_for_a_held_selection_change() {
+        let mut cache = SnapshotCache::default();
+        let key = BlocksKey {
+            rev: 1,
+            viewing_cached: None,
+        };
+        let build = || {
+            let mut rows = vec![
+                block_row(0, 10, UiBlockSource::Forward),
+                block_row(1, 9, UiBlockSource::Forward),
+            ];
+            rows[0].is_selected = true;
+            (rows, 2)
+        };
+
+        let (first, _) = cache.blocks(key, Some(10), build);
+        let (same, _) = cache.blocks(key, Some(10), build);
+        assert!(Arc::ptr_eq(&first, &same));
+
+        // An earlier snapshot still holds the rows: it keeps its selection
+        let (moved, _) = cache.blocks(key, Some(9), build);
+        assert!(!Arc::ptr_eq(&first, &moved));
+        assert!(first[0].is_selected && moved[1].is_selected);
+
+        // Nobody else holds them: re-marked in place
+        let moved_ptr = Arc::as_ptr(&moved);
+        drop((first, same, moved));
+        let (back, _) = cache.blocks(key, Some(10), build);
+        assert_eq!(Arc::as_ptr(&back), moved_ptr);
+        assert!(back[0].is_selected && !back[1].is_selected);
+    }
+
+    #[test]
+    fn rebuilds_over_budget_are_spread_across_snapshots() {
+        let mut cache = SnapshotCache::default();
+        cache.set_rebuild_budget_us(1_000.0);
+        let builds = Cell::new(0);
+        let build = || {
+            builds.set(builds.get() + 1);
+            (vec![block_row(0, 10, UiBlockSource::Forward)], 1)
+        };
+        let key = |rev| BlocksKey {
+            rev,
+            viewing_cached: None,
+        };
+
+        cache.blocks(key(1), None, build);
+        cache.stats.blocks.rebuild_us = 3_000.0; // pretend a rebuild takes 3 budgets
+        cache.blocks(key(2), None, build);
+        cache.blocks(key(3), None, build);
+        assert_eq!((builds.get(), cache.stats.blocks.deferred), (1, 2));
+        cache.blocks(key(3), None, build);
+        assert_eq!(builds.get(), 2);
+
+        // Opening a cached block changes what is selectable: never deferred
+        cache.stats.blocks.rebuild_us = 3_000.0;
+        cache.blocks(
+            BlocksKey {
+                rev: 3,
+                viewing_cached: Some(5),
+            },
+            Some(5),
+            build,
+        );
+        assert_eq!(builds.get(), 3);
+    }
+}
@@ -0,0 +1,440 @@
+//! GraphQL block source (`SOURCE=graphql`)
+//!
+//! For indexers that expose GraphQL rather than JSON-RPC. Two query templates
+//! drive the poller: `GRAPHQL_LATEST_QUERY` returns the newest indexed height,
+//! `GRAPHQL_BLOCKS_QUERY` the blocks with heights in `$from..=$to` and their
+//! transactions. Templates use GraphQL aliases to produce the field names
+//! below, so another schema is mapped by editing the query, not the code:
+//!
+//! ```text
+//! latest: height              (number / numeric string, or { height }, or [{ height }])
+//! blocks: [{ height, hash, prev_hash?, timestamp, gas_price?,
+//!            transactions: [{ hash, signer_id, receiver_id, nonce?, actions? }] }]
+//! ```
+//!
+//! `timestamp` may be nanoseconds, milliseconds, seconds or RFC 3339.
+//! `actions` are RPC-shaped (`{"FunctionCall": {...}}`) or indexer-shaped
+//! (`{kind: "FUNCTION_CALL", args: {...}}`). A response that doesn't fit is
+//! reported on the source badge as a schema mismatch naming the path that
+//! failed and the fields that were there instead.
+
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    config::Config,
+    source_health::{SourceKind, SourceReport},
+    types::{AppEvent, BlockRow, TxLite},
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::time::{sleep, Duration};
+
+#[cfg(target_arch = "wasm32")]
+use web_time::Duration;
+
+#[cfg(target_arch = "wasm32")]
+async fn sleep(duration: Duration) {
+    gloo_timers::future::sleep(std::time::Duration::from_millis(duration.as_millis() as u64)).await;
+}
+
+/// Newest indexed height (Hasura over the NEAR Indexer for Explorer tables)
+pub const DEFAULT_LATEST_QUERY: &str = r#"query Latest {
+  latest: blocks(limit: 1, order_by: {block_height: desc}) { height: block_height }
+}"#;
+
+/// Blocks `$from..=$to` with their transactions (same schema as the default latest query)
+pub const DEFAULT_BLOCKS_QUERY: &str = r#"query Blocks($from: numeric!, $to: numeric!) {
+  blocks(where: {block_height: {_gte: $from, _lte: $to}}, order_by: {block_height: asc}) {
+    height: block_height
+    hash: block_hash
+    prev_hash: prev_block_hash
+    timestamp: block_timestamp
+    gas_price
+    transactions(order_by: {index_in_chunk: asc}) {
+      hash: transaction_hash
+      signer_id: signer_account_id
+      receiver_id: receiver_account_id
+      nonce
+      actions: transaction_actions(order_by: {index_in_transaction: asc}) { kind: action_kind args }
+    }
+  }
+}"#;
+
+/// Parse `GRAPHQL_HEADERS`: `Name: value` pairs separated by `;`
+pub fn parse_headers(s: &str) -> Result<Vec<(String, String)>> {
+    s.split(';')
+        .map(str::trim)
+        .filter(|h| !h.is_empty())
+        .map(|h| {
+            let (name, value) = h.split_once(':').ok_or_else(|| {
+                anyhow!("Invalid GRAPHQL_HEADERS entry '{h}'. Expected 'Name: value' (separate several with ';')")
+            })?;
+            Ok((name.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Field names of an object, or the JSON type of anything else
+fn describe(v: &Value) -> String {
+    match v {
+        Value::Object(m) if m.is_empty() => "empty object".to_string(),
+        Value::Object(m) => format!(
+            "fields {}",
+            m.keys().cloned().collect::<Vec<_>>().join(", ")
+        ),
+        Value::Array(a) => format!("a list of {}", a.len()),
+        Value::String(_) => "a string".to_string(),
+        Value::Number(_) => "a number".to_string(),
+        Value::Bool(_) => "a boolean".to_string(),
+        Value::Null => "null".to_string(),
+    }
+}
+
+fn mismatch(path: &str, expected: &str, found: &Value) -> anyhow::Error {
+    anyhow!(
+        "schema mismatch at {path}: expected {expected}, found {}",
+        describe(found)
+    )
+}
+
+/// Required field `key` of `obj`, with a hint to alias it when missing
+fn field<'a>(obj: &'a Value, path: &str, key: &str) -> Result<&'a Value> {
+    match obj.get(key) {
+        Some(v) if !v.is_null() => Ok(v),
+        _ => Err(anyhow!(
+            "schema mismatch at {path}: no `{key}` (found {}); alias it in the query, e.g. `{key}: <your field>`",
+            describe(obj)
+        )),
+    }
+}
+
+/// Integer from a JSON number or a numeric string (`numeric` / `BigInt` scalars)
+fn number(v: &Value) -> Option<u128> {
+    match v {
+        Value::Number(n) => n
+            .as_u64()
+            .map(u128::from)
+            .or_else(|| n.as_f64().map(|f| f as u128)),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Block time in nanoseconds from ns / ms / s since the epoch or RFC 3339
+fn timestamp_ns(v: &Value) -> Option<u128> {
+    if let Some(s) = v.as_str().filter(|s| s.contains('-')) {
+        let dt = chrono::DateTime::parse_from_rfc3339(s).ok()?;
+        return dt.timestamp_nanos_opt().map(|ns| ns.max(0) as u128);
+    }
+    let n = number(v)?;
+    Some(match n {
+        n if n >= 1_000_000_000_000_000 => n,
+        n if n >= 1_000_000_000_000 => n * 1_000_000,
+        n => n * 1_000_000_000,
+    })
+}
+
+/// Newest height from the latest query's `data`
+pub fn parse_latest(data: &Value) -> Result<u64> {
+    let latest = field(data, "data", "latest")?;
+    let row = match latest {
+        Value::Array(rows) => rows.first().ok_or_else(|| {
+            anyhow!("schema mismatch at data.latest: empty list (nothing indexed yet?)")
+        })?,
+        other => other,
+    };
+    let height = if row.is_object() {
+        field(row, "data.latest", "height")?
+    } else {
+        row
+    };
+    number(height)
+        .map(|h| h as u64)
+        .ok_or_else(|| mismatch("data.latest.height", "a block height", height))
+}
+
+/// Indexer-shaped action (`{kind: "FUNCTION_CALL", args}`) in the RPC shape
+fn rpc_action(action: &Value) -> Value {
+    let Some(kind) = action.get("kind").and_then(Value::as_str) else {
+        return action.clone();
+    };
+    let name: String = kind
+        .split('_')
+        .map(|w| {
+            let w = w.to_lowercase();
+            let mut c = w.chars();
+            c.next()
+                .map(|f| f.to_uppercase().chain(c).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect();
+    let args = action.get("args").cloned().unwrap_or_else(|| json!({}));
+    let args = match name.as_str() {
+        "FunctionCall" => json!({
+            "method_name": args["method_name"],
+            "args": args.get("args_base64").unwrap_or(&args["args"]),
+            "gas": number(&args["gas"]).map(|g| g as u64),
+            "deposit": number(&args["deposit"]).map(|d| d.to_string()),
+        }),
+        "Transfer" => json!({ "deposit": number(&args["deposit"]).map(|d| d.to_string()) }),
+        "Stake" => json!({
+            "stake": number(&args["stake"]).map(|s| s.to_string()),
+            "public_key": args["public_key"],
+        }),
+        _ => args,
+    };
+    json!({ name: args })
+}
+
+fn parse_tx(t: &Value, path: &str) -> Result<TxLite> {
+    let text = |key: &str| -> Result<String> {
+        let v = field(t, path, key)?;
+        v.as_str()
+            .map(str::to_string)
+            .ok_or_else(|| mismatch(&format!("{path}.{key}"), "a string", v))
+    };
+    let actions = match t.get("actions") {
+        None | Some(Value::Null) => None,
+        Some(Value::Array(actions)) => {
+            let actions: Vec<Value> = actions.iter().map(rpc_action).collect();
+            Some(crate::rpc_utils::parse_actions(&actions))
+        }
+        Some(other) => return Err(mismatch(&format!("{path}.actions"), "a list", other)),
+    };
+    Ok(TxLite {
+        hash: text("hash")?,
+        signer_id: Some(text("signer_id")?),
+        receiver_id: Some(text("receiver_id")?),
+        actions,
+        nonce: t.get("nonce").and_then(number).map(|n| n as u64),
+    })
+}
+
+fn parse_block(b: &Value, path: &str) -> Result<BlockRow> {
+    let height_v = field(b, path, "height")?;
+    let height = number(height_v)
+        .map(|h| h as u64)
+        .ok_or_else(|| mismatch(&format!("{path}.height"), "a block height", height_v))?;
+    let hash_v = field(b, path, "hash")?;
+    let hash = hash_v
+        .as_str()
+        .ok_or_else(|| mismatch(&format!("{path}.hash"), "a string", hash_v))?
+        .to_string();
+    let ts_v = field(b, path, "timestamp")?;
+    let timestamp = timestamp_ns(ts_v).ok_or_else(|| {
+        mismatch(
+            &format!("{path}.timestamp"),
+            "ns/ms/s since the epoch or RFC 3339",
+            ts_v,
+        )
+    })?;
+
+    let txs_v = field(b, path, "transactions")?;
+    let txs = txs_v
+        .as_array()
+        .ok_or_else(|| mismatch(&format!("{path}.transactions"), "a list", txs_v))?
+        .iter()
+        .enumerate()
+        .map(|(i, t)| parse_tx(t, &format!("{path}.transactions[{i}]")))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(BlockRow {
+        height,
+        hash,
+        prev_height: b.get("prev_height").and_then(number).map(|h| h as u64),
+        prev_hash: b
+            .get("prev_hash")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        timestamp: (timestamp / 1_000_000) as u64,
+        tx_count: txs.len(),
+        when: crate::rpc_utils::chrono_fmt(timestamp as i64),
+        transactions: txs,
+        gas_price: b.get("gas_price").and_then(number),
+        gas_used: b.get("gas_used").and_then(number).map(|g| g as u64),
+    })
+}
+
+/// Blocks from the blocks query's `data`, oldest first
+pub fn parse_blocks(data: &Value) -> Result<Vec<BlockRow>> {
+    let blocks_v = field(data, "data", "blocks")?;
+    let mut blocks = blocks_v
+        .as_array()
+        .ok_or_else(|| mismatch("data.blocks", "a list", blocks_v))?
+        .iter()
+        .enumerate()
+        .map(|(i, b)| parse_block(b, &format!("data.blocks[{i}]")))
+        .collect::<Result<Vec<_>>>()?;
+    blocks.sort_by_key(|b| b.height);
+    Ok(blocks)
+}
+
+/// POST one query; returns `data`, or the server's `errors` as the error
+pub async fn query(cfg: &Config, query: &str, variables: Value) -> Result<Value> {
+    let url = cfg
+        .graphql_url
+        .as_deref()
+        .ok_or_else(|| anyhow!("GRAPHQL_URL is not set"))?;
+    let mut req = crate::rpc_utils::http_client()
+        .post(url)
+        .json(&json!({ "query": query, "variables": variables }))
+        .timeout(std::time::Duration::from_millis(cfg.rpc_timeout_ms));
+    for (name, value) in &cfg.graphql_headers {
+        req = req.header(name.as_str(), value.as_str());
+    }
+    let res = req.send().await?;
+    let status = res.status();
+    let body: Value = res
+        .json()
+        .await
+        .map_err(|e| anyhow!("http {status}: response is not JSON ({e})"))?;
+    if let Some(errors) = body["errors"].as_array().filter(|e| !e.is_empty()) {
+        let messages: Vec<&str> = errors
+            .iter()
+            .filter_map(|e| e["message"].as_str())
+            .collect();
+        return Err(anyhow!("graphql: {}", messages.join("; ")));
+    }
+    if !status.is_success() {
+        return Err(anyhow!("http {status}"));
+    }
+    match body.get("data") {
+        Some(data) if !data.is_null() => Ok(data.clone()),
+        _ => Err(mismatch("(response)", "a `data` object", &body)),
+    }
+}
+
+pub async fn latest_height(cfg: &Config) -> Result<u64> {
+    parse_latest(&query(cfg, &cfg.graphql_latest_query, json!({})).await?)
+}
+
+pub async fn fetch_blocks(cfg: &Config, from: u64, to: u64) -> Result<Vec<BlockRow>> {
+    parse_blocks(
+        &query(
+            cfg,
+            &cfg.graphql_blocks_query,
+            json!({ "from": from, "to": to }),
+        )
+        .await?,
+    )
+}
+
+pub async fn run_graphql(cfg: &Config, tx: UnboundedSender<AppEvent>) -> Result<()> {
+    let report = |report: SourceReport| {
+        let _ = tx.send(AppEvent::SourceStatus {
+            kind: SourceKind::GraphQl,
+            report,
+        });
+    };
+    log::info!(
+        "GraphQL polling loop started - endpoint: {}",
+        cfg.graphql_url.as_deref().unwrap_or_default()
+    );
+
+    let mut last_height: u64 = 0;
+    loop {
+        match latest_height(cfg).await {
+            Ok(latest) => {
+                if last_height == 0 {
+                    last_height = latest;
+                    log::info!("GraphQL: starting from block height {last_height}");
+                }
+                if latest > last_height {
+                    // Heights the chain skipped simply don't come back
+                    let start = last_height + 1;
+                    let end = (start + cfg.poll_max_catchup - 1).min(latest);
+                    match fetch_blocks(cfg, start, end).await {
+                        Ok(blocks) => {
+                            report(SourceReport::Ok);
+                            for row in blocks {
+                                let _ = tx.send(AppEvent::NewBlock(row));
+                            }
+                            last_height = end;
+                        }
+                        Err(e) => {
+                            log::warn!("GraphQL: blocks {start}..={end}: {e:#}");
+                            report(SourceReport::Error(format!("#{start}..#{end}: {e:#}")));
+                        }
+                    }
+                } else {
+                    report(SourceReport::Ok);
+                }
+            }
+            Err(e) => {
+                log::warn!("GraphQL: latest height: {e:#}");
+                report(SourceReport::Error(format!("{e:#}")));
+            }
+        }
+        sleep(Duration::from_millis(cfg.poll_interval_ms)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_blocks_and_reports_mismatches() {
+        assert_eq!(
+            parse_latest(&json!({"latest": [{"height": "150"}]})).unwrap(),
+            150
+        );
+        assert_eq!(parse_latest(&json!({"latest": 151})).unwrap(), 151);
+        let err = parse_latest(&json!({"latest": [{"block_height": 1}]})).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("no `height` (found fields block_height)"));
+
+        let data = json!({"blocks": [
+            {"height": 101, "hash": "H2", "timestamp": "2024-05-01T12:00:01Z", "transactions": []},
+            {"height": "100", "hash": "H1", "prev_hash": "H0", "timestamp": "1714564800000000000",
+             "gas_price": "100000000", "transactions": [
+                {"hash": "T1", "signer_id": "alice.near", "receiver_id": "intents.near", "nonce": 7,
+                 "actions": [
+                    {"kind": "FUNCTION_CALL", "args": {"method_name": "ft_transfer", "args_base64": "e30=",
+                                                       "gas": 30000000000000u64, "deposit": "1"}},
+                    {"Transfer": {"deposit": "5"}}
+                 ]}
+            ]}
+        ]});
+        let blocks = parse_blocks(&data).unwrap();
+        assert_eq!(
+            blocks.iter().map(|b| b.height).collect::<Vec<_>>(),
+            vec![100, 101]
+        );
+        assert_eq!(blocks[0].timestamp, 1_714_564_800_000);
+        assert_eq!(blocks[1].timestamp, 1_714_564_801_000);
+        assert_eq!(blocks[0].gas_price, Some(100_000_000));
+        let tx = &blocks[0].transactions[0];
+        assert_eq!(tx.nonce, Some(7));
+        let actions = tx.actions.as_ref().unwrap();
+        assert!(
+            matches!(&actions[0], crate::types::ActionSummary::FunctionCall { method_name, deposit: 1, .. } if method_name == "ft_transfer")
+        );
+        assert!(matches!(
+            actions[1],
+            crate::types::ActionSummary::Transfer { deposit: 5 }
+        ));
+
+        let bad = json!({"blocks": [{"height": 1, "hash": "H", "timestamp": 1, "transactions": [
+            {"hash": "T", "signer": "a.near", "receiver_id": "b.near"}
+        ]}]});
+        let err = parse_blocks(&bad).unwrap_err().to_string();
+        assert!(
+            err.starts_with("schema mismatch at data.blocks[0].transactions[0]: no `signer_id`"),
+            "{err}"
+        );
+        assert!(parse_blocks(&json!({"blocks": {"height": 1}})).is_err());
+
+        assert_eq!(
+            parse_headers("x-hasura-admin-secret: s3cret; Authorization: Bearer t").unwrap(),
+            vec![
+                ("x-hasura-admin-secret".to_string(), "s3cret".to_string()),
+                ("Authorization".to_string(), "Bearer t".to_string())
+            ]
+        );
+        assert!(parse_headers("nocolon").is_err());
+    }
+}
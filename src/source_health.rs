@@ -0,0 +1,320 @@
+//! Block source health
+//!
+//! Source tasks report each success or failure (`AppEvent::SourceStatus`); the
+//! tracker turns the reports into a footer badge per configured source:
+//! ok (recent success), degraded (recent error, or a live source gone quiet)
+//! or down (repeated errors, or the task stopped). Sources can be switched off
+//! for the session from the sources overlay; the runtime respawns its tasks
+//! without them.
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+
+#[cfg(target_arch = "wasm32")]
+use web_time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// Consecutive errors (no success in between) before a source counts as down
+const DOWN_AFTER_ERRORS: u32 = 3;
+/// An error this recent keeps the badge degraded
+const DEGRADED_FOR: Duration = Duration::from_secs(30);
+/// Live sources without a success for this long are degraded (archival is on demand)
+const LIVE_QUIET_AFTER: Duration = Duration::from_secs(15);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SourceKind {
+    Ws,
+    Rpc,
+    GraphQl,
+    Archival,
+}
+
+impl SourceKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            SourceKind::Ws => "WS",
+            SourceKind::Rpc => "RPC",
+            SourceKind::GraphQl => "GraphQL",
+            SourceKind::Archival => "Archival",
+        }
+    }
+}
+
+/// What a source task reports
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SourceReport {
+    Ok,
+    Error(String),
+    /// The task gave up (e.g. WebSocket closed); down until it reports again
+    Stopped(String),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HealthStatus {
+    /// No report yet
+    Unknown,
+    Ok,
+    Degraded,
+    Down,
+    /// Switched off in the sources overlay
+    Disabled,
+}
+
+impl HealthStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            HealthStatus::Unknown => "waiting",
+            HealthStatus::Ok => "ok",
+            HealthStatus::Degraded => "degraded",
+            HealthStatus::Down => "down",
+            HealthStatus::Disabled => "off",
+        }
+    }
+}
+
+/// Where the live feed is, for loading states (skeleton rows, pane indicators)
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SourcePhase {
+    /// No live source has answered yet
+    Connecting,
+    /// A live source answered; the first block isn't in yet
+    FirstSync,
+    /// Blocks arriving
+    Live,
+    /// Blocks arrived earlier, but no live source is healthy now
+    Stalled,
+}
+
+impl SourcePhase {
+    pub fn label(self) -> &'static str {
+        match self {
+            SourcePhase::Connecting => "connecting…",
+            SourcePhase::FirstSync => "first sync…",
+            SourcePhase::Live => "live",
+            SourcePhase::Stalled => "stalled",
+        }
+    }
+
+    /// Still waiting for the first live block
+    pub fn is_loading(self) -> bool {
+        matches!(self, SourcePhase::Connecting | SourcePhase::FirstSync)
+    }
+}
+
+/// Phase of the live feed from the live (non-archival) sources; `has_blocks`
+/// is whether a live block has arrived (warm-start rows don't count)
+pub fn phase(sources: &[SourceHealth], has_blocks: bool, now: Instant) -> SourcePhase {
+    let live: Vec<&SourceHealth> = sources
+        .iter()
+        .filter(|s| s.enabled && s.kind != SourceKind::Archival)
+        .collect();
+    if has_blocks {
+        // A recent error alone doesn't stall the feed; silence or a dead source does
+        let fresh = live.iter().any(|s| {
+            s.status(now) != HealthStatus::Down
+                && s.last_ok.is_some_and(|ok| now.duration_since(ok) <= LIVE_QUIET_AFTER)
+        });
+        return if fresh { SourcePhase::Live } else { SourcePhase::Stalled };
+    }
+    if live.iter().any(|s| s.last_ok.is_some()) {
+        SourcePhase::FirstSync
+    } else {
+        SourcePhase::Connecting
+    }
+}
+
+/// Default age of the newest block past which the Blocks pane is watermarked stale
+pub const DEFAULT_STALE_AFTER_SECS: u64 = 60;
+
+/// `STALE — last block 94s ago` once the newest buffered block (timestamp in
+/// unix ms) is `after_secs` old; `None` while fresh, without a timestamp, or off (0)
+pub fn stale_watermark(newest_ms: u64, now_ms: i64, after_secs: u64) -> Option<String> {
+    if after_secs == 0 || newest_ms == 0 {
+        return None;
+    }
+    let age = (now_ms - newest_ms as i64).max(0) as u64 / 1000;
+    (age >= after_secs).then(|| format!("STALE — last block {} ago", age_label(age)))
+}
+
+/// `94s`, `12m`, `5h`, `3d`
+fn age_label(secs: u64) -> String {
+    match secs {
+        s if s < 120 => format!("{s}s"),
+        s if s < 2 * 3600 => format!("{}m", s / 60),
+        s if s < 2 * 86_400 => format!("{}h", s / 3600),
+        s => format!("{}d", s / 86_400),
+    }
+}
+
+/// Point-in-time view of one source, for the footer and the sources overlay
+#[derive(Clone, Debug)]
+pub struct SourceBadge {
+    pub kind: SourceKind,
+    pub status: HealthStatus,
+    /// Last error and how long ago it happened
+    pub last_error: Option<(Duration, String)>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SourceHealth {
+    pub kind: SourceKind,
+    pub enabled: bool,
+    last_ok: Option<Instant>,
+    last_error: Option<(Instant, String)>,
+    errors_since_ok: u32,
+    stopped: bool,
+}
+
+impl SourceHealth {
+    pub fn new(kind: SourceKind) -> Self {
+        Self {
+            kind,
+            enabled: true,
+            last_ok: None,
+            last_error: None,
+            errors_since_ok: 0,
+            stopped: false,
+        }
+    }
+
+    pub fn record(&mut self, now: Instant, report: SourceReport) {
+        match report {
+            SourceReport::Ok => {
+                self.last_ok = Some(now);
+                self.errors_since_ok = 0;
+                self.stopped = false;
+            }
+            SourceReport::Error(e) => {
+                self.last_error = Some((now, e));
+                self.errors_since_ok += 1;
+            }
+            SourceReport::Stopped(e) => {
+                self.last_error = Some((now, e));
+                self.stopped = true;
+            }
+        }
+    }
+
+    /// Forget past reports (the task was respawned)
+    pub fn reset(&mut self) {
+        *self = Self {
+            enabled: self.enabled,
+            ..Self::new(self.kind)
+        };
+    }
+
+    pub fn status(&self, now: Instant) -> HealthStatus {
+        if !self.enabled {
+            return HealthStatus::Disabled;
+        }
+        if self.stopped || self.errors_since_ok >= DOWN_AFTER_ERRORS {
+            return HealthStatus::Down;
+        }
+        let recent_error = self
+            .last_error
+            .as_ref()
+            .is_some_and(|(at, _)| now.duration_since(*at) < DEGRADED_FOR);
+        match self.last_ok {
+            None if self.errors_since_ok > 0 => HealthStatus::Degraded,
+            None => HealthStatus::Unknown,
+            Some(_) if recent_error => HealthStatus::Degraded,
+            Some(ok) if self.kind != SourceKind::Archival && now.duration_since(ok) > LIVE_QUIET_AFTER => {
+                HealthStatus::Degraded
+            }
+            Some(_) => HealthStatus::Ok,
+        }
+    }
+
+    /// Last error and how long ago it happened
+    pub fn last_error(&self, now: Instant) -> Option<(Duration, &str)> {
+        self.last_error
+            .as_ref()
+            .map(|(at, e)| (now.duration_since(*at), e.as_str()))
+    }
+
+    pub fn badge(&self, now: Instant) -> SourceBadge {
+        SourceBadge {
+            kind: self.kind,
+            status: self.status(now),
+            last_error: self.last_error(now).map(|(age, e)| (age, e.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_follows_reports() {
+        let t0 = Instant::now();
+        let mut h = SourceHealth::new(SourceKind::Rpc);
+        assert_eq!(h.status(t0), HealthStatus::Unknown);
+
+        h.record(t0, SourceReport::Ok);
+        assert_eq!(h.status(t0), HealthStatus::Ok);
+        // Live source gone quiet
+        assert_eq!(h.status(t0 + Duration::from_secs(20)), HealthStatus::Degraded);
+
+        h.record(t0, SourceReport::Error("timeout".into()));
+        assert_eq!(h.status(t0), HealthStatus::Degraded);
+        h.record(t0, SourceReport::Error("timeout".into()));
+        h.record(t0, SourceReport::Error("timeout".into()));
+        assert_eq!(h.status(t0), HealthStatus::Down);
+        assert_eq!(h.last_error(t0), Some((Duration::ZERO, "timeout")));
+
+        h.record(t0, SourceReport::Ok);
+        assert_eq!(h.status(t0), HealthStatus::Degraded); // error still recent
+        assert_eq!(h.status(t0 + Duration::from_secs(31)), HealthStatus::Degraded); // quiet
+        h.record(t0 + Duration::from_secs(31), SourceReport::Ok);
+        assert_eq!(h.status(t0 + Duration::from_secs(31)), HealthStatus::Ok);
+
+        h.record(t0, SourceReport::Stopped("closed".into()));
+        assert_eq!(h.status(t0), HealthStatus::Down);
+        h.enabled = false;
+        assert_eq!(h.status(t0), HealthStatus::Disabled);
+    }
+
+    #[test]
+    fn phase_tracks_first_answer_first_block_and_stalls() {
+        let t0 = Instant::now();
+        let mut rpc = SourceHealth::new(SourceKind::Rpc);
+        let mut archival = SourceHealth::new(SourceKind::Archival);
+        archival.record(t0, SourceReport::Ok);
+        assert_eq!(phase(&[rpc.clone(), archival.clone()], false, t0), SourcePhase::Connecting);
+
+        rpc.record(t0, SourceReport::Error("timeout".into()));
+        assert_eq!(phase(&[rpc.clone()], false, t0), SourcePhase::Connecting);
+        rpc.record(t0, SourceReport::Ok);
+        assert_eq!(phase(&[rpc.clone()], false, t0), SourcePhase::FirstSync);
+        assert!(phase(&[rpc.clone()], false, t0).is_loading());
+        assert_eq!(phase(&[rpc.clone()], true, t0), SourcePhase::Live);
+
+        // Quiet for too long, or switched off
+        let later = t0 + Duration::from_secs(60);
+        assert_eq!(phase(&[rpc.clone()], true, later), SourcePhase::Stalled);
+        rpc.enabled = false;
+        assert_eq!(phase(&[rpc], true, t0), SourcePhase::Stalled);
+    }
+
+    #[test]
+    fn stale_watermark_after_threshold() {
+        let newest = 1_700_000_000_000u64;
+        let at = |secs: i64| newest as i64 + secs * 1000;
+        assert_eq!(stale_watermark(newest, at(59), 60), None);
+        assert_eq!(
+            stale_watermark(newest, at(94), 60).as_deref(),
+            Some("STALE — last block 94s ago")
+        );
+        assert_eq!(
+            stale_watermark(newest, at(3 * 3600), 60).as_deref(),
+            Some("STALE — last block 3h ago")
+        );
+        // Off, or no timestamp from the source
+        assert_eq!(stale_watermark(newest, at(3600), 0), None);
+        assert_eq!(stale_watermark(0, at(3600), 60), None);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     config::Config,
     rpc_utils::{fetch_block_with_txs, get_latest_block},
+    source_health::{SourceKind, SourceReport},
     types::AppEvent,
 };
 use anyhow::Result;
@@ -50,6 +51,10 @@ pub async fn run_rpc(cfg: &Config, tx: UnboundedSender<AppEvent>) -> Result<()>
         // non-overlapping loop, catch-up limited (guide's pattern).
         match get_latest_block(&cfg.near_node_url, cfg.rpc_timeout_ms, token.as_deref()).await {
             Ok(latest) => {
+                let _ = tx.send(AppEvent::SourceStatus {
+                    kind: SourceKind::Rpc,
+                    report: SourceReport::Ok,
+                });
                 let latest_h = latest["header"]["height"].as_u64().unwrap_or(0);
                 log::debug!("✅ Got latest block height: {latest_h}");
 
@@ -70,7 +75,7 @@ pub async fn run_rpc(cfg: &Config, tx: UnboundedSender<AppEvent>) -> Result<()>
 
                     for h in start..=end {
                         let token = get_token(); // Refresh token for each block fetch
-                        if let Ok(row) = fetch_block_with_txs(
+                        match fetch_block_with_txs(
                             &cfg.near_node_url,
                             h,
                             cfg.rpc_timeout_ms,
@@ -79,15 +84,22 @@ pub async fn run_rpc(cfg: &Config, tx: UnboundedSender<AppEvent>) -> Result<()>
                         )
                         .await
                         {
-                            log::info!(
-                                "🔔 Sending NewBlock event - height: {}, txs: {}",
-                                h,
-                                row.tx_count
-                            );
-                            let _ = tx.send(AppEvent::NewBlock(row));
-                            last_height = h;
-                        } else {
-                            log::warn!("⚠️ Failed to fetch block {h}");
+                            Ok(row) => {
+                                log::info!(
+                                    "🔔 Sending NewBlock event - height: {}, txs: {}",
+                                    h,
+                                    row.tx_count
+                                );
+                                let _ = tx.send(AppEvent::NewBlock(row));
+                                last_height = h;
+                            }
+                            Err(e) => {
+                                log::warn!("⚠️ Failed to fetch block {h}");
+                                let _ = tx.send(AppEvent::SourceStatus {
+                                    kind: SourceKind::Rpc,
+                                    report: SourceReport::Error(format!("#{h}: {e:#}")),
+                                });
+                            }
                         }
                     }
                 } else {
@@ -96,6 +108,10 @@ pub async fn run_rpc(cfg: &Config, tx: UnboundedSender<AppEvent>) -> Result<()>
             }
             Err(e) => {
                 log::error!("❌ RPC error: {e:?}");
+                let _ = tx.send(AppEvent::SourceStatus {
+                    kind: SourceKind::Rpc,
+                    report: SourceReport::Error(format!("{e:#}")),
+                });
             }
         }
 
@@ -5,6 +5,7 @@
 use crate::{
     config::Config,
     rpc_utils::fetch_block_with_txs,
+    source_health::{SourceKind, SourceReport},
     types::{AppEvent, WsPayload},
 };
 use anyhow::Result;
@@ -32,8 +33,19 @@ fn get_rpc_url_for_network(network: &str) -> &'static str {
     }
 }
 
+fn report(tx: &UnboundedSender<AppEvent>, kind: SourceKind, report: SourceReport) {
+    let _ = tx.send(AppEvent::SourceStatus { kind, report });
+}
+
 pub async fn run_ws(cfg: &Config, tx: UnboundedSender<AppEvent>) -> Result<()> {
-    let (ws, _) = connect_async(&cfg.ws_url).await?;
+    let ws = match connect_async(&cfg.ws_url).await {
+        Ok((ws, _)) => ws,
+        Err(e) => {
+            report(&tx, SourceKind::Ws, SourceReport::Stopped(format!("connect failed: {e}")));
+            return Err(e.into());
+        }
+    };
+    report(&tx, SourceKind::Ws, SourceReport::Ok);
     let (mut ws_write, mut ws_read) = ws.split();
 
     // Optional: identify as NEARx client
@@ -44,11 +56,15 @@ pub async fn run_ws(cfg: &Config, tx: UnboundedSender<AppEvent>) -> Result<()> {
     while let Some(msg) = ws_read.next().await {
         let msg = match msg {
             Ok(m) => m,
-            Err(_) => break,
+            Err(e) => {
+                report(&tx, SourceKind::Ws, SourceReport::Stopped(e.to_string()));
+                return Ok(());
+            }
         };
         if !msg.is_text() {
             continue;
         }
+        report(&tx, SourceKind::Ws, SourceReport::Ok);
         let text = msg.into_text().unwrap_or_default();
         if let Ok(payload) = serde_json::from_str::<WsPayload>(&text) {
             match payload {
@@ -80,11 +96,13 @@ pub async fn run_ws(cfg: &Config, tx: UnboundedSender<AppEvent>) -> Result<()> {
                         .await
                         {
                             Ok(row) => {
+                                report(&tx_clone, SourceKind::Rpc, SourceReport::Ok);
                                 let _ = tx_clone.send(AppEvent::NewBlock(row));
                             }
-                            Err(_e) => {
-                                // Silently fail (logging would break TUI)
+                            Err(e) => {
+                                // No logging (would break TUI); the RPC badge shows the error.
                                 // Fallback: send empty block notification
+                                report(&tx_clone, SourceKind::Rpc, SourceReport::Error(format!("#{height}: {e:#}")));
                                 let _ = tx_clone
                                     .send(AppEvent::FromWs(WsPayload::Block { data: height }));
                             }
@@ -98,5 +116,6 @@ pub async fn run_ws(cfg: &Config, tx: UnboundedSender<AppEvent>) -> Result<()> {
             }
         }
     }
+    report(&tx, SourceKind::Ws, SourceReport::Stopped("connection closed".into()));
     Ok(())
 }
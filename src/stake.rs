@@ -0,0 +1,200 @@
+//! Validator stake concentration
+//!
+//! Summarizes the `validators` RPC once per epoch: how many validators it
+//! takes to control more than a third of the stake (the Nakamoto coefficient,
+//! enough to halt finality) and the share held by the largest N. Consecutive
+//! epochs are compared so a concentration change of `alert_pp` percentage
+//! points (or any change in the coefficient) raises an alert.
+
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+
+use crate::rpc_utils::rpc_post;
+
+/// Default alert threshold: top-10 share moving this many percentage points
+pub const DEFAULT_STAKE_ALERT_PP: f64 = 2.0;
+/// How often the validators RPC is polled (a new epoch is only reported once)
+pub const POLL_SECS: u64 = 300;
+/// Top-N stake shares shown in the panel
+pub const TOP_N: [usize; 3] = [1, 10, 20];
+/// Top-N share compared between epochs for alerts
+const ALERT_TOP_N: usize = 10;
+
+/// Stake distribution of the current validator set
+#[derive(Debug, Clone, PartialEq)]
+pub struct StakeDistribution {
+    pub epoch_height: u64,
+    pub epoch_start_height: u64,
+    pub validators: usize,
+    pub total_stake: u128,
+    /// Fewest validators holding more than 1/3 of the stake
+    pub nakamoto: usize,
+    /// Largest validator by stake
+    pub largest: String,
+    /// (N, percent of total stake held by the largest N), per `TOP_N`
+    pub top_shares: Vec<(usize, f64)>,
+}
+
+/// Concentration moved between two epochs
+#[derive(Debug, Clone, PartialEq)]
+pub struct StakeAlert {
+    pub epoch_height: u64,
+    pub nakamoto_before: usize,
+    pub nakamoto_after: usize,
+    pub top_n: usize,
+    /// Change in the top-N share (percentage points, positive = more concentrated)
+    pub share_change_pp: f64,
+}
+
+impl StakeAlert {
+    /// Stake became more concentrated (fewer validators to halt, or larger top share)
+    pub fn concentrating(&self) -> bool {
+        self.nakamoto_after < self.nakamoto_before
+            || (self.nakamoto_after == self.nakamoto_before && self.share_change_pp > 0.0)
+    }
+}
+
+impl StakeDistribution {
+    /// Build from a `validators` RPC result
+    pub fn from_validators(result: &Value) -> Result<Self> {
+        let current = result["current_validators"]
+            .as_array()
+            .ok_or_else(|| anyhow!("validators: missing current_validators"))?;
+        let mut stakes: Vec<(String, u128)> = current
+            .iter()
+            .filter_map(|v| {
+                let account = v["account_id"].as_str()?;
+                let stake = v["stake"].as_str()?.parse().ok()?;
+                Some((account.to_string(), stake))
+            })
+            .collect();
+        if stakes.is_empty() {
+            return Err(anyhow!("validators: empty validator set"));
+        }
+        stakes.sort_by(|a, b| b.1.cmp(&a.1));
+
+        let total_stake: u128 = stakes.iter().map(|(_, s)| s).sum();
+        let mut held = 0u128;
+        let mut nakamoto = stakes.len();
+        for (i, (_, stake)) in stakes.iter().enumerate() {
+            held += stake;
+            // held > total / 3, without the rounding of the division
+            if held * 3 > total_stake {
+                nakamoto = i + 1;
+                break;
+            }
+        }
+
+        let share = |n: usize| {
+            let top: u128 = stakes.iter().take(n).map(|(_, s)| s).sum();
+            percent(top, total_stake)
+        };
+        Ok(Self {
+            epoch_height: result["epoch_height"].as_u64().unwrap_or_default(),
+            epoch_start_height: result["epoch_start_height"].as_u64().unwrap_or_default(),
+            validators: stakes.len(),
+            total_stake,
+            nakamoto,
+            largest: stakes[0].0.clone(),
+            top_shares: TOP_N.iter().map(|&n| (n, share(n))).collect(),
+        })
+    }
+
+    /// Percent of stake held by the largest `n` (only for N in `TOP_N`)
+    pub fn top_share(&self, n: usize) -> Option<f64> {
+        self.top_shares
+            .iter()
+            .find(|(k, _)| *k == n)
+            .map(|(_, p)| *p)
+    }
+
+    /// Alert when the coefficient changed or the top-10 share moved by `alert_pp`
+    pub fn compare(&self, previous: &Self, alert_pp: f64) -> Option<StakeAlert> {
+        let change = self.top_share(ALERT_TOP_N)? - previous.top_share(ALERT_TOP_N)?;
+        if self.nakamoto == previous.nakamoto && change.abs() < alert_pp {
+            return None;
+        }
+        Some(StakeAlert {
+            epoch_height: self.epoch_height,
+            nakamoto_before: previous.nakamoto,
+            nakamoto_after: self.nakamoto,
+            top_n: ALERT_TOP_N,
+            share_change_pp: change,
+        })
+    }
+}
+
+fn percent(part: u128, total: u128) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    part as f64 * 100.0 / total as f64
+}
+
+/// Fetch the current validator set and summarize its stake
+pub async fn fetch(
+    url: &str,
+    timeout_ms: u64,
+    auth_token: Option<&str>,
+) -> Result<StakeDistribution> {
+    let result = rpc_post(
+        url,
+        &json!({"jsonrpc":"2.0","id":"nearx","method":"validators","params":[null]}),
+        timeout_ms,
+        auth_token,
+    )
+    .await?;
+    StakeDistribution::from_validators(&result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validators(epoch: u64, stakes: &[u128]) -> Value {
+        let current: Vec<Value> = stakes
+            .iter()
+            .enumerate()
+            .map(
+                |(i, s)| json!({"account_id": format!("v{i}.poolv1.near"), "stake": s.to_string()}),
+            )
+            .collect();
+        json!({"epoch_height": epoch, "epoch_start_height": epoch * 43_200, "current_validators": current})
+    }
+
+    #[test]
+    fn nakamoto_and_top_shares_with_alerts() {
+        // 30 validators: one at 20, one at 14, the rest at 2 (total 90)
+        let mut stakes = vec![20u128, 14];
+        stakes.extend([2u128; 28]);
+        let d = StakeDistribution::from_validators(&validators(100, &stakes)).unwrap();
+        assert_eq!(d.validators, 30);
+        assert_eq!(d.total_stake, 90);
+        // 20 + 14 = 34 > 30 (a third); 20 alone is not
+        assert_eq!(d.nakamoto, 2);
+        assert_eq!(d.largest, "v0.poolv1.near");
+        assert!((d.top_share(1).unwrap() - 22.22).abs() < 0.01);
+        assert!((d.top_share(10).unwrap() - 55.56).abs() < 0.01);
+
+        // Exactly a third is not enough
+        let even = StakeDistribution::from_validators(&validators(1, &[1, 1, 1])).unwrap();
+        assert_eq!(even.nakamoto, 2);
+
+        // Same epoch shape: no alert; top-10 up 2.8pp with the same coefficient: alert
+        assert_eq!(d.compare(&d, DEFAULT_STAKE_ALERT_PP), None);
+        stakes[2] = 8;
+        let next = StakeDistribution::from_validators(&validators(101, &stakes)).unwrap();
+        let alert = next.compare(&d, DEFAULT_STAKE_ALERT_PP).unwrap();
+        assert_eq!(alert.epoch_height, 101);
+        assert!(alert.concentrating());
+        assert!(alert.share_change_pp > 2.0);
+
+        // Largest validator alone passes a third: coefficient drops to 1
+        stakes[0] = 60;
+        let worse = StakeDistribution::from_validators(&validators(102, &stakes)).unwrap();
+        let alert = worse.compare(&next, 100.0).unwrap();
+        assert_eq!((alert.nakamoto_before, alert.nakamoto_after), (2, 1));
+
+        assert!(StakeDistribution::from_validators(&json!({"current_validators": []})).is_err());
+    }
+}
@@ -0,0 +1,139 @@
+//! Staged startup: first frame first, everything else behind it
+//!
+//! The native TUI draws its first frame before anything slow runs. History
+//! reads (warm blocks, marks, watches, telemetry, an interrupted fetch) happen
+//! on a background task and arrive as one [`StartupLoad`]; block sources, the
+//! archival fetch and the watchers are spawned side by side right after that
+//! frame. Until every stage is done the status bar lists what is still
+//! pending; the timings go to the log once the first live block is in.
+
+use std::time::Duration;
+
+use crate::history::{PersistedMark, TelemetryCounters};
+use crate::types::BlockRow;
+use crate::watch::WatchRule;
+
+/// First interactive frame should be up within this (logged when missed)
+pub const FIRST_FRAME_TARGET: Duration = Duration::from_millis(200);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StartupStage {
+    /// First frame drawn; keys are handled from here on
+    Frame,
+    /// Last session's state read from History
+    History,
+    /// First live block received
+    Sources,
+}
+
+impl StartupStage {
+    pub const ALL: [StartupStage; 3] = [
+        StartupStage::Frame,
+        StartupStage::History,
+        StartupStage::Sources,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StartupStage::Frame => "ui",
+            StartupStage::History => "history",
+            StartupStage::Sources => "sources",
+        }
+    }
+}
+
+/// Last session's state, read from History after the first frame
+#[derive(Clone, Debug, Default)]
+pub struct StartupLoad {
+    /// Empty unless warm start is on
+    pub warm_blocks: Vec<BlockRow>,
+    /// Jump marks (trash from earlier sessions already purged)
+    pub marks: Vec<PersistedMark>,
+    pub watch_rules: Vec<WatchRule>,
+    pub telemetry: Option<TelemetryCounters>,
+    /// Offer to resume a range download interrupted last time
+    pub fetch_hint: Option<String>,
+}
+
+/// Stages done so far, with when they finished (since process start)
+#[derive(Clone, Debug, Default)]
+pub struct StartupProgress {
+    done: Vec<(StartupStage, Duration)>,
+}
+
+impl StartupProgress {
+    /// Mark `stage` done `elapsed` after start; false when it already was
+    pub fn complete(&mut self, stage: StartupStage, elapsed: Duration) -> bool {
+        if self.is_done(stage) {
+            return false;
+        }
+        self.done.push((stage, elapsed));
+        true
+    }
+
+    pub fn is_done(&self, stage: StartupStage) -> bool {
+        self.done.iter().any(|(s, _)| *s == stage)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        StartupStage::ALL.iter().all(|s| self.is_done(*s))
+    }
+
+    pub fn elapsed(&self, stage: StartupStage) -> Option<Duration> {
+        self.done.iter().find(|(s, _)| *s == stage).map(|(_, d)| *d)
+    }
+
+    /// `starting: history, sources` (`None` once complete)
+    pub fn status_text(&self) -> Option<String> {
+        let pending: Vec<_> = StartupStage::ALL
+            .iter()
+            .filter(|s| !self.is_done(**s))
+            .map(|s| s.label())
+            .collect();
+        (!pending.is_empty()).then(|| format!("⟳ starting: {}", pending.join(", ")))
+    }
+
+    /// `ui 35ms · history 80ms · sources 640ms`, in completion order
+    pub fn summary(&self) -> String {
+        self.done
+            .iter()
+            .map(|(s, d)| format!("{} {}ms", s.label(), d.as_millis()))
+            .collect::<Vec<_>>()
+            .join(" · ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_pending_stages_until_complete() {
+        let mut progress = StartupProgress::default();
+        assert_eq!(
+            progress.status_text().as_deref(),
+            Some("⟳ starting: ui, history, sources")
+        );
+
+        assert!(progress.complete(StartupStage::Frame, Duration::from_millis(35)));
+        assert!(progress.complete(StartupStage::Sources, Duration::from_millis(640)));
+        assert!(!progress.complete(StartupStage::Frame, Duration::from_millis(900)));
+        assert_eq!(
+            progress.status_text().as_deref(),
+            Some("⟳ starting: history")
+        );
+        assert!(!progress.is_complete());
+
+        progress.complete(StartupStage::History, Duration::from_millis(700));
+        assert!(progress.is_complete());
+        assert_eq!(progress.status_text(), None);
+        assert_eq!(
+            progress.elapsed(StartupStage::Frame),
+            Some(Duration::from_millis(35))
+        );
+        assert_eq!(
+            progress.summary(),
+            "ui 35ms · sources 640ms · history 700ms"
+        );
+    }
+}
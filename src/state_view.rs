@@ -0,0 +1,358 @@
+//! Contract state inspector (`view_state`, Ctrl+K)
+//!
+//! Fetches a contract's key-value storage under an optional key prefix and
+//! shows it as a tree: keys grouped by their leading text (SDK collection
+//! prefixes such as `t` or `STATE`), each entry expandable to its full value.
+//! Keys and values are decoded where the bytes allow it (JSON, text, borsh
+//! strings, little-endian integers), with hex as the fallback. Nodes refuse
+//! `view_state` for contracts with large state; a prefix narrows the request
+//! and entries are paged client-side.
+
+use anyhow::{anyhow, Result};
+use base64::engine::general_purpose::STANDARD as B64;
+use base64::Engine as _;
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// Entries per page
+pub const PAGE_SIZE: usize = 200;
+/// Group label for keys that are plain text throughout
+const TEXT_KEYS: &str = "(text keys)";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StateEntry {
+    pub key: Vec<u8>,
+    pub value: Vec<u8>,
+}
+
+/// `view_state` result: entries sorted by key
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContractState {
+    pub account: String,
+    pub prefix: Vec<u8>,
+    pub block_height: u64,
+    pub entries: Vec<StateEntry>,
+}
+
+/// Parse a `view_state` query result
+pub fn parse_view_state(account: &str, prefix: &[u8], result: &Value) -> Result<ContractState> {
+    let values = result["values"]
+        .as_array()
+        .ok_or_else(|| anyhow!("view_state result has no values"))?;
+    let field = |v: &Value, name: &str| -> Result<Vec<u8>> {
+        let s = v[name].as_str().ok_or_else(|| anyhow!("state entry without {name}"))?;
+        B64.decode(s).map_err(|e| anyhow!("state {name}: {e}"))
+    };
+    let mut entries = values
+        .iter()
+        .map(|v| {
+            Ok(StateEntry {
+                key: field(v, "key")?,
+                value: field(v, "value")?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(ContractState {
+        account: account.to_string(),
+        prefix: prefix.to_vec(),
+        block_height: result["block_height"].as_u64().unwrap_or(0),
+        entries,
+    })
+}
+
+/// Fetch `account`'s state under `prefix` at final finality
+pub async fn fetch(
+    url: &str,
+    account: &str,
+    prefix: &[u8],
+    timeout_ms: u64,
+    auth_token: Option<&str>,
+) -> Result<ContractState> {
+    let body = serde_json::json!({"jsonrpc":"2.0","id":"nearx","method":"query","params":{
+        "request_type": "view_state",
+        "finality": "final",
+        "account_id": account,
+        "prefix_base64": B64.encode(prefix),
+    }});
+    let result = crate::rpc_utils::rpc_post(url, &body, timeout_ms, auth_token)
+        .await
+        .map_err(|e| match format!("{e:#}") {
+            msg if msg.to_lowercase().contains("too large") => {
+                anyhow!("{msg} · narrow it with a key prefix (/)")
+            }
+            _ => e,
+        })?;
+    parse_view_state(account, prefix, &result)
+}
+
+/// Prefix typed in the inspector: `0x…` is hex, anything else the UTF-8 bytes
+pub fn parse_prefix(input: &str) -> Result<Vec<u8>> {
+    let input = input.trim();
+    match input.strip_prefix("0x") {
+        Some(hex) if hex.len() % 2 == 0 => (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+            .collect::<std::result::Result<_, _>>()
+            .map_err(|_| anyhow!("invalid hex prefix '{input}'")),
+        Some(_) => Err(anyhow!("hex prefix needs an even number of digits")),
+        None => Ok(input.as_bytes().to_vec()),
+    }
+}
+
+fn is_text_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"_-.:/@#$".contains(&b)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Borsh `String`: u32 little-endian length, then exactly that much UTF-8
+fn borsh_string(bytes: &[u8]) -> Option<&str> {
+    let len = u32::from_le_bytes(bytes.get(..4)?.try_into().ok()?) as usize;
+    (bytes.len() == 4 + len)
+        .then(|| std::str::from_utf8(&bytes[4..]).ok())
+        .flatten()
+        .filter(|s| !s.chars().any(char::is_control))
+}
+
+/// Leading text of a key, or `TEXT_KEYS` when the whole key is text
+fn group_of(key: &[u8]) -> String {
+    let text = key.iter().take_while(|b| is_text_byte(**b)).count();
+    if text == key.len() {
+        TEXT_KEYS.to_string()
+    } else if text == 0 {
+        "0x".to_string()
+    } else {
+        String::from_utf8_lossy(&key[..text]).into_owned()
+    }
+}
+
+/// `t"alice.near"`, `STATE`, `a0x0100000000000000`
+pub fn decode_key(key: &[u8]) -> String {
+    let text = key.iter().take_while(|b| is_text_byte(**b)).count();
+    let (head, rest) = key.split_at(text);
+    let head = String::from_utf8_lossy(head);
+    if rest.is_empty() {
+        return head.into_owned();
+    }
+    match borsh_string(rest) {
+        Some(s) => format!("{head}\"{s}\""),
+        None => format!("{head}0x{}", hex(rest)),
+    }
+}
+
+/// One-line value summary plus the lines shown when the entry is expanded
+pub fn decode_value(value: &[u8], fmt: crate::json_pretty::JsonFormat) -> (String, Vec<String>) {
+    let raw = vec![
+        format!("hex: {}", hex(value)),
+        format!("base64: {}", B64.encode(value)),
+    ];
+    if value.is_empty() {
+        return ("(empty)".to_string(), raw);
+    }
+    if let Ok(json) = serde_json::from_slice::<Value>(value) {
+        let pretty = crate::json_pretty::pretty(&json, fmt);
+        return (json.to_string(), pretty.lines().map(str::to_string).collect());
+    }
+    if let Some(s) = borsh_string(value) {
+        return (format!("\"{s}\" (borsh string)"), raw);
+    }
+    if let Ok(s) = std::str::from_utf8(value) {
+        if !s.chars().any(char::is_control) {
+            return (format!("\"{s}\""), raw);
+        }
+    }
+    let summary = match value.len() {
+        16 => format!("{} (as u128)", u128::from_le_bytes(value.try_into().unwrap_or_default())),
+        8 => format!("{} (as u64)", u64::from_le_bytes(value.try_into().unwrap_or_default())),
+        4 => format!("{} (as u32)", u32::from_le_bytes(value.try_into().unwrap_or_default())),
+        n => format!("0x{}{} ({n} bytes)", hex(&value[..n.min(24)]), if n > 24 { "…" } else { "" }),
+    };
+    (summary, raw)
+}
+
+/// One visible line of the tree
+#[derive(Clone, Debug, PartialEq)]
+pub enum StateRow {
+    Group { label: String, count: usize, open: bool },
+    Entry { index: usize, key: String, value: String, open: bool },
+    /// Expanded value line of entry `index`
+    Detail { index: usize, text: String },
+}
+
+/// Inspector state: target, fetched entries, page and tree expansion
+#[derive(Clone, Debug, Default)]
+pub struct StateInspector {
+    pub account: String,
+    pub prefix: Vec<u8>,
+    /// `None` while the fetch is in flight
+    pub result: Option<std::result::Result<ContractState, String>>,
+    pub page: usize,
+    pub selection: usize,
+    closed_groups: HashSet<String>,
+    open_entries: HashSet<usize>,
+    /// Prefix being typed (`/`)
+    pub prefix_draft: Option<String>,
+    /// Layout of expanded JSON values (`JSON_INDENT`, `JSON_SORT_KEYS`)
+    pub json_format: crate::json_pretty::JsonFormat,
+}
+
+impl StateInspector {
+    pub fn new(account: String, prefix: Vec<u8>) -> Self {
+        Self {
+            account,
+            prefix,
+            ..Self::default()
+        }
+    }
+
+    /// Prefix as typed: text, or `0x…` when it has non-text bytes
+    pub fn prefix_label(&self) -> String {
+        match std::str::from_utf8(&self.prefix) {
+            Ok(s) if !s.chars().any(char::is_control) => s.to_string(),
+            _ => format!("0x{}", hex(&self.prefix)),
+        }
+    }
+
+    pub fn state(&self) -> Option<&ContractState> {
+        self.result.as_ref().and_then(|r| r.as_ref().ok())
+    }
+
+    pub fn pages(&self) -> usize {
+        self.state().map_or(1, |s| s.entries.len().div_ceil(PAGE_SIZE).max(1))
+    }
+
+    /// Rows of the current page
+    pub fn rows(&self) -> Vec<StateRow> {
+        let Some(state) = self.state() else {
+            return Vec::new();
+        };
+        let start = self.page * PAGE_SIZE;
+        let page = &state.entries[start.min(state.entries.len())..(start + PAGE_SIZE).min(state.entries.len())];
+
+        // Groups in first-key order (entries are sorted, so groups are contiguous)
+        let mut rows = Vec::new();
+        let mut i = 0;
+        while i < page.len() {
+            let label = group_of(&page[i].key);
+            let len = page[i..].iter().take_while(|e| group_of(&e.key) == label).count();
+            let open = !self.closed_groups.contains(&label);
+            rows.push(StateRow::Group {
+                label: label.clone(),
+                count: len,
+                open,
+            });
+            if open {
+                for (offset, entry) in page[i..i + len].iter().enumerate() {
+                    let index = start + i + offset;
+                    let (value, detail) = decode_value(&entry.value, self.json_format);
+                    let entry_open = self.open_entries.contains(&index);
+                    rows.push(StateRow::Entry {
+                        index,
+                        key: decode_key(&entry.key),
+                        value,
+                        open: entry_open,
+                    });
+                    if entry_open {
+                        rows.extend(detail.into_iter().map(|text| StateRow::Detail { index, text }));
+                    }
+                }
+            }
+            i += len;
+        }
+        rows
+    }
+
+    pub fn up(&mut self) {
+        self.selection = self.selection.saturating_sub(1);
+    }
+
+    pub fn down(&mut self) {
+        if self.selection + 1 < self.rows().len() {
+            self.selection += 1;
+        }
+    }
+
+    /// Expand / collapse the selected group or entry
+    pub fn toggle(&mut self) {
+        match self.rows().get(self.selection) {
+            Some(StateRow::Group { label, .. }) => {
+                if !self.closed_groups.remove(label) {
+                    self.closed_groups.insert(label.clone());
+                }
+            }
+            Some(StateRow::Entry { index, .. }) | Some(StateRow::Detail { index, .. }) => {
+                let index = *index;
+                if !self.open_entries.remove(&index) {
+                    self.open_entries.insert(index);
+                }
+                // Stay on the entry when collapsing from one of its lines
+                if let Some(pos) = self
+                    .rows()
+                    .iter()
+                    .position(|r| matches!(r, StateRow::Entry { index: i, .. } if *i == index))
+                {
+                    self.selection = pos;
+                }
+            }
+            None => {}
+        }
+    }
+
+    pub fn next_page(&mut self) {
+        if self.page + 1 < self.pages() {
+            self.page += 1;
+            self.selection = 0;
+        }
+    }
+
+    pub fn prev_page(&mut self) {
+        if self.page > 0 {
+            self.page -= 1;
+            self.selection = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_sdk_style_state_into_groups() {
+        let mut borsh_key = b"t".to_vec();
+        borsh_key.extend(10u32.to_le_bytes());
+        borsh_key.extend(b"alice.near");
+        let result = serde_json::json!({
+            "block_height": 42,
+            "values": [
+                {"key": B64.encode(&borsh_key), "value": B64.encode(1_000u128.to_le_bytes())},
+                {"key": B64.encode(b"STATE"), "value": B64.encode(br#"{"owner":"bob.near"}"#)},
+            ]
+        });
+        let state = parse_view_state("token.near", b"", &result).unwrap();
+        assert_eq!(state.block_height, 42);
+        assert_eq!(decode_key(&state.entries[1].key), "t\"alice.near\"");
+        assert_eq!(decode_value(&state.entries[1].value, Default::default()).0, "1000 (as u128)");
+        assert_eq!(decode_value(&state.entries[0].value, Default::default()).1, ["{", "  \"owner\": \"bob.near\"", "}"]);
+
+        let mut view = StateInspector::new("token.near".into(), Vec::new());
+        view.result = Some(Ok(state));
+        let rows = view.rows();
+        assert!(matches!(&rows[0], StateRow::Group { label, count: 1, open: true } if label == TEXT_KEYS));
+        assert!(matches!(&rows[2], StateRow::Group { label, .. } if label == "t"));
+
+        view.selection = 1;
+        view.toggle(); // expand STATE
+        assert_eq!(view.rows().len(), 7);
+        view.selection = 0;
+        view.toggle(); // collapse the text-keys group
+        assert_eq!(view.rows().len(), 3);
+
+        assert_eq!(parse_prefix("0x7400").unwrap(), vec![0x74, 0]);
+        assert_eq!(parse_prefix("STATE").unwrap(), b"STATE");
+        assert!(parse_prefix("0x7").is_err());
+    }
+}
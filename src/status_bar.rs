@@ -0,0 +1,260 @@
+//! Segment-based status bar
+//!
+//! The footer is a list of segments (text + tone + priority + optional click
+//! action) instead of hand-built spans in each renderer. Core indicators come
+//! from `App::status_segments`; other features and plugins contribute their
+//! own through `StatusBar::set` (`AppEvent::StatusSegment` for plugins), keyed
+//! by id so an update replaces the previous text. When the bar is too narrow,
+//! the lowest-priority segments are dropped first; the rest keep their order.
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+
+#[cfg(target_arch = "wasm32")]
+use web_time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// Separator between segments (both renderers)
+pub const SEPARATOR: &str = " • ";
+
+/// Priority of keyboard hints: first to go on narrow terminals
+pub const PRIORITY_HINT: u8 = 10;
+/// Priority of passive counters (blocks/txs, FPS)
+pub const PRIORITY_LOW: u8 = 30;
+/// Default for contributed segments
+pub const PRIORITY_NORMAL: u8 = 50;
+/// Priority of state the user switched on (owned-only, debug, filtering)
+pub const PRIORITY_HIGH: u8 = 70;
+/// Priority of health and toasts: dropped last
+pub const PRIORITY_CRITICAL: u8 = 90;
+
+/// Semantic color; each renderer maps it onto its theme
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum Tone {
+    #[default]
+    Plain,
+    Muted,
+    Accent,
+    Success,
+    Warn,
+    Error,
+    Debug,
+}
+
+/// Key sent when a segment is clicked (same shape as `UiAction::Key`)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SegmentAction {
+    pub code: String,
+    #[serde(default)]
+    pub ctrl: bool,
+    #[serde(default)]
+    pub shift: bool,
+}
+
+impl SegmentAction {
+    pub fn key(code: &str) -> Self {
+        Self {
+            code: code.to_string(),
+            ctrl: false,
+            shift: false,
+        }
+    }
+
+    pub fn ctrl(code: &str) -> Self {
+        Self {
+            ctrl: true,
+            ..Self::key(code)
+        }
+    }
+
+    pub fn shift(code: &str) -> Self {
+        Self {
+            shift: true,
+            ..Self::key(code)
+        }
+    }
+}
+
+/// One status bar segment
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct StatusSegment {
+    /// Stable id ("core.source.rpc", "plugin:tx-analyzer"); setting the same id replaces
+    pub id: String,
+    /// Key shown before the text in the accent color ("Ctrl+F" in "Ctrl+F search")
+    #[serde(default)]
+    pub key: Option<String>,
+    pub text: String,
+    #[serde(default)]
+    pub tone: Tone,
+    /// Higher survives longer when the bar overflows
+    #[serde(default = "default_priority")]
+    pub priority: u8,
+    #[serde(default)]
+    pub action: Option<SegmentAction>,
+    /// Hover text (web) with detail that doesn't fit in the bar
+    #[serde(default)]
+    pub tooltip: Option<String>,
+}
+
+fn default_priority() -> u8 {
+    PRIORITY_NORMAL
+}
+
+impl StatusSegment {
+    pub fn new(id: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            key: None,
+            text: text.into(),
+            tone: Tone::Plain,
+            priority: PRIORITY_NORMAL,
+            action: None,
+            tooltip: None,
+        }
+    }
+
+    /// Keyboard hint: `key` in the accent color, then `text`
+    pub fn hint(key: &str, text: &str) -> Self {
+        Self {
+            key: Some(key.to_string()),
+            priority: PRIORITY_HINT,
+            ..Self::new(format!("hint.{key}"), text)
+        }
+    }
+
+    pub fn tone(mut self, tone: Tone) -> Self {
+        self.tone = tone;
+        self
+    }
+
+    pub fn priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn action(mut self, action: SegmentAction) -> Self {
+        self.action = Some(action);
+        self
+    }
+
+    pub fn tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Display width in cells (segment glyphs are all single-width)
+    pub fn width(&self) -> usize {
+        let key = self.key.as_ref().map_or(0, |k| k.chars().count() + 1);
+        key + self.text.chars().count()
+    }
+}
+
+/// Segments contributed from outside the core (features, plugins)
+#[derive(Clone, Debug, Default)]
+pub struct StatusBar {
+    contributed: Vec<(StatusSegment, Option<Instant>)>,
+}
+
+impl StatusBar {
+    /// Add or replace (by id) a segment; `ttl` drops it automatically
+    pub fn set(&mut self, now: Instant, segment: StatusSegment, ttl: Option<Duration>) {
+        let expires = ttl.map(|t| now + t);
+        match self
+            .contributed
+            .iter_mut()
+            .find(|(s, _)| s.id == segment.id)
+        {
+            Some(slot) => *slot = (segment, expires),
+            None => self.contributed.push((segment, expires)),
+        }
+    }
+
+    pub fn remove(&mut self, id: &str) {
+        self.contributed.retain(|(s, _)| s.id != id);
+    }
+
+    /// Live contributed segments, in the order they were first set
+    pub fn segments(&self, now: Instant) -> impl Iterator<Item = &StatusSegment> {
+        self.contributed
+            .iter()
+            .filter(move |(_, expires)| expires.is_none_or(|at| now < at))
+            .map(|(s, _)| s)
+    }
+}
+
+/// Indices of the segments that fit in `width` cells (joined by `SEPARATOR`),
+/// dropping the lowest priority first (the later one on ties). Order is kept.
+pub fn fit(segments: &[StatusSegment], width: usize) -> Vec<usize> {
+    let sep = SEPARATOR.chars().count();
+    let mut keep: Vec<usize> = (0..segments.len()).collect();
+    let total = |keep: &[usize]| {
+        keep.iter().map(|&i| segments[i].width()).sum::<usize>()
+            + sep * keep.len().saturating_sub(1)
+    };
+    while !keep.is_empty() && total(&keep) > width {
+        let drop = keep
+            .iter()
+            .enumerate()
+            .min_by_key(|(pos, &i)| (segments[i].priority, std::cmp::Reverse(*pos)))
+            .map(|(pos, _)| pos)
+            .unwrap_or(0);
+        keep.remove(drop);
+    }
+    keep
+}
+
+/// Which kept segment (index into `segments`) covers column `col` of the bar
+pub fn hit_test(segments: &[StatusSegment], kept: &[usize], col: usize) -> Option<usize> {
+    let sep = SEPARATOR.chars().count();
+    let mut x = 0;
+    for &i in kept {
+        let w = segments[i].width();
+        if col >= x && col < x + w {
+            return Some(i);
+        }
+        x += w + sep;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overflow_drops_lowest_priority_and_keeps_order() {
+        let segs = vec![
+            StatusSegment::hint("q", "quit"),
+            StatusSegment::new("rpc", "● RPC").priority(PRIORITY_CRITICAL),
+            StatusSegment::new("fps", "FPS 30").priority(PRIORITY_LOW),
+            StatusSegment::hint("/", "filter"),
+        ];
+        assert_eq!(fit(&segs, 200), vec![0, 1, 2, 3]);
+        // "q quit • ● RPC • FPS 30" = 23 cells: the later hint goes first
+        assert_eq!(fit(&segs, 23), vec![0, 1, 2]);
+        assert_eq!(fit(&segs, 14), vec![1, 2]);
+        assert_eq!(fit(&segs, 3), Vec::<usize>::new());
+
+        let kept = fit(&segs, 23);
+        assert_eq!(hit_test(&segs, &kept, 0), Some(0));
+        assert_eq!(hit_test(&segs, &kept, 7), None); // separator
+        assert_eq!(hit_test(&segs, &kept, 9), Some(1));
+
+        let t0 = Instant::now();
+        let mut bar = StatusBar::default();
+        bar.set(t0, StatusSegment::new("p", "one"), None);
+        bar.set(
+            t0,
+            StatusSegment::new("p", "two"),
+            Some(Duration::from_secs(5)),
+        );
+        let texts: Vec<_> = bar.segments(t0).map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["two"]);
+        assert_eq!(bar.segments(t0 + Duration::from_secs(6)).count(), 0);
+    }
+}
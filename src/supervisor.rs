@@ -0,0 +1,240 @@
+//! Background task supervision (native)
+//!
+//! Long-running tasks (block sources, archival fetch, watchers, pollers) are
+//! spawned through a `Supervisor` and stop cooperatively when its
+//! `CancellationToken` fires: the task's future is dropped at its next await
+//! point instead of being aborted from outside mid-write. Supervisors nest
+//! (`child`), so one group (e.g. the block sources, respawned on an endpoint
+//! change) can be cancelled on its own while the root still reaches it.
+//!
+//! On quit the binary shuts groups down in order (producers first, then
+//! watchers) and drains the History writer last, all under one deadline;
+//! a task that hasn't stopped by then is aborted and named in the log.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+/// Budget for the whole shutdown sequence (tasks, then the History flush)
+pub const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    notify: Notify,
+    children: Mutex<Vec<CancellationToken>>,
+}
+
+/// Cloneable cancellation signal; cancelling a token also cancels its children
+#[derive(Clone, Default)]
+pub struct CancellationToken(Arc<Inner>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Token cancelled together with this one (but not the other way round)
+    pub fn child_token(&self) -> Self {
+        let child = Self::new();
+        if self.is_cancelled() {
+            child.cancel();
+        } else if let Ok(mut children) = self.0.children.lock() {
+            children.retain(|c| !c.is_cancelled());
+            children.push(child.clone());
+        }
+        child
+    }
+
+    pub fn cancel(&self) {
+        if self.0.cancelled.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        self.0.notify.notify_waiters();
+        let children = self
+            .0
+            .children
+            .lock()
+            .map(|mut c| std::mem::take(&mut *c))
+            .unwrap_or_default();
+        for child in children {
+            child.cancel();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Resolves once the token is cancelled
+    pub async fn cancelled(&self) {
+        loop {
+            let notified = self.0.notify.notified();
+            tokio::pin!(notified);
+            // Register before checking, so a cancel in between isn't missed
+            notified.as_mut().enable();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Run `fut` until it finishes (`Some`) or the token is cancelled (`None`)
+    pub async fn run_until_cancelled<F: Future>(&self, fut: F) -> Option<F::Output> {
+        tokio::select! {
+            biased;
+            _ = self.cancelled() => None,
+            out = fut => Some(out),
+        }
+    }
+}
+
+/// A named group of background tasks sharing one cancellation token
+pub struct Supervisor {
+    name: &'static str,
+    /// Parent's token (`restart` hangs the fresh token under it again)
+    parent: Option<CancellationToken>,
+    token: CancellationToken,
+    tasks: Vec<(&'static str, JoinHandle<()>)>,
+}
+
+impl Supervisor {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            parent: None,
+            token: CancellationToken::new(),
+            tasks: Vec::new(),
+        }
+    }
+
+    /// Nested group, cancelled with this one
+    pub fn child(&self, name: &'static str) -> Self {
+        Self {
+            name,
+            parent: Some(self.token.clone()),
+            token: self.token.child_token(),
+            tasks: Vec::new(),
+        }
+    }
+
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+
+    /// Spawn `fut`, dropped at its next await point once the group is cancelled
+    pub fn spawn<F>(&mut self, name: &'static str, fut: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.tasks.retain(|(_, handle)| !handle.is_finished());
+        let token = self.token.clone();
+        let handle = tokio::spawn(async move {
+            token.run_until_cancelled(fut).await;
+        });
+        self.tasks.push((name, handle));
+    }
+
+    /// Tasks still running
+    pub fn running(&self) -> Vec<&'static str> {
+        self.tasks
+            .iter()
+            .filter(|(_, handle)| !handle.is_finished())
+            .map(|(name, _)| *name)
+            .collect()
+    }
+
+    /// Cancel the running tasks without waiting and carry on with a fresh
+    /// token (respawning the group, e.g. on an endpoint change)
+    pub fn restart(&mut self) {
+        self.token.cancel();
+        self.tasks.clear();
+        self.token = match &self.parent {
+            Some(parent) => parent.child_token(),
+            None => CancellationToken::new(),
+        };
+    }
+
+    /// Cancel the group and wait for its tasks until `deadline`; stragglers are
+    /// aborted and returned by name
+    pub async fn shutdown(&mut self, deadline: Instant) -> Vec<&'static str> {
+        self.token.cancel();
+        let mut aborted = Vec::new();
+        for (name, mut handle) in self.tasks.drain(..) {
+            if tokio::time::timeout_at(deadline, &mut handle)
+                .await
+                .is_err()
+            {
+                handle.abort();
+                aborted.push(name);
+            }
+        }
+        if !aborted.is_empty() {
+            log::warn!(
+                "[shutdown] {}: aborted after timeout: {}",
+                self.name,
+                aborted.join(", ")
+            );
+        }
+        aborted
+    }
+}
+
+impl Drop for Supervisor {
+    fn drop(&mut self) {
+        self.token.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn cancels_children_and_aborts_stragglers() {
+        let mut root = Supervisor::new("root");
+        let mut sources = root.child("sources");
+
+        // Cooperative: dropped at its await point, never reports
+        let (done_tx, mut done_rx) = tokio::sync::mpsc::unbounded_channel::<()>();
+        sources.spawn("poller", async move {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            let _ = done_tx.send(());
+        });
+        // Stuck inside one poll: only the deadline gets rid of it
+        let (started_tx, started_rx) = std::sync::mpsc::channel();
+        root.spawn("stubborn", async move {
+            let _ = started_tx.send(());
+            std::thread::sleep(Duration::from_millis(200));
+        });
+        started_rx.recv().unwrap();
+
+        // Restarting a child cancels its tasks, not the parent
+        let old = sources.token().clone();
+        sources.restart();
+        assert!(old.is_cancelled());
+        assert!(!sources.token().is_cancelled());
+        assert!(!root.token().is_cancelled());
+        assert_eq!(root.running(), vec!["stubborn"]);
+
+        let aborted = root
+            .shutdown(Instant::now() + Duration::from_millis(50))
+            .await;
+        assert_eq!(aborted, vec!["stubborn"]);
+        assert!(done_rx.recv().await.is_none());
+        // The restarted child still hangs under the root
+        assert!(sources.token().is_cancelled());
+
+        // Children of a cancelled token start cancelled
+        assert!(root.child("late").token().is_cancelled());
+        tokio::time::timeout(Duration::from_millis(10), root.token().cancelled())
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,192 @@
+//! Contract method tailing
+//!
+//! Matches function calls to a single `contract` / `method` pair across incoming
+//! blocks. Used by the native-host bridge (`TailMethod`) to stream live calls to
+//! browser-extension dashboards.
+
+use crate::near_args::DecodedArgs;
+use crate::types::{ActionSummary, BlockRow};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// Method name that matches every method on the contract
+pub const ANY_METHOD: &str = "*";
+
+/// A tail subscription: one contract, one method (or `*`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodTail {
+    pub contract: String,
+    pub method: String,
+}
+
+/// A single matching call, ready to push over a bridge
+#[derive(Debug, Clone, Serialize)]
+pub struct TailedCall {
+    pub block_height: u64,
+    pub block_hash: String,
+    pub tx_hash: String,
+    pub signer_id: Option<String>,
+    pub contract: String,
+    pub method: String,
+    /// Decoded args (JSON when possible, string otherwise)
+    pub args: Value,
+    pub gas: u64,
+    /// Yoctonear as a string (u128 is not JSON-safe)
+    pub deposit: String,
+    /// True when the call was wrapped in a meta-transaction (Delegate)
+    pub delegated: bool,
+}
+
+impl MethodTail {
+    pub fn new(contract: impl Into<String>, method: impl Into<String>) -> Self {
+        Self {
+            contract: contract.into(),
+            method: method.into(),
+        }
+    }
+
+    fn method_matches(&self, method_name: &str) -> bool {
+        self.method == ANY_METHOD || self.method == method_name
+    }
+
+    /// Collect every matching call in a block, in transaction order
+    pub fn calls_in_block(&self, block: &BlockRow) -> Vec<TailedCall> {
+        let mut out = Vec::new();
+        for tx in &block.transactions {
+            let Some(actions) = tx.actions.as_ref() else {
+                continue;
+            };
+            let receiver = tx.receiver_id.as_deref().unwrap_or_default();
+
+            for action in actions {
+                match action {
+                    ActionSummary::FunctionCall { .. } if receiver == self.contract => {
+                        self.push_call(&mut out, block, &tx.hash, tx.signer_id.clone(), action, false);
+                    }
+                    ActionSummary::Delegate {
+                        sender_id,
+                        receiver_id,
+                        actions: inner,
+                    } if *receiver_id == self.contract => {
+                        for inner_action in inner {
+                            self.push_call(
+                                &mut out,
+                                block,
+                                &tx.hash,
+                                Some(sender_id.clone()),
+                                inner_action,
+                                true,
+                            );
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        out
+    }
+
+    fn push_call(
+        &self,
+        out: &mut Vec<TailedCall>,
+        block: &BlockRow,
+        tx_hash: &str,
+        signer_id: Option<String>,
+        action: &ActionSummary,
+        delegated: bool,
+    ) {
+        let ActionSummary::FunctionCall {
+            method_name,
+            args_decoded,
+            gas,
+            deposit,
+            ..
+        } = action
+        else {
+            return;
+        };
+        if !self.method_matches(method_name) {
+            return;
+        }
+        out.push(TailedCall {
+            block_height: block.height,
+            block_hash: block.hash.clone(),
+            tx_hash: tx_hash.to_string(),
+            signer_id,
+            contract: self.contract.clone(),
+            method: method_name.clone(),
+            args: args_to_value(args_decoded),
+            gas: *gas,
+            deposit: deposit.to_string(),
+            delegated,
+        });
+    }
+}
+
+/// Flatten decoded args into plain JSON for bridge consumers
+fn args_to_value(args: &DecodedArgs) -> Value {
+    match args {
+        DecodedArgs::Json(v) => v.clone(),
+        DecodedArgs::Text(t) => json!(t),
+        DecodedArgs::Bytes { preview, .. } => json!(format!("[binary: {}]", preview)),
+        DecodedArgs::Empty => json!({}),
+        DecodedArgs::Error(e) => json!(format!("<decode error: {}>", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{fixtures, TxLite};
+
+    fn call(method: &str) -> ActionSummary {
+        ActionSummary::FunctionCall {
+            method_name: method.to_string(),
+            _args_base64: String::new(),
+            args_decoded: DecodedArgs::Json(json!({"amount": "1"})),
+            gas: 30_000_000_000_000,
+            deposit: 1,
+        }
+    }
+
+    fn block(txs: Vec<TxLite>) -> BlockRow {
+        fixtures::block(100, txs)
+    }
+
+    fn tx(hash: &str, receiver: &str, actions: Vec<ActionSummary>) -> TxLite {
+        TxLite {
+            actions: Some(actions),
+            ..fixtures::tx(hash, "alice.near", receiver)
+        }
+    }
+
+    #[test]
+    fn matches_contract_and_method_only() {
+        let b = block(vec![
+            tx("t1", "token.near", vec![call("ft_transfer"), call("storage_deposit")]),
+            tx("t2", "other.near", vec![call("ft_transfer")]),
+        ]);
+        let calls = MethodTail::new("token.near", "ft_transfer").calls_in_block(&b);
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].tx_hash, "t1");
+        assert_eq!(calls[0].args, json!({"amount": "1"}));
+        assert_eq!(calls[0].deposit, "1");
+    }
+
+    #[test]
+    fn wildcard_and_delegate() {
+        let b = block(vec![tx(
+            "t1",
+            "relayer.near",
+            vec![ActionSummary::Delegate {
+                sender_id: "bob.near".into(),
+                receiver_id: "token.near".into(),
+                actions: vec![call("ft_transfer"), call("ft_burn")],
+            }],
+        )]);
+        let calls = MethodTail::new("token.near", ANY_METHOD).calls_in_block(&b);
+        assert_eq!(calls.len(), 2);
+        assert!(calls.iter().all(|c| c.delegated));
+        assert_eq!(calls[0].signer_id.as_deref(), Some("bob.near"));
+    }
+}
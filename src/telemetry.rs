@@ -0,0 +1,155 @@
+//! Opt-in usage telemetry
+//!
+//! Counts which features get used (panes, filter fields, overlays, export
+//! formats), never what they were used on: counter names come from a fixed
+//! vocabulary of `&'static str`, so no account, hash or filter text can end up
+//! in the report. Counting only happens with `TELEMETRY=true`, and the report
+//! is only sent when `TELEMETRY_URL` is also set (on exit, then the counters
+//! reset). `Ctrl+T` previews the exact JSON and purges it with one key.
+
+use std::collections::BTreeMap;
+
+use serde_json::{json, Value};
+
+use crate::filter::FilterField;
+
+/// Bumped when the report's shape changes
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, Default)]
+pub struct Telemetry {
+    enabled: bool,
+    /// Where the report goes (None = counted and previewed, never sent)
+    url: Option<String>,
+    /// Start of the counting period (unix ms; only the day is reported)
+    since_ms: Option<i64>,
+    counters: BTreeMap<String, u64>,
+}
+
+impl Telemetry {
+    pub fn new(enabled: bool, url: Option<String>) -> Self {
+        Self {
+            enabled,
+            url,
+            ..Self::default()
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn url(&self) -> Option<&str> {
+        self.url.as_deref()
+    }
+
+    /// Count one use of a feature (no-op unless opted in)
+    pub fn record(&mut self, counter: &'static str, now_ms: i64) {
+        if !self.enabled {
+            return;
+        }
+        self.since_ms.get_or_insert(now_ms);
+        *self.counters.entry(counter.to_string()).or_default() += 1;
+    }
+
+    /// Counters kept from an earlier session (History)
+    pub fn restore(&mut self, since_ms: i64, counters: BTreeMap<String, u64>) {
+        self.since_ms = Some(self.since_ms.map_or(since_ms, |s| s.min(since_ms)));
+        for (name, n) in counters {
+            *self.counters.entry(name).or_default() += n;
+        }
+    }
+
+    pub fn since_ms(&self) -> Option<i64> {
+        self.since_ms
+    }
+
+    pub fn counters(&self) -> &BTreeMap<String, u64> {
+        &self.counters
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counters.is_empty()
+    }
+
+    /// Drop everything counted so far
+    pub fn purge(&mut self) {
+        self.since_ms = None;
+        self.counters.clear();
+    }
+
+    /// The report exactly as it would be sent
+    pub fn payload(&self) -> Value {
+        let since_day = self
+            .since_ms
+            .and_then(chrono::DateTime::from_timestamp_millis)
+            .map(|t| t.format("%Y-%m-%d").to_string());
+        json!({
+            "schema": SCHEMA_VERSION,
+            "app_version": env!("CARGO_PKG_VERSION"),
+            "platform": if cfg!(target_arch = "wasm32") { "web" } else { "native" },
+            "since_day": since_day,
+            "counters": self.counters,
+        })
+    }
+}
+
+/// Counter for a filter field used in an applied filter
+pub fn filter_counter(field: FilterField) -> &'static str {
+    match field {
+        FilterField::Signer => "filter.signer",
+        FilterField::Receiver => "filter.receiver",
+        FilterField::Acct => "filter.acct",
+        FilterField::Action => "filter.action",
+        FilterField::Method => "filter.method",
+        FilterField::Raw => "filter.raw",
+        FilterField::Hash => "filter.hash",
+        FilterField::Free => "filter.free",
+        FilterField::Bridge => "filter.bridge",
+    }
+}
+
+/// POST the report to `url` (native only; the caller resets counters on success)
+#[cfg(feature = "native")]
+pub async fn send(url: &str, payload: &Value) -> anyhow::Result<()> {
+    let resp = reqwest::Client::new()
+        .post(url)
+        .timeout(std::time::Duration::from_secs(3))
+        .json(payload)
+        .send()
+        .await?;
+    resp.error_for_status()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_only_when_enabled_and_reports_day_granularity() {
+        let mut off = Telemetry::new(false, None);
+        off.record("pane.txs", 0);
+        assert!(off.is_empty());
+
+        // 2026-10-17T12:00:00Z
+        let noon = 1_792_238_400_000;
+        let mut t = Telemetry::new(true, None);
+        t.record("pane.txs", noon);
+        t.record("pane.txs", noon + 1);
+        t.restore(
+            noon - 86_400_000,
+            BTreeMap::from([("export.digest_md".to_string(), 2)]),
+        );
+
+        let p = t.payload();
+        assert_eq!(p["since_day"], "2026-10-16");
+        assert_eq!(p["counters"]["pane.txs"], 2);
+        assert_eq!(p["counters"]["export.digest_md"], 2);
+        assert_eq!(p["platform"], "native");
+
+        t.purge();
+        assert!(t.is_empty());
+        assert_eq!(t.payload()["since_day"], Value::Null);
+    }
+}
@@ -4,6 +4,7 @@
 //! - Consistent colors across all deployment targets
 //! - Focus-aware pane backgrounds (csli-dashboard style)
 //! - WCAG AA compliant contrast ratios
+//! - Light, color-vision (deuteranopia/protanopia/tritanopia) and high-contrast variants
 //! - Helpers for both ratatui and egui
 
 pub mod tokens;
@@ -65,6 +66,118 @@ impl Default for Theme {
     }
 }
 
+/// Selectable palette variant (THEME setting)
+///
+/// Every variant keeps the same semantic slots (success/warn/error/selection/focus),
+/// so renderers that read colors from [`Theme`] pick up the remapping automatically.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ThemeVariant {
+    /// Default dark palette
+    #[default]
+    Dark,
+    /// Light palette (default light side when following the OS appearance)
+    Light,
+    /// Red-green safe (green-weak): semantics carried on the blue/orange axis
+    Deuteranopia,
+    /// Red-green safe (red-weak): avoids dark reds, error uses bright amber
+    Protanopia,
+    /// Blue-yellow safe: semantics carried on the red/cyan axis
+    Tritanopia,
+    /// Pure black background, white text, saturated semantic colors (WCAG AAA)
+    HighContrast,
+}
+
+impl ThemeVariant {
+    /// All variants, in settings/cycle order
+    pub const ALL: [ThemeVariant; 6] = [
+        ThemeVariant::Dark,
+        ThemeVariant::Light,
+        ThemeVariant::Deuteranopia,
+        ThemeVariant::Protanopia,
+        ThemeVariant::Tritanopia,
+        ThemeVariant::HighContrast,
+    ];
+
+    /// Stable identifier used by the THEME setting
+    pub fn name(self) -> &'static str {
+        match self {
+            ThemeVariant::Dark => "dark",
+            ThemeVariant::Deuteranopia => "deuteranopia",
+            ThemeVariant::Protanopia => "protanopia",
+            ThemeVariant::Tritanopia => "tritanopia",
+            ThemeVariant::HighContrast => "high-contrast",
+            ThemeVariant::Light => "light",
+        }
+    }
+
+    /// Next variant in cycle order (wraps around)
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|v| *v == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    /// Variant whose palette is exactly `theme` (`None` for a custom palette)
+    pub fn of(theme: &Theme) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|v| Theme::for_variant(*v) == *theme)
+    }
+}
+
+impl std::str::FromStr for ThemeVariant {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "dark" | "default" => Ok(ThemeVariant::Dark),
+            "deuteranopia" | "deutan" => Ok(ThemeVariant::Deuteranopia),
+            "protanopia" | "protan" => Ok(ThemeVariant::Protanopia),
+            "tritanopia" | "tritan" => Ok(ThemeVariant::Tritanopia),
+            "high-contrast" | "high_contrast" | "highcontrast" | "hc" => {
+                Ok(ThemeVariant::HighContrast)
+            }
+            "light" => Ok(ThemeVariant::Light),
+            _ => Err(anyhow::anyhow!(
+                "Invalid theme '{s}'. Valid options: dark, light, deuteranopia, protanopia, tritanopia, high-contrast"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for ThemeVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// OS-level dark/light appearance
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Appearance {
+    Dark,
+    Light,
+}
+
+/// Variant pair used when following the OS appearance (THEME_FOLLOW_SYSTEM)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SystemThemes {
+    pub dark: ThemeVariant,
+    pub light: ThemeVariant,
+}
+
+impl SystemThemes {
+    pub fn variant_for(self, appearance: Appearance) -> ThemeVariant {
+        match appearance {
+            Appearance::Dark => self.dark,
+            Appearance::Light => self.light,
+        }
+    }
+}
+
+impl From<ThemeVariant> for Theme {
+    fn from(variant: ThemeVariant) -> Self {
+        Theme::for_variant(variant)
+    }
+}
+
 impl Rgb {
     /// Convert RGB to CSS hex color string
     pub fn to_css_hex(&self) -> String {
@@ -73,6 +186,113 @@ impl Rgb {
 }
 
 impl Theme {
+    /// Build the palette for a variant
+    pub fn for_variant(variant: ThemeVariant) -> Self {
+        match variant {
+            ThemeVariant::Dark => Theme::default(),
+            ThemeVariant::Deuteranopia => Theme::deuteranopia(),
+            ThemeVariant::Protanopia => Theme::protanopia(),
+            ThemeVariant::Tritanopia => Theme::tritanopia(),
+            ThemeVariant::HighContrast => Theme::high_contrast(),
+            ThemeVariant::Light => Theme::light(),
+        }
+    }
+
+    /// Light palette (GitHub-light proportions, WCAG AA on white)
+    pub fn light() -> Self {
+        Theme {
+            bg: Rgb(0xf6, 0xf8, 0xfa),            // #f6f8fa - backdrop
+            panel: Rgb(0xff, 0xff, 0xff),         // #ffffff - unfocused pane bg
+            panel_alt: Rgb(0xf0, 0xf4, 0xf9),     // #f0f4f9 - focused pane bg
+            text: Rgb(0x1f, 0x23, 0x28),          // #1f2328 - primary text
+            text_dim: Rgb(0x57, 0x60, 0x6a),      // #57606a - secondary text
+            border: Rgb(0x8c, 0x95, 0x9f),        // #8c959f - unfocused borders
+            accent: Rgb(0x09, 0x69, 0xda),        // #0969da - links/highlights
+            accent_strong: Rgb(0xbf, 0x87, 0x00), // #bf8700 - focused borders (amber)
+            sel_bg: Rgb(0xdd, 0xf4, 0xff),        // #ddf4ff - selection background
+            hover_bg: Rgb(0xf3, 0xf4, 0xf6),      // #f3f4f6 - hover background
+            success: Rgb(0x1a, 0x7f, 0x37),       // #1a7f37 - success
+            warn: Rgb(0x9a, 0x67, 0x00),          // #9a6700 - warnings
+            error: Rgb(0xcf, 0x22, 0x2e),         // #cf222e - errors
+            json_bg: Rgb(0xff, 0xff, 0xff),       // #ffffff
+            json_key: Rgb(0x05, 0x50, 0xae),      // #0550ae - blue
+            json_string: Rgb(0x11, 0x63, 0x29),   // #116329 - green
+            json_number: Rgb(0x95, 0x38, 0x00),   // #953800 - burnt orange
+            json_bool: Rgb(0x82, 0x50, 0xdf),     // #8250df - purple
+            json_struct: Rgb(0x24, 0x29, 0x2f),   // #24292f - near black
+        }
+    }
+
+    /// Red-green safe palette for green-weak vision (Okabe-Ito derived)
+    pub fn deuteranopia() -> Self {
+        Theme {
+            success: Rgb(0x56, 0xb4, 0xe9),     // #56b4e9 - sky blue
+            warn: Rgb(0xf0, 0xe4, 0x42),        // #f0e442 - yellow
+            error: Rgb(0xff, 0x8c, 0x42),       // #ff8c42 - vermillion/orange
+            sel_bg: Rgb(0x1a, 0x2c, 0x4a),      // #1a2c4a - blue-tinted selection
+            json_string: Rgb(0xf0, 0xe4, 0x42), // #f0e442 - yellow (was yellow-green)
+            json_number: Rgb(0xcc, 0xa8, 0xff), // #cca8ff - lilac (was orange)
+            json_bool: Rgb(0xff, 0xa6, 0x4d),   // #ffa64d - orange (was light blue)
+            ..Theme::default()
+        }
+    }
+
+    /// Red-green safe palette for red-weak vision (no dark reds)
+    pub fn protanopia() -> Self {
+        Theme {
+            success: Rgb(0x56, 0xb4, 0xe9),     // #56b4e9 - sky blue
+            warn: Rgb(0xf0, 0xe4, 0x42),        // #f0e442 - yellow
+            error: Rgb(0xff, 0xa3, 0x1a),       // #ffa31a - bright amber (reds read dark)
+            sel_bg: Rgb(0x1a, 0x2c, 0x4a),      // #1a2c4a - blue-tinted selection
+            json_string: Rgb(0xf0, 0xe4, 0x42), // #f0e442 - yellow
+            json_number: Rgb(0xcc, 0xa8, 0xff), // #cca8ff - lilac
+            json_bool: Rgb(0xff, 0xb8, 0x66),   // #ffb866 - light amber
+            ..Theme::default()
+        }
+    }
+
+    /// Blue-yellow safe palette for tritan vision (red/cyan axis)
+    pub fn tritanopia() -> Self {
+        Theme {
+            accent: Rgb(0x4d, 0xd0, 0xe1),        // #4dd0e1 - cyan
+            accent_strong: Rgb(0xff, 0x6f, 0xb5), // #ff6fb5 - pink focus (yellow reads pale)
+            success: Rgb(0x2e, 0xd3, 0xc0),       // #2ed3c0 - teal
+            warn: Rgb(0xff, 0xa3, 0xd1),          // #ffa3d1 - light pink
+            error: Rgb(0xff, 0x4d, 0x4d),         // #ff4d4d - red
+            sel_bg: Rgb(0x3a, 0x1e, 0x2e),        // #3a1e2e - rose-tinted selection
+            json_key: Rgb(0x4d, 0xd0, 0xe1),      // #4dd0e1 - cyan
+            json_string: Rgb(0xff, 0xa3, 0xd1),   // #ffa3d1 - pink
+            json_number: Rgb(0xff, 0x8a, 0x80),   // #ff8a80 - salmon
+            json_bool: Rgb(0xb2, 0xeb, 0xf2),     // #b2ebf2 - pale cyan
+            ..Theme::default()
+        }
+    }
+
+    /// Maximum contrast palette (WCAG AAA for text, saturated semantics)
+    pub fn high_contrast() -> Self {
+        Theme {
+            bg: Rgb(0x00, 0x00, 0x00),            // #000000
+            panel: Rgb(0x00, 0x00, 0x00),         // #000000
+            panel_alt: Rgb(0x12, 0x12, 0x12),     // #121212
+            text: Rgb(0xff, 0xff, 0xff),          // #ffffff
+            text_dim: Rgb(0xd0, 0xd0, 0xd0),      // #d0d0d0
+            border: Rgb(0xa0, 0xa0, 0xa0),        // #a0a0a0
+            accent: Rgb(0x00, 0xff, 0xff),        // #00ffff
+            accent_strong: Rgb(0xff, 0xff, 0x00), // #ffff00
+            sel_bg: Rgb(0x00, 0x3a, 0x8c),        // #003a8c
+            hover_bg: Rgb(0x1a, 0x1a, 0x1a),      // #1a1a1a
+            success: Rgb(0x00, 0xff, 0x66),       // #00ff66
+            warn: Rgb(0xff, 0xff, 0x00),          // #ffff00
+            error: Rgb(0xff, 0x55, 0x55),         // #ff5555
+            json_bg: Rgb(0x00, 0x00, 0x00),       // #000000
+            json_key: Rgb(0x00, 0xff, 0xff),      // #00ffff
+            json_string: Rgb(0xb4, 0xff, 0x00),   // #b4ff00
+            json_number: Rgb(0xff, 0xb0, 0x00),   // #ffb000
+            json_bool: Rgb(0x80, 0xc0, 0xff),     // #80c0ff
+            json_struct: Rgb(0xff, 0xff, 0xff),   // #ffffff
+        }
+    }
+
     /// Export theme as CSS custom properties for web/Tauri
     ///
     /// Returns (var_name, hex_value) pairs that should be set on document.documentElement.style
@@ -103,12 +323,27 @@ impl Theme {
     }
 }
 
+// ---------- Monochrome mode (NO_COLOR / MONOCHROME) ----------
+
+static MONOCHROME: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Switch renderers to monochrome (set once at startup from `Config::monochrome`)
+pub fn set_monochrome(on: bool) {
+    MONOCHROME.store(on, std::sync::atomic::Ordering::Relaxed);
+}
+
+/// True when colors are disabled and emphasis is carried by bold/dim/underline only
+pub fn monochrome() -> bool {
+    MONOCHROME.load(std::sync::atomic::Ordering::Relaxed)
+}
+
 // ---------- Ratatui helpers (native TUI) ----------
 
 #[cfg(feature = "native")]
 #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
 pub mod ratatui_helpers {
-    use super::{Rgb, Theme};
+    use super::{monochrome, Rgb, Theme};
+    use ratatui::buffer::Buffer;
     use ratatui::style::{Color, Modifier, Style};
 
     /// Convert theme RGB to ratatui Color
@@ -131,6 +366,17 @@ pub mod ratatui_helpers {
 
     /// Generate ratatui styles from theme
     pub fn styles(t: &Theme) -> Styles {
+        if monochrome() {
+            return Styles {
+                border: Style::default().add_modifier(Modifier::DIM),
+                border_focus: Style::default().add_modifier(Modifier::BOLD),
+                title: Style::default().add_modifier(Modifier::BOLD),
+                title_focus: Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                text: Style::default(),
+                text_dim: Style::default().add_modifier(Modifier::DIM),
+                selected: Style::default().add_modifier(Modifier::REVERSED),
+            };
+        }
         Styles {
             border: Style::default().fg(c(t.border)),
             border_focus: Style::default().fg(c(t.accent_strong)),
@@ -143,6 +389,33 @@ pub mod ratatui_helpers {
             selected: Style::default().bg(c(t.sel_bg)).fg(c(t.text)),
         }
     }
+
+    /// Strip every color from a rendered frame, translating the theme's semantic
+    /// colors into modifiers: focus/accents/warnings → bold, borders and
+    /// secondary text → dim, selection backgrounds → reversed. ANSI fallbacks
+    /// used on non-truecolor terminals map the same way.
+    pub fn monochrome_buffer(buf: &mut Buffer, t: &Theme) {
+        let bold = [t.accent, t.accent_strong, t.warn, t.error].map(c);
+        let dim = [t.border, t.text_dim].map(c);
+        for cell in buf.content.iter_mut() {
+            let mut modifier = cell.modifier;
+            match cell.fg {
+                Color::Yellow | Color::LightYellow | Color::Red | Color::LightRed => {
+                    modifier |= Modifier::BOLD
+                }
+                Color::DarkGray | Color::Gray => modifier |= Modifier::DIM,
+                fg if bold.contains(&fg) => modifier |= Modifier::BOLD,
+                fg if dim.contains(&fg) => modifier |= Modifier::DIM,
+                _ => {}
+            }
+            if cell.bg == c(t.sel_bg) || cell.bg == Color::Yellow {
+                modifier |= Modifier::REVERSED;
+            }
+            cell.modifier = modifier;
+            cell.fg = Color::Reset;
+            cell.bg = Color::Reset;
+        }
+    }
 }
 
 // ---------- Contrast calculation (for testing) ----------
@@ -232,6 +505,94 @@ mod tests {
         );
     }
 
+    #[test]
+    fn variants_meet_wcag_aa() {
+        for v in ThemeVariant::ALL {
+            let t = Theme::for_variant(v);
+            for (name, fg, bg, min) in [
+                ("text/panel", t.text, t.panel, 4.5),
+                ("text/sel_bg", t.text, t.sel_bg, 4.5),
+                ("success/panel", t.success, t.panel, 4.5),
+                ("warn/panel", t.warn, t.panel, 4.5),
+                ("error/panel", t.error, t.panel, 4.5),
+                ("focus/panel", t.accent_strong, t.panel, 3.0),
+                ("json_string/json_bg", t.json_string, t.json_bg, 7.0),
+            ] {
+                let ratio = contrast_ratio(fg, bg);
+                assert!(ratio >= min, "{v}: {name} got {ratio:.2}:1, need >={min}:1");
+            }
+        }
+    }
+
+    #[test]
+    fn system_themes_pick_by_appearance() {
+        let pair = SystemThemes {
+            dark: ThemeVariant::Deuteranopia,
+            light: ThemeVariant::Light,
+        };
+        assert_eq!(pair.variant_for(Appearance::Dark), ThemeVariant::Deuteranopia);
+        assert_eq!(pair.variant_for(Appearance::Light), ThemeVariant::Light);
+        assert!(rel_luminance(Theme::light().panel) > rel_luminance(Theme::light().text));
+    }
+
+    #[test]
+    fn high_contrast_is_aaa() {
+        let t = Theme::high_contrast();
+        assert!(contrast_ratio(t.text, t.panel) >= 7.0);
+        assert!(contrast_ratio(t.text, t.sel_bg) >= 7.0);
+        assert!(contrast_ratio(t.text_dim, t.panel) >= 7.0);
+    }
+
+    #[test]
+    fn variant_names_round_trip() {
+        for v in ThemeVariant::ALL {
+            assert_eq!(v.name().parse::<ThemeVariant>().unwrap(), v);
+        }
+        assert_eq!("".parse::<ThemeVariant>().unwrap(), ThemeVariant::Dark);
+        assert!("nord".parse::<ThemeVariant>().is_err());
+        assert_eq!(ThemeVariant::HighContrast.next(), ThemeVariant::Dark);
+    }
+
+    #[test]
+    fn palettes_identify_their_variant() {
+        // Cycling at runtime starts from the active palette, so none may collide
+        for v in ThemeVariant::ALL {
+            assert_eq!(ThemeVariant::of(&Theme::for_variant(v)), Some(v));
+        }
+        let custom = Theme {
+            accent: Rgb(1, 2, 3),
+            ..Theme::default()
+        };
+        assert_eq!(ThemeVariant::of(&custom), None);
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn monochrome_buffer_maps_semantics_to_modifiers() {
+        use ratatui::buffer::Buffer;
+        use ratatui::layout::Rect;
+        use ratatui::style::{Color, Modifier, Style};
+        use ratatui_helpers::{c, monochrome_buffer};
+
+        let t = Theme::default();
+        let mut buf = Buffer::empty(Rect::new(0, 0, 4, 1));
+        buf.set_string(0, 0, "a", Style::default().fg(c(t.accent_strong)));
+        buf.set_string(1, 0, "b", Style::default().fg(c(t.border)));
+        buf.set_string(2, 0, "c", Style::default().bg(c(t.sel_bg)).fg(c(t.text)));
+        buf.set_string(3, 0, "d", Style::default().bg(c(t.panel)).fg(c(t.text)));
+        monochrome_buffer(&mut buf, &t);
+
+        let mods: Vec<Modifier> = buf.content.iter().map(|cell| cell.modifier).collect();
+        assert_eq!(
+            mods,
+            [Modifier::BOLD, Modifier::DIM, Modifier::REVERSED, Modifier::empty()]
+        );
+        assert!(buf
+            .content
+            .iter()
+            .all(|cell| cell.fg == Color::Reset && cell.bg == Color::Reset));
+    }
+
     #[test]
     fn wcag_focus_border_visible() {
         let t = Theme::default();
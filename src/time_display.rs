@@ -0,0 +1,172 @@
+//! Timezone and timestamp format shared by every view
+//!
+//! Block rows, Details, search / feed / browser lists, the toast history and
+//! Markdown exports all format times through one `TimeDisplay`, so switching
+//! the zone with `T` (UTC → local → custom offset) changes them together.
+//! Lists show the time of day; rows and exports show the full `TIME_FORMAT`
+//! followed by the zone.
+
+use anyhow::{anyhow, Result};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, FixedOffset, Local, Utc};
+
+/// Full timestamp format when `TIME_FORMAT` is unset
+pub const DEFAULT_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Time of day in lists
+const TIME_OF_DAY: &str = "%H:%M:%S";
+
+/// Zone times are shown in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TimeZonePref {
+    Utc,
+    #[default]
+    Local,
+    /// Fixed offset east of UTC, in seconds
+    Offset(i32),
+}
+
+impl TimeZonePref {
+    pub fn label(self) -> String {
+        match self {
+            TimeZonePref::Utc => "UTC".to_string(),
+            TimeZonePref::Local => "local".to_string(),
+            TimeZonePref::Offset(secs) => offset_label(secs),
+        }
+    }
+}
+
+/// `+05:30` / `-08:00`
+fn offset_label(secs: i32) -> String {
+    let sign = if secs < 0 { '-' } else { '+' };
+    let m = secs.unsigned_abs() / 60;
+    format!("{sign}{:02}:{:02}", m / 60, m % 60)
+}
+
+/// Parse `TIMEZONE`: `utc`, `local`, or an offset (`+05:30`, `-8`, `UTC+2`)
+pub fn parse_zone(s: &str) -> Result<TimeZonePref> {
+    let s = s.trim();
+    let lower = s.to_ascii_lowercase();
+    match lower.as_str() {
+        "utc" | "z" => return Ok(TimeZonePref::Utc),
+        "local" => return Ok(TimeZonePref::Local),
+        _ => {}
+    }
+    let off = lower.strip_prefix("utc").unwrap_or(&lower);
+    let invalid = || anyhow!("Invalid TIMEZONE '{s}'. Expected utc, local, or an offset like +05:30");
+    let (sign, rest) = match off.as_bytes().first() {
+        Some(b'+') => (1, &off[1..]),
+        Some(b'-') => (-1, &off[1..]),
+        _ => return Err(invalid()),
+    };
+    let (h, m) = rest.split_once(':').unwrap_or((rest, "0"));
+    let (h, m): (i32, i32) = (h.parse().map_err(|_| invalid())?, m.parse().map_err(|_| invalid())?);
+    if h > 14 || m > 59 {
+        return Err(invalid());
+    }
+    Ok(TimeZonePref::Offset(sign * (h * 3600 + m * 60)))
+}
+
+/// Check a `TIME_FORMAT` strftime pattern
+pub fn parse_format(s: &str) -> Result<String> {
+    if s.trim().is_empty() || StrftimeItems::new(s).any(|i| matches!(i, Item::Error)) {
+        return Err(anyhow!("Invalid TIME_FORMAT '{s}'. Expected a strftime pattern like %Y-%m-%d %H:%M:%S"));
+    }
+    Ok(s.to_string())
+}
+
+/// How times are shown: zone (cycled with `T`) and full-timestamp format
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TimeDisplay {
+    pub zone: TimeZonePref,
+    pub format: String,
+    /// Configured offset, kept in the `T` cycle
+    custom: Option<i32>,
+}
+
+impl Default for TimeDisplay {
+    fn default() -> Self {
+        Self::new(TimeZonePref::default(), DEFAULT_TIME_FORMAT.to_string())
+    }
+}
+
+impl TimeDisplay {
+    pub fn new(zone: TimeZonePref, format: String) -> Self {
+        let custom = match zone {
+            TimeZonePref::Offset(secs) => Some(secs),
+            _ => None,
+        };
+        Self { zone, format, custom }
+    }
+
+    /// UTC → local → configured offset (if any) → UTC
+    pub fn cycle(&mut self) {
+        self.zone = match (self.zone, self.custom) {
+            (TimeZonePref::Utc, _) => TimeZonePref::Local,
+            (TimeZonePref::Local, Some(secs)) => TimeZonePref::Offset(secs),
+            _ => TimeZonePref::Utc,
+        };
+    }
+
+    /// Full timestamp with the zone, e.g. `2024-05-01 12:00:00 UTC` ("-" if invalid)
+    pub fn at(&self, ms: i64) -> String {
+        let zone = match self.zone {
+            TimeZonePref::Local => self.format_ms(ms, "%:z"),
+            zone => zone.label(),
+        };
+        format!("{} {zone}", self.format_ms(ms, &self.format))
+    }
+
+    /// Time of day (`HH:MM:SS`) for lists
+    pub fn time(&self, ms: i64) -> String {
+        self.format_ms(ms, TIME_OF_DAY)
+    }
+
+    fn format_ms(&self, ms: i64, pattern: &str) -> String {
+        let Some(utc) = DateTime::<Utc>::from_timestamp_millis(ms) else {
+            return "-".to_string();
+        };
+        match self.zone {
+            TimeZonePref::Utc => utc.format(pattern).to_string(),
+            TimeZonePref::Local => utc.with_timezone(&Local).format(pattern).to_string(),
+            TimeZonePref::Offset(secs) => match FixedOffset::east_opt(secs) {
+                Some(tz) => utc.with_timezone(&tz).format(pattern).to_string(),
+                None => utc.format(pattern).to_string(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_in_the_selected_zone_and_cycles_through_the_offset() {
+        assert_eq!(parse_zone("UTC").unwrap(), TimeZonePref::Utc);
+        assert_eq!(parse_zone("+05:30").unwrap(), TimeZonePref::Offset(19_800));
+        assert_eq!(parse_zone("utc-8").unwrap(), TimeZonePref::Offset(-28_800));
+        assert!(parse_zone("+25").is_err());
+        assert!(parse_zone("Mars/Olympus").is_err());
+        assert!(parse_format("%Y %Q").is_err());
+
+        // 2024-05-01 00:00:00 UTC
+        let ms = 1_714_521_600_000;
+        let mut t = TimeDisplay::new(TimeZonePref::Offset(19_800), DEFAULT_TIME_FORMAT.into());
+        assert_eq!(t.at(ms), "2024-05-01 05:30:00 +05:30");
+        assert_eq!(t.time(ms), "05:30:00");
+        t.cycle();
+        assert_eq!(t.at(ms), "2024-05-01 00:00:00 UTC");
+        t.cycle();
+        assert_eq!(t.zone, TimeZonePref::Local);
+        t.cycle();
+        assert_eq!(t.zone, TimeZonePref::Offset(19_800));
+
+        let mut plain = TimeDisplay::new(TimeZonePref::Utc, "%d/%m %H:%M".into());
+        assert_eq!(plain.at(ms), "01/05 00:00 UTC");
+        plain.cycle();
+        plain.cycle();
+        assert_eq!(plain.zone, TimeZonePref::Utc); // no offset configured
+        assert_eq!(plain.time(i64::MAX), "-");
+    }
+}
@@ -0,0 +1,442 @@
+//! Per-transaction event timeline
+//!
+//! Traces a tx through `EXPERIMENTAL_tx_status` (the receipt tracer): the
+//! signing block, every receipt execution and gas refunds, each placed on the
+//! block it happened in with its offset from the signing block. Shards are
+//! resolved for the signing chunk; receipts inherit the shard of an executor
+//! already seen on that shard, otherwise they show `?`. Txs still in flight
+//! show the outcomes known so far, and NEP-519 yields (see `yields`) get their
+//! own section with wait time and timeout countdown.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+use crate::exec_error::{self, ExecError};
+use crate::outcome_cache::Outcomes;
+use crate::rpc_utils::{get_block_by_hash, get_chunk, get_latest_block, get_protocol_config};
+use crate::yields::{self, YieldState, YieldWait};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimelineKind {
+    Signed,
+    Receipt,
+    Refund,
+    /// Callback of a NEP-519 yield, run by a resume (or the timeout)
+    Resumed,
+}
+
+impl TimelineKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            TimelineKind::Signed => "✍ signed",
+            TimelineKind::Receipt => "⚙ receipt",
+            TimelineKind::Refund => "↩ refund",
+            TimelineKind::Resumed => "▶ resumed",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TimelineEvent {
+    pub kind: TimelineKind,
+    pub block_hash: String,
+    pub block_height: Option<u64>,
+    pub timestamp_ns: Option<u64>,
+    pub shard: Option<u64>,
+    pub executor: String,
+    /// Method name / action type (receipts) or `signer → receiver` (signing)
+    pub detail: String,
+    /// Outcome status key (`SuccessValue`, `SuccessReceiptId`, `Failure`, ...)
+    pub status: String,
+    /// Decoded failure (status `Failure` only)
+    pub error: Option<ExecError>,
+}
+
+/// Block facts needed to place events (from a `block` RPC response)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockInfo {
+    pub height: u64,
+    pub timestamp_ns: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct TxTimeline {
+    pub hash: String,
+    /// Ordered by block height (signing first)
+    pub events: Vec<TimelineEvent>,
+    /// NEP-519 yields, resumed or still parked
+    pub yields: Vec<YieldWait>,
+    /// Chain head when traced (countdown for parked yields)
+    pub head: Option<BlockInfo>,
+    pub yield_timeout_blocks: u64,
+}
+
+/// Outcome status as its variant name
+pub(crate) fn status_key(outcome: &Value) -> String {
+    match &outcome["status"] {
+        Value::Object(map) => map.keys().next().cloned().unwrap_or_default(),
+        Value::String(s) => s.clone(),
+        _ => String::new(),
+    }
+}
+
+/// Method name of a receipt's first FunctionCall, else its first action type
+pub(crate) fn receipt_detail(receipt: &Value) -> String {
+    let actions = receipt["receipt"]["Action"]["actions"].as_array();
+    let Some(first) = actions.and_then(|a| a.first()) else {
+        return "data".to_string();
+    };
+    match first {
+        Value::String(s) => s.clone(), // e.g. "CreateAccount"
+        Value::Object(map) => match map.get("FunctionCall") {
+            Some(fc) => fc["method_name"].as_str().unwrap_or("FunctionCall").to_string(),
+            None => map.keys().next().cloned().unwrap_or_default(),
+        },
+        _ => String::new(),
+    }
+}
+
+impl TxTimeline {
+    /// Build unresolved events (no heights yet) from an `EXPERIMENTAL_tx_status` result
+    pub fn from_status(status: &Value) -> Result<Self> {
+        let tx = &status["transaction"];
+        let hash = tx["hash"]
+            .as_str()
+            .ok_or_else(|| anyhow!("tx status has no transaction hash"))?
+            .to_string();
+        let tx_outcome = &status["transaction_outcome"];
+
+        let mut events = vec![TimelineEvent {
+            kind: TimelineKind::Signed,
+            block_hash: tx_outcome["block_hash"].as_str().unwrap_or_default().to_string(),
+            block_height: None,
+            timestamp_ns: None,
+            shard: None,
+            executor: tx["signer_id"].as_str().unwrap_or_default().to_string(),
+            detail: format!(
+                "→ {}",
+                tx["receiver_id"].as_str().unwrap_or_default()
+            ),
+            status: status_key(&tx_outcome["outcome"]),
+            error: exec_error::decode(&tx_outcome["outcome"]["status"]),
+        }];
+
+        // Receipt bodies (EXPERIMENTAL only) tell refunds apart and name the method
+        let receipts: HashMap<&str, &Value> = status["receipts"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|r| Some((r["receipt_id"].as_str()?, r)))
+            .collect();
+
+        let yields = yields::detect(status);
+        for ro in status["receipts_outcome"].as_array().into_iter().flatten() {
+            let id = ro["id"].as_str().unwrap_or_default();
+            let body = receipts.get(id);
+            let refund = body.is_some_and(|r| r["predecessor_id"].as_str() == Some("system"));
+            let resumed = yields.iter().any(|y| y.receipt_id == id);
+            events.push(TimelineEvent {
+                kind: if refund {
+                    TimelineKind::Refund
+                } else if resumed {
+                    TimelineKind::Resumed
+                } else {
+                    TimelineKind::Receipt
+                },
+                block_hash: ro["block_hash"].as_str().unwrap_or_default().to_string(),
+                block_height: None,
+                timestamp_ns: None,
+                shard: None,
+                executor: ro["outcome"]["executor_id"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                detail: body.map(|r| receipt_detail(r)).unwrap_or_default(),
+                status: status_key(&ro["outcome"]),
+                error: exec_error::decode(&ro["outcome"]["status"]),
+            });
+        }
+
+        Ok(Self {
+            hash,
+            events,
+            yields,
+            head: None,
+            yield_timeout_blocks: yields::DEFAULT_TIMEOUT_BLOCKS,
+        })
+    }
+
+    /// Block hashes that still need a height / timestamp
+    pub fn unresolved_blocks(&self) -> Vec<String> {
+        let mut hashes: Vec<String> = Vec::new();
+        for e in &self.events {
+            if e.block_height.is_none() && !hashes.contains(&e.block_hash) {
+                hashes.push(e.block_hash.clone());
+            }
+        }
+        hashes
+    }
+
+    /// Place events on their blocks and order them by height (stable)
+    pub fn resolve(&mut self, blocks: &HashMap<String, BlockInfo>) {
+        for e in &mut self.events {
+            if let Some(info) = blocks.get(&e.block_hash) {
+                e.block_height = Some(info.height);
+                e.timestamp_ns = Some(info.timestamp_ns);
+            }
+        }
+        for y in &mut self.yields {
+            y.resolve(blocks);
+        }
+        // Signing stays first even when receipts land in the same block
+        self.events[1..].sort_by_key(|e| e.block_height.unwrap_or(u64::MAX));
+    }
+
+    /// Record the signing shard; receipts executed by an account seen on that
+    /// shard inherit it (an account lives on exactly one shard)
+    pub fn set_signing_shard(&mut self, shard: u64) {
+        let Some(signer) = self.events.first().map(|e| e.executor.clone()) else {
+            return;
+        };
+        for e in &mut self.events {
+            if e.executor == signer {
+                e.shard = Some(shard);
+            }
+        }
+    }
+
+    /// Aligned text rows for the Details pane; `expand_errors` adds each
+    /// failure's original JSON (formatted with `fmt`) under its row
+    pub fn render_lines(&self, expand_errors: bool, fmt: crate::json_pretty::JsonFormat) -> Vec<String> {
+        let base_h = self.events.first().and_then(|e| e.block_height);
+        let base_ts = self.events.first().and_then(|e| e.timestamp_ns);
+
+        let rows: Vec<[String; 6]> = self
+            .events
+            .iter()
+            .map(|e| {
+                let blocks = match (e.block_height, base_h) {
+                    (Some(h), Some(b)) => format!("+{}", h.saturating_sub(b)),
+                    _ => "?".to_string(),
+                };
+                let secs = match (e.timestamp_ns, base_ts) {
+                    (Some(t), Some(b)) => format!("+{:.1}s", t.saturating_sub(b) as f64 / 1e9),
+                    _ => "?".to_string(),
+                };
+                let height = e
+                    .block_height
+                    .map(|h| format!("#{h}"))
+                    .unwrap_or_else(|| "#?".to_string());
+                let shard = e
+                    .shard
+                    .map(|s| format!("shard {s}"))
+                    .unwrap_or_else(|| "shard ?".to_string());
+                let what = if e.detail.is_empty() {
+                    e.executor.clone()
+                } else {
+                    format!("{} {}", e.executor, e.detail)
+                };
+                let status = match &e.error {
+                    Some(err) => format!("✗ {}", err.summary),
+                    None => e.status.clone(),
+                };
+                [blocks, secs, height, shard, e.kind.label().to_string(), format!("{what}  {status}")]
+            })
+            .collect();
+
+        let mut widths = [0usize; 5];
+        for row in &rows {
+            for (w, cell) in widths.iter_mut().zip(row.iter()) {
+                *w = (*w).max(cell.chars().count());
+            }
+        }
+
+        let mut lines = vec![format!("Timeline for {}", self.hash), String::new()];
+        for (row, event) in rows.iter().zip(&self.events) {
+            let mut line = String::new();
+            for (cell, w) in row.iter().zip(widths.iter()) {
+                line.push_str(cell);
+                line.push_str(&" ".repeat(w - cell.chars().count() + 2));
+            }
+            line.push_str(&row[5]);
+            lines.push(line.trim_end().to_string());
+            if let (true, Some(err)) = (expand_errors, &event.error) {
+                let json = crate::json_pretty::pretty(&err.raw, fmt);
+                lines.extend(json.lines().map(|l| format!("    {l}")));
+            }
+        }
+        lines.extend(self.yield_lines());
+        if !expand_errors && self.has_errors() {
+            lines.push(String::new());
+            lines.push("(e: show the raw error JSON)".to_string());
+        }
+        lines
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.events.iter().any(|e| e.error.is_some())
+    }
+
+    /// Yields still waiting for a response
+    pub fn parked_yields(&self) -> usize {
+        self.yields.iter().filter(|y| y.pending()).count()
+    }
+
+    /// "Yielded (NEP-519)" section: one row per yield with its wait / countdown
+    fn yield_lines(&self) -> Vec<String> {
+        if self.yields.is_empty() {
+            return Vec::new();
+        }
+        let mut lines = vec![
+            String::new(),
+            format!("Yielded (NEP-519) · timeout {} blocks", self.yield_timeout_blocks),
+        ];
+        for y in &self.yields {
+            let state = y.state(self.head, self.yield_timeout_blocks);
+            let icon = match state {
+                YieldState::Waiting { .. } => "⏳",
+                YieldState::TimedOut { .. } => "⌛",
+                YieldState::Resumed { .. } => "▶",
+                YieldState::Unknown => "⏸",
+            };
+            let created = y
+                .created
+                .map(|c| format!("#{}", c.height))
+                .unwrap_or_else(|| "#?".to_string());
+            let callback = y.callback.as_deref().unwrap_or("(callback pending)");
+            lines.push(format!(
+                "  {icon} {} {callback}  yielded at {created}, {}",
+                y.account,
+                yields::describe(state)
+            ));
+        }
+        lines
+    }
+}
+
+/// Trace `hash` (signed by `signer`) via RPC and resolve heights, timing and the signing shard
+/// (receipt outcomes come from `outcomes` when already known)
+pub async fn trace(
+    outcomes: &Outcomes,
+    url: &str,
+    hash: &str,
+    signer: &str,
+    timeout_ms: u64,
+    auth_token: Option<&str>,
+) -> Result<TxTimeline> {
+    // Outcomes so far (a tx parked on a yield would block FINAL until it resumes);
+    // nodes that answer without them get the FINAL query
+    let mut status = outcomes
+        .tx_status(url, hash, signer, "INCLUDED_FINAL", timeout_ms, auth_token)
+        .await?;
+    if status.get("transaction_outcome").is_none() {
+        status = outcomes
+            .tx_status(url, hash, signer, "FINAL", timeout_ms, auth_token)
+            .await?;
+    }
+    let mut timeline = TxTimeline::from_status(&status)?;
+
+    let mut blocks = HashMap::new();
+    let mut signing_block = None;
+    for block_hash in timeline.unresolved_blocks() {
+        let block = get_block_by_hash(url, &block_hash, timeout_ms, auth_token).await?;
+        let header = &block["header"];
+        if let (Some(height), Some(timestamp_ns)) =
+            (header["height"].as_u64(), header["timestamp"].as_u64())
+        {
+            blocks.insert(block_hash.clone(), BlockInfo { height, timestamp_ns });
+        }
+        if timeline.events[0].block_hash == block_hash {
+            signing_block = Some(block);
+        }
+    }
+    timeline.resolve(&blocks);
+
+    // Yields: the protocol's timeout, and the head for parked ones' countdown
+    if !timeline.yields.is_empty() {
+        if let Ok(config) = get_protocol_config(url, timeout_ms, auth_token).await {
+            if let Some(blocks) = yields::timeout_from_protocol_config(&config) {
+                timeline.yield_timeout_blocks = blocks;
+            }
+        }
+        if timeline.parked_yields() > 0 {
+            let head = get_latest_block(url, timeout_ms, auth_token).await?;
+            let header = &head["header"];
+            timeline.head = header["height"].as_u64().zip(header["timestamp"].as_u64()).map(
+                |(height, timestamp_ns)| BlockInfo { height, timestamp_ns },
+            );
+            yields::retain_parked(&mut timeline.yields, timeline.head);
+        }
+    }
+
+    // Signing shard: the chunk whose transactions include this tx
+    if let Some(block) = signing_block {
+        for chunk in block["chunks"].as_array().into_iter().flatten() {
+            let Some(chunk_hash) = chunk["chunk_hash"].as_str() else {
+                continue;
+            };
+            let Ok(body) = get_chunk(url, chunk_hash, timeout_ms, auth_token).await else {
+                continue;
+            };
+            let found = body["transactions"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .any(|t| t["hash"].as_str() == Some(hash));
+            if found {
+                if let Some(shard) = chunk["shard_id"].as_u64() {
+                    timeline.set_signing_shard(shard);
+                }
+                break;
+            }
+        }
+    }
+
+    Ok(timeline)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn orders_receipts_and_marks_refunds() {
+        let status = json!({
+            "transaction": {"hash": "TX", "signer_id": "alice.near", "receiver_id": "token.near"},
+            "transaction_outcome": {"block_hash": "B0", "outcome": {"executor_id": "alice.near", "status": {"SuccessReceiptId": "R1"}}},
+            "receipts_outcome": [
+                {"id": "R2", "block_hash": "B2", "outcome": {"executor_id": "alice.near", "status": {"SuccessValue": ""}}},
+                {"id": "R1", "block_hash": "B1", "outcome": {"executor_id": "token.near", "status": {"SuccessValue": ""}}}
+            ],
+            "receipts": [
+                {"receipt_id": "R1", "predecessor_id": "alice.near", "receipt": {"Action": {"actions": [{"FunctionCall": {"method_name": "ft_transfer"}}]}}},
+                {"receipt_id": "R2", "predecessor_id": "system", "receipt": {"Action": {"actions": [{"Transfer": {"deposit": "1"}}]}}}
+            ]
+        });
+        let mut tl = TxTimeline::from_status(&status).unwrap();
+        assert_eq!(tl.unresolved_blocks(), ["B0", "B2", "B1"]);
+
+        let blocks = HashMap::from([
+            ("B0".to_string(), BlockInfo { height: 100, timestamp_ns: 0 }),
+            ("B1".to_string(), BlockInfo { height: 101, timestamp_ns: 1_200_000_000 }),
+            ("B2".to_string(), BlockInfo { height: 102, timestamp_ns: 2_400_000_000 }),
+        ]);
+        tl.resolve(&blocks);
+        tl.set_signing_shard(2);
+
+        let kinds: Vec<_> = tl.events.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            [TimelineKind::Signed, TimelineKind::Receipt, TimelineKind::Refund]
+        );
+        assert_eq!(tl.events[1].detail, "ft_transfer");
+        assert_eq!(tl.events[2].shard, Some(2)); // refund to the signer's shard
+
+        let lines = tl.render_lines(false, Default::default());
+        assert!(lines[3].starts_with("+1  +1.2s  #101  shard ?  ⚙ receipt"));
+        assert!(lines[4].starts_with("+2  +2.4s  #102  shard 2  ↩ refund"));
+    }
+}
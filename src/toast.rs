@@ -0,0 +1,159 @@
+//! Toast notification queue
+//!
+//! Toasts stack up to `TOAST_MAX_VISIBLE` at a time; the rest wait their turn
+//! and start their own timer only once shown, so bursts of events no longer
+//! overwrite each other. Expired toasts move to a bounded history that can be
+//! recalled from the toast history overlay.
+
+use std::collections::VecDeque;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+
+#[cfg(target_arch = "wasm32")]
+use web_time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::constants::app::{TOAST_DURATION_SECS, TOAST_HISTORY_LEN, TOAST_MAX_VISIBLE};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum ToastLevel {
+    Info,
+    Success,
+    Warn,
+    Error,
+}
+
+impl ToastLevel {
+    /// Default on-screen time (warnings and errors linger longer)
+    pub fn duration(self) -> Duration {
+        match self {
+            ToastLevel::Info | ToastLevel::Success => Duration::from_secs(TOAST_DURATION_SECS),
+            ToastLevel::Warn => Duration::from_secs(TOAST_DURATION_SECS * 2),
+            ToastLevel::Error => Duration::from_secs(TOAST_DURATION_SECS * 3),
+        }
+    }
+
+    pub fn icon(self) -> &'static str {
+        match self {
+            ToastLevel::Info => "ℹ",
+            ToastLevel::Success => "✓",
+            ToastLevel::Warn => "⚠",
+            ToastLevel::Error => "✗",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Toast {
+    pub id: u64,
+    pub level: ToastLevel,
+    pub message: String,
+    pub duration: Duration,
+    /// Wall-clock time of creation (ms), for the history overlay
+    pub created_ms: i64,
+    /// Set when the toast becomes visible; its duration counts from here
+    shown_at: Option<Instant>,
+}
+
+impl Toast {
+    fn expired(&self, now: Instant) -> bool {
+        self.shown_at
+            .is_some_and(|t| now.saturating_duration_since(t) >= self.duration)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ToastQueue {
+    /// Visible toasts first (oldest → newest), then waiting ones
+    active: VecDeque<Toast>,
+    /// Expired toasts, newest first
+    history: VecDeque<Toast>,
+    next_id: u64,
+}
+
+impl ToastQueue {
+    /// Queue a toast; shown immediately if a visible slot is free. Returns its id.
+    pub fn push(&mut self, level: ToastLevel, message: String, duration: Duration, now: Instant) -> u64 {
+        self.next_id += 1;
+        self.active.push_back(Toast {
+            id: self.next_id,
+            level,
+            message,
+            duration,
+            created_ms: chrono::Utc::now().timestamp_millis(),
+            shown_at: None,
+        });
+        self.tick(now);
+        self.next_id
+    }
+
+    /// Retire expired toasts into history and promote waiting ones
+    pub fn tick(&mut self, now: Instant) {
+        let (expired, kept): (Vec<Toast>, Vec<Toast>) =
+            self.active.drain(..).partition(|t| t.expired(now));
+        self.active = kept.into();
+        for t in expired {
+            self.history.push_front(t);
+        }
+        self.history.truncate(TOAST_HISTORY_LEN);
+
+        for t in self.active.iter_mut().take(TOAST_MAX_VISIBLE) {
+            t.shown_at.get_or_insert(now);
+        }
+    }
+
+    /// Currently visible toasts (oldest first), excluding any that expired since the last tick
+    pub fn visible(&self, now: Instant) -> impl Iterator<Item = &Toast> {
+        self.active
+            .iter()
+            .take(TOAST_MAX_VISIBLE)
+            .filter(move |t| t.shown_at.is_some() && !t.expired(now))
+    }
+
+    /// Toasts waiting for a visible slot
+    pub fn waiting(&self) -> usize {
+        self.active.len().saturating_sub(TOAST_MAX_VISIBLE)
+    }
+
+    /// Past toasts, newest first
+    pub fn history(&self) -> impl Iterator<Item = &Toast> {
+        self.history.iter()
+    }
+
+    /// Drop all visible and waiting toasts (they go to history)
+    pub fn dismiss_all(&mut self) {
+        for t in self.active.drain(..) {
+            self.history.push_front(t);
+        }
+        self.history.truncate(TOAST_HISTORY_LEN);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stacks_queues_and_retires() {
+        let t0 = Instant::now();
+        let mut q = ToastQueue::default();
+        let short = Duration::from_secs(1);
+        for i in 0..TOAST_MAX_VISIBLE + 1 {
+            q.push(ToastLevel::Info, format!("t{i}"), short, t0);
+        }
+        assert_eq!(q.visible(t0).count(), TOAST_MAX_VISIBLE);
+        assert_eq!(q.waiting(), 1);
+
+        // First batch expires; the waiting toast gets a full duration of its own
+        let t1 = t0 + short;
+        q.tick(t1);
+        let visible: Vec<_> = q.visible(t1).map(|t| t.message.clone()).collect();
+        assert_eq!(visible, [format!("t{TOAST_MAX_VISIBLE}")]);
+        assert_eq!(q.history().count(), TOAST_MAX_VISIBLE);
+        assert_eq!(q.visible(t1 + short / 2).count(), 1);
+        assert_eq!(q.visible(t1 + short).count(), 0);
+    }
+}
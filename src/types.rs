@@ -39,6 +39,14 @@ pub struct BlockRow {
     pub tx_count: usize,
     pub when: String,
     pub transactions: Vec<TxLite>,
+    /// Header gas price in yoctoNEAR per gas (None for WS height-only rows)
+    #[serde(default)]
+    #[cfg_attr(target_arch = "wasm32", serde(serialize_with = "crate::util_text::serialize_option_u128_as_string"))]
+    pub gas_price: Option<u128>,
+    /// Gas burnt by the chunks new in this block (None when the source doesn't report it)
+    #[serde(default)]
+    #[cfg_attr(target_arch = "wasm32", serde(serialize_with = "crate::util_text::serialize_option_u64_as_string"))]
+    pub gas_used: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -114,9 +122,107 @@ pub enum ActionSummary {
 pub enum AppEvent {
     FromWs(WsPayload),
     NewBlock(BlockRow),
+    PluginAlert(PluginAlert),
+    /// Analysis from the tx-analyzer plugin (persisted to History, browsed with Ctrl+R)
+    TxAnalysis(TxAnalysis),
+    /// Add or replace (by id) a footer segment; dropped after `ttl_secs` if set
+    StatusSegment {
+        segment: crate::status_bar::StatusSegment,
+        ttl_secs: Option<u64>,
+    },
+    /// Remove a contributed footer segment by id
+    ClearStatusSegment(String),
+    SystemAppearance(crate::theme::Appearance),
+    /// Action from the local control channel (`CONTROL_SOCKET`), already authorized
+    Control(crate::ui_snapshot::UiAction),
+    /// History's related-tx candidates for a tx opened in Details
+    RelatedTxs {
+        hash: String,
+        txs: Vec<crate::history::TxPersist>,
+    },
+    /// Receipt trace for a tx requested with `t` (error text on failure)
+    TxTimeline {
+        hash: String,
+        result: Result<crate::timeline::TxTimeline, String>,
+    },
+    /// Receipt gas tree for a tx requested with `G` (error text on failure)
+    GasProfile {
+        hash: String,
+        result: Result<crate::gas_profile::GasProfile, String>,
+    },
+    /// Validator stake summary, sent once per new epoch (native poller)
+    StakeDistribution(crate::stake::StakeDistribution),
+    /// Contract digest requested with Ctrl+G
+    ContractDigest(crate::digest::ContractDigest),
+    /// Last session's state, read from History after the first frame (native)
+    StartupLoaded(Box<crate::startup::StartupLoad>),
+    /// Export written off the UI loop (digest re-run, HTML report); error text on failure
+    ExportWritten(Result<crate::exports::ExportRecord, String>),
+    /// One hash of an imported tx collection resolved (Shift+I)
+    CollectionResolved(crate::collection::Resolution),
+    /// `view_state` result for the state inspector (Ctrl+K)
+    ContractState {
+        account: String,
+        prefix: Vec<u8>,
+        result: Result<crate::state_view::ContractState, String>,
+    },
+    /// FastNEAR holder lookup for the inspector's Holders tab
+    TokenHolders {
+        token: String,
+        account: Option<String>,
+        result: Result<crate::holders::TokenHolders, String>,
+    },
+    /// Success / failure report from a block source task
+    SourceStatus {
+        kind: crate::source_health::SourceKind,
+        report: crate::source_health::SourceReport,
+    },
+    /// Config file change (live keys to apply, restart-only keys to report)
+    ConfigReload(Result<crate::config_file::ConfigReload, String>),
+    /// Current account set from the credentials watcher (lowercase account ids)
+    OwnedAccounts(std::collections::HashSet<String>),
     Quit,
 }
 
+/// Plugin alert severity (mirrors `nearx_plugin_core::AlertSeverity`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum AlertSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+/// Alert raised by a plugin, optionally tied to a block or transaction
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PluginAlert {
+    pub plugin_id: String,
+    pub severity: AlertSeverity,
+    pub block_height: Option<u64>,
+    pub tx_hash: Option<String>,
+    pub message: String,
+}
+
+/// Default "high-risk" cut-off for the Risk browser (the tx-analyzer warns above it)
+pub const HIGH_RISK_SCORE: u8 = 70;
+
+/// Result of the tx-analyzer plugin (the fields of its `TransactionAnalysis`
+/// JSON that NEARx stores; unknown fields are ignored)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TxAnalysis {
+    pub hash: String,
+    #[serde(default)]
+    pub signer: String,
+    #[serde(default)]
+    pub receiver: String,
+    #[serde(default)]
+    pub total_gas_used: u64,
+    #[serde(default)]
+    pub patterns_detected: Vec<String>,
+    pub risk_score: u8,
+    #[serde(default)]
+    pub insights: Vec<String>,
+}
+
 /// Jump mark for navigation bookmarks
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Mark {
@@ -127,3 +233,75 @@ pub struct Mark {
     pub when_ms: i64,
     pub pinned: bool,
 }
+
+/// Shared test fixtures for block-driven modules
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use super::{BlockRow, TxLite};
+    use crate::history::{BlockPersist, TxPersist};
+    use crate::ui_snapshot::{UiBlockRow, UiBlockSource};
+
+    /// Transaction `hash` from `signer` to `receiver`, without actions
+    pub fn tx(hash: &str, signer: &str, receiver: &str) -> TxLite {
+        TxLite {
+            hash: hash.into(),
+            signer_id: Some(signer.into()),
+            receiver_id: Some(receiver.into()),
+            actions: None,
+            nonce: None,
+        }
+    }
+
+    /// Block `height` (hash `B{height}`) holding `transactions`, no gas data
+    pub fn block(height: u64, transactions: Vec<TxLite>) -> BlockRow {
+        BlockRow {
+            height,
+            hash: format!("B{height}"),
+            prev_height: None,
+            prev_hash: None,
+            timestamp: 0,
+            tx_count: transactions.len(),
+            when: String::new(),
+            transactions,
+            gas_price: None,
+            gas_used: None,
+        }
+    }
+
+    /// Stored tx `hash` at `height` from `signer` to `receiver`, without actions
+    pub fn stored_tx(hash: &str, height: u64, signer: &str, receiver: &str) -> TxPersist {
+        TxPersist {
+            hash: hash.into(),
+            height,
+            signer: Some(signer.into()),
+            receiver: Some(receiver.into()),
+            actions_json: None,
+            raw_json: None,
+        }
+    }
+
+    /// Stored block `height` (hash `B{height}`) holding `txs`
+    pub fn stored_block(height: u64, txs: Vec<TxPersist>) -> BlockPersist {
+        BlockPersist {
+            height,
+            hash: format!("B{height}"),
+            ts_ms: 0,
+            txs,
+        }
+    }
+
+    /// Blocks pane row `index` for block `height`, available and unselected
+    pub fn block_row(index: usize, height: u64, source: UiBlockSource) -> UiBlockRow {
+        UiBlockRow {
+            index,
+            height,
+            hash: format!("B{height}"),
+            when: String::new(),
+            tx_count: 0,
+            available: true,
+            is_selected: false,
+            source,
+            stale: false,
+        }
+    }
+}
@@ -1,20 +1,38 @@
-use crate::app::{App, InputMode};
-use crate::history::HistoryHit;
+use crate::account_feed::{AccountEvent, AccountEventKind, FeedSummary};
+use crate::app::{App, DetailsBuffer, EndpointDraft, FilterPreview, InputMode, ZoomedPane};
+use crate::digest::ContractDigest;
+use crate::gas_oracle::{format_gas_price, GasGuidance};
+use crate::history::{HistoryHit, RiskHit};
+use crate::history_browser::{BrowseFocus, HistoryBrowser};
+use crate::holders::HoldersTab;
+use crate::collection::{Collection, ItemStatus, TxOutcome};
+use crate::exports::ExportRecord;
+use crate::pin_board::PinItem;
+use crate::watch::WatchRule;
+use crate::source_health::{HealthStatus, SourceBadge};
+use crate::stake::StakeDistribution;
+use crate::status_bar::{self, SegmentAction, StatusSegment, Tone};
+use crate::telemetry::Telemetry;
+use crate::time_display::TimeDisplay;
+use crate::ui_snapshot::{blocks_empty_reason, txs_empty_reason, EmptyReason};
+use crate::state_view::{StateInspector, StateRow};
+use crate::toast::{Toast, ToastLevel};
 use crate::theme::tokens;
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Modifier, Style, Stylize},
-    text::{Line, Span},
+    text::{Line, Span, Text},
     widgets::{
         Block, BorderType, Borders, Clear, List, ListItem, ListState, Padding, Paragraph,
-        Wrap,
+        Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap,
     },
     Frame,
 };
 
-// Theme system for native builds
+// Theme system (colors come from the active App theme)
+use crate::theme::Theme;
 #[cfg(feature = "native")]
-use crate::theme::{ratatui_helpers::c, Theme};
+use crate::theme::ratatui_helpers::c;
 
 // Detect truecolor support for terminal builds so we can cleanly
 // fall back to ANSI colors on older terminals.
@@ -48,7 +66,7 @@ fn supports_true_color() -> bool {
 }
 
 // Mark type used by both native and web builds
-use crate::types::Mark;
+use crate::types::{ActionSummary, Mark, TxLite};
 
 // ===============================
 // Pane identification
@@ -67,21 +85,21 @@ pub enum PaneKind {
 
 #[cfg(feature = "native")]
 #[inline]
-fn get_accent() -> ratatui::style::Color {
-    c(Theme::default().accent)
+fn get_accent(theme: &Theme) -> ratatui::style::Color {
+    c(theme.accent)
 }
 
 #[cfg(not(feature = "native"))]
 #[inline]
-fn get_accent() -> ratatui::style::Color {
+fn get_accent(_theme: &Theme) -> ratatui::style::Color {
     Color::Yellow
 }
 
 #[cfg(feature = "native")]
 #[inline]
-fn get_accent_strong() -> ratatui::style::Color {
+fn get_accent_strong(theme: &Theme) -> ratatui::style::Color {
     if supports_true_color() {
-        c(Theme::default().accent_strong)
+        c(theme.accent_strong)
     } else {
         // ANSI fallback for legacy terminals
         Color::Yellow
@@ -90,15 +108,15 @@ fn get_accent_strong() -> ratatui::style::Color {
 
 #[cfg(not(feature = "native"))]
 #[inline]
-fn get_accent_strong() -> ratatui::style::Color {
+fn get_accent_strong(_theme: &Theme) -> ratatui::style::Color {
     Color::Yellow
 }
 
 #[cfg(feature = "native")]
 #[inline]
-fn get_border() -> ratatui::style::Color {
+fn get_border(theme: &Theme) -> ratatui::style::Color {
     if supports_true_color() {
-        c(Theme::default().border)
+        c(theme.border)
     } else {
         // ANSI fallback so unfocused borders are still visible
         Color::DarkGray
@@ -107,32 +125,45 @@ fn get_border() -> ratatui::style::Color {
 
 #[cfg(not(feature = "native"))]
 #[inline]
-fn get_border() -> ratatui::style::Color {
+fn get_border(_theme: &Theme) -> ratatui::style::Color {
     Color::Gray
 }
 
 #[cfg(feature = "native")]
 #[inline]
-fn get_success() -> ratatui::style::Color {
+fn get_toast_color(theme: &Theme, level: ToastLevel) -> ratatui::style::Color {
     if supports_true_color() {
-        c(Theme::default().success)
+        c(match level {
+            ToastLevel::Info => theme.accent,
+            ToastLevel::Success => theme.success,
+            ToastLevel::Warn => theme.warn,
+            ToastLevel::Error => theme.error,
+        })
     } else {
-        // ANSI fallback for "green success" on basic terminals
-        Color::Green
+        match level {
+            ToastLevel::Info => Color::Cyan,
+            ToastLevel::Success => Color::Green,
+            ToastLevel::Warn => Color::Yellow,
+            ToastLevel::Error => Color::Red,
+        }
     }
 }
 
 #[cfg(not(feature = "native"))]
 #[inline]
-fn get_success() -> ratatui::style::Color {
-    Color::Green
+fn get_toast_color(_theme: &Theme, level: ToastLevel) -> ratatui::style::Color {
+    match level {
+        ToastLevel::Info => Color::Cyan,
+        ToastLevel::Success => Color::Green,
+        ToastLevel::Warn => Color::Yellow,
+        ToastLevel::Error => Color::Red,
+    }
 }
 
 #[cfg(feature = "native")]
 #[inline]
-fn get_panel(_kind: PaneKind, focused: bool) -> ratatui::style::Color {
+fn get_panel(theme: &Theme, _kind: PaneKind, focused: bool) -> ratatui::style::Color {
     if supports_true_color() {
-        let theme = Theme::default();
         c(if focused {
             theme.panel_alt
         } else {
@@ -147,20 +178,19 @@ fn get_panel(_kind: PaneKind, focused: bool) -> ratatui::style::Color {
 
 #[cfg(not(feature = "native"))]
 #[inline]
-fn get_panel(_kind: PaneKind, _focused: bool) -> ratatui::style::Color {
+fn get_panel(_theme: &Theme, _kind: PaneKind, _focused: bool) -> ratatui::style::Color {
     Color::Black
 }
 
 #[cfg(feature = "native")]
 #[inline]
-fn get_sel_style() -> Style {
-    let theme = Theme::default();
+fn get_sel_style(theme: &Theme) -> Style {
     Style::default().bg(c(theme.sel_bg)).fg(c(theme.text))
 }
 
 #[cfg(not(feature = "native"))]
 #[inline]
-fn get_sel_style() -> Style {
+fn get_sel_style(_theme: &Theme) -> Style {
     Style::default().bg(Color::Yellow).fg(Color::Black)
 }
 
@@ -205,18 +235,115 @@ pub fn draw(f: &mut Frame, app: &mut App, marks: &[Mark]) {
 
     // Overlays render last
     if app.input_mode() == InputMode::Search {
-        draw_search_overlay(
+        let preview_colored = app.search_preview_colored_window();
+        let preview_lines = draw_search_overlay(
             f,
+            app.theme(),
+            app.time_display(),
             app.search_query(),
             app.search_results(),
             app.search_selection(),
+            app.search_preview(),
+            preview_colored,
         );
+        app.set_search_preview_viewport_lines(preview_lines);
     }
     if app.input_mode() == InputMode::Marks {
-        draw_marks_overlay(f, marks, app.marks_selection());
+        draw_marks_overlay(f, app.theme(), marks, app.marks_trash(), app.marks_selection());
+    }
+    if app.input_mode() == InputMode::Endpoints {
+        draw_endpoints_overlay(f, app.theme(), app.endpoint_draft());
+    }
+    if app.input_mode() == InputMode::Accounts {
+        draw_account_feed_overlay(
+            f,
+            app.theme(),
+            app.time_display(),
+            app.account_feed_query(),
+            &app.account_feed_events(),
+            app.account_feed_selection(),
+        );
+    }
+    if app.input_mode() == InputMode::Risk {
+        draw_risk_overlay(
+            f,
+            app.theme(),
+            app.time_display(),
+            app.risk_query(),
+            app.risk_results(),
+            app.risk_selection(),
+        );
+    }
+    if app.input_mode() == InputMode::HistoryBrowser {
+        let browser = app.history_browser();
+        draw_history_browser(f, app.theme(), app.time_display(), browser, &browser.details(app.json_format()));
+    }
+    if app.input_mode() == InputMode::Sources {
+        draw_sources_overlay(f, app.theme(), &app.source_badges(), app.sources_selection());
+    }
+    if app.input_mode() == InputMode::Board {
+        draw_board_overlay(
+            f,
+            app.theme(),
+            app.time_display(),
+            &app.board_items(),
+            app.board_selection(),
+            app.board_note_draft(),
+        );
+    }
+    if app.input_mode() == InputMode::Exports {
+        draw_exports_overlay(f, app.theme(), app.time_display(), app.exports(), app.exports_selection());
     }
-    if let Some(toast) = app.toast_message() {
-        draw_toast_modal(f, toast);
+    if app.input_mode() == InputMode::Import {
+        draw_import_overlay(f, app.theme(), app.import_draft());
+    }
+    if let (InputMode::Collection, Some(collection)) = (app.input_mode(), app.collection()) {
+        draw_collection_overlay(f, app.theme(), collection);
+    }
+    if app.input_mode() == InputMode::Watches {
+        draw_watches_overlay(
+            f,
+            app.theme(),
+            app.time_display(),
+            app.watch_rules(),
+            app.watch_selection(),
+            app.watch_webhook_draft(),
+        );
+    }
+    if let (InputMode::State, Some(view)) = (app.input_mode(), app.state_view()) {
+        draw_state_overlay(f, app.theme(), view, app.holders_tab());
+    }
+    if let (InputMode::Digest, Some(target)) = (app.input_mode(), app.digest_target()) {
+        draw_digest_overlay(f, app.theme(), app.time_display(), target, app.digest(), app.digest_scroll());
+    }
+    if app.input_mode() == InputMode::Telemetry {
+        draw_telemetry_overlay(f, app.theme(), app.telemetry(), app.telemetry_scroll(), app.json_format());
+    }
+    if let Some(preview) = app.filter_preview() {
+        draw_filter_preview(f, app, preview);
+    }
+    if app.gas_panel_visible() {
+        draw_gas_panel(f, app.theme(), app.gas_guidance().as_ref());
+    }
+    if app.stake_panel_visible() {
+        draw_stake_panel(
+            f,
+            app.theme(),
+            app.stake_distribution(),
+            app.stake_previous(),
+            app.gas_panel_visible(),
+        );
+    }
+    if app.toast_history_visible() {
+        draw_toast_history_overlay(f, app.theme(), app.time_display(), &app.toast_history());
+    } else {
+        draw_toast_stack(f, app.theme(), &app.visible_toasts(), app.waiting_toasts());
+    }
+
+    // NO_COLOR / MONOCHROME: colors become bold/dim/reversed emphasis
+    #[cfg(feature = "native")]
+    if crate::theme::monochrome() {
+        crate::theme::ratatui_helpers::monochrome_buffer(f.buffer_mut(), app.theme());
     }
 }
 
@@ -226,6 +353,7 @@ pub fn draw(f: &mut Frame, app: &mut App, marks: &[Mark]) {
 // Note: header() function removed - screen now starts with filter bar
 
 fn filter_bar(f: &mut Frame, area: Rect, app: &App) {
+    let theme = *app.theme();
     let focused = app.input_mode() == InputMode::Filter;
     let filter_text = app.filter_query();
 
@@ -234,17 +362,17 @@ fn filter_bar(f: &mut Frame, area: Rect, app: &App) {
         let rule = Block::default()
             .borders(Borders::BOTTOM)
             .border_type(BorderType::Plain)
-            .border_style(Style::default().fg(get_border()));
+            .border_style(Style::default().fg(get_border(&theme)));
         f.render_widget(rule, area);
         return;
     }
 
     let border_color = if focused {
-        get_accent_strong()
+        get_accent_strong(&theme)
     } else {
-        get_border()
+        get_border(&theme)
     };
-    let text_color = if focused { get_accent() } else { Color::White };
+    let text_color = if focused { get_accent(&theme) } else { Color::White };
     let hint = "(Press / or f to filter • comma=OR, space=AND • e.g. signer:alice,bob)";
     let text = if filter_text.is_empty() && !focused {
         hint
@@ -319,9 +447,13 @@ fn body(f: &mut Frame, area: Rect, app: &mut App) {
         return;
     }
 
-    // Fullscreen details mode (Spacebar toggle from any pane)
-    if app.details_fullscreen() {
-        render_details_pane(f, area, app);
+    // Zoomed pane (`z` on any pane, or Spacebar details fullscreen)
+    if let Some(zoomed) = app.zoomed_pane() {
+        match zoomed {
+            ZoomedPane::Blocks => render_blocks_pane(f, area, app),
+            ZoomedPane::Txs => render_txs_pane(f, area, app),
+            ZoomedPane::Details => render_details_pane(f, area, app),
+        }
         return;
     }
 
@@ -368,14 +500,24 @@ fn body(f: &mut Frame, area: Rect, app: &mut App) {
     }
 }
 
+/// Title suffix reminding how to leave a zoomed list pane
+fn zoom_hint(app: &App, pane: ZoomedPane) -> &'static str {
+    if app.zoomed_pane() == Some(pane) {
+        " • z/Esc restore"
+    } else {
+        ""
+    }
+}
+
 // Helper function to render blocks pane
 fn render_blocks_pane(f: &mut Frame, area: Rect, app: &App) {
+    let theme = *app.theme();
     let blocks_focused = app.pane() == 0;
 
     // csli-style background fill (focused = panel_alt, unfocused = panel)
     f.render_widget(Clear, area);
     f.render_widget(
-        Paragraph::new("").style(Style::default().bg(get_panel(PaneKind::Blocks, blocks_focused))),
+        Paragraph::new("").style(Style::default().bg(get_panel(&theme, PaneKind::Blocks, blocks_focused))),
         area,
     );
 
@@ -398,8 +540,22 @@ fn render_blocks_pane(f: &mut Frame, area: Rect, app: &App) {
     let mut items_blocks: Vec<ListItem> = filtered_blocks
         .iter()
         .map(|b| {
-            let label = format!("{}  |  {} tx  |  {}", b.height, b.tx_count, b.when);
-            ListItem::new(label)
+            let label = if app.compact() {
+                format!("{} {}tx", b.height, b.tx_count)
+            } else {
+                format!("{}  |  {} tx  |  {}", b.height, b.tx_count, app.block_when(b))
+            };
+            if app.is_block_stale(b.height) {
+                // Warm-start row from the last session, not yet refreshed
+                ListItem::new(format!("{label}  · stale"))
+                    .style(Style::default().fg(Color::DarkGray))
+            } else if app.block_involves_new_owned(b) {
+                // Involves an account whose key was just added
+                ListItem::new(format!("{label}  + new key"))
+                    .style(Style::default().fg(get_accent(&theme)).add_modifier(Modifier::BOLD))
+            } else {
+                ListItem::new(label)
+            }
         })
         .collect();
 
@@ -433,6 +589,10 @@ fn render_blocks_pane(f: &mut Frame, area: Rect, app: &App) {
         );
     }
 
+    if items_blocks.is_empty() {
+        items_blocks = empty_state_items(&theme, &blocks_empty_reason(app), "blocks");
+    }
+
     // Try to keep the selected block roughly vertically centered in the pane.
     let total_rows = items_blocks.len();
     let inner_height = area.height.saturating_sub(2) as usize; // account for borders
@@ -451,21 +611,22 @@ fn render_blocks_pane(f: &mut Frame, area: Rect, app: &App) {
     }
 
     // Dynamic title based on filtering and cache state
+    let zoom_hint = zoom_hint(app, ZoomedPane::Blocks);
     let blocks_title = if app.is_viewing_cached_block() {
-        " Blocks (cached) — (↑↓ nav • ← recent) ".to_string()
+        format!(" Blocks (cached) — (↑↓ nav • ← recent{zoom_hint}) ")
     } else if filtered_blocks.len() < total {
-        format!(" Blocks ({} / {}) — (↑↓ nav • Enter select) ", filtered_blocks.len(), total)
+        format!(" Blocks ({} / {}) — (↑↓ nav • Enter select{zoom_hint}) ", filtered_blocks.len(), total)
     } else {
-        " Blocks — (↑↓ nav • Enter select) ".to_string()
+        format!(" Blocks — (↑↓ nav • Enter select{zoom_hint}) ")
     };
 
     // Determine border colors - TOP gets yellow when focused, sides stay gray
     let top_border_color = if blocks_focused {
-        get_accent_strong()
+        get_accent_strong(&theme)
     } else {
-        get_border()
+        get_border(&theme)
     };
-    let side_border_color = get_border(); // Always gray
+    let side_border_color = get_border(&theme); // Always gray
 
     // Render title bar at chunks[0]
     // First pass: sides (LEFT + RIGHT) in gray
@@ -513,16 +674,11 @@ fn render_blocks_pane(f: &mut Frame, area: Rect, app: &App) {
     // Content with padding (no borders, rendered on top)
     let slot_text = app.selection_slot_text();
     let slot_widget = Paragraph::new(slot_text)
-        .style(Style::default().fg(get_accent_strong()).add_modifier(Modifier::BOLD))
+        .style(Style::default().fg(get_accent_strong(&theme)).add_modifier(Modifier::BOLD))
         .block(
             Block::default()
                 .borders(Borders::NONE)
-                .padding(Padding {
-                    left: 1,
-                    right: 1,
-                    top: 0,
-                    bottom: 0,
-                }),
+                .padding(row_padding(app)),
         );
     f.render_widget(slot_widget, chunks[1]);
 
@@ -536,30 +692,80 @@ fn render_blocks_pane(f: &mut Frame, area: Rect, app: &App) {
 
     // List content (no borders, rendered on top)
     let blocks_widget = List::new(items_blocks)
-        .highlight_style(get_sel_style().add_modifier(Modifier::BOLD))
+        .highlight_style(get_sel_style(&theme).add_modifier(Modifier::BOLD))
         .highlight_symbol("• ")
         .block(
             Block::default()
                 .borders(Borders::NONE)
-                .padding(Padding {
-                    left: 1,
-                    right: 1,
-                    top: 0,
-                    bottom: 0,
-                }),
+                .padding(row_padding(app)),
         );
 
     f.render_stateful_widget(blocks_widget, chunks[2], &mut st_blocks);
+
+    if let Some(text) = app.stale_watermark() {
+        draw_stale_watermark(f, chunks[2], &text);
+    }
+}
+
+/// Dimmed diagonal watermark: one word per row, each row stepped right, centered
+/// in `area` and drawn over the rows (cells between the words are left alone)
+fn draw_stale_watermark(f: &mut Frame, area: Rect, text: &str) {
+    const STEP: u16 = 3;
+    let words: Vec<&str> = text.split(' ').collect();
+    let rows = words.len() as u16;
+    let span = words
+        .iter()
+        .enumerate()
+        .map(|(i, w)| i as u16 * STEP + w.chars().count() as u16)
+        .max()
+        .unwrap_or(0);
+    let inner = area.inner(Margin::new(1, 0));
+    if rows > inner.height || span > inner.width {
+        // Too small for the diagonal: one line across the middle
+        let line = Rect::new(inner.x, inner.y + inner.height / 2, inner.width, 1.min(inner.height));
+        f.render_widget(
+            Paragraph::new(text)
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(Color::DarkGray).add_modifier(Modifier::DIM)),
+            line,
+        );
+        return;
+    }
+    let x0 = inner.x + (inner.width - span) / 2;
+    let y0 = inner.y + (inner.height - rows) / 2;
+    let style = Style::default()
+        .fg(Color::DarkGray)
+        .add_modifier(Modifier::DIM | Modifier::BOLD);
+    let buf = f.buffer_mut();
+    for (i, word) in words.iter().enumerate() {
+        buf.set_string(x0 + i as u16 * STEP, y0 + i as u16, word, style);
+    }
+}
+
+/// Guided empty state rows: headline, source error, then the hint
+fn empty_state_items(theme: &Theme, reason: &EmptyReason, noun: &str) -> Vec<ListItem<'static>> {
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut items = vec![ListItem::new(reason.title(noun)).style(
+        Style::default().fg(get_accent(theme)).add_modifier(Modifier::BOLD),
+    )];
+    if let EmptyReason::SourceError { error, .. } = reason {
+        items.push(ListItem::new(error.clone()).style(Style::default().fg(get_toast_color(theme, ToastLevel::Error))));
+    }
+    if let Some(hint) = reason.hint() {
+        items.push(ListItem::new(hint).style(dim.add_modifier(Modifier::ITALIC)));
+    }
+    items
 }
 
 // Helper function to render txs pane
 fn render_txs_pane(f: &mut Frame, area: Rect, app: &App) {
+    let theme = *app.theme();
     let txs_focused = app.pane() == 1;
 
     // csli-style background fill (focused = panel_alt, unfocused = panel)
     f.render_widget(Clear, area);
     f.render_widget(
-        Paragraph::new("").style(Style::default().bg(get_panel(PaneKind::Txs, txs_focused))),
+        Paragraph::new("").style(Style::default().bg(get_panel(&theme, PaneKind::Txs, txs_focused))),
         area,
     );
 
@@ -569,39 +775,58 @@ fn render_txs_pane(f: &mut Frame, area: Rect, app: &App) {
         st_txs.select(Some(sel_tx));
     }
 
-    let tx_items: Vec<ListItem> = txs
+    let mut tx_items: Vec<ListItem> = txs
         .iter()
         .map(|t| {
-            // Always show full hash first (most important for people)
-            let mut display = t.hash.clone();
+            // Always show the hash first (most important for people); compact
+            // mode shortens it and drops the separator padding
+            let (mut display, sep) = if app.compact() {
+                (crate::util_text::short_hash(&t.hash), " ")
+            } else {
+                (t.hash.clone(), " | ")
+            };
 
             // Then add contract info if available
             if let (Some(signer), Some(receiver)) = (&t.signer_id, &t.receiver_id) {
                 display.push_str(&format!(
-                    " | {} → {}",
-                    truncate_account(signer, 18),
-                    truncate_account(receiver, 18)
+                    "{sep}{} → {}",
+                    truncate_account(app.account_label(signer), 18),
+                    truncate_account(app.account_label(receiver), 18)
                 ));
             }
+            if let Some(bridge) = crate::bridge::detect(t) {
+                display.push_str(&format!(" ⇄ {}", bridge.summary()));
+            }
 
+            if app.tx_involves_new_owned(t) {
+                display.push_str("  + new key");
+                return ListItem::new(display)
+                    .style(Style::default().fg(get_accent(&theme)).add_modifier(Modifier::BOLD));
+            }
             ListItem::new(display)
         })
         .collect();
+    if tx_items.is_empty() {
+        if let Some(reason) = txs_empty_reason(app) {
+            tx_items = empty_state_items(&theme, &reason, "transactions");
+        }
+    }
 
+    let zoom_hint = zoom_hint(app, ZoomedPane::Txs);
     let title = if txs.len() < total {
         // Show filtered count when filter is hiding some transactions
-        format!(" Txs ({} / {}) — (↑↓ nav • Enter select) ", txs.len(), total)
+        format!(" Txs ({} / {}) — (↑↓ nav • Enter select{zoom_hint}) ", txs.len(), total)
     } else {
-        format!(" Txs ({}) — (↑↓ nav • Enter select) ", txs.len())
+        format!(" Txs ({}) — (↑↓ nav • Enter select{zoom_hint}) ", txs.len())
     };
 
     // Two-pass rendering: sides in gray, top in yellow (when focused)
     let top_border_color = if txs_focused {
-        get_accent_strong()
+        get_accent_strong(&theme)
     } else {
-        get_border()
+        get_border(&theme)
     };
-    let side_border_color = get_border(); // Always gray
+    let side_border_color = get_border(&theme); // Always gray
 
     // First pass: sides (LEFT + RIGHT) in gray
     let side_block = Block::default()
@@ -612,7 +837,7 @@ fn render_txs_pane(f: &mut Frame, area: Rect, app: &App) {
 
     // Second pass: top border and content
     let tx_widget = List::new(tx_items)
-        .highlight_style(get_sel_style().add_modifier(Modifier::BOLD))
+        .highlight_style(get_sel_style(&theme).add_modifier(Modifier::BOLD))
         .highlight_symbol("• ")
         .block(
             Block::default()
@@ -649,6 +874,7 @@ fn render_txs_pane(f: &mut Frame, area: Rect, app: &App) {
 
 // Helper function to render details pane
 fn render_details_pane(f: &mut Frame, area: Rect, app: &mut App) {
+    let theme = *app.theme();
     // Update viewport height for windowed rendering
     // Subtract 1 for top border/title (no bottom border)
     let inner_height = area.height.saturating_sub(1) as usize;
@@ -659,21 +885,23 @@ fn render_details_pane(f: &mut Frame, area: Rect, app: &mut App) {
     // csli-style background fill (focused = panel_alt, unfocused = panel)
     f.render_widget(Clear, area);
     f.render_widget(
-        Paragraph::new("").style(Style::default().bg(get_panel(PaneKind::Details, details_focused))),
+        Paragraph::new("").style(Style::default().bg(get_panel(&theme, PaneKind::Details, details_focused))),
         area,
     );
 
     // IMPORTANT: Buffer content is already set when entering fullscreen mode
     // No need to recompute on every frame - content is cached in details buffer
 
-    // Get windowed view (always use windowing to prevent UI freezes on huge JSON)
-    let details_text = app.details_window();
-
-    let theme = app.theme();
+    // Colored window (chunks around the viewport are colorized once and cached)
+    let mut colored_lines = app.details_colored_window();
 
     // Get scroll info for title/status
     let (scroll_line, total_lines) = app.details_scroll_info();
-    let scroll_indicator = if total_lines > 1 {
+    let scrollable = total_lines > inner_height;
+    let scroll_indicator = if scrollable {
+        let max_scroll = total_lines - inner_height.max(1);
+        format!(" ({}/{} · {}%)", scroll_line + 1, total_lines, scroll_line * 100 / max_scroll.max(1))
+    } else if total_lines > 1 {
         format!(" ({}/{})", scroll_line + 1, total_lines)
     } else {
         String::new()
@@ -708,28 +936,20 @@ fn render_details_pane(f: &mut Frame, area: Rect, app: &mut App) {
     } else {
         format!(" Transaction Details{} ", scroll_indicator)
     };
+    // Jump prompt replaces the hints while typing
+    let title = match app.details_goto_draft() {
+        Some(draft) => format!(
+            " Transaction Details{} — go to :{}▏ (Enter line · % percent · Esc cancel) ",
+            scroll_indicator, draft
+        ),
+        None => title,
+    };
 
     // Match Blocks/Txs panes: use theme-driven border colors
     let border_color = if details_focused {
-        get_accent_strong()
-    } else {
-        get_border()
-    };
-
-    // Check if the content looks like JSON before trying to colorize
-    let trimmed = details_text.trim();
-    let is_json = trimmed.starts_with('{') || trimmed.starts_with('[');
-
-
-    let mut colored_lines = if is_json {
-        // Use character-based colorizer with ANSI colors
-        crate::json_syntax::colorize_json(&details_text, theme)
+        get_accent_strong(&theme)
     } else {
-        // Not JSON (e.g., "No transaction selected"), just split into lines
-        details_text
-            .lines()
-            .map(|line| Line::from(line.to_string()))
-            .collect()
+        get_border(&theme)
     };
 
     // Add truncation message if content was cut off
@@ -738,8 +958,11 @@ fn render_details_pane(f: &mut Frame, area: Rect, app: &mut App) {
         colored_lines.push(Line::from(""));
         colored_lines.push(Line::from(vec![
             Span::styled(
-                "… large output truncated at 5000 lines; press 'c' to copy full JSON",
-                Style::default().fg(get_accent()).add_modifier(Modifier::DIM)
+                format!(
+                    "… large output truncated at {} lines; press 'c' to copy full JSON",
+                    DetailsBuffer::MAX_LINES
+                ),
+                Style::default().fg(get_accent(&theme)).add_modifier(Modifier::DIM)
             )
         ]));
     }
@@ -768,76 +991,129 @@ fn render_details_pane(f: &mut Frame, area: Rect, app: &mut App) {
                 )
                 .padding(Padding {
                     left: 0,
-                    right: 0,
+                    right: u16::from(scrollable), // Keep text clear of the scrollbar
                     top: 1,
                     bottom: 0,
                 })
         });
 
     f.render_widget(details_widget, area);
+
+    // Position in long documents (thumb tracks the top visible line)
+    if scrollable {
+        let mut state = ScrollbarState::new(total_lines - inner_height.max(1) + 1)
+            .position(scroll_line)
+            .viewport_content_length(inner_height);
+        let track = Rect {
+            y: area.y + 2,
+            height: area.height.saturating_sub(2),
+            ..area
+        };
+        f.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .track_style(Style::default().fg(get_border(&theme)))
+                .thumb_style(Style::default().fg(border_color)),
+            track,
+            &mut state,
+        );
+    }
 }
 
 // ===============================
 // Footer / Debug
 // ===============================
-fn footer(f: &mut Frame, area: Rect, app: &App, marks: &[Mark]) {
-    // Build pinned marks chip (max 3)
+/// Everything the TUI footer shows: key hints, the shared indicators
+/// (`App::status_segments`), then the newest toast and FPS
+pub fn footer_segments(app: &App, marks: &[Mark]) -> Vec<StatusSegment> {
+    let mut segs = vec![
+        StatusSegment::hint("Tab", "switch").action(SegmentAction::key("Tab")),
+        StatusSegment::hint("/", "filter").action(SegmentAction::key("/")),
+        StatusSegment::hint("Ctrl+F", "search").action(SegmentAction::ctrl("f")),
+        StatusSegment::hint("←/→", "page"),
+        StatusSegment::hint("m", "mark").action(SegmentAction::key("m")),
+        StatusSegment::hint("Ctrl+P", "pin").action(SegmentAction::ctrl("p")),
+        StatusSegment::hint("Ctrl+D", "debug").action(SegmentAction::ctrl("d")),
+        StatusSegment::hint("q", "quit"),
+    ];
     let pinned_total = marks.iter().filter(|m| m.pinned).count();
-    let mut spans: Vec<Span> = Vec::with_capacity(32);
-
-    let accent = Style::default().fg(get_accent());
-    spans.push(Span::styled("Tab", accent));
-    spans.push(Span::raw(" switch  "));
-    spans.push(Span::styled("/", accent));
-    spans.push(Span::raw(" filter  "));
-    spans.push(Span::styled("Ctrl+F", accent));
-    spans.push(Span::raw(" search  "));
-    spans.push(Span::styled("←/→", accent));
-    spans.push(Span::raw(" page  "));
-    spans.push(Span::styled("m", accent));
-    spans.push(Span::raw(" mark  "));
-    spans.push(Span::styled("Ctrl+P", accent));
-    spans.push(Span::raw(" pin  "));
-    spans.push(Span::styled("Ctrl+D", accent));
-    spans.push(Span::raw(" debug  "));
-    spans.push(Span::styled("q", accent));
-    spans.push(Span::raw(" quit  "));
-
-    if pinned_total > 0 {
-        spans.push(Span::raw(" • "));
-        spans.push(Span::styled(format!("★ {pinned_total}"), accent));
-    }
-    if app.debug_visible() {
-        spans.push(Span::raw(" • "));
-        spans.push(Span::styled("[DEBUG]", Style::default().fg(Color::Magenta)));
-    }
-    if let Some(toast) = app.toast_message() {
-        spans.push(Span::raw(" • "));
-        spans.push(Span::styled(
-            toast,
-            Style::default()
-                .fg(get_success())
-                .add_modifier(Modifier::BOLD),
-        ));
+    segs.extend(app.status_segments(pinned_total));
+    if let Some(toast) = app.visible_toasts().last() {
+        let tone = match toast.level {
+            ToastLevel::Info => Tone::Accent,
+            ToastLevel::Success => Tone::Success,
+            ToastLevel::Warn => Tone::Warn,
+            ToastLevel::Error => Tone::Error,
+        };
+        segs.push(
+            StatusSegment::new("core.toast", toast.message.as_str())
+                .tone(tone)
+                .priority(status_bar::PRIORITY_CRITICAL)
+                .action(SegmentAction::key("n")),
+        );
     }
-    spans.push(Span::raw(format!(" • FPS {}", app.fps())));
-
-    let line = Line::from(spans);
-    let w = Paragraph::new(line).block(
-        Block::default()
-            .borders(Borders::TOP)
-            .border_type(BorderType::Plain),
+    segs.push(
+        StatusSegment::new("core.fps", format!("FPS {}", app.fps()))
+            .priority(status_bar::PRIORITY_LOW)
+            .action(SegmentAction::ctrl("o")),
     );
-    f.render_widget(w, area);
+    segs
+}
+
+/// Click action of the footer segment under column `col` (footer spans the full width)
+pub fn footer_action(app: &App, marks: &[Mark], width: u16, col: u16) -> Option<SegmentAction> {
+    let segs = footer_segments(app, marks);
+    let kept = status_bar::fit(&segs, width as usize);
+    let hit = status_bar::hit_test(&segs, &kept, col as usize)?;
+    segs[hit].action.clone()
+}
+
+fn tone_color(theme: &Theme, tone: Tone) -> Color {
+    match tone {
+        Tone::Plain => Color::Reset,
+        Tone::Muted => get_border(theme),
+        Tone::Accent => get_accent(theme),
+        Tone::Success => get_toast_color(theme, ToastLevel::Success),
+        Tone::Warn => get_toast_color(theme, ToastLevel::Warn),
+        Tone::Error => get_toast_color(theme, ToastLevel::Error),
+        Tone::Debug => Color::Magenta,
+    }
+}
+
+fn footer(f: &mut Frame, area: Rect, app: &App, marks: &[Mark]) {
+    let theme = *app.theme();
+    let accent = Style::default().fg(get_accent(&theme));
+    let segs = footer_segments(app, marks);
+    let kept = status_bar::fit(&segs, area.width as usize);
+
+    let mut spans: Vec<Span> = Vec::with_capacity(kept.len() * 3);
+    for (n, &i) in kept.iter().enumerate() {
+        let seg = &segs[i];
+        if n > 0 {
+            spans.push(Span::raw(status_bar::SEPARATOR));
+        }
+        if let Some(key) = &seg.key {
+            spans.push(Span::styled(format!("{key} "), accent));
+        }
+        let mut style = Style::default().fg(tone_color(&theme, seg.tone));
+        if seg.id == "core.toast" {
+            style = style.add_modifier(Modifier::BOLD);
+        }
+        spans.push(Span::styled(seg.text.as_str(), style));
+    }
+
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
 }
 
 fn debug_panel(f: &mut Frame, area: Rect, app: &App) {
+    let theme = *app.theme();
     let log = app.debug_log();
     if area.height <= 1 {
         let rule = Block::default()
             .borders(Borders::TOP)
             .border_type(BorderType::Plain)
-            .border_style(Style::default().fg(get_border()));
+            .border_style(Style::default().fg(get_border(&theme)));
         f.render_widget(rule, area);
         return;
     }
@@ -856,7 +1132,7 @@ fn debug_panel(f: &mut Frame, area: Rect, app: &App) {
                 .title(" Debug ")
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(get_border())),
+                .border_style(Style::default().fg(get_border(&theme))),
         );
 
     f.render_widget(paragraph, area);
@@ -865,7 +1141,18 @@ fn debug_panel(f: &mut Frame, area: Rect, app: &App) {
 // ===============================
 // Overlays
 // ===============================
-fn draw_search_overlay(f: &mut Frame, query: &str, results: &[HistoryHit], sel: usize) {
+/// Returns the preview pane's inner height (viewport lines for scrolling).
+#[allow(clippy::too_many_arguments)]
+fn draw_search_overlay(
+    f: &mut Frame,
+    theme: &Theme,
+    times: &TimeDisplay,
+    query: &str,
+    results: &[HistoryHit],
+    sel: usize,
+    preview: &DetailsBuffer,
+    preview_colored: Vec<Line<'static>>,
+) -> usize {
     // Centered overlay (90% width, 80% height)
     let area = f.area();
     let width = (area.width * 9) / 10;
@@ -885,7 +1172,7 @@ fn draw_search_overlay(f: &mut Frame, query: &str, results: &[HistoryHit], sel:
         .title(" History Search (Ctrl+F) ")
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(get_accent_strong()))
+        .border_style(Style::default().fg(get_accent_strong(theme)))
         .style(Style::default().bg(Color::Black));
     f.render_widget(container, overlay);
 
@@ -903,13 +1190,13 @@ fn draw_search_overlay(f: &mut Frame, query: &str, results: &[HistoryHit], sel:
 
     // Query input
     let q = Paragraph::new(query)
-        .style(Style::default().fg(get_accent()))
+        .style(Style::default().fg(get_accent(theme)))
         .block(
             Block::default()
                 .title(" Query ")
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(get_accent_strong())),
+                .border_style(Style::default().fg(get_accent_strong(theme))),
         );
     f.render_widget(q, chunks[0]);
 
@@ -927,9 +1214,7 @@ fn draw_search_overlay(f: &mut Frame, query: &str, results: &[HistoryHit], sel:
     let items: Vec<ListItem> = results
         .iter()
         .map(|h| {
-            let ts = chrono::DateTime::from_timestamp_millis(h.ts_ms)
-                .map(|dt| dt.format("%H:%M:%S").to_string())
-                .unwrap_or_else(|| "-".into());
+            let ts = times.time(h.ts_ms);
             let signer = h.signer.as_deref().unwrap_or("-");
             let receiver = h.receiver.as_deref().unwrap_or("-");
             let methods = h.methods.as_deref().unwrap_or("");
@@ -950,18 +1235,53 @@ fn draw_search_overlay(f: &mut Frame, query: &str, results: &[HistoryHit], sel:
         st.select(Some(sel.min(results.len().saturating_sub(1))));
     }
     let list = List::new(items)
-        .highlight_style(get_sel_style().add_modifier(Modifier::BOLD))
+        .highlight_style(get_sel_style(theme).add_modifier(Modifier::BOLD))
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .title(format!(" Results ({}) ", results.len()))
                 .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(get_accent_strong())),
+                .border_style(Style::default().fg(get_accent_strong(theme))),
         );
-    f.render_stateful_widget(list, chunks[1], &mut st);
+
+    // Dual-pane: results left, preview of the highlighted hit right
+    if results.is_empty() {
+        f.render_stateful_widget(list, chunks[1], &mut st);
+        return 0;
+    }
+    let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(chunks[1]);
+    f.render_stateful_widget(list, panes[0], &mut st);
+
+    let viewport = panes[1].height.saturating_sub(2) as usize;
+    let title = if preview.total_lines() > viewport {
+        format!(
+            " Preview ({}/{}) • PgUp/PgDn ",
+            preview.current_scroll_line() + 1,
+            preview.total_lines()
+        )
+    } else {
+        " Preview ".to_string()
+    };
+    let lines = if preview.is_empty() {
+        vec![Line::from(Span::styled("Loading…", Style::default().fg(get_border(theme))))]
+    } else {
+        preview_colored
+    };
+    let preview_widget = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(get_border(theme))),
+    );
+    f.render_widget(preview_widget, panes[1]);
+    viewport
 }
 
-fn draw_marks_overlay(f: &mut Frame, marks: &[Mark], sel: usize) {
+fn draw_marks_overlay(f: &mut Frame, theme: &Theme, marks: &[Mark], trash: &[Mark], sel: usize) {
     // Centered overlay (70% width, 60% height)
     let area = f.area();
     let width = (area.width * 7) / 10;
@@ -981,7 +1301,7 @@ fn draw_marks_overlay(f: &mut Frame, marks: &[Mark], sel: usize) {
         .title(" Jump Marks (m: set, Ctrl+P: pin, ': jump) ")
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(get_accent_strong()))
+        .border_style(Style::default().fg(get_accent_strong(theme)))
         .style(Style::default().bg(Color::Black));
     f.render_widget(container, overlay);
 
@@ -997,97 +1317,1727 @@ fn draw_marks_overlay(f: &mut Frame, marks: &[Mark], sel: usize) {
         .constraints([Constraint::Min(0), Constraint::Length(2)])
         .split(inner);
 
-    let items: Vec<ListItem> = marks
-        .iter()
-        .map(|m| {
-            let pin = if m.pinned { "★" } else { " " };
-            let pane = match m.pane {
-                0 => "Blocks",
-                1 => "Transactions",
-                2 => "Tx Parsed",
-                _ => "?",
-            };
-            let height_str = m
-                .height
-                .map(|h| format!("#{h}"))
-                .unwrap_or_else(|| "-".into());
-            let tx_str = m
-                .tx_hash
-                .as_deref()
-                .map(|h| &h[..8.min(h.len())])
-                .unwrap_or("-");
-            ListItem::new(format!(
-                "{} {:3} | {:8} | {:8} | {}",
-                pin, m.label, pane, height_str, tx_str
-            ))
-        })
-        .collect();
+    let row = |m: &Mark| {
+        let pin = if m.pinned { "★" } else { " " };
+        let pane = match m.pane {
+            0 => "Blocks",
+            1 => "Transactions",
+            2 => "Tx Parsed",
+            _ => "?",
+        };
+        let height_str = m
+            .height
+            .map(|h| format!("#{h}"))
+            .unwrap_or_else(|| "-".into());
+        let tx_str = m
+            .tx_hash
+            .as_deref()
+            .map(|h| &h[..8.min(h.len())])
+            .unwrap_or("-");
+        format!("{} {:3} | {:8} | {:8} | {}", pin, m.label, pane, height_str, tx_str)
+    };
+
+    let mut items: Vec<ListItem> = marks.iter().map(|m| ListItem::new(row(m))).collect();
+
+    // Trash section (soft-deleted this session): header row, then dimmed entries
+    let dim = Style::default().fg(get_border(theme));
+    if !trash.is_empty() {
+        items.push(
+            ListItem::new(format!("── Trash ({}) · Enter restore · d delete for good ──", trash.len()))
+                .style(dim),
+        );
+        items.extend(trash.iter().map(|m| ListItem::new(row(m)).style(dim)));
+    }
 
+    let total = marks.len() + trash.len();
     let mut st = ListState::default();
-    if !marks.is_empty() {
-        st.select(Some(sel.min(marks.len().saturating_sub(1))));
+    if total > 0 {
+        let sel = sel.min(total - 1);
+        // Skip over the trash header row
+        st.select(Some(if sel < marks.len() { sel } else { sel + 1 }));
     }
     let list = List::new(items)
-        .highlight_style(get_sel_style().add_modifier(Modifier::BOLD))
+        .highlight_style(get_sel_style(theme).add_modifier(Modifier::BOLD))
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .title(format!(" Marks ({}) ", marks.len()))
                 .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(get_accent_strong())),
+                .border_style(Style::default().fg(get_accent_strong(theme))),
         );
     f.render_stateful_widget(list, chunks[0], &mut st);
 
     // KEEP ORIGINAL KEYBINDINGS: 'd' for delete, not Space for pin
-    let accent = Style::default().fg(get_accent());
+    let accent = Style::default().fg(get_accent(theme));
     let help = Paragraph::new(Line::from(vec![
         Span::raw("↑/↓ move  "),
         Span::styled("Enter", accent),
         Span::raw(" jump  "),
         Span::styled("d", accent),
         Span::raw(" delete  "),
+        Span::styled("u", accent),
+        Span::raw(" undo  "),
         Span::styled("Esc", accent),
         Span::raw(" close"),
     ]));
     f.render_widget(help, chunks[1]);
 }
 
-fn draw_toast_modal(f: &mut Frame, message: &str) {
-    // Small centered box (40% width, 3 lines height)
+fn draw_board_overlay(
+    f: &mut Frame,
+    theme: &Theme,
+    times: &TimeDisplay,
+    items: &[PinItem],
+    sel: usize,
+    note: Option<&str>,
+) {
+    // Centered overlay (70% width, 60% height), same shape as marks
     let area = f.area();
-    let width = (area.width * 4) / 10;
-    let height = 3;
-    let x = (area.width.saturating_sub(width)) / 2;
-    let y = (area.height.saturating_sub(height)) / 2;
+    let width = (area.width * 7) / 10;
+    let height = (area.height * 6) / 10;
     let overlay = Rect {
-        x,
-        y,
+        x: (area.width.saturating_sub(width)) / 2,
+        y: (area.height.saturating_sub(height)) / 2,
         width,
         height,
     };
 
     f.render_widget(Clear, overlay);
 
-    let block = Block::default()
+    let container = Block::default()
+        .title(format!(" Pin Board ({}) ", items.len()))
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(get_success()));
+        .border_style(Style::default().fg(get_accent_strong(theme)))
+        .style(Style::default().bg(Color::Black));
+    f.render_widget(container, overlay);
 
-    let text = Paragraph::new(format!("✓ {message}"))
-        .style(
-            Style::default()
-                .fg(get_success())
-                .add_modifier(Modifier::BOLD),
-        )
-        .alignment(Alignment::Center)
-        .block(block);
+    let inner = Rect {
+        x: overlay.x + 1,
+        y: overlay.y + 1,
+        width: overlay.width.saturating_sub(2),
+        height: overlay.height.saturating_sub(2),
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1), Constraint::Length(1)])
+        .split(inner);
+
+    let dim = Style::default().fg(get_border(theme));
+    let list_items: Vec<ListItem> = if items.is_empty() {
+        vec![ListItem::new("Nothing yet: pin marks (Ctrl+P), copy (c) or add a note (a)").style(dim)]
+    } else {
+        items
+            .iter()
+            .map(|item| {
+                let time = times.time(item.at_ms);
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{time} "), dim),
+                    Span::raw(format!("{} ", item.kind.label())),
+                    Span::raw(item.title.clone()),
+                    Span::styled(format!("  {}", item.context()), dim),
+                ]))
+            })
+            .collect()
+    };
+    let mut st = ListState::default();
+    if !items.is_empty() {
+        st.select(Some(sel.min(items.len() - 1)));
+    }
+    let list = List::new(list_items).highlight_style(get_sel_style(theme).add_modifier(Modifier::BOLD));
+    f.render_stateful_widget(list, chunks[0], &mut st);
+
+    let accent = Style::default().fg(get_accent(theme));
+    if let Some(draft) = note {
+        let input = Paragraph::new(Line::from(vec![
+            Span::styled("Note: ", accent),
+            Span::raw(draft.to_string()),
+            Span::styled("▏", accent),
+        ]));
+        f.render_widget(input, chunks[1]);
+    }
 
-    f.render_widget(text, overlay);
+    let help = if note.is_some() {
+        Line::from(vec![
+            Span::styled("Enter", accent),
+            Span::raw(" add note  "),
+            Span::styled("Esc", accent),
+            Span::raw(" cancel"),
+        ])
+    } else {
+        Line::from(vec![
+            Span::raw("↑/↓ move  "),
+            Span::styled("Enter", accent),
+            Span::raw(" jump  "),
+            Span::styled("a", accent),
+            Span::raw(" note  "),
+            Span::styled("d", accent),
+            Span::raw(" delete  "),
+            Span::styled("e", accent),
+            Span::raw(" export .md  "),
+            Span::styled("Esc", accent),
+            Span::raw(" close"),
+        ])
+    };
+    f.render_widget(Paragraph::new(help), chunks[2]);
+}
+
+fn draw_exports_overlay(f: &mut Frame, theme: &Theme, times: &TimeDisplay, records: &[ExportRecord], sel: usize) {
+    // Centered overlay (80% width, 60% height): paths need the room
+    let area = f.area();
+    let width = (area.width * 8) / 10;
+    let height = (area.height * 6) / 10;
+    let overlay = Rect {
+        x: (area.width.saturating_sub(width)) / 2,
+        y: (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, overlay);
+
+    let container = Block::default()
+        .title(format!(" Exports ({}) ", records.len()))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(get_accent_strong(theme)))
+        .style(Style::default().bg(Color::Black));
+    f.render_widget(container, overlay);
+
+    let inner = Rect {
+        x: overlay.x + 1,
+        y: overlay.y + 1,
+        width: overlay.width.saturating_sub(2),
+        height: overlay.height.saturating_sub(2),
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1), Constraint::Length(1)])
+        .split(inner);
+
+    let dim = Style::default().fg(get_border(theme));
+    let list_items: Vec<ListItem> = if records.is_empty() {
+        vec![ListItem::new("No exports yet: e in the pin board (Shift+B) or a digest (Ctrl+G), Shift+R for a report").style(dim)]
+    } else {
+        records
+            .iter()
+            .map(|r| {
+                let mut spans = vec![
+                    Span::styled(format!("{} ", times.at(r.created_ms)), dim),
+                    Span::raw(r.params.label()),
+                    Span::styled(format!("  {}", r.details(times)), dim),
+                ];
+                if r.missing {
+                    spans.push(Span::styled("  (file gone)", dim));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect()
+    };
+    let mut st = ListState::default();
+    if !records.is_empty() {
+        st.select(Some(sel.min(records.len() - 1)));
+    }
+    let list = List::new(list_items).highlight_style(get_sel_style(theme).add_modifier(Modifier::BOLD));
+    f.render_stateful_widget(list, chunks[0], &mut st);
+
+    if let Some(r) = records.get(sel) {
+        f.render_widget(Paragraph::new(Span::styled(r.path.clone(), dim)), chunks[1]);
+    }
+
+    let accent = Style::default().fg(get_accent(theme));
+    let help = Line::from(vec![
+        Span::raw("↑/↓ move  "),
+        Span::styled("Enter", accent),
+        Span::raw(" re-run  "),
+        Span::styled("o", accent),
+        Span::raw(" open folder  "),
+        Span::styled("d", accent),
+        Span::raw(" forget  "),
+        Span::styled("Esc", accent),
+        Span::raw(" close"),
+    ]);
+    f.render_widget(Paragraph::new(help), chunks[2]);
+}
+
+fn draw_import_overlay(f: &mut Frame, theme: &Theme, draft: &str) {
+    // Centered overlay (70% width, 60% height), same shape as marks
+    let area = f.area();
+    let width = (area.width * 7) / 10;
+    let height = (area.height * 6) / 10;
+    let overlay = Rect {
+        x: (area.width.saturating_sub(width)) / 2,
+        y: (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, overlay);
+
+    let (hashes, rejected) = crate::collection::parse_hashes(draft);
+    let container = Block::default()
+        .title(format!(" Import Tx Hashes ({} found) ", hashes.len()))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(get_accent_strong(theme)))
+        .style(Style::default().bg(Color::Black));
+    f.render_widget(container, overlay);
+
+    let inner = Rect {
+        x: overlay.x + 1,
+        y: overlay.y + 1,
+        width: overlay.width.saturating_sub(2),
+        height: overlay.height.saturating_sub(2),
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1), Constraint::Length(1)])
+        .split(inner);
+
+    let dim = Style::default().fg(get_border(theme));
+    let accent = Style::default().fg(get_accent(theme));
+    let body = if draft.is_empty() {
+        Text::from(Line::styled(
+            "Paste tx hashes: one per line, or separated by commas / spaces (explorer URLs work too)",
+            dim,
+        ))
+    } else {
+        // Show the tail so the latest paste stays in view
+        let lines: Vec<&str> = draft.lines().collect();
+        let skip = lines.len().saturating_sub(chunks[0].height as usize);
+        let mut text: Vec<Line> = lines[skip..]
+            .iter()
+            .map(|l| Line::raw(crate::safe_text::escape_controls(l).into_owned()))
+            .collect();
+        if let Some(last) = text.last_mut().filter(|_| !draft.ends_with('\n')) {
+            last.push_span(Span::styled("▏", accent));
+        }
+        Text::from(text)
+    };
+    f.render_widget(Paragraph::new(body), chunks[0]);
+
+    if !rejected.is_empty() {
+        let preview: Vec<&str> = rejected.iter().take(3).map(String::as_str).collect();
+        f.render_widget(
+            Paragraph::new(Span::styled(
+                format!("{} not recognized as tx hashes: {}", rejected.len(), preview.join(", ")),
+                dim,
+            )),
+            chunks[1],
+        );
+    }
+
+    let help = Line::from(vec![
+        Span::styled("Enter", accent),
+        Span::raw(" import  "),
+        Span::styled("Backspace", accent),
+        Span::raw(" delete  "),
+        Span::styled("Esc", accent),
+        Span::raw(" cancel"),
+    ]);
+    f.render_widget(Paragraph::new(help), chunks[2]);
+}
+
+fn draw_collection_overlay(f: &mut Frame, theme: &Theme, collection: &Collection) {
+    // Centered overlay (80% width, 60% height): hashes need the room
+    let area = f.area();
+    let width = (area.width * 8) / 10;
+    let height = (area.height * 6) / 10;
+    let overlay = Rect {
+        x: (area.width.saturating_sub(width)) / 2,
+        y: (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, overlay);
+
+    let counts = collection.counts();
+    let mut title = format!(
+        " Tx Collection ({}): {} ok · {} failed · {} not found",
+        collection.items.len(),
+        counts.success,
+        counts.failed,
+        counts.missing
+    );
+    if counts.pending > 0 {
+        title.push_str(&format!(" · {} pending", counts.pending));
+    }
+    let container = Block::default()
+        .title(format!("{title} "))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(get_accent_strong(theme)))
+        .style(Style::default().bg(Color::Black));
+    f.render_widget(container, overlay);
+
+    let inner = Rect {
+        x: overlay.x + 1,
+        y: overlay.y + 1,
+        width: overlay.width.saturating_sub(2),
+        height: overlay.height.saturating_sub(2),
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1), Constraint::Length(1)])
+        .split(inner);
+
+    let dim = Style::default().fg(get_border(theme));
+    let list_items: Vec<ListItem> = collection
+        .items
+        .iter()
+        .map(|item| {
+            let status_style = match &item.status {
+                ItemStatus::Found(r) => match r.outcome {
+                    Some(TxOutcome::Success) => Style::default().fg(get_toast_color(theme, ToastLevel::Success)),
+                    Some(TxOutcome::Failure(_)) => Style::default().fg(get_toast_color(theme, ToastLevel::Error)),
+                    None => Style::default(),
+                },
+                ItemStatus::Pending => dim,
+                ItemStatus::NotFound | ItemStatus::Error(_) => {
+                    Style::default().fg(get_toast_color(theme, ToastLevel::Warn))
+                }
+            };
+            let mut spans = vec![
+                Span::raw(if item.checked { "[x] " } else { "[ ] " }),
+                Span::raw(format!("{:<44} ", item.hash)),
+                Span::styled(item.status_text(), status_style),
+            ];
+            if let Some(r) = item.resolved() {
+                let route = format!(
+                    "{} → {}",
+                    r.signer.as_deref().unwrap_or("?"),
+                    r.receiver.as_deref().unwrap_or("?")
+                );
+                let height = r.height.map(|h| format!("  #{h}")).unwrap_or_default();
+                spans.push(Span::styled(format!("  {route}{height}"), dim));
+                if let Some(via) = r.via {
+                    spans.push(Span::styled(format!("  [{}]", via.label()), dim));
+                }
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    let mut st = ListState::default();
+    if !collection.items.is_empty() {
+        st.select(Some(collection.selection.min(collection.items.len() - 1)));
+    }
+    let list = List::new(list_items).highlight_style(get_sel_style(theme).add_modifier(Modifier::BOLD));
+    f.render_stateful_widget(list, chunks[0], &mut st);
+
+    let checked = collection.items.iter().filter(|i| i.checked).count();
+    let mut summary = if checked > 0 {
+        format!("{checked} checked: m / e act on them")
+    } else {
+        "Nothing checked: m / e act on every tx".to_string()
+    };
+    if collection.rejected > 0 {
+        summary.push_str(&format!(" · {} pasted tokens skipped", collection.rejected));
+    }
+    f.render_widget(Paragraph::new(Span::styled(summary, dim)), chunks[1]);
+
+    let accent = Style::default().fg(get_accent(theme));
+    let help = Line::from(vec![
+        Span::raw("↑/↓ move  "),
+        Span::styled("Enter", accent),
+        Span::raw(" open  "),
+        Span::styled("Space", accent),
+        Span::raw(" check  "),
+        Span::styled("a", accent),
+        Span::raw(" all  "),
+        Span::styled("m", accent),
+        Span::raw(" mark  "),
+        Span::styled("e", accent),
+        Span::raw(" export .md  "),
+        Span::styled("i", accent),
+        Span::raw(" new import  "),
+        Span::styled("Esc", accent),
+        Span::raw(" close"),
+    ]);
+    f.render_widget(Paragraph::new(help), chunks[2]);
+}
+
+fn draw_watches_overlay(
+    f: &mut Frame,
+    theme: &Theme,
+    times: &TimeDisplay,
+    rules: &[WatchRule],
+    sel: usize,
+    webhook: Option<&str>,
+) {
+    // Centered overlay (70% width, 60% height), same shape as the pin board
+    let area = f.area();
+    let width = (area.width * 7) / 10;
+    let height = (area.height * 6) / 10;
+    let overlay = Rect {
+        x: (area.width.saturating_sub(width)) / 2,
+        y: (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, overlay);
+
+    let container = Block::default()
+        .title(format!(" Watches ({}) ", rules.len()))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(get_accent_strong(theme)))
+        .style(Style::default().bg(Color::Black));
+    f.render_widget(container, overlay);
+
+    let inner = Rect {
+        x: overlay.x + 1,
+        y: overlay.y + 1,
+        width: overlay.width.saturating_sub(2),
+        height: overlay.height.saturating_sub(2),
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1), Constraint::Length(1)])
+        .split(inner);
+
+    let dim = Style::default().fg(get_border(theme));
+    let accent = Style::default().fg(get_accent(theme));
+    let list_items: Vec<ListItem> = if rules.is_empty() {
+        vec![ListItem::new("No watches: set a filter (f), then press W to keep it running").style(dim)]
+    } else {
+        rules
+            .iter()
+            .map(|rule| {
+                let mut spans = vec![
+                    Span::styled(format!("{} ", times.time(rule.created_ms)), dim),
+                    Span::raw(rule.query.clone()),
+                    Span::styled(format!("  {} hits", rule.hits), dim),
+                ];
+                if let Some(height) = rule.last_height {
+                    spans.push(Span::styled(format!(" · last #{height}"), dim));
+                }
+                if rule.unseen > 0 {
+                    spans.push(Span::styled(format!("  +{} new", rule.unseen), accent));
+                }
+                if rule.webhook.is_some() {
+                    spans.push(Span::styled("  ⇢ webhook", dim));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect()
+    };
+    let mut st = ListState::default();
+    if !rules.is_empty() {
+        st.select(Some(sel.min(rules.len() - 1)));
+    }
+    let list = List::new(list_items).highlight_style(get_sel_style(theme).add_modifier(Modifier::BOLD));
+    f.render_stateful_widget(list, chunks[0], &mut st);
+
+    if let Some(draft) = webhook {
+        let input = Paragraph::new(Line::from(vec![
+            Span::styled("Webhook: ", accent),
+            Span::raw(draft.to_string()),
+            Span::styled("▏", accent),
+        ]));
+        f.render_widget(input, chunks[1]);
+    } else if let Some(url) = rules.get(sel).and_then(|r| r.webhook.as_deref()) {
+        f.render_widget(Paragraph::new(Span::styled(format!("⇢ {url}"), dim)), chunks[1]);
+    }
+
+    let help = if webhook.is_some() {
+        Line::from(vec![
+            Span::styled("Enter", accent),
+            Span::raw(" save (blank removes)  "),
+            Span::styled("Esc", accent),
+            Span::raw(" cancel"),
+        ])
+    } else {
+        Line::from(vec![
+            Span::raw("↑/↓ move  "),
+            Span::styled("Enter", accent),
+            Span::raw(" apply as filter  "),
+            Span::styled("w", accent),
+            Span::raw(" webhook  "),
+            Span::styled("d", accent),
+            Span::raw(" delete  "),
+            Span::styled("Esc", accent),
+            Span::raw(" close"),
+        ])
+    };
+    f.render_widget(Paragraph::new(help), chunks[2]);
+}
+
+fn draw_state_overlay(
+    f: &mut Frame,
+    theme: &Theme,
+    view: &StateInspector,
+    holders: Option<&HoldersTab>,
+) {
+    // Centered overlay (80% width, 80% height), same shape as the digest
+    let area = f.area();
+    let width = (area.width * 8) / 10;
+    let height = (area.height * 8) / 10;
+    let overlay = Rect {
+        x: (area.width.saturating_sub(width)) / 2,
+        y: (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, overlay);
+
+    let prefix = view.prefix_label();
+    let mut title = format!(" State · {}", view.account);
+    if let Some(tab) = holders {
+        title = format!(" Holders · {}", tab.token);
+        if let Some(Ok(h)) = &tab.result {
+            title.push_str(&format!(" · top {}", h.holders.len()));
+        }
+    } else if !prefix.is_empty() {
+        title.push_str(&format!(" · prefix {prefix}"));
+    }
+    if let Some(state) = view.state() {
+        title.push_str(&format!(" · #{} · {} keys", state.block_height, state.entries.len()));
+        if view.pages() > 1 {
+            title.push_str(&format!(" · page {}/{}", view.page + 1, view.pages()));
+        }
+    }
+    title.push(' ');
+    let container = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(get_accent_strong(theme)))
+        .style(Style::default().bg(Color::Black));
+    f.render_widget(container, overlay);
+
+    let inner = Rect {
+        x: overlay.x + 1,
+        y: overlay.y + 1,
+        width: overlay.width.saturating_sub(2),
+        height: overlay.height.saturating_sub(2),
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    let dim = Style::default().fg(get_border(theme));
+    let accent = Style::default().fg(get_accent(theme));
+    if let Some(tab) = holders {
+        draw_holders_tab(f, theme, tab, chunks[0], chunks[1]);
+        return;
+    }
+    let rows = view.rows();
+    let items: Vec<ListItem> = match &view.result {
+        None => vec![ListItem::new("Fetching view_state…").style(dim)],
+        Some(Err(e)) => vec![ListItem::new(format!("view_state failed: {e}"))
+            .style(Style::default().fg(get_toast_color(theme, ToastLevel::Error)))],
+        Some(Ok(_)) if rows.is_empty() => vec![ListItem::new("No keys under this prefix").style(dim)],
+        Some(Ok(_)) => rows
+            .iter()
+            .map(|row| match row {
+                StateRow::Group { label, count, open } => ListItem::new(Line::from(vec![
+                    Span::styled(format!("{} {label}", if *open { "▾" } else { "▸" }), accent),
+                    Span::styled(format!(" ({count})"), dim),
+                ])),
+                StateRow::Entry { key, value, open, .. } => ListItem::new(Line::from(vec![
+                    Span::raw(format!("  {} ", if *open { "▾" } else { "▸" })),
+                    Span::styled(key.clone(), Style::default().add_modifier(Modifier::BOLD)),
+                    Span::styled(" = ", dim),
+                    Span::raw(value.clone()),
+                ])),
+                StateRow::Detail { text, .. } => ListItem::new(format!("      {text}")).style(dim),
+            })
+            .collect(),
+    };
+    let mut st = ListState::default();
+    if !rows.is_empty() {
+        st.select(Some(view.selection.min(rows.len() - 1)));
+    }
+    let list = List::new(items).highlight_style(get_sel_style(theme).add_modifier(Modifier::BOLD));
+    f.render_stateful_widget(list, chunks[0], &mut st);
+
+    let help = match &view.prefix_draft {
+        Some(draft) => Line::from(vec![
+            Span::styled("Prefix: ", accent),
+            Span::raw(draft.clone()),
+            Span::styled("▏", accent),
+            Span::styled("  (text, or 0x… hex) Enter fetch · Esc cancel", dim),
+        ]),
+        None => Line::from(vec![
+            Span::raw("↑/↓ move  "),
+            Span::styled("Enter", accent),
+            Span::raw(" expand  "),
+            Span::styled("/", accent),
+            Span::raw(" prefix  "),
+            Span::styled("PgUp/PgDn", accent),
+            Span::raw(" page  "),
+            Span::styled("Tab", accent),
+            Span::raw(" holders  "),
+            Span::styled("Esc", accent),
+            Span::raw(" close"),
+        ]),
+    };
+    f.render_widget(Paragraph::new(help), chunks[1]);
+}
+
+/// Holders tab body: the signer's balance / rank, then the ranked holder list
+fn draw_holders_tab(f: &mut Frame, theme: &Theme, tab: &HoldersTab, list_area: Rect, help_area: Rect) {
+    let dim = Style::default().fg(get_border(theme));
+    let accent = Style::default().fg(get_accent(theme));
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)])
+        .split(list_area);
+
+    let items: Vec<ListItem> = match &tab.result {
+        None => vec![ListItem::new("Looking up holders (FastNEAR API)…").style(dim)],
+        Some(Err(e)) => vec![ListItem::new(format!("Holder lookup failed: {e}"))
+            .style(Style::default().fg(get_toast_color(theme, ToastLevel::Error)))],
+        Some(Ok(h)) if h.holders.is_empty() => vec![ListItem::new("No holders indexed").style(dim)],
+        Some(Ok(h)) => {
+            let width = h.holders.len().to_string().len();
+            h.holders
+                .iter()
+                .enumerate()
+                .map(|(i, holder)| {
+                    let mine = h.account.as_ref().is_some_and(|(a, _)| *a == holder.account);
+                    let name = if mine {
+                        Style::default().fg(get_accent_strong(theme)).add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default()
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!("#{:<width$}  ", i + 1), dim),
+                        Span::styled(format!("{:<40}", holder.account), name),
+                        Span::raw(h.format_balance(holder.balance)),
+                    ]))
+                })
+                .collect()
+        }
+    };
+
+    let summary = match (&tab.result, &tab.account) {
+        (Some(Ok(h)), Some(account)) => {
+            let balance = match h.account.as_ref().and_then(|(_, b)| *b) {
+                Some(0) => "holds none".to_string(),
+                Some(b) => h.format_balance(b),
+                None => "balance unavailable".to_string(),
+            };
+            let rank = match h.rank(account) {
+                Some(r) => format!("rank #{r}"),
+                None => format!("not in the top {}", h.holders.len()),
+            };
+            Line::from(vec![
+                Span::styled("Signer ", dim),
+                Span::styled(account.clone(), Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(format!(": {balance} · {rank}")),
+            ])
+        }
+        _ => Line::from(""),
+    };
+    f.render_widget(Paragraph::new(summary), chunks[0]);
+
+    let mut st = ListState::default();
+    if !tab.holders().is_empty() {
+        st.select(Some(tab.selection.min(tab.holders().len() - 1)));
+    }
+    let list = List::new(items).highlight_style(get_sel_style(theme).add_modifier(Modifier::BOLD));
+    f.render_stateful_widget(list, chunks[1], &mut st);
+
+    let help = Line::from(vec![
+        Span::raw("↑/↓ move  "),
+        Span::styled("Enter", accent),
+        Span::raw(" filter on account  "),
+        Span::styled("r", accent),
+        Span::raw(" refresh  "),
+        Span::styled("Tab", accent),
+        Span::raw(" state  "),
+        Span::styled("Esc", accent),
+        Span::raw(" close"),
+    ]);
+    f.render_widget(Paragraph::new(help), help_area);
+}
+
+fn draw_digest_overlay(
+    f: &mut Frame,
+    theme: &Theme,
+    times: &TimeDisplay,
+    (contract, window_secs): (&str, u64),
+    digest: Option<&ContractDigest>,
+    scroll: u16,
+) {
+    // Centered overlay (80% width, 80% height)
+    let area = f.area();
+    let width = (area.width * 8) / 10;
+    let height = (area.height * 8) / 10;
+    let overlay = Rect {
+        x: (area.width.saturating_sub(width)) / 2,
+        y: (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, overlay);
+
+    let window = crate::digest::window_label(window_secs);
+    let container = Block::default()
+        .title(format!(" Digest · {contract} · last {window} "))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(get_accent_strong(theme)))
+        .style(Style::default().bg(Color::Black));
+    f.render_widget(container, overlay);
+
+    let inner = Rect {
+        x: overlay.x + 2,
+        y: overlay.y + 1,
+        width: overlay.width.saturating_sub(4),
+        height: overlay.height.saturating_sub(2),
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    let accent = Style::default().fg(get_accent(theme));
+    let body: Vec<Line> = match digest {
+        Some(d) => d
+            .to_markdown(times)
+            .lines()
+            .map(|l| match l.strip_prefix("## ").or_else(|| l.strip_prefix("# ")) {
+                Some(heading) => Line::styled(heading.to_string(), accent.add_modifier(Modifier::BOLD)),
+                None => Line::raw(l.to_string()),
+            })
+            .collect(),
+        None => vec![Line::styled(
+            format!("Building digest of {contract} (last {window}, outcomes sampled over RPC)…"),
+            Style::default().fg(get_border(theme)),
+        )],
+    };
+    f.render_widget(
+        Paragraph::new(body).wrap(Wrap { trim: false }).scroll((scroll, 0)),
+        chunks[0],
+    );
+
+    let help = Paragraph::new(Line::from(vec![
+        Span::raw("↑/↓ scroll  "),
+        Span::styled("w", accent),
+        Span::raw(" window (1h/24h/7d)  "),
+        Span::styled("e", accent),
+        Span::raw(" export .md  "),
+        Span::styled("Esc", accent),
+        Span::raw(" close"),
+    ]));
+    f.render_widget(help, chunks[1]);
+}
+
+fn draw_telemetry_overlay(
+    f: &mut Frame,
+    theme: &Theme,
+    telemetry: &Telemetry,
+    scroll: u16,
+    json_format: crate::json_pretty::JsonFormat,
+) {
+    // Centered overlay (70% width, 70% height)
+    let area = f.area();
+    let width = (area.width * 7) / 10;
+    let height = (area.height * 7) / 10;
+    let overlay = Rect {
+        x: (area.width.saturating_sub(width)) / 2,
+        y: (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, overlay);
+
+    let container = Block::default()
+        .title(" Telemetry preview ")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(get_accent_strong(theme)))
+        .style(Style::default().bg(Color::Black));
+    f.render_widget(container, overlay);
+
+    let inner = Rect {
+        x: overlay.x + 2,
+        y: overlay.y + 1,
+        width: overlay.width.saturating_sub(4),
+        height: overlay.height.saturating_sub(2),
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    let accent = Style::default().fg(get_accent(theme));
+    let dim = Style::default().fg(get_border(theme));
+    let status = if telemetry.enabled() {
+        Line::styled("Counting: on (TELEMETRY=true)", accent)
+    } else {
+        Line::styled("Counting: off (set TELEMETRY=true to opt in)", dim)
+    };
+    let destination = match (telemetry.enabled(), telemetry.url()) {
+        (true, Some(url)) => format!("Sent on exit to {url}, then reset"),
+        (false, Some(url)) => format!("Not sent while off (TELEMETRY_URL={url})"),
+        (_, None) => "Never sent: no TELEMETRY_URL configured".to_string(),
+    };
+    let header = vec![
+        status,
+        Line::raw(destination),
+        Line::styled("Feature counts only: no accounts, hashes or filter text", dim),
+    ];
+    f.render_widget(Paragraph::new(header), chunks[0]);
+
+    let payload = crate::json_pretty::pretty(&telemetry.payload(), json_format);
+    let body: Vec<Line> = payload.lines().map(|l| Line::raw(l.to_string())).collect();
+    f.render_widget(
+        Paragraph::new(body)
+            .block(Block::default().borders(Borders::TOP).border_style(dim))
+            .scroll((scroll, 0)),
+        chunks[1],
+    );
+
+    let help = Paragraph::new(Line::from(vec![
+        Span::raw("↑/↓ scroll  "),
+        Span::styled("x", accent),
+        Span::raw(" purge counters  "),
+        Span::styled("Esc", accent),
+        Span::raw(" close"),
+    ]));
+    f.render_widget(help, chunks[2]);
+}
+
+fn draw_risk_overlay(
+    f: &mut Frame,
+    theme: &Theme,
+    times: &TimeDisplay,
+    query: &str,
+    results: &[RiskHit],
+    sel: usize,
+) {
+    // Centered overlay (90% width, 80% height), same shape as history search
+    let area = f.area();
+    let width = (area.width * 9) / 10;
+    let height = (area.height * 8) / 10;
+    let overlay = Rect {
+        x: (area.width.saturating_sub(width)) / 2,
+        y: (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, overlay);
+
+    let container = Block::default()
+        .title(" Risk (Ctrl+R) · tx-analyzer ")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(get_accent_strong(theme)))
+        .style(Style::default().bg(Color::Black));
+    f.render_widget(container, overlay);
+
+    let inner = Rect {
+        x: overlay.x + 1,
+        y: overlay.y + 1,
+        width: overlay.width.saturating_sub(2),
+        height: overlay.height.saturating_sub(2),
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    let min_risk = crate::history::parse_risk_query(query).min_risk;
+    let q = Paragraph::new(query)
+        .style(Style::default().fg(get_accent(theme)))
+        .block(
+            Block::default()
+                .title(format!(" Filter (risk>N, account, pattern) · score ≥ {min_risk} "))
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(get_accent_strong(theme))),
+        );
+    f.render_widget(q, chunks[0]);
+    if chunks[0].width > 2 {
+        let x = chunks[0].x + 1 + (query.len().min((chunks[0].width.saturating_sub(2)) as usize) as u16);
+        f.set_cursor_position((x, chunks[0].y + 1));
+    }
+
+    let score_style = |score: u8| {
+        let level = if score > crate::types::HIGH_RISK_SCORE {
+            ToastLevel::Error
+        } else {
+            ToastLevel::Warn
+        };
+        Style::default().fg(get_toast_color(theme, level))
+    };
+    let items: Vec<ListItem> = results
+        .iter()
+        .map(|h| {
+            let a = &h.analysis;
+            let ts = times.time(h.ts_ms);
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:>3} ", a.risk_score), score_style(a.risk_score)),
+                Span::raw(format!(
+                    "{} {:20} → {:<20} {}",
+                    ts,
+                    &a.signer[..a.signer.len().min(20)],
+                    &a.receiver[..a.receiver.len().min(20)],
+                    a.patterns_detected.join(", ")
+                )),
+            ]))
+        })
+        .collect();
+
+    let mut st = ListState::default();
+    if !results.is_empty() {
+        st.select(Some(sel.min(results.len() - 1)));
+    }
+    let list = List::new(items)
+        .highlight_style(get_sel_style(theme).add_modifier(Modifier::BOLD))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Analyses ({}) ", results.len()))
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(get_accent_strong(theme))),
+        );
+
+    // Dual-pane: analyses left, insights of the highlighted one right
+    match results.get(sel) {
+        None => f.render_stateful_widget(list, chunks[1], &mut st),
+        Some(hit) => {
+            let panes = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .split(chunks[1]);
+            f.render_stateful_widget(list, panes[0], &mut st);
+
+            let a = &hit.analysis;
+            let dim = Style::default().fg(get_border(theme));
+            let mut lines = vec![
+                Line::from(a.hash.clone()),
+                Line::from(vec![
+                    Span::styled("risk ", dim),
+                    Span::styled(a.risk_score.to_string(), score_style(a.risk_score)),
+                    Span::styled("  block ", dim),
+                    Span::raw(hit.height.map(|h| format!("#{h}")).unwrap_or_else(|| "?".into())),
+                ]),
+                Line::from(vec![
+                    Span::styled("patterns ", dim),
+                    Span::raw(a.patterns_detected.join(", ")),
+                ]),
+                Line::from(""),
+            ];
+            lines.extend(a.insights.iter().map(|i| Line::from(format!("• {i}"))));
+            let detail = Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(" Insights ")
+                        .border_type(BorderType::Rounded)
+                        .border_style(Style::default().fg(get_border(theme))),
+                );
+            f.render_widget(detail, panes[1]);
+        }
+    }
+
+    let accent = Style::default().fg(get_accent(theme));
+    let help = Paragraph::new(Line::from(vec![
+        Span::raw("type to filter  ↑/↓ move  "),
+        Span::styled("Enter", accent),
+        Span::raw(" open tx  "),
+        Span::styled("Esc", accent),
+        Span::raw(" close"),
+    ]));
+    f.render_widget(help, chunks[2]);
+}
+
+fn draw_history_browser(
+    f: &mut Frame,
+    theme: &Theme,
+    times: &TimeDisplay,
+    browser: &HistoryBrowser,
+    details: &str,
+) {
+    // Centered overlay (90% width, 80% height), same shape as history search
+    let area = f.area();
+    let width = (area.width * 9) / 10;
+    let height = (area.height * 8) / 10;
+    let overlay = Rect {
+        x: (area.width.saturating_sub(width)) / 2,
+        y: (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, overlay);
+
+    let container = Block::default()
+        .title(" History (Ctrl+B) · stored blocks ")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(get_accent_strong(theme)))
+        .style(Style::default().bg(Color::Black));
+    f.render_widget(container, overlay);
+
+    let inner = Rect {
+        x: overlay.x + 1,
+        y: overlay.y + 1,
+        width: overlay.width.saturating_sub(2),
+        height: overlay.height.saturating_sub(2),
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    let query_title = match (&browser.error, browser.note) {
+        (Some(e), _) => format!(" Range · {e} "),
+        (None, Some(note)) => format!(" Range (height..height, YYYY-MM-DD) · {note} "),
+        (None, None) => " Range (height..height, YYYY-MM-DD) ".to_string(),
+    };
+    let query_border = if browser.error.is_some() {
+        get_toast_color(theme, ToastLevel::Error)
+    } else {
+        get_accent_strong(theme)
+    };
+    let q = Paragraph::new(browser.query.as_str())
+        .style(Style::default().fg(get_accent(theme)))
+        .block(
+            Block::default()
+                .title(query_title)
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(query_border)),
+        );
+    f.render_widget(q, chunks[0]);
+    if chunks[0].width > 2 {
+        let x = chunks[0].x + 1 + (browser.query.len().min((chunks[0].width.saturating_sub(2)) as usize) as u16);
+        f.set_cursor_position((x, chunks[0].y + 1));
+    }
+
+    // Blocks | txs on top, details below (the main layout's split)
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Ratio(3, 10), Constraint::Ratio(7, 10)])
+        .split(chunks[1]);
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(rows[0]);
+    let pane = |title: String, focus: BrowseFocus| {
+        let color = if browser.focus == focus {
+            get_accent_strong(theme)
+        } else {
+            get_border(theme)
+        };
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(color))
+    };
+
+    let items: Vec<ListItem> = browser
+        .blocks
+        .iter()
+        .map(|b| {
+            let ts = times.at(b.ts_ms);
+            ListItem::new(format!("#{}  {}  {} txs", b.height, ts, b.txs.len()))
+        })
+        .collect();
+    let mut st = ListState::default();
+    if !browser.blocks.is_empty() {
+        st.select(Some(browser.sel_block));
+    }
+    let list = List::new(items)
+        .highlight_style(get_sel_style(theme).add_modifier(Modifier::BOLD))
+        .block(pane(format!(" Blocks ({}) ", browser.blocks.len()), BrowseFocus::Blocks));
+    f.render_stateful_widget(list, top[0], &mut st);
+
+    let txs = browser.selected_block().map(|b| b.txs.as_slice()).unwrap_or_default();
+    let items: Vec<ListItem> = txs
+        .iter()
+        .map(|t| {
+            ListItem::new(format!(
+                "{}  {} → {}",
+                &t.hash[..t.hash.len().min(12)],
+                t.signer.as_deref().unwrap_or("?"),
+                t.receiver.as_deref().unwrap_or("?")
+            ))
+        })
+        .collect();
+    let mut st = ListState::default();
+    if !txs.is_empty() {
+        st.select(Some(browser.sel_tx));
+    }
+    let list = List::new(items)
+        .highlight_style(get_sel_style(theme).add_modifier(Modifier::BOLD))
+        .block(pane(format!(" Txs ({}) ", txs.len()), BrowseFocus::Txs));
+    f.render_stateful_widget(list, top[1], &mut st);
+
+    let detail = Paragraph::new(details)
+        .scroll((browser.details_scroll.min(u16::MAX as usize) as u16, 0))
+        .block(pane(" Details ".to_string(), BrowseFocus::Details));
+    f.render_widget(detail, rows[1]);
+
+    let accent = Style::default().fg(get_accent(theme));
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("Enter", accent),
+        Span::raw(" load range  "),
+        Span::styled("PgDn/PgUp", accent),
+        Span::raw(" older/newer  "),
+        Span::styled("Home", accent),
+        Span::raw(" newest  "),
+        Span::styled("Tab", accent),
+        Span::raw(" pane  ↑/↓ move  "),
+        Span::styled("Esc", accent),
+        Span::raw(" close (live view untouched)"),
+    ]));
+    f.render_widget(help, chunks[2]);
+}
+
+fn draw_account_feed_overlay(
+    f: &mut Frame,
+    theme: &Theme,
+    times: &TimeDisplay,
+    query: &str,
+    events: &[&AccountEvent],
+    sel: usize,
+) {
+    // Centered overlay (90% width, 80% height), same shape as the Risk browser
+    let area = f.area();
+    let width = (area.width * 9) / 10;
+    let height = (area.height * 8) / 10;
+    let overlay = Rect {
+        x: (area.width.saturating_sub(width)) / 2,
+        y: (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, overlay);
+
+    let container = Block::default()
+        .title(" Accounts (Ctrl+A) · created / deleted ")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(get_accent_strong(theme)))
+        .style(Style::default().bg(Color::Black));
+    f.render_widget(container, overlay);
+
+    let inner = Rect {
+        x: overlay.x + 1,
+        y: overlay.y + 1,
+        width: overlay.width.saturating_sub(2),
+        height: overlay.height.saturating_sub(2),
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    let q = Paragraph::new(query)
+        .style(Style::default().fg(get_accent(theme)))
+        .block(
+            Block::default()
+                .title(" Filter (.near, .tg, implicit, eth, create, delete, !term, account) ")
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(get_accent_strong(theme))),
+        );
+    f.render_widget(q, chunks[0]);
+    if chunks[0].width > 2 {
+        let x = chunks[0].x + 1 + (query.len().min((chunks[0].width.saturating_sub(2)) as usize) as u16);
+        f.set_cursor_position((x, chunks[0].y + 1));
+    }
+
+    // Creations per signer: one factory dominating the list is the bot signal
+    let dim = Style::default().fg(get_border(theme));
+    let summary = FeedSummary::of(events);
+    let top = summary
+        .top_creators
+        .iter()
+        .map(|(signer, n)| format!("{signer} ×{n}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    f.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(format!(" {} created", summary.creates), Style::default().fg(get_accent(theme))),
+            Span::styled(format!(" · {} deleted", summary.deletes), dim),
+            Span::styled(if top.is_empty() { String::new() } else { format!(" · top creators: {top}") }, dim),
+        ])),
+        chunks[1],
+    );
+
+    let items: Vec<ListItem> = events
+        .iter()
+        .map(|e| {
+            let ts = times.time(e.ts_ms);
+            let (mark, level) = match e.kind {
+                AccountEventKind::Create => ("+", ToastLevel::Success),
+                AccountEventKind::Delete => ("−", ToastLevel::Error),
+            };
+            let by = e.signer.as_deref().unwrap_or("?");
+            let detail = match &e.beneficiary {
+                Some(b) => format!("by {by} → {b}"),
+                None => format!("by {by}"),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{mark} "), Style::default().fg(get_toast_color(theme, level))),
+                Span::raw(format!("{ts} #{:<10} {:<40} ", e.height, e.account)),
+                Span::styled(detail, dim),
+            ]))
+        })
+        .collect();
+
+    let mut st = ListState::default();
+    if !events.is_empty() {
+        st.select(Some(sel.min(events.len() - 1)));
+    }
+    let list = List::new(items)
+        .highlight_style(get_sel_style(theme).add_modifier(Modifier::BOLD))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Events ({}) ", events.len()))
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(get_accent_strong(theme))),
+        );
+    f.render_stateful_widget(list, chunks[2], &mut st);
+
+    let accent = Style::default().fg(get_accent(theme));
+    let help = Paragraph::new(Line::from(vec![
+        Span::raw("type to filter  ↑/↓ move  "),
+        Span::styled("Enter", accent),
+        Span::raw(" open tx  "),
+        Span::styled("Esc", accent),
+        Span::raw(" close"),
+    ]));
+    f.render_widget(help, chunks[3]);
+}
+
+/// Stacked toasts (oldest on top), centered; "+N more" when some are queued
+fn draw_toast_stack(f: &mut Frame, theme: &Theme, toasts: &[&Toast], waiting: usize) {
+    if toasts.is_empty() {
+        return;
+    }
+    // Small centered boxes (40% width, 3 lines each)
+    let area = f.area();
+    let width = (area.width * 4) / 10;
+    let height = (toasts.len() as u16 * 3).min(area.height);
+    let x = (area.width.saturating_sub(width)) / 2;
+    let mut y = (area.height.saturating_sub(height)) / 2;
+
+    for (i, toast) in toasts.iter().enumerate() {
+        if y + 3 > area.height {
+            break;
+        }
+        let overlay = Rect {
+            x,
+            y,
+            width,
+            height: 3,
+        };
+        f.render_widget(Clear, overlay);
+
+        let color = get_toast_color(theme, toast.level);
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(color));
+        if waiting > 0 && i + 1 == toasts.len() {
+            block = block.title_bottom(format!(" +{waiting} more (n: history) "));
+        }
+
+        let text = Paragraph::new(format!("{} {}", toast.level.icon(), toast.message))
+            .style(Style::default().fg(color).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center)
+            .block(block);
+
+        f.render_widget(text, overlay);
+        y += 3;
+    }
+}
+
+fn draw_toast_history_overlay(f: &mut Frame, theme: &Theme, times: &TimeDisplay, history: &[&Toast]) {
+    // Centered overlay (60% width, 60% height)
+    let area = f.area();
+    let width = (area.width * 6) / 10;
+    let height = (area.height * 6) / 10;
+    let overlay = Rect {
+        x: (area.width.saturating_sub(width)) / 2,
+        y: (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, overlay);
+
+    let lines: Vec<Line> = if history.is_empty() {
+        vec![Line::from(Span::styled(
+            "No notifications yet",
+            Style::default().fg(get_border(theme)),
+        ))]
+    } else {
+        history
+            .iter()
+            .map(|t| {
+                let ts = times.time(t.created_ms);
+                let color = get_toast_color(theme, t.level);
+                Line::from(vec![
+                    Span::styled(format!("{ts} "), Style::default().fg(get_border(theme))),
+                    Span::styled(format!("{} ", t.level.icon()), Style::default().fg(color)),
+                    Span::raw(t.message.as_str()),
+                ])
+            })
+            .collect()
+    };
+
+    let list = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!(" Notifications ({}) — n/Esc close ", history.len()))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(get_accent_strong(theme)))
+            .style(Style::default().bg(Color::Black)),
+    );
+    f.render_widget(list, overlay);
+}
+
+fn draw_gas_panel(f: &mut Frame, theme: &Theme, guidance: Option<&GasGuidance>) {
+    // Small panel pinned to the bottom-right corner, above the footer
+    let area = f.area();
+    let width = 38.min(area.width);
+    let height = 7.min(area.height.saturating_sub(1));
+    let panel = Rect {
+        x: area.width.saturating_sub(width),
+        y: area.height.saturating_sub(height + 1),
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, panel);
+
+    let dim = Style::default().fg(get_border(theme));
+    let lines: Vec<Line> = match guidance {
+        None => vec![Line::from(Span::styled("Waiting for blocks with gas price…", dim))],
+        Some(g) => {
+            let row = |label: &str, price: u128| {
+                Line::from(vec![
+                    Span::styled(format!("{label:<5}"), dim),
+                    Span::raw(format_gas_price(price)),
+                ])
+            };
+            vec![
+                row("now", g.current),
+                row("p50", g.p50),
+                row("p90", g.p90),
+                row("max", g.max),
+                Line::from(Span::styled(
+                    format!("#{} · last {} of {} blocks", g.height, g.samples, g.window),
+                    dim,
+                )),
+            ]
+        }
+    };
+
+    let widget = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Gas price — g close ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(get_accent_strong(theme)))
+            .style(Style::default().bg(Color::Black)),
+    );
+    f.render_widget(widget, panel);
+}
+
+fn draw_filter_preview(f: &mut Frame, app: &App, preview: &FilterPreview) {
+    // Right-hand side panel just under the (expanded) filter bar
+    let theme = app.theme();
+    let area = f.area();
+    let width = 56.min(area.width);
+    let height = 11.min(area.height.saturating_sub(4));
+    let panel = Rect {
+        x: area.width.saturating_sub(width),
+        y: 3.min(area.height),
+        width,
+        height,
+    };
+    if panel.height < 3 {
+        return;
+    }
+
+    f.render_widget(Clear, panel);
+
+    let dim = Style::default().fg(get_border(theme));
+    let row = |height: u64, tx: &TxLite| {
+        let what = tx
+            .actions
+            .iter()
+            .flatten()
+            .map(|a| match a {
+                ActionSummary::FunctionCall { method_name, .. } => method_name.as_str(),
+                other => crate::filter::action_type_name(other),
+            })
+            .next()
+            .unwrap_or("");
+        Line::from(vec![
+            Span::styled(format!("  #{height} "), dim),
+            Span::raw(format!(
+                "{} {} → {} ",
+                crate::util_text::short_hash(&tx.hash),
+                truncate_account(app.account_label(tx.signer_id.as_deref().unwrap_or("?")), 14),
+                truncate_account(app.account_label(tx.receiver_id.as_deref().unwrap_or("?")), 14),
+            )),
+            Span::styled(what.to_string(), Style::default().fg(get_accent(theme))),
+        ])
+    };
+    let section = |lines: &mut Vec<Line>, label: &str, level: ToastLevel, txs: &[(u64, TxLite)]| {
+        lines.push(Line::from(Span::styled(
+            label.to_string(),
+            Style::default()
+                .fg(get_toast_color(theme, level))
+                .add_modifier(Modifier::BOLD),
+        )));
+        if txs.is_empty() {
+            lines.push(Line::from(Span::styled("  (none in buffer)", dim)));
+        }
+        lines.extend(txs.iter().map(|(h, tx)| row(*h, tx)));
+    };
+
+    let mut lines: Vec<Line> = Vec::new();
+    section(&mut lines, "✓ matches", ToastLevel::Success, &preview.matching);
+    section(&mut lines, "✗ doesn't match", ToastLevel::Error, &preview.other);
+    let more = if preview.truncated { "+" } else { "" };
+    lines.push(Line::from(Span::styled(
+        format!("{}{more} txs scanned", preview.scanned),
+        dim,
+    )));
+
+    let widget = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Filter preview — Enter apply · Esc clear ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(get_accent_strong(theme)))
+            .style(Style::default().bg(Color::Black)),
+    );
+    f.render_widget(widget, panel);
+}
+
+fn draw_stake_panel(
+    f: &mut Frame,
+    theme: &Theme,
+    dist: Option<&StakeDistribution>,
+    previous: Option<&StakeDistribution>,
+    beside_gas: bool,
+) {
+    // Bottom-right above the footer like the gas panel (to its left when both are open)
+    let area = f.area();
+    let width = 38.min(area.width);
+    let height = 8.min(area.height.saturating_sub(1));
+    let right = if beside_gas { area.width.saturating_sub(38) } else { area.width };
+    let panel = Rect {
+        x: right.saturating_sub(width),
+        y: area.height.saturating_sub(height + 1),
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, panel);
+
+    let dim = Style::default().fg(get_border(theme));
+    let lines: Vec<Line> = match dist {
+        None => vec![Line::from(Span::styled("Waiting for the validators RPC…", dim))],
+        Some(d) => {
+            let was = |value: String| Span::styled(format!("  was {value}"), dim);
+            let mut nakamoto = vec![
+                Span::styled(format!("{:<10}", "nakamoto"), dim),
+                Span::styled(d.nakamoto.to_string(), Style::default().fg(get_accent(theme))),
+            ];
+            if let Some(p) = previous.filter(|p| p.nakamoto != d.nakamoto) {
+                nakamoto.push(was(p.nakamoto.to_string()));
+            }
+            let mut lines = vec![Line::from(nakamoto)];
+            for &(n, share) in &d.top_shares {
+                let mut row = vec![
+                    Span::styled(format!("{:<10}", format!("top {n}")), dim),
+                    Span::raw(format!("{share:>5.1}%")),
+                ];
+                if let Some(prev) = previous.and_then(|p| p.top_share(n)) {
+                    row.push(Span::styled(format!("  {:+.1}pp", share - prev), dim));
+                }
+                lines.push(Line::from(row));
+            }
+            lines.push(Line::from(Span::styled(
+                format!("largest {}", d.largest),
+                dim,
+            )));
+            lines.push(Line::from(Span::styled(
+                format!("epoch {} · {} validators", d.epoch_height, d.validators),
+                dim,
+            )));
+            lines
+        }
+    };
+
+    let widget = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Stake — v close ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(get_accent_strong(theme)))
+            .style(Style::default().bg(Color::Black)),
+    );
+    f.render_widget(widget, panel);
+}
+
+fn draw_endpoints_overlay(f: &mut Frame, theme: &Theme, draft: &EndpointDraft) {
+    // Centered overlay (70% width, fixed height: two labeled inputs + hint)
+    let area = f.area();
+    let width = (area.width * 7) / 10;
+    let height = 8.min(area.height);
+    let overlay = Rect {
+        x: (area.width.saturating_sub(width)) / 2,
+        y: (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, overlay);
+
+    let fields = [
+        ("RPC      ", draft.near_node_url.as_str(), ""),
+        ("Archival ", draft.archival_rpc_url.as_str(), "(empty = disabled)"),
+    ];
+    let mut lines: Vec<Line> = Vec::with_capacity(5);
+    for (i, (label, value, placeholder)) in fields.iter().enumerate() {
+        let focused = draft.field == i;
+        let marker = if focused { "▸ " } else { "  " };
+        let value_span = if value.is_empty() {
+            Span::styled(*placeholder, Style::default().fg(get_border(theme)))
+        } else {
+            Span::styled(
+                *value,
+                Style::default().fg(if focused { get_accent(theme) } else { Color::White }),
+            )
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{marker}{label}"), Style::default().fg(get_accent_strong(theme))),
+            value_span,
+        ]));
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(Span::styled(
+        "Tab: switch field • Enter: apply (keeps buffer) • Esc: cancel",
+        Style::default().fg(get_border(theme)),
+    )));
+
+    let inner_x = overlay.x + 1;
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Endpoints (Ctrl+E) ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(get_accent_strong(theme)))
+            .style(Style::default().bg(Color::Black)),
+    );
+    f.render_widget(paragraph, overlay);
+
+    // Cursor at the end of the focused input
+    let (label, value, _) = fields[draft.field.min(1)];
+    let x = inner_x + (2 + label.len() + value.chars().count()) as u16;
+    let y = overlay.y + 1 + (draft.field.min(1) * 2) as u16;
+    if x < overlay.x + overlay.width.saturating_sub(1) && y < overlay.y + overlay.height {
+        f.set_cursor_position((x, y));
+    }
+}
+
+fn draw_sources_overlay(f: &mut Frame, theme: &Theme, badges: &[SourceBadge], sel: usize) {
+    // Centered overlay (70% width, one row per source + error line + hint)
+    let area = f.area();
+    let width = (area.width * 7) / 10;
+    let height = (badges.len() as u16 * 2 + 4).min(area.height);
+    let overlay = Rect {
+        x: (area.width.saturating_sub(width)) / 2,
+        y: (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, overlay);
+
+    let dim = Style::default().fg(get_border(theme));
+    let mut lines: Vec<Line> = Vec::with_capacity(badges.len() * 2 + 2);
+    for (i, badge) in badges.iter().enumerate() {
+        let marker = if i == sel { "▸ " } else { "  " };
+        let name_style = if i == sel {
+            Style::default().fg(get_accent(theme)).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(get_accent_strong(theme))
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{marker}{:9}", badge.kind.label()), name_style),
+            Span::styled(
+                format!("● {}", badge.status.label()),
+                Style::default().fg(health_color(theme, badge.status)),
+            ),
+        ]));
+        lines.push(Line::from(match &badge.last_error {
+            Some((age, err)) => Span::styled(format!("    {}s ago: {err}", age.as_secs()), dim),
+            None => Span::styled("    no errors", dim),
+        }));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "↑/↓: select • Enter/Space: switch on/off (this session) • Esc: close",
+        dim,
+    )));
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Sources (Ctrl+S) ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(get_accent_strong(theme)))
+            .style(Style::default().bg(Color::Black)),
+    );
+    f.render_widget(paragraph, overlay);
+}
+
+// ===============================
+// Helpers
+// ===============================
+/// Horizontal list padding (none in compact mode)
+fn row_padding(app: &App) -> Padding {
+    let side = if app.compact() { 0 } else { 1 };
+    Padding {
+        left: side,
+        right: side,
+        top: 0,
+        bottom: 0,
+    }
+}
+
+/// Badge color: toast colors for ok/degraded/down, border gray otherwise
+fn health_color(theme: &Theme, status: HealthStatus) -> Color {
+    match status {
+        HealthStatus::Ok => get_toast_color(theme, ToastLevel::Success),
+        HealthStatus::Degraded => get_toast_color(theme, ToastLevel::Warn),
+        HealthStatus::Down => get_toast_color(theme, ToastLevel::Error),
+        HealthStatus::Unknown | HealthStatus::Disabled => get_border(theme),
+    }
 }
 
-// ===============================
-// Helpers
-// ===============================
 fn truncate_account(account: &str, max_len: usize) -> String {
     if account.len() <= max_len {
         return account.to_string();
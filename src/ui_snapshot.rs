@@ -1,9 +1,23 @@
 use serde::{Deserialize, Serialize};
-
+use std::sync::Arc;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+use crate::filter::FilterField;
+use crate::gas_oracle::format_gas_price;
+use crate::source_health::SourcePhase;
+use crate::toast::{Toast, ToastLevel};
+use crate::status_bar::StatusSegment;
+use crate::app::Endpoints;
+use crate::snapshot_cache::{BlocksKey, SnapshotCache, SnapshotStats, TxsKey};
 use crate::{App, InputMode};
 
 /// Block source type for two-list architecture
 #[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum UiBlockSource {
     Forward,           // Live/cached block from forward list
@@ -13,6 +27,7 @@ pub enum UiBlockSource {
 
 /// One row in the Blocks pane (filtered view).
 #[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct UiBlockRow {
     pub index: usize,
     pub height: u64,
@@ -22,20 +37,212 @@ pub struct UiBlockRow {
     pub available: bool,
     pub is_selected: bool,
     pub source: UiBlockSource,  // NEW: tracks whether forward or backfill
+    /// Warm-start row from the last session, not yet refreshed
+    pub stale: bool,
 }
 
 /// One row in the Transactions pane (filtered view).
 #[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct UiTxRow {
     pub index: usize,
     pub hash: String,
     pub signer_id: String,
     pub receiver_id: String,
     pub is_selected: bool,
+    /// Bridge summary ("bridge in from Ethereum (tx 0x…)") for recognized bridge txs
+    pub bridge: Option<String>,
+}
+
+/// One toast notification.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct UiToast {
+    pub id: u64,
+    pub level: ToastLevel,
+    pub message: String,
+    /// Creation time (unix ms)
+    pub created_ms: i64,
+}
+
+/// Health badge for one configured block source.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct UiSourceBadge {
+    /// "WS", "RPC" or "Archival"
+    pub name: String,
+    /// "waiting", "ok", "degraded", "down" or "off"
+    pub status: String,
+    /// Last error with its age, e.g. "12s ago: timeout"
+    pub last_error: Option<String>,
+}
+
+/// Why a list pane has no rows (drives its guided empty state).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum EmptyReason {
+    /// No block yet and the live source is failing (r retries)
+    SourceError { source: String, error: String },
+    /// The filter hides all `hidden` rows (Esc clears it)
+    FilteredOut { hidden: usize },
+    /// Waiting for the first block, with no archival endpoint for backfill
+    NoArchival,
+    /// Waiting for the first block
+    Waiting,
+}
+
+impl EmptyReason {
+    /// Headline; `noun` is what the pane lists ("blocks", "transactions")
+    pub fn title(&self, noun: &str) -> String {
+        match self {
+            EmptyReason::SourceError { source, .. } => format!("{source} unreachable"),
+            EmptyReason::FilteredOut { hidden } => format!("Filter hides all {hidden} {noun}"),
+            EmptyReason::NoArchival | EmptyReason::Waiting => "Waiting for the first block…".to_string(),
+        }
+    }
+
+    /// What to do about it
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            EmptyReason::SourceError { .. } => Some("press r to retry"),
+            EmptyReason::FilteredOut { .. } => Some("press Esc to clear filter"),
+            EmptyReason::NoArchival => Some("set ARCHIVAL_RPC_URL to backfill older blocks"),
+            EmptyReason::Waiting => None,
+        }
+    }
+}
+
+/// Guided empty state for a list pane (text pre-built from `reason`).
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct UiEmptyState {
+    pub reason: EmptyReason,
+    pub title: String,
+    /// The source error for `source_error`
+    pub detail: Option<String>,
+    pub hint: Option<String>,
+}
+
+impl UiEmptyState {
+    fn new(reason: EmptyReason, noun: &str) -> Self {
+        let detail = match &reason {
+            EmptyReason::SourceError { error, .. } => Some(error.clone()),
+            _ => None,
+        };
+        Self {
+            title: reason.title(noun),
+            hint: reason.hint().map(str::to_string),
+            detail,
+            reason,
+        }
+    }
+}
+
+/// Why the Blocks pane has no rows (only meaningful when it has none)
+pub fn blocks_empty_reason(app: &App) -> EmptyReason {
+    use crate::source_health::{HealthStatus, SourceKind};
+
+    let (_, _, total) = app.filtered_blocks();
+    if total > 0 {
+        return EmptyReason::FilteredOut { hidden: total };
+    }
+    // Unreachable only while no live source has succeeded
+    let now = Instant::now();
+    let live: Vec<_> = app
+        .sources()
+        .iter()
+        .filter(|s| s.enabled && s.kind != SourceKind::Archival)
+        .collect();
+    if !live.iter().any(|s| s.status(now) == HealthStatus::Ok) {
+        if let Some((kind, error)) = live
+            .iter()
+            .find_map(|s| Some((s.kind, s.last_error(now)?.1)))
+        {
+            return EmptyReason::SourceError {
+                source: kind.label().to_string(),
+                error: error.to_string(),
+            };
+        }
+    }
+    if app.endpoints().archival_rpc_url.is_none() {
+        EmptyReason::NoArchival
+    } else {
+        EmptyReason::Waiting
+    }
+}
+
+/// Why the Txs pane has no rows, when it's the filter hiding the selected block's txs
+pub fn txs_empty_reason(app: &App) -> Option<EmptyReason> {
+    let hidden = app.current_block()?.transactions.len();
+    (hidden > 0 && app.filtered_txs_len() == 0).then_some(EmptyReason::FilteredOut { hidden })
+}
+
+/// Gas price guidance over the rolling window (prices pre-formatted as mⓃ/Tgas).
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct UiGasPanel {
+    pub height: u64,
+    pub current: String,
+    pub p50: String,
+    pub p90: String,
+    pub max: String,
+    pub samples: usize,
+    pub window: usize,
+}
+
+/// Snapshot build timings for the profiler overlay (times in µs).
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct UiProfiler {
+    pub builds: u64,
+    pub last_us: f64,
+    pub avg_us: f64,
+    /// Estimated time saved per snapshot by reusing unchanged sections
+    pub saved_us: f64,
+    /// `saved_us` as a share of a full rebuild (0-100)
+    pub saved_pct: f64,
+    /// Share of snapshots that reused the Blocks / Txs rows (0-100)
+    pub blocks_reuse_pct: f64,
+    pub txs_reuse_pct: f64,
+    /// Average cost of rebuilding the Blocks / Txs rows
+    pub blocks_rebuild_us: f64,
+    pub txs_rebuild_us: f64,
+    /// Snapshots that kept outdated Blocks rows to stay within the frame budget
+    pub blocks_deferred: u64,
+}
+
+impl From<&SnapshotStats> for UiProfiler {
+    fn from(s: &SnapshotStats) -> Self {
+        UiProfiler {
+            builds: s.builds,
+            last_us: s.last_us,
+            avg_us: s.avg_us,
+            saved_us: s.saved_per_build_us(),
+            saved_pct: s.saved_pct(),
+            blocks_reuse_pct: s.blocks.reuse_pct(),
+            txs_reuse_pct: s.txs.reuse_pct(),
+            blocks_rebuild_us: s.blocks.rebuild_us,
+            blocks_deferred: s.blocks.deferred,
+            txs_rebuild_us: s.txs.rebuild_us,
+        }
+    }
+}
+
+impl From<&Toast> for UiToast {
+    fn from(t: &Toast) -> Self {
+        UiToast {
+            id: t.id,
+            level: t.level,
+            message: t.message.clone(),
+            created_ms: t.created_ms,
+        }
+    }
 }
 
 /// DOM-/JSON-/TUI-friendly snapshot of `App` state (Rust → UI).
 #[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct UiSnapshot {
     /// 0 = Blocks, 1 = Txs, 2 = Details
     pub pane: usize,
@@ -49,16 +256,26 @@ pub struct UiSnapshot {
     /// Whether the filter input is focused (InputMode::Filter).
     pub filter_focused: bool,
 
-    /// Blocks pane rows (filtered + backfill combined).
-    pub blocks: Vec<UiBlockRow>,
+    /// Blocks pane rows (filtered + backfill combined), shared with the
+    /// snapshot cache.
+    pub blocks: Arc<[UiBlockRow]>,
     pub blocks_total: usize,
     pub blocks_scroll_offset: usize,  // NEW: for vertical centering
     pub selected_block_height: Option<u64>,
     pub viewing_cached: bool,
 
-    /// Transactions pane rows (filtered).
-    pub txs: Vec<UiTxRow>,
+    /// Guided empty state when the Blocks pane has no rows.
+    pub blocks_empty: Option<UiEmptyState>,
+
+    /// Dimmed diagonal watermark over the Blocks pane while the newest block
+    /// is older than `STALE_AFTER_SECS` ("STALE — last block 94s ago").
+    pub blocks_stale: Option<String>,
+
+    /// Transactions pane rows (filtered), shared with the snapshot cache.
+    pub txs: Arc<[UiTxRow]>,
     pub txs_total: usize,
+    /// Guided empty state when the filter hides every tx of the selected block.
+    pub txs_empty: Option<UiEmptyState>,
 
     /// Details pane (windowed JSON for performance)
     pub details: String,
@@ -66,70 +283,89 @@ pub struct UiSnapshot {
     pub details_scroll_line: usize, // Current scroll line (0-based)
     pub details_total_lines: usize, // Total lines in buffer
     pub details_truncated: bool,  // Whether content was truncated at MAX_LINES
+    pub details_max_lines: usize, // MAX_LINES, for the truncation notice
+    /// Details jump prompt being typed (digits only; `None` = closed)
+    pub details_goto: Option<String>,
     pub details_fullscreen: bool,
     pub fullscreen_mode: String, // "Scroll" or "Navigate"
     pub fullscreen_content_type: String, // "BlockRawJson", "TransactionRawJson", or "ParsedDetails"
+    pub zoomed_pane: Option<String>, // "Blocks", "Txs" or "Details" (None = normal layout)
 
-    /// Toast notification text (if any).
+    /// Toast notification text (if any): newest visible toast.
     pub toast: Option<String>,
 
+    /// Visible toast stack, oldest first (at most `TOAST_MAX_VISIBLE`).
+    pub toasts: Vec<UiToast>,
+
+    /// Toasts waiting for a visible slot.
+    pub toasts_waiting: usize,
+
+    /// Whether the toast history overlay is open (n key).
+    pub toast_history_visible: bool,
+
+    /// Past toasts, newest first (only filled while the overlay is open).
+    pub toast_history: Vec<UiToast>,
+
     /// Whether keyboard shortcuts overlay is visible (Web/Tauri render this).
     pub show_shortcuts: bool,
 
     /// Block height currently being fetched from archival RPC (if any).
     pub loading_block: Option<u64>,
+
+    /// Blocks whose filter results are still being recomputed (0 = counts are fresh).
+    pub filter_pending: usize,
+
+    /// RPC endpoint the block source is polling.
+    pub near_node_url: String,
+
+    /// Archival RPC endpoint (None = archival fetch disabled).
+    pub archival_rpc_url: Option<String>,
+
+    /// Whether the gas price panel is open (g key).
+    pub gas_panel_visible: bool,
+
+    /// Gas guidance (None until a block with a gas price arrives).
+    pub gas: Option<UiGasPanel>,
+
+    /// Block source health badges (also in `status` as segments).
+    pub sources: Vec<UiSourceBadge>,
+
+    /// Live feed phase: skeleton rows while `connecting` / `first_sync`,
+    /// per-pane indicator while `stalled`.
+    pub source_phase: SourcePhase,
+
+    /// Footer status bar segments: core indicators, then contributed ones
+    /// (render in order; on overflow hide the lowest `priority` first).
+    pub status: Vec<StatusSegment>,
+
+    /// Compact mode (- key): short hashes, no when column, tight rows.
+    pub compact: bool,
+
+    /// Snapshot build timings (only while the profiler overlay is open, P key).
+    pub profiler: Option<UiProfiler>,
 }
 
 impl UiSnapshot {
     /// Build a snapshot from the current app state.
     pub fn from_app(app: &App) -> Self {
+        Self::build(app, &mut SnapshotCache::default())
+    }
+
+    /// Build a snapshot, reusing `cache`'s Blocks / Txs rows when `App` reports
+    /// them unchanged (see `snapshot_cache`)
+    pub fn build(app: &App, cache: &mut SnapshotCache) -> Self {
+        let start = Instant::now();
         let pane = app.pane();
         let selection_slot_text = app.selection_slot_text();
 
-        // Blocks: forward list (filtered, newest → oldest)
-        let (blocks_filtered, selected_block_idx_opt, blocks_total) = app.filtered_blocks();
+        // Blocks: forward list (filtered, newest → oldest) + backfill slots
         let selected_block_height = app.selected_block_height();
-
-        let mut blocks: Vec<UiBlockRow> = blocks_filtered
-            .iter()
-            .enumerate()
-            .map(|(idx, b)| UiBlockRow {
-                index: idx,
-                height: b.height,
-                hash: b.hash.clone(),
-                when: b.when.clone(),
-                tx_count: b.tx_count,
-                available: app.is_block_height_available(b.height),
-                is_selected: selected_block_idx_opt == Some(idx),
-                source: UiBlockSource::Forward,
-            })
-            .collect();
-
-        // Blocks: append backfill slots (second list, backwards in time from anchor)
-        let loading_block = app.loading_block();
-        for slot in app.back_slots() {
-            // Skip if block already loaded into forward list/cache
-            if app.is_block_available(slot.height) {
-                continue;
-            }
-
-            let is_loading = loading_block == Some(slot.height);
-
-            blocks.push(UiBlockRow {
-                index: blocks.len(),  // Continue index sequence
-                height: slot.height,
-                hash: slot.hash.clone(),
-                when: String::new(),
-                tx_count: 0,
-                available: false,
-                is_selected: false,  // Placeholders never selected
-                source: if is_loading {
-                    UiBlockSource::BackfillLoading
-                } else {
-                    UiBlockSource::BackfillPending
-                },
-            });
-        }
+        let viewing_cached = app.is_viewing_cached_block();
+        let blocks_key = BlocksKey {
+            rev: app.blocks_rev(),
+            viewing_cached: selected_block_height.filter(|_| viewing_cached),
+        };
+        let (blocks, blocks_total) = cache.blocks(blocks_key, selected_block_height, || block_rows(app));
 
         // Compute scroll offset for vertical centering (like TUI ui.rs:439)
         let viewport_rows = 24;  // Reasonable default for web viewport
@@ -146,21 +382,20 @@ impl UiSnapshot {
             }
         }
 
-        let viewing_cached = app.is_viewing_cached_block();
-
         // Transactions (filtered for current block)
-        let (txs_vec, selected_tx_idx, txs_total) = app.txs();
-        let txs: Vec<UiTxRow> = txs_vec
-            .into_iter()
-            .enumerate()
-            .map(|(idx, tx)| UiTxRow {
-                index: idx,
-                hash: tx.hash.clone(),
-                signer_id: tx.signer_id.clone().unwrap_or_default(),
-                receiver_id: tx.receiver_id.clone().unwrap_or_default(),
-                is_selected: idx == selected_tx_idx,
-            })
-            .collect();
+        let txs_key = TxsKey {
+            rev: app.blocks_rev(),
+            block: app.current_block().map(|b| b.height),
+        };
+        let (txs, txs_total) = cache.txs(txs_key, app.sel_tx(), || tx_rows(app));
+        let blocks_empty = blocks
+            .is_empty()
+            .then(|| UiEmptyState::new(blocks_empty_reason(app), "blocks"));
+        let txs_empty = txs
+            .is_empty()
+            .then(|| txs_empty_reason(app))
+            .flatten()
+            .map(|reason| UiEmptyState::new(reason, "transactions"));
 
         // Details: use windowed view (prevents UI freeze on huge JSON)
         let details = app.details_window();
@@ -178,13 +413,21 @@ impl UiSnapshot {
             crate::app::FullscreenContentType::TransactionRawJson => "TransactionRawJson".to_string(),
             crate::app::FullscreenContentType::ParsedDetails => "ParsedDetails".to_string(),
         };
+        let zoomed_pane = app.zoomed_pane().map(|z| z.name().to_string());
         let toast = app.toast_message().map(|s| s.to_string());
+        let toasts = app.visible_toasts().into_iter().map(UiToast::from).collect();
+        let toast_history_visible = app.toast_history_visible();
+        let toast_history = if toast_history_visible {
+            app.toast_history().into_iter().map(UiToast::from).collect()
+        } else {
+            Vec::new()
+        };
         let show_shortcuts = app.show_shortcuts();
         let loading_block = app.loading_block();
         let filter_query = app.filter_query().to_string();
         let filter_focused = app.input_mode() == InputMode::Filter;
 
-        UiSnapshot {
+        let mut snapshot = UiSnapshot {
             pane,
             selection_slot_text,
             filter_query,
@@ -194,27 +437,139 @@ impl UiSnapshot {
             blocks_scroll_offset,
             selected_block_height,
             viewing_cached,
+            blocks_empty,
+            blocks_stale: app.stale_watermark(),
             txs,
             txs_total,
+            txs_empty,
             details,
             details_scroll,
             details_scroll_line,
             details_total_lines,
             details_truncated,
+            details_max_lines: crate::app::DetailsBuffer::MAX_LINES,
+            details_goto: app.details_goto_draft().map(str::to_string),
             details_fullscreen,
             fullscreen_mode,
             fullscreen_content_type,
+            zoomed_pane,
             toast,
+            toasts,
+            toasts_waiting: app.waiting_toasts(),
+            toast_history_visible,
+            toast_history,
             show_shortcuts,
             loading_block,
+            filter_pending: app.filter_pending(),
+            near_node_url: app.endpoints().near_node_url.clone(),
+            archival_rpc_url: app.endpoints().archival_rpc_url.clone(),
+            gas_panel_visible: app.gas_panel_visible(),
+            gas: app.gas_guidance().map(|g| UiGasPanel {
+                height: g.height,
+                current: format_gas_price(g.current),
+                p50: format_gas_price(g.p50),
+                p90: format_gas_price(g.p90),
+                max: format_gas_price(g.max),
+                samples: g.samples,
+                window: g.window,
+            }),
+            sources: app
+                .source_badges()
+                .into_iter()
+                .map(|b| UiSourceBadge {
+                    name: b.kind.label().to_string(),
+                    status: b.status.label().to_string(),
+                    last_error: b
+                        .last_error
+                        .map(|(age, e)| format!("{}s ago: {e}", age.as_secs())),
+                })
+                .collect(),
+            source_phase: app.source_phase(),
+            status: app.status_segments(0),
+            compact: app.compact(),
+            profiler: None,
+        };
+        cache.stats.record_build(start.elapsed().as_secs_f64() * 1e6);
+        if app.profiler_visible() {
+            snapshot.profiler = Some(UiProfiler::from(&cache.stats));
         }
+        snapshot
     }
 }
 
+/// Blocks pane rows: the filtered forward list, then backfill placeholders
+fn block_rows(app: &App) -> (Vec<UiBlockRow>, usize) {
+    let (blocks_filtered, selected_block_idx_opt, blocks_total) = app.filtered_blocks();
+    let mut blocks: Vec<UiBlockRow> = blocks_filtered
+        .iter()
+        .enumerate()
+        .map(|(idx, b)| UiBlockRow {
+            index: idx,
+            height: b.height,
+            hash: b.hash.clone(),
+            when: app.block_when(b),
+            tx_count: b.tx_count,
+            available: app.is_block_height_available(b.height),
+            is_selected: selected_block_idx_opt == Some(idx),
+            source: UiBlockSource::Forward,
+            stale: app.is_block_stale(b.height),
+        })
+        .collect();
+
+    // Blocks: append backfill slots (second list, backwards in time from anchor)
+    let loading_block = app.loading_block();
+    for slot in app.back_slots() {
+        // Skip if block already loaded into forward list/cache
+        if app.is_block_available(slot.height) {
+            continue;
+        }
+
+        let is_loading = loading_block == Some(slot.height);
+
+        blocks.push(UiBlockRow {
+            index: blocks.len(),  // Continue index sequence
+            height: slot.height,
+            hash: slot.hash.clone(),
+            when: String::new(),
+            tx_count: 0,
+            available: false,
+            is_selected: false,  // Placeholders never selected
+            source: if is_loading {
+                UiBlockSource::BackfillLoading
+            } else {
+                UiBlockSource::BackfillPending
+            },
+            stale: false,
+        });
+    }
+
+    (blocks, blocks_total)
+}
+
+/// Txs pane rows for the current block (filtered)
+fn tx_rows(app: &App) -> (Vec<UiTxRow>, usize) {
+    let (txs_vec, selected_tx_idx, txs_total) = app.txs();
+    let txs = txs_vec
+        .into_iter()
+        .enumerate()
+        .map(|(idx, tx)| UiTxRow {
+            index: idx,
+            hash: tx.hash.clone(),
+            signer_id: tx.signer_id.clone().unwrap_or_default(),
+            receiver_id: tx.receiver_id.clone().unwrap_or_default(),
+            is_selected: idx == selected_tx_idx,
+            bridge: crate::bridge::detect(&tx).map(|b| b.summary()),
+        })
+        .collect();
+    (txs, txs_total)
+}
+
 /// Frontend-agnostic high-level UI actions (UI → Rust).
 ///
 /// These are what TUI/web/Tauri frontends should send into the core.
-#[derive(Debug, Clone, Deserialize)]
+/// `VariantNames` lists the `type` tags (the control channel's allowlist).
+#[derive(Debug, Clone, Deserialize, strum::VariantNames)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 pub enum UiAction {
     /// Update the filter query (applied immediately).
@@ -229,12 +584,24 @@ pub enum UiAction {
     /// Select a tx row by index in the filtered list.
     SelectTx { index: usize },
 
+    /// Open a `nearx://` deep link (tx / block / account / home), as the router does.
+    OpenRoute { link: String },
+
     /// Toggle details fullscreen mode.
     ToggleDetailsFullscreen,
 
+    /// Zoom the focused pane to the full body, or restore the layout (`z`).
+    ToggleZoom,
+
+    /// Toggle the toast history overlay (n key).
+    ToggleToastHistory,
+
     /// Toggle keyboard shortcuts overlay (? key - Web/Tauri only for now).
     ToggleShortcuts,
 
+    /// Switch to the next theme variant (C key).
+    CycleTheme,
+
     /// Keyboard navigation (Arrow keys, PageUp/Down, Tab, Vim keys, etc.).
     Key {
         code: String,
@@ -246,6 +613,18 @@ pub enum UiAction {
 
     /// Copy JSON / focused data (pane-aware).
     CopyFocusedJson,
+
+    /// Append a negated term for the selected tx's `field` to the filter
+    /// (`x` = signer, `X` = receiver).
+    ExcludeSelected { field: FilterField },
+
+    /// Switch RPC / archival endpoints without restarting (empty or missing
+    /// `archival_rpc_url` disables archival fetch).
+    SetEndpoints {
+        near_node_url: String,
+        #[serde(default)]
+        archival_rpc_url: Option<String>,
+    },
 }
 
 /// Apply a UI action to the core `App`.
@@ -266,12 +645,25 @@ pub fn apply_ui_action(app: &mut App, action: UiAction) {
         UiAction::SelectTx { index } => {
             app.select_tx_clamped(index);
         }
+        UiAction::OpenRoute { link } => match crate::router::parse_link(&link) {
+            Some(route) => app.apply_route_link(&route),
+            None => app.log_debug(format!("OpenRoute: not a NEARx link: {link}")),
+        },
         UiAction::ToggleDetailsFullscreen => {
             app.toggle_details_fullscreen();
         }
+        UiAction::ToggleZoom => {
+            app.toggle_zoom();
+        }
         UiAction::ToggleShortcuts => {
             app.toggle_shortcuts();
         }
+        UiAction::ToggleToastHistory => {
+            app.toggle_toast_history();
+        }
+        UiAction::CycleTheme => {
+            app.cycle_theme();
+        }
         UiAction::Key {
             code,
             ctrl,
@@ -280,10 +672,40 @@ pub fn apply_ui_action(app: &mut App, action: UiAction) {
             meta,
         } => handle_key(app, &code, ctrl || meta, shift),
         UiAction::CopyFocusedJson => handle_copy(app),
+        UiAction::ExcludeSelected { field } => app.exclude_selected(field),
+        UiAction::SetEndpoints {
+            near_node_url,
+            archival_rpc_url,
+        } => app.request_endpoints(Endpoints {
+            near_node_url,
+            archival_rpc_url,
+        }),
     }
 }
 
 fn handle_key(app: &mut App, code: &str, _ctrl: bool, shift: bool) {
+    // Toast history overlay is modal: only n/Esc (close) get through
+    if app.toast_history_visible() {
+        if matches!(code, "n" | "Escape") {
+            app.hide_toast_history();
+        }
+        return;
+    }
+
+    // Details jump prompt (`:120` Enter, `50%`): digits edit it, other keys close it
+    if app.details_goto_draft().is_some() {
+        match code {
+            "Enter" => app.details_goto_commit(false),
+            "%" => app.details_goto_commit(true),
+            "Backspace" => app.details_goto_backspace(),
+            d if d.len() == 1 && d.as_bytes()[0].is_ascii_digit() => {
+                app.details_goto_add_char(d.as_bytes()[0] as char)
+            }
+            _ => app.details_goto_cancel(),
+        }
+        return;
+    }
+
     // Special handling when Details is fullscreen: arrows scroll the buffer
     if app.details_fullscreen() {
         match code {
@@ -313,13 +735,37 @@ fn handle_key(app: &mut App, code: &str, _ctrl: bool, shift: bool) {
                 app.details_end();
                 return;
             }
+            "0" if app.gas_profile_active() => {
+                app.gas_profile_back();
+                return;
+            }
+            "e" if app.timeline_has_errors() => {
+                app.toggle_timeline_errors();
+                return;
+            }
+            d @ ("1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9") if app.gas_profile_active() => {
+                app.gas_profile_drill(d.parse().unwrap_or(1));
+                return;
+            }
+            ":" => {
+                app.details_goto_start("");
+                return;
+            }
+            d @ ("1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9") => {
+                app.details_goto_start(d);
+                return;
+            }
+            "{" | "}" => {
+                app.details_jump_section(code == "}");
+                return;
+            }
             " " => {
                 // Space exits fullscreen
                 app.toggle_details_fullscreen();
                 return;
             }
-            "Escape" => {
-                // Esc exits fullscreen
+            "Escape" | "z" => {
+                // Esc / z exit fullscreen
                 app.toggle_details_fullscreen();
                 return;
             }
@@ -349,25 +795,25 @@ fn handle_key(app: &mut App, code: &str, _ctrl: bool, shift: bool) {
         "Home" => app.home(),
         "End" => app.end(),
 
-        // Tab / Shift+Tab for pane cycling (BLOCKED in fullscreen to prevent impossible-to-exit state).
+        // Tab / Shift+Tab for pane cycling (BLOCKED while zoomed: focus stays on the zoomed pane).
         "Tab" if !shift => {
-            if !app.details_fullscreen() {
+            if app.zoomed_pane().is_none() {
                 app.next_pane();
             }
-            // Ignore Tab in fullscreen mode
+            // Ignore Tab in zoom mode
         }
         "Tab" if shift => {
-            if !app.details_fullscreen() {
+            if app.zoomed_pane().is_none() {
                 app.prev_pane();
             }
-            // Ignore Shift+Tab in fullscreen mode
+            // Ignore Shift+Tab in zoom mode
         }
 
-        // Esc: priority-based handling (exit fullscreen > clear filter > no-op).
+        // Esc: priority-based handling (restore zoom > clear filter > no-op).
         "Escape" => {
-            if app.details_fullscreen() {
-                // Priority 1: Exit fullscreen if open
-                app.toggle_details_fullscreen();
+            if app.zoomed_pane().is_some() {
+                // Priority 1: Restore the layout if a pane is zoomed
+                app.toggle_zoom();
             } else if !app.filter_query().is_empty() {
                 // Priority 2: Clear filter if non-empty
                 app.clear_filter();
@@ -381,6 +827,63 @@ fn handle_key(app: &mut App, code: &str, _ctrl: bool, shift: bool) {
         // Space: toggle details fullscreen.
         " " => app.toggle_details_fullscreen(),
 
+        // z: zoom / restore the focused pane.
+        "z" => app.toggle_zoom(),
+
+        // n: recall past notifications.
+        "n" => app.toggle_toast_history(),
+
+        // t: receipt timeline for the selected tx (rendered into Details).
+        "t" => app.request_timeline(),
+
+        // e: expand / collapse raw error JSON in a timeline with failures.
+        "e" if app.timeline_has_errors() => app.toggle_timeline_errors(),
+
+        // g: gas price panel (current + rolling percentiles).
+        "g" => app.toggle_gas_panel(),
+
+        // -: compact mode (short hashes, no when column, tight rows).
+        "-" => app.toggle_compact(),
+
+        // P: snapshot profiler overlay (build time, reused sections).
+        "P" => app.toggle_profiler(),
+
+        // T: timezone for every timestamp (UTC → local → configured offset).
+        "T" => app.cycle_timezone(),
+
+        // C: next color theme (dark → light → colorblind variants → high contrast).
+        "C" => app.cycle_theme(),
+
+        // G: receipt gas profile for the selected tx; 1-9 / 0 drill in / out while shown.
+        "G" => app.request_gas_profile(),
+        "0" if app.gas_profile_active() => app.gas_profile_back(),
+        d @ ("1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9") if app.gas_profile_active() => {
+            app.gas_profile_drill(d.parse().unwrap_or(1))
+        }
+
+        // Details: `:N` Enter / `N%` jump to a line / percentage; { } hop between top-level keys.
+        ":" if app.pane() == 2 => app.details_goto_start(""),
+        d @ ("1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9") if app.pane() == 2 => {
+            app.details_goto_start(d)
+        }
+        "{" | "}" if app.pane() == 2 => app.details_jump_section(code == "}"),
+
+        // 1-9 (Blocks / Txs): open the numbered tx under `related` in Details.
+        d @ ("1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9") if !app.related().is_empty() => {
+            app.open_related(d.parse().unwrap_or(1))
+        }
+
+        // r: retry the block sources while the Blocks pane shows a source error.
+        "r" if app.filtered_blocks_len() == 0
+            && matches!(blocks_empty_reason(app), EmptyReason::SourceError { .. }) =>
+        {
+            app.retry_sources()
+        }
+
+        // x / X: hide everything from the selected signer / to the selected receiver.
+        "x" => app.exclude_selected(FilterField::Signer),
+        "X" => app.exclude_selected(FilterField::Receiver),
+
         // Quit is a no-op for web/Tauri; TUI can layer its own logic.
         "q" | "Q" => {}
 
@@ -390,15 +893,24 @@ fn handle_key(app: &mut App, code: &str, _ctrl: bool, shift: bool) {
 }
 
 fn handle_copy(app: &mut App) {
-    if crate::copy_api::copy_current(app) {
-        let msg = match app.pane() {
-            0 => "Copied block".to_string(),
-            1 => "Copied transaction".to_string(),
-            2 => "Copied details".to_string(),
-            _ => "Copied".to_string(),
-        };
-        app.show_toast(msg);
-    } else {
-        app.show_toast("Copy failed".to_string());
+    match crate::copy_api::copy_current_report(app) {
+        Some(report) if report.ok() => {
+            let msg = match app.pane() {
+                0 => "Copied block".to_string(),
+                1 => "Copied transaction".to_string(),
+                2 => "Copied details".to_string(),
+                _ => "Copied".to_string(),
+            };
+            // Keep the payload on the pin board
+            if let Some(text) = crate::copy_api::current_text(app) {
+                app.record_copy(&msg, text);
+            }
+            // Name the backend so an OSC 52 / file fallback isn't a surprise
+            app.push_toast(ToastLevel::Success, format!("{msg} · {}", report.summary()));
+        }
+        Some(report) if !report.failures.is_empty() => {
+            app.push_toast(ToastLevel::Error, format!("Copy failed: {}", report.summary()));
+        }
+        _ => app.push_toast(ToastLevel::Error, "Copy failed".to_string()),
     }
 }
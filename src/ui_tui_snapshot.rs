@@ -138,6 +138,14 @@ fn filter_from_snapshot(f: &mut Frame, area: Rect, snapshot: &UiSnapshot) {
 }
 
 fn body_from_snapshot(f: &mut Frame, area: Rect, snapshot: &UiSnapshot, theme: &Theme) {
+    // Zoomed pane takes the whole body.
+    match snapshot.zoomed_pane.as_deref() {
+        Some("Blocks") => return render_blocks_pane(f, area, snapshot),
+        Some("Txs") => return render_txs_pane(f, area, snapshot),
+        Some("Details") => return render_details_pane(f, area, snapshot, theme),
+        _ => {}
+    }
+
     // Responsive layout: on narrow terminals stack panes vertically.
     const NARROW_THRESHOLD: u16 = 80;
     let is_narrow = area.width < NARROW_THRESHOLD;
@@ -198,12 +206,15 @@ fn render_blocks_pane(f: &mut Frame, area: Rect, snapshot: &UiSnapshot) {
         .blocks
         .iter()
         .map(|b| {
-            let text = format!(
+            let mut text = format!(
                 "#{}  | {} txs | {}",
                 b.height, b.tx_count, b.when
             );
+            if b.stale {
+                text.push_str("  · stale");
+            }
             let item = ListItem::new(text);
-            if b.available {
+            if b.available && !b.stale {
                 item
             } else {
                 item.style(Style::default().fg(Color::DarkGray))
@@ -277,6 +288,10 @@ fn render_txs_pane(f: &mut Frame, area: Rect, snapshot: &UiSnapshot) {
             } else {
                 t.hash.clone()
             };
+            let label = match &t.bridge {
+                Some(bridge) => format!("{label} ⇄ {bridge}"),
+                None => label,
+            };
             ListItem::new(label)
         })
         .collect();
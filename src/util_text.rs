@@ -70,6 +70,18 @@ pub fn format_gas_compact(gas: u64) -> String {
     }
 }
 
+/// Hash shortened to 8 chars with a middle ellipsis for compact mode
+/// (e.g., "9fQx…k2P"); shorter input is returned unchanged
+pub fn short_hash(hash: &str) -> String {
+    let chars: Vec<char> = hash.chars().collect();
+    if chars.len() <= 8 {
+        return hash.to_string();
+    }
+    let head: String = chars[..4].iter().collect();
+    let tail: String = chars[chars.len() - 3..].iter().collect();
+    format!("{head}…{tail}")
+}
+
 /// Format NEAR amount with compact suffix for UI (e.g., "1.5Ⓝ")
 #[allow(dead_code)]
 pub fn format_near_compact(yoctonear: u128) -> String {
@@ -127,3 +139,15 @@ where
 {
     serializer.serialize_str(&value.to_string())
 }
+
+/// Serialize Option<u128> as string for JavaScript compatibility
+#[cfg(target_arch = "wasm32")]
+pub fn serialize_option_u128_as_string<S>(value: &Option<u128>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value {
+        Some(v) => serializer.serialize_str(&v.to_string()),
+        None => serializer.serialize_none(),
+    }
+}
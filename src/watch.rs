@@ -0,0 +1,327 @@
+//! Standing watches: filters kept running against every new block
+//!
+//! `W` turns the current filter query into a watch rule. Each live block is
+//! checked once against every rule; a rule with new matches raises a toast,
+//! bumps the unseen badge in the status bar and, when it has a webhook, posts
+//! the matches there (native). Rules persist in History; the watch manager
+//! (Ctrl+W) lists them with their hit counts.
+//!
+//! Only blocks newer than any seen before are checked, so backfill, archival
+//! fetches and re-delivered heights never fire a rule twice.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::filter::{compile_filter, haystack_matches, is_empty, CompiledFilter, TxHaystack};
+use crate::router::RouteV1;
+use crate::types::BlockRow;
+
+/// Matches listed per hit (toast count and webhook payload keep the total)
+pub const MAX_TXS_PER_HIT: usize = 20;
+
+/// A filter query kept running against new blocks
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WatchRule {
+    pub id: u32,
+    pub query: String,
+    /// POSTed a JSON payload on every hit (native)
+    #[serde(default)]
+    pub webhook: Option<String>,
+    pub created_ms: i64,
+    /// Matching txs since the rule was created
+    #[serde(default)]
+    pub hits: u64,
+    /// Height of the most recent hit
+    #[serde(default)]
+    pub last_height: Option<u64>,
+    /// Matches since the manager was last opened (not persisted)
+    #[serde(skip)]
+    pub unseen: u64,
+}
+
+/// One matching tx in a hit
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct WatchedTx {
+    pub hash: String,
+    pub signer: Option<String>,
+    pub receiver: Option<String>,
+}
+
+/// New matches of one rule in one block
+#[derive(Clone, Debug, PartialEq)]
+pub struct WatchHit {
+    pub rule_id: u32,
+    pub query: String,
+    pub webhook: Option<String>,
+    pub height: u64,
+    pub block_hash: String,
+    /// Total matching txs in the block
+    pub count: usize,
+    /// First `MAX_TXS_PER_HIT` of them
+    pub txs: Vec<WatchedTx>,
+}
+
+impl WatchHit {
+    /// Webhook body
+    pub fn payload(&self) -> Value {
+        let txs: Vec<Value> = self
+            .txs
+            .iter()
+            .map(|tx| {
+                json!({
+                    "hash": tx.hash,
+                    "signer": tx.signer,
+                    "receiver": tx.receiver,
+                    "link": RouteV1::Tx { hash: tx.hash.clone() }.url(),
+                })
+            })
+            .collect();
+        json!({
+            "source": "nearx",
+            "watch": { "id": self.rule_id, "query": self.query },
+            "height": self.height,
+            "block_hash": self.block_hash,
+            "count": self.count,
+            "txs": txs,
+        })
+    }
+}
+
+/// Watch rules with their compiled filters
+#[derive(Debug, Default)]
+pub struct WatchList {
+    rules: Vec<WatchRule>,
+    compiled: Vec<CompiledFilter>,
+    /// Newest height checked; older blocks are never checked
+    checked_height: Option<u64>,
+}
+
+impl WatchList {
+    /// Replace the rules (loaded from History)
+    pub fn load(&mut self, rules: Vec<WatchRule>) {
+        self.compiled = rules.iter().map(|r| compile_filter(&r.query)).collect();
+        self.rules = rules;
+    }
+
+    pub fn rules(&self) -> &[WatchRule] {
+        &self.rules
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Matches not yet seen in the manager, over all rules
+    pub fn unseen(&self) -> u64 {
+        self.rules.iter().map(|r| r.unseen).sum()
+    }
+
+    pub fn mark_seen(&mut self) {
+        for rule in &mut self.rules {
+            rule.unseen = 0;
+        }
+    }
+
+    /// Start watching `query`; fails for an empty query or one already watched
+    pub fn add(&mut self, query: &str, now_ms: i64) -> Result<&WatchRule, &'static str> {
+        let query = query.trim();
+        let compiled = compile_filter(query);
+        if is_empty(&compiled) {
+            return Err("Empty filter: nothing to watch");
+        }
+        if self.rules.iter().any(|r| r.query == query) {
+            return Err("Already watching this filter");
+        }
+        let id = self.rules.iter().map(|r| r.id).max().unwrap_or(0) + 1;
+        self.rules.push(WatchRule {
+            id,
+            query: query.to_string(),
+            webhook: None,
+            created_ms: now_ms,
+            hits: 0,
+            last_height: None,
+            unseen: 0,
+        });
+        self.compiled.push(compiled);
+        Ok(self.rules.last().expect("just pushed"))
+    }
+
+    pub fn remove(&mut self, id: u32) -> Option<WatchRule> {
+        let idx = self.rules.iter().position(|r| r.id == id)?;
+        self.compiled.remove(idx);
+        Some(self.rules.remove(idx))
+    }
+
+    /// Set or clear (`None` / blank) a rule's webhook URL
+    pub fn set_webhook(&mut self, id: u32, url: Option<String>) -> bool {
+        let Some(rule) = self.rules.iter_mut().find(|r| r.id == id) else {
+            return false;
+        };
+        rule.webhook = url.map(|u| u.trim().to_string()).filter(|u| !u.is_empty());
+        true
+    }
+
+    /// Check a newly arrived block against every rule (once per height, newer
+    /// than anything checked before); one hit per rule with matches
+    pub fn check_block(&mut self, block: &BlockRow) -> Vec<WatchHit> {
+        if self.checked_height.is_some_and(|h| block.height <= h) {
+            return Vec::new();
+        }
+        self.checked_height = Some(block.height);
+        if self.rules.is_empty() || block.transactions.is_empty() {
+            return Vec::new();
+        }
+
+        let haystacks: Vec<TxHaystack> =
+            block.transactions.iter().map(TxHaystack::from_tx).collect();
+        let mut hits = Vec::new();
+        for (rule, filter) in self.rules.iter_mut().zip(&self.compiled) {
+            let matching: Vec<_> = block
+                .transactions
+                .iter()
+                .zip(&haystacks)
+                .filter(|(_, h)| haystack_matches(h, filter))
+                .map(|(tx, _)| tx)
+                .collect();
+            if matching.is_empty() {
+                continue;
+            }
+            rule.hits += matching.len() as u64;
+            rule.unseen += matching.len() as u64;
+            rule.last_height = Some(block.height);
+            hits.push(WatchHit {
+                rule_id: rule.id,
+                query: rule.query.clone(),
+                webhook: rule.webhook.clone(),
+                height: block.height,
+                block_hash: block.hash.clone(),
+                count: matching.len(),
+                txs: matching
+                    .into_iter()
+                    .take(MAX_TXS_PER_HIT)
+                    .map(|tx| WatchedTx {
+                        hash: tx.hash.clone(),
+                        signer: tx.signer_id.clone(),
+                        receiver: tx.receiver_id.clone(),
+                    })
+                    .collect(),
+            });
+        }
+        hits
+    }
+}
+
+/// POST a hit to its webhook (native only)
+#[cfg(feature = "native")]
+pub async fn send_webhook(url: &str, hit: &WatchHit) -> anyhow::Result<()> {
+    let resp = reqwest::Client::new()
+        .post(url)
+        .timeout(std::time::Duration::from_secs(5))
+        .json(&hit.payload())
+        .send()
+        .await?;
+    resp.error_for_status()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::fixtures::{block, tx};
+
+    fn to_token(height: u64, signers: &[&str]) -> BlockRow {
+        let txs = signers
+            .iter()
+            .enumerate()
+            .map(|(i, s)| tx(&format!("T{height}_{i}"), s, "token.near"))
+            .collect();
+        block(height, txs)
+    }
+
+    #[test]
+    fn rejects_empty_and_duplicate_rules() {
+        let mut watches = WatchList::default();
+        assert!(watches.add("   ", 0).is_err());
+        let id = watches.add("receiver:token.near", 0).unwrap().id;
+        assert!(watches.add("  receiver:token.near ", 0).is_err());
+        assert_eq!(watches.add("signer:bob.near", 0).unwrap().id, id + 1);
+        assert_eq!(watches.rules().len(), 2);
+    }
+
+    #[test]
+    fn one_hit_per_rule_counting_every_matching_tx() {
+        let mut watches = WatchList::default();
+        let id = watches.add("receiver:token.near", 0).unwrap().id;
+        watches.add("signer:bob.near", 0).unwrap();
+
+        let mut b = to_token(10, &["alice.near", "carol.near"]);
+        b.transactions.push(tx("T10_2", "alice.near", "other.near"));
+        let hits = watches.check_block(&b);
+        assert_eq!(hits.len(), 1);
+        assert_eq!((hits[0].rule_id, hits[0].count), (id, 2));
+        assert_eq!(hits[0].payload()["txs"][1]["hash"], "T10_1");
+        assert_eq!(watches.rules()[0].last_height, Some(10));
+    }
+
+    #[test]
+    fn redelivered_and_older_heights_never_fire_twice() {
+        let mut watches = WatchList::default();
+        watches.add("receiver:token.near", 0).unwrap();
+        let b = to_token(10, &["alice.near"]);
+
+        assert_eq!(watches.check_block(&b).len(), 1);
+        assert!(watches.check_block(&b).is_empty());
+        assert!(watches.check_block(&to_token(9, &["bob.near"])).is_empty());
+        assert_eq!(watches.check_block(&to_token(11, &["bob.near"])).len(), 1);
+        assert_eq!(watches.rules()[0].hits, 2);
+    }
+
+    #[test]
+    fn webhook_goes_with_hits_only_while_set() {
+        let mut watches = WatchList::default();
+        let id = watches.add("receiver:token.near", 0).unwrap().id;
+        assert!(!watches.set_webhook(id + 1, Some("https://hooks.example/x".into())));
+
+        assert!(watches.set_webhook(id, Some(" https://hooks.example/x ".into())));
+        let hits = watches.check_block(&to_token(10, &["alice.near"]));
+        assert_eq!(hits[0].webhook.as_deref(), Some("https://hooks.example/x"));
+
+        // Blank clears it like None
+        assert!(watches.set_webhook(id, Some("  ".into())));
+        let hits = watches.check_block(&to_token(11, &["alice.near"]));
+        assert_eq!(hits[0].webhook, None);
+    }
+
+    #[test]
+    fn unseen_badge_counts_matches_until_marked_seen() {
+        let mut watches = WatchList::default();
+        watches.add("receiver:token.near", 0).unwrap();
+        watches.add("signer:bob.near", 0).unwrap();
+
+        watches.check_block(&to_token(10, &["alice.near", "bob.near"]));
+        assert_eq!(watches.unseen(), 3);
+        watches.mark_seen();
+        assert_eq!(watches.unseen(), 0);
+        assert_eq!(watches.rules()[0].hits, 2);
+    }
+
+    #[test]
+    fn persisted_rules_round_trip_without_unseen_count() {
+        let mut watches = WatchList::default();
+        let id = watches.add("receiver:token.near", 0).unwrap().id;
+        watches.check_block(&to_token(10, &["alice.near"]));
+        assert_eq!(watches.unseen(), 1);
+
+        let saved = serde_json::to_string(watches.rules()).unwrap();
+        let mut restored = WatchList::default();
+        restored.load(serde_json::from_str(&saved).unwrap());
+        assert_eq!(restored.unseen(), 0);
+        watches.mark_seen();
+        assert_eq!(restored.rules(), watches.rules());
+        assert_eq!(restored.check_block(&to_token(11, &["x.near"])).len(), 1);
+
+        // A removed rule stops firing
+        assert!(restored.remove(id).is_some());
+        assert!(restored.check_block(&to_token(12, &["x.near"])).is_empty());
+    }
+}
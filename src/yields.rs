@@ -0,0 +1,314 @@
+//! NEP-519 yield / resume detection
+//!
+//! A contract calling `promise_yield_create` parks a callback receipt on
+//! itself until another tx delivers its data with `promise_yield_resume`, or
+//! until the protocol's yield timeout (200 blocks on mainnet) resumes it with
+//! an error. Intents solvers and the MPC signer use this to wait for off-chain
+//! responses. In a tx status this shows up as:
+//!
+//! - an executed self-callback whose `input_data_ids` no receipt in the tx
+//!   produces (the data came from a resume, or from the timeout), and
+//! - while still parked, a spawned receipt with no outcome long after its
+//!   parent executed (ordinary receipts run within a block or two).
+//!
+//! Heights come from the timeline's block lookup; the timeline renders the
+//! waits with elapsed time and the countdown to the timeout.
+
+use std::collections::{HashMap, HashSet};
+
+use serde_json::Value;
+
+use crate::timeline::{receipt_detail, BlockInfo};
+
+/// `yield_timeout_length_in_blocks` when the protocol config can't be read
+pub const DEFAULT_TIMEOUT_BLOCKS: u64 = 200;
+/// A spawned receipt still without an outcome this many blocks after its
+/// parent executed is taken to be parked on a yield
+pub const PENDING_YIELD_BLOCKS: u64 = 3;
+
+/// One yielded (or apparently yielded) callback
+#[derive(Debug, Clone, PartialEq)]
+pub struct YieldWait {
+    pub receipt_id: String,
+    /// Contract that yielded (the callback runs on itself)
+    pub account: String,
+    /// Callback method (known once it executed)
+    pub callback: Option<String>,
+    /// Block of the receipt that called `promise_yield_create`
+    pub created_block: String,
+    /// Block the callback executed in (`None` = still waiting)
+    pub resumed_block: Option<String>,
+    pub created: Option<BlockInfo>,
+    pub resumed: Option<BlockInfo>,
+}
+
+/// Where a yield stands relative to the chain head and the timeout
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum YieldState {
+    /// Resumed by a response after `blocks` / `secs`
+    Resumed { blocks: u64, secs: f64 },
+    /// Resumed by the protocol timeout (no response arrived)
+    TimedOut { blocks: u64 },
+    /// Still parked: waited so far, and blocks / est. seconds left
+    Waiting {
+        blocks: u64,
+        secs: f64,
+        left_blocks: u64,
+        left_secs: f64,
+    },
+    /// Heights not resolved
+    Unknown,
+}
+
+impl YieldWait {
+    pub fn pending(&self) -> bool {
+        self.resumed_block.is_none()
+    }
+
+    /// State against `head` (pending yields) and the protocol timeout
+    pub fn state(&self, head: Option<BlockInfo>, timeout_blocks: u64) -> YieldState {
+        let Some(created) = self.created else {
+            return YieldState::Unknown;
+        };
+        let secs =
+            |to: BlockInfo| to.timestamp_ns.saturating_sub(created.timestamp_ns) as f64 / 1e9;
+        match (self.resumed, head) {
+            (Some(resumed), _) => {
+                let blocks = resumed.height.saturating_sub(created.height);
+                if blocks >= timeout_blocks {
+                    YieldState::TimedOut { blocks }
+                } else {
+                    YieldState::Resumed {
+                        blocks,
+                        secs: secs(resumed),
+                    }
+                }
+            }
+            (None, Some(head)) if self.resumed_block.is_none() => {
+                let blocks = head.height.saturating_sub(created.height);
+                let elapsed = secs(head);
+                // Block time observed over the wait so far (1s when too short to tell)
+                let per_block = if blocks > 0 {
+                    elapsed / blocks as f64
+                } else {
+                    1.0
+                };
+                let left_blocks = timeout_blocks.saturating_sub(blocks);
+                YieldState::Waiting {
+                    blocks,
+                    secs: elapsed,
+                    left_blocks,
+                    left_secs: left_blocks as f64 * per_block,
+                }
+            }
+            _ => YieldState::Unknown,
+        }
+    }
+
+    /// Fill in heights / timestamps from resolved blocks
+    pub fn resolve(&mut self, blocks: &HashMap<String, BlockInfo>) {
+        self.created = blocks.get(&self.created_block).copied();
+        self.resumed = self
+            .resumed_block
+            .as_ref()
+            .and_then(|b| blocks.get(b))
+            .copied();
+    }
+}
+
+/// Yields in an `EXPERIMENTAL_tx_status` result (partial results included).
+/// Pending candidates are filtered by `PENDING_YIELD_BLOCKS` once heights resolve.
+pub fn detect(status: &Value) -> Vec<YieldWait> {
+    let bodies: HashMap<&str, &Value> = status["receipts"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|r| Some((r["receipt_id"].as_str()?, r)))
+        .collect();
+
+    // Data some receipt in this tx will deliver; anything else came from outside
+    let produced: HashSet<&str> = bodies
+        .values()
+        .flat_map(|r| {
+            r["receipt"]["Action"]["output_data_receivers"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|d| d["data_id"].as_str())
+        })
+        .collect();
+
+    // child receipt id -> (parent's block, parent's executor)
+    let mut parents: HashMap<&str, (&str, &str)> = HashMap::new();
+    let mut outcomes: HashMap<&str, &str> = HashMap::new();
+    let all_outcomes = std::iter::once(&status["transaction_outcome"])
+        .chain(status["receipts_outcome"].as_array().into_iter().flatten());
+    for (i, o) in all_outcomes.enumerate() {
+        let block = o["block_hash"].as_str().unwrap_or_default();
+        let executor = o["outcome"]["executor_id"].as_str().unwrap_or_default();
+        if i > 0 {
+            if let Some(id) = o["id"].as_str() {
+                outcomes.insert(id, block);
+            }
+        }
+        for child in o["outcome"]["receipt_ids"].as_array().into_iter().flatten() {
+            if let Some(child) = child.as_str() {
+                parents.insert(child, (block, executor));
+            }
+        }
+    }
+
+    let mut waits = Vec::new();
+    for (&id, &(parent_block, parent_executor)) in &parents {
+        match outcomes.get(id) {
+            Some(&block) => {
+                let Some(body) = bodies.get(id) else {
+                    continue;
+                };
+                let receiver = body["receiver_id"].as_str().unwrap_or_default();
+                let self_callback = body["predecessor_id"].as_str() == Some(receiver);
+                let external_input = body["receipt"]["Action"]["input_data_ids"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|d| d.as_str())
+                    .any(|d| !produced.contains(d));
+                if self_callback && external_input {
+                    waits.push(YieldWait {
+                        receipt_id: id.to_string(),
+                        account: receiver.to_string(),
+                        callback: Some(receipt_detail(body)),
+                        created_block: parent_block.to_string(),
+                        resumed_block: Some(block.to_string()),
+                        created: None,
+                        resumed: None,
+                    });
+                }
+            }
+            None => waits.push(YieldWait {
+                receipt_id: id.to_string(),
+                account: parent_executor.to_string(),
+                callback: None,
+                created_block: parent_block.to_string(),
+                resumed_block: None,
+                created: None,
+                resumed: None,
+            }),
+        }
+    }
+    waits.sort_by(|a, b| a.receipt_id.cmp(&b.receipt_id));
+    waits
+}
+
+/// Drop pending candidates that are merely in flight (parent executed under
+/// `PENDING_YIELD_BLOCKS` before `head`, or heights unknown)
+pub fn retain_parked(waits: &mut Vec<YieldWait>, head: Option<BlockInfo>) {
+    waits.retain(|w| {
+        !w.pending()
+            || matches!((w.created, head), (Some(c), Some(h)) if h.height.saturating_sub(c.height) >= PENDING_YIELD_BLOCKS)
+    });
+}
+
+/// `yield_timeout_length_in_blocks` from an `EXPERIMENTAL_protocol_config` result
+pub fn timeout_from_protocol_config(config: &Value) -> Option<u64> {
+    config["runtime_config"]["wasm_config"]["limit_config"]["yield_timeout_length_in_blocks"]
+        .as_u64()
+}
+
+/// One-line description for the timeline ("resumed after 42 blocks (46.2s)")
+pub fn describe(state: YieldState) -> String {
+    match state {
+        YieldState::Resumed { blocks, secs } => {
+            format!("resumed after {blocks} blocks ({secs:.1}s)")
+        }
+        YieldState::TimedOut { blocks } => {
+            format!("timed out after {blocks} blocks (no response)")
+        }
+        YieldState::Waiting {
+            blocks,
+            secs,
+            left_blocks,
+            left_secs,
+        } => format!(
+            "waiting {blocks} blocks ({secs:.1}s) · times out in {left_blocks} blocks (~{left_secs:.0}s)"
+        ),
+        YieldState::Unknown => "yielded (heights unknown)".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn detects_resumed_timed_out_and_parked_yields() {
+        // sign -> yields callback R2 (resumed) and R3 (parked); R4 is an ordinary
+        // callback fed by R1's output
+        let status = json!({
+            "transaction_outcome": {"block_hash": "B0", "outcome": {"executor_id": "alice.near", "receipt_ids": ["R1"]}},
+            "receipts_outcome": [
+                {"id": "R1", "block_hash": "B1", "outcome": {"executor_id": "v1.signer", "receipt_ids": ["R2", "R3", "R4"]}},
+                {"id": "R2", "block_hash": "B9", "outcome": {"executor_id": "v1.signer", "receipt_ids": []}},
+                {"id": "R4", "block_hash": "B2", "outcome": {"executor_id": "v1.signer", "receipt_ids": []}}
+            ],
+            "receipts": [
+                {"receipt_id": "R1", "predecessor_id": "alice.near", "receiver_id": "v1.signer",
+                 "receipt": {"Action": {"actions": [{"FunctionCall": {"method_name": "sign"}}],
+                                        "input_data_ids": [], "output_data_receivers": [{"data_id": "D1", "receiver_id": "v1.signer"}]}}},
+                {"receipt_id": "R2", "predecessor_id": "v1.signer", "receiver_id": "v1.signer",
+                 "receipt": {"Action": {"actions": [{"FunctionCall": {"method_name": "return_signature"}}],
+                                        "input_data_ids": ["YIELD"], "output_data_receivers": []}}},
+                {"receipt_id": "R4", "predecessor_id": "v1.signer", "receiver_id": "v1.signer",
+                 "receipt": {"Action": {"actions": [{"FunctionCall": {"method_name": "on_done"}}],
+                                        "input_data_ids": ["D1"], "output_data_receivers": []}}}
+            ]
+        });
+        let mut waits = detect(&status);
+        assert_eq!(waits.len(), 2);
+        assert_eq!(waits[0].receipt_id, "R2");
+        assert_eq!(waits[0].callback.as_deref(), Some("return_signature"));
+        assert_eq!(waits[1].receipt_id, "R3");
+        assert!(waits[1].pending());
+
+        let info = |height, secs: u64| BlockInfo {
+            height,
+            timestamp_ns: secs * 1_000_000_000,
+        };
+        let blocks = HashMap::from([
+            ("B1".to_string(), info(100, 0)),
+            ("B9".to_string(), info(142, 46)),
+        ]);
+        for w in &mut waits {
+            w.resolve(&blocks);
+        }
+        let head = Some(info(150, 55));
+        assert_eq!(
+            waits[0].state(head, 200),
+            YieldState::Resumed {
+                blocks: 42,
+                secs: 46.0
+            }
+        );
+        assert_eq!(
+            waits[0].state(head, 40),
+            YieldState::TimedOut { blocks: 42 }
+        );
+        match waits[1].state(head, 200) {
+            YieldState::Waiting {
+                blocks,
+                left_blocks,
+                left_secs,
+                ..
+            } => {
+                assert_eq!((blocks, left_blocks), (50, 150));
+                assert!((left_secs - 165.0).abs() < 0.01);
+            }
+            other => panic!("expected waiting, got {other:?}"),
+        }
+
+        // Parent executed just now: in flight, not parked
+        retain_parked(&mut waits, Some(info(101, 1)));
+        assert_eq!(waits.len(), 1);
+    }
+}
@@ -25,69 +25,41 @@ pub enum ParseError {
 impl FromStr for DeepLink {
     type Err = ParseError;
     fn from_str(raw: &str) -> Result<Self, Self::Err> {
-        let url = url::Url::parse(raw).map_err(|_| ParseError::Scheme)?;
-        if url.scheme() != "near" {
-            return Err(ParseError::Scheme);
-        }
-
-        let host = url.host_str().unwrap_or_default().to_ascii_lowercase();
-        let path = url.path().trim_start_matches('/');
+        // Same canonicalization as the router and native host: scheme aliases
+        // (`near:`, `web+nearx:`), slashes, percent-encoding, case folding
+        let ev = nearx::deeplink::parse_event(raw).ok_or(ParseError::Scheme)?;
+        let path = ev.path.join("/");
 
-        if host == "nearx" || path.starts_with("nearx") {
+        if ev.host == "nearx" || path.starts_with("nearx") {
             return Ok(DeepLink::Nearx);
         }
 
-        if host == "tx" {
-            if path.is_empty() {
-                return Err(ParseError::Missing);
-            }
-            return Ok(DeepLink::Tx {
-                hash: path.to_string(),
-            });
-        }
-
-        if host == "account" {
-            if path.is_empty() {
-                return Err(ParseError::Missing);
-            }
-            return Ok(DeepLink::Account {
-                id: path.to_string(),
-            });
-        }
-
-        if host == "block" {
-            if path.is_empty() {
-                return Err(ParseError::Missing);
+        match ev.host.as_str() {
+            "tx" | "account" | "block" if path.is_empty() => Err(ParseError::Missing),
+            "tx" => Ok(DeepLink::Tx { hash: path }),
+            "account" => Ok(DeepLink::Account { id: path }),
+            "block" => {
+                let height = path.parse::<u64>().map_err(|_| ParseError::Num)?;
+                Ok(DeepLink::Block { height })
             }
-            let h = path.parse::<u64>().map_err(|_| ParseError::Num)?;
-            return Ok(DeepLink::Block { height: h });
-        }
-
-        if host == "open" {
-            if path.starts_with("session/") {
-                let id = path.trim_start_matches("session/").to_string();
-                if id.is_empty() {
-                    return Err(ParseError::Missing);
+            "open" => {
+                if let Some(id) = path.strip_prefix("session/") {
+                    let read_only = ev
+                        .query
+                        .get("readOnly")
+                        .is_some_and(|v| v == "1" || v.eq_ignore_ascii_case("true"));
+                    return Ok(DeepLink::Session {
+                        id: id.to_string(),
+                        read_only,
+                    });
                 }
-                let ro = url
-                    .query_pairs()
-                    .any(|(k, v)| k == "readOnly" && (v == "1" || v.eq_ignore_ascii_case("true")));
-                return Ok(DeepLink::Session { id, read_only: ro });
-            }
-            if let Some(p) = url
-                .query_pairs()
-                .find(|(k, _)| k == "path")
-                .map(|(_, v)| v.to_string())
-            {
-                if !p.starts_with('/') {
-                    return Err(ParseError::Missing);
+                match ev.query.get("path") {
+                    Some(p) if p.starts_with('/') => Ok(DeepLink::OpenPath { path: p.clone() }),
+                    _ => Err(ParseError::Missing),
                 }
-                return Ok(DeepLink::OpenPath { path: p });
             }
-            return Err(ParseError::Missing);
+            _ => Err(ParseError::Missing),
         }
-
-        Err(ParseError::Missing)
     }
 }
 
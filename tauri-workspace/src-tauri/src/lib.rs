@@ -5,8 +5,8 @@
 
 mod deeplink;
 
-use serde::Serialize;
-use std::collections::BTreeMap;
+use nearx::deeplink::DeepLinkEvent;
+use nearx::release_verify::{Artifact, ReleaseKey, Verification};
 use std::sync::{Mutex, OnceLock};
 use tauri::{Emitter, Manager, Runtime};
 use tauri_plugin_deep_link::DeepLinkExt;
@@ -16,6 +16,7 @@ use tauri::path::BaseDirectory;
 
 static READY: OnceLock<Mutex<bool>> = OnceLock::new();
 static QUEUE: OnceLock<Mutex<Vec<DeepLinkEvent>>> = OnceLock::new();
+static SECURITY: OnceLock<Mutex<SecurityReport>> = OnceLock::new();
 
 fn ready_get() -> bool {
     *READY.get_or_init(|| Mutex::new(false)).lock().unwrap()
@@ -27,16 +28,6 @@ fn queue() -> &'static Mutex<Vec<DeepLinkEvent>> {
     QUEUE.get_or_init(|| Mutex::new(Vec::new()))
 }
 
-#[derive(Debug, Serialize, Clone)]
-#[serde(rename_all = "snake_case")]
-pub struct DeepLinkEvent {
-    pub url: String,
-    pub scheme: String,
-    pub host: String,
-    pub path: Vec<String>,               // slug segments
-    pub query: BTreeMap<String, String>, // ?k=v
-}
-
 #[tauri::command]
 fn deeplink_frontend_ready() -> Vec<DeepLinkEvent> {
     log::info!(
@@ -47,127 +38,6 @@ fn deeplink_frontend_ready() -> Vec<DeepLinkEvent> {
     queue().lock().unwrap().drain(..).collect()
 }
 
-fn normalize(raw: &str) -> Option<String> {
-    log::info!("🔵 [NORMALIZE] ==================== START ====================");
-    log::info!("🔵 [NORMALIZE] Input raw: {raw:?}");
-
-    let s = raw.trim();
-    log::info!("🔵 [NORMALIZE] After trim: {s:?}");
-
-    if s.is_empty() {
-        log::warn!("🔵 [NORMALIZE] Empty string after trim - returning None");
-        return None;
-    }
-
-    // Accept: nearx://..., nearx:..., web+nearx:..., near:... (legacy)
-    let mut s = s.replacen("web+nearx:", "nearx://", 1);
-    s = s.replacen("web+near:", "nearx://", 1); // Legacy support
-    log::info!("🔵 [NORMALIZE] After web+ replacement: {s:?}");
-
-    // nearx:tx/abc  -> nearx://tx/abc
-    // near:tx/abc   -> nearx://tx/abc (legacy)
-    if let Some((sch, rest)) = s.split_once(':') {
-        log::info!("🔵 [NORMALIZE] Split scheme: {sch:?}, rest: {rest:?}");
-        if (sch.eq_ignore_ascii_case("nearx") || sch.eq_ignore_ascii_case("near"))
-            && !rest.starts_with("//")
-        {
-            s = format!("nearx://{rest}");
-            log::info!("🔵 [NORMALIZE] Added slashes: {s:?}");
-        } else if sch.eq_ignore_ascii_case("near") && rest.starts_with("//") {
-            // Legacy: near:// -> nearx://
-            s = format!("nearx://{rest}");
-            log::info!("🔵 [NORMALIZE] Converted legacy near:// to nearx://: {s:?}");
-        }
-    }
-
-    // Must parse as a URL now
-    match url::Url::parse(&s) {
-        Ok(parsed) => {
-            log::info!("🔵 [NORMALIZE] Successfully parsed as URL: {parsed:?}");
-            log::info!("🔵 [NORMALIZE] Returning: {s:?}");
-            log::info!("🔵 [NORMALIZE] ==================== END ====================");
-            Some(s)
-        }
-        Err(e) => {
-            log::warn!("🔵 [NORMALIZE] Failed to parse as URL: {e} - returning None");
-            log::info!("🔵 [NORMALIZE] ==================== END ====================");
-            None
-        }
-    }
-}
-
-fn parse_event(s: &str) -> Option<DeepLinkEvent> {
-    log::info!("🟣 [PARSE-EVENT] ==================== START ====================");
-    log::info!("🟣 [PARSE-EVENT] Input string: {s:?}");
-
-    let url = match url::Url::parse(s) {
-        Ok(u) => {
-            log::info!("🟣 [PARSE-EVENT] Successfully parsed URL");
-            log::info!("🟣 [PARSE-EVENT] Scheme: {:?}", u.scheme());
-            log::info!("🟣 [PARSE-EVENT] Host: {:?}", u.host_str());
-            log::info!("🟣 [PARSE-EVENT] Path: {:?}", u.path());
-            log::info!("🟣 [PARSE-EVENT] Query: {:?}", u.query());
-            u
-        }
-        Err(e) => {
-            log::warn!("🟣 [PARSE-EVENT] Failed to parse URL: {e} - returning None");
-            log::info!("🟣 [PARSE-EVENT] ==================== END ====================");
-            return None;
-        }
-    };
-
-    if url.scheme() != "nearx" {
-        log::warn!(
-            "🟣 [PARSE-EVENT] Wrong scheme: {:?} (expected 'nearx') - returning None",
-            url.scheme()
-        );
-        log::info!("🟣 [PARSE-EVENT] ==================== END ====================");
-        return None;
-    }
-
-    // host is your "resource" (tx/account/block/open/nearx)
-    let host = url.host_str().unwrap_or_default().to_string();
-    log::info!("🟣 [PARSE-EVENT] Extracted host: {host:?}");
-
-    // collect non-empty path segments
-    let path: Vec<String> = url
-        .path_segments()
-        .map(|segs| {
-            let segments: Vec<String> = segs
-                .filter(|p| !p.is_empty())
-                .map(|p| p.to_string())
-                .collect();
-            log::info!("🟣 [PARSE-EVENT] Extracted path segments: {segments:?}");
-            segments
-        })
-        .unwrap_or_default();
-
-    // map query pairs
-    let mut query = BTreeMap::new();
-    for (k, v) in url.query_pairs() {
-        log::info!("🟣 [PARSE-EVENT] Query pair: {k:?} = {v:?}");
-        query.insert(k.to_string(), v.to_string());
-    }
-    log::info!("🟣 [PARSE-EVENT] Total query pairs: {}", query.len());
-
-    let event = DeepLinkEvent {
-        url: url.to_string(),
-        scheme: "nearx".into(),
-        host: host.clone(),
-        path: path.clone(),
-        query: query.clone(),
-    };
-
-    log::info!("🟣 [PARSE-EVENT] ✅ Created DeepLinkEvent:");
-    log::info!("🟣 [PARSE-EVENT]    url: {:?}", event.url);
-    log::info!("🟣 [PARSE-EVENT]    host: {:?}", event.host);
-    log::info!("🟣 [PARSE-EVENT]    path: {:?}", event.path);
-    log::info!("🟣 [PARSE-EVENT]    query: {:?}", event.query);
-    log::info!("🟣 [PARSE-EVENT] ==================== END ====================");
-
-    Some(event)
-}
-
 fn emit_or_queue<R: Runtime>(app: &tauri::AppHandle<R>, evs: Vec<DeepLinkEvent>) {
     log::info!("🟤 [EMIT-OR-QUEUE] ==================== START ====================");
     log::info!("🟤 [EMIT-OR-QUEUE] Received {} event(s)", evs.len());
@@ -217,7 +87,55 @@ fn emit_or_queue<R: Runtime>(app: &tauri::AppHandle<R>, evs: Vec<DeepLinkEvent>)
     log::info!("🟤 [EMIT-OR-QUEUE] ==================== END ====================");
 }
 
-/// Spawn the native messaging host sidecar if present in resources
+/// Release signature checks, shown in the About dialog's security section
+#[derive(Clone, Debug, Default, serde::Serialize)]
+struct SecurityReport {
+    /// Fingerprint of the bundled release key (None = built without one)
+    release_key: Option<String>,
+    /// Unsigned artifacts may run (debug builds only)
+    allow_unverified: bool,
+    sidecar: Option<ArtifactCheck>,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+struct ArtifactCheck {
+    name: String,
+    verification: Verification,
+    summary: String,
+    spawned: bool,
+}
+
+fn security() -> &'static Mutex<SecurityReport> {
+    SECURITY.get_or_init(|| {
+        Mutex::new(SecurityReport {
+            release_key: release_key().map(|k| k.fingerprint()),
+            allow_unverified: cfg!(debug_assertions),
+            sidecar: None,
+        })
+    })
+}
+
+/// Release public key bundled at build time (`NEARX_RELEASE_PUBKEY`)
+fn release_key() -> Option<ReleaseKey> {
+    let raw = option_env!("NEARX_RELEASE_PUBKEY")?;
+    match ReleaseKey::parse(raw) {
+        Ok(key) => Some(key),
+        Err(e) => {
+            log::error!("🔐 Bundled release key unusable: {e}");
+            None
+        }
+    }
+}
+
+#[tauri::command]
+fn release_security() -> SecurityReport {
+    security().lock().unwrap().clone()
+}
+
+/// Spawn the native messaging host sidecar if present in resources, after
+/// checking its detached signature against the bundled release key. The
+/// checked bytes are staged in the app cache and that copy is spawned, so the
+/// resource can't be swapped between the check and the spawn.
 #[cfg(desktop)]
 fn spawn_sidecar_if_present(app: &tauri::AppHandle) {
     use std::process::Command;
@@ -230,11 +148,46 @@ fn spawn_sidecar_if_present(app: &tauri::AppHandle) {
 
     match app.path().resolve(name, BaseDirectory::Resource) {
         Ok(path) if path.exists() => {
-            log::info!("🚀 Spawning native messaging host sidecar: {path:?}");
-            match Command::new(&path).spawn() {
-                Ok(child) => log::info!("✅ Sidecar started with PID: {}", child.id()),
-                Err(e) => log::error!("❌ Failed to spawn sidecar: {e}"),
+            let artifact = Artifact::load(&path, release_key().as_ref());
+            let verification = artifact.verification().clone();
+            let summary = verification.summary();
+            let allowed = verification.allows_run(cfg!(debug_assertions));
+            let mut spawned = false;
+            if !allowed {
+                log::error!("🔐 Refusing to spawn sidecar {path:?}: {summary}");
+            } else {
+                if matches!(verification, Verification::Verified { .. }) {
+                    log::info!("🔐 Sidecar signature {summary}");
+                } else {
+                    log::warn!("🔐 Sidecar {summary} - allowed in debug builds only");
+                }
+                let staged = app
+                    .path()
+                    .app_cache_dir()
+                    .map_err(|e| std::io::Error::other(e.to_string()))
+                    .and_then(|dir| artifact.stage(&dir.join("sidecar"), name));
+                match staged {
+                    Ok(staged) => {
+                        log::info!(
+                            "🚀 Spawning native messaging host sidecar: {staged:?} (from {path:?})"
+                        );
+                        match Command::new(&staged).spawn() {
+                            Ok(child) => {
+                                spawned = true;
+                                log::info!("✅ Sidecar started with PID: {}", child.id())
+                            }
+                            Err(e) => log::error!("❌ Failed to spawn sidecar: {e}"),
+                        }
+                    }
+                    Err(e) => log::error!("❌ Failed to stage sidecar {path:?}: {e}"),
+                }
             }
+            security().lock().unwrap().sidecar = Some(ArtifactCheck {
+                name: name.to_string(),
+                verification,
+                summary,
+                spawned,
+            });
         }
         Ok(path) => log::debug!("Sidecar not found at {path:?}"),
         Err(e) => log::error!("Failed to resolve sidecar path: {e}"),
@@ -249,20 +202,21 @@ fn handle_urls<R: Runtime>(app: &tauri::AppHandle<R>, raws: &[String]) {
     }
 
     let mut out = Vec::new();
+    let mut canonical = Vec::new();
     for (i, r) in raws.iter().enumerate() {
         log::info!("🟢 [HANDLE-URLS] Processing Raw[{i}]: {r:?}");
-        log::info!("🟢 [HANDLE-URLS] Calling normalize()...");
-        if let Some(n) = normalize(r) {
-            log::info!("🟢 [HANDLE-URLS] Normalized[{i}] = {n:?}");
-            log::info!("🟢 [HANDLE-URLS] Calling parse_event()...");
-            if let Some(ev) = parse_event(&n) {
+        log::info!("🟢 [HANDLE-URLS] Calling deeplink::parse_event()...");
+        match nearx::deeplink::parse_event(r) {
+            Some(ev) => {
                 log::info!(
-                    "🟢 [HANDLE-URLS] Parsed event[{}]: host={}, path={:?}, query={:?}",
+                    "🟢 [HANDLE-URLS] Parsed event[{}]: url={}, host={}, path={:?}, query={:?}",
                     i,
+                    ev.url,
                     ev.host,
                     ev.path,
                     ev.query
                 );
+                canonical.push(ev.url.clone());
                 // Optional: special-case nearx://nearx to open a secondary window
                 if ev.host == "nearx" {
                     log::info!(
@@ -273,11 +227,8 @@ fn handle_urls<R: Runtime>(app: &tauri::AppHandle<R>, raws: &[String]) {
                     log::info!("🟢 [HANDLE-URLS] Adding event to output queue");
                     out.push(ev);
                 }
-            } else {
-                log::warn!("🟢 [HANDLE-URLS] parse_event() returned None for: {n:?}");
             }
-        } else {
-            log::warn!("🟢 [HANDLE-URLS] normalize() returned None for: {r:?}");
+            None => log::warn!("🟢 [HANDLE-URLS] Not a deep link (or malformed): {r:?}"),
         }
     }
 
@@ -285,11 +236,12 @@ fn handle_urls<R: Runtime>(app: &tauri::AppHandle<R>, raws: &[String]) {
     log::info!("🟢 [HANDLE-URLS] Calling emit_or_queue()...");
     emit_or_queue(app, out);
 
-    // Also emit raw URLs for JavaScript bridge to handle
+    // Also emit the canonical URLs for the JavaScript bridge to handle, so the
+    // hash router sees exactly what the `deep-link` event carries
     // The JS bridge (web/deep_link.js) listens for "nearx://open" and updates location.hash
-    for raw_url in raws {
-        let _ = app.emit("nearx://open", raw_url);
-        log::info!("🟢 [HANDLE-URLS] Emitted 'nearx://open' event with URL: {raw_url}");
+    for url in &canonical {
+        let _ = app.emit("nearx://open", url);
+        log::info!("🟢 [HANDLE-URLS] Emitted 'nearx://open' event with URL: {url}");
     }
 
     log::info!("🟢 [HANDLE-URLS] ==================== END ====================");
@@ -335,7 +287,8 @@ pub fn run() {
         .plugin(tauri_plugin_deep_link::init())
         .invoke_handler(tauri::generate_handler![
             deeplink_frontend_ready,
-            copy_text
+            copy_text,
+            release_security
         ])
         .setup(|app| {
             log::info!("NEARx Tauri starting");
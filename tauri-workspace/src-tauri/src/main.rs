@@ -28,11 +28,9 @@ fn main() {
         .plugin(tauri_plugin_deep_link::init())
         // Single instance; forward deep links / CLI args to the running window
         .plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
-            // Forward CLI args that look like deep links
-            for arg in argv {
-                if arg.starts_with("nearx://") {
-                    let _ = app.emit("nearx://open", arg);
-                }
+            // Forward CLI args that are deep links (canonical form, like the router sees them)
+            for url in argv.iter().filter_map(|arg| nearx::deeplink::canonicalize(arg)) {
+                let _ = app.emit("nearx://open", url);
             }
         }))
         // System browser for Google OAuth / external links
@@ -72,8 +70,8 @@ fn main() {
 
                 // When a deep link arrives, buffer and emit
                 app.deep_link().on_open_url(move |event| {
-                    for url in event.urls() {
-                        let s = url.to_string();
+                    let urls = event.urls();
+                    for s in urls.iter().filter_map(|u| nearx::deeplink::canonicalize(u.as_str())) {
                         // Buffer in case window isn't ready
                         pending_clone.0.lock().unwrap().push(s.clone());
                         // Try immediate delivery
@@ -84,7 +82,9 @@ fn main() {
                 // Check for initial deep links on cold start
                 if let Some(urls) = app.deep_link().get_current()? {
                     for url in urls {
-                        pending.0.lock().unwrap().push(url.to_string());
+                        if let Some(s) = nearx::deeplink::canonicalize(url.as_str()) {
+                            pending.0.lock().unwrap().push(s);
+                        }
                     }
                 }
             }
@@ -99,6 +99,16 @@ fn main() {
 
             Ok(())
         })
+        // Forward OS dark/light switches to the frontend (web/app.js → setSystemAppearance)
+        .on_window_event(|window, event| {
+            if let tauri::WindowEvent::ThemeChanged(theme) = event {
+                let appearance = match theme {
+                    tauri::Theme::Light => "light",
+                    _ => "dark",
+                };
+                let _ = window.emit("nearx://theme", appearance);
+            }
+        })
         .run(tauri::generate_context!())
         .expect("NEARx Tauri failed");
 }
@@ -21,6 +21,14 @@ let lastSnapshot = null;
 let clientToastActive = false;  // Track if client-side toast is showing
 let suppressFilterEvent = false;
 
+// Read-only mirror of a native session (`?mirror=ws://host:port`, served with
+// MIRROR_LISTEN): frames from the socket are rendered, no local sources run,
+// and input is not applied.
+const MIRROR_URL = new URLSearchParams(window.location.search).get("mirror");
+const MIRROR_RETRY_MAX_MS = 10000;
+
+const TOAST_ICONS = { Info: "ℹ", Success: "✓", Warn: "⚠", Error: "✗" };
+
 // Track viewport size to avoid redundant updates
 let lastViewportLines = 0;
 
@@ -46,8 +54,16 @@ async function main() {
   // window.wasm_bindgen.nearx_auth_callback(qs).
   window.wasm_bindgen = wasm;
 
+  if (MIRROR_URL) {
+    hookEvents();
+    startMirror(MIRROR_URL);
+    return;
+  }
+
   wasmApp = new wasm.WasmApp();
   hookEvents();
+  hookSystemAppearance();
+  loadReleaseSecurity();
 
   // Set initial viewport size
   updateDetailsViewport();
@@ -65,6 +81,54 @@ async function main() {
   startRenderLoop();
 }
 
+// Forward OS dark/light changes to Rust (no-op unless built with THEME_FOLLOW_SYSTEM=true).
+// Browsers report them via prefers-color-scheme; Tauri also emits nearx://theme.
+function hookSystemAppearance() {
+  if (!wasmApp.setSystemAppearance) return;
+
+  const mq = window.matchMedia ? window.matchMedia("(prefers-color-scheme: dark)") : null;
+  if (mq && mq.addEventListener) {
+    mq.addEventListener("change", (e) => wasmApp.setSystemAppearance(e.matches));
+  }
+
+  if (window.__TAURI__ && window.__TAURI__.event) {
+    window.__TAURI__.event.listen("nearx://theme", (event) => {
+      wasmApp.setSystemAppearance(event && event.payload === "dark");
+    });
+  }
+}
+
+// About dialog security section: the desktop shell's release signature
+// checks (bundled key, native host sidecar). Browsers keep the default text.
+async function loadReleaseSecurity() {
+  const el = document.getElementById("nearx-about-security");
+  const t = window.__TAURI__;
+  const invoke = t?.core?.invoke || t?.invoke;
+  if (!el || typeof invoke !== "function") return;
+  try {
+    const report = await invoke("release_security");
+    const lines = [
+      report.release_key
+        ? `Release key ${report.release_key}`
+        : "No release key in this build",
+    ];
+    const sidecar = report.sidecar;
+    if (sidecar) {
+      lines.push(`${sidecar.name}: ${sidecar.summary}${sidecar.spawned ? "" : " (not started)"}`);
+    } else {
+      lines.push("Native host sidecar: not bundled");
+    }
+    if (report.allow_unverified) lines.push("Debug build: unsigned binaries may run");
+    el.replaceChildren(...lines.map((text) => {
+      const line = document.createElement("div");
+      line.textContent = text;
+      return line;
+    }));
+  } catch (e) {
+    el.textContent = `Security report unavailable: ${e}`;
+  }
+}
+
 function snapshot() {
   const json = wasmApp.snapshot_json();
   lastSnapshot = JSON.parse(json);
@@ -72,6 +136,7 @@ function snapshot() {
 }
 
 function apply(action) {
+  if (!wasmApp) return;  // Mirror: read-only
   const json = wasmApp.handle_action_json(JSON.stringify(action));
   lastSnapshot = JSON.parse(json);
   render(lastSnapshot);
@@ -88,6 +153,63 @@ function startRenderLoop() {
   pollAndRender();
 }
 
+/* ---------- Mirror (read-only shared view) ---------- */
+
+function setMirrorBadge(state, text) {
+  const badge = document.getElementById("nearx-mirror");
+  if (!badge) return;
+  badge.hidden = false;
+  badge.dataset.state = state;
+  badge.textContent = text;
+}
+
+// Render every frame the native instance publishes; reconnect with backoff.
+function startMirror(url) {
+  const filter = document.getElementById("nearx-filter");
+  if (filter) {
+    filter.readOnly = true;
+    filter.placeholder = "Mirror: filter follows the operator";
+  }
+
+  let retryMs = 500;
+  function connect() {
+    let socket;
+    try {
+      socket = new WebSocket(url);
+    } catch (err) {
+      setMirrorBadge("down", `✗ mirror: ${err.message}`);
+      return;
+    }
+    setMirrorBadge("down", `… connecting to ${url}`);
+
+    socket.addEventListener("open", () => {
+      retryMs = 500;
+      setMirrorBadge("up", `◉ MIRROR · read-only · ${url}`);
+    });
+    socket.addEventListener("message", (e) => {
+      let frame;
+      try {
+        frame = JSON.parse(e.data);
+      } catch (err) {
+        console.warn("[nearx][mirror] Bad frame:", err);
+        return;
+      }
+      if (frame.v !== 1 || !frame.snapshot) {
+        setMirrorBadge("down", `✗ mirror: unsupported frame version ${frame.v}`);
+        return;
+      }
+      lastSnapshot = frame.snapshot;
+      render(lastSnapshot);
+    });
+    socket.addEventListener("close", () => {
+      setMirrorBadge("down", `○ mirror disconnected · retrying in ${Math.round(retryMs / 1000)}s`);
+      setTimeout(connect, retryMs);
+      retryMs = Math.min(retryMs * 2, MIRROR_RETRY_MAX_MS);
+    });
+  }
+  connect();
+}
+
 /* ---------- JSON syntax highlight ---------- */
 
 function syntaxHighlightJson(text) {
@@ -115,6 +237,36 @@ function syntaxHighlightJson(text) {
   );
 }
 
+// Highlighted HTML per details text (LRU). Output only references CSS classes,
+// so theme changes never invalidate it; flipping back to a recently viewed tx
+// (or scroll window) skips the regex pass entirely.
+const HIGHLIGHT_CACHE_MAX = 64;
+const highlightCache = new Map();
+
+function highlightJsonCached(text, truncated, maxLines) {
+  const key = `${truncated ? 1 : 0}|${text}`;
+  let html = highlightCache.get(key);
+  if (html !== undefined) {
+    // Refresh recency (Map iterates in insertion order)
+    highlightCache.delete(key);
+    highlightCache.set(key, html);
+    return html;
+  }
+
+  html = syntaxHighlightJson(text);
+
+  // Add truncation message if content was cut off
+  if (truncated) {
+    html += `<br><br><span style="color: var(--fg-dim); font-style: italic;">… large output truncated at ${maxLines.toLocaleString()} lines; press 'c' to copy full JSON</span>`;
+  }
+
+  highlightCache.set(key, html);
+  if (highlightCache.size > HIGHLIGHT_CACHE_MAX) {
+    highlightCache.delete(highlightCache.keys().next().value);
+  }
+  return html;
+}
+
 /* ---------- DOM wiring ---------- */
 
 function hookEvents() {
@@ -200,6 +352,7 @@ function hookEvents() {
     // Special handling for Tab - instant visual feedback (optimistic UI)
     if (e.key === "Tab") {
       e.preventDefault();
+      if (!wasmApp) return;  // Mirror: panes follow the operator
 
       // Optimistic UI: instantly update pane focus before WASM round-trip
       if (lastSnapshot) {
@@ -233,6 +386,20 @@ function hookEvents() {
     // When typing into filter, let keystrokes through (Esc and Tab handled above).
     if (filterActive) return;
 
+    // Ctrl+E → switch RPC / archival endpoints (poller restarts, buffer kept).
+    if ((e.key === "e" || e.key === "E") && (e.ctrlKey || e.metaKey) && !e.altKey) {
+      e.preventDefault();
+      const rpc = window.prompt("RPC endpoint", lastSnapshot?.near_node_url ?? "");
+      if (rpc === null) return;
+      const archival = window.prompt(
+        "Archival RPC endpoint (empty = disabled)",
+        lastSnapshot?.archival_rpc_url ?? "",
+      );
+      if (archival === null) return;
+      apply({ type: "SetEndpoints", near_node_url: rpc, archival_rpc_url: archival });
+      return;
+    }
+
     // Plain 'c' → copy focused JSON (no modifiers).
     if (e.key === "c" || e.key === "C") {
       if (!e.ctrlKey && !e.metaKey && !e.altKey) {
@@ -261,7 +428,21 @@ function hookEvents() {
       "Tab",
       "Enter",
       " ",
-      "Escape",  // Exit fullscreen / clear filter (priority-based)
+      "Escape",  // Restore zoom / clear filter (priority-based)
+      "z",       // Zoom / restore the focused pane
+      "n",       // Toast history overlay
+      "t",       // Receipt timeline for the selected tx
+      "e",       // Expand raw error JSON in the timeline
+      "g",       // Gas price panel
+      "G",       // Receipt gas profile for the selected tx
+      "P",       // Snapshot profiler overlay
+      "T",       // Timezone: UTC → local → configured offset
+      "C",       // Next color theme
+      "-",       // Compact mode
+      "r",       // Retry block sources (Blocks pane source error)
+      "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", // Gas profile drill-down / Details jump prompt
+      ":", "%", "Backspace", // Details jump prompt (`:120` Enter, `50%`)
+      "{", "}",  // Previous / next top-level key in Details
       "j",
       "k",
       "h",
@@ -303,6 +484,22 @@ function hookEvents() {
     apply({ type: "SelectTx", index });
   });
 
+  // Footer segment clicks send the segment's key.
+  const footerEl = document.getElementById("nearx-footer");
+  footerEl?.addEventListener("click", (e) => {
+    const seg = e.target.closest("[data-action]");
+    if (!seg) return;
+    const action = JSON.parse(seg.dataset.action);
+    apply({
+      type: "Key",
+      code: action.code,
+      ctrl: !!action.ctrl,
+      alt: false,
+      shift: !!action.shift,
+      meta: false,
+    });
+  });
+
   // Help modal close button (use UiAction instead of DOM manipulation)
   const modalCloseBtn = document.querySelector(".nx-modal-close");
   if (modalCloseBtn) {
@@ -323,6 +520,11 @@ function hookEvents() {
 // Store previous snapshot for scroll preservation
 let prevSnapshot = null;
 
+// Compact mode hash: 8 chars with a middle ellipsis (matches util_text::short_hash)
+function shortHash(hash) {
+  return hash.length <= 8 ? hash : `${hash.slice(0, 4)}…${hash.slice(-3)}`;
+}
+
 function render(snapshot) {
   const filter = document.getElementById("nearx-filter");
 
@@ -354,6 +556,14 @@ function render(snapshot) {
     return;
   }
 
+  // Compact mode: tighter rows via CSS; rows are built once, so rebuild them
+  // with the other format when the mode flips
+  if (prevSnapshot && !!prevSnapshot.compact !== !!snapshot.compact) {
+    blocksBody.replaceChildren();
+    txBody.replaceChildren();
+  }
+  document.body.classList.toggle("nx-compact", !!snapshot.compact);
+
   // Store scroll positions before re-render
   const scrollPositions = {
     blocks: blocksBody.scrollTop,
@@ -397,6 +607,14 @@ function render(snapshot) {
     if (height) existingRows.set(height, row);
   });
 
+  // Guided empty state (why there are no rows and what to do), with skeleton
+  // rows while the first live block is on its way
+  const phase = snapshot.source_phase || "live";
+  const loading = phase === "connecting" || phase === "first_sync";
+  renderEmptyState(blocksBody, blocks.length ? null : snapshot.blocks_empty);
+  renderSkeleton(blocksBody, loading && !blocks.length ? SKELETON_ROWS.blocks : 0);
+  renderStaleWatermark(blocksBody.parentElement, snapshot.blocks_stale);
+
   // Process blocks in order
   blocks.forEach((b, index) => {
     const heightStr = String(b.height);
@@ -425,7 +643,9 @@ function render(snapshot) {
       if (b.source === "backfill_pending" || b.source === "backfill_loading") {
         row.textContent = `#${b.height} · ${b.source === "backfill_loading" ? "archival lookup in flight…" : "archival lookup queued…"}`;
       } else {
-        row.textContent = `#${b.height} · ${b.tx_count} tx · ${b.when}`;
+        row.textContent = snapshot.compact
+          ? `#${b.height} ${b.tx_count}tx`
+          : `#${b.height} · ${b.tx_count} tx · ${b.when}`;
       }
 
       // Insert at correct position
@@ -455,7 +675,7 @@ function render(snapshot) {
 
   // Apply vertical centering via scroll offset (like TUI)
   if (snapshot.blocks_scroll_offset != null && snapshot.blocks_scroll_offset > 0) {
-    const rowHeight = 24;  // Approximate based on CSS line-height
+    const rowHeight = snapshot.compact ? 17 : 24;  // Approximate based on CSS line-height
     blocksBody.scrollTop = snapshot.blocks_scroll_offset * rowHeight;
   }
 
@@ -470,6 +690,7 @@ function render(snapshot) {
       title = "Blocks — (↑↓ nav • Enter select)";
     }
     blocksTitle.textContent = title;
+    renderPhaseIndicator(blocksTitle, phase);
   }
 
   // Txs pane: Update only what changed
@@ -482,6 +703,9 @@ function render(snapshot) {
     if (hash) existingTxRows.set(hash, row);
   });
 
+  renderEmptyState(txBody, txs.length ? null : snapshot.txs_empty);
+  renderSkeleton(txBody, loading && !txs.length ? SKELETON_ROWS.txs : 0);
+
   // Process transactions in order
   txs.forEach((t, index) => {
     let row = existingTxRows.get(t.hash);
@@ -497,10 +721,11 @@ function render(snapshot) {
       // Set content ONCE - it never changes for a given tx
       const signer = t.signer_id || "";
       const receiver = t.receiver_id || "";
+      const hash = snapshot.compact ? shortHash(t.hash) : t.hash;
       const label = signer && receiver
         ? `${signer} → ${receiver}`
-        : signer || receiver || t.hash;
-      row.textContent = label;
+        : signer || receiver || hash;
+      row.textContent = t.bridge ? `${label} ⇄ ${t.bridge}` : label;
 
       // Insert at correct position
       if (index < txBody.children.length) {
@@ -539,6 +764,7 @@ function render(snapshot) {
       title = "Txs — (↑↓ nav • Enter select)";
     }
     txTitle.textContent = title;
+    renderPhaseIndicator(txTitle, phase);
   }
 
   // Details pane: Only update if content actually changed
@@ -546,14 +772,7 @@ function render(snapshot) {
   const detailsChanged = detailsPre.dataset.lastDetails !== rawDetails;
 
   if (detailsChanged) {
-    let html = syntaxHighlightJson(rawDetails);
-
-    // Add truncation message if content was cut off
-    if (snapshot.details_truncated) {
-      html += '<br><br><span style="color: var(--fg-dim); font-style: italic;">… large output truncated at 5000 lines; press \'c\' to copy full JSON</span>';
-    }
-
-    detailsPre.innerHTML = html;
+    detailsPre.innerHTML = highlightJsonCached(rawDetails, !!snapshot.details_truncated, snapshot.details_max_lines);
     detailsPre.dataset.lastDetails = rawDetails;
     detailsPre.scrollTop = 0; // Reset scroll when content changes
   }
@@ -562,6 +781,8 @@ function render(snapshot) {
     "nx-details--fullscreen",
     !!snapshot.details_fullscreen,
   );
+  blocksPane.classList.toggle("nx-pane--zoomed", snapshot.zoomed_pane === "Blocks");
+  txPane.classList.toggle("nx-pane--zoomed", snapshot.zoomed_pane === "Txs");
 
   // Update title with mode indicator, content type, and scroll indicator
   if (snapshot.details_fullscreen) {
@@ -585,22 +806,28 @@ function render(snapshot) {
       : "";
     detailsTitle.textContent = `Transaction details${scrollIndicator} – c: copy • Space: expand`;
   }
+  // Jump prompt replaces the hints while typing (same as TUI)
+  if (snapshot.details_goto != null) {
+    detailsTitle.textContent = `Transaction details – go to :${snapshot.details_goto}▏ (Enter line • % percent • Esc cancel)`;
+  }
 
-  // Content is already updated above only when changed
+  renderPhaseIndicator(detailsTitle, phase);
+  renderSkeleton(detailsPre.parentElement, loading && !rawDetails ? SKELETON_ROWS.details : 0);
 
-  // Footer.
-  const parts = [];
-  parts.push(`Blocks ${snapshot.blocks_total ?? 0}`);
-  parts.push(`Txs ${snapshot.txs_total ?? 0}`);
-  if (snapshot.selected_block_height != null)
-    parts.push(`Block #${snapshot.selected_block_height}`);
+  // Content is already updated above only when changed
 
-  footer.textContent = parts.join("  •  ");
+  // Footer: status bar segments, lowest priority hidden first on overflow.
+  renderStatusBar(footer, snapshot.status ?? []);
 
   // Toast - only update if no client toast is active
   if (toastEl && !clientToastActive) {
-    if (snapshot.toast) {
-      toastEl.textContent = snapshot.toast;
+    const toasts = snapshot.toasts || [];
+    if (toasts.length > 0) {
+      // Stack (oldest first); border color follows the newest toast's level
+      const lines = toasts.map((t) => `${TOAST_ICONS[t.level] || ""} ${t.message}`);
+      if (snapshot.toasts_waiting > 0) lines.push(`+${snapshot.toasts_waiting} more`);
+      toastEl.textContent = lines.join("\n");
+      toastEl.dataset.level = toasts[toasts.length - 1].level;
       toastEl.hidden = false;
     } else {
       toastEl.hidden = true;
@@ -608,6 +835,57 @@ function render(snapshot) {
     }
   }
 
+  // Toast history overlay (n key)
+  const historyModal = document.getElementById("nearx-toast-history");
+  if (historyModal) {
+    historyModal.classList.toggle("hidden", !snapshot.toast_history_visible);
+    if (snapshot.toast_history_visible) {
+      const list = document.getElementById("nearx-toast-history-list");
+      const history = snapshot.toast_history || [];
+      list.textContent = history.length
+        ? history
+            .map((t) => `${new Date(t.created_ms).toLocaleTimeString()}  ${TOAST_ICONS[t.level] || ""} ${t.message}`)
+            .join("\n")
+        : "No notifications yet";
+    }
+  }
+
+  // Gas price panel (g key)
+  const gasEl = document.getElementById("nearx-gas");
+  if (gasEl) {
+    gasEl.hidden = !snapshot.gas_panel_visible;
+    if (snapshot.gas_panel_visible) {
+      const g = snapshot.gas;
+      gasEl.textContent = g
+        ? [
+            `now  ${g.current}`,
+            `p50  ${g.p50}`,
+            `p90  ${g.p90}`,
+            `max  ${g.max}`,
+            `#${g.height} · last ${g.samples} of ${g.window} blocks`,
+          ].join("\n")
+        : "Waiting for blocks with gas price…";
+    }
+  }
+
+  // Snapshot profiler (P key)
+  const profEl = document.getElementById("nearx-profiler");
+  if (profEl) {
+    const p = snapshot.profiler;
+    profEl.hidden = !p;
+    if (p) {
+      const us = (v) => `${Math.round(v)}µs`;
+      const pct = (v) => `${Math.round(v)}%`;
+      profEl.textContent = [
+        `snapshot ${us(p.last_us)} (avg ${us(p.avg_us)})`,
+        `saved    ${us(p.saved_us)} · ${pct(p.saved_pct)}`,
+        `blocks   ${pct(p.blocks_reuse_pct)} reused · rebuild ${us(p.blocks_rebuild_us)} · ${p.blocks_deferred} deferred`,
+        `txs      ${pct(p.txs_reuse_pct)} reused · rebuild ${us(p.txs_rebuild_us)}`,
+        `${p.builds} builds`,
+      ].join("\n");
+    }
+  }
+
   // Keyboard shortcuts modal visibility (driven by snapshot state).
   const modal = document.getElementById("nearx-help-modal");
   if (modal) {
@@ -635,17 +913,151 @@ function render(snapshot) {
  * Handle copy action with on-demand content fetching.
  * Gets content from WASM only when needed (not on every frame).
  */
+// Placeholder rows per pane while `source_phase` is connecting / first_sync
+const SKELETON_ROWS = { blocks: 8, txs: 5, details: 6 };
+
+// Pane title suffix for the live feed phase (nothing once live)
+const PHASE_LABELS = {
+  connecting: "connecting…",
+  first_sync: "first sync…",
+  stalled: "stalled",
+};
+
+// Skeleton placeholder rows at the end of a pane body (0 removes them)
+function renderSkeleton(body, count) {
+  if (!body) return;
+  const existing = body.querySelector(".nx-skeleton");
+  if (!count) {
+    if (existing) existing.remove();
+    return;
+  }
+  if (existing && existing.childElementCount === count) return;
+  const el = existing || document.createElement("div");
+  el.className = "nx-skeleton";
+  el.setAttribute("aria-hidden", "true");
+  el.replaceChildren(...Array.from({ length: count }, (_, i) => {
+    const row = document.createElement("div");
+    row.className = "nx-skeleton__row";
+    // Vary widths so the placeholder reads as a list, not a block
+    row.style.width = `${60 + ((i * 37) % 35)}%`;
+    return row;
+  }));
+  if (!existing) body.appendChild(el);
+}
+
+// Dimmed diagonal "STALE — last block 94s ago" over a pane (null removes it)
+function renderStaleWatermark(pane, text) {
+  if (!pane) return;
+  let el = pane.querySelector(".nx-stale-watermark");
+  if (!text) {
+    if (el) el.remove();
+    return;
+  }
+  if (!el) {
+    el = document.createElement("div");
+    el.className = "nx-stale-watermark";
+    el.setAttribute("role", "status");
+    el.appendChild(document.createElement("span"));
+    pane.appendChild(el);
+  }
+  const label = el.firstChild;
+  if (label.textContent !== text) label.textContent = text;
+}
+
+// Per-pane loading indicator appended to a title (titles are rewritten each frame)
+function renderPhaseIndicator(title, phase) {
+  if (!title) return;
+  const label = PHASE_LABELS[phase];
+  let el = title.querySelector(".nx-pane-phase");
+  if (!label) {
+    if (el) el.remove();
+    return;
+  }
+  if (!el) {
+    el = document.createElement("span");
+    el.className = "nx-pane-phase";
+    el.setAttribute("role", "status");
+    title.appendChild(el);
+  }
+  el.dataset.phase = phase;
+  el.textContent = ` · ${label}`;
+}
+
+// Rebuild the footer only when the segments (or its width) change, then hide the
+// lowest-priority ones (later first on ties) until the bar fits.
+// Empty pane: headline, source error and hint from the snapshot's
+// `blocks_empty` / `txs_empty` (null removes it)
+function renderEmptyState(body, state) {
+  const existing = body.querySelector(".nx-empty");
+  if (!state) {
+    if (existing) existing.remove();
+    return;
+  }
+  const el = existing || document.createElement("div");
+  el.className = "nx-empty";
+  el.dataset.reason = state.reason.kind;
+  const lines = [["nx-empty__title", state.title]];
+  if (state.detail) lines.push(["nx-empty__detail", state.detail]);
+  if (state.hint) lines.push(["nx-empty__hint", state.hint]);
+  el.replaceChildren(...lines.map(([cls, text]) => {
+    const line = document.createElement("div");
+    line.className = cls;
+    line.textContent = text;
+    return line;
+  }));
+  if (!existing) body.appendChild(el);
+}
+
+function renderStatusBar(footer, segments) {
+  const key = `${footer.clientWidth}|${JSON.stringify(segments)}`;
+  if (footer.dataset.lastStatus === key) return;
+  footer.dataset.lastStatus = key;
+
+  const nodes = segments.map((seg, i) => {
+    const el = document.createElement("span");
+    el.className = `nx-seg nx-seg--${seg.tone}`;
+    if (i > 0) el.classList.add("nx-seg--sep");
+    if (seg.key) {
+      const k = document.createElement("span");
+      k.className = "nx-seg-key";
+      k.textContent = `${seg.key} `;
+      el.append(k);
+    }
+    el.append(seg.text);
+    if (seg.tooltip) el.title = seg.tooltip;
+    if (seg.action) el.dataset.action = JSON.stringify(seg.action);
+    return el;
+  });
+  footer.replaceChildren(...nodes);
+
+  const byPriority = segments
+    .map((seg, i) => ({ priority: seg.priority, i }))
+    .sort((a, b) => a.priority - b.priority || b.i - a.i);
+  for (const { i } of byPriority) {
+    if (footer.scrollWidth <= footer.clientWidth) break;
+    nodes[i].hidden = true;
+  }
+  const first = nodes.find((n) => !n.hidden);
+  first?.classList.remove("nx-seg--sep");
+}
+
 async function handleCopyClientSide(snapshot) {
   const paneNames = ["block", "transaction", "details"];
   const paneName = paneNames[snapshot.pane] || "data";
 
+  // Mirror: only the visible details window is sent, so that is what copies
+  if (MIRROR_URL && snapshot.pane !== 2) {
+    showToastClientSide("Mirror: only the Details view can be copied");
+    return;
+  }
+
   // Get content on-demand from WASM (only when user presses 'c')
-  if (!wasmApp || !wasmApp.getClipboardContent) {
+  if (!MIRROR_URL && (!wasmApp || !wasmApp.getClipboardContent)) {
     showToastClientSide("Copy not available");
     return;
   }
 
-  const content = wasmApp.getClipboardContent();
+  const content = MIRROR_URL ? snapshot.details : wasmApp.getClipboardContent();
 
   // Handle empty content
   if (!content || content.startsWith("No ") || content === "") {
@@ -679,6 +1091,7 @@ function showToastClientSide(message) {
 
   // Add checkmark prefix like TUI
   toastEl.textContent = `✓ ${message}`;
+  toastEl.dataset.level = "Success";
   toastEl.hidden = false;
   clientToastActive = true;  // Mark client toast as active
 